@@ -9,7 +9,7 @@ use core::{
     fmt::{Display, Formatter},
 };
 use math::StarkField;
-use utils::{collections::Vec, string::ToString};
+use utils::collections::Vec;
 
 #[cfg(test)]
 mod tests;
@@ -19,6 +19,7 @@ mod tests;
 
 const MIN_STRIDE_LENGTH: usize = 2;
 const NO_STRIDE: usize = 0;
+const RANGE_STRIDE: usize = 1;
 
 // ASSERTION
 // ================================================================================================
@@ -26,25 +27,31 @@ const NO_STRIDE: usize = 0;
 /// An assertion made against an execution trace.
 ///
 /// An assertion is always placed against a single register of an execution trace, but can cover
-/// multiple steps and multiple values. Specifically, there are three kinds of assertions:
+/// multiple steps and multiple values. Specifically, there are four kinds of assertions:
 ///
 /// 1. **Single** assertion - which requires that a value in a single cell of an execution trace
 ///    is equal to the specified value.
 /// 2. **Periodic** assertion - which requires that values in multiple cells of a single register
-///   are equal to the specified value. The cells must be evenly spaced at intervals with lengths
-///   equal to powers of two. For example, we can specify that values in a register must be equal
-///   to 0 at steps 0, 8, 16, 24, 32 etc. Steps can also start at some offset - e.g., 1, 9, 17,
-///   25, 33 is also a valid sequence of steps.
+///    are equal to the specified value. The cells must be evenly spaced at intervals with lengths
+///    equal to powers of two. For example, we can specify that values in a register must be equal
+///    to 0 at steps 0, 8, 16, 24, 32 etc. Steps can also start at some offset - e.g., 1, 9, 17,
+///    25, 33 is also a valid sequence of steps.
 /// 3. **Sequence** assertion - which requires that multiple cells in a single register are equal
-///   to the values from the provided list. The cells must be evenly spaced at intervals with
-///   lengths equal to powers of two. For example, we can specify that values in a register must
-///   be equal to a sequence 1, 2, 3, 4 at steps 0, 8, 16, 24. That is, value at step 0 should be
-///   equal to 1, value at step 8 should be equal to 2 etc.
+///    to the values from the provided list. The cells must be evenly spaced at intervals with
+///    lengths equal to powers of two. For example, we can specify that values in a register must
+///    be equal to a sequence 1, 2, 3, 4 at steps 0, 8, 16, 24. That is, value at step 0 should be
+///    equal to 1, value at step 8 should be equal to 2 etc.
+/// 4. **Range** assertion - which requires that cells in a contiguous range of steps of a single
+///    register are equal to the values from the provided list. For example, we can specify that
+///    values in a register must be equal to a sequence 1, 2, 3 at steps 5, 6, 7. The number of
+///    steps in the range does not need to be a power of two.
 ///
 /// Note that single and periodic assertions are succinct. That is, a verifier can evaluate them
-/// very efficiently. However, sequence assertions have liner complexity in the number of
-/// asserted values. Though, unless many thousands of values are asserted, practical impact of
-/// this linear complexity should be negligible.
+/// very efficiently. However, sequence and range assertions have liner complexity in the number
+/// of asserted values. Though, unless many thousands of values are asserted, practical impact of
+/// this linear complexity should be negligible. In any case, a single range assertion is much
+/// cheaper to verify than an equivalent set of single assertions, as all values asserted by a
+/// range assertion are combined into a single boundary constraint.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Assertion<B: StarkField> {
     pub(super) register: usize,
@@ -121,6 +128,47 @@ impl<B: StarkField> Assertion<B> {
         }
     }
 
+    /// Returns a multi-value assertion against a contiguous range of steps of a single register.
+    ///
+    /// The returned assertion requires that values in the specified `register` must be equal to
+    /// the provided `values` at steps `first_step`, `first_step + 1`, ..., `last_step`. That is,
+    /// value at `first_step` should be equal to `values[0]`, value at `first_step + 1` should be
+    /// equal to `values[1]` etc.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `last_step` is smaller than `first_step`.
+    /// * The number of `values` is not equal to the number of steps in the range.
+    pub fn range(register: usize, first_step: usize, last_step: usize, values: Vec<B>) -> Self {
+        assert!(
+            first_step <= last_step,
+            "invalid assertion for register {}: last step must not be smaller than first step ({}), but was {}",
+            register,
+            first_step,
+            last_step
+        );
+        assert_eq!(
+            values.len(),
+            last_step - first_step + 1,
+            "invalid assertion for register {}: expected {} values for steps {}..={}, but received {}",
+            register,
+            last_step - first_step + 1,
+            first_step,
+            last_step,
+            values.len()
+        );
+        Assertion {
+            register,
+            first_step,
+            stride: if values.len() == 1 {
+                NO_STRIDE
+            } else {
+                RANGE_STRIDE
+            },
+            values,
+        }
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -138,7 +186,7 @@ impl<B: StarkField> Assertion<B> {
 
     /// Returns the interval at which the assertion repeats in the execution trace.
     ///
-    /// For single value assertions, this will be 0.
+    /// For single value assertions, this will be 0; for range assertions, this will be 1.
     pub fn stride(&self) -> usize {
         self.stride
     }
@@ -160,9 +208,30 @@ impl<B: StarkField> Assertion<B> {
         self.stride != NO_STRIDE && self.values.len() == 1
     }
 
-    /// Returns true if this is a sequence assertion (many values, many steps).
+    /// Returns true if this is a sequence assertion (many values, many evenly spaced steps).
     pub fn is_sequence(&self) -> bool {
-        self.values.len() > 1
+        self.values.len() > 1 && self.stride != RANGE_STRIDE
+    }
+
+    /// Returns true if this is a range assertion (many values, many consecutive steps).
+    pub fn is_range(&self) -> bool {
+        self.stride == RANGE_STRIDE
+    }
+
+    /// Returns true if this assertion is placed against the specified step of the execution
+    /// trace.
+    ///
+    /// Periodic and sequence assertions are assumed to repeat until the end of the trace.
+    fn contains_step(&self, step: usize) -> bool {
+        if step < self.first_step {
+            false
+        } else if self.is_single() {
+            step == self.first_step
+        } else if self.is_range() {
+            step < self.first_step + self.values.len()
+        } else {
+            (step - self.first_step).is_multiple_of(self.stride)
+        }
     }
 
     // PUBLIC METHODS
@@ -178,6 +247,23 @@ impl<B: StarkField> Assertion<B> {
         if self.first_step == other.first_step {
             return true;
         }
+
+        // for range assertions, check all steps covered by the range; if both assertions are
+        // ranges, it is sufficient to check that the ranges intersect
+        if self.is_range() || other.is_range() {
+            let (range, other) = if self.is_range() {
+                (self, other)
+            } else {
+                (other, self)
+            };
+            let last_step = range.first_step + range.values.len() - 1;
+            if other.is_range() {
+                return other.first_step <= last_step
+                    && range.first_step < other.first_step + other.values.len();
+            }
+            return (range.first_step..=last_step).any(|step| other.contains_step(step));
+        }
+
         if self.stride == other.stride {
             return false;
         }
@@ -190,7 +276,7 @@ impl<B: StarkField> Assertion<B> {
                 return false;
             }
             if other.is_single() || self.stride < other.stride {
-                (other.first_step - self.first_step) % self.stride == 0
+                (other.first_step - self.first_step).is_multiple_of(self.stride)
             } else {
                 false
             }
@@ -199,7 +285,7 @@ impl<B: StarkField> Assertion<B> {
                 return false;
             }
             if self.is_single() || other.stride < self.stride {
-                (self.first_step - other.first_step) % other.stride == 0
+                (self.first_step - other.first_step).is_multiple_of(other.stride)
             } else {
                 false
             }
@@ -225,6 +311,7 @@ impl<B: StarkField> Assertion<B> {
     /// * For single assertion, `first_step` >= `trace_length`.
     /// * For periodic assertion, `stride` > `trace_length`.
     /// * For sequence assertion, `num_values` * `stride` != `trace_length`;
+    /// * For range assertion, the last step of the range >= `trace_length`.
     pub fn validate_trace_length(&self, trace_length: usize) -> Result<(), AssertionError> {
         if !trace_length.is_power_of_two() {
             return Err(AssertionError::TraceLengthNotPowerOfTwo(trace_length));
@@ -243,6 +330,14 @@ impl<B: StarkField> Assertion<B> {
                     trace_length,
                 ));
             }
+        } else if self.is_range() {
+            let num_steps = self.first_step + self.values.len();
+            if num_steps > trace_length {
+                return Err(AssertionError::TraceLengthTooShort(
+                    num_steps.next_power_of_two(),
+                    trace_length,
+                ));
+            }
         } else {
             let expected_length = self.values.len() * self.stride;
             if expected_length != trace_length {
@@ -266,7 +361,7 @@ impl<B: StarkField> Assertion<B> {
     {
         self.validate_trace_length(trace_length)
             .unwrap_or_else(|err| {
-                panic!("invalid trace length: {}", err.to_string());
+                panic!("invalid trace length: {}", err);
             });
         if self.is_single() {
             f(self.first_step, self.values[0]);
@@ -286,14 +381,14 @@ impl<B: StarkField> Assertion<B> {
     ///
     /// * For single-value assertions, this will always be one.
     /// * For periodic assertions this will be equal to `trace_length` / `stride`.
-    /// * For sequence assertions this will be equal to the number of asserted values.
+    /// * For sequence and range assertions this will be equal to the number of asserted values.
     ///
     /// # Panics
     /// Panics if the specified trace length is not valid for this assertion.
     pub fn get_num_steps(&self, trace_length: usize) -> usize {
         self.validate_trace_length(trace_length)
             .unwrap_or_else(|err| {
-                panic!("invalid trace length: {}", err.to_string());
+                panic!("invalid trace length: {}", err);
             });
         if self.is_single() {
             1
@@ -335,6 +430,10 @@ impl<B: StarkField> Display for Assertion<B> {
         write!(f, "(register={}, ", self.register)?;
         match self.stride {
             0 => write!(f, "step={}, ", self.first_step)?,
+            RANGE_STRIDE => {
+                let last_step = self.first_step + self.values.len() - 1;
+                write!(f, "steps=[{}..={}], ", self.first_step, last_step)?;
+            }
             _ => {
                 let second_step = self.first_step + self.stride;
                 write!(f, "steps=[{}, {}, ...], ", self.first_step, second_step)?;
@@ -62,7 +62,7 @@ fn periodic_assertion() {
             assert_eq!(value, val);
             return;
         }
-        assert!(false);
+        panic!();
     });
 
     assert_eq!(Ok(()), a.validate_trace_width(5));
@@ -129,7 +129,7 @@ fn sequence_assertion() {
             assert_eq!(values[1], val);
             return;
         }
-        assert!(false);
+        panic!();
     });
 
     assert_eq!(Ok(()), a.validate_trace_length(8));
@@ -199,6 +199,59 @@ fn sequence_assertion_num_values_not_power_of_two() {
     );
 }
 
+// RANGE ASSERTIONS
+// ================================================================================================
+
+#[test]
+fn range_assertion() {
+    let values = rand_vector::<BaseElement>(5);
+    let a = Assertion::range(2, 3, 7, values.clone());
+    assert_eq!(2, a.register);
+    assert_eq!(3, a.first_step);
+    assert_eq!(values, a.values);
+    assert_eq!(1, a.stride);
+    assert!(a.is_range());
+    assert!(!a.is_sequence());
+    assert_eq!(5, a.get_num_steps(8));
+    assert_eq!(5, a.get_num_steps(16));
+
+    let mut i = 0;
+    a.apply(16, |step, val| {
+        assert_eq!(3 + i, step);
+        assert_eq!(values[i], val);
+        i += 1;
+    });
+    assert_eq!(5, i);
+
+    assert_eq!(Ok(()), a.validate_trace_length(8));
+    let a = Assertion::range(2, 5, 9, values);
+    assert_eq!(
+        Err(AssertionError::TraceLengthTooShort(16, 8)),
+        a.validate_trace_length(8)
+    );
+
+    // a range with a single step is a single assertion
+    let value = rand_value::<BaseElement>();
+    let a = Assertion::range(1, 4, 4, vec![value]);
+    assert_eq!(Assertion::single(1, 4, value), a);
+}
+
+#[test]
+#[should_panic(
+    expected = "invalid assertion for register 1: expected 3 values for steps 2..=4, but received 2"
+)]
+fn range_assertion_inconsistent_values() {
+    let _ = Assertion::range(1, 2, 4, rand_vector::<BaseElement>(2));
+}
+
+#[test]
+#[should_panic(
+    expected = "invalid assertion for register 1: last step must not be smaller than first step (4), but was 2"
+)]
+fn range_assertion_invalid_steps() {
+    let _ = Assertion::range(1, 4, 2, rand_vector::<BaseElement>(2));
+}
+
 // OVERLAPPING ASSERTIONS
 // ================================================================================================
 
@@ -362,4 +415,35 @@ fn assertion_overlap() {
     let b = Assertion::periodic(0, 0, 16, BaseElement::ONE);
     assert!(!a.overlaps_with(&b));
     assert!(!b.overlaps_with(&a));
+
+    // ----- range overlap ------------------------------------------------------------------------
+
+    let a = Assertion::range(3, 4, 9, rand_vector::<BaseElement>(6));
+    let b = Assertion::single(3, 9, BaseElement::ONE);
+    assert!(a.overlaps_with(&b));
+    assert!(b.overlaps_with(&a));
+
+    let b = Assertion::single(3, 10, BaseElement::ONE);
+    assert!(!a.overlaps_with(&b));
+    assert!(!b.overlaps_with(&a));
+
+    let b = Assertion::periodic(3, 1, 8, BaseElement::ONE);
+    assert!(a.overlaps_with(&b));
+    assert!(b.overlaps_with(&a));
+
+    let b = Assertion::periodic(3, 2, 8, BaseElement::ONE);
+    assert!(!a.overlaps_with(&b));
+    assert!(!b.overlaps_with(&a));
+
+    let b = Assertion::range(3, 9, 10, rand_vector::<BaseElement>(2));
+    assert!(a.overlaps_with(&b));
+    assert!(b.overlaps_with(&a));
+
+    let b = Assertion::range(3, 0, 3, rand_vector::<BaseElement>(4));
+    assert!(!a.overlaps_with(&b));
+    assert!(!b.overlaps_with(&a));
+
+    // different registers: no overlap
+    let b = Assertion::range(1, 4, 9, rand_vector::<BaseElement>(6));
+    assert!(!a.overlaps_with(&b));
 }
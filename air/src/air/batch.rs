@@ -0,0 +1,203 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    Air, AirContext, Assertion, CommittedColumn, ConstraintDivisor, EvaluationFrame, TraceInfo,
+};
+use crate::ProofOptions;
+use crypto::{PublicCoin, RandomCoinError};
+use math::FieldElement;
+use utils::collections::Vec;
+
+// BATCH AIR
+// ================================================================================================
+/// AIR for a batch of instances of the same computation proven together in a single proof.
+///
+/// Execution traces of all instances are stacked side by side, such that registers of instance
+/// `i` occupy registers `i * w..(i + 1) * w` of the combined trace, where `w` is the trace width
+/// of a single instance. Transition constraints of every instance are evaluated against its own
+/// registers, and assertions of every instance are shifted to its registers. Thus, all instances
+/// share trace and constraint commitments, FRI proof, and queries, and the cost of proving and
+/// verifying a batch grows much slower than the number of instances.
+///
+/// All instances must have traces of the same length and width, and the combined trace width
+/// cannot exceed 255 registers. Public inputs of the batch are the public inputs of all instances
+/// in the order in which the traces are stacked.
+///
+/// Besides transition constraint degrees, instances may define the steps on which their
+/// transition constraints hold by overriding [Air::transition_constraint_divisor()] (e.g., to
+/// exempt several last steps of the trace). Since transition constraints of all instances are
+/// divided by the same divisor, the batch uses the divisor of its instances, and all instances
+/// must agree on it.
+///
+/// Batch proofs are generated and verified via `prove_batch()` and `verify_batch()` functions of
+/// the prover and verifier crates.
+pub struct BatchAir<A: Air> {
+    context: AirContext<A::BaseElement>,
+    instances: Vec<A>,
+    instance_width: usize,
+    num_periodic_columns: Vec<usize>,
+    transition_divisor: ConstraintDivisor<A::BaseElement>,
+}
+
+impl<A: Air> BatchAir<A> {
+    /// Returns AIR instances of all computations in this batch.
+    pub fn instances(&self) -> &[A] {
+        &self.instances
+    }
+}
+
+impl<A: Air> Air for BatchAir<A> {
+    type BaseElement = A::BaseElement;
+    type PublicInputs = Vec<A::PublicInputs>;
+
+    /// Returns a new AIR for a batch of computations with the specified public inputs.
+    ///
+    /// `trace_info` describes the combined trace of the batch; the number of instances in the
+    /// batch is defined by the number of public inputs.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `pub_inputs` is empty.
+    /// * The width of the combined trace is not a multiple of the number of instances.
+    /// * Any of the instances was built with a trace info or proof options which differ from
+    ///   the ones it was instantiated with.
+    /// * Instances have different transition constraint divisors.
+    fn new(trace_info: TraceInfo, pub_inputs: Self::PublicInputs, options: ProofOptions) -> Self {
+        let num_instances = pub_inputs.len();
+        assert!(
+            num_instances > 0,
+            "a batch must contain at least one instance"
+        );
+        assert!(
+            trace_info.width().is_multiple_of(num_instances),
+            "trace width {} is not a multiple of the number of instances {}",
+            trace_info.width(),
+            num_instances
+        );
+
+        let instance_width = trace_info.width() / num_instances;
+        let instance_info = TraceInfo::with_meta(
+            instance_width,
+            trace_info.length(),
+            trace_info.meta().to_vec(),
+        )
+        .with_unpadded_length(trace_info.unpadded_length());
+        let instances = pub_inputs
+            .into_iter()
+            .map(|pub_inputs| A::new(instance_info.clone(), pub_inputs, options.clone()))
+            .collect::<Vec<_>>();
+
+        // the batch context is built from the parameters shared by all instances; thus, any
+        // settings of an instance which cannot be carried over to the batch must be rejected
+        let transition_divisor = instances[0].transition_constraint_divisor();
+        for (i, instance) in instances.iter().enumerate() {
+            assert!(
+                instance.trace_info() == &instance_info && instance.options() == &options,
+                "instance {} was not built with the trace info and proof options of the batch",
+                i
+            );
+            assert!(
+                instance.transition_constraint_divisor() == transition_divisor,
+                "transition constraint divisor of instance {} differs from the divisor of \
+                instance 0",
+                i
+            );
+        }
+
+        let transition_constraint_degrees = instances
+            .iter()
+            .flat_map(|instance| instance.transition_constraint_degrees().iter().cloned())
+            .collect();
+        let num_periodic_columns = instances
+            .iter()
+            .map(|instance| instance.get_periodic_column_values().len())
+            .collect();
+
+        BatchAir {
+            context: AirContext::new(trace_info, transition_constraint_degrees, options),
+            instances,
+            instance_width,
+            num_periodic_columns,
+            transition_divisor,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseElement> {
+        &self.context
+    }
+
+    fn transition_constraint_divisor(&self) -> ConstraintDivisor<Self::BaseElement> {
+        self.transition_divisor.clone()
+    }
+
+    fn evaluate_transition<E: FieldElement<BaseField = Self::BaseElement>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let mut register_offset = 0;
+        let mut periodic_offset = 0;
+        let mut result_offset = 0;
+        for (instance, &num_periodic_columns) in
+            self.instances.iter().zip(self.num_periodic_columns.iter())
+        {
+            let registers = register_offset..register_offset + self.instance_width;
+            let instance_frame = EvaluationFrame::from_rows(
+                frame.current()[registers.clone()].to_vec(),
+                frame.next()[registers].to_vec(),
+            );
+            let num_constraints = instance.num_transition_constraints();
+            instance.evaluate_transition(
+                &instance_frame,
+                &periodic_values[periodic_offset..periodic_offset + num_periodic_columns],
+                &mut result[result_offset..result_offset + num_constraints],
+            );
+
+            register_offset += self.instance_width;
+            periodic_offset += num_periodic_columns;
+            result_offset += num_constraints;
+        }
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseElement>> {
+        let mut result = Vec::new();
+        for (i, instance) in self.instances.iter().enumerate() {
+            for mut assertion in instance.get_assertions() {
+                assertion.register += i * self.instance_width;
+                result.push(assertion);
+            }
+        }
+        result
+    }
+
+    fn get_periodic_column_values(&self) -> Vec<Vec<Self::BaseElement>> {
+        self.instances
+            .iter()
+            .flat_map(|instance| instance.get_periodic_column_values())
+            .collect()
+    }
+
+    fn get_query_positions<P>(&self, public_coin: &mut P) -> Result<Vec<usize>, RandomCoinError>
+    where
+        P: PublicCoin<BaseField = Self::BaseElement>,
+    {
+        // all instances share proof options and the LDE domain with the batch, and thus, query
+        // positions are drawn in the same way as for any of the instances
+        self.instances[0].get_query_positions(public_coin)
+    }
+
+    fn get_committed_columns(&self) -> Vec<CommittedColumn> {
+        let mut result = Vec::new();
+        for (i, instance) in self.instances.iter().enumerate() {
+            for column in instance.get_committed_columns() {
+                let register = column.register() + i * self.instance_width;
+                result.push(CommittedColumn::new(register, column.commitment().to_vec()));
+            }
+        }
+        result
+    }
+}
@@ -4,7 +4,8 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{Assertion, ConstraintDivisor};
-use math::{fft, polynom, FieldElement, StarkField};
+use core::ops::Range;
+use math::{batch_inversion, fft, log2, polynom, FieldElement, StarkField};
 use utils::collections::{BTreeMap, Vec};
 
 #[cfg(test)]
@@ -79,13 +80,13 @@ impl<B: StarkField, E: FieldElement<BaseField = B>> BoundaryConstraintGroup<B, E
     pub(super) fn add(
         &mut self,
         assertion: Assertion<B>,
-        inv_g: B,
+        trace_length: usize,
         twiddle_map: &mut BTreeMap<usize, Vec<B>>,
         coefficients: (E, E),
     ) {
         self.constraints.push(BoundaryConstraint::new(
             assertion,
-            inv_g,
+            trace_length,
             twiddle_map,
             coefficients,
         ));
@@ -151,6 +152,7 @@ pub struct BoundaryConstraint<B: StarkField, E: FieldElement<BaseField = B>> {
     register: usize,
     poly: Vec<B>,
     poly_offset: (usize, B),
+    range: Option<(usize, Range<usize>)>,
     cc: (E, E),
 }
 
@@ -160,7 +162,7 @@ impl<B: StarkField, E: FieldElement<BaseField = B>> BoundaryConstraint<B, E> {
     /// Creates a new boundary constraint from the specified assertion.
     pub(super) fn new(
         assertion: Assertion<B>,
-        inv_g: B,
+        trace_length: usize,
         twiddle_map: &mut BTreeMap<usize, Vec<B>>,
         cc: (E, E),
     ) -> Self {
@@ -169,8 +171,21 @@ impl<B: StarkField, E: FieldElement<BaseField = B>> BoundaryConstraint<B, E> {
         // polynomial; but for multi-value assertions, we need to interpolate the values
         // into a polynomial using inverse FFT
         let mut poly_offset = (0, B::ONE);
+        let mut range = None;
+        let is_range = assertion.is_range();
         let mut poly = assertion.values;
-        if poly.len() > 1 {
+        if is_range {
+            // steps of range assertions are not evenly spaced over the trace domain, and thus,
+            // the values cannot be interpolated into a polynomial of degree smaller than the
+            // number of steps via FFT. Instead, the value polynomial is defined as the
+            // polynomial which evaluates to the asserted values at the asserted steps, and to
+            // zeros at all other steps of the trace domain. This polynomial is never
+            // interpolated here: the verifier evaluates it via the barycentric formula (see
+            // evaluate_at()), and the prover interpolates it via inverse FFT over the trace
+            // domain. Thus, for range constraints, `poly` contains the asserted values.
+            let first_step = assertion.first_step;
+            range = Some((trace_length, first_step..first_step + poly.len()));
+        } else if poly.len() > 1 {
             // get the twiddles from the map; if twiddles for this domain haven't been built
             // yet, build them and add them to the map
             let inv_twiddles = twiddle_map
@@ -183,6 +198,7 @@ impl<B: StarkField, E: FieldElement<BaseField = B>> BoundaryConstraint<B, E> {
                 // use FFT to interpolate the values into a polynomial. This would make such
                 // assertions quite impractical. To get around this, we still use FFT to build
                 // the polynomial, but then we evaluate it as f(x * offset) instead of f(x)
+                let inv_g = B::get_root_of_unity(log2(trace_length)).inv();
                 let x_offset = inv_g.exp((assertion.first_step as u64).into());
                 poly_offset = (assertion.first_step, x_offset);
             }
@@ -192,6 +208,7 @@ impl<B: StarkField, E: FieldElement<BaseField = B>> BoundaryConstraint<B, E> {
             register: assertion.register,
             poly,
             poly_offset,
+            range,
             cc,
         }
     }
@@ -205,10 +222,20 @@ impl<B: StarkField, E: FieldElement<BaseField = B>> BoundaryConstraint<B, E> {
     }
 
     /// Returns a value polynomial for this constraint.
+    ///
+    /// For constraints derived from range assertions, the asserted values are returned instead;
+    /// the value polynomial of such constraints evaluates to these values at the steps of
+    /// [range()](Self::range), and to zeros at all other steps of the trace domain.
     pub fn poly(&self) -> &[B] {
         &self.poly
     }
 
+    /// Returns the length of the trace and the range of asserted steps if this constraint was
+    /// derived from a range assertion, or None otherwise.
+    pub fn range(&self) -> Option<(usize, Range<usize>)> {
+        self.range.clone()
+    }
+
     /// Returns offset by which we need to shift the domain before evaluating this constraint.
     ///
     /// The offset is returned as a tuple describing both, the number of steps by which the
@@ -234,7 +261,9 @@ impl<B: StarkField, E: FieldElement<BaseField = B>> BoundaryConstraint<B, E> {
     ///
     /// For boundary constraints derived from single and periodic assertions, $b(x)$ is a constant.
     pub fn evaluate_at(&self, x: E, trace_value: E) -> E {
-        let assertion_value = if self.poly.len() == 1 {
+        let assertion_value = if let Some((trace_length, steps)) = &self.range {
+            evaluate_range_poly(&self.poly, *trace_length, steps.start, x)
+        } else if self.poly.len() == 1 {
             // if the value polynomial consists of just a constant, use that constant
             E::from(self.poly[0])
         } else {
@@ -252,3 +281,50 @@ impl<B: StarkField, E: FieldElement<BaseField = B>> BoundaryConstraint<B, E> {
         trace_value - assertion_value
     }
 }
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Evaluates the polynomial which evaluates to the specified `values` at the consecutive steps
+/// of the trace domain starting at `first_step`, and to zeros at all other steps, at `x`.
+///
+/// For `x` outside of the trace domain, the polynomial is evaluated via the barycentric formula
+/// $b(x) = \frac{x^n - 1}{n} \cdot \sum_i \frac{v_i \cdot g^i}{x - g^i}$, where $n$ is the
+/// length of the trace and $g$ is the generator of the trace domain; thus, the evaluation
+/// requires a linear number of operations in the number of values.
+fn evaluate_range_poly<B, E>(values: &[B], trace_length: usize, first_step: usize, x: E) -> E
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+{
+    let g = B::get_root_of_unity(log2(trace_length));
+    let first_x = g.exp((first_step as u64).into());
+
+    // in the trace domain, the polynomial evaluates either to one of the values or to zero
+    let vanishing_value = x.exp((trace_length as u64).into()) - E::ONE;
+    if vanishing_value == E::ZERO {
+        let mut step_x = first_x;
+        for &value in values {
+            if x == E::from(step_x) {
+                return E::from(value);
+            }
+            step_x *= g;
+        }
+        return E::ZERO;
+    }
+
+    let mut step_x = first_x;
+    let mut denominators = Vec::with_capacity(values.len());
+    for _ in 0..values.len() {
+        denominators.push(x - E::from(step_x));
+        step_x *= g;
+    }
+
+    let mut step_x = first_x;
+    let mut result = E::ZERO;
+    for (&value, inv_denominator) in values.iter().zip(batch_inversion(&denominators)) {
+        result += inv_denominator * E::from(value * step_x);
+        step_x *= g;
+    }
+    result * vanishing_value / E::from(trace_length as u64)
+}
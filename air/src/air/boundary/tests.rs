@@ -8,7 +8,7 @@ use super::{
     Assertion, BoundaryConstraint,
 };
 use crypto::{hashers::Blake3_256, RandomCoin};
-use math::{fields::f128::BaseElement, log2, polynom, FieldElement, StarkField};
+use math::{fft, fields::f128::BaseElement, log2, polynom, FieldElement, StarkField};
 use rand_utils::{rand_value, rand_vector};
 use utils::collections::{BTreeMap, Vec};
 
@@ -18,14 +18,14 @@ use utils::collections::{BTreeMap, Vec};
 #[test]
 fn boundary_constraint_from_single_assertion() {
     let mut test_prng = build_prng();
-    let (inv_g, mut twiddle_map, mut prng) = build_constraint_params(16);
+    let (_, mut twiddle_map, mut prng) = build_constraint_params(16);
 
     // constraint should be built correctly for register 0, step 0
     let value = rand_value::<BaseElement>();
     let assertion = Assertion::single(0, 0, value);
     let constraint = BoundaryConstraint::<BaseElement, BaseElement>::new(
         assertion,
-        inv_g,
+        16,
         &mut twiddle_map,
         prng.draw_pair().unwrap(),
     );
@@ -46,7 +46,7 @@ fn boundary_constraint_from_single_assertion() {
     let assertion = Assertion::single(1, 8, value);
     let constraint = BoundaryConstraint::<BaseElement, BaseElement>::new(
         assertion,
-        inv_g,
+        16,
         &mut twiddle_map,
         prng.draw_pair().unwrap(),
     );
@@ -69,14 +69,14 @@ fn boundary_constraint_from_single_assertion() {
 #[test]
 fn boundary_constraint_from_periodic_assertion() {
     let mut test_prng = build_prng();
-    let (inv_g, mut twiddle_map, mut prng) = build_constraint_params(16);
+    let (_, mut twiddle_map, mut prng) = build_constraint_params(16);
 
     // constraint should be built correctly for register 0, step 0, stride 4
     let value = rand_value::<BaseElement>();
     let assertion = Assertion::periodic(0, 0, 4, value);
     let constraint = BoundaryConstraint::<BaseElement, BaseElement>::new(
         assertion,
-        inv_g,
+        16,
         &mut twiddle_map,
         prng.draw_pair().unwrap(),
     );
@@ -97,7 +97,7 @@ fn boundary_constraint_from_periodic_assertion() {
     let assertion = Assertion::periodic(2, 3, 8, value);
     let constraint = BoundaryConstraint::<BaseElement, BaseElement>::new(
         assertion,
-        inv_g,
+        16,
         &mut twiddle_map,
         prng.draw_pair().unwrap(),
     );
@@ -128,7 +128,7 @@ fn boundary_constraint_from_sequence_assertion() {
     let assertion = Assertion::sequence(0, 0, 4, values);
     let constraint = BoundaryConstraint::<BaseElement, BaseElement>::new(
         assertion,
-        inv_g,
+        16,
         &mut twiddle_map,
         prng.draw_pair().unwrap(),
     );
@@ -153,7 +153,7 @@ fn boundary_constraint_from_sequence_assertion() {
     let assertion = Assertion::sequence(0, 3, 8, values.clone());
     let constraint = BoundaryConstraint::<BaseElement, BaseElement>::new(
         assertion,
-        inv_g,
+        16,
         &mut twiddle_map,
         prng.draw_pair().unwrap(),
     );
@@ -173,8 +173,55 @@ fn boundary_constraint_from_sequence_assertion() {
     );
 }
 
+#[test]
+fn boundary_constraint_from_range_assertion() {
+    let trace_length = 16;
+    let (inv_g, mut twiddle_map, mut prng) = build_constraint_params(trace_length);
+    let g = inv_g.inv();
+
+    // constraint should be built correctly for register 2, steps 5..=10
+    let values = rand_vector::<BaseElement>(6);
+    let assertion = Assertion::range(2, 5, 10, values.clone());
+    let constraint = BoundaryConstraint::<BaseElement, BaseElement>::new(
+        assertion,
+        trace_length,
+        &mut twiddle_map,
+        prng.draw_pair().unwrap(),
+    );
+    assert_eq!(2, constraint.register());
+    assert_eq!(values, constraint.poly());
+    assert_eq!((0, BaseElement::ONE), constraint.poly_offset());
+    assert_eq!(Some((trace_length, 5..11)), constraint.range());
+    assert!(twiddle_map.is_empty());
+
+    // in the trace domain, the constraint should evaluate to trace_value - asserted value at
+    // asserted steps, and to trace_value at all other steps
+    let trace_value = rand_value::<BaseElement>();
+    for step in 0..trace_length {
+        let x = g.exp((step as u64).into());
+        let expected = match step.checked_sub(5).and_then(|i| values.get(i)) {
+            Some(&value) => trace_value - value,
+            None => trace_value,
+        };
+        assert_eq!(expected, constraint.evaluate_at(x, trace_value));
+    }
+
+    // outside of the trace domain, the constraint should evaluate to
+    // trace_value - constraint_poly(x), where constraint_poly is interpolated over the entire
+    // trace domain
+    let mut constraint_poly = BaseElement::zeroed_vector(trace_length);
+    constraint_poly[5..11].copy_from_slice(&values);
+    fft::interpolate_poly(&mut constraint_poly, &fft::get_inv_twiddles(trace_length));
+    let x = rand_value::<BaseElement>();
+    assert_eq!(
+        trace_value - polynom::eval(&constraint_poly, x),
+        constraint.evaluate_at(x, trace_value)
+    );
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
+#[allow(clippy::type_complexity)]
 fn build_constraint_params(
     trace_length: usize,
 ) -> (
@@ -73,6 +73,15 @@ pub struct ConstraintCompositionCoefficients<E: FieldElement> {
 /// C(x) = Y(x) \cdot (\lambda + \mu \cdot x)
 /// $$
 /// where $\lambda$ and $\mu$ are the composition coefficients for degree adjustment.
+///
+/// When registers of the execution trace are tied to [committed columns](crate::CommittedColumn),
+/// the following term is also added to $Y(x)$ for every committed column:
+/// $$
+/// \epsilon \cdot \frac{T_r(x) - C(x)}{x^n - 1}
+/// $$
+/// where $r$ is the register tied to the column, $C(x)$ is an evaluation of the column
+/// polynomial at $x$, and $\epsilon$ is the composition coefficient for the column. This term
+/// is zero when $T_r(x) = C(x)$, and is not a polynomial otherwise.
 #[derive(Debug, Clone)]
 pub struct DeepCompositionCoefficients<E: FieldElement> {
     /// Trace polynomial composition coefficients $\alpha_i$, $\beta_i$, and $\gamma_i$.
@@ -81,4 +90,6 @@ pub struct DeepCompositionCoefficients<E: FieldElement> {
     pub constraints: Vec<E>,
     /// Degree adjustment composition coefficients $\lambda$ and $\mu$.
     pub degree: (E, E),
+    /// Committed column composition coefficients $\epsilon$.
+    pub columns: Vec<E>,
 }
@@ -0,0 +1,206 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{HashFunction, ProofOptions, TraceInfo};
+use crypto::{
+    hashers::{Blake3_192, Blake3_256, Sha3_256},
+    ElementHasher, Hasher, MerkleTree,
+};
+use math::{fft, StarkField};
+use utils::{
+    collections::Vec, ByteReader, Deserializable, DeserializationError, Serializable, SliceReader,
+};
+
+// COMMITTED COLUMN
+// ================================================================================================
+/// An assertion that all values in a register of an execution trace are equal to the values of
+/// a public column committed to by a Merkle root.
+///
+/// Committed columns make it possible to tie an execution trace to large amounts of public data
+/// (e.g., a batch of transactions) without requiring the verifier to know, or to interpolate,
+/// this data. Instead, the verifier needs to know only a commitment to the data, which can be
+/// built via [build_commitment()](CommittedColumn::build_commitment) function.
+///
+/// A commitment is the root of a Merkle tree built over evaluations of the column polynomial
+/// over the low-degree extension domain, such that the hash of every evaluation becomes a leaf
+/// in the tree. At every position queried by the verifier, the prover opens the committed
+/// column against this root, and the verifier ties the opened values to the register by adding
+/// a term $(T(x) - C(x)) / (x^n - 1)$ into the DEEP composition polynomial, where $T(x)$ is the
+/// polynomial of the register, $C(x)$ is the column polynomial, and $n$ is the length of the
+/// trace. For an honest prover $T(x) = C(x)$, and thus, this term is zero. Otherwise, the term
+/// is not a polynomial, and the low-degree proof fails with the probability bounded by the
+/// soundness error of the proof. Thus, a committed column adds only one Merkle authentication
+/// path per query to the proof, and a constant amount of work per query to the verifier.
+///
+/// Since the commitment is built over the low-degree extension domain, it depends on the hash
+/// function and the blowup factor of the proof, and must be built for the same proof options
+/// which are used to generate the proof.
+///
+/// A committed column fully defines values of its register. To assert that only some steps of
+/// another register are equal to the committed data, the committed register can be tied to that
+/// register via transition constraints (e.g., using [PeriodicMask](crate::PeriodicMask) as a
+/// selector).
+///
+/// The commitment should also be included into public inputs of the computation so that it is
+/// bound to the proof.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommittedColumn {
+    register: usize,
+    commitment: Vec<u8>,
+}
+
+impl CommittedColumn {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+    /// Returns an assertion that values in the specified `register` are equal to the values of
+    /// a column described by the specified `commitment`.
+    ///
+    /// # Panics
+    /// Panics if `commitment` is empty.
+    pub fn new(register: usize, commitment: Vec<u8>) -> Self {
+        assert!(!commitment.is_empty(), "column commitment cannot be empty");
+        CommittedColumn {
+            register,
+            commitment,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns index of the register against which this assertion is placed.
+    pub fn register(&self) -> usize {
+        self.register
+    }
+
+    /// Returns the commitment to the column values as a sequence of bytes.
+    pub fn commitment(&self) -> &[u8] {
+        &self.commitment
+    }
+
+    /// Returns the commitment to the column values parsed into a digest of the specified hash
+    /// function.
+    ///
+    /// # Errors
+    /// Returns an error if the commitment is not a valid digest of the hash function `H`.
+    pub fn parse_commitment<H: Hasher>(&self) -> Result<H::Digest, DeserializationError> {
+        let mut reader = SliceReader::new(&self.commitment);
+        let digest = H::Digest::read_from(&mut reader)?;
+        if reader.has_more_bytes() {
+            return Err(DeserializationError::UnconsumedBytes);
+        }
+        Ok(digest)
+    }
+
+    // COMMITMENT BUILDER
+    // --------------------------------------------------------------------------------------------
+    /// Returns a commitment to the specified column `values` for the hash function and the
+    /// blowup factor specified by `options`.
+    ///
+    /// The number of values must be equal to the length of the execution trace against which
+    /// the commitment will be used.
+    ///
+    /// # Panics
+    /// Panics if the number of values is smaller than 8 or is not a power of two.
+    pub fn build_commitment<B: StarkField>(values: &[B], options: &ProofOptions) -> Vec<u8> {
+        match options.hash_fn() {
+            HashFunction::Blake3_192 => build_root::<Blake3_192<B>>(values, options).to_bytes(),
+            HashFunction::Blake3_256 => build_root::<Blake3_256<B>>(values, options).to_bytes(),
+            HashFunction::Sha3_256 => build_root::<Sha3_256<B>>(values, options).to_bytes(),
+        }
+    }
+
+    /// Returns a Merkle tree built over evaluations of a column polynomial over the low-degree
+    /// extension domain; the root of this tree is the commitment to the column for the hash
+    /// function `H`.
+    pub fn build_tree<H: ElementHasher>(evaluations: &[H::BaseField]) -> MerkleTree<H> {
+        build_tree(evaluations)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Extends the column `values` into the low-degree extension domain and builds a Merkle tree
+/// out of the extended values.
+fn build_root<H: ElementHasher>(values: &[H::BaseField], options: &ProofOptions) -> H::Digest {
+    assert!(
+        values.len() >= TraceInfo::MIN_TRACE_LENGTH,
+        "number of column values must be at least {}, but was {}",
+        TraceInfo::MIN_TRACE_LENGTH,
+        values.len()
+    );
+    assert!(
+        values.len().is_power_of_two(),
+        "number of column values must be a power of two, but was {}",
+        values.len()
+    );
+
+    // extend the column the same way the prover extends execution trace registers
+    let mut poly = values.to_vec();
+    let inv_twiddles = fft::get_inv_twiddles(poly.len());
+    fft::interpolate_poly(&mut poly, &inv_twiddles);
+    let twiddles = fft::get_twiddles(poly.len());
+    let evaluations = fft::evaluate_poly_with_offset(
+        &poly,
+        &twiddles,
+        options.domain_offset(),
+        options.blowup_factor(),
+    );
+
+    *build_tree::<H>(&evaluations).root()
+}
+
+/// Builds a Merkle tree in which the leaf at index `i` is the hash of the `i`th evaluation.
+fn build_tree<H: ElementHasher>(evaluations: &[H::BaseField]) -> MerkleTree<H> {
+    let leaves = evaluations
+        .iter()
+        .map(|&value| H::hash_elements(&[value]))
+        .collect();
+    MerkleTree::new(leaves).expect("failed to construct column Merkle tree")
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::CommittedColumn;
+    use crate::{FieldExtension, HashFunction, ProofOptions};
+    use crypto::hashers::{Blake3_192, Blake3_256};
+    use math::fields::f128::BaseElement;
+    use utils::collections::Vec;
+
+    #[test]
+    fn committed_column_commitment() {
+        let values = (0..16u128).map(BaseElement::new).collect::<Vec<_>>();
+
+        let options = build_options(HashFunction::Blake3_256);
+        let column = CommittedColumn::new(1, CommittedColumn::build_commitment(&values, &options));
+        assert_eq!(1, column.register());
+        assert_eq!(32, column.commitment().len());
+        assert!(column.parse_commitment::<Blake3_256<BaseElement>>().is_ok());
+
+        // a commitment built with a different hash function cannot be parsed
+        let options = build_options(HashFunction::Blake3_192);
+        let column = CommittedColumn::new(1, CommittedColumn::build_commitment(&values, &options));
+        assert_eq!(24, column.commitment().len());
+        assert!(column.parse_commitment::<Blake3_192<BaseElement>>().is_ok());
+        assert!(column
+            .parse_commitment::<Blake3_256<BaseElement>>()
+            .is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "number of column values must be a power of two, but was 12")]
+    fn committed_column_invalid_length() {
+        let values = (0..12u128).map(BaseElement::new).collect::<Vec<_>>();
+        CommittedColumn::build_commitment(&values, &build_options(HashFunction::Blake3_256));
+    }
+
+    fn build_options(hash_fn: HashFunction) -> ProofOptions {
+        ProofOptions::new(32, 8, 0, hash_fn, FieldExtension::None, 4, 256)
+    }
+}
@@ -31,7 +31,10 @@ impl<B: StarkField> AirContext<B> {
     /// in the order defined by this list.
     ///
     /// # Panics
-    /// Panics if `transition_constraint_degrees` is an empty vector.
+    /// Panics if:
+    /// * `transition_constraint_degrees` is an empty vector.
+    /// * Blowup factor specified by `options` is too small to evaluate the highest degree
+    ///   transition constraint; the panic message identifies the offending constraint.
     pub fn new(
         trace_info: TraceInfo,
         transition_constraint_degrees: Vec<TransitionConstraintDegree>,
@@ -43,22 +46,28 @@ impl<B: StarkField> AirContext<B> {
         );
 
         // determine minimum blowup factor needed to evaluate transition constraints by taking
-        // the blowup factor of the highest degree constraint
+        // the blowup factor of the highest degree constraint; we also keep track of the index
+        // of this constraint so that it can be reported if the blowup factor is too small
         let mut ce_blowup_factor = 0;
-        for degree in transition_constraint_degrees.iter() {
+        let mut max_degree_idx = 0;
+        for (i, degree) in transition_constraint_degrees.iter().enumerate() {
             if degree.min_blowup_factor() > ce_blowup_factor {
                 ce_blowup_factor = degree.min_blowup_factor();
+                max_degree_idx = i;
             }
         }
 
+        let trace_length = trace_info.length();
         assert!(
             options.blowup_factor() >= ce_blowup_factor,
-            "blowup factor too small; expected at least {}, but was {}",
+            "blowup factor too small; transition constraint {} of degree {} requires blowup \
+            factor of at least {}, but was {}",
+            max_degree_idx,
+            transition_constraint_degrees[max_degree_idx].get_evaluation_degree(trace_length),
             ce_blowup_factor,
             options.blowup_factor()
         );
 
-        let trace_length = trace_info.length();
         let lde_domain_size = trace_length * options.blowup_factor();
 
         AirContext {
@@ -4,7 +4,10 @@
 // LICENSE file in the root directory of this source tree.
 
 use crate::air::Assertion;
-use core::fmt::{Display, Formatter};
+use core::{
+    fmt::{Display, Formatter},
+    ops::Range,
+};
 use math::{log2, FieldElement, StarkField};
 use utils::collections::Vec;
 
@@ -20,11 +23,17 @@ use utils::collections::Vec;
 /// For example divisor $(x^a - 1) \cdot (x^b - 2) / (x - 3)$ can be represented as:
 /// numerator: `[(a, 1), (b, 2)]`, exclude: `[3]`.
 ///
+/// Additionally, the numerator may include a range term which encodes an expression
+/// $(x - g^a) \cdot (x - g^{a + 1}) ... (x - g^{b - 1})$, where $g$ is the generator of the trace
+/// domain, and $[a, b)$ is a range of trace steps. Such terms are described by the length of the
+/// trace and the range of steps, and thus, their size does not depend on the number of steps.
+///
 /// A divisor cannot be instantiated directly, and instead must be created either for an
 /// [Assertion] or for a transition constraint.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ConstraintDivisor<B: StarkField> {
     pub(super) numerator: Vec<(usize, B)>,
+    pub(super) range: Option<(usize, Range<usize>)>,
     pub(super) exclude: Vec<B>,
 }
 
@@ -34,7 +43,11 @@ impl<B: StarkField> ConstraintDivisor<B> {
 
     /// Returns a new divisor instantiated from the provided parameters.
     fn new(numerator: Vec<(usize, B)>, exclude: Vec<B>) -> Self {
-        ConstraintDivisor { numerator, exclude }
+        ConstraintDivisor {
+            numerator,
+            range: None,
+            exclude,
+        }
     }
 
     /// Builds a divisor for transition constraints.
@@ -75,12 +88,22 @@ impl<B: StarkField> ConstraintDivisor<B> {
     ///   deviate from a power of two, and $k$ is the number of asserted steps. This is equivalent to
     ///   $(x - g^a) \cdot (x - g^{a + j}) \cdot (x - g^{a + 2 \cdot j}) ... (x - g^{a + (k  - 1) \cdot j})$,
     ///   where $j$ is the length of interval between asserted steps (e.g. 8).
+    /// * For an assertion against a contiguous range of steps, it is
+    ///   $(x - g^a) \cdot (x - g^{a + 1}) ... (x - g^{a + k - 1})$, where $a$ is the first step
+    ///   of the range. In this case, the divisor is described by a single range term.
     ///
     /// # Panics
     /// Panics of the specified `trace_length` is inconsistent with the specified `assertion`.
     pub fn from_assertion(assertion: &Assertion<B>, trace_length: usize) -> Self {
         let num_steps = assertion.get_num_steps(trace_length);
-        if assertion.first_step == 0 {
+        if assertion.is_range() {
+            let steps = assertion.first_step..assertion.first_step + num_steps;
+            ConstraintDivisor {
+                numerator: vec![],
+                range: Some((trace_length, steps)),
+                exclude: vec![],
+            }
+        } else if assertion.first_step == 0 {
             Self::new(vec![(num_steps, B::ONE)], vec![])
         } else {
             let trace_offset = num_steps * assertion.first_step;
@@ -97,6 +120,12 @@ impl<B: StarkField> ConstraintDivisor<B> {
         &self.numerator
     }
 
+    /// Returns the length of the trace and the range of steps described by the range term of
+    /// this constraint divisor, or None if the divisor has no range term.
+    pub fn range(&self) -> Option<(usize, Range<usize>)> {
+        self.range.clone()
+    }
+
     /// Returns exclusion points (the denominator portion) of this constraints divisor.
     pub fn exclude(&self) -> &[B] {
         &self.exclude
@@ -108,6 +137,10 @@ impl<B: StarkField> ConstraintDivisor<B> {
             .numerator
             .iter()
             .fold(0, |degree, term| degree + term.0);
+        let numerator_degree = match &self.range {
+            Some((_, steps)) => numerator_degree + steps.len(),
+            None => numerator_degree,
+        };
         let denominator_degree = self.exclude.len();
         numerator_degree - denominator_degree
     }
@@ -123,6 +156,14 @@ impl<B: StarkField> ConstraintDivisor<B> {
             let v = v - E::from(*constant);
             numerator *= v;
         }
+        if let Some((trace_length, steps)) = &self.range {
+            let g = B::get_root_of_unity(log2(*trace_length));
+            let mut step_x = get_trace_domain_value_at::<B>(*trace_length, steps.start);
+            for _ in steps.clone() {
+                numerator *= x - E::from(step_x);
+                step_x *= g;
+            }
+        }
 
         // compute the denominator value
         let mut denominator = E::ONE;
@@ -140,6 +181,9 @@ impl<B: StarkField> Display for ConstraintDivisor<B> {
         for (degree, offset) in self.numerator.iter() {
             write!(f, "(x^{} - {})", degree, offset)?;
         }
+        if let Some((_, steps)) = &self.range {
+            write!(f, "prod(x - g^i) for i in {:?}", steps)?;
+        }
         if !self.exclude.is_empty() {
             write!(f, " / ")?;
             for x in self.exclude.iter() {
@@ -238,7 +282,7 @@ mod tests {
     fn constraint_divisor_equivalence() {
         let n = 8_usize;
         let g = BaseElement::get_root_of_unity(n.trailing_zeros());
-        let k = 4 as u32;
+        let k = 4_u32;
         let j = n as u32 / k;
 
         // ----- periodic assertion divisor, no offset --------------------------------------------
@@ -302,7 +346,7 @@ mod tests {
 
         // create a divisor for assertion which repeats every 4 steps starting at step 3
         let offset = 3u32;
-        let k = 2 as u32;
+        let k = 2_u32;
         let j = n as u32 / k;
         let assertion = Assertion::periodic(0, offset as usize, j as usize, BaseElement::ONE);
         let divisor = ConstraintDivisor::from_assertion(&assertion, n);
@@ -326,4 +370,27 @@ mod tests {
             }
         }
     }
+    #[test]
+    fn constraint_divisor_from_range_assertion() {
+        let n = 16_usize;
+        let g = BaseElement::get_root_of_unity(n.trailing_zeros());
+
+        // create a divisor for assertion against steps 3, 4, 5, 6, 7
+        let assertion = Assertion::range(0, 3, 7, vec![BaseElement::ONE; 5]);
+        let divisor = ConstraintDivisor::from_assertion(&assertion, n);
+        assert_eq!(5, divisor.degree());
+        assert!(divisor.numerator().is_empty());
+        assert_eq!(Some((n, 3..8)), divisor.range());
+
+        // z(x) = (x - g^3) * (x - g^4) * (x - g^5) * (x - g^6) * (x - g^7)
+        let x = BaseElement::new(11);
+        let expected = (3..8).fold(BaseElement::ONE, |acc, i| acc * (x - g.exp(i)));
+        assert_eq!(expected, divisor.evaluate_at(x));
+
+        // the divisor should evaluate to zero only at the asserted steps
+        for i in 0..n {
+            let actual = divisor.evaluate_at(g.exp((i as u32).into()));
+            assert_eq!((3..=7).contains(&i), actual == BaseElement::ZERO);
+        }
+    }
 }
@@ -0,0 +1,54 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{Air, TraceInfo};
+use crate::ProofOptions;
+use utils::Serializable;
+
+// COMMITTED INPUTS AIR
+// ================================================================================================
+/// Describes a computation which can be verified against a commitment to its public inputs
+/// rather than against the public inputs themselves.
+///
+/// For computations with large public inputs (e.g., a batch of transactions), requiring the
+/// verifier to read all of the inputs may be prohibitively expensive. For such computations, the
+/// AIR can define a short [InputCommitment](CommittedInputsAir::InputCommitment) to the inputs,
+/// and the proof can then be generated and verified via `prove_with_input_commitment()` and
+/// `verify_with_input_commitment()` functions of the prover and verifier crates. In this mode:
+///
+/// * The commitment takes the place of the public inputs in the seed of the public coin; that
+///   is, the commitment is bound to the transcript via its serialization into bytes.
+/// * Both the prover and the verifier instantiate the AIR via
+///   [from_input_commitment()](CommittedInputsAir::from_input_commitment) function. Thus,
+///   assertions and transition constraints of the AIR may depend only on the commitment.
+///
+/// The AIR is responsible for making sure that the commitment actually binds the execution
+/// trace to the public inputs. This is usually done via boundary constraints (e.g., by asserting
+/// that the trace computes a hash of the inputs which is equal to the commitment), or via
+/// [committed columns](crate::CommittedColumn) whose commitments are a part of the input
+/// commitment.
+///
+/// Proofs generated in this mode can be verified only against the commitment; to make the proofs
+/// verifiable against the full public inputs as well, public inputs of the AIR should serialize
+/// into the bytes of their commitment.
+pub trait CommittedInputsAir: Air {
+    /// A type defining a commitment to public inputs of the computation.
+    type InputCommitment: Serializable;
+
+    /// Returns a commitment to the specified public inputs for proofs generated with the
+    /// specified `options`.
+    fn commit_to_inputs(
+        pub_inputs: &Self::PublicInputs,
+        options: &ProofOptions,
+    ) -> Self::InputCommitment;
+
+    /// Returns new instance of AIR for this computation instantiated from a commitment to its
+    /// public inputs. Other parameters have the same meaning as in [Air::new()] function.
+    fn from_input_commitment(
+        trace_info: TraceInfo,
+        commitment: Self::InputCommitment,
+        options: ProofOptions,
+    ) -> Self;
+}
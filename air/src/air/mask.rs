@@ -0,0 +1,208 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{TransitionConstraintDegree, MIN_CYCLE_LENGTH};
+use core::ops::Range;
+use math::FieldElement;
+use utils::collections::Vec;
+
+// PERIODIC MASK
+// ================================================================================================
+/// A periodic column which contains only binary values.
+///
+/// Masks are frequently used as selectors in transition constraints - e.g., to enable a set of
+/// constraints only on the last step of every 16-step cycle, or only on the first 8 steps of
+/// the execution trace. A mask can be converted into values of a periodic column via
+/// [to_column()](PeriodicMask::to_column) method, and the values can then be returned from
+/// [Air::get_periodic_column_values()](crate::Air::get_periodic_column_values).
+///
+/// The cycle length of a mask is always reduced to the shortest power-of-two period in which
+/// the mask repeats. For example, a mask of length 16 containing alternating ones and zeros is
+/// reduced to a mask with cycle length 2. This ensures that the degree of the mask polynomial
+/// is described correctly by [degree()](PeriodicMask::degree) and
+/// [cycle_length()](PeriodicMask::cycle_length) methods.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PeriodicMask {
+    values: Vec<bool>,
+}
+
+impl PeriodicMask {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+    /// Returns a mask which is set to one on steps within the specified `steps` range of every
+    /// cycle, and to zero on all other steps.
+    ///
+    /// For example, `PeriodicMask::new(8, 0..7)` describes a mask consisting of seven ones
+    /// followed by a zero. To describe a mask which is set to one on steps 0..8 of the execution
+    /// trace only, `cycle_length` should be set to the length of the trace.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `cycle_length` is smaller than 2 or is not a power of two.
+    /// * `steps` is empty or is not fully contained within a single cycle.
+    pub fn new(cycle_length: usize, steps: Range<usize>) -> Self {
+        validate_cycle_length(cycle_length);
+        assert!(!steps.is_empty(), "range of masked steps cannot be empty");
+        assert!(
+            steps.end <= cycle_length,
+            "range of masked steps must end at or before step {}, but ended at step {}",
+            cycle_length,
+            steps.end
+        );
+        let values = (0..cycle_length).map(|i| steps.contains(&i)).collect();
+        Self::from_values(values)
+    }
+
+    /// Returns a mask which is set to one on the first step of every cycle, and to zero on all
+    /// other steps.
+    ///
+    /// # Panics
+    /// Panics if `cycle_length` is smaller than 2 or is not a power of two.
+    pub fn cycle_start(cycle_length: usize) -> Self {
+        Self::new(cycle_length, 0..1)
+    }
+
+    /// Returns a mask which is set to one on the last step of every cycle, and to zero on all
+    /// other steps.
+    ///
+    /// # Panics
+    /// Panics if `cycle_length` is smaller than 2 or is not a power of two.
+    pub fn cycle_end(cycle_length: usize) -> Self {
+        Self::new(cycle_length, cycle_length - 1..cycle_length)
+    }
+
+    /// Returns a mask which is set to one on the steps at which the provided `values` are
+    /// `true`, and to zero on all other steps; the cycle length of the mask is implied by the
+    /// number of values.
+    ///
+    /// # Panics
+    /// Panics if the number of values is smaller than 2 or is not a power of two.
+    pub fn from_values(mut values: Vec<bool>) -> Self {
+        validate_cycle_length(values.len());
+
+        // reduce the mask to the shortest period in which it repeats
+        while values.len() > MIN_CYCLE_LENGTH {
+            let half = values.len() / 2;
+            if values[..half] != values[half..] {
+                break;
+            }
+            values.truncate(half);
+        }
+
+        PeriodicMask { values }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of steps after which this mask repeats.
+    ///
+    /// This is the value which should be used to describe the mask in
+    /// [TransitionConstraintDegree::with_cycles()].
+    pub fn cycle_length(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if this mask is set to one on the specified step of the execution trace.
+    pub fn is_set(&self, step: usize) -> bool {
+        self.values[step % self.values.len()]
+    }
+
+    /// Returns the degree descriptor of a constraint of the specified base degree which is
+    /// multiplied by this mask.
+    ///
+    /// For example, if a constraint which multiplies two trace registers is enabled by this mask,
+    /// the degree of the resulting constraint is `mask.degree(2)`.
+    ///
+    /// # Panics
+    /// Panics if `base_degree` is zero.
+    pub fn degree(&self, base_degree: usize) -> TransitionConstraintDegree {
+        TransitionConstraintDegree::with_cycles(base_degree, vec![self.cycle_length()])
+    }
+
+    // TRANSFORMATIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a mask which is set to one wherever this mask is set to zero, and vice versa.
+    pub fn not(&self) -> Self {
+        PeriodicMask {
+            values: self.values.iter().map(|&v| !v).collect(),
+        }
+    }
+
+    /// Returns values of this mask for a single cycle as field elements.
+    pub fn to_column<E: FieldElement>(&self) -> Vec<E> {
+        self.values
+            .iter()
+            .map(|&v| if v { E::ONE } else { E::ZERO })
+            .collect()
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn validate_cycle_length(cycle_length: usize) {
+    assert!(
+        cycle_length >= MIN_CYCLE_LENGTH,
+        "cycle length must be at least {}, but was {}",
+        MIN_CYCLE_LENGTH,
+        cycle_length
+    );
+    assert!(
+        cycle_length.is_power_of_two(),
+        "cycle length must be a power of two, but was {}",
+        cycle_length
+    );
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::PeriodicMask;
+    use crate::TransitionConstraintDegree;
+    use math::{fields::f128::BaseElement, FieldElement};
+
+    #[test]
+    fn periodic_mask_new() {
+        let mask = PeriodicMask::new(8, 0..7);
+        assert_eq!(8, mask.cycle_length());
+        let mut expected = vec![BaseElement::ONE; 8];
+        expected[7] = BaseElement::ZERO;
+        assert_eq!(expected, mask.to_column::<BaseElement>());
+
+        let mask = PeriodicMask::cycle_end(16);
+        assert!(mask.is_set(15));
+        assert!(mask.is_set(31));
+        assert!(!mask.is_set(16));
+        assert_eq!(
+            TransitionConstraintDegree::with_cycles(2, vec![16]),
+            mask.degree(2)
+        );
+
+        let mask = PeriodicMask::cycle_start(4).not();
+        assert_eq!(vec![false, true, true, true], mask.values);
+    }
+
+    #[test]
+    fn periodic_mask_reduction() {
+        // alternating ones and zeros repeat every 2 steps
+        let values = (0..16).map(|i| i % 2 == 0).collect();
+        let mask = PeriodicMask::from_values(values);
+        assert_eq!(2, mask.cycle_length());
+
+        // a mask which is all ones is reduced to the minimum cycle length
+        let mask = PeriodicMask::new(32, 0..32);
+        assert_eq!(2, mask.cycle_length());
+    }
+
+    #[test]
+    #[should_panic(expected = "range of masked steps must end at or before step 8")]
+    fn periodic_mask_invalid_range() {
+        PeriodicMask::new(8, 4..9);
+    }
+}
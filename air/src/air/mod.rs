@@ -4,7 +4,7 @@
 // LICENSE file in the root directory of this source tree.
 
 use crate::ProofOptions;
-use crypto::{Hasher, RandomCoin, RandomCoinError};
+use crypto::{PublicCoin, RandomCoinError};
 use math::{fft, FieldElement, StarkField};
 use utils::{
     collections::{BTreeMap, BTreeSet, Vec},
@@ -12,7 +12,7 @@ use utils::{
 };
 
 mod trace_info;
-pub use trace_info::TraceInfo;
+pub use trace_info::{TraceInfo, TracePadding};
 
 mod context;
 pub use context::AirContext;
@@ -32,6 +32,21 @@ pub use coefficients::{ConstraintCompositionCoefficients, DeepCompositionCoeffic
 mod divisor;
 pub use divisor::ConstraintDivisor;
 
+mod mask;
+pub use mask::PeriodicMask;
+
+mod committed;
+pub use committed::CommittedColumn;
+
+mod report;
+pub use report::ConstraintReport;
+
+mod batch;
+pub use batch::BatchAir;
+
+mod inputs;
+pub use inputs::CommittedInputsAir;
+
 #[cfg(test)]
 mod tests;
 
@@ -62,6 +77,9 @@ const MIN_CYCLE_LENGTH: usize = 2;
 ///    [assertions](#trace-assertions) for a given instance of your computation.
 /// 7. If your computation requires [periodic values](#periodic-values), you can also override
 ///    the default [Air::get_periodic_column_values()] method.
+/// 8. If your computation consumes large amounts of public data, you can also override the
+///    default [Air::get_committed_columns()] method to tie trace registers to
+///    [committed public columns](CommittedColumn).
 ///
 /// ### Transition constraints
 /// Transition constraints define algebraic relations between two consecutive steps of a
@@ -200,6 +218,16 @@ pub trait Air: Send + Sync {
         Vec::new()
     }
 
+    /// Returns a list of registers of the execution trace which must be equal to externally
+    /// committed public columns.
+    ///
+    /// The default implementation of this method returns an empty vector. For computations
+    /// which rely on [committed columns](CommittedColumn), this method should be overridden in
+    /// the specialized implementation.
+    fn get_committed_columns(&self) -> Vec<CommittedColumn> {
+        Vec::new()
+    }
+
     /// Returns polynomial for all periodic columns.
     ///
     /// These polynomials are interpolated from the values returned from the
@@ -285,10 +313,6 @@ pub trait Air: Send + Sync {
         &self,
         coefficients: &[(E, E)],
     ) -> Vec<BoundaryConstraintGroup<Self::BaseElement, E>> {
-        // compute inverse of the trace domain generator; this will be used for offset
-        // computations when creating sequence constraints
-        let inv_g = self.trace_domain_generator().inv();
-
         // cache inverse twiddles for multi-value assertions in this map so that we don't have
         // to re-build them for assertions with identical strides
         let mut twiddle_map = BTreeMap::new();
@@ -305,10 +329,12 @@ pub trait Air: Send + Sync {
         );
 
         // iterate over all assertions, which are sorted first by stride and then by first_step
-        // in ascending order
+        // in ascending order; the number of steps is included in the group key because range
+        // assertions with the same first step may cover different numbers of steps
         let mut groups = BTreeMap::new();
         for (i, assertion) in assertions.into_iter().enumerate() {
-            let key = (assertion.stride(), assertion.first_step());
+            let num_steps = assertion.get_num_steps(self.trace_length());
+            let key = (assertion.stride(), assertion.first_step(), num_steps);
             let group = groups.entry(key).or_insert_with(|| {
                 BoundaryConstraintGroup::new(
                     ConstraintDivisor::from_assertion(&assertion, self.trace_length()),
@@ -318,7 +344,12 @@ pub trait Air: Send + Sync {
             });
 
             // add a new assertion constraint to the current group (last group in the list)
-            group.add(assertion, inv_g, &mut twiddle_map, coefficients[i]);
+            group.add(
+                assertion,
+                self.trace_length(),
+                &mut twiddle_map,
+                coefficients[i],
+            );
         }
 
         // make sure groups are sorted by adjustment degree
@@ -367,6 +398,16 @@ pub trait Air: Send + Sync {
         self.trace_length() - 1
     }
 
+    /// Returns the index of the last meaningful step of the execution trace for an instance of
+    /// the computation described by this AIR.
+    ///
+    /// For traces which were not padded, this is `trace_length` - 1; for padded traces, this is
+    /// the last step before the padding rows. Assertions against the final state of the
+    /// computation should be placed at this step.
+    fn last_step(&self) -> usize {
+        self.context().trace_info.unpadded_length() - 1
+    }
+
     /// Returns the generator of the trace domain for an instance of the computation described
     /// by this AIR.
     ///
@@ -464,18 +505,67 @@ pub trait Air: Send + Sync {
         ConstraintDivisor::from_transition(self.trace_length())
     }
 
+    // CONSTRAINT REPORT
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a summary of constraints and STARK protocol parameters for an instance of the
+    /// computation described by this AIR.
+    ///
+    /// The report includes the number and degrees of transition and boundary constraints, sizes
+    /// of the constraint evaluation and low-degree extension domains, and an estimate of the size
+    /// of a proof generated with the current proof options.
+    ///
+    /// # Panics
+    /// Panics if assertions returned from [get_assertions()](Air::get_assertions) method are not
+    /// valid for this instance of the computation.
+    fn get_constraint_report(&self) -> ConstraintReport {
+        let trace_length = self.trace_length();
+        let transition_constraint_degrees = self
+            .transition_constraint_degrees()
+            .iter()
+            .map(|degree| degree.get_evaluation_degree(trace_length))
+            .collect();
+        let boundary_constraint_degrees = prepare_assertions(self.get_assertions(), self.context())
+            .iter()
+            .map(|assertion| self.trace_poly_degree() - assertion.get_num_steps(trace_length))
+            .collect();
+
+        let lde_domain_size = self.lde_domain_size();
+        ConstraintReport {
+            trace_width: self.trace_width(),
+            trace_length,
+            transition_constraint_degrees,
+            boundary_constraint_degrees,
+            ce_domain_size: self.ce_domain_size(),
+            composition_degree: self.composition_degree(),
+            lde_domain_size,
+            num_fri_layers: self
+                .options()
+                .to_fri_options()
+                .num_fri_layers(lde_domain_size),
+            estimated_proof_size: report::estimate_proof_size(
+                self.trace_info(),
+                self.options(),
+                self.ce_blowup_factor(),
+                self.get_committed_columns().len(),
+                Self::BaseElement::ELEMENT_BYTES,
+                Self::BaseElement::get_modulus_le_bytes().len(),
+            ),
+        }
+    }
+
     // LINEAR COMBINATION COEFFICIENTS
     // --------------------------------------------------------------------------------------------
 
     /// Returns coefficients needed for random linear combination during construction of constraint
     /// composition polynomial.
-    fn get_constraint_composition_coefficients<E, H>(
+    fn get_constraint_composition_coefficients<E, P>(
         &self,
-        public_coin: &mut RandomCoin<Self::BaseElement, H>,
+        public_coin: &mut P,
     ) -> Result<ConstraintCompositionCoefficients<E>, RandomCoinError>
     where
         E: FieldElement<BaseField = Self::BaseElement>,
-        H: Hasher,
+        P: PublicCoin<BaseField = Self::BaseElement>,
     {
         let mut t_coefficients = Vec::new();
         for _ in 0..self.num_transition_constraints() {
@@ -497,13 +587,13 @@ pub trait Air: Send + Sync {
 
     /// Returns coefficients needed for random linear combinations during construction of DEEP
     /// composition polynomial.
-    fn get_deep_composition_coefficients<E, H>(
+    fn get_deep_composition_coefficients<E, P>(
         &self,
-        public_coin: &mut RandomCoin<Self::BaseElement, H>,
+        public_coin: &mut P,
     ) -> Result<DeepCompositionCoefficients<E>, RandomCoinError>
     where
         E: FieldElement<BaseField = Self::BaseElement>,
-        H: Hasher,
+        P: PublicCoin<BaseField = Self::BaseElement>,
     {
         let mut t_coefficients = Vec::new();
         for _ in 0..self.trace_width() {
@@ -516,12 +606,40 @@ pub trait Air: Send + Sync {
             c_coefficients.push(public_coin.draw()?);
         }
 
+        let degree = public_coin.draw_pair()?;
+
+        let mut column_coefficients = Vec::new();
+        for _ in 0..self.get_committed_columns().len() {
+            column_coefficients.push(public_coin.draw()?);
+        }
+
         Ok(DeepCompositionCoefficients {
             trace: t_coefficients,
             constraints: c_coefficients,
-            degree: public_coin.draw_pair()?,
+            degree,
+            columns: column_coefficients,
         })
     }
+
+    // QUERY POSITIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns positions in the LDE domain at which trace and constraint commitments are opened.
+    ///
+    /// By default, positions are drawn using the query sampling strategy specified by proof
+    /// options (see [QuerySampling](crate::QuerySampling)). This method can be overridden to draw
+    /// positions using a custom [QuerySampler](crate::QuerySampler); since the prover and the
+    /// verifier instantiate the same AIR, they always draw positions in the same way. However,
+    /// security estimates of a proof are computed from its options alone; thus, a custom sampler
+    /// should be expected to draw at least as many distinct positions as the strategy specified
+    /// by the options.
+    fn get_query_positions<P>(&self, public_coin: &mut P) -> Result<Vec<usize>, RandomCoinError>
+    where
+        P: PublicCoin<BaseField = Self::BaseElement>,
+    {
+        self.options()
+            .draw_query_positions(public_coin, self.lde_domain_size())
+    }
 }
 
 // HELPER FUNCTIONS
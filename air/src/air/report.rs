@@ -0,0 +1,187 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{ProofOptions, TraceInfo};
+use core::fmt;
+use math::log2;
+use utils::{collections::Vec, Serializable};
+
+// CONSTRAINT REPORT
+// ================================================================================================
+/// A summary of constraints and STARK protocol parameters for a specific instance of a
+/// computation.
+///
+/// A report can be obtained via [Air::get_constraint_report()](crate::Air::get_constraint_report)
+/// method without executing the computation or generating a proof. This makes it suitable for
+/// validating AIR definitions and proof options at startup, as well as for capacity planning.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConstraintReport {
+    /// Width of the execution trace.
+    pub trace_width: usize,
+    /// Length of the execution trace.
+    pub trace_length: usize,
+    /// Degrees of transition constraint polynomials, in the order in which transition
+    /// constraints are defined. These degrees are derived from the transition constraint degree
+    /// descriptors and do not account for division by the transition constraint divisor.
+    pub transition_constraint_degrees: Vec<usize>,
+    /// Degrees of boundary constraint polynomials after division by their divisors, in the
+    /// order in which assertions are sorted for constraint composition.
+    pub boundary_constraint_degrees: Vec<usize>,
+    /// Size of the domain over which constraints are evaluated.
+    pub ce_domain_size: usize,
+    /// Degree to which all constraint polynomials are normalized before they are composed
+    /// together.
+    pub composition_degree: usize,
+    /// Size of the low-degree extension domain.
+    pub lde_domain_size: usize,
+    /// Number of FRI layers (not including the remainder layer) in a proof.
+    pub num_fri_layers: usize,
+    /// Estimated size of a serialized proof (in bytes).
+    ///
+    /// The estimate assumes that all queried positions are distinct, and approximates sizes of
+    /// batch Merkle authentication paths. Thus, it usually exceeds the size of an actual proof
+    /// by a few percent.
+    pub estimated_proof_size: usize,
+}
+
+impl ConstraintReport {
+    /// Returns the number of transition constraints described by this report.
+    pub fn num_transition_constraints(&self) -> usize {
+        self.transition_constraint_degrees.len()
+    }
+
+    /// Returns the number of boundary constraints described by this report.
+    pub fn num_boundary_constraints(&self) -> usize {
+        self.boundary_constraint_degrees.len()
+    }
+}
+
+impl fmt::Display for ConstraintReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "execution trace: {} registers x 2^{} steps",
+            self.trace_width,
+            log2(self.trace_length)
+        )?;
+        writeln!(
+            f,
+            "transition constraints: {}",
+            self.num_transition_constraints()
+        )?;
+        writeln!(
+            f,
+            "boundary constraints: {}",
+            self.num_boundary_constraints()
+        )?;
+        writeln!(
+            f,
+            "constraint evaluation domain: 2^{} (composition degree {})",
+            log2(self.ce_domain_size),
+            self.composition_degree
+        )?;
+        writeln!(
+            f,
+            "LDE domain: 2^{} ({} FRI layers)",
+            log2(self.lde_domain_size),
+            self.num_fri_layers
+        )?;
+        write!(
+            f,
+            "estimated proof size: {:.1} KB",
+            self.estimated_proof_size as f64 / 1024f64
+        )
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Estimates the size of a serialized STARK proof (in bytes) for a computation with the specified
+/// parameters.
+///
+/// The estimate mirrors serialization logic of [StarkProof](crate::proof::StarkProof) and its
+/// components.
+pub(super) fn estimate_proof_size(
+    trace_info: &TraceInfo,
+    options: &ProofOptions,
+    ce_blowup_factor: usize,
+    num_committed_columns: usize,
+    base_element_bytes: usize,
+    num_modulus_bytes: usize,
+) -> usize {
+    let element_bytes = base_element_bytes * options.field_extension().degree() as usize;
+    let digest_size = options.hash_fn().digest_size();
+    let num_queries = options.num_queries();
+    let trace_width = trace_info.width();
+
+    let lde_domain_size = trace_info.length() * options.blowup_factor();
+    let lde_tree_depth = log2(lde_domain_size) as usize;
+    let fri_options = options.to_fri_options();
+    let num_fri_layers = fri_options.num_fri_layers(lde_domain_size);
+
+    // version header and proof context
+    let mut result = 2;
+    result += 4 + trace_info.meta().len() + 1 + num_modulus_bytes;
+    result += options.to_bytes().len();
+
+    // commitments to the trace, constraint evaluations, and all FRI layers
+    result += 2 + (num_fri_layers + 3) * digest_size;
+
+    // trace and constraint queries
+    let path_size = estimate_batch_path_size(num_queries, lde_tree_depth) * digest_size;
+    result += 8 + num_queries * trace_width * base_element_bytes + path_size;
+    result += 8 + num_queries * ce_blowup_factor * element_bytes + path_size;
+
+    // committed column queries
+    result += 1 + num_committed_columns * (8 + num_queries * base_element_bytes + path_size);
+
+    // out-of-domain frame
+    result += 4 + (2 * trace_width + ce_blowup_factor) * element_bytes;
+
+    // FRI layers; each query opens `folding_factor` evaluations in a tree with
+    // `domain_size / folding_factor` leaves, where the folding factor, the domain size, and the
+    // number of queries may differ between layers
+    result += 1;
+    for layer_idx in 0..num_fri_layers {
+        let folding_factor = fri_options.layer_folding_factor(layer_idx);
+        let domain_size = fri_options.layer_domain_size(lde_domain_size, layer_idx);
+        let num_layer_queries =
+            fri_options.layer_num_queries(lde_domain_size, layer_idx, num_queries);
+        let tree_depth = log2(domain_size / folding_factor) as usize;
+        result += 8 + num_layer_queries * folding_factor * element_bytes;
+        result += estimate_batch_path_size(num_layer_queries, tree_depth) * digest_size;
+    }
+
+    // FRI remainder, layer proof-of-work nonces, the number of partitions, out-of-domain
+    // evaluations of STIR rounds (if any), and the number of committed FRI layers (with early
+    // termination); with early termination, the estimate assumes that no layers are skipped
+    result += 1 + fri_options.fri_remainder_len(lde_domain_size) * element_bytes;
+    result += 1;
+    let num_ood_evaluations = fri_options.num_ood_evaluations(lde_domain_size);
+    if num_ood_evaluations > 0 {
+        result += 2 + num_ood_evaluations * element_bytes;
+    }
+    if fri_options.early_termination() {
+        result += 1;
+    }
+    if options.fri_layer_grinding_factor() > 0 {
+        result += (num_fri_layers + 1) * 8;
+    }
+    result += 1;
+
+    // query seed proof-of-work nonce
+    result + 8
+}
+
+/// Returns an approximate number of digests in a batch Merkle authentication path for the
+/// specified number of random queries against a tree of the specified depth.
+///
+/// Authentication paths for different queries share nodes at the top levels of the tree; thus,
+/// only nodes below these levels need to be included for every query.
+fn estimate_batch_path_size(num_queries: usize, tree_depth: usize) -> usize {
+    let num_shared_levels = log2(num_queries.next_power_of_two()) as usize;
+    num_queries * tree_depth.saturating_sub(num_shared_levels)
+}
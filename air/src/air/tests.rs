@@ -4,8 +4,8 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{
-    Air, AirContext, Assertion, EvaluationFrame, ProofOptions, TraceInfo,
-    TransitionConstraintDegree,
+    Air, AirContext, Assertion, BatchAir, ConstraintDivisor, EvaluationFrame, ProofOptions,
+    TraceInfo, TransitionConstraintDegree,
 };
 use crate::{FieldExtension, HashFunction};
 use crypto::{hashers::Blake3_256, RandomCoin};
@@ -60,7 +60,19 @@ fn get_periodic_column_polys_num_values_not_power_of_two() {
 // TRANSITION CONSTRAINTS
 // ================================================================================================
 
-// TODO
+#[test]
+#[should_panic(
+    expected = "blowup factor too small; transition constraint 1 of degree 135 requires blowup factor of at least 16, but was 8"
+)]
+fn transition_constraint_degree_too_high() {
+    let options = build_options();
+    let t_degrees = vec![
+        TransitionConstraintDegree::new(2),
+        TransitionConstraintDegree::new(9),
+        TransitionConstraintDegree::new(3),
+    ];
+    let _ = AirContext::<BaseElement>::new(TraceInfo::new(4, 16), t_degrees, options);
+}
 
 // BOUNDARY CONSTRAINTS
 // ================================================================================================
@@ -216,6 +228,28 @@ fn get_boundary_constraints() {
     assert_eq!(expected_cc[&7], constraint.cc().clone());
 }
 
+// CONSTRAINT REPORT
+// ================================================================================================
+
+#[test]
+fn get_constraint_report() {
+    let assertions = vec![
+        Assertion::single(0, 0, BaseElement::ONE),
+        Assertion::periodic(1, 0, 8, BaseElement::ZERO),
+    ];
+    let air = MockAir::with_assertions(assertions, 128);
+    let report = air.get_constraint_report();
+
+    assert_eq!(4, report.trace_width);
+    assert_eq!(128, report.trace_length);
+    assert_eq!(vec![254], report.transition_constraint_degrees);
+    assert_eq!(vec![126, 111], report.boundary_constraint_degrees);
+    assert_eq!(256, report.ce_domain_size);
+    assert_eq!(255, report.composition_degree);
+    assert_eq!(1024, report.lde_domain_size);
+    assert_eq!(1, report.num_fri_layers);
+}
+
 // PREPARE ASSERTIONS
 // ================================================================================================
 
@@ -293,6 +327,49 @@ fn prepare_assertions_with_invalid_trace_width() {
     let _ = super::prepare_assertions(assertions.clone(), &context);
 }
 
+// BATCH AIR
+// ================================================================================================
+
+#[test]
+fn batch_air_transition_divisor() {
+    let trace_info = TraceInfo::new(8, 16);
+    let options = build_options();
+
+    let batch = BatchAir::<ExemptionsAir>::new(trace_info.clone(), vec![3, 3], options.clone());
+    let expected = ExemptionsAir::new(TraceInfo::new(4, 16), 3, options.clone());
+    assert_eq!(
+        expected.transition_constraint_divisor(),
+        batch.transition_constraint_divisor()
+    );
+    assert_ne!(
+        ConstraintDivisor::from_transition(16),
+        batch.transition_constraint_divisor()
+    );
+
+    let batch = BatchAir::<ExemptionsAir>::new(trace_info, vec![1, 1], options);
+    assert_eq!(
+        ConstraintDivisor::from_transition(16),
+        batch.transition_constraint_divisor()
+    );
+}
+
+#[test]
+#[should_panic(
+    expected = "transition constraint divisor of instance 1 differs from the divisor of instance 0"
+)]
+fn batch_air_different_transition_divisors() {
+    let _ = BatchAir::<ExemptionsAir>::new(TraceInfo::new(8, 16), vec![1, 2], build_options());
+}
+
+#[test]
+#[should_panic(
+    expected = "instance 0 was not built with the trace info and proof options of the batch"
+)]
+fn batch_air_different_options() {
+    let options = build_options().with_trace_segment_width(1);
+    let _ = BatchAir::<MockAir>::new(TraceInfo::new(8, 16), vec![(), ()], options);
+}
+
 // MOCK AIR
 // ================================================================================================
 
@@ -307,37 +384,13 @@ impl MockAir {
         column_values: Vec<Vec<BaseElement>>,
         trace_length: usize,
     ) -> Self {
-        let mut result = Self::new(
-            TraceInfo::new(4, trace_length),
-            (),
-            ProofOptions::new(
-                32,
-                8,
-                0,
-                HashFunction::Blake3_256,
-                FieldExtension::None,
-                4,
-                256,
-            ),
-        );
+        let mut result = Self::new(TraceInfo::new(4, trace_length), (), build_options());
         result.periodic_columns = column_values;
         result
     }
 
     pub fn with_assertions(assertions: Vec<Assertion<BaseElement>>, trace_length: usize) -> Self {
-        let mut result = Self::new(
-            TraceInfo::new(4, trace_length),
-            (),
-            ProofOptions::new(
-                32,
-                8,
-                0,
-                HashFunction::Blake3_256,
-                FieldExtension::None,
-                4,
-                256,
-            ),
-        );
+        let mut result = Self::new(TraceInfo::new(4, trace_length), (), build_options());
         result.assertions = assertions;
         result
     }
@@ -377,11 +430,66 @@ impl Air for MockAir {
     }
 }
 
+/// AIR which exempts the specified number of last steps of the trace from its transition
+/// constraint.
+struct ExemptionsAir {
+    context: AirContext<BaseElement>,
+    num_exemptions: u32,
+}
+
+impl Air for ExemptionsAir {
+    type BaseElement = BaseElement;
+    type PublicInputs = u32;
+
+    fn new(trace_info: TraceInfo, num_exemptions: u32, options: ProofOptions) -> Self {
+        let degrees = vec![TransitionConstraintDegree::new(2)];
+        ExemptionsAir {
+            context: AirContext::new(trace_info, degrees, options),
+            num_exemptions,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseElement> {
+        &self.context
+    }
+
+    fn transition_constraint_divisor(&self) -> ConstraintDivisor<Self::BaseElement> {
+        let n = self.trace_length();
+        let exclude = (n - self.num_exemptions as usize..n)
+            .map(|step| self.trace_domain_generator().exp((step as u32).into()))
+            .collect();
+        ConstraintDivisor {
+            numerator: vec![(n, BaseElement::ONE)],
+            range: None,
+            exclude,
+        }
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseElement>> {
+        Vec::new()
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseElement>>(
+        &self,
+        _frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        _result: &mut [E],
+    ) {
+    }
+}
+
 // UTILITY FUNCTIONS
 // ================================================================================================
 
 pub fn build_context<B: StarkField>(trace_length: usize, trace_width: usize) -> AirContext<B> {
-    let options = ProofOptions::new(
+    let options = build_options();
+    let t_degrees = vec![TransitionConstraintDegree::new(2)];
+    let trace_info = TraceInfo::new(trace_width, trace_length);
+    AirContext::new(trace_info, t_degrees, options)
+}
+
+pub fn build_options() -> ProofOptions {
+    ProofOptions::new(
         32,
         8,
         0,
@@ -389,10 +497,7 @@ pub fn build_context<B: StarkField>(trace_length: usize, trace_width: usize) ->
         FieldExtension::None,
         4,
         256,
-    );
-    let t_degrees = vec![TransitionConstraintDegree::new(2)];
-    let trace_info = TraceInfo::new(trace_width, trace_length);
-    AirContext::new(trace_info, t_degrees, options)
+    )
 }
 
 pub fn build_prng() -> RandomCoin<BaseElement, Blake3_256<BaseElement>> {
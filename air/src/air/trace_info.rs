@@ -11,10 +11,15 @@ use utils::collections::Vec;
 ///
 /// Trace info consists of trace width, length, and optional custom metadata. Metadata is just a
 /// vector of bytes and can store any values up to 64KB in size.
+///
+/// For traces which were padded to a power-of-two length (see [TracePadding]), trace info also
+/// records the length of the trace before padding. This length is included in the proof context,
+/// and thus, is bound to the proof the same way public inputs are.
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct TraceInfo {
     width: usize,
     length: usize,
+    unpadded_length: usize,
     meta: Vec<u8>,
 }
 
@@ -74,10 +79,31 @@ impl TraceInfo {
         TraceInfo {
             width,
             length,
+            unpadded_length: length,
             meta,
         }
     }
 
+    /// Returns this trace info updated to describe a trace which contained `unpadded_length`
+    /// meaningful rows before it was padded to its current length.
+    ///
+    /// # Panics
+    /// Panics if `unpadded_length` is zero or is greater than the length of the trace.
+    pub fn with_unpadded_length(mut self, unpadded_length: usize) -> Self {
+        assert!(
+            unpadded_length > 0,
+            "unpadded trace length must be greater than 0"
+        );
+        assert!(
+            unpadded_length <= self.length,
+            "unpadded trace length cannot be greater than trace length {}, but was {}",
+            self.length,
+            unpadded_length
+        );
+        self.unpadded_length = unpadded_length;
+        self
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -95,8 +121,32 @@ impl TraceInfo {
         self.length
     }
 
+    /// Returns the number of meaningful rows in the execution trace, not counting the rows which
+    /// were added as padding.
+    ///
+    /// For traces which were not padded, this is the same as trace length.
+    pub fn unpadded_length(&self) -> usize {
+        self.unpadded_length
+    }
+
     /// Returns execution trace metadata.
     pub fn meta(&self) -> &[u8] {
         &self.meta
     }
 }
+
+// TRACE PADDING
+// ================================================================================================
+/// Defines how an execution trace is padded to the next power of two.
+///
+/// Padding rows are appended after the last meaningful row of the trace, and thus, transition
+/// constraints of the computation must hold for the padding transition as well. Assertions
+/// against the last meaningful row should be placed at [Air::last_step()](crate::Air::last_step)
+/// rather than at the last step of the padded trace.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum TracePadding {
+    /// Every padding row is a copy of the last meaningful row of the trace.
+    RepeatLastRow,
+    /// Every padding row consists of all zeros.
+    Zeros,
+}
@@ -43,3 +43,6 @@ impl fmt::Display for AssertionError {
         }
     }
 }
+
+#[cfg(feature = "std")]
+impl std::error::Error for AssertionError {}
@@ -39,11 +39,15 @@ mod errors;
 pub use errors::AssertionError;
 
 mod options;
-pub use options::{FieldExtension, HashFunction, ProofOptions};
+pub use options::{FieldExtension, HashFunction, ProofOptions, QuerySampling};
+
+mod sampling;
+pub use sampling::{DistinctSampler, QuerySampler, StratifiedSampler, UniformSampler};
 
 mod air;
 pub use air::{
-    Air, AirContext, Assertion, BoundaryConstraint, BoundaryConstraintGroup,
-    ConstraintCompositionCoefficients, ConstraintDivisor, DeepCompositionCoefficients,
-    EvaluationFrame, TraceInfo, TransitionConstraintDegree, TransitionConstraintGroup,
+    Air, AirContext, Assertion, BatchAir, BoundaryConstraint, BoundaryConstraintGroup,
+    CommittedColumn, CommittedInputsAir, ConstraintCompositionCoefficients, ConstraintDivisor,
+    ConstraintReport, DeepCompositionCoefficients, EvaluationFrame, PeriodicMask, TraceInfo,
+    TracePadding, TransitionConstraintDegree, TransitionConstraintGroup,
 };
@@ -3,12 +3,58 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use fri::FriOptions;
+use crate::{
+    proof::{get_conjectured_security, get_proven_security},
+    QuerySampler,
+};
+use core::ops::Range;
+use crypto::{PublicCoin, RandomCoinError};
+use fri::{FriOptions, LowDegreeTest, RemainderFormat};
 use math::StarkField;
 use utils::{
-    string::ToString, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+    collections::Vec, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+};
+
+#[cfg(feature = "serde")]
+use utils::{
+    serde::{Deserialize, Deserializer, Serialize, Serializer},
+    serde_adapter,
 };
 
+// CONSTANTS
+// ================================================================================================
+
+/// Blowup factors which can be selected by [ProofOptions::with_security()], in order of
+/// preference.
+const SECURITY_BLOWUP_FACTORS: [usize; 5] = [8, 16, 32, 64, 128];
+
+/// Grinding factor used by [ProofOptions::with_security()].
+const SECURITY_GRINDING_FACTOR: u32 = 16;
+
+/// Log2 of the largest LDE domain for which options built by [ProofOptions::with_security()]
+/// are guaranteed to achieve the target security level.
+const SECURITY_MAX_LDE_DOMAIN_SIZE_LOG2: u32 = 24;
+
+/// FRI folding factor used by [ProofOptions::with_security()].
+const SECURITY_FRI_FOLDING_FACTOR: usize = 8;
+
+/// FRI max remainder size used by [ProofOptions::with_security()].
+const SECURITY_FRI_MAX_REMAINDER_SIZE: usize = 256;
+
+/// Bit of the serialized FRI flags which indicates that out-of-domain sampling is performed
+/// within FRI layers.
+const FRI_DEEP_SAMPLING_FLAG: u8 = 0x01;
+
+/// Bit of the serialized FRI flags which indicates that the FRI prover may terminate folding
+/// early.
+const FRI_EARLY_TERMINATION_FLAG: u8 = 0x02;
+
+/// All bits of the serialized FRI flags which are assigned a meaning; other bits must not be set.
+const FRI_FLAGS_MASK: u8 = FRI_DEEP_SAMPLING_FLAG | FRI_EARLY_TERMINATION_FLAG;
+
+/// Field extensions which can be selected automatically, in order of preference.
+const FIELD_EXTENSIONS: [FieldExtension; 2] = [FieldExtension::None, FieldExtension::Quadratic];
+
 // TYPES AND INTERFACES
 // ================================================================================================
 
@@ -58,6 +104,34 @@ pub enum FieldExtension {
     Quadratic = 2,
 }
 
+/// Defines a built-in strategy for deriving query positions from the query seed.
+///
+/// After the prover commits to all polynomials and grinds the query seed, the verifier draws
+/// `num_queries` positions in the LDE domain from the public coin, and the prover opens trace and
+/// constraint commitments at these positions. The strategy is a part of proof options, and thus,
+/// the prover and the verifier always draw positions in the same way. Every strategy is
+/// implemented by a [QuerySampler](crate::QuerySampler).
+///
+/// Positions are opened only once, and thus, a strategy which may draw the same position more
+/// than once may open fewer than `num_queries` positions; such proofs are smaller, but every
+/// repeated position adds no security to the proof. Security estimates of such proofs are based
+/// on the expected number of distinct positions.
+#[repr(u8)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum QuerySampling {
+    /// Positions are drawn via [DistinctSampler](crate::DistinctSampler). This always yields
+    /// `num_queries` distinct positions, and is the default strategy.
+    Distinct = 1,
+
+    /// Positions are drawn via [UniformSampler](crate::UniformSampler). This may yield fewer
+    /// than `num_queries` distinct positions.
+    Uniform = 2,
+
+    /// Positions are drawn via [StratifiedSampler](crate::StratifiedSampler). This always yields
+    /// `num_queries` distinct positions.
+    StratifiedByCoset = 3,
+}
+
 /// STARK protocol parameters.
 ///
 /// These parameters have a direct impact on proof soundness, proof generation time, and proof
@@ -78,20 +152,68 @@ pub enum FieldExtension {
 /// 5. Grinding factor - higher values increase proof soundness, but also may increase proof
 ///    generation time. More precisely, proof soundness is bounded by
 ///    `num_queries * log2(blowup_factor) + grinding_factor`.
+/// 6. FRI layer grinding factor - when not zero, the prover must perform a proof-of-work for
+///    every FRI layer before the folding challenge for that layer is drawn. This makes it more
+///    expensive for a dishonest prover to search for favorable folding challenges, but increases
+///    proof generation time and adds 8 bytes per FRI layer to the proof.
+/// 7. Query sampling - defines how query positions are derived from the query seed. See
+///    [QuerySampling] for the available strategies.
+/// 8. Trace segment width - when not zero, the extended execution trace is committed to in
+///    segments of at most this many columns, such that every segment is committed to via a
+///    separate Merkle tree. The trees can be built in parallel, but each segment adds a
+///    commitment and a set of authentication paths to the proof.
+/// 9. FRI folding schedule - when not empty, specifies folding factors for the first FRI layers,
+///    with the remaining layers folded by the FRI folding factor. Higher folding factors result
+///    in fewer FRI layers, but increase the number of values opened per query at each layer.
+/// 10. FRI remainder format - defines whether the last FRI layer is sent as evaluations or as
+///     coefficients of the remainder polynomial. See [RemainderFormat] for more info.
+/// 11. Low-degree test - defines whether FRI or STIR is used to prove that the DEEP composition
+///     polynomial is of low degree. STIR requires fewer queries in later rounds, and thus,
+///     usually results in smaller proofs, but requires more work from the prover and the
+///     verifier. For tiny instances and for testing, the polynomial can also be sent to the
+///     verifier in full. See [LowDegreeTest] for more info.
+/// 12. FRI early termination - when enabled, the FRI prover stops folding as soon as the degree
+///     of the polynomial at the current FRI layer is smaller than the max remainder degree. This
+///     results in fewer FRI layers when the DEEP composition polynomial turns out to be of lower
+///     degree than the worst case.
+/// 13. FRI out-of-domain sampling - when enabled, the FRI prover evaluates the polynomial at
+///     every FRI layer at a random point outside of the evaluation domain (as in DEEP-FRI), and
+///     the next layer is built from the quotient of the polynomial by this point. This improves
+///     soundness of every query at the cost of a single extension field element per FRI layer.
+///
+/// # Proof-of-work verification
+/// Proof-of-work requirements are a part of proof options, and thus are bound to every proof via
+/// the proof context. The verifier checks that the query seed nonce produces a seed with at
+/// least `grinding_factor` leading zeros, and, if FRI layer grinding is enabled, that a nonce
+/// for every FRI layer produces a seed with at least `fri_layer_grinding_factor` leading zeros.
+/// Proof options which specify grinding factors greater than 64 are rejected during proof
+/// deserialization.
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct ProofOptions {
     num_queries: u8,
     blowup_factor: u8,
     grinding_factor: u8,
+    fri_layer_grinding_factor: u8,
     hash_fn: HashFunction,
     field_extension: FieldExtension,
     fri_folding_factor: u8,
     fri_max_remainder_size: u8, // stored as power of 2
+    query_sampling: QuerySampling,
+    trace_segment_width: u8,
+    fri_folding_schedule: Vec<u8>,
+    fri_remainder_format: RemainderFormat,
+    low_degree_test: LowDegreeTest,
+    fri_early_termination: bool,
+    fri_deep_sampling: bool,
 }
 
 // PROOF OPTIONS IMPLEMENTATION
 // ================================================================================================
 impl ProofOptions {
+    /// Maximum number of leading zeros which can be required by a proof-of-work; currently set
+    /// at 64.
+    pub const MAX_GRINDING_FACTOR: u32 = FriOptions::MAX_GRINDING_FACTOR;
+
     // CONSTRUCTORS
     // --------------------------------------------------------------------------------------------
     /// Returns a new instance of [ProofOptions] struct constructed from the specified parameters.
@@ -100,8 +222,8 @@ impl ProofOptions {
     /// Panics if:
     /// * `num_queries` is zero or greater than 128.
     /// * `blowup_factor` is smaller than 4, greater than 256, or is not a power of two.
-    /// * `grinding_factor` is greater than 32.
-    /// * `fri_folding_factor` is not 4, 8, or 16.
+    /// * `grinding_factor` is greater than 64.
+    /// * `fri_folding_factor` is not 2, 4, 8, or 16.
     /// * `fri_max_remainder_size` is smaller than 32, greater than 1024, or is not a power of two.
     #[rustfmt::skip]
     pub fn new(
@@ -121,10 +243,10 @@ impl ProofOptions {
         assert!(blowup_factor >= 4, "blowup factor cannot be smaller than 4");
         assert!(blowup_factor <= 128, "blowup factor cannot be greater than 128");
 
-        assert!(grinding_factor <= 32, "grinding factor cannot be greater than 32");
+        assert!(grinding_factor <= Self::MAX_GRINDING_FACTOR, "grinding factor cannot be greater than 64");
 
         assert!(fri_folding_factor.is_power_of_two(), "FRI folding factor must be a power of 2");
-        assert!(fri_folding_factor >= 4, "FRI folding factor cannot be smaller than 4");
+        assert!(fri_folding_factor >= 2, "FRI folding factor cannot be smaller than 2");
         assert!(fri_folding_factor <= 16, "FRI folding factor cannot be greater than 16");
 
         assert!(fri_max_remainder_size.is_power_of_two(), "FRI max remainder size must be a power of 2");
@@ -135,11 +257,291 @@ impl ProofOptions {
             num_queries: num_queries as u8,
             blowup_factor: blowup_factor as u8,
             grinding_factor: grinding_factor as u8,
+            fri_layer_grinding_factor: 0,
             hash_fn,
             field_extension,
             fri_folding_factor: fri_folding_factor as u8,
             fri_max_remainder_size: fri_max_remainder_size.trailing_zeros() as u8,
+            query_sampling: QuerySampling::Distinct,
+            trace_segment_width: 0,
+            fri_folding_schedule: Vec::new(),
+            fri_remainder_format: RemainderFormat::Evaluations,
+            low_degree_test: LowDegreeTest::Fri,
+            fri_early_termination: false,
+            fri_deep_sampling: false,
+        }
+    }
+
+    /// Returns these options updated to require a proof-of-work with the specified number of
+    /// leading zeros for every FRI layer.
+    ///
+    /// Setting FRI layer grinding factor to zero (the default) disables per-layer proof-of-work.
+    ///
+    /// # Panics
+    /// Panics if `grinding_factor` is greater than 64.
+    pub fn with_fri_layer_grinding_factor(mut self, grinding_factor: u32) -> Self {
+        assert!(
+            grinding_factor <= Self::MAX_GRINDING_FACTOR,
+            "FRI layer grinding factor cannot be greater than {}",
+            Self::MAX_GRINDING_FACTOR
+        );
+        self.fri_layer_grinding_factor = grinding_factor as u8;
+        self
+    }
+
+    /// Returns these options updated to derive query positions from the query seed using the
+    /// specified strategy.
+    ///
+    /// By default, [QuerySampling::Distinct] strategy is used.
+    pub fn with_query_sampling(mut self, query_sampling: QuerySampling) -> Self {
+        self.query_sampling = query_sampling;
+        self
+    }
+
+    /// Returns these options updated to commit to the extended execution trace in segments of
+    /// at most `segment_width` columns each.
+    ///
+    /// Setting trace segment width to zero (the default) commits to the entire extended trace
+    /// via a single Merkle tree.
+    ///
+    /// # Panics
+    /// Panics if `segment_width` is greater than 255.
+    pub fn with_trace_segment_width(mut self, segment_width: usize) -> Self {
+        assert!(
+            segment_width <= u8::MAX as usize,
+            "trace segment width cannot be greater than {}",
+            u8::MAX
+        );
+        self.trace_segment_width = segment_width as u8;
+        self
+    }
+
+    /// Returns these options updated to fold FRI layers according to the specified schedule.
+    ///
+    /// The first FRI layer is folded by `schedule[0]`, the second layer by `schedule[1]` etc.;
+    /// layers beyond the end of the schedule are folded by the FRI folding factor of these
+    /// options. Thus, a schedule such as `[16, 8, 4, 4]` folds early layers aggressively and
+    /// later layers gently. See [FriOptions::with_folding_schedule()] for more info.
+    ///
+    /// Setting an empty schedule (the default) folds all layers by the FRI folding factor.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * Any of the folding factors in the `schedule` is not 2, 4, 8, or 16.
+    /// * The `schedule` contains more than 255 folding factors.
+    pub fn with_fri_folding_schedule(mut self, schedule: &[usize]) -> Self {
+        assert!(
+            schedule.len() <= u8::MAX as usize,
+            "FRI folding schedule cannot contain more than {} folding factors",
+            u8::MAX
+        );
+        for &folding_factor in schedule {
+            assert!(
+                folding_factor.is_power_of_two() && (2..=16).contains(&folding_factor),
+                "FRI folding factor {} is not supported",
+                folding_factor
+            );
         }
+        self.fri_folding_schedule = schedule.iter().map(|&f| f as u8).collect();
+        self
+    }
+
+    /// Returns these options updated to send the FRI remainder (the last FRI layer) in the
+    /// specified format.
+    ///
+    /// The maximum degree of the remainder polynomial is defined by the FRI max remainder size
+    /// and the blowup factor: it is one less than `fri_max_remainder_size / blowup_factor`. When
+    /// [RemainderFormat::Coefficients] is used, this is also the number of remainder values
+    /// included in the proof (rather than `fri_max_remainder_size` evaluations).
+    ///
+    /// By default, [RemainderFormat::Evaluations] format is used.
+    pub fn with_fri_remainder_format(mut self, remainder_format: RemainderFormat) -> Self {
+        self.fri_remainder_format = remainder_format;
+        self
+    }
+
+    /// Returns these options updated to use the specified low-degree test.
+    ///
+    /// When [LowDegreeTest::Stir] is used, FRI folding factor and FRI folding schedule define
+    /// the folding factor of every STIR round, and the number of queries in every round is
+    /// derived from `num_queries` such that every round provides the same level of security as
+    /// `num_queries` FRI queries. FRI remainder format does not apply to STIR: the final
+    /// polynomial of STIR is always sent as coefficients.
+    ///
+    /// When [LowDegreeTest::Direct] is used, the DEEP composition polynomial is sent to the
+    /// verifier as coefficients, and FRI folding parameters are ignored.
+    ///
+    /// By default, [LowDegreeTest::Fri] is used.
+    pub fn with_low_degree_test(mut self, low_degree_test: LowDegreeTest) -> Self {
+        self.low_degree_test = low_degree_test;
+        self
+    }
+
+    /// Returns these options updated to allow the FRI prover to stop folding as soon as the
+    /// degree of the polynomial at the current FRI layer is smaller than the max remainder degree.
+    ///
+    /// The number of FRI layers committed to by the prover is then included in the proof, and the
+    /// verifier accepts any number of layers up to the number implied by these options. With
+    /// early termination, the FRI remainder is always sent as coefficients; early termination
+    /// does not apply to low-degree tests other than [LowDegreeTest::Fri].
+    pub fn with_fri_early_termination(mut self) -> Self {
+        self.fri_early_termination = true;
+        self
+    }
+
+    /// Returns these options updated to perform DEEP-style out-of-domain sampling within FRI
+    /// layers.
+    ///
+    /// An evaluation of the polynomial at every FRI layer at a random out-of-domain point is
+    /// then included in the proof, which increases the number of queries which can be verified
+    /// in the provable security regime. Out-of-domain sampling does not apply to low-degree
+    /// tests other than [LowDegreeTest::Fri].
+    pub fn with_fri_deep_sampling(mut self) -> Self {
+        self.fri_deep_sampling = true;
+        self
+    }
+
+    /// Returns a new instance of [ProofOptions] struct which achieves the specified security
+    /// level (in bits) for a base field with modulus of the specified number of bits.
+    ///
+    /// When `conjectured` is true, the options are selected to achieve the target conjectured
+    /// security level; otherwise, the options are selected to achieve the target provable
+    /// security level. The parameters are derived as follows:
+    /// * Hash function is set to BLAKE3 with the smallest output which provides sufficient
+    ///   collision resistance.
+    /// * Field extension is set to the smallest extension of the base field which provides
+    ///   sufficient security for LDE domains of up to 2^24 elements.
+    /// * Grinding factor is set to 16.
+    /// * Blowup factor is set to the smallest value (starting with 8) for which the target
+    ///   security level can be reached with no more than 128 queries, and the number of queries
+    ///   is set to the smallest value which reaches the target security level for this blowup
+    ///   factor.
+    ///
+    /// Note that blowup factor must also be large enough to evaluate transition constraints of
+    /// the computation; thus, computations with very high-degree constraints may require
+    /// options to be constructed manually.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `security_level` is zero or greater than 128.
+    /// * The target security level cannot be achieved for a field with `base_field_bits` bits
+    ///   using any of the supported field extensions.
+    /// * The target security level cannot be achieved with any of the supported combinations of
+    ///   blowup factor and the number of queries.
+    pub fn with_security(security_level: u32, conjectured: bool, base_field_bits: u32) -> Self {
+        assert!(security_level > 0, "security level must be greater than 0");
+        assert!(
+            security_level <= 128,
+            "security level cannot be greater than 128"
+        );
+
+        let hash_fn = if security_level <= HashFunction::Blake3_192.collision_resistance() {
+            HashFunction::Blake3_192
+        } else {
+            HashFunction::Blake3_256
+        };
+
+        let lde_domain_size = 2u64.pow(SECURITY_MAX_LDE_DOMAIN_SIZE_LOG2);
+        let estimate_security = |options: &ProofOptions| {
+            if conjectured {
+                get_conjectured_security(options, base_field_bits, lde_domain_size)
+            } else {
+                get_proven_security(options, base_field_bits, lde_domain_size)
+            }
+        };
+
+        // pick the smallest field extension for which field security is not the bottleneck;
+        // we do this by checking the security level of options which use the maximum number of
+        // queries and the maximum blowup factor
+        let max_blowup_factor = SECURITY_BLOWUP_FACTORS[SECURITY_BLOWUP_FACTORS.len() - 1];
+        let field_extension = FIELD_EXTENSIONS
+            .iter()
+            .copied()
+            .find(|&field_extension| {
+                let options =
+                    Self::new_for_security(128, max_blowup_factor, hash_fn, field_extension);
+                estimate_security(&options) >= security_level
+            })
+            .unwrap_or_else(|| {
+                panic!(
+                    "security level of {} bits cannot be achieved for a {}-bit field",
+                    security_level, base_field_bits
+                )
+            });
+
+        // find the smallest blowup factor and the smallest number of queries for this blowup
+        // factor which achieve the target security level
+        for &blowup_factor in SECURITY_BLOWUP_FACTORS.iter() {
+            for num_queries in 1..=128 {
+                let options =
+                    Self::new_for_security(num_queries, blowup_factor, hash_fn, field_extension);
+                if estimate_security(&options) >= security_level {
+                    return options;
+                }
+            }
+        }
+
+        panic!(
+            "security level of {} bits cannot be achieved with any supported number of queries",
+            security_level
+        );
+    }
+
+    /// Returns these options updated to use the smallest field extension which achieves the
+    /// specified security level (in bits) for an execution trace of the specified length and a
+    /// base field with modulus of the specified number of bits.
+    ///
+    /// Security provided by the field decreases as the LDE domain grows; thus, a field extension
+    /// which is sufficient for a short trace may not be sufficient for a long one. This method
+    /// makes it possible to select the extension for every proof individually rather than fixing
+    /// it for the worst case. The selected extension is a part of proof options, and thus, it is
+    /// recorded in the proof context and is used by the verifier.
+    ///
+    /// When `conjectured` is true, the extension is selected to achieve the target conjectured
+    /// security level; otherwise, the target provable security level. All other options are
+    /// left unchanged.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `trace_length` is not a power of two.
+    /// * The target security level cannot be achieved with these options using any of the
+    ///   supported field extensions.
+    pub fn with_field_extension_for_trace(
+        mut self,
+        security_level: u32,
+        conjectured: bool,
+        base_field_bits: u32,
+        trace_length: usize,
+    ) -> Self {
+        assert!(
+            trace_length.is_power_of_two(),
+            "trace length must be a power of 2, but was {}",
+            trace_length
+        );
+
+        let lde_domain_size = (trace_length * self.blowup_factor()) as u64;
+        self.field_extension = FIELD_EXTENSIONS
+            .iter()
+            .copied()
+            .find(|&field_extension| {
+                let options = ProofOptions {
+                    field_extension,
+                    ..self.clone()
+                };
+                let security = if conjectured {
+                    get_conjectured_security(&options, base_field_bits, lde_domain_size)
+                } else {
+                    get_proven_security(&options, base_field_bits, lde_domain_size)
+                };
+                security >= security_level
+            })
+            .unwrap_or_else(|| {
+                panic!(
+                    "security level of {} bits cannot be achieved for a {}-bit field and trace length {}",
+                    security_level, base_field_bits, trace_length
+                )
+            });
+        self
     }
 
     // PUBLIC ACCESSORS
@@ -175,6 +577,13 @@ impl ProofOptions {
         self.grinding_factor as u32
     }
 
+    /// Returns the number of leading zeros required by the proof-of-work for each FRI layer.
+    ///
+    /// If zero is returned, FRI layer commitments are not accompanied by a proof-of-work.
+    pub fn fri_layer_grinding_factor(&self) -> u32 {
+        self.fri_layer_grinding_factor as u32
+    }
+
     /// Returns a hash functions to be used during STARK proof construction.
     ///
     /// Security of a STARK proof is bounded by collision resistance of the hash function used
@@ -194,6 +603,64 @@ impl ProofOptions {
         self.field_extension
     }
 
+    /// Returns the strategy used to derive query positions from the query seed.
+    pub fn query_sampling(&self) -> QuerySampling {
+        self.query_sampling
+    }
+
+    /// Returns the expected number of distinct query positions drawn from an LDE domain of the
+    /// specified size using the query sampling strategy of these options.
+    ///
+    /// This is equal to [num_queries()](ProofOptions::num_queries) for strategies which always
+    /// draw distinct positions, and may be smaller otherwise. Security estimates are based on
+    /// this number.
+    pub fn expected_num_query_positions(&self, lde_domain_size: usize) -> usize {
+        self.query_sampling
+            .expected_num_positions(self.num_queries(), lde_domain_size)
+    }
+
+    /// Returns the maximum number of columns in a single segment of the extended execution
+    /// trace.
+    ///
+    /// If zero is returned, the entire extended trace is committed to via a single Merkle tree.
+    pub fn trace_segment_width(&self) -> usize {
+        self.trace_segment_width as usize
+    }
+
+    /// Returns ranges of columns of an execution trace of the specified width which are
+    /// committed to via separate Merkle trees.
+    ///
+    /// All segments contain [trace_segment_width()](ProofOptions::trace_segment_width) columns,
+    /// except for the last segment which may contain fewer columns.
+    pub fn trace_segments(&self, trace_width: usize) -> Vec<Range<usize>> {
+        let segment_width = match self.trace_segment_width() {
+            0 => trace_width,
+            segment_width => segment_width,
+        };
+        (0..trace_width)
+            .step_by(segment_width.max(1))
+            .map(|start| start..(start + segment_width).min(trace_width))
+            .collect()
+    }
+
+    /// Draws query positions in the LDE domain from the specified `public_coin` using the query
+    /// sampling strategy of these options.
+    ///
+    /// # Errors
+    /// Returns an error if the required number of distinct positions could not be drawn.
+    pub fn draw_query_positions<P: PublicCoin>(
+        &self,
+        public_coin: &mut P,
+        lde_domain_size: usize,
+    ) -> Result<Vec<usize>, RandomCoinError> {
+        self.query_sampling.draw_positions(
+            public_coin,
+            self.num_queries(),
+            lde_domain_size,
+            self.blowup_factor(),
+        )
+    }
+
     /// Returns the offset by which the low-degree extension domain is shifted in relation to the
     /// trace domain.
     ///
@@ -206,7 +673,52 @@ impl ProofOptions {
     pub fn to_fri_options(&self) -> FriOptions {
         let folding_factor = self.fri_folding_factor as usize;
         let max_remainder_size = 2usize.pow(self.fri_max_remainder_size as u32);
-        FriOptions::new(self.blowup_factor(), folding_factor, max_remainder_size)
+        let folding_schedule = self
+            .fri_folding_schedule
+            .iter()
+            .map(|&f| f as usize)
+            .collect::<Vec<_>>();
+        let options = FriOptions::new(self.blowup_factor(), folding_factor, max_remainder_size)
+            .with_layer_grinding_factor(self.fri_layer_grinding_factor())
+            .with_folding_schedule(&folding_schedule)
+            .with_remainder_format(self.fri_remainder_format);
+        let options = if self.fri_early_termination {
+            options.with_early_termination()
+        } else {
+            options
+        };
+        let options = if self.fri_deep_sampling {
+            options.with_deep_sampling()
+        } else {
+            options
+        };
+        match self.low_degree_test {
+            LowDegreeTest::Fri => options,
+            LowDegreeTest::Stir => options.with_stir(self.num_queries()),
+            LowDegreeTest::Direct => options.with_direct(),
+        }
+    }
+
+    // HELPER FUNCTIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns options with the specified parameters, and all other parameters set to the
+    /// defaults used by [ProofOptions::with_security()].
+    fn new_for_security(
+        num_queries: usize,
+        blowup_factor: usize,
+        hash_fn: HashFunction,
+        field_extension: FieldExtension,
+    ) -> Self {
+        Self::new(
+            num_queries,
+            blowup_factor,
+            SECURITY_GRINDING_FACTOR,
+            hash_fn,
+            field_extension,
+            SECURITY_FRI_FOLDING_FACTOR,
+            SECURITY_FRI_MAX_REMAINDER_SIZE,
+        )
     }
 }
 
@@ -216,29 +728,170 @@ impl Serializable for ProofOptions {
         target.write_u8(self.num_queries);
         target.write_u8(self.blowup_factor);
         target.write_u8(self.grinding_factor);
+        target.write_u8(self.fri_layer_grinding_factor);
         target.write(self.hash_fn);
         target.write(self.field_extension);
         target.write_u8(self.fri_folding_factor);
         target.write_u8(self.fri_max_remainder_size);
+        target.write_u8(self.trace_segment_width);
+        target.write(self.query_sampling);
+        target.write(self.fri_remainder_format);
+        let mut fri_flags = 0;
+        if self.fri_deep_sampling {
+            fri_flags |= FRI_DEEP_SAMPLING_FLAG;
+        }
+        if self.fri_early_termination {
+            fri_flags |= FRI_EARLY_TERMINATION_FLAG;
+        }
+        target.write_u8(fri_flags);
+        target.write(self.low_degree_test);
+        // the FRI folding schedule is serialized as the number of factors followed by the
+        // factors; an empty schedule is serialized as a single zero byte
+        target.write_u8(self.fri_folding_schedule.len() as u8);
+        target.write_u8_slice(&self.fri_folding_schedule);
     }
 }
 
 impl Deserializable for ProofOptions {
     /// Reads proof options from the specified `source` and returns the result.
     ///
+    /// All values are validated before the options are constructed; thus, options read from
+    /// untrusted sources result in an error rather than a panic if they are invalid.
+    ///
     /// # Errors
-    /// Returns an error of a valid proof options could not be read from the specified `source`.
+    /// Returns an error of a valid proof options could not be read from the specified `source`,
+    /// or if any of the read values is outside of the range accepted by [ProofOptions::new()].
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
-        Ok(ProofOptions::new(
-            source.read_u8()? as usize,
-            source.read_u8()? as usize,
-            source.read_u8()? as u32,
-            HashFunction::read_from(source)?,
-            FieldExtension::read_from(source)?,
-            source.read_u8()? as usize,
-            2usize.pow(source.read_u8()? as u32),
-        ))
+        let num_queries = source.read_u8()? as usize;
+        if num_queries == 0 || num_queries > 128 {
+            return Err(DeserializationError::InvalidValue(format!(
+                "number of queries must be between 1 and 128, but was {}",
+                num_queries
+            )));
+        }
+        let blowup_factor = read_power_of_two(source, "blowup factor", 4, 128)?;
+        let grinding_factor = read_grinding_factor(source, "grinding factor")?;
+        let fri_layer_grinding_factor = read_grinding_factor(source, "FRI layer grinding factor")?;
+        let hash_fn = HashFunction::read_from(source)?;
+        let field_extension = FieldExtension::read_from(source)?;
+
+        let fri_folding_factor = read_power_of_two(source, "FRI folding factor", 2, 16)?;
+
+        // FRI max remainder size is serialized as log2 of its value
+        let fri_max_remainder_size_log2 = source.read_u8()? as u32;
+        if !(5..=10).contains(&fri_max_remainder_size_log2) {
+            return Err(DeserializationError::InvalidValue(format!(
+                "FRI max remainder size must be between 32 and 1024, but was 2^{}",
+                fri_max_remainder_size_log2
+            )));
+        }
+
+        let mut options = ProofOptions::new(
+            num_queries,
+            blowup_factor,
+            grinding_factor,
+            hash_fn,
+            field_extension,
+            fri_folding_factor,
+            2usize.pow(fri_max_remainder_size_log2),
+        )
+        .with_fri_layer_grinding_factor(fri_layer_grinding_factor)
+        .with_trace_segment_width(source.read_u8()? as usize)
+        .with_query_sampling(QuerySampling::read_from(source)?)
+        .with_fri_remainder_format(RemainderFormat::read_from(source)?);
+
+        let fri_flags = source.read_u8()?;
+        if fri_flags & !FRI_FLAGS_MASK != 0 {
+            return Err(DeserializationError::InvalidValue(format!(
+                "FRI flags {:#04x} contain unknown bits",
+                fri_flags
+            )));
+        }
+        if fri_flags & FRI_DEEP_SAMPLING_FLAG != 0 {
+            options = options.with_fri_deep_sampling();
+        }
+        if fri_flags & FRI_EARLY_TERMINATION_FLAG != 0 {
+            options = options.with_fri_early_termination();
+        }
+        options = options.with_low_degree_test(LowDegreeTest::read_from(source)?);
+
+        let schedule_len = source.read_u8()? as usize;
+        if schedule_len == 0 {
+            return Ok(options);
+        }
+        let mut fri_folding_schedule = Vec::with_capacity(schedule_len);
+        for _ in 0..schedule_len {
+            fri_folding_schedule.push(read_power_of_two(source, "FRI folding factor", 2, 16)?);
+        }
+
+        Ok(options.with_fri_folding_schedule(&fri_folding_schedule))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for ProofOptions {
+    /// Serializes `self` into the `serializer` as bytes of its binary serialization.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serde_adapter::serialize(self, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for ProofOptions {
+    /// Reads proof options from bytes of its binary serialization read from the `deserializer`.
+    ///
+    /// # Errors
+    /// Returns an error if valid proof options could not be read from the `deserializer`.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        serde_adapter::deserialize(deserializer)
+    }
+}
+
+/// Reads a value from the specified `source` and makes sure it is a power of two between `min`
+/// and `max` (both inclusive).
+fn read_power_of_two<R: ByteReader>(
+    source: &mut R,
+    name: &str,
+    min: usize,
+    max: usize,
+) -> Result<usize, DeserializationError> {
+    let value = source.read_u8()? as usize;
+    check_power_of_two(value, name, min, max)
+}
+
+/// Makes sure the specified value is a power of two between `min` and `max` (both inclusive),
+/// and returns the value.
+fn check_power_of_two(
+    value: usize,
+    name: &str,
+    min: usize,
+    max: usize,
+) -> Result<usize, DeserializationError> {
+    if !value.is_power_of_two() || value < min || value > max {
+        return Err(DeserializationError::InvalidValue(format!(
+            "{} must be a power of two between {} and {}, but was {}",
+            name, min, max, value
+        )));
     }
+    Ok(value)
+}
+
+/// Reads a grinding factor from the specified `source` and makes sure it does not exceed the
+/// maximum allowed grinding factor.
+fn read_grinding_factor<R: ByteReader>(
+    source: &mut R,
+    name: &str,
+) -> Result<u32, DeserializationError> {
+    let grinding_factor = source.read_u8()? as u32;
+    if grinding_factor > ProofOptions::MAX_GRINDING_FACTOR {
+        return Err(DeserializationError::InvalidValue(format!(
+            "{} cannot be greater than {}, but was {}",
+            name,
+            ProofOptions::MAX_GRINDING_FACTOR,
+            grinding_factor
+        )));
+    }
+    Ok(grinding_factor)
 }
 
 // FIELD EXTENSION IMPLEMENTATION
@@ -274,7 +927,7 @@ impl Deserializable for FieldExtension {
             2 => Ok(FieldExtension::Quadratic),
             value => Err(DeserializationError::InvalidValue(format!(
                 "value {} cannot be deserialized as FieldExtension enum",
-                value.to_string()
+                value
             ))),
         }
     }
@@ -292,6 +945,15 @@ impl HashFunction {
             Self::Sha3_256 => 128,
         }
     }
+
+    /// Returns the number of bytes in a digest produced by this hash function.
+    pub fn digest_size(&self) -> usize {
+        match self {
+            Self::Blake3_192 => 24,
+            Self::Blake3_256 => 32,
+            Self::Sha3_256 => 32,
+        }
+    }
 }
 
 impl Serializable for HashFunction {
@@ -310,8 +972,528 @@ impl Deserializable for HashFunction {
             3 => Ok(HashFunction::Sha3_256),
             value => Err(DeserializationError::InvalidValue(format!(
                 "value {} cannot be deserialized as HashFunction enum",
-                value.to_string()
+                value
             ))),
         }
     }
 }
+
+// QUERY SAMPLING IMPLEMENTATION
+// ================================================================================================
+
+impl Serializable for QuerySampling {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8(*self as u8);
+    }
+}
+
+impl Deserializable for QuerySampling {
+    /// Reads a query sampling enum from the specified `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        match source.read_u8()? {
+            1 => Ok(QuerySampling::Distinct),
+            2 => Ok(QuerySampling::Uniform),
+            3 => Ok(QuerySampling::StratifiedByCoset),
+            value => Err(DeserializationError::InvalidValue(format!(
+                "value {} cannot be deserialized as QuerySampling enum",
+                value
+            ))),
+        }
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        FieldExtension, HashFunction, LowDegreeTest, ProofOptions, QuerySampling, RemainderFormat,
+    };
+    use crate::proof::{get_conjectured_security, get_proven_security};
+    use crypto::{hashers::Blake3_256, RandomCoin};
+    use math::fields::f128::BaseElement;
+    use utils::{Deserializable, Serializable, SliceReader};
+
+    #[test]
+    fn grinding_serialization() {
+        let options = ProofOptions::new(
+            28,
+            8,
+            40,
+            HashFunction::Blake3_256,
+            FieldExtension::None,
+            8,
+            256,
+        )
+        .with_fri_layer_grinding_factor(12);
+        assert_eq!(12, options.to_fri_options().layer_grinding_factor());
+
+        let mut bytes = Vec::new();
+        options.write_into(&mut bytes);
+        let parsed = ProofOptions::read_from(&mut SliceReader::new(&bytes)).unwrap();
+        assert_eq!(options, parsed);
+
+        // grinding factors above the maximum should be rejected rather than cause a panic
+        bytes[2] = 65;
+        assert!(ProofOptions::read_from(&mut SliceReader::new(&bytes)).is_err());
+    }
+
+    #[test]
+    fn invalid_options_deserialization() {
+        let options = ProofOptions::new(
+            28,
+            8,
+            0,
+            HashFunction::Blake3_256,
+            FieldExtension::None,
+            8,
+            256,
+        );
+        let mut bytes = Vec::new();
+        options.write_into(&mut bytes);
+
+        // out-of-range values at any position should be rejected rather than cause a panic; the
+        // positions are: number of queries, blowup factor, FRI folding factor and log2 of FRI
+        // max remainder size
+        for &(position, value) in [
+            (0, 0),
+            (0, 129),
+            (1, 2),
+            (1, 6),
+            (6, 1),
+            (6, 32),
+            (6, 0x48),
+            (7, 11),
+            (7, 64),
+            (7, 0x18),
+            (7, 0x48),
+            (7, 0x28),
+        ]
+        .iter()
+        {
+            let mut invalid_bytes = bytes.clone();
+            invalid_bytes[position] = value;
+            assert!(ProofOptions::read_from(&mut SliceReader::new(&invalid_bytes)).is_err());
+        }
+    }
+
+    #[test]
+    fn fri_folding_schedule_serialization() {
+        let options = ProofOptions::new(
+            28,
+            8,
+            0,
+            HashFunction::Blake3_256,
+            FieldExtension::None,
+            4,
+            256,
+        );
+        let mut bytes = Vec::new();
+        options.write_into(&mut bytes);
+
+        // the schedule should be serialized at the end of the options as the number of factors
+        // followed by the factors, and should round-trip
+        let scheduled_options = options.clone().with_fri_folding_schedule(&[16, 8, 2]);
+        let fri_options = scheduled_options.to_fri_options();
+        assert_eq!(&[16, 8, 2], fri_options.folding_schedule());
+        assert_eq!(4, fri_options.layer_folding_factor(3));
+
+        let mut scheduled_bytes = Vec::new();
+        scheduled_options.write_into(&mut scheduled_bytes);
+        let schedule_pos = bytes.len() - 1;
+        assert_eq!(0, bytes[schedule_pos]);
+        assert_eq!(bytes[..schedule_pos], scheduled_bytes[..schedule_pos]);
+        assert_eq!(&[3, 16, 8, 2], &scheduled_bytes[schedule_pos..]);
+        let parsed = ProofOptions::read_from(&mut SliceReader::new(&scheduled_bytes)).unwrap();
+        assert_eq!(scheduled_options, parsed);
+
+        // unsupported folding factors and truncated schedules should be rejected
+        let mut invalid_bytes = scheduled_bytes.clone();
+        invalid_bytes[schedule_pos + 2] = 32;
+        assert!(ProofOptions::read_from(&mut SliceReader::new(&invalid_bytes)).is_err());
+        invalid_bytes[schedule_pos] = 4;
+        assert!(ProofOptions::read_from(&mut SliceReader::new(&invalid_bytes)).is_err());
+    }
+
+    #[test]
+    fn fri_remainder_format_serialization() {
+        let options = ProofOptions::new(
+            28,
+            8,
+            0,
+            HashFunction::Blake3_256,
+            FieldExtension::None,
+            4,
+            256,
+        );
+        let mut bytes = Vec::new();
+        options.write_into(&mut bytes);
+
+        // the remainder format should be serialized as a separate field following query
+        // sampling, and should round-trip
+        let coefficient_options = options
+            .clone()
+            .with_fri_remainder_format(RemainderFormat::Coefficients);
+        let fri_options = coefficient_options.to_fri_options();
+        assert_eq!(
+            RemainderFormat::Coefficients,
+            fri_options.remainder_format()
+        );
+        assert_eq!(32, fri_options.fri_remainder_len(1024));
+
+        let mut coefficient_bytes = Vec::new();
+        coefficient_options.write_into(&mut coefficient_bytes);
+        assert_eq!(bytes.len(), coefficient_bytes.len());
+        assert_eq!(bytes[..10], coefficient_bytes[..10]);
+        assert_eq!(1, bytes[10]);
+        assert_eq!(2, coefficient_bytes[10]);
+        let parsed = ProofOptions::read_from(&mut SliceReader::new(&coefficient_bytes)).unwrap();
+        assert_eq!(coefficient_options, parsed);
+
+        // unknown remainder formats should be rejected
+        for value in [0, 3, 0x80] {
+            coefficient_bytes[10] = value;
+            assert!(ProofOptions::read_from(&mut SliceReader::new(&coefficient_bytes)).is_err());
+        }
+    }
+
+    #[test]
+    fn low_degree_test_serialization() {
+        let options = ProofOptions::new(
+            28,
+            8,
+            0,
+            HashFunction::Blake3_256,
+            FieldExtension::None,
+            4,
+            256,
+        );
+        assert_eq!(
+            LowDegreeTest::Fri,
+            options.to_fri_options().low_degree_test()
+        );
+        let mut bytes = Vec::new();
+        options.write_into(&mut bytes);
+
+        // the low-degree test should be serialized as a separate field following the FRI flags,
+        // and should round-trip together with the remainder format
+        let stir_options = options
+            .clone()
+            .with_low_degree_test(LowDegreeTest::Stir)
+            .with_fri_remainder_format(RemainderFormat::Coefficients);
+        let fri_options = stir_options.to_fri_options();
+        assert_eq!(LowDegreeTest::Stir, fri_options.low_degree_test());
+        assert_eq!(28, fri_options.num_queries());
+
+        let mut stir_bytes = Vec::new();
+        stir_options.write_into(&mut stir_bytes);
+        assert_eq!(bytes.len(), stir_bytes.len());
+        assert_eq!(bytes[..10], stir_bytes[..10]);
+        assert_eq!(2, stir_bytes[10]);
+        assert_eq!(1, bytes[12]);
+        assert_eq!(2, stir_bytes[12]);
+        let parsed = ProofOptions::read_from(&mut SliceReader::new(&stir_bytes)).unwrap();
+        assert_eq!(stir_options, parsed);
+
+        // the direct low-degree test should be serialized in the same field as STIR, and
+        // should always send the polynomial as coefficients
+        let direct_options = options.with_low_degree_test(LowDegreeTest::Direct);
+        let fri_options = direct_options.to_fri_options();
+        assert_eq!(0, fri_options.num_fri_layers(1024));
+        assert_eq!(
+            RemainderFormat::Coefficients,
+            fri_options.remainder_format()
+        );
+        assert_eq!(128, fri_options.fri_remainder_len(1024));
+
+        let mut direct_bytes = Vec::new();
+        direct_options.write_into(&mut direct_bytes);
+        assert_eq!(bytes[..12], direct_bytes[..12]);
+        assert_eq!(3, direct_bytes[12]);
+        let parsed = ProofOptions::read_from(&mut SliceReader::new(&direct_bytes)).unwrap();
+        assert_eq!(direct_options, parsed);
+
+        // unknown low-degree tests should be rejected
+        for value in [0, 4, 0x40] {
+            direct_bytes[12] = value;
+            assert!(ProofOptions::read_from(&mut SliceReader::new(&direct_bytes)).is_err());
+        }
+    }
+
+    #[test]
+    fn fri_early_termination_serialization() {
+        let options = ProofOptions::new(
+            28,
+            8,
+            0,
+            HashFunction::Blake3_256,
+            FieldExtension::None,
+            4,
+            256,
+        );
+        assert!(!options.to_fri_options().early_termination());
+        let mut bytes = Vec::new();
+        options.write_into(&mut bytes);
+
+        // early termination should be encoded in the second least significant bit of the FRI
+        // flags, and should always send the remainder as coefficients
+        let early_options = options.with_fri_early_termination();
+        let fri_options = early_options.to_fri_options();
+        assert!(fri_options.early_termination());
+        assert_eq!(
+            RemainderFormat::Coefficients,
+            fri_options.remainder_format()
+        );
+
+        let mut early_bytes = Vec::new();
+        early_options.write_into(&mut early_bytes);
+        assert_eq!(bytes.len(), early_bytes.len());
+        assert_eq!(bytes[..11], early_bytes[..11]);
+        assert_eq!(2, early_bytes[11]);
+        let parsed = ProofOptions::read_from(&mut SliceReader::new(&early_bytes)).unwrap();
+        assert_eq!(early_options, parsed);
+    }
+
+    #[test]
+    fn fri_deep_sampling_serialization() {
+        let options = ProofOptions::new(
+            28,
+            8,
+            0,
+            HashFunction::Blake3_256,
+            FieldExtension::None,
+            4,
+            256,
+        );
+        assert!(!options.to_fri_options().deep_sampling());
+        let mut bytes = Vec::new();
+        options.write_into(&mut bytes);
+
+        // out-of-domain sampling should be encoded in the least significant bit of the FRI
+        // flags which follow the remainder format
+        let deep_options = options.clone().with_fri_deep_sampling();
+        assert!(deep_options.to_fri_options().deep_sampling());
+        let mut deep_bytes = Vec::new();
+        deep_options.write_into(&mut deep_bytes);
+        assert_eq!(bytes.len(), deep_bytes.len());
+        assert_eq!(bytes[..11], deep_bytes[..11]);
+        assert_eq!(0, bytes[11]);
+        assert_eq!(1, deep_bytes[11]);
+        let parsed = ProofOptions::read_from(&mut SliceReader::new(&deep_bytes)).unwrap();
+        assert_eq!(deep_options, parsed);
+
+        // unknown FRI flags should be rejected
+        for value in [0x04, 0x80, 0xFF] {
+            let mut invalid_bytes = deep_bytes.clone();
+            invalid_bytes[11] = value;
+            assert!(ProofOptions::read_from(&mut SliceReader::new(&invalid_bytes)).is_err());
+        }
+
+        // the flag should be preserved together with a folding schedule
+        let deep_options = options
+            .with_fri_folding_schedule(&[8])
+            .with_fri_deep_sampling();
+        let mut deep_bytes = Vec::new();
+        deep_options.write_into(&mut deep_bytes);
+        assert_eq!(4, deep_bytes[6]);
+        assert_eq!(1, deep_bytes[11]);
+        let parsed = ProofOptions::read_from(&mut SliceReader::new(&deep_bytes)).unwrap();
+        assert_eq!(deep_options, parsed);
+    }
+
+    #[test]
+    fn query_sampling() {
+        let options = ProofOptions::new(
+            64,
+            8,
+            0,
+            HashFunction::Blake3_256,
+            FieldExtension::None,
+            8,
+            256,
+        );
+        assert_eq!(QuerySampling::Distinct, options.query_sampling());
+        let draw = |options: &ProofOptions| {
+            let mut coin = RandomCoin::<BaseElement, Blake3_256<BaseElement>>::new(&[1, 2, 3]);
+            options.draw_query_positions(&mut coin, 128).unwrap()
+        };
+
+        // distinct sampling always yields the requested number of distinct positions
+        let positions = draw(&options);
+        assert_eq!(64, positions.len());
+
+        // uniform sampling drops repeated positions; with 64 draws from 128 positions, some
+        // positions are virtually guaranteed to repeat
+        let options = options.with_query_sampling(QuerySampling::Uniform);
+        let mut positions = draw(&options);
+        let num_positions = positions.len();
+        assert!(num_positions < 64);
+        positions.sort_unstable();
+        positions.dedup();
+        assert_eq!(num_positions, positions.len());
+
+        // stratified sampling assigns queries to cosets in a round-robin fashion
+        let options = options.with_query_sampling(QuerySampling::StratifiedByCoset);
+        let positions = draw(&options);
+        assert_eq!(64, positions.len());
+        for (i, position) in positions.iter().enumerate() {
+            assert_eq!(i % 8, position % 8);
+        }
+
+        // query sampling strategy is a part of serialized options
+        let mut bytes = Vec::new();
+        options.write_into(&mut bytes);
+        let parsed = ProofOptions::read_from(&mut SliceReader::new(&bytes)).unwrap();
+        assert_eq!(options, parsed);
+
+        *bytes.last_mut().unwrap() = 4;
+        assert!(ProofOptions::read_from(&mut SliceReader::new(&bytes)).is_err());
+    }
+
+    #[test]
+    fn query_sampling_security() {
+        let options = ProofOptions::new(
+            32,
+            8,
+            0,
+            HashFunction::Blake3_256,
+            FieldExtension::None,
+            8,
+            256,
+        );
+
+        // strategies which always draw distinct positions get credit for all queries
+        assert_eq!(32, options.expected_num_query_positions(128));
+        let stratified = options
+            .clone()
+            .with_query_sampling(QuerySampling::StratifiedByCoset);
+        assert_eq!(32, stratified.expected_num_query_positions(128));
+        assert_eq!(95, get_conjectured_security(&options, 128, 128));
+
+        // for uniform sampling, only the expected number of distinct positions is credited; with
+        // 32 draws from 128 positions, about 28 distinct positions are expected
+        let uniform = options.clone().with_query_sampling(QuerySampling::Uniform);
+        assert_eq!(28, uniform.expected_num_query_positions(128));
+        assert_eq!(31, uniform.expected_num_query_positions(1 << 20));
+        assert_eq!(83, get_conjectured_security(&uniform, 128, 128));
+        assert!(get_proven_security(&uniform, 128, 128) < get_proven_security(&options, 128, 128));
+    }
+
+    #[test]
+    fn trace_segments() {
+        let options = ProofOptions::new(
+            28,
+            8,
+            0,
+            HashFunction::Blake3_256,
+            FieldExtension::None,
+            8,
+            256,
+        );
+        assert_eq!(0, options.trace_segment_width());
+        assert_eq!(vec![0..10], options.trace_segments(10));
+
+        let options = options.with_trace_segment_width(4);
+        assert_eq!(vec![0..4, 4..8, 8..10], options.trace_segments(10));
+        assert_eq!(vec![0..4, 4..8], options.trace_segments(8));
+        assert_eq!(vec![0..2], options.trace_segments(2));
+
+        // trace segment width is a part of serialized options
+        let mut bytes = Vec::new();
+        options.write_into(&mut bytes);
+        let parsed = ProofOptions::read_from(&mut SliceReader::new(&bytes)).unwrap();
+        assert_eq!(options, parsed);
+    }
+
+    #[test]
+    fn with_security_conjectured() {
+        let options = ProofOptions::with_security(96, true, 128);
+        assert_eq!(HashFunction::Blake3_192, options.hash_fn());
+        assert_eq!(FieldExtension::None, options.field_extension());
+        assert_eq!(8, options.blowup_factor());
+        assert_eq!(16, options.grinding_factor());
+        assert!(get_conjectured_security(&options, 128, 1 << 24) >= 96);
+
+        // a 62-bit field requires a quadratic extension to reach 96 bits
+        let options = ProofOptions::with_security(96, true, 62);
+        assert_eq!(FieldExtension::Quadratic, options.field_extension());
+        assert!(get_conjectured_security(&options, 62, 1 << 24) >= 96);
+
+        // one query less should not be enough
+        let weaker = ProofOptions::new_for_security(
+            options.num_queries() - 1,
+            options.blowup_factor(),
+            options.hash_fn(),
+            options.field_extension(),
+        );
+        assert!(get_conjectured_security(&weaker, 62, 1 << 24) < 96);
+    }
+
+    #[test]
+    fn with_security_proven() {
+        let conjectured = ProofOptions::with_security(96, true, 128);
+        let proven = ProofOptions::with_security(96, false, 128);
+        assert_eq!(FieldExtension::Quadratic, proven.field_extension());
+        assert!(proven.num_queries() > 2 * conjectured.num_queries());
+        assert!(get_proven_security(&proven, 128, 1 << 24) >= 96);
+    }
+
+    #[test]
+    fn field_extension_for_trace() {
+        let options = ProofOptions::new(
+            42,
+            8,
+            16,
+            HashFunction::Blake3_256,
+            FieldExtension::Quadratic,
+            8,
+            256,
+        );
+
+        // for a 62-bit field, the base field is sufficient for short traces
+        let short = options
+            .clone()
+            .with_field_extension_for_trace(48, true, 62, 1 << 10);
+        assert_eq!(FieldExtension::None, short.field_extension());
+        assert!(get_conjectured_security(&short, 62, 1 << 13) >= 48);
+
+        // but long traces require a quadratic extension
+        let long = options
+            .clone()
+            .with_field_extension_for_trace(48, true, 62, 1 << 20);
+        assert_eq!(FieldExtension::Quadratic, long.field_extension());
+        assert!(get_conjectured_security(&long, 62, 1 << 23) >= 48);
+
+        // all other options are left unchanged
+        assert_eq!(
+            ProofOptions {
+                field_extension: FieldExtension::Quadratic,
+                ..short
+            },
+            long
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot be achieved for a 62-bit field and trace length 1024")]
+    fn field_extension_for_trace_too_small() {
+        ProofOptions::new(
+            4,
+            8,
+            0,
+            HashFunction::Blake3_256,
+            FieldExtension::None,
+            8,
+            256,
+        )
+        .with_field_extension_for_trace(48, true, 62, 1 << 10);
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot be achieved for a 62-bit field")]
+    fn with_security_field_too_small() {
+        ProofOptions::with_security(100, true, 62);
+    }
+}
@@ -0,0 +1,276 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{ProofLimits, StarkProof};
+use core::marker::PhantomData;
+use crypto::Hasher;
+use utils::{
+    collections::{BTreeMap, Vec},
+    ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+};
+
+// PROOF CHUNK
+// ================================================================================================
+/// A fixed-size piece of a serialized STARK proof.
+///
+/// Chunks make it possible to send a proof over a transport which limits the size of a single
+/// message (e.g., a gossip protocol over UDP, or a blockchain which limits the size of
+/// transaction calldata). A serialized proof is split into chunks via [ProofChunk::split()]
+/// (or [StarkProof::to_chunks()]); every chunk contains its index, the total number of chunks,
+/// and a digest of the entire serialized proof. The chunks can then be sent in any order, and
+/// reassembled on the receiving side via [ProofReassembler], which makes sure that all chunks
+/// belong to the same proof and that the reassembled proof matches the digest.
+///
+/// A chunk is serialized via the [Serializable] trait as follows: chunk index (`u32`), number
+/// of chunks (`u32`), digest length (`u8`) followed by the digest, and data length (`u32`)
+/// followed by the data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofChunk {
+    index: u32,
+    num_chunks: u32,
+    digest: Vec<u8>,
+    data: Vec<u8>,
+}
+
+impl ProofChunk {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Splits the specified serialized proof into chunks carrying at most `chunk_size` bytes of
+    /// the proof each; the digest of the proof is computed using the hash function `H`.
+    ///
+    /// All chunks except for the last one carry exactly `chunk_size` bytes of the proof.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `chunk_size` is zero.
+    /// * `proof_bytes` is empty, or would be split into more than 2^32 - 1 chunks.
+    pub fn split<H: Hasher>(proof_bytes: &[u8], chunk_size: usize) -> Vec<Self> {
+        assert!(chunk_size > 0, "chunk size must be greater than zero");
+        assert!(!proof_bytes.is_empty(), "proof cannot be empty");
+        let num_chunks = proof_bytes.len().div_ceil(chunk_size);
+        assert!(
+            num_chunks <= u32::MAX as usize,
+            "number of chunks cannot exceed {}, but was {}",
+            u32::MAX,
+            num_chunks
+        );
+
+        let digest = H::hash(proof_bytes).to_bytes();
+        proof_bytes
+            .chunks(chunk_size)
+            .enumerate()
+            .map(|(index, data)| ProofChunk {
+                index: index as u32,
+                num_chunks: num_chunks as u32,
+                digest: digest.clone(),
+                data: data.to_vec(),
+            })
+            .collect()
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the index of this chunk.
+    pub fn index(&self) -> usize {
+        self.index as usize
+    }
+
+    /// Returns the total number of chunks the proof was split into.
+    pub fn num_chunks(&self) -> usize {
+        self.num_chunks as usize
+    }
+
+    /// Returns the digest of the entire serialized proof.
+    pub fn digest(&self) -> &[u8] {
+        &self.digest
+    }
+
+    /// Returns the bytes of the proof carried by this chunk.
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+impl Serializable for ProofChunk {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u32(self.index);
+        target.write_u32(self.num_chunks);
+        target.write_u8(self.digest.len() as u8);
+        target.write_u8_slice(&self.digest);
+        target.write_u32(self.data.len() as u32);
+        target.write_u8_slice(&self.data);
+    }
+}
+
+impl Deserializable for ProofChunk {
+    /// Reads a proof chunk from the specified `source` and returns the result.
+    ///
+    /// # Errors
+    /// Returns an error if a valid proof chunk could not be read from the specified `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let index = source.read_u32()?;
+        let num_chunks = source.read_u32()?;
+        if index >= num_chunks {
+            return Err(DeserializationError::InvalidValue(format!(
+                "chunk index must be smaller than the number of chunks {}, but was {}",
+                num_chunks, index
+            )));
+        }
+        let digest_len = source.read_u8()? as usize;
+        let digest = source.read_u8_vec(digest_len)?;
+        let data_len = source.read_u32()? as usize;
+        if data_len == 0 {
+            return Err(DeserializationError::InvalidValue(
+                "proof chunk cannot be empty".into(),
+            ));
+        }
+        let data = source.read_u8_vec(data_len)?;
+        Ok(ProofChunk {
+            index,
+            num_chunks,
+            digest,
+            data,
+        })
+    }
+}
+
+// PROOF REASSEMBLER
+// ================================================================================================
+/// Reassembles a serialized STARK proof from [ProofChunk]s received in any order.
+///
+/// The number of chunks and the digest of the proof are taken from the first added chunk; all
+/// subsequent chunks must carry the same values. Chunks which were already added may be added
+/// again (e.g., if they were retransmitted) as long as they carry the same data. Once all chunks
+/// have been added, the proof can be reassembled via [into_bytes()](Self::into_bytes) or
+/// [into_proof()](Self::into_proof); both functions make sure that the reassembled proof
+/// matches the digest computed with the hash function `H`.
+///
+/// The total size of the added chunks is checked against the maximum proof size of the
+/// [ProofLimits] specified when the reassembler is created; thus, a peer sending chunks of a
+/// proof which is too large cannot make the reassembler allocate more memory than the proof
+/// would take.
+pub struct ProofReassembler<H: Hasher> {
+    limits: ProofLimits,
+    num_chunks: usize,
+    digest: Vec<u8>,
+    chunks: BTreeMap<u32, Vec<u8>>,
+    num_bytes: usize,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: Hasher> ProofReassembler<H> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new reassembler for a proof which does not exceed the size limit specified by
+    /// `limits`; all other limits are enforced when the proof is parsed via
+    /// [into_proof()](Self::into_proof).
+    pub fn new(limits: ProofLimits) -> Self {
+        ProofReassembler {
+            limits,
+            num_chunks: 0,
+            digest: Vec::new(),
+            chunks: BTreeMap::new(),
+            num_bytes: 0,
+            _hasher: PhantomData,
+        }
+    }
+
+    // CHUNK PROCESSING
+    // --------------------------------------------------------------------------------------------
+
+    /// Adds the specified `chunk` to the proof being reassembled.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The number of chunks or the digest of the `chunk` are not the same as in previously
+    ///   added chunks.
+    /// * A chunk with the same index but different data has already been added.
+    /// * The total size of the added chunks exceeds the maximum proof size.
+    pub fn add_chunk(&mut self, chunk: ProofChunk) -> Result<(), DeserializationError> {
+        if self.chunks.is_empty() {
+            self.num_chunks = chunk.num_chunks();
+            self.digest = chunk.digest.clone();
+        } else if chunk.num_chunks() != self.num_chunks || chunk.digest != self.digest {
+            return Err(DeserializationError::InvalidValue(
+                "proof chunk belongs to a different proof".into(),
+            ));
+        }
+
+        if let Some(data) = self.chunks.get(&chunk.index) {
+            if *data != chunk.data {
+                return Err(DeserializationError::InvalidValue(format!(
+                    "proof chunk {} was already received with different data",
+                    chunk.index
+                )));
+            }
+            return Ok(());
+        }
+
+        self.num_bytes += chunk.data.len();
+        self.limits.check_proof_size(self.num_bytes)?;
+        self.chunks.insert(chunk.index, chunk.data);
+        Ok(())
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns true if all chunks of the proof have been added.
+    pub fn is_complete(&self) -> bool {
+        !self.chunks.is_empty() && self.chunks.len() == self.num_chunks
+    }
+
+    /// Returns indexes of chunks which have not been added yet; this is empty if no chunks
+    /// have been added, as the number of chunks is not known yet.
+    pub fn missing_chunks(&self) -> Vec<usize> {
+        (0..self.num_chunks as u32)
+            .filter(|index| !self.chunks.contains_key(index))
+            .map(|index| index as usize)
+            .collect()
+    }
+
+    // REASSEMBLY
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the serialized proof reassembled from the added chunks.
+    ///
+    /// # Errors
+    /// Returns an error if not all chunks of the proof have been added, or if the reassembled
+    /// proof does not match the digest carried by the chunks.
+    pub fn into_bytes(self) -> Result<Vec<u8>, DeserializationError> {
+        if !self.is_complete() {
+            return Err(DeserializationError::InvalidValue(format!(
+                "cannot reassemble proof: {} of {} chunks are missing",
+                self.num_chunks - self.chunks.len(),
+                self.num_chunks
+            )));
+        }
+
+        let mut result = Vec::with_capacity(self.num_bytes);
+        for data in self.chunks.values() {
+            result.extend_from_slice(data);
+        }
+        if H::hash(&result).to_bytes() != self.digest {
+            return Err(DeserializationError::InvalidValue(
+                "reassembled proof does not match the proof digest".into(),
+            ));
+        }
+        Ok(result)
+    }
+
+    /// Returns a STARK proof reassembled from the added chunks, if the proof does not exceed the
+    /// limits of this reassembler.
+    ///
+    /// # Errors
+    /// Returns an error if the proof could not be reassembled via
+    /// [into_bytes()](Self::into_bytes), or if it could not be read via
+    /// [StarkProof::from_bytes_with_limits()].
+    pub fn into_proof(self) -> Result<StarkProof, DeserializationError> {
+        let limits = self.limits;
+        StarkProof::from_bytes_with_limits(&self.into_bytes()?, &limits)
+    }
+}
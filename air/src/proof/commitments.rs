@@ -9,18 +9,24 @@ use utils::{
     SliceReader,
 };
 
+#[cfg(feature = "serde")]
+use utils::{
+    serde::{Deserialize, Deserializer, Serialize, Serializer},
+    serde_adapter,
+};
+
 // COMMITMENTS
 // ================================================================================================
 /// Commitments made by the prover during commit phase of the protocol.
 ///
 /// These commitments include:
-/// * Commitment to the extended execution trace.
+/// * Commitments to the extended execution trace; one commitment for each trace segment.
 /// * Commitment to the evaluations of constraint composition polynomial over LDE domain.
 /// * Commitments to the evaluations of polynomials at all FRI layers.
 ///
 /// Internally, the commitments are stored as a sequence of bytes. Thus, to retrieve the
 /// commitments, [parse()](Commitments::parse) function should be used.
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq, Default)]
 pub struct Commitments(Vec<u8>);
 
 impl Commitments {
@@ -28,12 +34,12 @@ impl Commitments {
     // --------------------------------------------------------------------------------------------
     /// Returns a new Commitments struct initialized with the provided commitments.
     pub fn new<H: Hasher>(
-        trace_root: H::Digest,
+        trace_roots: Vec<H::Digest>,
         constraint_root: H::Digest,
         fri_roots: Vec<H::Digest>,
     ) -> Self {
         let mut bytes = Vec::new();
-        bytes.write(trace_root);
+        bytes.write(trace_roots);
         bytes.write(constraint_root);
         bytes.write(fri_roots);
         Commitments(bytes)
@@ -53,7 +59,7 @@ impl Commitments {
     /// Parses the serialized commitments into distinct parts.
     ///
     /// The parts are (in the order in which they appear in the tuple):
-    /// 1. Extended execution trace commitment.
+    /// 1. Extended execution trace commitments; one for each of `num_trace_segments` segments.
     /// 2. Constraint composition polynomial evaluation commitment.
     /// 3. FRI layer commitments.
     ///
@@ -63,23 +69,35 @@ impl Commitments {
     #[allow(clippy::type_complexity)]
     pub fn parse<H: Hasher>(
         self,
+        num_trace_segments: usize,
         num_fri_layers: usize,
-    ) -> Result<(H::Digest, H::Digest, Vec<H::Digest>), DeserializationError> {
-        // +1 for trace_root, +1 for constraint root, +1 for FRI remainder commitment
-        let num_commitments = num_fri_layers + 3;
+    ) -> Result<(Vec<H::Digest>, H::Digest, Vec<H::Digest>), DeserializationError> {
+        // +1 for constraint root, +1 for FRI remainder commitment
+        let num_commitments = num_trace_segments + num_fri_layers + 2;
         let mut reader = SliceReader::new(&self.0);
         let commitments = H::Digest::read_batch_from(&mut reader, num_commitments)?;
         // make sure we consumed all available commitment bytes
         if reader.has_more_bytes() {
             return Err(DeserializationError::UnconsumedBytes);
         }
-        Ok((commitments[0], commitments[1], commitments[2..].to_vec()))
+        Ok((
+            commitments[..num_trace_segments].to_vec(),
+            commitments[num_trace_segments],
+            commitments[num_trace_segments + 1..].to_vec(),
+        ))
     }
-}
 
-impl Default for Commitments {
-    fn default() -> Self {
-        Commitments(Vec::new())
+    // SERIALIZATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Advances the specified `source` past commitments serialized via the [Serializable] trait
+    /// without reading them into memory.
+    ///
+    /// # Errors
+    /// Returns an error if the `source` does not contain enough bytes for the commitments.
+    pub(crate) fn skip_from<R: ByteReader>(source: &mut R) -> Result<(), DeserializationError> {
+        let num_bytes = source.read_u16()? as usize;
+        source.skip_bytes(num_bytes)
     }
 }
 
@@ -104,3 +122,22 @@ impl Deserializable for Commitments {
         Ok(Commitments(result))
     }
 }
+
+#[cfg(feature = "serde")]
+impl Serialize for Commitments {
+    /// Serializes `self` into the `serializer` as bytes of its binary serialization.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serde_adapter::serialize(self, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Commitments {
+    /// Reads commitments from bytes of its binary serialization read from the `deserializer`.
+    ///
+    /// # Errors
+    /// Returns an error if valid commitments could not be read from the `deserializer`.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        serde_adapter::deserialize(deserializer)
+    }
+}
@@ -0,0 +1,189 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use utils::{collections::Vec, ByteReader, ByteWriter, DeserializationError, SliceReader};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Maximum number of bytes in a variable-length encoding of a u32 value.
+const MAX_VARINT_BYTES: usize = 5;
+
+// COMPACT WRITER
+// ================================================================================================
+
+/// A byte writer which writes u16 and u32 values using variable-length encoding.
+///
+/// In a serialized proof, u16 and u32 values are used only for lengths and counts, which are
+/// usually much smaller than their maximum values. These values are written in LEB128 format:
+/// 7 bits per byte starting with the least significant bits, with the most significant bit of
+/// each byte indicating whether more bytes follow. All other values are written as is.
+pub(super) struct CompactWriter(Vec<u8>);
+
+impl CompactWriter {
+    pub fn new() -> Self {
+        CompactWriter(Vec::new())
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.0
+    }
+
+    fn write_varint(&mut self, mut value: u32) {
+        while value >= 0x80 {
+            self.0.push((value as u8) | 0x80);
+            value >>= 7;
+        }
+        self.0.push(value as u8);
+    }
+}
+
+impl ByteWriter for CompactWriter {
+    fn write_u8(&mut self, value: u8) {
+        self.0.push(value);
+    }
+
+    fn write_u8_slice(&mut self, values: &[u8]) {
+        self.0.extend_from_slice(values);
+    }
+
+    fn write_u16(&mut self, value: u16) {
+        self.write_varint(value as u32);
+    }
+
+    fn write_u32(&mut self, value: u32) {
+        self.write_varint(value);
+    }
+}
+
+// COMPACT READER
+// ================================================================================================
+
+/// A byte reader which reads u16 and u32 values written by [CompactWriter].
+///
+/// Only the shortest encoding of every value is accepted; thus, every proof has a single
+/// compressed representation.
+pub(super) struct CompactReader<'a>(SliceReader<'a>);
+
+impl<'a> CompactReader<'a> {
+    pub fn new(source: &'a [u8]) -> Self {
+        CompactReader(SliceReader::new(source))
+    }
+
+    fn read_varint(&mut self) -> Result<u32, DeserializationError> {
+        let mut value = 0u64;
+        for i in 0..MAX_VARINT_BYTES {
+            let byte = self.0.read_u8()?;
+            value |= ((byte & 0x7f) as u64) << (7 * i);
+            if byte & 0x80 == 0 {
+                if byte == 0 && i != 0 {
+                    return Err(DeserializationError::InvalidValue(
+                        "variable-length value is not encoded in the shortest form".into(),
+                    ));
+                }
+                if value > u32::MAX as u64 {
+                    break;
+                }
+                return Ok(value as u32);
+            }
+        }
+        Err(DeserializationError::InvalidValue(
+            "variable-length value does not fit into 32 bits".into(),
+        ))
+    }
+}
+
+impl<'a> ByteReader for CompactReader<'a> {
+    fn read_u8(&mut self) -> Result<u8, DeserializationError> {
+        self.0.read_u8()
+    }
+
+    fn read_u16(&mut self) -> Result<u16, DeserializationError> {
+        let value = self.read_varint()?;
+        if value > u16::MAX as u32 {
+            return Err(DeserializationError::InvalidValue(format!(
+                "variable-length value must fit into 16 bits, but was {}",
+                value
+            )));
+        }
+        Ok(value as u16)
+    }
+
+    fn read_u32(&mut self) -> Result<u32, DeserializationError> {
+        self.read_varint()
+    }
+
+    fn read_u64(&mut self) -> Result<u64, DeserializationError> {
+        self.0.read_u64()
+    }
+
+    fn read_u128(&mut self) -> Result<u128, DeserializationError> {
+        self.0.read_u128()
+    }
+
+    fn read_u8_vec(&mut self, len: usize) -> Result<Vec<u8>, DeserializationError> {
+        self.0.read_u8_vec(len)
+    }
+
+    fn read_u8_array<const N: usize>(&mut self) -> Result<[u8; N], DeserializationError> {
+        self.0.read_u8_array()
+    }
+
+    fn has_more_bytes(&self) -> bool {
+        self.0.has_more_bytes()
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{CompactReader, CompactWriter};
+    use utils::{ByteReader, ByteWriter};
+
+    #[test]
+    fn compact_values() {
+        let mut writer = CompactWriter::new();
+        writer.write_u16(0);
+        writer.write_u16(127);
+        writer.write_u16(128);
+        writer.write_u16(u16::MAX);
+        writer.write_u32(u32::MAX);
+        writer.write_u64(1);
+        let bytes = writer.into_bytes();
+        assert_eq!(1 + 1 + 2 + 3 + 5 + 8, bytes.len());
+
+        let mut reader = CompactReader::new(&bytes);
+        assert_eq!(0, reader.read_u16().unwrap());
+        assert_eq!(127, reader.read_u16().unwrap());
+        assert_eq!(128, reader.read_u16().unwrap());
+        assert_eq!(u16::MAX, reader.read_u16().unwrap());
+        assert_eq!(u32::MAX, reader.read_u32().unwrap());
+        assert_eq!(1, reader.read_u64().unwrap());
+        assert!(!reader.has_more_bytes());
+    }
+
+    #[test]
+    fn compact_values_invalid() {
+        // values which do not fit into the expected type
+        let mut writer = CompactWriter::new();
+        writer.write_u32(u16::MAX as u32 + 1);
+        assert!(CompactReader::new(&writer.into_bytes()).read_u16().is_err());
+        assert!(CompactReader::new(&[0xff, 0xff, 0xff, 0xff, 0x10])
+            .read_u32()
+            .is_err());
+        assert!(CompactReader::new(&[0x80, 0x80, 0x80, 0x80, 0x80, 0x01])
+            .read_u32()
+            .is_err());
+
+        // values which are not encoded in the shortest form
+        assert!(CompactReader::new(&[0x80, 0x00]).read_u16().is_err());
+        assert!(CompactReader::new(&[0x81, 0x00]).read_u32().is_err());
+
+        // truncated values
+        assert!(CompactReader::new(&[0x80]).read_u16().is_err());
+    }
+}
@@ -3,6 +3,7 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use super::{get_conjectured_security, get_proven_security};
 use crate::{ProofOptions, TraceInfo};
 use math::{log2, StarkField};
 use utils::{
@@ -17,6 +18,7 @@ use utils::{
 pub struct Context {
     trace_width: u8,
     trace_length: u8, // stored as power of two
+    num_padding_rows: u32,
     trace_meta: Vec<u8>,
     field_modulus_bytes: Vec<u8>,
     options: ProofOptions,
@@ -31,6 +33,7 @@ impl Context {
         Context {
             trace_width: trace_info.width() as u8,
             trace_length: log2(trace_info.length()) as u8,
+            num_padding_rows: (trace_info.length() - trace_info.unpadded_length()) as u32,
             trace_meta: trace_info.meta().to_vec(),
             field_modulus_bytes: B::get_modulus_le_bytes(),
             options,
@@ -45,6 +48,12 @@ impl Context {
         2_usize.pow(self.trace_length as u32)
     }
 
+    /// Returns the number of meaningful rows in the execution trace of the computation described
+    /// by this context, not counting the rows which were added as padding.
+    pub fn unpadded_trace_length(&self) -> usize {
+        self.trace_length() - self.num_padding_rows as usize
+    }
+
     /// Returns execution trace width of the computation described by this context.
     pub fn trace_width(&self) -> usize {
         self.trace_width as usize
@@ -57,6 +66,7 @@ impl Context {
             self.trace_length(),
             self.trace_meta.clone(),
         )
+        .with_unpadded_length(self.unpadded_trace_length())
     }
 
     /// Returns the size of the LDE domain for the computation described by this context.
@@ -90,6 +100,19 @@ impl Context {
     pub fn options(&self) -> &ProofOptions {
         &self.options
     }
+
+    /// Returns security level (in bits) of a proof generated in this context.
+    ///
+    /// See [StarkProof::security_level()](super::StarkProof::security_level) for more info.
+    pub fn security_level(&self, conjectured: bool) -> u32 {
+        let base_field_bits = self.num_modulus_bits();
+        let lde_domain_size = self.lde_domain_size() as u64;
+        if conjectured {
+            get_conjectured_security(&self.options, base_field_bits, lde_domain_size)
+        } else {
+            get_proven_security(&self.options, base_field_bits, lde_domain_size)
+        }
+    }
 }
 
 impl Serializable for Context {
@@ -97,6 +120,7 @@ impl Serializable for Context {
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
         target.write_u8(self.trace_width);
         target.write_u8(self.trace_length);
+        target.write_u32(self.num_padding_rows);
         target.write_u16(self.trace_meta.len() as u16);
         target.write_u8_slice(&self.trace_meta);
         assert!(self.field_modulus_bytes.len() < u8::MAX as usize);
@@ -112,8 +136,25 @@ impl Deserializable for Context {
     /// # Errors
     /// Returns an error of a valid Context struct could not be read from the specified `source`.
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
-        // read and validate trace width
         let trace_width = source.read_u8()?;
+        Self::read_after_trace_width(trace_width, source)
+    }
+}
+
+impl Context {
+    /// Reads the remainder of a proof context from the specified `source` given the trace width
+    /// which has already been read from it.
+    ///
+    /// This is used to parse proofs serialized without a version header, in which the first byte
+    /// of the proof is the trace width.
+    ///
+    /// # Errors
+    /// Returns an error of a valid Context struct could not be read from the specified `source`.
+    pub(super) fn read_after_trace_width<R: ByteReader>(
+        trace_width: u8,
+        source: &mut R,
+    ) -> Result<Self, DeserializationError> {
+        // validate trace width
         if trace_width == 0 {
             return Err(DeserializationError::InvalidValue(
                 "trace width must be greater than zero".to_string(),
@@ -127,8 +168,16 @@ impl Deserializable for Context {
             )));
         }
 
-        // read and validate trace length
+        // read and validate trace length; the length is serialized as log2 of its value, and must
+        // fit into usize
         let trace_length = source.read_u8()?;
+        if trace_length as u32 >= usize::BITS {
+            return Err(DeserializationError::InvalidValue(format!(
+                "Trace length cannot be greater than 2^{}, but had 2^{}",
+                usize::BITS - 1,
+                trace_length
+            )));
+        }
         if 2_usize.pow(trace_length as u32) < TraceInfo::MIN_TRACE_LENGTH {
             return Err(DeserializationError::InvalidValue(format!(
                 "Trace length cannot be smaller than {}, but had {}",
@@ -137,6 +186,17 @@ impl Deserializable for Context {
             )));
         }
 
+        // read and validate the number of padding rows; at least one row of the trace must
+        // not be padding
+        let num_padding_rows = source.read_u32()?;
+        if num_padding_rows as usize >= 2_usize.pow(trace_length as u32) {
+            return Err(DeserializationError::InvalidValue(format!(
+                "Number of padding rows must be smaller than trace length {}, but had {}",
+                2_usize.pow(trace_length as u32),
+                num_padding_rows
+            )));
+        }
+
         // read trace metadata
         let num_meta_bytes = source.read_u16()? as usize;
         let trace_meta = if num_meta_bytes != 0 {
@@ -154,12 +214,21 @@ impl Deserializable for Context {
         }
         let field_modulus_bytes = source.read_u8_vec(num_modulus_bytes)?;
 
-        // read options
+        // read options and make sure the size of the LDE domain fits into usize
         let options = ProofOptions::read_from(source)?;
+        let lde_domain_size_log2 = trace_length as u32 + log2(options.blowup_factor());
+        if lde_domain_size_log2 >= usize::BITS {
+            return Err(DeserializationError::InvalidValue(format!(
+                "LDE domain size cannot be greater than 2^{}, but had 2^{}",
+                usize::BITS - 1,
+                lde_domain_size_log2
+            )));
+        }
 
         Ok(Context {
             trace_width,
             trace_length,
+            num_padding_rows,
             trace_meta,
             field_modulus_bytes,
             options,
@@ -0,0 +1,89 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{Commitments, Context};
+use crate::{ProofOptions, TraceInfo};
+
+// PROOF HEADER
+// ================================================================================================
+/// The beginning of a serialized STARK proof decoded via [StarkProof::peek_header()]: the
+/// version of the serialization format, the context of the proof, and the commitments made by
+/// the prover.
+///
+/// A header is intended for routing proofs (e.g., by the computation they attest to, by their
+/// size, or by the parameters they were generated with) before they are parsed in full. Since
+/// the rest of the proof is not read, a proof with a valid header may still be malformed.
+///
+/// [StarkProof::peek_header()]: super::StarkProof::peek_header
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofHeader {
+    version: u8,
+    context: Context,
+    commitments: Commitments,
+    proof_size: usize,
+}
+
+impl ProofHeader {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new header for a proof of the specified size (in bytes).
+    pub(super) fn new(
+        version: u8,
+        context: Context,
+        commitments: Commitments,
+        proof_size: usize,
+    ) -> Self {
+        ProofHeader {
+            version,
+            context,
+            commitments,
+            proof_size,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the version of the serialization format of the proof; proofs without a version
+    /// header are reported as proofs of version 0.
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
+    /// Returns the context of the proof.
+    pub fn context(&self) -> &Context {
+        &self.context
+    }
+
+    /// Returns STARK protocol parameters used to generate the proof.
+    pub fn options(&self) -> &ProofOptions {
+        self.context.options()
+    }
+
+    /// Returns execution trace info for the computation attested to by the proof.
+    pub fn get_trace_info(&self) -> TraceInfo {
+        self.context.get_trace_info()
+    }
+
+    /// Returns the length of the execution trace for the computation attested to by the proof.
+    pub fn trace_length(&self) -> usize {
+        self.context.trace_length()
+    }
+
+    /// Returns the width of the execution trace for the computation attested to by the proof.
+    pub fn trace_width(&self) -> usize {
+        self.context.trace_width()
+    }
+
+    /// Returns commitments made by the prover during the commit phase of the protocol.
+    pub fn commitments(&self) -> &Commitments {
+        &self.commitments
+    }
+
+    /// Returns the size of the entire serialized proof (in bytes).
+    pub fn proof_size(&self) -> usize {
+        self.proof_size
+    }
+}
@@ -0,0 +1,154 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::Context;
+use utils::DeserializationError;
+
+// PROOF LIMITS
+// ================================================================================================
+/// Defines limits on the size and dimensions of a proof which are enforced while the proof is
+/// deserialized via [StarkProof::from_bytes_with_limits()](super::StarkProof::from_bytes_with_limits).
+///
+/// A proof received from an untrusted source may specify dimensions which are valid, but which
+/// would make the verifier allocate much more memory than the verifier is willing to spend on a
+/// single proof. Limits make it possible to reject such proofs before any memory is allocated
+/// for them:
+///
+/// * The size of the serialized proof is checked before the proof is parsed. Since every value
+///   is read from the serialized proof, this also bounds the total amount of memory allocated
+///   while the proof is parsed.
+/// * The trace width and the number of queries are checked as soon as the proof context is read,
+///   and thus, before any queries are parsed. These values also bound the amount of memory the
+///   verifier allocates while verifying the proof.
+/// * The number of FRI layers is checked as soon as the FRI proof is read.
+///
+/// Limits are built starting from [new()](ProofLimits::new), which imposes no limits beyond the
+/// ones imposed by the proof format; further limits can then be added via `with_*()` methods.
+/// For example:
+///
+/// ```
+/// # use winter_air::proof::ProofLimits;
+/// let limits = ProofLimits::new()
+///     .with_max_proof_size(1 << 20)
+///     .with_max_trace_width(64)
+///     .with_max_num_queries(64)
+///     .with_max_fri_layers(16);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProofLimits {
+    max_proof_size: usize,
+    max_trace_width: usize,
+    max_num_queries: usize,
+    max_fri_layers: usize,
+}
+
+impl ProofLimits {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+    /// Returns limits which do not restrict proofs beyond the restrictions imposed by the proof
+    /// format.
+    pub fn new() -> Self {
+        ProofLimits {
+            max_proof_size: usize::MAX,
+            max_trace_width: usize::MAX,
+            max_num_queries: usize::MAX,
+            max_fri_layers: usize::MAX,
+        }
+    }
+
+    // LIMITS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns these limits updated to reject proofs which are serialized into more than the
+    /// specified number of bytes.
+    pub fn with_max_proof_size(mut self, num_bytes: usize) -> Self {
+        self.max_proof_size = num_bytes;
+        self
+    }
+
+    /// Returns these limits updated to reject proofs for execution traces with more than the
+    /// specified number of columns.
+    pub fn with_max_trace_width(mut self, trace_width: usize) -> Self {
+        self.max_trace_width = trace_width;
+        self
+    }
+
+    /// Returns these limits updated to reject proofs generated with more than the specified
+    /// number of queries.
+    pub fn with_max_num_queries(mut self, num_queries: usize) -> Self {
+        self.max_num_queries = num_queries;
+        self
+    }
+
+    /// Returns these limits updated to reject proofs with more than the specified number of FRI
+    /// layers (not counting the remainder).
+    pub fn with_max_fri_layers(mut self, num_layers: usize) -> Self {
+        self.max_fri_layers = num_layers;
+        self
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the maximum size of a serialized proof (in bytes).
+    pub fn max_proof_size(&self) -> usize {
+        self.max_proof_size
+    }
+
+    /// Returns the maximum width of an execution trace.
+    pub fn max_trace_width(&self) -> usize {
+        self.max_trace_width
+    }
+
+    /// Returns the maximum number of queries.
+    pub fn max_num_queries(&self) -> usize {
+        self.max_num_queries
+    }
+
+    /// Returns the maximum number of FRI layers.
+    pub fn max_fri_layers(&self) -> usize {
+        self.max_fri_layers
+    }
+
+    // CHECKS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns an error if a proof serialized into the specified number of bytes exceeds these
+    /// limits.
+    pub(super) fn check_proof_size(&self, num_bytes: usize) -> Result<(), DeserializationError> {
+        check_limit("proof size", num_bytes, self.max_proof_size)
+    }
+
+    /// Returns an error if dimensions of a proof with the specified context exceed these limits.
+    pub(super) fn check_context(&self, context: &Context) -> Result<(), DeserializationError> {
+        check_limit("trace width", context.trace_width(), self.max_trace_width)?;
+        check_limit(
+            "number of queries",
+            context.options().num_queries(),
+            self.max_num_queries,
+        )
+    }
+
+    /// Returns an error if the specified number of FRI layers exceeds these limits.
+    pub(super) fn check_fri_layers(&self, num_layers: usize) -> Result<(), DeserializationError> {
+        check_limit("number of FRI layers", num_layers, self.max_fri_layers)
+    }
+}
+
+impl Default for ProofLimits {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn check_limit(name: &'static str, value: usize, limit: usize) -> Result<(), DeserializationError> {
+    if value > limit {
+        return Err(DeserializationError::LimitExceeded(name, value, limit));
+    }
+    Ok(())
+}
@@ -5,12 +5,19 @@
 
 //! Contains STARK proof struct and associated components.
 
-use crate::{ProofOptions, TraceInfo};
-use core::cmp;
+use crate::{HashFunction, ProofOptions, TraceInfo};
+use crypto::Hasher;
 use fri::FriProof;
-use math::log2;
 use utils::{
-    collections::Vec, ByteReader, Deserializable, DeserializationError, Serializable, SliceReader,
+    cbor::{CborReader, CborWriter},
+    collections::Vec,
+    ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable, SliceReader,
+};
+
+#[cfg(feature = "serde")]
+use utils::{
+    serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer},
+    serde_adapter,
 };
 
 mod context;
@@ -25,10 +32,38 @@ pub use queries::Queries;
 mod ood_frame;
 pub use ood_frame::OodFrame;
 
+mod limits;
+pub use limits::ProofLimits;
+
+mod security;
+pub(crate) use security::{get_conjectured_security, get_proven_security};
+
+mod compression;
+use compression::{CompactReader, CompactWriter};
+
+mod size_report;
+pub use size_report::ProofSizeReport;
+
+mod chunks;
+pub use chunks::{ProofChunk, ProofReassembler};
+
+mod proof_ref;
+pub use proof_ref::StarkProofRef;
+
+mod header;
+pub use header::ProofHeader;
+
+#[cfg(test)]
+mod tests;
+
 // CONSTANTS
 // ================================================================================================
 
-const GRINDING_CONTRIBUTION_FLOOR: u32 = 80;
+/// The first byte of a version header; proof contexts never start with this byte.
+const VERSION_MARKER: u8 = 0;
+
+/// Bit of the version in the header which indicates that the proof is compressed.
+const COMPRESSED_FLAG: u8 = 0x80;
 
 // STARK PROOF
 // ================================================================================================
@@ -42,6 +77,18 @@ const GRINDING_CONTRIBUTION_FLOOR: u32 = 80;
 /// function, and deserialized from a sequence of bytes using [from_bytes()](StarkProof::from_bytes)
 /// function.
 ///
+/// Serialized proofs start with a version header, which consists of a zero byte followed by the
+/// [VERSION](StarkProof::VERSION) of the serialization format, the identifier of the hash
+/// function, and the modulus of the base field used to generate the proof. Proofs serialized
+/// before the header was introduced start directly with the proof context (whose first byte, the
+/// trace width, is never zero); such proofs are parsed as proofs of version 0. Thus, verifiers
+/// can be upgraded ahead of the provers which send proofs to them, and proofs of a version newer
+/// than the one supported by a verifier are rejected with
+/// [UnsupportedVersion](DeserializationError::UnsupportedVersion) error.
+///
+/// Proofs of versions older than the current version can be read only when the crate is compiled
+/// with `legacy-proofs` feature enabled (which is the default).
+///
 /// To estimate soundness of a proof (in bits), [security_level()](StarkProof::security_level)
 /// function can be used.
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -50,11 +97,15 @@ pub struct StarkProof {
     pub context: Context,
     /// Commitments made by the prover during the commit phase of the protocol.
     pub commitments: Commitments,
-    /// Decommitments of extended execution trace values at positions queried by the verifier.
-    pub trace_queries: Queries,
+    /// Decommitments of extended execution trace values at positions queried by the verifier;
+    /// one set of queries for each trace segment.
+    pub trace_queries: Vec<Queries>,
     /// Decommitments of constraint composition polynomial evaluations at positions queried by
     /// the verifier.
     pub constraint_queries: Queries,
+    /// Decommitments of committed public columns at positions queried by the verifier; one set
+    /// of queries for each [CommittedColumn](crate::CommittedColumn) defined by the AIR.
+    pub committed_column_queries: Vec<Queries>,
     /// Trace and constraint polynomial evaluations at an out-of-domain point.
     pub ood_frame: OodFrame,
     /// Low-degree proof for a DEEP composition polynomial.
@@ -64,6 +115,23 @@ pub struct StarkProof {
 }
 
 impl StarkProof {
+    // CONSTANTS
+    // --------------------------------------------------------------------------------------------
+
+    /// Version of the serialization format written by [to_bytes()](StarkProof::to_bytes) and
+    /// [to_streaming_bytes()](StarkProof::to_streaming_bytes) functions; this is also the latest
+    /// version which can be read.
+    ///
+    /// The versions are as follows:
+    /// * Version 0: proofs without a version header.
+    /// * Version 1: proofs with a version header; otherwise the same layout as version 0.
+    /// * Version 2: the version header additionally contains the identifier of the hash function
+    ///   and the modulus of the base field; otherwise the same layout as version 1.
+    pub const VERSION: u8 = 2;
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
     /// Returns STARK protocol parameters used to generate this proof.
     pub fn options(&self) -> &ProofOptions {
         self.context.options()
@@ -97,16 +165,46 @@ impl StarkProof {
     /// security level is returned. Usually, the number of queries needed for provable security is
     /// 2x - 3x higher than the number of queries needed for conjectured security at the same
     /// security level.
+    ///
+    /// Both estimates take into account the size of the base field and the degree of its
+    /// extension, the number of queries, the blowup factor, the grinding factor, and collision
+    /// resistance of the hash function used to generate this proof.
     pub fn security_level(&self, conjectured: bool) -> u32 {
-        if conjectured {
-            get_conjectured_security(
-                self.context.options(),
-                self.context.num_modulus_bits(),
-                self.lde_domain_size() as u64,
-            )
-        } else {
-            // TODO: implement provable security estimation
-            unimplemented!("proven security estimation has not been implement yet")
+        self.context.security_level(conjectured)
+    }
+
+    // SIZE REPORT
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a breakdown of the size of this proof serialized via [to_bytes()](Self::to_bytes)
+    /// by proof component.
+    ///
+    /// This can be used to find out which components of the proof are affected the most by
+    /// changes to proof options.
+    pub fn size_report(&self) -> ProofSizeReport {
+        let mut header = Vec::new();
+        self.write_header_into(&mut header, Self::VERSION);
+        // +1 for the number of trace segments and the number of committed columns
+        let trace_queries = 1 + self.trace_queries.iter().map(queries_size).sum::<usize>();
+        let committed_column_queries = 1 + self
+            .committed_column_queries
+            .iter()
+            .map(queries_size)
+            .sum::<usize>();
+        let fri_layers = self.fri_proof.layers_size();
+        let fri_remainder = self.fri_proof.remainder_size();
+
+        ProofSizeReport {
+            context: header.len() + self.context.to_bytes().len(),
+            commitments: self.commitments.to_bytes().len(),
+            trace_queries,
+            constraint_queries: queries_size(&self.constraint_queries),
+            committed_column_queries,
+            ood_frame: self.ood_frame.to_bytes().len(),
+            fri_layers,
+            fri_remainder,
+            fri_other: self.fri_proof.size() - fri_layers - fri_remainder,
+            pow_nonce: 8,
         }
     }
 
@@ -116,65 +214,510 @@ impl StarkProof {
     /// Serializes this proof into a vector of bytes.
     pub fn to_bytes(&self) -> Vec<u8> {
         let mut result = Vec::new();
+        self.write_into(&mut result, Self::VERSION);
+        result
+    }
+
+    /// Serializes this proof into the specified `target` using the same layout as
+    /// [to_bytes()](Self::to_bytes).
+    ///
+    /// This can be used to write a proof directly into a file or a socket (e.g., via
+    /// `WriteAdapter` of the utils crate) without buffering the entire serialized proof in
+    /// memory first.
+    pub fn write_to<W: ByteWriter>(&self, target: &mut W) {
+        self.write_into(target, Self::VERSION);
+    }
+
+    /// Serializes this proof into a vector of bytes using a compressed layout.
+    ///
+    /// The compressed layout is the same as the layout produced by [to_bytes()](Self::to_bytes),
+    /// except that all lengths and counts which are otherwise serialized as 16-bit or 32-bit
+    /// values are serialized using variable-length encoding (LEB128), and thus, usually take 1 - 3
+    /// bytes each. Merkle authentication paths are already serialized in a batched form (with
+    /// shared nodes included only once), and queried values are close to uniformly random
+    /// field elements; thus, no further compression is applied to them.
+    ///
+    /// A compressed proof can be read only via [from_bytes_compressed()](Self::from_bytes_compressed)
+    /// function; this is indicated by the most significant bit of the version in the header.
+    pub fn to_bytes_compressed(&self) -> Vec<u8> {
+        let mut writer = CompactWriter::new();
+        self.write_into(&mut writer, Self::VERSION | COMPRESSED_FLAG);
+        writer.into_bytes()
+    }
+
+    /// Serializes this proof into a vector of bytes in the order in which the values are read
+    /// by the verifier.
+    ///
+    /// Unlike [to_bytes()](StarkProof::to_bytes), which places the out-of-domain frame and the
+    /// proof-of-work nonces after the queries, this layout places all values needed to draw
+    /// the verifier's challenges before the queries, and FRI layer queries after the trace and
+    /// constraint queries. Thus, a proof serialized this way can be verified as it is being read
+    /// (e.g., via `verify_from_reader()` function of the verifier crate) without buffering the
+    /// entire proof first. The layout is as follows:
+    ///
+    /// 1. Version header and proof context.
+    /// 2. Trace, constraint, and FRI layer commitments.
+    /// 3. Out-of-domain evaluation frame.
+    /// 4. FRI layer proof-of-work nonces and the number of FRI partitions.
+    /// 5. Query seed proof-of-work nonce.
+    /// 6. Trace queries (preceded by the number of trace segments), constraint queries, and
+    ///    committed column queries (preceded by the number of committed columns).
+    /// 7. FRI layer queries and the FRI remainder.
+    pub fn to_streaming_bytes(&self) -> Vec<u8> {
+        let mut result = Vec::new();
+        self.write_header_into(&mut result, Self::VERSION);
         self.context.write_into(&mut result);
         self.commitments.write_into(&mut result);
-        self.trace_queries.write_into(&mut result);
-        self.constraint_queries.write_into(&mut result);
         self.ood_frame.write_into(&mut result);
-        self.fri_proof.write_into(&mut result);
+        self.fri_proof.write_commit_phase_into(&mut result);
         result.extend_from_slice(&self.pow_nonce.to_le_bytes());
+        result.push(self.trace_queries.len() as u8);
+        for queries in self.trace_queries.iter() {
+            queries.write_into(&mut result);
+        }
+        self.constraint_queries.write_into(&mut result);
+        result.push(self.committed_column_queries.len() as u8);
+        for queries in self.committed_column_queries.iter() {
+            queries.write_into(&mut result);
+        }
+        self.fri_proof.write_query_phase_into(&mut result);
         result
     }
 
+    /// Serializes this proof into a vector of bytes using canonical CBOR encoding.
+    ///
+    /// Unlike [to_bytes()](Self::to_bytes), the top-level structure of the encoding does not
+    /// depend on the internal layout of the proof: the proof is encoded as a CBOR map with
+    /// unsigned integer keys written in ascending order, all integers and lengths are encoded in
+    /// the shortest possible form, and all values have definite lengths (see
+    /// [utils::cbor] for more info). The map contains the following entries:
+    ///
+    /// | Key | Value                                                                    |
+    /// | --- | ------------------------------------------------------------------------ |
+    /// | 0   | Proof version as an unsigned integer.                                    |
+    /// | 1   | Hash function as an unsigned integer.                                    |
+    /// | 2   | Modulus of the base field as a byte string.                              |
+    /// | 3   | Proof context as a byte string.                                          |
+    /// | 4   | Trace, constraint, and FRI layer commitments as a byte string.           |
+    /// | 5   | Array of trace queries, one byte string for each trace segment.          |
+    /// | 6   | Constraint queries as a byte string.                                     |
+    /// | 7   | Array of committed column queries, one byte string for each column.      |
+    /// | 8   | Out-of-domain evaluation frame as a byte string.                         |
+    /// | 9   | FRI proof as a byte string.                                              |
+    /// | 10  | Query seed proof-of-work nonce as an unsigned integer.                   |
+    ///
+    /// Byte strings contain binary serializations of the respective proof components; field
+    /// elements and digests in these serializations are always encoded using a fixed number of
+    /// bytes. Since every proof has exactly one such encoding, the encoding can be hashed to
+    /// obtain an identifier of the proof.
+    pub fn to_cbor(&self) -> Vec<u8> {
+        let mut writer = CborWriter::new();
+        writer.write_map_header(11);
+        writer.write_uint(0);
+        writer.write_uint(Self::VERSION as u64);
+        writer.write_uint(1);
+        writer.write_uint(self.options().hash_fn() as u64);
+        writer.write_uint(2);
+        writer.write_bytes(self.context.field_modulus_bytes());
+        writer.write_uint(3);
+        writer.write_serializable(&self.context);
+        writer.write_uint(4);
+        writer.write_serializable(&self.commitments);
+        writer.write_uint(5);
+        writer.write_array_header(self.trace_queries.len());
+        for queries in self.trace_queries.iter() {
+            writer.write_serializable(queries);
+        }
+        writer.write_uint(6);
+        writer.write_serializable(&self.constraint_queries);
+        writer.write_uint(7);
+        writer.write_array_header(self.committed_column_queries.len());
+        for queries in self.committed_column_queries.iter() {
+            writer.write_serializable(queries);
+        }
+        writer.write_uint(8);
+        writer.write_serializable(&self.ood_frame);
+        writer.write_uint(9);
+        writer.write_serializable(&self.fri_proof);
+        writer.write_uint(10);
+        writer.write_uint(self.pow_nonce);
+        writer.into_bytes()
+    }
+
+    /// Serializes this proof via [to_bytes()](Self::to_bytes) and splits the result into chunks
+    /// carrying at most `chunk_size` bytes of the proof each.
+    ///
+    /// The digest of the proof included in every chunk is computed using the hash function `H`;
+    /// the chunks can be reassembled into a proof via [ProofReassembler].
+    ///
+    /// # Panics
+    /// Panics if `chunk_size` is zero.
+    pub fn to_chunks<H: Hasher>(&self, chunk_size: usize) -> Vec<ProofChunk> {
+        ProofChunk::split::<H>(&self.to_bytes(), chunk_size)
+    }
+
     /// Returns a STARK proof read from the specified `source`.
     ///
     /// # Errors
     /// Returns an error of a valid STARK proof could not be read from the specified `source`.
     pub fn from_bytes(source: &[u8]) -> Result<Self, DeserializationError> {
-        let mut source = SliceReader::new(source);
+        Self::from_bytes_with_limits(source, &ProofLimits::new())
+    }
+
+    /// Returns a STARK proof read from the specified `source` if the proof does not exceed the
+    /// specified `limits`.
+    ///
+    /// The size of the `source` is checked before the proof is parsed, and the dimensions of the
+    /// proof are checked as soon as they are read; thus, a proof exceeding the limits is rejected
+    /// before memory is allocated for the values it contains. See [ProofLimits] for more info.
+    ///
+    /// # Errors
+    /// Returns [LimitExceeded](DeserializationError::LimitExceeded) error if the proof exceeds
+    /// any of the `limits`, or another error if a valid STARK proof could not be read from the
+    /// specified `source`.
+    pub fn from_bytes_with_limits(
+        source: &[u8],
+        limits: &ProofLimits,
+    ) -> Result<Self, DeserializationError> {
+        limits.check_proof_size(source.len())?;
+        Self::read_from(&mut SliceReader::new(source), false, limits)
+    }
+
+    /// Returns a STARK proof read from the specified `source` serialized via
+    /// [to_bytes_compressed()](Self::to_bytes_compressed) function, if the proof does not exceed
+    /// the specified `limits`.
+    ///
+    /// The size limit applies to the size of the compressed proof.
+    ///
+    /// # Errors
+    /// Returns [LimitExceeded](DeserializationError::LimitExceeded) error if the proof exceeds
+    /// any of the `limits`, or another error if a valid compressed STARK proof could not be read
+    /// from the specified `source`.
+    pub fn from_bytes_compressed(
+        source: &[u8],
+        limits: &ProofLimits,
+    ) -> Result<Self, DeserializationError> {
+        limits.check_proof_size(source.len())?;
+        Self::read_from(&mut CompactReader::new(source), true, limits)
+    }
+
+    /// Returns a STARK proof read from the specified `source` encoded via
+    /// [to_cbor()](Self::to_cbor) function, if the proof does not exceed the specified `limits`.
+    ///
+    /// Only canonical encodings of proofs of the current [VERSION](Self::VERSION) are accepted.
+    ///
+    /// # Errors
+    /// Returns [LimitExceeded](DeserializationError::LimitExceeded) error if the proof exceeds
+    /// any of the `limits`, or another error if a canonically encoded STARK proof could not be
+    /// read from the specified `source`.
+    pub fn from_cbor(source: &[u8], limits: &ProofLimits) -> Result<Self, DeserializationError> {
+        limits.check_proof_size(source.len())?;
+        let mut reader = CborReader::new(source);
+        if reader.read_map_header()? != 11 {
+            return Err(DeserializationError::InvalidValue(
+                "CBOR-encoded proof must be a map with 11 entries".into(),
+            ));
+        }
+
+        reader.read_map_key(0)?;
+        let version = reader.read_uint()?;
+        if version != Self::VERSION as u64 {
+            return Err(DeserializationError::InvalidValue(format!(
+                "CBOR-encoded proofs must be of version {}, but was {}",
+                Self::VERSION,
+                version
+            )));
+        }
+        reader.read_map_key(1)?;
+        let hash_fn = reader.read_uint()?;
+        reader.read_map_key(2)?;
+        let field_modulus_bytes = reader.read_bytes()?;
+        reader.read_map_key(3)?;
+        let context: Context = reader.read_deserializable()?;
+        if hash_fn != context.options().hash_fn() as u64 {
+            return Err(DeserializationError::InvalidValue(
+                "hash function of the proof does not match the proof context".into(),
+            ));
+        }
+        if field_modulus_bytes != context.field_modulus_bytes() {
+            return Err(DeserializationError::InvalidValue(
+                "field modulus of the proof does not match the proof context".into(),
+            ));
+        }
+        limits.check_context(&context)?;
+
+        reader.read_map_key(4)?;
+        let commitments = reader.read_deserializable()?;
+        reader.read_map_key(5)?;
+        let trace_queries = read_cbor_queries(&mut reader)?;
+        reader.read_map_key(6)?;
+        let constraint_queries = reader.read_deserializable()?;
+        reader.read_map_key(7)?;
+        let committed_column_queries = read_cbor_queries(&mut reader)?;
+        reader.read_map_key(8)?;
+        let ood_frame = reader.read_deserializable()?;
+        reader.read_map_key(9)?;
+        let fri_proof: FriProof = reader.read_deserializable()?;
+        limits.check_fri_layers(fri_proof.num_layers())?;
+        reader.read_map_key(10)?;
+        let pow_nonce = reader.read_uint()?;
+        if reader.has_more_bytes() {
+            return Err(DeserializationError::UnconsumedBytes);
+        }
+
+        Ok(StarkProof {
+            context,
+            commitments,
+            trace_queries,
+            constraint_queries,
+            committed_column_queries,
+            ood_frame,
+            fri_proof,
+            pow_nonce,
+        })
+    }
+
+    /// Decodes the version header, the context, and the commitments of a STARK proof serialized
+    /// via [to_bytes()](Self::to_bytes) into the specified `source`, without reading the rest
+    /// of the proof.
+    ///
+    /// This is much cheaper than reading the entire proof, and is intended for dispatching
+    /// proofs (e.g., by the trace info, the size, or the options of a proof) before they are
+    /// read in full and verified. Queries, the out-of-domain frame, and the FRI proof are not
+    /// parsed, and thus, a proof for which a header is returned may still be malformed; proofs
+    /// should be read via [from_bytes()](Self::from_bytes) (or
+    /// [StarkProofRef::new()](StarkProofRef::new)) before verification.
+    ///
+    /// # Errors
+    /// Returns an error if a valid version header, proof context, or commitments could not be
+    /// read from the specified `source`.
+    pub fn peek_header(source: &[u8]) -> Result<ProofHeader, DeserializationError> {
+        let mut reader = SliceReader::new(source);
+        let (version, context) = Self::read_header(&mut reader, false)?;
+        let commitments = reader.read_in_context("commitments", Commitments::read_from)?;
+        Ok(ProofHeader::new(
+            version,
+            context,
+            commitments,
+            source.len(),
+        ))
+    }
+
+    /// Reads the version header and the context of a proof from the specified `source`, and
+    /// returns the version of the proof together with its context.
+    ///
+    /// Proofs without a version header are reported as proofs of version 0. This can be used to
+    /// parse the beginning of a proof serialized via [to_streaming_bytes()](Self::to_streaming_bytes)
+    /// function.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The version of the proof is newer than [VERSION](Self::VERSION), or the version is older
+    ///   than [VERSION](Self::VERSION) and `legacy-proofs` feature is not enabled.
+    /// * A valid proof context could not be read from the specified `source`.
+    /// * The hash function or the base field modulus in the version header are not the same as
+    ///   in the proof context.
+    pub fn read_header_from<R: ByteReader>(
+        source: &mut R,
+    ) -> Result<(u8, Context), DeserializationError> {
+        Self::read_header(source, false)
+    }
+
+    // HELPER FUNCTIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Reads the version header and the context of a proof from the specified `source`; the
+    /// proof is expected to be compressed if `compressed` is true.
+    fn read_header<R: ByteReader>(
+        source: &mut R,
+        compressed: bool,
+    ) -> Result<(u8, Context), DeserializationError> {
+        let first_byte = source.read_u8()?;
+        if first_byte != VERSION_MARKER {
+            // proofs of version 0 have no header and start with the trace width
+            check_legacy_version(0)?;
+            let context = Context::read_after_trace_width(first_byte, source)?;
+            return Ok((0, context));
+        }
+
+        // version 0 is never written into a header; the most significant bit of the version
+        // indicates whether the proof is compressed, which is supported starting with version 2
+        let version = source.read_u8()?;
+        if (version & COMPRESSED_FLAG != 0) != compressed {
+            return Err(DeserializationError::InvalidValue(if compressed {
+                "proof is not compressed".into()
+            } else {
+                "compressed proofs must be read via from_bytes_compressed() function".into()
+            }));
+        }
+        let version = version & !COMPRESSED_FLAG;
+        if compressed && version < 2 {
+            return Err(DeserializationError::InvalidValue(format!(
+                "proofs of version {} cannot be compressed",
+                version
+            )));
+        }
+        if version == 0 {
+            return Err(DeserializationError::InvalidValue(
+                "proofs of version 0 cannot have a version header".into(),
+            ));
+        }
+        if version > Self::VERSION {
+            return Err(DeserializationError::UnsupportedVersion(
+                version,
+                Self::VERSION,
+            ));
+        }
+        if version == 1 {
+            check_legacy_version(1)?;
+            return Ok((version, Context::read_from(source)?));
+        }
+
+        // starting with version 2, the header identifies the hash function and the base field;
+        // these must be the same as in the context
+        let hash_fn = HashFunction::read_from(source)?;
+        let num_modulus_bytes = source.read_u8()? as usize;
+        let field_modulus_bytes = source.read_u8_vec(num_modulus_bytes)?;
+        let context = Context::read_from(source)?;
+        if hash_fn != context.options().hash_fn() {
+            return Err(DeserializationError::InvalidValue(
+                "hash function in the proof header does not match the proof context".into(),
+            ));
+        }
+        if field_modulus_bytes != context.field_modulus_bytes() {
+            return Err(DeserializationError::InvalidValue(
+                "field modulus in the proof header does not match the proof context".into(),
+            ));
+        }
+        Ok((version, context))
+    }
+
+    /// Reads a proof from the specified `source` after making sure it does not exceed the
+    /// specified `limits`; the proof is expected to be compressed if `compressed` is true.
+    fn read_from<R: ByteReader>(
+        source: &mut R,
+        compressed: bool,
+        limits: &ProofLimits,
+    ) -> Result<Self, DeserializationError> {
+        // the layout of the proof following the context is the same for all supported versions
+        let (_version, context) = Self::read_header(source, compressed)?;
+        limits.check_context(&context)?;
         let proof = StarkProof {
-            context: Context::read_from(&mut source)?,
-            commitments: Commitments::read_from(&mut source)?,
-            trace_queries: Queries::read_from(&mut source)?,
-            constraint_queries: Queries::read_from(&mut source)?,
-            ood_frame: OodFrame::read_from(&mut source)?,
-            fri_proof: FriProof::read_from(&mut source)?,
-            pow_nonce: source.read_u64()?,
+            context,
+            commitments: source.read_in_context("commitments", Commitments::read_from)?,
+            trace_queries: source.read_in_context("trace queries", |source| {
+                let num_segments = source.read_u8()? as usize;
+                Queries::read_batch_from(source, num_segments)
+            })?,
+            constraint_queries: source.read_in_context("constraint queries", Queries::read_from)?,
+            committed_column_queries: source.read_in_context(
+                "committed column queries",
+                |source| {
+                    let num_columns = source.read_u8()? as usize;
+                    Queries::read_batch_from(source, num_columns)
+                },
+            )?,
+            ood_frame: source.read_in_context("OOD frame", OodFrame::read_from)?,
+            fri_proof: {
+                let fri_proof = source.read_in_context("FRI proof", FriProof::read_from)?;
+                limits.check_fri_layers(fri_proof.num_layers())?;
+                fri_proof
+            },
+            pow_nonce: source.read_in_context("proof-of-work nonce", |source| source.read_u64())?,
         };
         if source.has_more_bytes() {
             return Err(DeserializationError::UnconsumedBytes);
         }
         Ok(proof)
     }
+
+    /// Writes this proof into `target` with the specified `version` in the header.
+    fn write_into<W: ByteWriter>(&self, target: &mut W, version: u8) {
+        self.write_header_into(target, version);
+        self.context.write_into(target);
+        self.commitments.write_into(target);
+        target.write_u8(self.trace_queries.len() as u8);
+        for queries in self.trace_queries.iter() {
+            queries.write_into(target);
+        }
+        self.constraint_queries.write_into(target);
+        target.write_u8(self.committed_column_queries.len() as u8);
+        for queries in self.committed_column_queries.iter() {
+            queries.write_into(target);
+        }
+        self.ood_frame.write_into(target);
+        self.fri_proof.write_into(target);
+        target.write_u64(self.pow_nonce);
+    }
+
+    /// Writes the version header with the specified `version` into `target`.
+    fn write_header_into<W: ByteWriter>(&self, target: &mut W, version: u8) {
+        target.write_u8(VERSION_MARKER);
+        target.write_u8(version);
+        self.options().hash_fn().write_into(target);
+        let field_modulus_bytes = self.context.field_modulus_bytes();
+        target.write_u8(field_modulus_bytes.len() as u8);
+        target.write_u8_slice(field_modulus_bytes);
+    }
 }
 
-// HELPER FUNCTIONS
-// ================================================================================================
+#[cfg(feature = "serde")]
+impl Serialize for StarkProof {
+    /// Serializes this proof into the `serializer` as bytes produced by
+    /// [to_bytes()](StarkProof::to_bytes).
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serde_adapter::serialize_bytes(&self.to_bytes(), serializer)
+    }
+}
 
-/// Computes conjectured security level for the specified proof parameters.
-fn get_conjectured_security(
-    options: &ProofOptions,
-    base_field_bits: u32,
-    lde_domain_size: u64,
-) -> u32 {
-    // compute max security we can get for a given field size
-    let field_size = base_field_bits * options.field_extension().degree();
-    let field_security = field_size - lde_domain_size.trailing_zeros();
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for StarkProof {
+    /// Reads a STARK proof from bytes read from the `deserializer` via
+    /// [from_bytes()](StarkProof::from_bytes).
+    ///
+    /// Proofs received from untrusted sources should be deserialized into bytes first, and then
+    /// read via [from_bytes_with_limits()](StarkProof::from_bytes_with_limits).
+    ///
+    /// # Errors
+    /// Returns an error if a valid STARK proof could not be read from the `deserializer`.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bytes = serde_adapter::deserialize_bytes(deserializer)?;
+        StarkProof::from_bytes(&bytes).map_err(D::Error::custom)
+    }
+}
 
-    // compute max security we can get for a given hash function
-    let hash_fn_security = options.hash_fn().collision_resistance();
+// HELPER FUNCTIONS
+// ================================================================================================
 
-    // compute security we get by executing multiple query rounds
-    let security_per_query = log2(options.blowup_factor());
-    let mut query_security = security_per_query * options.num_queries() as u32;
+/// Returns an error if proofs of the specified legacy `version` cannot be read because the crate
+/// was compiled without `legacy-proofs` feature.
+fn check_legacy_version(version: u8) -> Result<(), DeserializationError> {
+    if cfg!(feature = "legacy-proofs") {
+        Ok(())
+    } else {
+        Err(DeserializationError::UnsupportedVersion(
+            version,
+            StarkProof::VERSION,
+        ))
+    }
+}
 
-    // include grinding factor contributions only for proofs adequate security
-    if query_security >= GRINDING_CONTRIBUTION_FLOOR {
-        query_security += options.grinding_factor();
+/// Reads an array of queries encoded via [StarkProof::to_cbor()] from the specified `reader`.
+fn read_cbor_queries(reader: &mut CborReader) -> Result<Vec<Queries>, DeserializationError> {
+    let num_queries = reader.read_array_header()?;
+    if num_queries > u8::MAX as usize {
+        return Err(DeserializationError::InvalidValue(format!(
+            "number of query sets cannot exceed {}, but was {}",
+            u8::MAX,
+            num_queries
+        )));
     }
+    (0..num_queries)
+        .map(|_| reader.read_deserializable())
+        .collect()
+}
 
-    cmp::min(
-        cmp::min(field_security, query_security) - 1,
-        hash_fn_security,
-    )
+/// Returns the size of the specified `queries` serialized via [Serializable] trait.
+fn queries_size(queries: &Queries) -> usize {
+    queries.to_bytes().len()
 }
@@ -23,7 +23,7 @@ use utils::{
 ///
 /// Internally, the evaluations are stored as a sequence of bytes. Thus, to retrieve the
 /// evaluations, [parse()](OodFrame::parse) function should be used.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, Default)]
 pub struct OodFrame {
     trace_at_z1: Vec<u8>,
     trace_at_z2: Vec<u8>,
@@ -126,15 +126,20 @@ impl OodFrame {
 
         Ok((EvaluationFrame::from_rows(current, next), evaluations))
     }
-}
 
-impl Default for OodFrame {
-    fn default() -> Self {
-        OodFrame {
-            trace_at_z1: Vec::new(),
-            trace_at_z2: Vec::new(),
-            evaluations: Vec::new(),
-        }
+    // SERIALIZATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Advances the specified `source` past an OOD frame serialized via the [Serializable] trait
+    /// without reading it into memory.
+    ///
+    /// # Errors
+    /// Returns an error if the `source` does not contain enough bytes for the OOD frame.
+    pub(crate) fn skip_from<R: ByteReader>(source: &mut R) -> Result<(), DeserializationError> {
+        let trace_row_bytes = source.read_u16()? as usize;
+        source.skip_bytes(2 * trace_row_bytes)?;
+        let constraint_row_bytes = source.read_u16()? as usize;
+        source.skip_bytes(constraint_row_bytes)
     }
 }
 
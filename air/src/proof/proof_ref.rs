@@ -0,0 +1,253 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{Commitments, Context, OodFrame, ProofLimits, Queries, StarkProof};
+use crate::ProofOptions;
+use fri::FriProof;
+use utils::{collections::Vec, ByteReader, Deserializable, DeserializationError, SliceReader};
+
+// STARK PROOF REF
+// ================================================================================================
+/// A view of a STARK proof serialized via [StarkProof::to_bytes()] which borrows the serialized
+/// proof instead of copying it.
+///
+/// When a view is created via [new()](StarkProofRef::new), only the version header and the
+/// context of the proof are decoded; for all other components of the proof, the view records
+/// only where in the serialized proof they are located. A component is decoded when it is
+/// requested via the corresponding accessor (e.g., [ood_frame()](StarkProofRef::ood_frame)).
+/// Thus, a caller which needs only some of the components (e.g., to route a proof based on its
+/// context, or to check its commitments against a list of known roots) does not pay for copying
+/// the rest of the proof into memory.
+///
+/// The structure of the entire proof is validated when the view is created, and the proof is
+/// checked against the specified [ProofLimits] in the same way as in
+/// [StarkProof::from_bytes_with_limits()]; thus, a proof which is accepted by
+/// [new()](StarkProofRef::new) can always be converted into a [StarkProof] via
+/// [to_proof()](StarkProofRef::to_proof).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StarkProofRef<'a> {
+    version: u8,
+    context: Context,
+    commitments: &'a [u8],
+    trace_queries: Vec<&'a [u8]>,
+    constraint_queries: &'a [u8],
+    committed_column_queries: Vec<&'a [u8]>,
+    ood_frame: &'a [u8],
+    fri_proof: &'a [u8],
+    pow_nonce: u64,
+}
+
+impl<'a> StarkProofRef<'a> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a view of a STARK proof serialized in the specified `source` if the proof does
+    /// not exceed the specified `limits`.
+    ///
+    /// # Errors
+    /// Returns [LimitExceeded](DeserializationError::LimitExceeded) error if the proof exceeds
+    /// any of the `limits`, or another error if a valid STARK proof could not be read from the
+    /// specified `source`.
+    pub fn new(source: &'a [u8], limits: &ProofLimits) -> Result<Self, DeserializationError> {
+        limits.check_proof_size(source.len())?;
+        let mut reader = SliceReader::new(source);
+
+        // the layout of the proof following the context is the same for all supported versions
+        let (version, context) = StarkProof::read_header(&mut reader, false)?;
+        limits.check_context(&context)?;
+
+        let commitments = read_section(&mut reader, source, "commitments", Commitments::skip_from)?;
+        let num_segments = reader.read_u8()? as usize;
+        let trace_queries = (0..num_segments)
+            .map(|_| read_section(&mut reader, source, "trace queries", Queries::skip_from))
+            .collect::<Result<Vec<_>, _>>()?;
+        let constraint_queries = read_section(
+            &mut reader,
+            source,
+            "constraint queries",
+            Queries::skip_from,
+        )?;
+        let num_columns = reader.read_u8()? as usize;
+        let committed_column_queries = (0..num_columns)
+            .map(|_| {
+                read_section(
+                    &mut reader,
+                    source,
+                    "committed column queries",
+                    Queries::skip_from,
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let ood_frame = read_section(&mut reader, source, "OOD frame", OodFrame::skip_from)?;
+
+        let fri_start = reader.position();
+        let num_fri_layers = reader.read_in_context("FRI proof", FriProof::skip_from)?;
+        limits.check_fri_layers(num_fri_layers)?;
+        let fri_proof = &source[fri_start..reader.position()];
+
+        let pow_nonce = reader.read_u64()?;
+        if reader.has_more_bytes() {
+            return Err(DeserializationError::UnconsumedBytes);
+        }
+
+        Ok(StarkProofRef {
+            version,
+            context,
+            commitments,
+            trace_queries,
+            constraint_queries,
+            committed_column_queries,
+            ood_frame,
+            fri_proof,
+            pow_nonce,
+        })
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the version of the serialization format of the proof; proofs without a version
+    /// header are reported as proofs of version 0.
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
+    /// Returns the context of the proof.
+    pub fn context(&self) -> &Context {
+        &self.context
+    }
+
+    /// Returns STARK protocol parameters used to generate the proof.
+    pub fn options(&self) -> &ProofOptions {
+        self.context.options()
+    }
+
+    /// Returns the number of trace segments for which the proof contains queries.
+    pub fn num_trace_segments(&self) -> usize {
+        self.trace_queries.len()
+    }
+
+    /// Returns the number of committed public columns for which the proof contains queries.
+    pub fn num_committed_columns(&self) -> usize {
+        self.committed_column_queries.len()
+    }
+
+    /// Returns the proof-of-work nonce for query seed grinding.
+    pub fn pow_nonce(&self) -> u64 {
+        self.pow_nonce
+    }
+
+    // COMPONENT DECODING
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns commitments made by the prover during the commit phase of the protocol.
+    ///
+    /// # Errors
+    /// Returns an error if the commitments could not be decoded.
+    pub fn commitments(&self) -> Result<Commitments, DeserializationError> {
+        decode(self.commitments)
+    }
+
+    /// Returns decommitments of extended execution trace values for the trace segment at the
+    /// specified `index`.
+    ///
+    /// # Errors
+    /// Returns an error if the queries could not be decoded.
+    ///
+    /// # Panics
+    /// Panics if `index` is greater than or equal to the number of trace segments.
+    pub fn trace_queries(&self, index: usize) -> Result<Queries, DeserializationError> {
+        decode(self.trace_queries[index])
+    }
+
+    /// Returns decommitments of constraint composition polynomial evaluations.
+    ///
+    /// # Errors
+    /// Returns an error if the queries could not be decoded.
+    pub fn constraint_queries(&self) -> Result<Queries, DeserializationError> {
+        decode(self.constraint_queries)
+    }
+
+    /// Returns decommitments of the committed public column at the specified `index`.
+    ///
+    /// # Errors
+    /// Returns an error if the queries could not be decoded.
+    ///
+    /// # Panics
+    /// Panics if `index` is greater than or equal to the number of committed columns.
+    pub fn committed_column_queries(&self, index: usize) -> Result<Queries, DeserializationError> {
+        decode(self.committed_column_queries[index])
+    }
+
+    /// Returns trace and constraint polynomial evaluations at an out-of-domain point.
+    ///
+    /// # Errors
+    /// Returns an error if the OOD frame could not be decoded.
+    pub fn ood_frame(&self) -> Result<OodFrame, DeserializationError> {
+        decode(self.ood_frame)
+    }
+
+    /// Returns the low-degree proof for a DEEP composition polynomial.
+    ///
+    /// # Errors
+    /// Returns an error if the FRI proof could not be decoded.
+    pub fn fri_proof(&self) -> Result<FriProof, DeserializationError> {
+        decode(self.fri_proof)
+    }
+
+    /// Returns a STARK proof with all components of this view decoded.
+    ///
+    /// # Errors
+    /// Returns an error if any of the components could not be decoded.
+    pub fn to_proof(&self) -> Result<StarkProof, DeserializationError> {
+        Ok(StarkProof {
+            context: self.context.clone(),
+            commitments: self.commitments()?,
+            trace_queries: self
+                .trace_queries
+                .iter()
+                .map(|q| decode(q))
+                .collect::<Result<_, _>>()?,
+            constraint_queries: self.constraint_queries()?,
+            committed_column_queries: self
+                .committed_column_queries
+                .iter()
+                .map(|q| decode(q))
+                .collect::<Result<_, _>>()?,
+            ood_frame: self.ood_frame()?,
+            fri_proof: self.fri_proof()?,
+            pow_nonce: self.pow_nonce,
+        })
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Advances the `reader` past a single proof component with the specified name using the `skip`
+/// function, and returns the bytes of the component in the `source` the `reader` was created
+/// from.
+fn read_section<'a, F>(
+    reader: &mut SliceReader<'a>,
+    source: &'a [u8],
+    name: &'static str,
+    skip: F,
+) -> Result<&'a [u8], DeserializationError>
+where
+    F: FnOnce(&mut SliceReader<'a>) -> Result<(), DeserializationError>,
+{
+    let start = reader.position();
+    reader.read_in_context(name, skip)?;
+    Ok(&source[start..reader.position()])
+}
+
+/// Decodes a proof component from the specified `bytes` and makes sure all bytes were consumed.
+fn decode<D: Deserializable>(bytes: &[u8]) -> Result<D, DeserializationError> {
+    let mut reader = SliceReader::new(bytes);
+    let result = D::read_from(&mut reader)?;
+    if reader.has_more_bytes() {
+        return Err(DeserializationError::UnconsumedBytes);
+    }
+    Ok(result)
+}
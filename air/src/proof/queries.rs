@@ -6,8 +6,17 @@
 use crypto::{BatchMerkleProof, ElementHasher, Hasher};
 use math::{log2, FieldElement};
 use utils::{
-    collections::Vec, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
-    SliceReader,
+    collections::Vec, iter_mut, ByteReader, ByteWriter, Deserializable, DeserializationError,
+    Serializable, SliceReader,
+};
+
+#[cfg(feature = "concurrent")]
+use utils::iterators::*;
+
+#[cfg(feature = "serde")]
+use utils::{
+    serde::{Deserialize, Deserializer, Serialize, Serializer},
+    serde_adapter,
 };
 
 // QUERIES
@@ -119,18 +128,20 @@ impl Queries {
             )));
         }
 
-        let mut hashed_queries = vec![H::Digest::default(); num_queries];
+        // read bytes corresponding to each query and convert them into field elements
         let mut query_values = Vec::with_capacity(num_queries);
-
-        // read bytes corresponding to each query, convert them into field elements,
-        // and also hash them to build leaf nodes of the batch Merkle proof
         let mut reader = SliceReader::new(&self.values);
-        for query_hash in hashed_queries.iter_mut() {
-            let elements = E::read_batch_from(&mut reader, values_per_query)?;
-            *query_hash = H::hash_elements(&elements);
-            query_values.push(elements);
+        for _ in 0..num_queries {
+            query_values.push(E::read_batch_from(&mut reader, values_per_query)?);
         }
 
+        // hash the values of each query to build leaf nodes of the batch Merkle proof; when
+        // `concurrent` feature is enabled, the queries are hashed in multiple threads
+        let mut hashed_queries = vec![H::Digest::default(); num_queries];
+        iter_mut!(hashed_queries)
+            .zip(&query_values)
+            .for_each(|(query_hash, elements)| *query_hash = H::hash_elements(elements));
+
         // build batch Merkle proof
         let mut reader = SliceReader::new(&self.paths);
         let tree_depth = log2(domain_size) as u8;
@@ -141,6 +152,21 @@ impl Queries {
 
         Ok((merkle_proof, query_values))
     }
+
+    // SERIALIZATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Advances the specified `source` past queries serialized via the [Serializable] trait
+    /// without reading them into memory.
+    ///
+    /// # Errors
+    /// Returns an error if the `source` does not contain enough bytes for the queries.
+    pub(crate) fn skip_from<R: ByteReader>(source: &mut R) -> Result<(), DeserializationError> {
+        let num_value_bytes = source.read_u32()?;
+        source.skip_bytes(num_value_bytes as usize)?;
+        let num_paths_bytes = source.read_u32()?;
+        source.skip_bytes(num_paths_bytes as usize)
+    }
 }
 
 impl Serializable for Queries {
@@ -173,3 +199,22 @@ impl Deserializable for Queries {
         Ok(Queries { paths, values })
     }
 }
+
+#[cfg(feature = "serde")]
+impl Serialize for Queries {
+    /// Serializes `self` into the `serializer` as bytes of its binary serialization.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serde_adapter::serialize(self, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Queries {
+    /// Reads a query struct from bytes of its binary serialization read from the `deserializer`.
+    ///
+    /// # Errors
+    /// Returns an error if a valid query struct could not be read from the `deserializer`.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        serde_adapter::deserialize(deserializer)
+    }
+}
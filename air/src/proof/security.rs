@@ -0,0 +1,98 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::ProofOptions;
+use core::cmp;
+use math::log2;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Query security (in bits) below which grinding factor does not contribute to conjectured
+/// security of a proof.
+const GRINDING_CONTRIBUTION_FLOOR: u32 = 80;
+
+// SECURITY ESTIMATES
+// ================================================================================================
+
+/// Computes conjectured security level for the specified proof parameters.
+///
+/// The estimate relies on the conjectured soundness of FRI in the list-decoding regime; in this
+/// regime, each distinct query position contributes `log2(blowup_factor)` bits of security.
+pub(crate) fn get_conjectured_security(
+    options: &ProofOptions,
+    base_field_bits: u32,
+    lde_domain_size: u64,
+) -> u32 {
+    // compute max security we can get for a given field size
+    let field_size = base_field_bits * options.field_extension().degree();
+    let field_security = field_size.saturating_sub(lde_domain_size.trailing_zeros());
+
+    // compute max security we can get for a given hash function
+    let hash_fn_security = options.hash_fn().collision_resistance();
+
+    // compute security we get by executing multiple query rounds
+    let security_per_query = log2(options.blowup_factor());
+    let num_positions = options.expected_num_query_positions(lde_domain_size as usize);
+    let mut query_security = security_per_query * num_positions as u32;
+
+    // include grinding factor contributions only for proofs adequate security
+    if query_security >= GRINDING_CONTRIBUTION_FLOOR {
+        query_security += options.grinding_factor();
+    }
+
+    cmp::min(
+        cmp::min(field_security, query_security).saturating_sub(1),
+        hash_fn_security,
+    )
+}
+
+/// Computes provable security level for the specified proof parameters.
+///
+/// The estimate is a conservative one based on the soundness of FRI in the unique decoding
+/// regime. In this regime, proximity parameter is set to `(1 - rho) / 2`, where `rho` is the
+/// code rate (i.e., `1 / blowup_factor`), and thus each query contributes only
+/// `log2(2 * blowup_factor / (blowup_factor + 1))` bits of security. Errors introduced by
+/// sampling random values from the field are bounded by `lde_domain_size^2 / |F|`.
+pub(crate) fn get_proven_security(
+    options: &ProofOptions,
+    base_field_bits: u32,
+    lde_domain_size: u64,
+) -> u32 {
+    // compute max security we can get for a given field size
+    let field_size = base_field_bits * options.field_extension().degree();
+    let field_security = field_size.saturating_sub(2 * lde_domain_size.trailing_zeros());
+
+    // compute max security we can get for a given hash function
+    let hash_fn_security = options.hash_fn().collision_resistance();
+
+    // compute security we get by executing multiple query rounds; since queries are not
+    // expected to contribute full bits, the probability of each query catching a cheating
+    // prover is accumulated first and is converted to bits only at the end
+    let blowup_factor = options.blowup_factor() as f64;
+    let query_gain = 2.0 * blowup_factor / (blowup_factor + 1.0);
+    let mut query_error_inv = 1.0f64;
+    for _ in 0..options.expected_num_query_positions(lde_domain_size as usize) {
+        query_error_inv *= query_gain;
+    }
+    let query_security = floor_log2(query_error_inv) + options.grinding_factor();
+
+    cmp::min(
+        cmp::min(field_security, query_security).saturating_sub(1),
+        hash_fn_security,
+    )
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns floor(log2(value)) for a value which is greater than or equal to 1.
+///
+/// This reads the exponent directly from the binary representation of the value so that it
+/// can be used in no_std environments.
+fn floor_log2(value: f64) -> u32 {
+    debug_assert!(value >= 1.0, "value must be greater than or equal to 1");
+    (((value.to_bits() >> 52) & 0x7ff) as u32).saturating_sub(1023)
+}
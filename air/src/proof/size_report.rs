@@ -0,0 +1,85 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use core::fmt;
+
+// PROOF SIZE REPORT
+// ================================================================================================
+/// A breakdown of the size of a serialized STARK proof (in bytes) by proof component.
+///
+/// A report can be obtained via [StarkProof::size_report()](super::StarkProof::size_report)
+/// function. The sizes refer to the layout produced by
+/// [StarkProof::to_bytes()](super::StarkProof::to_bytes), and include the bytes used to encode
+/// lengths and counts of the respective components; thus, the sizes of all components add up to
+/// the size of the serialized proof.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProofSizeReport {
+    /// Size of the version header and the proof context.
+    pub context: usize,
+    /// Size of the trace, constraint, and FRI layer commitments.
+    pub commitments: usize,
+    /// Size of the trace queries (values and Merkle authentication paths) for all trace
+    /// segments.
+    pub trace_queries: usize,
+    /// Size of the constraint queries (values and Merkle authentication paths).
+    pub constraint_queries: usize,
+    /// Size of the queries against all committed public columns.
+    pub committed_column_queries: usize,
+    /// Size of the out-of-domain evaluation frame.
+    pub ood_frame: usize,
+    /// Size of the FRI layer queries (values and Merkle authentication paths) for all FRI layers.
+    pub fri_layers: usize,
+    /// Size of the FRI remainder.
+    pub fri_remainder: usize,
+    /// Size of all other parts of the FRI proof: FRI layer proof-of-work nonces, the number of
+    /// partitions, and out-of-domain evaluations sent during FRI (if any).
+    pub fri_other: usize,
+    /// Size of the query seed proof-of-work nonce.
+    pub pow_nonce: usize,
+}
+
+impl ProofSizeReport {
+    /// Returns the size of the entire serialized proof in bytes.
+    pub fn total(&self) -> usize {
+        self.context
+            + self.commitments
+            + self.trace_queries
+            + self.constraint_queries
+            + self.committed_column_queries
+            + self.ood_frame
+            + self.fri_layers
+            + self.fri_remainder
+            + self.fri_other
+            + self.pow_nonce
+    }
+}
+
+impl fmt::Display for ProofSizeReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let total = self.total();
+        let components = [
+            ("context", self.context),
+            ("commitments", self.commitments),
+            ("trace queries", self.trace_queries),
+            ("constraint queries", self.constraint_queries),
+            ("committed column queries", self.committed_column_queries),
+            ("OOD frame", self.ood_frame),
+            ("FRI layers", self.fri_layers),
+            ("FRI remainder", self.fri_remainder),
+            ("FRI other", self.fri_other),
+            ("PoW nonce", self.pow_nonce),
+        ];
+        for (name, size) in components.iter() {
+            writeln!(
+                f,
+                "{}: {} bytes ({:.1}%)",
+                name,
+                size,
+                *size as f64 * 100f64 / total as f64
+            )?;
+        }
+        write!(f, "total: {:.1} KB", total as f64 / 1024f64)
+    }
+}
@@ -0,0 +1,624 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    Commitments, Context, OodFrame, ProofChunk, ProofLimits, ProofReassembler, Queries, StarkProof,
+    StarkProofRef,
+};
+use crate::{EvaluationFrame, FieldExtension, HashFunction, ProofOptions, TraceInfo};
+use crypto::{hashers::Blake3_256, ElementHasher, Hasher, MerkleTree};
+use fri::{DefaultProverChannel, FriProver};
+use math::{fft, fields::f128::BaseElement, FieldElement, StarkField};
+use utils::{collections::Vec, Deserializable, DeserializationError, Serializable, SliceReader};
+
+type Blake3 = Blake3_256<BaseElement>;
+
+const TRACE_WIDTH: usize = 2;
+const TRACE_LENGTH: usize = 64;
+
+// SECURITY LEVEL TESTS
+// ================================================================================================
+
+#[test]
+fn proof_security_level() {
+    let proof = build_proof(build_options(28));
+
+    // proven security is always lower than conjectured security
+    let conjectured = proof.security_level(true);
+    let proven = proof.security_level(false);
+    assert!(proven < conjectured);
+
+    // security level of a proof is derived only from its parameters
+    let weak_proof = build_proof(build_options(4));
+    assert!(weak_proof.security_level(true) < conjectured);
+    assert!(weak_proof.security_level(false) < proven);
+    assert_eq!(
+        weak_proof.security_level(true),
+        StarkProof::from_bytes(&weak_proof.to_bytes())
+            .unwrap()
+            .security_level(true)
+    );
+
+    // options built for a target security level yield proofs at or above that level
+    let options = ProofOptions::with_security(96, true, BaseElement::MODULUS_BITS);
+    assert!(build_proof(options).security_level(true) >= 96);
+}
+
+// PROOF VERSION TESTS
+// ================================================================================================
+
+#[test]
+fn proof_version_header() {
+    let proof = build_proof(build_options(28));
+
+    // both layouts should start with a version header
+    let proof_bytes = proof.to_bytes();
+    for bytes in [&proof_bytes, &proof.to_streaming_bytes()] {
+        let (version, context) =
+            StarkProof::read_header_from(&mut SliceReader::new(bytes)).unwrap();
+        assert_eq!(StarkProof::VERSION, version);
+        assert_eq!(proof.context, context);
+    }
+
+    // the header should contain the hash function and the field modulus; the header consists
+    // of the marker, the version, the hash function, and the length-prefixed modulus
+    let field_modulus_bytes = proof.context.field_modulus_bytes();
+    let header_len = 4 + field_modulus_bytes.len();
+    assert_eq!(proof.options().hash_fn().to_bytes(), proof_bytes[2..3]);
+    assert_eq!(field_modulus_bytes, &proof_bytes[4..header_len]);
+    assert_eq!(proof, StarkProof::from_bytes(&proof_bytes).unwrap());
+
+    // a header which does not match the proof context should be rejected
+    let mut mismatched_bytes = proof_bytes.clone();
+    mismatched_bytes[4] ^= 1;
+    assert!(StarkProof::from_bytes(&mismatched_bytes).is_err());
+
+    // proofs of a newer version should be rejected with a clear error
+    let future_version = StarkProof::VERSION + 1;
+    let mut future_bytes = proof_bytes;
+    future_bytes[1] = future_version;
+    assert_eq!(
+        Err(DeserializationError::UnsupportedVersion(
+            future_version,
+            StarkProof::VERSION
+        )),
+        StarkProof::from_bytes(&future_bytes).map(|_| ())
+    );
+}
+
+#[test]
+#[cfg(feature = "legacy-proofs")]
+fn read_legacy_proofs() {
+    let proof = build_proof(build_options(28));
+    let proof_bytes = proof.to_bytes();
+    let header_len = 4 + proof.context.field_modulus_bytes().len();
+
+    // proofs without the header should be parsed as proofs of version 0
+    let legacy_bytes = &proof_bytes[header_len..];
+    let (version, _) = StarkProof::read_header_from(&mut SliceReader::new(legacy_bytes)).unwrap();
+    assert_eq!(0, version);
+    let legacy_proof = StarkProof::from_bytes(legacy_bytes).unwrap();
+    assert_eq!(proof, legacy_proof);
+    assert_eq!(proof_bytes, legacy_proof.to_bytes());
+
+    // proofs of version 1 have a header without the hash function and the field modulus
+    let v1_bytes = [&[0, 1], legacy_bytes].concat();
+    let (version, _) = StarkProof::read_header_from(&mut SliceReader::new(&v1_bytes)).unwrap();
+    assert_eq!(1, version);
+    assert_eq!(proof, StarkProof::from_bytes(&v1_bytes).unwrap());
+}
+
+// PROOF HEADER TESTS
+// ================================================================================================
+
+#[test]
+fn peek_proof_header() {
+    let proof = build_proof(build_options(28));
+    let proof_bytes = proof.to_bytes();
+
+    let header = StarkProof::peek_header(&proof_bytes).unwrap();
+    assert_eq!(StarkProof::VERSION, header.version());
+    assert_eq!(&proof.context, header.context());
+    assert_eq!(proof.options(), header.options());
+    assert_eq!(proof.get_trace_info(), header.get_trace_info());
+    assert_eq!(proof.trace_length(), header.trace_length());
+    assert_eq!(proof.trace_width(), header.trace_width());
+    assert_eq!(&proof.commitments, header.commitments());
+    assert_eq!(proof_bytes.len(), header.proof_size());
+
+    // the rest of the proof is not parsed, and thus, a header can be read from a proof which is
+    // truncated after the commitments
+    let truncated = &proof_bytes[..proof_bytes.len() / 2];
+    assert!(StarkProof::from_bytes(truncated).is_err());
+    let header = StarkProof::peek_header(truncated).unwrap();
+    assert_eq!(&proof.commitments, header.commitments());
+
+    // but the commitments must be present
+    let mut reader = SliceReader::new(&proof_bytes);
+    StarkProof::read_header_from(&mut reader).unwrap();
+    let err = StarkProof::peek_header(&proof_bytes[..reader.position()]).unwrap_err();
+    assert!(err.to_string().starts_with("failed to read commitments"));
+}
+
+// DESERIALIZATION ERROR TESTS
+// ================================================================================================
+
+#[test]
+fn read_proof_error_context() {
+    let proof = build_proof(build_options(28));
+    let proof_bytes = proof.to_bytes();
+    let nonce_start = proof_bytes.len() - 8;
+    let fri_start = nonce_start - proof.fri_proof.to_bytes().len();
+
+    // errors should specify which component of the proof could not be read, and where
+    let expected = DeserializationError::Context(
+        "proof-of-work nonce",
+        nonce_start,
+        Box::new(DeserializationError::UnexpectedEOF),
+    );
+    let err = StarkProof::from_bytes(&proof_bytes[..nonce_start + 4]).unwrap_err();
+    assert_eq!(expected, err);
+    assert_eq!(&DeserializationError::UnexpectedEOF, err.root_cause());
+
+    // contexts of nested components should be nested as well
+    let expected = DeserializationError::Context(
+        "FRI proof",
+        fri_start,
+        Box::new(DeserializationError::Context(
+            "FRI layer",
+            fri_start + 1,
+            Box::new(DeserializationError::UnexpectedEOF),
+        )),
+    );
+    let truncated_bytes = &proof_bytes[..fri_start + 3];
+    let err = StarkProof::from_bytes(truncated_bytes).unwrap_err();
+    assert_eq!(expected, err);
+    assert_eq!(
+        format!(
+            "failed to read FRI proof at byte offset {}: failed to read FRI layer at byte \
+            offset {}: unexpected EOF",
+            fri_start,
+            fri_start + 1
+        ),
+        err.to_string()
+    );
+    assert_eq!(
+        Err(expected),
+        StarkProofRef::new(truncated_bytes, &ProofLimits::new())
+    );
+
+    // errors which do not occur within a component should not be wrapped
+    let mut extended_bytes = proof_bytes.clone();
+    extended_bytes.push(0);
+    assert_eq!(
+        Err(DeserializationError::UnconsumedBytes),
+        StarkProof::from_bytes(&extended_bytes)
+    );
+}
+
+// PROOF REF TESTS
+// ================================================================================================
+
+#[test]
+fn read_proof_ref() {
+    let proof = build_proof(build_options(28));
+    let proof_bytes = proof.to_bytes();
+
+    // all components of the view should be the same as in the proof
+    let proof_ref = StarkProofRef::new(&proof_bytes, &ProofLimits::new()).unwrap();
+    assert_eq!(StarkProof::VERSION, proof_ref.version());
+    assert_eq!(&proof.context, proof_ref.context());
+    assert_eq!(proof.pow_nonce, proof_ref.pow_nonce());
+    assert_eq!(proof.commitments, proof_ref.commitments().unwrap());
+    assert_eq!(proof.trace_queries.len(), proof_ref.num_trace_segments());
+    assert_eq!(proof.trace_queries[0], proof_ref.trace_queries(0).unwrap());
+    assert_eq!(
+        proof.constraint_queries,
+        proof_ref.constraint_queries().unwrap()
+    );
+    assert_eq!(
+        proof.committed_column_queries.len(),
+        proof_ref.num_committed_columns()
+    );
+    assert_eq!(proof.ood_frame, proof_ref.ood_frame().unwrap());
+    assert_eq!(proof.fri_proof, proof_ref.fri_proof().unwrap());
+
+    // the decoded proof should be the same as the original proof
+    assert_eq!(proof, proof_ref.to_proof().unwrap());
+
+    // limits should be enforced when the view is created
+    let limits = ProofLimits::new().with_max_num_queries(proof.options().num_queries() - 1);
+    assert!(matches!(
+        StarkProofRef::new(&proof_bytes, &limits),
+        Err(DeserializationError::LimitExceeded(..))
+    ));
+    let limits = ProofLimits::new().with_max_fri_layers(proof.fri_proof.num_layers() - 1);
+    assert!(matches!(
+        StarkProofRef::new(&proof_bytes, &limits),
+        Err(DeserializationError::LimitExceeded(..))
+    ));
+
+    // truncated proofs and proofs with trailing bytes should be rejected
+    for len in [proof_bytes.len() / 2, proof_bytes.len() - 1] {
+        assert!(StarkProofRef::new(&proof_bytes[..len], &ProofLimits::new()).is_err());
+    }
+    let mut extended_bytes = proof_bytes.clone();
+    extended_bytes.push(0);
+    assert_eq!(
+        Err(DeserializationError::UnconsumedBytes),
+        StarkProofRef::new(&extended_bytes, &ProofLimits::new())
+    );
+}
+
+// COMPRESSED PROOF TESTS
+// ================================================================================================
+
+#[test]
+fn compressed_proof_serialization() {
+    let proof = build_proof(build_options(28));
+    let limits = ProofLimits::new();
+
+    // the compressed proof should be smaller, and should be read back into the same proof
+    let proof_bytes = proof.to_bytes();
+    let compressed_bytes = proof.to_bytes_compressed();
+    assert!(compressed_bytes.len() < proof_bytes.len());
+    let parsed = StarkProof::from_bytes_compressed(&compressed_bytes, &limits).unwrap();
+    assert_eq!(proof, parsed);
+    assert_eq!(proof_bytes, parsed.to_bytes());
+
+    // compressed and uncompressed proofs cannot be read by the other parser
+    assert!(StarkProof::from_bytes(&compressed_bytes).is_err());
+    assert!(StarkProof::from_bytes_compressed(&proof_bytes, &limits).is_err());
+
+    // size limits apply to the compressed proof
+    let limits = limits.with_max_proof_size(compressed_bytes.len() - 1);
+    assert!(matches!(
+        StarkProof::from_bytes_compressed(&compressed_bytes, &limits),
+        Err(DeserializationError::LimitExceeded(..))
+    ));
+}
+
+// CBOR ENCODING TESTS
+// ================================================================================================
+
+#[test]
+fn cbor_proof_serialization() {
+    let proof = build_proof(build_options(28));
+    let limits = ProofLimits::new();
+
+    // the encoding should be deterministic, and should be read back into the same proof
+    let cbor_bytes = proof.to_cbor();
+    let parsed = StarkProof::from_cbor(&cbor_bytes, &limits).unwrap();
+    assert_eq!(proof, parsed);
+    assert_eq!(cbor_bytes, parsed.to_cbor());
+
+    // the proof should be a map with 11 entries, starting with the proof version
+    assert_eq!(&[0xab, 0x00, StarkProof::VERSION][..], &cbor_bytes[..3]);
+
+    // binary and CBOR encodings cannot be read by the other parser
+    assert!(StarkProof::from_bytes(&cbor_bytes).is_err());
+    assert!(StarkProof::from_cbor(&proof.to_bytes(), &limits).is_err());
+
+    // non-canonical encodings should be rejected: here, the version is encoded using two bytes
+    let mut non_canonical = vec![0xab, 0x00, 0x18, StarkProof::VERSION];
+    non_canonical.extend_from_slice(&cbor_bytes[3..]);
+    assert!(StarkProof::from_cbor(&non_canonical, &limits).is_err());
+
+    // trailing bytes should be rejected
+    let mut extended = cbor_bytes.clone();
+    extended.push(0);
+    assert_eq!(
+        Err(DeserializationError::UnconsumedBytes),
+        StarkProof::from_cbor(&extended, &limits)
+    );
+
+    // limits should apply to CBOR-encoded proofs
+    let limits = limits.with_max_num_queries(proof.options().num_queries() - 1);
+    assert!(matches!(
+        StarkProof::from_cbor(&cbor_bytes, &limits),
+        Err(DeserializationError::LimitExceeded(..))
+    ));
+}
+
+// PROOF CHUNK TESTS
+// ================================================================================================
+
+#[test]
+fn proof_chunks() {
+    let proof = build_proof(build_options(28));
+    let proof_bytes = proof.to_bytes();
+    let chunk_size = 1000;
+    let chunks = proof.to_chunks::<Blake3>(chunk_size);
+    assert_eq!(proof_bytes.len().div_ceil(chunk_size), chunks.len());
+    assert!(chunks.len() > 2);
+    assert!(chunks[..chunks.len() - 1]
+        .iter()
+        .all(|chunk| chunk.data().len() == chunk_size));
+
+    // chunks should be reassembled into the same proof regardless of their order, and chunks
+    // should survive serialization
+    let mut reassembler = ProofReassembler::<Blake3>::new(ProofLimits::new());
+    for chunk in chunks.iter().rev() {
+        assert!(!reassembler.is_complete());
+        let chunk = ProofChunk::read_from(&mut SliceReader::new(&chunk.to_bytes())).unwrap();
+        reassembler.add_chunk(chunk).unwrap();
+    }
+    // retransmitted chunks should be ignored
+    reassembler.add_chunk(chunks[0].clone()).unwrap();
+    assert!(reassembler.is_complete());
+    assert!(reassembler.missing_chunks().is_empty());
+    assert_eq!(proof, reassembler.into_proof().unwrap());
+
+    // missing chunks should be reported
+    let mut reassembler = ProofReassembler::<Blake3>::new(ProofLimits::new());
+    assert!(reassembler.missing_chunks().is_empty());
+    reassembler.add_chunk(chunks[1].clone()).unwrap();
+    let mut expected = vec![0];
+    expected.extend(2..chunks.len());
+    assert_eq!(expected, reassembler.missing_chunks());
+    assert!(reassembler.into_bytes().is_err());
+
+    // a chunk with different data or a chunk of a different proof should be rejected
+    let mut reassembler = ProofReassembler::<Blake3>::new(ProofLimits::new());
+    reassembler.add_chunk(chunks[0].clone()).unwrap();
+    let mut modified_bytes = proof_bytes.clone();
+    modified_bytes[10] ^= 1;
+    let modified_chunks = ProofChunk::split::<Blake3>(&modified_bytes, chunk_size);
+    assert!(reassembler.add_chunk(modified_chunks[1].clone()).is_err());
+
+    // a proof which does not match the digest should be rejected
+    let mut tampered = chunks[1].to_bytes();
+    let last = tampered.len() - 1;
+    tampered[last] ^= 1;
+    let mut reassembler = ProofReassembler::<Blake3>::new(ProofLimits::new());
+    for (i, chunk) in chunks.iter().enumerate() {
+        let chunk = if i == 1 {
+            ProofChunk::read_from(&mut SliceReader::new(&tampered)).unwrap()
+        } else {
+            chunk.clone()
+        };
+        reassembler.add_chunk(chunk).unwrap();
+    }
+    assert!(reassembler.is_complete());
+    assert!(reassembler.into_bytes().is_err());
+
+    // the size of the added chunks should be limited
+    let limits = ProofLimits::new().with_max_proof_size(proof_bytes.len() - 1);
+    let mut reassembler = ProofReassembler::<Blake3>::new(limits);
+    let result = chunks
+        .iter()
+        .try_for_each(|chunk| reassembler.add_chunk(chunk.clone()));
+    assert!(matches!(
+        result,
+        Err(DeserializationError::LimitExceeded(..))
+    ));
+}
+
+// PROOF WRITER TESTS
+// ================================================================================================
+
+#[test]
+#[cfg(feature = "std")]
+fn write_proof_to_adapter() {
+    let proof = build_proof(build_options(28));
+
+    // a proof written via a write adapter should be the same as the serialized proof
+    let mut writer = utils::WriteAdapter::new(std::io::BufWriter::new(Vec::new()));
+    proof.write_to(&mut writer);
+    let written = writer.finish().unwrap().into_inner().unwrap();
+    assert_eq!(proof.to_bytes(), written);
+}
+
+// SIZE REPORT TESTS
+// ================================================================================================
+
+#[test]
+fn proof_size_report() {
+    let proof = build_proof(build_options(28));
+    let report = proof.size_report();
+
+    // the components should add up to the size of the serialized proof
+    assert_eq!(proof.to_bytes().len(), report.total());
+    assert_eq!(
+        proof.fri_proof.size(),
+        report.fri_layers + report.fri_remainder + report.fri_other
+    );
+    assert_eq!(8, report.pow_nonce);
+
+    // the proof has a committed column, and FRI layers are the largest FRI component
+    assert!(report.committed_column_queries > 1);
+    assert!(report.trace_queries > 1);
+    assert!(report.fri_layers > report.fri_remainder);
+}
+
+// PROOF CONTEXT TESTS
+// ================================================================================================
+
+#[test]
+fn read_context_with_large_lde_domain() {
+    let proof = build_proof(build_options(28));
+    let blowup_factor_log2 = proof.options().blowup_factor().trailing_zeros();
+    let mut context_bytes = proof.context.to_bytes();
+
+    // the largest LDE domain which fits into usize should be accepted
+    context_bytes[1] = (usize::BITS - blowup_factor_log2 - 1) as u8;
+    let context = Context::read_from(&mut SliceReader::new(&context_bytes)).unwrap();
+    assert_eq!(1 << (usize::BITS - 1), context.lde_domain_size());
+    assert!(context.security_level(false) <= context.security_level(true));
+
+    // a trace length for which the size of the LDE domain overflows should be rejected
+    context_bytes[1] = (usize::BITS - blowup_factor_log2) as u8;
+    assert!(matches!(
+        Context::read_from(&mut SliceReader::new(&context_bytes)),
+        Err(DeserializationError::InvalidValue(..))
+    ));
+}
+
+// PROOF LIMITS TESTS
+// ================================================================================================
+
+#[test]
+fn read_proof_with_limits() {
+    let proof = build_proof(build_options(28));
+    let proof_bytes = proof.to_bytes();
+    let num_queries = proof.context.options().num_queries();
+    let num_fri_layers = proof.fri_proof.num_layers();
+    assert!(num_fri_layers > 0);
+
+    // a proof within the limits should be parsed just like without the limits
+    let limits = ProofLimits::new()
+        .with_max_proof_size(proof_bytes.len())
+        .with_max_trace_width(proof.context.trace_width())
+        .with_max_num_queries(num_queries)
+        .with_max_fri_layers(num_fri_layers);
+    assert_eq!(
+        Ok(proof.clone()),
+        StarkProof::from_bytes_with_limits(&proof_bytes, &limits)
+    );
+
+    // a proof exceeding any of the limits should be rejected
+    let expected_errors = [
+        (
+            limits.with_max_proof_size(proof_bytes.len() - 1),
+            DeserializationError::LimitExceeded(
+                "proof size",
+                proof_bytes.len(),
+                proof_bytes.len() - 1,
+            ),
+        ),
+        (
+            limits.with_max_trace_width(1),
+            DeserializationError::LimitExceeded("trace width", TRACE_WIDTH, 1),
+        ),
+        (
+            limits.with_max_num_queries(num_queries - 1),
+            DeserializationError::LimitExceeded("number of queries", num_queries, num_queries - 1),
+        ),
+        (
+            limits.with_max_fri_layers(num_fri_layers - 1),
+            DeserializationError::LimitExceeded(
+                "number of FRI layers",
+                num_fri_layers,
+                num_fri_layers - 1,
+            ),
+        ),
+    ];
+    for (limits, expected_error) in expected_errors {
+        assert_eq!(
+            Err(expected_error),
+            StarkProof::from_bytes_with_limits(&proof_bytes, &limits)
+        );
+    }
+
+    // the proof size is checked before any bytes are parsed
+    let garbage = vec![0xff; 1024];
+    assert_eq!(
+        Err(DeserializationError::LimitExceeded(
+            "proof size",
+            1024,
+            1023
+        )),
+        StarkProof::from_bytes_with_limits(&garbage, &ProofLimits::new().with_max_proof_size(1023))
+    );
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn build_options(num_queries: usize) -> ProofOptions {
+    ProofOptions::new(
+        num_queries,
+        8,
+        0,
+        HashFunction::Blake3_256,
+        FieldExtension::None,
+        4,
+        256,
+    )
+}
+
+/// Builds a proof for a trace of 2 registers and 64 steps with a single committed column using
+/// the specified options.
+///
+/// The proof is well-formed: it can be serialized and read back, and all of its queries open
+/// leaves of the committed Merkle trees. However, it is not a valid proof of any computation.
+fn build_proof(options: ProofOptions) -> StarkProof {
+    let trace_info = TraceInfo::new(TRACE_WIDTH, TRACE_LENGTH);
+    let lde_domain_size = TRACE_LENGTH * options.blowup_factor();
+
+    // build a FRI proof for a polynomial of degree smaller than the trace length
+    let mut channel = DefaultProverChannel::<BaseElement, BaseElement, Blake3>::new(
+        lde_domain_size,
+        options.num_queries(),
+    );
+    let mut fri_prover = FriProver::new(options.to_fri_options());
+    fri_prover.build_layers(&mut channel, build_evaluations(lde_domain_size));
+    let positions = channel.draw_query_positions();
+    let fri_proof = fri_prover.build_proof(&positions);
+
+    // commit to the extended trace, constraint evaluations, and a committed column, and open
+    // them at the same positions as the FRI layers
+    let (trace_root, trace_queries) = build_queries(TRACE_WIDTH, lde_domain_size, &positions);
+    let (constraint_root, constraint_queries) = build_queries(1, lde_domain_size, &positions);
+    let (_, column_queries) = build_queries(1, lde_domain_size, &positions);
+    let commitments = Commitments::new::<Blake3>(
+        vec![trace_root],
+        constraint_root,
+        channel.layer_commitments().to_vec(),
+    );
+
+    let frame = EvaluationFrame::from_rows(
+        vec![BaseElement::new(1), BaseElement::new(2)],
+        vec![BaseElement::new(3), BaseElement::new(5)],
+    );
+    let ood_frame = OodFrame::new(frame, vec![BaseElement::new(8)]);
+
+    StarkProof {
+        context: Context::new::<BaseElement>(&trace_info, options),
+        commitments,
+        trace_queries: vec![trace_queries],
+        constraint_queries,
+        committed_column_queries: vec![column_queries],
+        ood_frame,
+        fri_proof,
+        pow_nonce: 42,
+    }
+}
+
+/// Returns evaluations of a polynomial of degree smaller than the trace length over a domain of
+/// the specified size.
+fn build_evaluations(domain_size: usize) -> Vec<BaseElement> {
+    let mut evaluations = (0..TRACE_LENGTH as u128)
+        .map(BaseElement::new)
+        .collect::<Vec<_>>();
+    evaluations.resize(domain_size, BaseElement::ZERO);
+    let twiddles = fft::get_twiddles::<BaseElement>(domain_size);
+    fft::evaluate_poly(&mut evaluations, &twiddles);
+    evaluations
+}
+
+/// Commits to a table of the specified width and length via a Merkle tree, and returns the root
+/// of the tree together with the rows of the table at the specified positions.
+fn build_queries(
+    width: usize,
+    length: usize,
+    positions: &[usize],
+) -> (<Blake3 as Hasher>::Digest, Queries) {
+    let rows = (0..length)
+        .map(|i| {
+            (0..width)
+                .map(|j| BaseElement::new((i * width + j) as u128))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let leaves = rows.iter().map(|row| Blake3::hash_elements(row)).collect();
+    let tree = MerkleTree::<Blake3>::new(leaves).unwrap();
+    let merkle_proof = tree.prove_batch(positions).unwrap();
+    let values = positions.iter().map(|&p| rows[p].clone()).collect();
+    (
+        *tree.root(),
+        Queries::new::<Blake3, BaseElement>(merkle_proof, values),
+    )
+}
@@ -0,0 +1,173 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::QuerySampling;
+use crypto::{PublicCoin, RandomCoinError};
+use utils::collections::Vec;
+
+// QUERY SAMPLER
+// ================================================================================================
+
+/// Defines how query positions are derived from the query seed.
+///
+/// After the prover commits to all polynomials and grinds the query seed, the positions at which
+/// trace and constraint commitments are opened are drawn from the public coin via a query
+/// sampler. Built-in samplers are [DistinctSampler], [UniformSampler], and [StratifiedSampler];
+/// proof options select one of them via [QuerySampling], which implements this trait by
+/// delegating to the selected sampler. A custom sampler can be used by overriding
+/// [Air::get_query_positions()](crate::Air::get_query_positions).
+pub trait QuerySampler {
+    /// Draws positions for `num_queries` queries from the range [0, `lde_domain_size`) using the
+    /// specified `public_coin`; `blowup_factor` is the number of cosets of the trace domain in
+    /// the LDE domain.
+    ///
+    /// Every position is opened only once, and thus, the returned positions must be distinct. A
+    /// sampler which may draw the same position more than once must drop repeated draws, and may
+    /// return fewer than `num_queries` positions.
+    ///
+    /// # Errors
+    /// Returns an error if the positions could not be drawn.
+    fn draw_positions<P: PublicCoin>(
+        &self,
+        public_coin: &mut P,
+        num_queries: usize,
+        lde_domain_size: usize,
+        blowup_factor: usize,
+    ) -> Result<Vec<usize>, RandomCoinError>;
+
+    /// Returns the expected number of distinct positions drawn for `num_queries` queries from an
+    /// LDE domain of the specified size, rounded down.
+    ///
+    /// Security estimates of a proof are based on this number rather than on the number of
+    /// queries, as repeated positions add no security. By default, `num_queries` is returned;
+    /// this is correct only for samplers which always draw `num_queries` distinct positions.
+    fn expected_num_positions(&self, num_queries: usize, _lde_domain_size: usize) -> usize {
+        num_queries
+    }
+}
+
+// BUILT-IN SAMPLERS
+// ================================================================================================
+
+/// Draws positions uniformly from the entire LDE domain; a position which has been drawn already
+/// is replaced with a new draw. This always yields `num_queries` distinct positions.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct DistinctSampler;
+
+impl QuerySampler for DistinctSampler {
+    fn draw_positions<P: PublicCoin>(
+        &self,
+        public_coin: &mut P,
+        num_queries: usize,
+        lde_domain_size: usize,
+        _blowup_factor: usize,
+    ) -> Result<Vec<usize>, RandomCoinError> {
+        public_coin.draw_integers(num_queries, lde_domain_size)
+    }
+}
+
+/// Draws positions independently and uniformly from the entire LDE domain; repeated positions
+/// are opened only once. This may yield fewer than `num_queries` distinct positions.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct UniformSampler;
+
+impl QuerySampler for UniformSampler {
+    fn draw_positions<P: PublicCoin>(
+        &self,
+        public_coin: &mut P,
+        num_queries: usize,
+        lde_domain_size: usize,
+        _blowup_factor: usize,
+    ) -> Result<Vec<usize>, RandomCoinError> {
+        let mut positions = Vec::with_capacity(num_queries);
+        for position in public_coin.draw_integers_with_replacement(num_queries, lde_domain_size) {
+            if !positions.contains(&position) {
+                positions.push(position);
+            }
+        }
+        Ok(positions)
+    }
+
+    fn expected_num_positions(&self, num_queries: usize, lde_domain_size: usize) -> usize {
+        // draw i yields a new position with probability (1 - 1/n)^i, where n is the size of the
+        // domain; thus, the expected number of distinct positions is the sum of these
+        // probabilities over all draws
+        let repeat_free = 1.0 - 1.0 / lde_domain_size as f64;
+        let mut probability = 1.0f64;
+        let mut result = 0.0f64;
+        for _ in 0..num_queries {
+            result += probability;
+            probability *= repeat_free;
+        }
+        result as usize
+    }
+}
+
+/// Spreads positions evenly across cosets of the trace domain in the LDE domain, such that
+/// query `i` is drawn from coset `i % blowup_factor`; a position which has been drawn already is
+/// replaced with a new draw from the same coset. This always yields `num_queries` distinct
+/// positions.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct StratifiedSampler;
+
+impl QuerySampler for StratifiedSampler {
+    fn draw_positions<P: PublicCoin>(
+        &self,
+        public_coin: &mut P,
+        num_queries: usize,
+        lde_domain_size: usize,
+        blowup_factor: usize,
+    ) -> Result<Vec<usize>, RandomCoinError> {
+        public_coin.draw_integers_stratified(num_queries, lde_domain_size, blowup_factor)
+    }
+}
+
+// QUERY SAMPLING
+// ================================================================================================
+
+impl QuerySampler for QuerySampling {
+    fn draw_positions<P: PublicCoin>(
+        &self,
+        public_coin: &mut P,
+        num_queries: usize,
+        lde_domain_size: usize,
+        blowup_factor: usize,
+    ) -> Result<Vec<usize>, RandomCoinError> {
+        match self {
+            QuerySampling::Distinct => DistinctSampler.draw_positions(
+                public_coin,
+                num_queries,
+                lde_domain_size,
+                blowup_factor,
+            ),
+            QuerySampling::Uniform => UniformSampler.draw_positions(
+                public_coin,
+                num_queries,
+                lde_domain_size,
+                blowup_factor,
+            ),
+            QuerySampling::StratifiedByCoset => StratifiedSampler.draw_positions(
+                public_coin,
+                num_queries,
+                lde_domain_size,
+                blowup_factor,
+            ),
+        }
+    }
+
+    fn expected_num_positions(&self, num_queries: usize, lde_domain_size: usize) -> usize {
+        match self {
+            QuerySampling::Distinct => {
+                DistinctSampler.expected_num_positions(num_queries, lde_domain_size)
+            }
+            QuerySampling::Uniform => {
+                UniformSampler.expected_num_positions(num_queries, lde_domain_size)
+            }
+            QuerySampling::StratifiedByCoset => {
+                StratifiedSampler.expected_num_positions(num_queries, lde_domain_size)
+            }
+        }
+    }
+}
@@ -20,16 +20,16 @@ pub fn merkle_tree_construction(c: &mut Criterion) {
     for size in &BATCH_SIZES {
         let data: Vec<Blake3Digest> = {
             let mut res = unsafe { uninit_vector(*size) };
-            for i in 0..*size {
-                res[i] = Blake3::hash(&rand_value::<u128>().to_le_bytes());
+            for value in res.iter_mut() {
+                *value = Blake3::hash(&rand_value::<u128>().to_le_bytes());
             }
             res
         };
         merkle_group.bench_with_input(BenchmarkId::new("sequential", size), &data, |b, i| {
-            b.iter(|| build_merkle_nodes::<Blake3>(&i))
+            b.iter(|| build_merkle_nodes::<Blake3>(i))
         });
         merkle_group.bench_with_input(BenchmarkId::new("concurrent", size), &data, |b, i| {
-            b.iter(|| concurrent::build_merkle_nodes::<Blake3>(&i))
+            b.iter(|| concurrent::build_merkle_nodes::<Blake3>(i))
         });
     }
 }
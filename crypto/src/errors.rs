@@ -71,6 +71,9 @@ impl fmt::Display for MerkleTreeError {
     }
 }
 
+#[cfg(feature = "std")]
+impl std::error::Error for MerkleTreeError {}
+
 // RANDOM COIN ERROR
 // ================================================================================================
 
@@ -82,6 +85,9 @@ pub enum RandomCoinError {
     /// The required number of integer values could not be drawn from the specified domain after
     /// the specified number of tries.
     FailedToDrawIntegers(usize, usize, usize),
+    /// An entry at the specified index of a transcript is not consistent with the values drawn
+    /// by a random coin when the transcript was replayed.
+    InconsistentTranscript(usize),
 }
 
 impl fmt::Display for RandomCoinError {
@@ -101,6 +107,16 @@ impl fmt::Display for RandomCoinError {
                     num_expected, num_actual, num_tries
                 )
             }
+            Self::InconsistentTranscript(index) => {
+                write!(
+                    f,
+                    "transcript entry {} is inconsistent with the random coin",
+                    index
+                )
+            }
         }
     }
 }
+
+#[cfg(feature = "std")]
+impl std::error::Error for RandomCoinError {}
@@ -3,7 +3,7 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{ByteDigest, ElementHasher, Hasher};
+use super::{count_hashed_bytes, ByteDigest, ElementHasher, Hasher};
 use core::{convert::TryInto, fmt::Debug, marker::PhantomData};
 use math::{FieldElement, StarkField};
 use utils::ByteWriter;
@@ -23,14 +23,17 @@ impl<B: StarkField> Hasher for Blake3_256<B> {
     type Digest = ByteDigest<32>;
 
     fn hash(bytes: &[u8]) -> Self::Digest {
+        count_hashed_bytes(bytes.len());
         ByteDigest(*blake3::hash(bytes).as_bytes())
     }
 
     fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+        count_hashed_bytes(ByteDigest::digests_as_bytes(values).len());
         ByteDigest(blake3::hash(ByteDigest::digests_as_bytes(values)).into())
     }
 
     fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
+        count_hashed_bytes(seed.0.len() + 8);
         let mut data = [0; 40];
         data[..32].copy_from_slice(&seed.0);
         data[32..].copy_from_slice(&value.to_le_bytes());
@@ -42,6 +45,7 @@ impl<B: StarkField> ElementHasher for Blake3_256<B> {
     type BaseField = B;
 
     fn hash_elements<E: FieldElement<BaseField = Self::BaseField>>(elements: &[E]) -> Self::Digest {
+        count_hashed_bytes(elements.len() * E::ELEMENT_BYTES);
         if B::IS_CANONICAL {
             // when element's internal and canonical representations are the same, we can hash
             // element bytes directly
@@ -69,16 +73,19 @@ impl<B: StarkField> Hasher for Blake3_192<B> {
     type Digest = ByteDigest<24>;
 
     fn hash(bytes: &[u8]) -> Self::Digest {
+        count_hashed_bytes(bytes.len());
         let result = blake3::hash(bytes);
         ByteDigest(result.as_bytes()[..24].try_into().unwrap())
     }
 
     fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+        count_hashed_bytes(ByteDigest::digests_as_bytes(values).len());
         let result = blake3::hash(ByteDigest::digests_as_bytes(values));
         ByteDigest(result.as_bytes()[..24].try_into().unwrap())
     }
 
     fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
+        count_hashed_bytes(seed.0.len() + 8);
         let mut data = [0; 32];
         data[..24].copy_from_slice(&seed.0);
         data[24..].copy_from_slice(&value.to_le_bytes());
@@ -92,6 +99,7 @@ impl<B: StarkField> ElementHasher for Blake3_192<B> {
     type BaseField = B;
 
     fn hash_elements<E: FieldElement<BaseField = Self::BaseField>>(elements: &[E]) -> Self::Digest {
+        count_hashed_bytes(elements.len() * E::ELEMENT_BYTES);
         if B::IS_CANONICAL {
             // when element's internal and canonical representations are the same, we can hash
             // element bytes directly
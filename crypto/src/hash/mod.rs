@@ -7,6 +7,9 @@ use core::{fmt::Debug, slice};
 use math::{FieldElement, StarkField};
 use utils::{ByteReader, Deserializable, DeserializationError, Serializable};
 
+#[cfg(feature = "profiling")]
+use core::sync::atomic::{AtomicU64, Ordering};
+
 mod blake;
 pub use blake::{Blake3_192, Blake3_256};
 
@@ -16,6 +19,35 @@ pub use sha::Sha3_256;
 mod rescue;
 pub use rescue::Rp62_248;
 
+// PROFILING
+// ================================================================================================
+
+#[cfg(feature = "profiling")]
+static NUM_HASHED_BYTES: AtomicU64 = AtomicU64::new(0);
+
+/// Returns the total number of bytes hashed by BLAKE3 and SHA3 hashers since the process was
+/// started.
+///
+/// The counter is shared by all threads in the process. Thus, to measure the number of bytes
+/// hashed by a single procedure, the procedure should be the only one using these hashers while
+/// it is being measured.
+#[cfg(feature = "profiling")]
+pub fn num_hashed_bytes() -> u64 {
+    NUM_HASHED_BYTES.load(Ordering::Relaxed)
+}
+
+/// Adds `num_bytes` to the counter of hashed bytes.
+#[cfg(feature = "profiling")]
+#[inline(always)]
+fn count_hashed_bytes(num_bytes: usize) {
+    NUM_HASHED_BYTES.fetch_add(num_bytes as u64, Ordering::Relaxed);
+}
+
+/// Does nothing; bytes are counted only when `profiling` feature is enabled.
+#[cfg(not(feature = "profiling"))]
+#[inline(always)]
+fn count_hashed_bytes(_num_bytes: usize) {}
+
 // HASHER TRAITS
 // ================================================================================================
 
@@ -3,7 +3,7 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{ByteDigest, ElementHasher, Hasher};
+use super::{count_hashed_bytes, ByteDigest, ElementHasher, Hasher};
 use core::marker::PhantomData;
 use math::{FieldElement, StarkField};
 use sha3::Digest;
@@ -20,14 +20,17 @@ impl<B: StarkField> Hasher for Sha3_256<B> {
     type Digest = ByteDigest<32>;
 
     fn hash(bytes: &[u8]) -> Self::Digest {
+        count_hashed_bytes(bytes.len());
         ByteDigest(sha3::Sha3_256::digest(bytes).into())
     }
 
     fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+        count_hashed_bytes(ByteDigest::digests_as_bytes(values).len());
         ByteDigest(sha3::Sha3_256::digest(ByteDigest::digests_as_bytes(values)).into())
     }
 
     fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
+        count_hashed_bytes(seed.0.len() + 8);
         let mut data = [0; 40];
         data[..32].copy_from_slice(&seed.0);
         data[32..].copy_from_slice(&value.to_le_bytes());
@@ -39,6 +42,7 @@ impl<B: StarkField> ElementHasher for Sha3_256<B> {
     type BaseField = B;
 
     fn hash_elements<E: FieldElement<BaseField = Self::BaseField>>(elements: &[E]) -> Self::Digest {
+        count_hashed_bytes(elements.len() * E::ELEMENT_BYTES);
         if B::IS_CANONICAL {
             // when element's internal and canonical representations are the same, we can hash
             // element bytes directly
@@ -72,7 +76,7 @@ impl ShaHasher {
 
 impl ByteWriter for ShaHasher {
     fn write_u8(&mut self, value: u8) {
-        self.0.update(&[value]);
+        self.0.update([value]);
     }
 
     fn write_u8_slice(&mut self, values: &[u8]) {
@@ -14,7 +14,8 @@
 //!   [Octopus](https://eprint.iacr.org/2017/933) algorithm.
 //! * **PRNG** - which is used to generate pseudo-random elements in a finite field. The
 //!   [RandomCoin] implementation uses a cryptographic hash function to generate pseudo-random
-//!   elements form a seed.
+//!   elements form a seed. Values absorbed into and drawn from the coin can be recorded into a
+//!   [Transcript] for auditing.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -24,6 +25,9 @@ extern crate alloc;
 
 mod hash;
 pub use hash::{Digest, ElementHasher, Hasher};
+
+#[cfg(feature = "profiling")]
+pub use hash::num_hashed_bytes;
 pub mod hashers {
     //! Contains implementations of currently supported hash functions.
 
@@ -40,7 +44,7 @@ pub use merkle::{build_merkle_nodes, BatchMerkleProof, MerkleTree};
 pub use merkle::concurrent;
 
 mod random;
-pub use random::RandomCoin;
+pub use random::{IntegerSampling, PublicCoin, RandomCoin, Transcript, TranscriptEntry};
 
 mod errors;
 pub use errors::{MerkleTreeError, RandomCoinError};
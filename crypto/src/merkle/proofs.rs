@@ -126,6 +126,50 @@ impl<H: Hasher> BatchMerkleProof<H> {
     /// * List of indexes contains duplicates.
     /// * The proof does not resolve to a single root.
     pub fn get_root(&self, indexes: &[usize]) -> Result<H::Digest, MerkleTreeError> {
+        let mut nodes = self.get_nodes(indexes)?;
+        nodes.remove(&1).ok_or(MerkleTreeError::InvalidProof)
+    }
+
+    /// Returns individual Merkle authentication paths for the specified `indexes` aggregated in
+    /// this proof.
+    ///
+    /// The paths are in the same format as the paths returned by
+    /// [MerkleTree::prove()](crate::MerkleTree::prove) (i.e., the leaf itself is the first element
+    /// of the path, followed by its sibling and then by siblings of all its ancestors up to the
+    /// root), and are returned in the same order as the `indexes`.
+    ///
+    /// # Errors
+    /// Returns an error for any of the reasons listed for [BatchMerkleProof::get_root()].
+    pub fn get_paths(&self, indexes: &[usize]) -> Result<Vec<Vec<H::Digest>>, MerkleTreeError> {
+        let nodes = self.get_nodes(indexes)?;
+        let get_node = |index: usize| {
+            nodes
+                .get(&index)
+                .copied()
+                .ok_or(MerkleTreeError::InvalidProof)
+        };
+
+        let offset = 2usize.pow(self.depth as u32);
+        let mut paths = Vec::with_capacity(indexes.len());
+        for &index in indexes {
+            let mut path = vec![get_node(offset + index)?, get_node(offset + (index ^ 1))?];
+            let mut node_index = (offset + index) >> 1;
+            while node_index > 1 {
+                path.push(get_node(node_index ^ 1)?);
+                node_index >>= 1;
+            }
+            paths.push(path);
+        }
+        Ok(paths)
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns all nodes of the Merkle tree which are either included in this proof or can be
+    /// computed from the nodes included in this proof, keyed by their position in the tree; the
+    /// root is at position 1, its children are at positions 2 and 3 etc.
+    fn get_nodes(&self, indexes: &[usize]) -> Result<BTreeMap<usize, H::Digest>, MerkleTreeError> {
         if indexes.is_empty() {
             return Err(MerkleTreeError::TooFewLeafIndexes);
         }
@@ -195,6 +239,8 @@ impl<H: Hasher> BatchMerkleProof<H> {
 
             // hash sibling nodes into their parent
             let parent = H::merge(&buf);
+            v.insert(offset + index, buf[0]);
+            v.insert(offset + index + 1, buf[1]);
 
             let parent_index = (offset + index) >> 1;
             v.insert(parent_index, parent);
@@ -226,6 +272,7 @@ impl<H: Hasher> BatchMerkleProof<H> {
                     }
                     sibling = self.nodes[i][pointer];
                     proof_pointers[i] += 1;
+                    v.insert(sibling_index, sibling);
                 }
 
                 // get the node from the map of hashed nodes
@@ -252,7 +299,7 @@ impl<H: Hasher> BatchMerkleProof<H> {
                 i += 1;
             }
         }
-        v.remove(&1).ok_or(MerkleTreeError::InvalidProof)
+        Ok(v)
     }
 
     // SERIALIZATION / DESERIALIZATION
@@ -232,6 +232,33 @@ fn verify_batch() {
     assert!(MerkleTree::verify_batch(tree.root(), &[0, 1, 2, 3, 4, 5, 6, 7], &proof).is_ok());
 }
 
+#[test]
+fn get_batch_paths() {
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+    let tree = MerkleTree::<Blake3_256>::new(leaves).unwrap();
+
+    // paths extracted from a batch proof should be the same as individually built paths
+    for indexes in [
+        vec![1],
+        vec![2, 1],
+        vec![1, 3, 6],
+        vec![7, 0, 1, 2, 3, 4, 5, 6],
+    ]
+    .iter()
+    {
+        let proof = tree.prove_batch(indexes).unwrap();
+        let expected = indexes
+            .iter()
+            .map(|&i| tree.prove(i).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(expected, proof.get_paths(indexes).unwrap());
+    }
+
+    // paths cannot be extracted for a different number of index pairs than covered by the proof
+    let proof = tree.prove_batch(&[1, 6]).unwrap();
+    assert!(proof.get_paths(&[1, 3, 6]).is_err());
+}
+
 proptest! {
     #[test]
     fn prove_n_verify(tree in random_blake3_merkle_tree(128),
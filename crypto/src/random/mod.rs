@@ -6,7 +6,10 @@
 use crate::{errors::RandomCoinError, Digest, Hasher};
 use core::{convert::TryInto, marker::PhantomData};
 use math::{FieldElement, StarkField};
-use utils::collections::Vec;
+use utils::{collections::Vec, Serializable};
+
+mod transcript;
+pub use transcript::{IntegerSampling, Transcript, TranscriptEntry};
 
 // RANDOM COIN
 // ================================================================================================
@@ -63,6 +66,7 @@ where
 {
     seed: H::Digest,
     counter: u64,
+    transcript: Option<Transcript>,
     _base_field: PhantomData<B>,
 }
 
@@ -75,10 +79,43 @@ impl<B: StarkField, H: Hasher> RandomCoin<B, H> {
         RandomCoin {
             seed,
             counter: 0,
+            transcript: None,
             _base_field: PhantomData,
         }
     }
 
+    /// Returns a new random coin instantiated with the provided `seed` which records all values
+    /// absorbed into and drawn from it into a [Transcript].
+    ///
+    /// The coin behaves exactly the same as a coin instantiated via [new()](RandomCoin::new);
+    /// the recorded transcript can be retrieved via [transcript()](RandomCoin::transcript).
+    ///
+    /// # Examples
+    /// ```
+    /// # use winter_crypto::{RandomCoin, Hasher, TranscriptEntry, hashers::Blake3_256};
+    /// # use math::fields::f128::BaseElement;
+    /// let mut coin = RandomCoin::<BaseElement, Blake3_256<BaseElement>>::with_transcript(&[1, 2]);
+    /// coin.reseed(Blake3_256::<BaseElement>::hash(&[3, 4]));
+    /// coin.draw::<BaseElement>().unwrap();
+    /// coin.draw_integers(4, 16).unwrap();
+    ///
+    /// let transcript = coin.transcript().unwrap();
+    /// assert_eq!(4, transcript.entries().len());
+    /// assert_eq!(TranscriptEntry::Seed(vec![1, 2]), transcript.entries()[0]);
+    ///
+    /// // replaying the transcript should reproduce all of the recorded draws
+    /// assert!(transcript
+    ///     .replay::<BaseElement, BaseElement, Blake3_256<BaseElement>>()
+    ///     .is_ok());
+    /// ```
+    pub fn with_transcript(seed: &[u8]) -> Self {
+        let mut coin = Self::new(seed);
+        let mut transcript = Transcript::default();
+        transcript.push(TranscriptEntry::Seed(seed.to_vec()));
+        coin.transcript = Some(transcript);
+        coin
+    }
+
     // RESEEDING
     // --------------------------------------------------------------------------------------------
 
@@ -105,6 +142,7 @@ impl<B: StarkField, H: Hasher> RandomCoin<B, H> {
     pub fn reseed(&mut self, data: H::Digest) {
         self.seed = H::merge(&[self.seed, data]);
         self.counter = 0;
+        self.record(|| TranscriptEntry::AbsorbDigest(data.to_bytes()));
     }
 
     /// Reseeds the coin with the specified value by setting the new seed to hash(`seed` ||
@@ -131,11 +169,18 @@ impl<B: StarkField, H: Hasher> RandomCoin<B, H> {
     pub fn reseed_with_int(&mut self, value: u64) {
         self.seed = H::merge_with_int(self.seed, value);
         self.counter = 0;
+        self.record(|| TranscriptEntry::AbsorbInt(value));
     }
 
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
+    /// Returns the transcript of all values absorbed into and drawn from this coin, or None if
+    /// the coin was not instantiated via [with_transcript()](RandomCoin::with_transcript).
+    pub fn transcript(&self) -> Option<&Transcript> {
+        self.transcript.as_ref()
+    }
+
     /// Returns the number of leading zeros in the seed if it is interpreted as an integer in
     /// big-endian byte order.
     ///
@@ -183,11 +228,14 @@ impl<B: StarkField, H: Hasher> RandomCoin<B, H> {
         for _ in 0..200 {
             // get the next pseudo-random value and take the first ELEMENT_BYTES from it
             let value = self.next();
-            let bytes = &value.as_bytes()[..E::ELEMENT_BYTES as usize];
+            let bytes = &value.as_bytes()[..E::ELEMENT_BYTES];
 
             // check if the bytes can be converted into a valid field element; if they can,
             // return; otherwise try again
             if let Some(element) = E::from_random_bytes(bytes) {
+                self.record(|| {
+                    TranscriptEntry::DrawElement(E::elements_as_bytes(&[element]).to_vec())
+                });
                 return Ok(element);
             }
         }
@@ -269,13 +317,146 @@ impl<B: StarkField, H: Hasher> RandomCoin<B, H> {
         // draw values from PRNG until we get as many unique values as specified by num_queries
         let mut values = Vec::new();
         for _ in 0..1000 {
-            // get the next pseudo-random value and read the first 8 bytes from it
-            let bytes: [u8; 8] = self.next().as_bytes()[..8].try_into().unwrap();
+            let value = self.next_integer(v_mask);
+            if values.contains(&value) {
+                continue;
+            }
+            values.push(value);
+            if values.len() == num_values {
+                break;
+            }
+        }
 
-            // convert to integer and limit the integer to the number of bits which can fit
-            // into the specified domain
-            let value = (u64::from_le_bytes(bytes) & v_mask) as usize;
+        if values.len() < num_values {
+            return Err(RandomCoinError::FailedToDrawIntegers(
+                num_values,
+                values.len(),
+                1000,
+            ));
+        }
 
+        self.record(|| TranscriptEntry::DrawIntegers {
+            sampling: IntegerSampling::Distinct,
+            domain_size,
+            values: values.clone(),
+        });
+        Ok(values)
+    }
+
+    /// Returns a vector of integers selected independently from the range [0, domain_size).
+    ///
+    /// Unlike [draw_integers()](RandomCoin::draw_integers), every value is drawn from the entire
+    /// range regardless of the values drawn before it; thus, the returned vector may contain
+    /// duplicates.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// - `domain_size` is not a power of two.
+    /// - `num_values` is greater than or equal to `domain_size`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use winter_crypto::{RandomCoin, hashers::Blake3_256};
+    /// # use math::fields::f128::BaseElement;
+    /// let mut coin = RandomCoin::<BaseElement, Blake3_256<BaseElement>>::new(&[1, 2, 3, 4]);
+    ///
+    /// let num_values = 20;
+    /// let domain_size = 64;
+    /// let values = coin.draw_integers_with_replacement(num_values, domain_size);
+    ///
+    /// assert_eq!(num_values, values.len());
+    /// assert!(values.iter().all(|&value| value < domain_size));
+    /// ```
+    pub fn draw_integers_with_replacement(
+        &mut self,
+        num_values: usize,
+        domain_size: usize,
+    ) -> Vec<usize> {
+        assert!(
+            domain_size.is_power_of_two(),
+            "domain size must be a power of two"
+        );
+        assert!(
+            num_values < domain_size,
+            "number of values must be smaller than domain size"
+        );
+
+        let v_mask = (domain_size - 1) as u64;
+        let values: Vec<usize> = (0..num_values).map(|_| self.next_integer(v_mask)).collect();
+
+        self.record(|| TranscriptEntry::DrawIntegers {
+            sampling: IntegerSampling::WithReplacement,
+            domain_size,
+            values: values.clone(),
+        });
+        values
+    }
+
+    /// Returns a vector of unique integers selected from the range [0, domain_size) such that
+    /// the values are spread evenly across `num_strata` strata of the range.
+    ///
+    /// Stratum `s` consists of all integers in the range which are equal to `s` modulo
+    /// `num_strata`, and the value at position `i` of the returned vector is selected from
+    /// stratum `i % num_strata`.
+    ///
+    /// # Errors
+    /// Returns an error if the specified number of unique integers could not be generated
+    /// after 1000 calls to the PRNG.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// - `domain_size` is not a power of two.
+    /// - `num_strata` is not a power of two or is greater than `domain_size`.
+    /// - `num_values` is greater than or equal to `domain_size`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use winter_crypto::{RandomCoin, hashers::Blake3_256};
+    /// # use math::fields::f128::BaseElement;
+    /// let mut coin = RandomCoin::<BaseElement, Blake3_256<BaseElement>>::new(&[1, 2, 3, 4]);
+    ///
+    /// let num_values = 20;
+    /// let domain_size = 64;
+    /// let num_strata = 8;
+    /// let values = coin.draw_integers_stratified(num_values, domain_size, num_strata).unwrap();
+    ///
+    /// assert_eq!(num_values, values.len());
+    /// for (i, value) in values.iter().enumerate() {
+    ///     assert!(*value < domain_size);
+    ///     assert_eq!(i % num_strata, value % num_strata);
+    /// }
+    /// ```
+    pub fn draw_integers_stratified(
+        &mut self,
+        num_values: usize,
+        domain_size: usize,
+        num_strata: usize,
+    ) -> Result<Vec<usize>, RandomCoinError> {
+        assert!(
+            domain_size.is_power_of_two(),
+            "domain size must be a power of two"
+        );
+        assert!(
+            num_strata.is_power_of_two(),
+            "number of strata must be a power of two"
+        );
+        assert!(
+            num_strata <= domain_size,
+            "number of strata cannot be greater than domain size"
+        );
+        assert!(
+            num_values < domain_size,
+            "number of values must be smaller than domain size"
+        );
+
+        // values are drawn from a stratum as offsets, which are then mapped into the range as
+        // offset * num_strata + stratum
+        let v_mask = (domain_size / num_strata - 1) as u64;
+
+        let mut values = Vec::with_capacity(num_values);
+        for _ in 0..1000 {
+            let stratum = values.len() % num_strata;
+            let value = self.next_integer(v_mask) * num_strata + stratum;
             if values.contains(&value) {
                 continue;
             }
@@ -293,15 +474,203 @@ impl<B: StarkField, H: Hasher> RandomCoin<B, H> {
             ));
         }
 
+        self.record(|| TranscriptEntry::DrawIntegers {
+            sampling: IntegerSampling::Stratified(num_strata),
+            domain_size,
+            values: values.clone(),
+        });
         Ok(values)
     }
 
     // HELPER METHODS
     // --------------------------------------------------------------------------------------------
 
+    /// Appends the entry built by `entry` to the transcript of this coin if the transcript is
+    /// being recorded.
+    fn record(&mut self, entry: impl FnOnce() -> TranscriptEntry) {
+        if let Some(transcript) = self.transcript.as_mut() {
+            transcript.push(entry());
+        }
+    }
+
+    /// Returns an integer built from the first 8 bytes of the next pseudo-random value, limited
+    /// to the bits specified by `v_mask`.
+    fn next_integer(&mut self, v_mask: u64) -> usize {
+        let bytes: [u8; 8] = self.next().as_bytes()[..8].try_into().unwrap();
+        (u64::from_le_bytes(bytes) & v_mask) as usize
+    }
+
     /// Updates the state by incrementing the counter and returns hash(seed || counter)
     fn next(&mut self) -> H::Digest {
         self.counter += 1;
         H::merge_with_int(self.seed, self.counter)
     }
 }
+
+// PUBLIC COIN
+// ================================================================================================
+
+/// Defines an interface for a source of pseudo-random challenges in the STARK protocol.
+///
+/// In the non-interactive version of the protocol, all challenges are derived from a public coin
+/// which absorbs commitments sent by the prover. [RandomCoin] is the implementation used by
+/// default; custom implementations of this trait can be used to verify proofs generated by
+/// systems which derive challenges differently (e.g., via a sponge over field elements). The
+/// coin must be deterministic: two coins instantiated with the same seed and reseeded with the
+/// same values must produce the same challenges.
+pub trait PublicCoin: Sized {
+    /// Base field from which elements are drawn (directly or via its extensions).
+    type BaseField: StarkField;
+    /// Hash function whose digests are absorbed into the coin.
+    type Hasher: Hasher;
+
+    // REQUIRED METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new coin instantiated with the provided `seed`.
+    fn new(seed: &[u8]) -> Self;
+
+    /// Absorbs the specified `data` into the coin.
+    fn reseed(&mut self, data: <Self::Hasher as Hasher>::Digest);
+
+    /// Absorbs the specified integer `value` into the coin.
+    fn reseed_with_int(&mut self, value: u64);
+
+    /// Returns the number of leading zeros in the current state of the coin; this is used to
+    /// check proof-of-work.
+    fn leading_zeros(&self) -> u32;
+
+    /// Returns the number of leading zeros the coin would have after absorbing the specified
+    /// `value`, without changing the state of the coin.
+    fn check_leading_zeros(&self, value: u64) -> u32;
+
+    /// Returns the next pseudo-random field element.
+    ///
+    /// # Errors
+    /// Returns an error if a valid field element could not be drawn.
+    fn draw<E>(&mut self) -> Result<E, RandomCoinError>
+    where
+        E: FieldElement<BaseField = Self::BaseField>;
+
+    /// Returns a vector of `num_values` unique integers selected from the range [0, domain_size).
+    ///
+    /// # Errors
+    /// Returns an error if the specified number of unique integers could not be drawn.
+    fn draw_integers(
+        &mut self,
+        num_values: usize,
+        domain_size: usize,
+    ) -> Result<Vec<usize>, RandomCoinError>;
+
+    /// Returns a vector of `num_values` integers selected independently from the range
+    /// [0, domain_size).
+    fn draw_integers_with_replacement(
+        &mut self,
+        num_values: usize,
+        domain_size: usize,
+    ) -> Vec<usize>;
+
+    /// Returns a vector of `num_values` unique integers selected from the range [0, domain_size)
+    /// such that the value at position `i` is equal to `i % num_strata` modulo `num_strata`.
+    ///
+    /// # Errors
+    /// Returns an error if the specified number of unique integers could not be drawn.
+    fn draw_integers_stratified(
+        &mut self,
+        num_values: usize,
+        domain_size: usize,
+        num_strata: usize,
+    ) -> Result<Vec<usize>, RandomCoinError>;
+
+    // PROVIDED METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the next pair of pseudo-random field elements.
+    ///
+    /// # Errors
+    /// Returns an error if any of the field elements could not be drawn.
+    fn draw_pair<E>(&mut self) -> Result<(E, E), RandomCoinError>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        Ok((self.draw()?, self.draw()?))
+    }
+
+    /// Returns the next triplet of pseudo-random field elements.
+    ///
+    /// # Errors
+    /// Returns an error if any of the field elements could not be drawn.
+    fn draw_triple<E>(&mut self) -> Result<(E, E, E), RandomCoinError>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        Ok((self.draw()?, self.draw()?, self.draw()?))
+    }
+
+    /// Returns the transcript of all values absorbed into and drawn from this coin, if the coin
+    /// records one; the default implementation returns None.
+    fn transcript(&self) -> Option<&Transcript> {
+        None
+    }
+}
+
+impl<B: StarkField, H: Hasher> PublicCoin for RandomCoin<B, H> {
+    type BaseField = B;
+    type Hasher = H;
+
+    fn new(seed: &[u8]) -> Self {
+        RandomCoin::new(seed)
+    }
+
+    fn reseed(&mut self, data: H::Digest) {
+        RandomCoin::reseed(self, data)
+    }
+
+    fn reseed_with_int(&mut self, value: u64) {
+        RandomCoin::reseed_with_int(self, value)
+    }
+
+    fn leading_zeros(&self) -> u32 {
+        RandomCoin::leading_zeros(self)
+    }
+
+    fn check_leading_zeros(&self, value: u64) -> u32 {
+        RandomCoin::check_leading_zeros(self, value)
+    }
+
+    fn draw<E>(&mut self) -> Result<E, RandomCoinError>
+    where
+        E: FieldElement<BaseField = B>,
+    {
+        RandomCoin::draw(self)
+    }
+
+    fn draw_integers(
+        &mut self,
+        num_values: usize,
+        domain_size: usize,
+    ) -> Result<Vec<usize>, RandomCoinError> {
+        RandomCoin::draw_integers(self, num_values, domain_size)
+    }
+
+    fn draw_integers_with_replacement(
+        &mut self,
+        num_values: usize,
+        domain_size: usize,
+    ) -> Vec<usize> {
+        RandomCoin::draw_integers_with_replacement(self, num_values, domain_size)
+    }
+
+    fn draw_integers_stratified(
+        &mut self,
+        num_values: usize,
+        domain_size: usize,
+        num_strata: usize,
+    ) -> Result<Vec<usize>, RandomCoinError> {
+        RandomCoin::draw_integers_stratified(self, num_values, domain_size, num_strata)
+    }
+
+    fn transcript(&self) -> Option<&Transcript> {
+        RandomCoin::transcript(self)
+    }
+}
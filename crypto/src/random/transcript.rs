@@ -0,0 +1,391 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::RandomCoin;
+use crate::{errors::RandomCoinError, Hasher};
+use core::fmt;
+use math::{FieldElement, StarkField};
+use utils::{
+    collections::Vec, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+    SliceReader,
+};
+
+// TRANSCRIPT
+// ================================================================================================
+/// A log of all values absorbed into and drawn from a [RandomCoin].
+///
+/// A transcript is recorded by a coin instantiated via
+/// [with_transcript()](RandomCoin::with_transcript) function. Since the prover and the verifier
+/// derive all of their challenges from the same sequence of absorbed values, transcripts
+/// recorded by the prover and by the verifier for a valid proof are identical; comparing the two
+/// via [first_divergence()](Transcript::first_divergence) pinpoints the first step at which
+/// their views of the protocol differ.
+///
+/// A transcript can also be checked on its own via [replay()](Transcript::replay), which feeds
+/// the absorbed values into a fresh coin and makes sure that every recorded challenge is the one
+/// the coin actually produces. Thus, an auditor can confirm that the challenges were derived via
+/// Fiat-Shamir from the committed values without access to the prover or the verifier.
+///
+/// A transcript is dumped in a human-readable form via its [Display](fmt::Display)
+/// implementation, with one line per entry, and all byte strings encoded in hex.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Transcript {
+    entries: Vec<TranscriptEntry>,
+}
+
+/// A single operation performed on a [RandomCoin].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TranscriptEntry {
+    /// The coin was instantiated with the specified seed.
+    Seed(Vec<u8>),
+    /// The coin was reseeded with the specified serialized digest.
+    AbsorbDigest(Vec<u8>),
+    /// The coin was reseeded with the specified integer value.
+    AbsorbInt(u64),
+    /// A field element with the specified serialization was drawn from the coin.
+    DrawElement(Vec<u8>),
+    /// The specified integers were drawn from the range [0, domain_size) using the specified
+    /// sampling method.
+    DrawIntegers {
+        sampling: IntegerSampling,
+        domain_size: usize,
+        values: Vec<usize>,
+    },
+}
+
+/// Defines how a set of integers was drawn from a [RandomCoin].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntegerSampling {
+    /// Unique integers drawn via [draw_integers()](RandomCoin::draw_integers).
+    Distinct,
+    /// Independent integers drawn via
+    /// [draw_integers_with_replacement()](RandomCoin::draw_integers_with_replacement).
+    WithReplacement,
+    /// Unique integers spread across the specified number of strata drawn via
+    /// [draw_integers_stratified()](RandomCoin::draw_integers_stratified).
+    Stratified(usize),
+}
+
+impl Transcript {
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the entries of this transcript in the order in which they were recorded.
+    pub fn entries(&self) -> &[TranscriptEntry] {
+        &self.entries
+    }
+
+    /// Returns the index of the first entry at which this transcript differs from the `other`
+    /// transcript, or None if the transcripts are identical.
+    ///
+    /// If one transcript is a prefix of the other, the length of the shorter transcript is
+    /// returned.
+    pub fn first_divergence(&self, other: &Transcript) -> Option<usize> {
+        let position = self
+            .entries
+            .iter()
+            .zip(other.entries.iter())
+            .position(|(a, b)| a != b);
+        match position {
+            Some(index) => Some(index),
+            None if self.entries.len() != other.entries.len() => {
+                Some(self.entries.len().min(other.entries.len()))
+            }
+            None => None,
+        }
+    }
+
+    // REPLAY
+    // --------------------------------------------------------------------------------------------
+
+    /// Replays this transcript against a fresh random coin and checks that every recorded draw
+    /// matches the value drawn by the coin.
+    ///
+    /// The coin is instantiated with the hash function `H` over the base field `B`, and field
+    /// elements are drawn from the field `E`; these must be the same as the ones used when the
+    /// transcript was recorded.
+    ///
+    /// # Errors
+    /// Returns an error if the transcript does not start with a seed, contains more than one
+    /// seed, contains a digest which is not valid for `H`, or if a recorded draw differs from
+    /// the value drawn by the coin.
+    pub fn replay<B, E, H>(&self) -> Result<(), RandomCoinError>
+    where
+        B: StarkField,
+        E: FieldElement<BaseField = B>,
+        H: Hasher,
+    {
+        let mut coin = match self.entries.first() {
+            Some(TranscriptEntry::Seed(seed)) => RandomCoin::<B, H>::new(seed),
+            _ => return Err(RandomCoinError::InconsistentTranscript(0)),
+        };
+
+        for (i, entry) in self.entries.iter().enumerate().skip(1) {
+            let consistent = match entry {
+                TranscriptEntry::Seed(_) => false,
+                TranscriptEntry::AbsorbDigest(bytes) => match read_digest::<H>(bytes) {
+                    Some(digest) => {
+                        coin.reseed(digest);
+                        true
+                    }
+                    None => false,
+                },
+                TranscriptEntry::AbsorbInt(value) => {
+                    coin.reseed_with_int(*value);
+                    true
+                }
+                TranscriptEntry::DrawElement(bytes) => match coin.draw::<E>() {
+                    Ok(element) => E::elements_as_bytes(&[element]) == bytes.as_slice(),
+                    Err(_) => false,
+                },
+                TranscriptEntry::DrawIntegers {
+                    sampling,
+                    domain_size,
+                    values,
+                } => {
+                    // make sure the coin would not panic on the recorded parameters
+                    let num_strata = match sampling {
+                        IntegerSampling::Stratified(num_strata) => *num_strata,
+                        _ => 1,
+                    };
+                    if !domain_size.is_power_of_two()
+                        || !num_strata.is_power_of_two()
+                        || num_strata > *domain_size
+                        || values.len() >= *domain_size
+                    {
+                        false
+                    } else {
+                        let drawn = match sampling {
+                            IntegerSampling::Distinct => {
+                                coin.draw_integers(values.len(), *domain_size)
+                            }
+                            IntegerSampling::WithReplacement => {
+                                Ok(coin.draw_integers_with_replacement(values.len(), *domain_size))
+                            }
+                            IntegerSampling::Stratified(num_strata) => coin
+                                .draw_integers_stratified(values.len(), *domain_size, *num_strata),
+                        };
+                        drawn.as_ref() == Ok(values)
+                    }
+                }
+            };
+
+            if !consistent {
+                return Err(RandomCoinError::InconsistentTranscript(i));
+            }
+        }
+
+        Ok(())
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Appends the specified entry to this transcript.
+    pub(super) fn push(&mut self, entry: TranscriptEntry) {
+        self.entries.push(entry);
+    }
+}
+
+// DISPLAY
+// ================================================================================================
+
+impl fmt::Display for Transcript {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, entry) in self.entries.iter().enumerate() {
+            writeln!(f, "{}: {}", i, entry)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for TranscriptEntry {
+    #[rustfmt::skip]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Seed(bytes) => {
+                write!(f, "seed {}", HexBytes(bytes))
+            }
+            Self::AbsorbDigest(bytes) => {
+                write!(f, "absorb digest {}", HexBytes(bytes))
+            }
+            Self::AbsorbInt(value) => {
+                write!(f, "absorb int {}", value)
+            }
+            Self::DrawElement(bytes) => {
+                write!(f, "draw element {}", HexBytes(bytes))
+            }
+            Self::DrawIntegers { sampling, domain_size, values } => {
+                write!(f, "draw integers {:?} from [0, {}) {:?}", values, domain_size, sampling)
+            }
+        }
+    }
+}
+
+/// Formats a slice of bytes as a lowercase hex string.
+struct HexBytes<'a>(&'a [u8]);
+
+impl<'a> fmt::Display for HexBytes<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in self.0 {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+// SERIALIZATION
+// ================================================================================================
+
+impl Serializable for Transcript {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u32(self.entries.len() as u32);
+        for entry in self.entries.iter() {
+            entry.write_into(target);
+        }
+    }
+}
+
+impl Deserializable for Transcript {
+    /// Reads a transcript from the specified `source` and returns the result.
+    ///
+    /// # Errors
+    /// Returns an error if a valid transcript could not be read from the specified `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let num_entries = source.read_u32()? as usize;
+        let mut entries = Vec::new();
+        for _ in 0..num_entries {
+            entries.push(TranscriptEntry::read_from(source)?);
+        }
+        Ok(Transcript { entries })
+    }
+}
+
+impl Serializable for TranscriptEntry {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        match self {
+            Self::Seed(bytes) => {
+                target.write_u8(0);
+                write_bytes(target, bytes);
+            }
+            Self::AbsorbDigest(bytes) => {
+                target.write_u8(1);
+                write_bytes(target, bytes);
+            }
+            Self::AbsorbInt(value) => {
+                target.write_u8(2);
+                target.write_u64(*value);
+            }
+            Self::DrawElement(bytes) => {
+                target.write_u8(3);
+                write_bytes(target, bytes);
+            }
+            Self::DrawIntegers {
+                sampling,
+                domain_size,
+                values,
+            } => {
+                target.write_u8(4);
+                match sampling {
+                    IntegerSampling::Distinct => target.write_u8(0),
+                    IntegerSampling::WithReplacement => target.write_u8(1),
+                    IntegerSampling::Stratified(num_strata) => {
+                        target.write_u8(2);
+                        target.write_u64(*num_strata as u64);
+                    }
+                }
+                target.write_u64(*domain_size as u64);
+                target.write_u32(values.len() as u32);
+                for &value in values.iter() {
+                    target.write_u64(value as u64);
+                }
+            }
+        }
+    }
+}
+
+impl Deserializable for TranscriptEntry {
+    /// Reads a transcript entry from the specified `source` and returns the result.
+    ///
+    /// # Errors
+    /// Returns an error if a valid transcript entry could not be read from the specified
+    /// `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        match source.read_u8()? {
+            0 => Ok(Self::Seed(read_bytes(source)?)),
+            1 => Ok(Self::AbsorbDigest(read_bytes(source)?)),
+            2 => Ok(Self::AbsorbInt(source.read_u64()?)),
+            3 => Ok(Self::DrawElement(read_bytes(source)?)),
+            4 => {
+                let sampling = match source.read_u8()? {
+                    0 => IntegerSampling::Distinct,
+                    1 => IntegerSampling::WithReplacement,
+                    2 => IntegerSampling::Stratified(read_usize(source)?),
+                    value => {
+                        return Err(DeserializationError::InvalidValue(format!(
+                            "value {} cannot be deserialized as integer sampling method",
+                            value
+                        )))
+                    }
+                };
+                let domain_size = read_usize(source)?;
+                let num_values = source.read_u32()? as usize;
+                let mut values = Vec::new();
+                for _ in 0..num_values {
+                    values.push(read_usize(source)?);
+                }
+                Ok(Self::DrawIntegers {
+                    sampling,
+                    domain_size,
+                    values,
+                })
+            }
+            value => Err(DeserializationError::InvalidValue(format!(
+                "value {} cannot be deserialized as transcript entry",
+                value
+            ))),
+        }
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Writes the length of `bytes` followed by the bytes themselves into the `target`.
+fn write_bytes<W: ByteWriter>(target: &mut W, bytes: &[u8]) {
+    target.write_u32(bytes.len() as u32);
+    target.write_u8_slice(bytes);
+}
+
+/// Reads a byte vector written via [write_bytes()] from the `source`.
+fn read_bytes<R: ByteReader>(source: &mut R) -> Result<Vec<u8>, DeserializationError> {
+    let num_bytes = source.read_u32()? as usize;
+    source.read_u8_vec(num_bytes)
+}
+
+/// Reads a digest of the hash function `H` from `bytes`; returns None if the bytes do not
+/// encode exactly one valid digest.
+fn read_digest<H: Hasher>(bytes: &[u8]) -> Option<H::Digest> {
+    let mut source = SliceReader::new(bytes);
+    let digest = H::Digest::read_from(&mut source).ok()?;
+    if source.has_more_bytes() {
+        return None;
+    }
+    Some(digest)
+}
+
+/// Reads a 64-bit integer from the `source` and makes sure it fits into a `usize`.
+fn read_usize<R: ByteReader>(source: &mut R) -> Result<usize, DeserializationError> {
+    let value = source.read_u64()?;
+    if value > usize::MAX as u64 {
+        return Err(DeserializationError::InvalidValue(format!(
+            "value {} does not fit into usize",
+            value
+        )));
+    }
+    Ok(value as usize)
+}
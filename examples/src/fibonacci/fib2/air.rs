@@ -64,7 +64,7 @@ impl Air for FibAir {
     fn get_assertions(&self) -> Vec<Assertion<Self::BaseElement>> {
         // a valid Fibonacci sequence should start with two ones and terminate with
         // the expected result
-        let last_step = self.trace_length() - 1;
+        let last_step = self.last_step();
         vec![
             Assertion::single(0, 0, Self::BaseElement::ONE),
             Assertion::single(1, 0, Self::BaseElement::ONE),
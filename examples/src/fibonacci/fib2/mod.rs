@@ -9,11 +9,12 @@ use log::debug;
 use std::time::Instant;
 use winterfell::{
     math::{fields::f128::BaseElement, log2, FieldElement},
-    ProofOptions, StarkProof, VerifierError,
+    AcceptableOptions, ProofOptions, StarkProof, VerifierError,
 };
 
 mod air;
-use air::{build_trace, FibAir};
+use air::build_trace;
+pub(crate) use air::FibAir;
 
 #[cfg(test)]
 mod tests;
@@ -31,7 +32,7 @@ pub fn get_example(options: ExampleOptions, sequence_length: usize) -> Box<dyn E
 pub struct FibExample {
     options: ProofOptions,
     sequence_length: usize,
-    result: BaseElement,
+    pub(crate) result: BaseElement,
 }
 
 impl FibExample {
@@ -87,10 +88,12 @@ impl Example for FibExample {
     }
 
     fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
-        winterfell::verify::<FibAir>(proof, self.result)
+        let acceptable_options = AcceptableOptions::from_options(vec![self.options.clone()]);
+        winterfell::verify::<FibAir>(proof, self.result, &acceptable_options)
     }
 
     fn verify_with_wrong_inputs(&self, proof: StarkProof) -> Result<(), VerifierError> {
-        winterfell::verify::<FibAir>(proof, self.result + BaseElement::ONE)
+        let acceptable_options = AcceptableOptions::from_options(vec![self.options.clone()]);
+        winterfell::verify::<FibAir>(proof, self.result + BaseElement::ONE, &acceptable_options)
     }
 }
@@ -22,3 +22,114 @@ fn fib2_test_basic_proof_verification_fail() {
     let fib = Box::new(super::FibExample::new(16, build_proof_options(false)));
     crate::tests::test_basic_proof_verification_fail(fib);
 }
+
+#[test]
+fn fib2_test_basic_proof_verification_with_grinding() {
+    let options = build_proof_options(false).with_fri_layer_grinding_factor(4);
+    let fib = Box::new(super::FibExample::new(1024, options));
+    crate::tests::test_basic_proof_verification(fib);
+}
+
+#[test]
+fn fib2_test_constraint_report() {
+    use super::FibAir;
+    use crate::Example;
+    use winterfell::Air;
+
+    let options = build_proof_options(true);
+    let fib = super::FibExample::new(1024, options.clone());
+    let proof = fib.prove();
+    let proof_size = proof.to_bytes().len();
+
+    let air = FibAir::new(proof.get_trace_info(), fib.result, options);
+    let report = air.get_constraint_report();
+    assert_eq!(2, report.num_transition_constraints());
+    assert_eq!(3, report.num_boundary_constraints());
+
+    // the estimate should be within 10% of the actual proof size
+    let estimate = report.estimated_proof_size;
+    assert!(estimate * 10 > proof_size * 9 && estimate * 10 < proof_size * 11);
+}
+
+#[test]
+fn fib2_test_basic_proof_verification_with_stir() {
+    use winterfell::fri::LowDegreeTest;
+
+    let options = build_proof_options(false).with_low_degree_test(LowDegreeTest::Stir);
+    let fib = Box::new(super::FibExample::new(1024, options.clone()));
+    crate::tests::test_basic_proof_verification(fib);
+
+    let fib = Box::new(super::FibExample::new(1024, options));
+    crate::tests::test_basic_proof_verification_fail(fib);
+}
+
+#[test]
+fn fib2_test_basic_proof_verification_with_direct_low_degree_test() {
+    use winterfell::fri::LowDegreeTest;
+
+    let options = build_proof_options(true).with_low_degree_test(LowDegreeTest::Direct);
+    let fib = Box::new(super::FibExample::new(16, options.clone()));
+    crate::tests::test_basic_proof_verification(fib);
+
+    let fib = Box::new(super::FibExample::new(16, options));
+    crate::tests::test_basic_proof_verification_fail(fib);
+}
+
+#[test]
+fn fib2_test_serde_proof_verification() {
+    let fib = Box::new(super::FibExample::new(16, build_proof_options(false)));
+    crate::tests::test_serde_proof_verification(fib);
+}
+
+#[test]
+fn fib2_test_basic_proof_verification_with_fri_early_termination() {
+    let options = build_proof_options(false).with_fri_early_termination();
+    let fib = Box::new(super::FibExample::new(1024, options.clone()));
+    crate::tests::test_basic_proof_verification(fib);
+
+    let fib = Box::new(super::FibExample::new(1024, options));
+    crate::tests::test_basic_proof_verification_fail(fib);
+}
+
+#[test]
+fn fib2_test_basic_proof_verification_with_fri_deep_sampling() {
+    let options = build_proof_options(false).with_fri_deep_sampling();
+    let fib = Box::new(super::FibExample::new(1024, options.clone()));
+    crate::tests::test_basic_proof_verification(fib);
+
+    let fib = Box::new(super::FibExample::new(1024, options));
+    crate::tests::test_basic_proof_verification_fail(fib);
+}
+
+#[test]
+fn fib2_test_constraint_report_with_stir() {
+    use super::FibAir;
+    use crate::Example;
+    use winterfell::{
+        fri::LowDegreeTest,
+        math::{fields::f128::BaseElement, StarkField},
+        Air, StarkProof,
+    };
+    type QuadElement = <BaseElement as StarkField>::QuadExtension;
+
+    let options = build_proof_options(true)
+        .with_low_degree_test(LowDegreeTest::Stir)
+        .with_fri_layer_grinding_factor(4);
+    let fib = super::FibExample::new(1024, options.clone());
+    let proof = fib.prove();
+    let proof_bytes = proof.to_bytes();
+
+    // the trace of 512 steps is proven in two STIR rounds
+    assert_eq!(2, proof.fri_proof.num_ood_evaluations::<QuadElement>());
+    let proof = StarkProof::from_bytes(&proof_bytes).unwrap();
+    assert_eq!(
+        LowDegreeTest::Stir,
+        proof.options().to_fri_options().low_degree_test()
+    );
+
+    // the estimate should be within 10% of the actual proof size
+    let air = FibAir::new(proof.get_trace_info(), fib.result, options);
+    let estimate = air.get_constraint_report().estimated_proof_size;
+    assert!(estimate * 10 > proof_bytes.len() * 9 && estimate * 10 < proof_bytes.len() * 11);
+    assert!(fib.verify(proof).is_ok());
+}
@@ -71,7 +71,7 @@ impl Air for Fib8Air {
     fn get_assertions(&self) -> Vec<Assertion<Self::BaseElement>> {
         // assert that the trace starts with 7th and 8th terms of Fibonacci sequence (the first
         // 6 terms are not recorded in the trace), and ends with the expected result
-        let last_step = self.trace_length() - 1;
+        let last_step = self.last_step();
         vec![
             Assertion::single(0, 0, BaseElement::new(13)),
             Assertion::single(1, 0, BaseElement::new(21)),
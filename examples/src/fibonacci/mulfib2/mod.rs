@@ -9,11 +9,12 @@ use log::debug;
 use std::time::Instant;
 use winterfell::{
     math::{fields::f128::BaseElement, log2, FieldElement},
-    ProofOptions, StarkProof, VerifierError,
+    AcceptableOptions, ProofOptions, StarkProof, VerifierError,
 };
 
 mod air;
-use air::{build_trace, MulFib2Air};
+use air::build_trace;
+pub(crate) use air::MulFib2Air;
 
 #[cfg(test)]
 mod tests;
@@ -30,7 +31,7 @@ pub fn get_example(options: ExampleOptions, sequence_length: usize) -> Box<dyn E
 pub struct MulFib2Example {
     options: ProofOptions,
     sequence_length: usize,
-    result: BaseElement,
+    pub(crate) result: BaseElement,
 }
 
 impl MulFib2Example {
@@ -86,10 +87,12 @@ impl Example for MulFib2Example {
     }
 
     fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
-        winterfell::verify::<MulFib2Air>(proof, self.result)
+        let acceptable_options = AcceptableOptions::from_options(vec![self.options.clone()]);
+        winterfell::verify::<MulFib2Air>(proof, self.result, &acceptable_options)
     }
 
     fn verify_with_wrong_inputs(&self, proof: StarkProof) -> Result<(), VerifierError> {
-        winterfell::verify::<MulFib2Air>(proof, self.result + BaseElement::ONE)
+        let acceptable_options = AcceptableOptions::from_options(vec![self.options.clone()]);
+        winterfell::verify::<MulFib2Air>(proof, self.result + BaseElement::ONE, &acceptable_options)
     }
 }
@@ -82,7 +82,7 @@ impl Air for MulFib8Air {
     fn get_assertions(&self) -> Vec<Assertion<Self::BaseElement>> {
         // a valid multiplicative Fibonacci sequence should start with 1, 2 and terminate
         // with the expected result
-        let last_step = self.trace_length() - 1;
+        let last_step = self.last_step();
         vec![
             Assertion::single(0, 0, BaseElement::new(1)),
             Assertion::single(1, 0, BaseElement::new(2)),
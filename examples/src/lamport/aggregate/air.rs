@@ -9,8 +9,8 @@ use super::{
 use crate::utils::{are_equal, is_binary, is_zero, not, EvaluationResult};
 use winterfell::{
     math::{fields::f128::BaseElement, FieldElement},
-    Air, AirContext, Assertion, ByteWriter, EvaluationFrame, ProofOptions, Serializable, TraceInfo,
-    TransitionConstraintDegree,
+    Air, AirContext, Assertion, ByteWriter, EvaluationFrame, PeriodicMask, ProofOptions,
+    Serializable, TraceInfo, TransitionConstraintDegree,
 };
 
 // CONSTANTS
@@ -164,9 +164,7 @@ impl Air for LamportAggregateAir {
         let mut result = vec![];
 
         // signature cycle mask: 1023 zeros followed by 1 one
-        let mut sig_cycle_mask = vec![BaseElement::ZERO; SIG_CYCLE_LEN];
-        sig_cycle_mask[SIG_CYCLE_LEN - 1] = BaseElement::ONE;
-        result.push(sig_cycle_mask);
+        result.push(PeriodicMask::cycle_end(SIG_CYCLE_LEN).to_column());
 
         // build powers of two column
         let mut powers_of_two = vec![BaseElement::ZERO; SIG_CYCLE_LEN];
@@ -183,7 +181,7 @@ impl Air for LamportAggregateAir {
         result.push(powers_of_two);
 
         // add hash cycle mask (seven ones followed by a zero), and rescue round constants
-        result.push(HASH_CYCLE_MASK.to_vec());
+        result.push(PeriodicMask::new(HASH_CYCLE_LEN, 0..HASH_CYCLE_LEN - 1).to_column());
         result.append(&mut rescue::get_round_constants());
 
         result
@@ -267,16 +265,3 @@ fn transpose(values: &[[BaseElement; 2]]) -> (Vec<BaseElement>, Vec<BaseElement>
     }
     (r1, r2)
 }
-
-// MASKS
-// ================================================================================================
-const HASH_CYCLE_MASK: [BaseElement; HASH_CYCLE_LEN] = [
-    BaseElement::ONE,
-    BaseElement::ONE,
-    BaseElement::ONE,
-    BaseElement::ONE,
-    BaseElement::ONE,
-    BaseElement::ONE,
-    BaseElement::ONE,
-    BaseElement::ZERO,
-];
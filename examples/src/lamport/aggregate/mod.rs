@@ -11,7 +11,7 @@ use log::debug;
 use std::time::Instant;
 use winterfell::{
     math::{fields::f128::BaseElement, log2},
-    ProofOptions, StarkProof, VerifierError,
+    AcceptableOptions, ProofOptions, StarkProof, VerifierError,
 };
 
 mod trace;
@@ -136,7 +136,8 @@ impl Example for LamportAggregateExample {
             pub_keys: self.pub_keys.clone(),
             messages: self.messages.clone(),
         };
-        winterfell::verify::<LamportAggregateAir>(proof, pub_inputs)
+        let acceptable_options = AcceptableOptions::from_options(vec![self.options.clone()]);
+        winterfell::verify::<LamportAggregateAir>(proof, pub_inputs, &acceptable_options)
     }
 
     fn verify_with_wrong_inputs(&self, proof: StarkProof) -> Result<(), VerifierError> {
@@ -146,6 +147,7 @@ impl Example for LamportAggregateExample {
             pub_keys,
             messages: self.messages.clone(),
         };
-        winterfell::verify::<LamportAggregateAir>(proof, pub_inputs)
+        let acceptable_options = AcceptableOptions::from_options(vec![self.options.clone()]);
+        winterfell::verify::<LamportAggregateAir>(proof, pub_inputs, &acceptable_options)
     }
 }
@@ -10,8 +10,8 @@ use super::{
 use crate::utils::{are_equal, is_binary, is_zero, not, EvaluationResult};
 use winterfell::{
     math::{fields::f128::BaseElement, log2, FieldElement, StarkField},
-    Air, AirContext, Assertion, ByteWriter, EvaluationFrame, ProofOptions, Serializable, TraceInfo,
-    TransitionConstraintDegree,
+    Air, AirContext, Assertion, ByteWriter, EvaluationFrame, PeriodicMask, ProofOptions,
+    Serializable, TraceInfo, TransitionConstraintDegree,
 };
 
 // CONSTANTS
@@ -184,7 +184,7 @@ impl Air for LamportThresholdAir {
 
         // ----- assertions for the entire execution trace -----------------------------------------
 
-        let last_step = self.trace_length() - 1;
+        let last_step = self.last_step();
         assertions.extend_from_slice(&[
             // signature counter starts at zero and terminates with the expected count of signatures
             Assertion::single(27, 0, BaseElement::ZERO),
@@ -201,9 +201,7 @@ impl Air for LamportThresholdAir {
         let mut result = vec![];
 
         // signature cycle mask: 1023 zeros followed by 1 one
-        let mut sig_cycle_mask = vec![BaseElement::ZERO; SIG_CYCLE_LEN];
-        sig_cycle_mask[SIG_CYCLE_LEN - 1] = BaseElement::ONE;
-        result.push(sig_cycle_mask);
+        result.push(PeriodicMask::cycle_end(SIG_CYCLE_LEN).to_column());
 
         // build powers of two column
         let mut powers_of_two = vec![BaseElement::ZERO; SIG_CYCLE_LEN];
@@ -233,7 +231,7 @@ impl Air for LamportThresholdAir {
         result.push(m1_bits);
 
         // add hash cycle mask (seven ones followed by a zero), and rescue round constants
-        result.push(HASH_CYCLE_MASK.to_vec());
+        result.push(PeriodicMask::new(HASH_CYCLE_LEN, 0..HASH_CYCLE_LEN - 1).to_column());
         result.append(&mut rescue::get_round_constants());
 
         result
@@ -345,16 +343,3 @@ fn get_index_list(num_keys: usize) -> Vec<BaseElement> {
     }
     result
 }
-
-// MASKS
-// ================================================================================================
-const HASH_CYCLE_MASK: [BaseElement; HASH_CYCLE_LEN] = [
-    BaseElement::ONE,
-    BaseElement::ONE,
-    BaseElement::ONE,
-    BaseElement::ONE,
-    BaseElement::ONE,
-    BaseElement::ONE,
-    BaseElement::ONE,
-    BaseElement::ZERO,
-];
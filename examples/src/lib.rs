@@ -4,7 +4,9 @@
 // LICENSE file in the root directory of this source tree.
 
 use structopt::StructOpt;
-use winterfell::{FieldExtension, HashFunction, ProofOptions, StarkProof, VerifierError};
+use winterfell::{
+    FieldExtension, HashFunction, ProofOptions, QuerySampling, StarkProof, VerifierError,
+};
 
 pub mod fibonacci;
 #[cfg(feature = "std")]
@@ -12,7 +14,10 @@ pub mod lamport;
 #[cfg(feature = "std")]
 pub mod merkle;
 pub mod rescue;
+pub mod sum;
 pub mod utils;
+#[cfg(feature = "std")]
+pub mod vectors;
 
 #[cfg(test)]
 mod tests;
@@ -51,6 +56,10 @@ pub struct ExampleOptions {
     #[structopt(short = "g", long = "grinding", default_value = "16")]
     grinding_factor: u32,
 
+    /// Grinding factor for each FRI layer
+    #[structopt(long = "fri_grinding", default_value = "0")]
+    fri_grinding_factor: u32,
+
     /// Whether to use field extension for composition polynomial
     #[structopt(short = "e", long = "extension")]
     field_extension: bool,
@@ -58,6 +67,10 @@ pub struct ExampleOptions {
     /// Folding factor for FRI protocol
     #[structopt(short = "f", long = "folding", default_value = "8")]
     folding_factor: usize,
+
+    /// Strategy for deriving query positions from the query seed
+    #[structopt(long = "query_sampling", default_value = "distinct")]
+    query_sampling: String,
 }
 
 impl ExampleOptions {
@@ -75,6 +88,12 @@ impl ExampleOptions {
             "sha3_256" => HashFunction::Sha3_256,
             val => panic!("'{}' is not a valid hash function option", val),
         };
+        let query_sampling = match self.query_sampling.as_str() {
+            "distinct" => QuerySampling::Distinct,
+            "uniform" => QuerySampling::Uniform,
+            "stratified" => QuerySampling::StratifiedByCoset,
+            val => panic!("'{}' is not a valid query sampling option", val),
+        };
 
         ProofOptions::new(
             num_queries,
@@ -85,6 +104,8 @@ impl ExampleOptions {
             self.folding_factor,
             256,
         )
+        .with_fri_layer_grinding_factor(self.fri_grinding_factor)
+        .with_query_sampling(query_sampling)
     }
 }
 
@@ -121,6 +142,12 @@ pub enum ExampleType {
         #[structopt(short = "n", default_value = "1024")]
         chain_length: usize,
     },
+    /// Compute a sum of values in a Merkle-committed public column
+    Sum {
+        /// Number of values to sum up; must be one less than a power of two
+        #[structopt(short = "n", default_value = "1023")]
+        num_values: usize,
+    },
     /// Compute a root of a Merkle path using Rescue hash function
     #[cfg(feature = "std")]
     Merkle {
@@ -142,4 +169,15 @@ pub enum ExampleType {
         #[structopt(short = "n", default_value = "3")]
         num_signers: usize,
     },
+    /// Generate conformance test vectors for the example AIRs, or check existing ones
+    #[cfg(feature = "std")]
+    Vectors {
+        /// Directory into which test vectors are written, or from which they are read
+        #[structopt(short = "d", long = "dir", default_value = "vectors")]
+        dir: String,
+
+        /// Check test vectors in the directory rather than generating them
+        #[structopt(long = "check")]
+        check: bool,
+    },
 }
@@ -9,9 +9,9 @@ use std::time::Instant;
 use structopt::StructOpt;
 use winterfell::StarkProof;
 
-use examples::{fibonacci, rescue, ExampleOptions, ExampleType};
+use examples::{fibonacci, rescue, sum, ExampleOptions, ExampleType};
 #[cfg(feature = "std")]
-use examples::{lamport, merkle};
+use examples::{lamport, merkle, vectors};
 
 // EXAMPLE RUNNER
 // ================================================================================================
@@ -43,6 +43,7 @@ fn main() {
             fibonacci::mulfib8::get_example(options, sequence_length)
         }
         ExampleType::Rescue { chain_length } => rescue::get_example(options, chain_length),
+        ExampleType::Sum { num_values } => sum::get_example(options, num_values),
         #[cfg(feature = "std")]
         ExampleType::Merkle { tree_depth } => merkle::get_example(options, tree_depth),
         #[cfg(feature = "std")]
@@ -53,6 +54,11 @@ fn main() {
         ExampleType::LamportT { num_signers } => {
             lamport::threshold::get_example(options, num_signers)
         }
+        #[cfg(feature = "std")]
+        ExampleType::Vectors { dir, check } => {
+            run_test_vectors(std::path::Path::new(&dir), check);
+            return;
+        }
     };
 
     // generate proof
@@ -65,7 +71,11 @@ fn main() {
 
     let proof_bytes = proof.to_bytes();
     debug!("Proof size: {:.1} KB", proof_bytes.len() as f64 / 1024f64);
-    debug!("Proof security: {} bits", proof.security_level(true));
+    debug!(
+        "Proof security: {} bits ({} proven)",
+        proof.security_level(true),
+        proof.security_level(false)
+    );
     #[cfg(feature = "std")]
     debug!(
         "Proof hash: {}",
@@ -86,3 +96,27 @@ fn main() {
     }
     debug!("============================================================");
 }
+
+// TEST VECTORS
+// ================================================================================================
+
+#[cfg(feature = "std")]
+fn run_test_vectors(dir: &std::path::Path, check: bool) {
+    if check {
+        let failures = vectors::check_test_vectors(dir).unwrap();
+        for (name, reason) in failures.iter() {
+            debug!("Test vector {} failed: {}", name, reason);
+        }
+        debug!("{} test vector(s) failed", failures.len());
+        if !failures.is_empty() {
+            std::process::exit(1);
+        }
+    } else {
+        let vectors = vectors::write_test_vectors(dir).unwrap();
+        debug!(
+            "Wrote {} test vectors into {}",
+            vectors.len(),
+            dir.display()
+        );
+    }
+}
@@ -13,8 +13,8 @@ use crate::utils::{
 };
 use winterfell::{
     math::{fields::f128::BaseElement, FieldElement},
-    Air, AirContext, Assertion, ByteWriter, EvaluationFrame, ExecutionTrace, ProofOptions,
-    Serializable, TraceInfo, TransitionConstraintDegree,
+    Air, AirContext, Assertion, Deserializable, EvaluationFrame, ExecutionTrace, PeriodicMask,
+    ProofOptions, Serializable, TraceInfo, TransitionConstraintDegree,
 };
 
 // CONSTANTS
@@ -25,16 +25,12 @@ const TRACE_WIDTH: usize = 7;
 // MERKLE PATH VERIFICATION AIR
 // ================================================================================================
 
+#[derive(Serializable, Deserializable)]
+#[serialization(crate = "winterfell")]
 pub struct PublicInputs {
     pub tree_root: [BaseElement; 2],
 }
 
-impl Serializable for PublicInputs {
-    fn write_into<W: ByteWriter>(&self, target: &mut W) {
-        target.write(&self.tree_root[..]);
-    }
-}
-
 pub struct MerkleAir {
     context: AirContext<BaseElement>,
     tree_root: [BaseElement; 2],
@@ -114,7 +110,7 @@ impl Air for MerkleAir {
     fn get_assertions(&self) -> Vec<Assertion<Self::BaseElement>> {
         // assert that Merkle path resolves to the tree root, and that hash capacity
         // registers (registers 4 and 5) are reset to ZERO every 8 steps
-        let last_step = self.trace_length() - 1;
+        let last_step = self.last_step();
         vec![
             Assertion::single(0, last_step, self.tree_root[0]),
             Assertion::single(1, last_step, self.tree_root[1]),
@@ -124,7 +120,7 @@ impl Air for MerkleAir {
     }
 
     fn get_periodic_column_values(&self) -> Vec<Vec<Self::BaseElement>> {
-        let mut result = vec![HASH_CYCLE_MASK.to_vec()];
+        let mut result = vec![PeriodicMask::new(HASH_CYCLE_LEN, 0..NUM_HASH_ROUNDS).to_column()];
         result.append(&mut rescue::get_round_constants());
         result
     }
@@ -198,16 +194,3 @@ pub fn build_trace(
 
     trace
 }
-
-// MASKS
-// ================================================================================================
-const HASH_CYCLE_MASK: [BaseElement; HASH_CYCLE_LEN] = [
-    BaseElement::ONE,
-    BaseElement::ONE,
-    BaseElement::ONE,
-    BaseElement::ONE,
-    BaseElement::ONE,
-    BaseElement::ONE,
-    BaseElement::ONE,
-    BaseElement::ZERO,
-];
@@ -13,7 +13,7 @@ use std::time::Instant;
 use winterfell::{
     crypto::{Digest, MerkleTree},
     math::{fields::f128::BaseElement, log2, StarkField},
-    ProofOptions, StarkProof, VerifierError,
+    AcceptableOptions, ProofOptions, StarkProof, VerifierError,
 };
 
 mod air;
@@ -110,7 +110,8 @@ impl Example for MerkleExample {
         let pub_inputs = PublicInputs {
             tree_root: self.tree_root.to_elements(),
         };
-        winterfell::verify::<MerkleAir>(proof, pub_inputs)
+        let acceptable_options = AcceptableOptions::from_options(vec![self.options.clone()]);
+        winterfell::verify::<MerkleAir>(proof, pub_inputs, &acceptable_options)
     }
 
     fn verify_with_wrong_inputs(&self, proof: StarkProof) -> Result<(), VerifierError> {
@@ -118,7 +119,8 @@ impl Example for MerkleExample {
         let pub_inputs = PublicInputs {
             tree_root: [tree_root[1], tree_root[0]],
         };
-        winterfell::verify::<MerkleAir>(proof, pub_inputs)
+        let acceptable_options = AcceptableOptions::from_options(vec![self.options.clone()]);
+        winterfell::verify::<MerkleAir>(proof, pub_inputs, &acceptable_options)
     }
 }
 
@@ -7,8 +7,9 @@ use super::rescue;
 use crate::utils::{are_equal, is_zero, not, EvaluationResult};
 use winterfell::{
     math::{fields::f128::BaseElement, FieldElement},
-    Air, AirContext, Assertion, ByteWriter, EvaluationFrame, ExecutionTrace, ProofOptions,
-    Serializable, TraceInfo, TransitionConstraintDegree,
+    Air, AirContext, Assertion, ByteReader, ByteWriter, Deserializable, DeserializationError,
+    EvaluationFrame, ExecutionTrace, PeriodicMask, ProofOptions, Serializable, TraceInfo,
+    TransitionConstraintDegree,
 };
 
 // CONSTANTS
@@ -18,26 +19,6 @@ const CYCLE_LENGTH: usize = 16;
 const NUM_HASH_ROUNDS: usize = 14;
 const TRACE_WIDTH: usize = 4;
 
-/// Specifies steps on which Rescue transition function is applied.
-const CYCLE_MASK: [BaseElement; CYCLE_LENGTH] = [
-    BaseElement::ONE,
-    BaseElement::ONE,
-    BaseElement::ONE,
-    BaseElement::ONE,
-    BaseElement::ONE,
-    BaseElement::ONE,
-    BaseElement::ONE,
-    BaseElement::ONE,
-    BaseElement::ONE,
-    BaseElement::ONE,
-    BaseElement::ONE,
-    BaseElement::ONE,
-    BaseElement::ONE,
-    BaseElement::ONE,
-    BaseElement::ZERO,
-    BaseElement::ZERO,
-];
-
 // RESCUE AIR
 // ================================================================================================
 
@@ -53,6 +34,20 @@ impl Serializable for PublicInputs {
     }
 }
 
+impl Deserializable for PublicInputs {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let seed = [
+            BaseElement::read_from(source)?,
+            BaseElement::read_from(source)?,
+        ];
+        let result = [
+            BaseElement::read_from(source)?,
+            BaseElement::read_from(source)?,
+        ];
+        Ok(PublicInputs { seed, result })
+    }
+}
+
 pub struct RescueAir {
     context: AirContext<BaseElement>,
     seed: [BaseElement; 2],
@@ -111,7 +106,7 @@ impl Air for RescueAir {
 
     fn get_assertions(&self) -> Vec<Assertion<Self::BaseElement>> {
         // Assert starting and ending values of the hash chain
-        let last_step = self.trace_length() - 1;
+        let last_step = self.last_step();
         vec![
             Assertion::single(0, 0, self.seed[0]),
             Assertion::single(1, 0, self.seed[1]),
@@ -121,7 +116,7 @@ impl Air for RescueAir {
     }
 
     fn get_periodic_column_values(&self) -> Vec<Vec<Self::BaseElement>> {
-        let mut result = vec![CYCLE_MASK.to_vec()];
+        let mut result = vec![PeriodicMask::new(CYCLE_LENGTH, 0..NUM_HASH_ROUNDS).to_column()];
         result.append(&mut rescue::get_round_constants());
         result
     }
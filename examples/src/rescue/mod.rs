@@ -8,14 +8,15 @@ use log::debug;
 use std::time::Instant;
 use winterfell::{
     math::{fields::f128::BaseElement, log2, FieldElement},
-    ProofOptions, StarkProof, VerifierError,
+    AcceptableOptions, ProofOptions, StarkProof, VerifierError,
 };
 
 #[allow(clippy::module_inception)]
 mod rescue;
 
 mod air;
-use air::{build_trace, PublicInputs, RescueAir};
+use air::build_trace;
+pub(crate) use air::{PublicInputs, RescueAir};
 
 #[cfg(test)]
 mod tests;
@@ -33,8 +34,8 @@ pub fn get_example(options: ExampleOptions, chain_length: usize) -> Box<dyn Exam
 pub struct RescueExample {
     options: ProofOptions,
     chain_length: usize,
-    seed: [BaseElement; 2],
-    result: [BaseElement; 2],
+    pub(crate) seed: [BaseElement; 2],
+    pub(crate) result: [BaseElement; 2],
 }
 
 impl RescueExample {
@@ -97,7 +98,8 @@ impl Example for RescueExample {
             seed: self.seed,
             result: self.result,
         };
-        winterfell::verify::<RescueAir>(proof, pub_inputs)
+        let acceptable_options = AcceptableOptions::from_options(vec![self.options.clone()]);
+        winterfell::verify::<RescueAir>(proof, pub_inputs, &acceptable_options)
     }
 
     fn verify_with_wrong_inputs(&self, proof: StarkProof) -> Result<(), VerifierError> {
@@ -105,7 +107,8 @@ impl Example for RescueExample {
             seed: self.seed,
             result: [self.result[0], self.result[1] + BaseElement::ONE],
         };
-        winterfell::verify::<RescueAir>(proof, pub_inputs)
+        let acceptable_options = AcceptableOptions::from_options(vec![self.options.clone()]);
+        winterfell::verify::<RescueAir>(proof, pub_inputs, &acceptable_options)
     }
 }
 
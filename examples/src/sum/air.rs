@@ -0,0 +1,122 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::utils::are_equal;
+use winterfell::{
+    math::{fields::f128::BaseElement, FieldElement},
+    Air, AirContext, Assertion, ByteWriter, CommittedColumn, EvaluationFrame, ExecutionTrace,
+    ProofOptions, Serializable, TraceInfo, TransitionConstraintDegree,
+};
+
+// CONSTANTS
+// ================================================================================================
+
+const TRACE_WIDTH: usize = 2;
+
+// SUM AIR
+// ================================================================================================
+
+pub struct PublicInputs {
+    pub commitment: Vec<u8>,
+    pub result: BaseElement,
+}
+
+impl Serializable for PublicInputs {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8_slice(&self.commitment);
+        target.write(self.result);
+    }
+}
+
+pub struct SumAir {
+    context: AirContext<BaseElement>,
+    commitment: Vec<u8>,
+    result: BaseElement,
+}
+
+impl Air for SumAir {
+    type BaseElement = BaseElement;
+    type PublicInputs = PublicInputs;
+
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    fn new(trace_info: TraceInfo, pub_inputs: PublicInputs, options: ProofOptions) -> Self {
+        let degrees = vec![TransitionConstraintDegree::new(1)];
+        assert_eq!(TRACE_WIDTH, trace_info.width());
+        SumAir {
+            context: AirContext::new(trace_info, degrees, options),
+            commitment: pub_inputs.commitment,
+            result: pub_inputs.result,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseElement> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseElement>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        // expected state width is 2 field elements
+        debug_assert_eq!(TRACE_WIDTH, current.len());
+        debug_assert_eq!(TRACE_WIDTH, next.len());
+
+        // the second register accumulates values of the first register
+        result[0] = are_equal(next[1], current[1] + current[0]);
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseElement>> {
+        // the accumulator must start at zero and terminate with the expected result; the values
+        // which are summed up are defined by the committed column and are not known to the
+        // verifier
+        let last_step = self.last_step();
+        vec![
+            Assertion::single(1, 0, BaseElement::ZERO),
+            Assertion::single(1, last_step, self.result),
+        ]
+    }
+
+    fn get_committed_columns(&self) -> Vec<CommittedColumn> {
+        vec![CommittedColumn::new(0, self.commitment.clone())]
+    }
+}
+
+// TRACE BUILDER
+// ================================================================================================
+
+/// Builds an execution trace for summing up the provided values; the values are placed into
+/// the first register, and are padded with a single zero so that the number of steps is a power
+/// of two.
+pub fn build_trace(values: &[BaseElement]) -> ExecutionTrace<BaseElement> {
+    let column = build_column(values);
+    let mut trace = ExecutionTrace::new(TRACE_WIDTH, column.len());
+    trace.fill(
+        |state| {
+            state[0] = column[0];
+            state[1] = BaseElement::ZERO;
+        },
+        |step, state| {
+            state[1] += state[0];
+            state[0] = column[step + 1];
+        },
+    );
+    trace
+}
+
+/// Returns values of the committed column for the provided values.
+pub fn build_column(values: &[BaseElement]) -> Vec<BaseElement> {
+    assert!(
+        (values.len() + 1).is_power_of_two(),
+        "number of values must be one less than a power of 2"
+    );
+    let mut column = values.to_vec();
+    column.push(BaseElement::ZERO);
+    column
+}
@@ -0,0 +1,110 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{Example, ExampleOptions};
+use log::debug;
+use std::time::Instant;
+use winterfell::{
+    math::{fields::f128::BaseElement, log2, FieldElement},
+    AcceptableOptions, CommittedColumn, ProofOptions, StarkProof, VerifierError,
+};
+
+mod air;
+use air::{build_column, build_trace, PublicInputs, SumAir};
+
+#[cfg(test)]
+mod tests;
+
+// COMMITTED SUM EXAMPLE
+// ================================================================================================
+
+pub fn get_example(options: ExampleOptions, num_values: usize) -> Box<dyn Example> {
+    Box::new(SumExample::new(num_values, options.to_proof_options(28, 8)))
+}
+
+pub struct SumExample {
+    options: ProofOptions,
+    values: Vec<BaseElement>,
+    commitment: Vec<u8>,
+    result: BaseElement,
+}
+
+impl SumExample {
+    pub fn new(num_values: usize, options: ProofOptions) -> SumExample {
+        assert!(
+            (num_values + 1).is_power_of_two(),
+            "number of values must be one less than a power of 2"
+        );
+        let values = (0..num_values as u128)
+            .map(|i| BaseElement::new(i * i + 1))
+            .collect::<Vec<_>>();
+
+        // commit to the values; only the commitment needs to be known to the verifier
+        let now = Instant::now();
+        let commitment = CommittedColumn::build_commitment(&build_column(&values), &options);
+        debug!(
+            "Committed to {} values in {} ms",
+            num_values,
+            now.elapsed().as_millis()
+        );
+
+        let result = values.iter().fold(BaseElement::ZERO, |acc, &v| acc + v);
+
+        SumExample {
+            options,
+            values,
+            commitment,
+            result,
+        }
+    }
+}
+
+// EXAMPLE IMPLEMENTATION
+// ================================================================================================
+
+impl Example for SumExample {
+    fn prove(&self) -> StarkProof {
+        debug!(
+            "Generating proof for summing up {} committed values\n\
+            ---------------------",
+            self.values.len()
+        );
+
+        // generate execution trace
+        let now = Instant::now();
+        let trace = build_trace(&self.values);
+        debug!(
+            "Generated execution trace of {} registers and 2^{} steps in {} ms",
+            trace.width(),
+            log2(trace.length()),
+            now.elapsed().as_millis()
+        );
+
+        // generate the proof
+        let pub_inputs = PublicInputs {
+            commitment: self.commitment.clone(),
+            result: self.result,
+        };
+        winterfell::prove::<SumAir>(trace, pub_inputs, self.options.clone()).unwrap()
+    }
+
+    fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let pub_inputs = PublicInputs {
+            commitment: self.commitment.clone(),
+            result: self.result,
+        };
+        let acceptable_options = AcceptableOptions::from_options(vec![self.options.clone()]);
+        winterfell::verify::<SumAir>(proof, pub_inputs, &acceptable_options)
+    }
+
+    fn verify_with_wrong_inputs(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let pub_inputs = PublicInputs {
+            commitment: self.commitment.clone(),
+            result: self.result + BaseElement::ONE,
+        };
+        let acceptable_options = AcceptableOptions::from_options(vec![self.options.clone()]);
+        winterfell::verify::<SumAir>(proof, pub_inputs, &acceptable_options)
+    }
+}
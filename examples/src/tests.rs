@@ -15,3 +15,34 @@ pub fn test_basic_proof_verification_fail(e: Box<dyn Example>) {
     let verified = e.verify_with_wrong_inputs(proof);
     assert!(verified.is_err());
 }
+
+pub fn test_serde_proof_verification(e: Box<dyn Example>) {
+    let proof = e.prove();
+
+    // proofs embedded into human-readable formats are encoded as hex strings
+    let json = serde_json::to_string(&proof).unwrap();
+    assert_eq!(format!("\"{}\"", hex::encode(proof.to_bytes())), json);
+    let parsed: winterfell::StarkProof = serde_json::from_str(&json).unwrap();
+    assert_eq!(proof, parsed);
+
+    // all components of a proof can be embedded separately
+    let options = serde_json::to_value(proof.options()).unwrap();
+    assert_eq!(proof.options(), &serde_json::from_value(options).unwrap());
+    let commitments = serde_json::to_value(&proof.commitments).unwrap();
+    assert_eq!(
+        proof.commitments,
+        serde_json::from_value(commitments).unwrap()
+    );
+    let queries = serde_json::to_value(&proof.constraint_queries).unwrap();
+    assert_eq!(
+        proof.constraint_queries,
+        serde_json::from_value(queries).unwrap()
+    );
+    let fri_proof = serde_json::to_value(&proof.fri_proof).unwrap();
+    assert_eq!(proof.fri_proof, serde_json::from_value(fri_proof).unwrap());
+
+    // proofs embedded into binary formats are encoded as byte strings
+    let cbor = serde_cbor::to_vec(&proof).unwrap();
+    let parsed: winterfell::StarkProof = serde_cbor::from_slice(&cbor).unwrap();
+    assert!(e.verify(parsed).is_ok());
+}
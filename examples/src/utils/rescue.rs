@@ -57,7 +57,7 @@ impl Rescue128 {
         for &element in data {
             self.state[self.idx] += element;
             self.idx += 1;
-            if self.idx % RATE_WIDTH == 0 {
+            if self.idx.is_multiple_of(RATE_WIDTH) {
                 apply_permutation(&mut self.state);
                 self.idx = 0;
             }
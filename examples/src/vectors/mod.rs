@@ -0,0 +1,333 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Deterministic conformance test vectors for the built-in example AIRs.
+//!
+//! Proof generation in Winterfell is deterministic: the public coin is seeded with the public
+//! inputs and the proof context, and no other source of randomness is used. Thus, proving the
+//! same computation with the same options always produces the same proof bytes. This module
+//! uses this property to produce a fixed set of test vectors (fixed traces of the example
+//! computations proven with fixed sets of options) which can be used by alternative verifier
+//! implementations (e.g., JavaScript verifiers or verification circuits) to check that they
+//! accept and reject exactly the same proofs as the reference verifier.
+
+use crate::{
+    fibonacci::{fib2, fib8, mulfib2, mulfib8},
+    rescue, Example,
+};
+use std::{fmt, fs, io, path::Path};
+use winterfell::{
+    math::{fields::f128::BaseElement, FieldElement},
+    AcceptableOptions, Air, ByteReader, Deserializable, FieldExtension, HashFunction, ProofOptions,
+    Serializable, SliceReader, StarkProof,
+};
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Identifiers of the AIRs for which test vectors are generated.
+pub const VECTOR_AIRS: [&str; 5] = ["fib2", "fib8", "mulfib2", "mulfib8", "rescue"];
+
+/// Extension of files into which test vectors are written.
+const FILE_EXTENSION: &str = "txt";
+
+// TEST VECTOR
+// ================================================================================================
+
+/// A serialized proof together with serialized public inputs of the computation, and the
+/// expected outcome of verifying the proof against these inputs.
+///
+/// A test vector is written into a text file as a list of `key: value` lines in the following
+/// order: `name`, `air`, `valid` (`true` or `false`), `pub_inputs` and `proof`. Public inputs
+/// and the proof are hex-encoded; the proof is serialized via `StarkProof::to_bytes()` method,
+/// and public inputs are serialized via the `Serializable` trait. Lines starting with `#` are
+/// ignored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestVector {
+    /// Name of the test vector; this is also the name of the file the vector is written into.
+    pub name: String,
+    /// Identifier of the AIR describing the computation; one of [VECTOR_AIRS].
+    pub air: String,
+    /// Whether the proof is expected to be accepted by the verifier.
+    pub valid: bool,
+    /// Serialized public inputs of the computation.
+    pub pub_inputs: Vec<u8>,
+    /// Serialized proof.
+    pub proof: Vec<u8>,
+}
+
+impl TestVector {
+    /// Parses a test vector from the specified text.
+    ///
+    /// # Errors
+    /// Returns an error if any of the fields is missing or malformed, or if the fields are not
+    /// in the expected order.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut lines = text
+            .lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty() && !line.starts_with('#'));
+        let mut read_field = |key: &str| match lines.next().and_then(|line| line.split_once(':')) {
+            Some((k, value)) if k.trim() == key => Ok(value.trim().to_string()),
+            _ => Err(format!("field '{}' is missing", key)),
+        };
+
+        let name = read_field("name")?;
+        let air = read_field("air")?;
+        let valid = match read_field("valid")?.as_str() {
+            "true" => true,
+            "false" => false,
+            value => {
+                return Err(format!(
+                    "'{}' is not a valid value for field 'valid'",
+                    value
+                ))
+            }
+        };
+        let pub_inputs = hex::decode(read_field("pub_inputs")?)
+            .map_err(|err| format!("public inputs are not valid hex: {}", err))?;
+        let proof = hex::decode(read_field("proof")?)
+            .map_err(|err| format!("proof is not valid hex: {}", err))?;
+
+        Ok(TestVector {
+            name,
+            air,
+            valid,
+            pub_inputs,
+            proof,
+        })
+    }
+
+    /// Writes this test vector into a file named after the vector in the specified directory.
+    pub fn write_to_dir(&self, dir: &Path) -> io::Result<()> {
+        let path = dir.join(&self.name).with_extension(FILE_EXTENSION);
+        fs::write(path, self.to_string())
+    }
+}
+
+impl fmt::Display for TestVector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "# Winterfell test vector")?;
+        writeln!(f, "name: {}", self.name)?;
+        writeln!(f, "air: {}", self.air)?;
+        writeln!(f, "valid: {}", self.valid)?;
+        writeln!(f, "pub_inputs: {}", hex::encode(&self.pub_inputs))?;
+        writeln!(f, "proof: {}", hex::encode(&self.proof))
+    }
+}
+
+// GENERATION
+// ================================================================================================
+
+/// Returns the full set of test vectors.
+///
+/// For each of [VECTOR_AIRS], a fixed instance of the computation is proven with every set of
+/// options returned by [vector_options()]. For each proof, two test vectors are produced: one
+/// with the correct public inputs, which must be accepted by the verifier, and one with altered
+/// public inputs, which must be rejected. The returned vectors are always the same.
+pub fn generate_test_vectors() -> Vec<TestVector> {
+    let mut vectors = Vec::new();
+    for air in VECTOR_AIRS {
+        for (options_name, options) in vector_options() {
+            let (proof, pub_inputs, wrong_pub_inputs) = prove_instance(air, options);
+            let name = format!("{}_{}", air, options_name);
+            vectors.push(TestVector {
+                name: name.clone(),
+                air: air.to_string(),
+                valid: true,
+                pub_inputs,
+                proof: proof.clone(),
+            });
+            vectors.push(TestVector {
+                name: format!("{}_wrong_inputs", name),
+                air: air.to_string(),
+                valid: false,
+                pub_inputs: wrong_pub_inputs,
+                proof,
+            });
+        }
+    }
+    vectors
+}
+
+/// Writes all test vectors into the specified directory; the directory is created if it does
+/// not exist.
+pub fn write_test_vectors(dir: &Path) -> io::Result<Vec<TestVector>> {
+    fs::create_dir_all(dir)?;
+    let vectors = generate_test_vectors();
+    for vector in vectors.iter() {
+        vector.write_to_dir(dir)?;
+    }
+    Ok(vectors)
+}
+
+/// Returns named sets of proof options with which test vectors are generated; the sets cover
+/// all supported hash functions, both field extension settings, FRI folding factors of 4 and 8,
+/// and proof-of-work for the query seed and for FRI layers.
+pub fn vector_options() -> Vec<(&'static str, ProofOptions)> {
+    vec![
+        (
+            "blake3_256",
+            ProofOptions::new(
+                28,
+                8,
+                0,
+                HashFunction::Blake3_256,
+                FieldExtension::None,
+                4,
+                256,
+            ),
+        ),
+        (
+            "blake3_192_quad",
+            ProofOptions::new(
+                24,
+                16,
+                4,
+                HashFunction::Blake3_192,
+                FieldExtension::Quadratic,
+                8,
+                256,
+            ),
+        ),
+        (
+            "sha3_256_grinding",
+            ProofOptions::new(
+                32,
+                8,
+                8,
+                HashFunction::Sha3_256,
+                FieldExtension::None,
+                8,
+                128,
+            )
+            .with_fri_layer_grinding_factor(4),
+        ),
+    ]
+}
+
+/// Proves a fixed instance of the computation described by the specified AIR, and returns the
+/// proof together with the serialized correct and altered public inputs.
+fn prove_instance(air: &str, options: ProofOptions) -> (Vec<u8>, Vec<u8>, Vec<u8>) {
+    match air {
+        "fib2" => {
+            let example = fib2::FibExample::new(1024, options);
+            let result = example.result;
+            build_instance(example.prove(), result, result + BaseElement::ONE)
+        }
+        "fib8" => {
+            let example = fib8::Fib8Example::new(1024, options);
+            let result = example.result;
+            build_instance(example.prove(), result, result + BaseElement::ONE)
+        }
+        "mulfib2" => {
+            let example = mulfib2::MulFib2Example::new(1024, options);
+            let result = example.result;
+            build_instance(example.prove(), result, result + BaseElement::ONE)
+        }
+        "mulfib8" => {
+            let example = mulfib8::MulFib8Example::new(1024, options);
+            let result = example.result;
+            build_instance(example.prove(), result, result + BaseElement::ONE)
+        }
+        "rescue" => {
+            let example = rescue::RescueExample::new(32, options);
+            let pub_inputs = rescue::PublicInputs {
+                seed: example.seed,
+                result: example.result,
+            };
+            let wrong_pub_inputs = rescue::PublicInputs {
+                seed: example.seed,
+                result: [example.result[0] + BaseElement::ONE, example.result[1]],
+            };
+            build_instance(example.prove(), pub_inputs, wrong_pub_inputs)
+        }
+        _ => panic!("'{}' is not a valid test vector AIR", air),
+    }
+}
+
+fn build_instance<I: Serializable>(
+    proof: StarkProof,
+    pub_inputs: I,
+    wrong_pub_inputs: I,
+) -> (Vec<u8>, Vec<u8>, Vec<u8>) {
+    (
+        proof.to_bytes(),
+        pub_inputs.to_bytes(),
+        wrong_pub_inputs.to_bytes(),
+    )
+}
+
+// VERIFICATION HARNESS
+// ================================================================================================
+
+/// Checks that the reference verifier produces the expected outcome for the specified test
+/// vector.
+///
+/// The proof is verified against the public inputs of the vector using the AIR identified by
+/// the vector; any set of proof options is accepted.
+///
+/// # Errors
+/// Returns an error if the AIR of the vector is unknown, if the proof or public inputs of a
+/// valid vector cannot be parsed, if a valid vector is rejected, or if an invalid vector is
+/// accepted.
+pub fn check_test_vector(vector: &TestVector) -> Result<(), String> {
+    let result = match vector.air.as_str() {
+        "fib2" => verify_vector::<fib2::FibAir>(vector),
+        "fib8" => verify_vector::<fib8::Fib8Air>(vector),
+        "mulfib2" => verify_vector::<mulfib2::MulFib2Air>(vector),
+        "mulfib8" => verify_vector::<mulfib8::MulFib8Air>(vector),
+        "rescue" => verify_vector::<rescue::RescueAir>(vector),
+        air => return Err(format!("'{}' is not a valid test vector AIR", air)),
+    };
+
+    match (vector.valid, result) {
+        (true, Ok(())) | (false, Err(_)) => Ok(()),
+        (true, Err(reason)) => Err(format!("valid proof was rejected: {}", reason)),
+        (false, Ok(())) => Err("invalid proof was accepted".to_string()),
+    }
+}
+
+/// Reads all test vectors from the specified directory and checks each of them via
+/// [check_test_vector()]; returns the names of the vectors which did not pass the check
+/// together with the reasons.
+pub fn check_test_vectors(dir: &Path) -> io::Result<Vec<(String, String)>> {
+    let mut paths = fs::read_dir(dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+    paths.retain(|path| path.extension().is_some_and(|ext| ext == FILE_EXTENSION));
+    paths.sort();
+
+    let mut failures = Vec::new();
+    for path in paths {
+        let text = fs::read_to_string(&path)?;
+        let result = TestVector::parse(&text).and_then(|vector| check_test_vector(&vector));
+        if let Err(reason) = result {
+            failures.push((path.display().to_string(), reason));
+        }
+    }
+    Ok(failures)
+}
+
+/// Parses the proof and the public inputs of the specified vector, and verifies the proof for
+/// the computation described by `AIR`; public inputs with trailing bytes are rejected.
+fn verify_vector<AIR: Air>(vector: &TestVector) -> Result<(), String>
+where
+    AIR::PublicInputs: Deserializable,
+{
+    let proof = StarkProof::from_bytes(&vector.proof)
+        .map_err(|err| format!("proof could not be parsed: {}", err))?;
+    let mut source = SliceReader::new(&vector.pub_inputs);
+    let pub_inputs = AIR::PublicInputs::read_from(&mut source)
+        .map_err(|err| format!("public inputs could not be parsed: {}", err))?;
+    if source.has_more_bytes() {
+        return Err("public inputs contain trailing bytes".to_string());
+    }
+    winterfell::verify::<AIR>(proof, pub_inputs, &AcceptableOptions::any())
+        .map_err(|err| err.to_string())
+}
@@ -0,0 +1,63 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{check_test_vector, check_test_vectors, generate_test_vectors, TestVector};
+use std::{env, fs};
+
+#[test]
+fn test_vectors_are_deterministic() {
+    let vectors = generate_test_vectors();
+    assert_eq!(generate_test_vectors(), vectors);
+    for vector in vectors.iter() {
+        assert_eq!(Ok(()), check_test_vector(vector), "{}", vector.name);
+    }
+}
+
+#[test]
+fn test_vectors_detect_mismatches() {
+    let vectors = generate_test_vectors();
+    let valid = vectors.iter().find(|vector| vector.valid).unwrap();
+
+    // a tampered proof should be rejected
+    let mut tampered = valid.clone();
+    let last = tampered.proof.len() - 1;
+    tampered.proof[last] ^= 1;
+    assert!(check_test_vector(&tampered).is_err());
+
+    // a vector claiming that a valid proof is invalid should not pass the check
+    let mut flipped = valid.clone();
+    flipped.valid = false;
+    assert!(check_test_vector(&flipped).is_err());
+
+    // public inputs with trailing bytes should be rejected
+    let mut extended = valid.clone();
+    extended.pub_inputs.push(0);
+    assert!(check_test_vector(&extended).is_err());
+
+    let mut unknown = valid.clone();
+    unknown.air = "unknown".to_string();
+    assert!(check_test_vector(&unknown).is_err());
+}
+
+#[test]
+fn test_vectors_round_trip_through_files() {
+    let vectors = generate_test_vectors();
+    let vector = &vectors[0];
+    assert_eq!(Ok(vector.clone()), TestVector::parse(&vector.to_string()));
+    assert!(TestVector::parse("name: fib2_blake3_256\nair: fib2\n").is_err());
+
+    let dir = env::temp_dir().join(format!("winterfell_vectors_{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    vectors[0].write_to_dir(&dir).unwrap();
+    vectors[1].write_to_dir(&dir).unwrap();
+    assert!(check_test_vectors(&dir).unwrap().is_empty());
+
+    let mut flipped = vectors[1].clone();
+    flipped.valid = true;
+    flipped.write_to_dir(&dir).unwrap();
+    let failures = check_test_vectors(&dir).unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(1, failures.len());
+}
@@ -0,0 +1,226 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! This crate contains C-compatible bindings for Winterfell STARK verifier.
+//!
+//! The bindings expose a single stateless `winterfell_verify()` function with C calling
+//! convention, which makes it possible to verify Winterfell proofs from other languages (e.g., Go
+//! or C++) by linking a static library. The function verifies a serialized proof against
+//! serialized public inputs for one of the AIRs registered at compile time under a numeric
+//! identifier, and returns a [VerifierStatus] code.
+//!
+//! # Usage
+//! To build a verifier library for a set of computations, create a `staticlib` (or `cdylib`)
+//! crate which depends on this crate, and register AIRs of the computations together with proof
+//! options acceptable for each of them via [register_airs!] macro:
+//!
+//! ```ignore
+//! winterfell_ffi::register_airs! {
+//!     1 => (FibAir, AcceptableOptions::any().with_min_security(96, true)),
+//!     2 => (RescueAir, AcceptableOptions::from_options(vec![rescue_options()])),
+//! }
+//! ```
+//!
+//! Each registered AIR must have public inputs which implement [Deserializable] trait. The
+//! library can then be linked into a C program and called as follows:
+//!
+//! ```c
+//! int32_t winterfell_verify(uint32_t air_id,
+//!                           const uint8_t *proof, size_t proof_len,
+//!                           const uint8_t *pub_inputs, size_t pub_inputs_len);
+//!
+//! if (winterfell_verify(1, proof, proof_len, pub_inputs, pub_inputs_len) == 0) {
+//!     // the proof is valid
+//! }
+//! ```
+//!
+//! # Untrusted input
+//! Proofs and public inputs are expected to come from untrusted sources. Thus, before a proof is
+//! verified, its size is checked against [MAX_PROOF_SIZE], and both the proof and the public
+//! inputs are parsed in full; inputs which contain trailing bytes are rejected. A panic must not
+//! unwind across the FFI boundary; thus, if the verifier panics (e.g., because the AIR rejects
+//! proof options via a panic), the panic is caught and [VerifierStatus::Panic] is returned.
+
+use std::panic;
+use verifier::{
+    AcceptableOptions, Air, ByteReader, Deserializable, DeserializationError, SliceReader,
+    StarkProof, VerifierError,
+};
+
+pub use verifier;
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Maximum size of a proof (in bytes) accepted by [verify_bytes()] function; currently set to
+/// 4 MB, which is an order of magnitude more than the size of proofs for the largest practical
+/// computations.
+pub const MAX_PROOF_SIZE: usize = 1 << 22;
+
+// VERIFIER STATUS
+// ================================================================================================
+
+/// Describes the outcome of verifying a serialized proof; the status is returned to the caller
+/// of `winterfell_verify()` function as an `int32_t` code.
+#[repr(i32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VerifierStatus {
+    /// The proof is valid.
+    Ok = 0,
+    /// No AIR is registered under the specified identifier.
+    UnknownAir = 1,
+    /// A null pointer was passed for a non-empty input.
+    NullPointer = 2,
+    /// The size of the proof exceeds [MAX_PROOF_SIZE].
+    ProofTooLarge = 3,
+    /// The proof could not be deserialized.
+    MalformedProof = 4,
+    /// The proof was serialized using a format version not supported by this verifier.
+    UnsupportedProofVersion = 5,
+    /// The public inputs could not be deserialized.
+    MalformedPublicInputs = 6,
+    /// The proof was generated with options which are not acceptable for the AIR.
+    UnacceptableProofOptions = 7,
+    /// The proof does not attest to a correct execution of the computation.
+    VerificationFailed = 8,
+    /// The verifier panicked while verifying the proof.
+    Panic = 9,
+}
+
+// VERIFIER
+// ================================================================================================
+
+/// Verifies the proof serialized into `proof_bytes` against public inputs serialized into
+/// `pub_input_bytes` for the computation specified by `AIR` type parameter, and returns the
+/// status of verification. The proof is accepted only if it was generated with options specified
+/// by `acceptable_options`.
+///
+/// A panic raised during verification is caught and reported as [VerifierStatus::Panic].
+pub fn verify_bytes<AIR: Air>(
+    proof_bytes: &[u8],
+    pub_input_bytes: &[u8],
+    acceptable_options: &AcceptableOptions,
+) -> VerifierStatus
+where
+    AIR::PublicInputs: Deserializable,
+{
+    panic::catch_unwind(|| {
+        verify_unchecked::<AIR>(proof_bytes, pub_input_bytes, acceptable_options)
+    })
+    .unwrap_or(VerifierStatus::Panic)
+}
+
+/// Builds a byte slice from the specified pointer and length; returns None if `ptr` is null and
+/// `len` is not zero.
+///
+/// # Safety
+/// If `ptr` is not null, it must point to `len` bytes which are valid for reads for the
+/// lifetime `'a`.
+pub unsafe fn slice_from_raw<'a>(ptr: *const u8, len: usize) -> Option<&'a [u8]> {
+    if ptr.is_null() {
+        if len == 0 {
+            Some(&[])
+        } else {
+            None
+        }
+    } else {
+        Some(std::slice::from_raw_parts(ptr, len))
+    }
+}
+
+/// Generates functions which verify proofs for the specified set of AIRs.
+///
+/// The macro takes a list of `id => (AirType, acceptable_options)` entries, where `id` is an
+/// integer literal identifying the AIR, and `acceptable_options` is an expression evaluating to
+/// [AcceptableOptions] for the AIR, and generates two functions in the module in which it is
+/// invoked:
+/// * `verify_registered(air_id: u32, proof: &[u8], pub_inputs: &[u8])`, which verifies the proof
+///   via [verify_bytes()] function for the AIR registered under the specified identifier against
+///   the options acceptable for this AIR, and returns the [VerifierStatus].
+/// * `winterfell_verify(air_id, proof, proof_len, pub_inputs, pub_inputs_len)`, which is
+///   exported with C calling convention under an unmangled name, and returns the
+///   [VerifierStatus] as an `int32_t` code.
+#[macro_export]
+macro_rules! register_airs {
+    ($($id:literal => ($air:ty, $options:expr)),+ $(,)?) => {
+        /// Verifies the specified proof against the specified public inputs for the AIR
+        /// registered under the identifier `air_id`.
+        pub fn verify_registered(
+            air_id: u32,
+            proof: &[u8],
+            pub_inputs: &[u8],
+        ) -> $crate::VerifierStatus {
+            match air_id {
+                $($id => $crate::verify_bytes::<$air>(proof, pub_inputs, &$options),)+
+                _ => $crate::VerifierStatus::UnknownAir,
+            }
+        }
+
+        /// Verifies the proof of `proof_len` bytes located at `proof` against the public inputs
+        /// of `pub_inputs_len` bytes located at `pub_inputs` for the AIR registered under the
+        /// identifier `air_id`, and returns the status of verification; 0 means that the proof
+        /// is valid.
+        ///
+        /// # Safety
+        /// Each pointer must either be null with the corresponding length set to zero, or point
+        /// to the specified number of bytes which are valid for reads for the duration of the
+        /// call.
+        #[no_mangle]
+        pub unsafe extern "C" fn winterfell_verify(
+            air_id: u32,
+            proof: *const u8,
+            proof_len: usize,
+            pub_inputs: *const u8,
+            pub_inputs_len: usize,
+        ) -> i32 {
+            let proof = $crate::slice_from_raw(proof, proof_len);
+            let pub_inputs = $crate::slice_from_raw(pub_inputs, pub_inputs_len);
+            let status = match (proof, pub_inputs) {
+                (Some(proof), Some(pub_inputs)) => verify_registered(air_id, proof, pub_inputs),
+                _ => $crate::VerifierStatus::NullPointer,
+            };
+            status as i32
+        }
+    };
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Parses and verifies the specified proof; panics raised by the verifier are not caught.
+fn verify_unchecked<AIR: Air>(
+    proof_bytes: &[u8],
+    pub_input_bytes: &[u8],
+    acceptable_options: &AcceptableOptions,
+) -> VerifierStatus
+where
+    AIR::PublicInputs: Deserializable,
+{
+    if proof_bytes.len() > MAX_PROOF_SIZE {
+        return VerifierStatus::ProofTooLarge;
+    }
+    let proof = match StarkProof::from_bytes(proof_bytes) {
+        Ok(proof) => proof,
+        Err(DeserializationError::UnsupportedVersion(..)) => {
+            return VerifierStatus::UnsupportedProofVersion
+        }
+        Err(_) => return VerifierStatus::MalformedProof,
+    };
+
+    let mut source = SliceReader::new(pub_input_bytes);
+    let pub_inputs = match AIR::PublicInputs::read_from(&mut source) {
+        Ok(pub_inputs) if !source.has_more_bytes() => pub_inputs,
+        _ => return VerifierStatus::MalformedPublicInputs,
+    };
+
+    match verifier::verify::<AIR>(proof, pub_inputs, acceptable_options) {
+        Ok(()) => VerifierStatus::Ok,
+        Err(VerifierError::UnacceptableProofOptions) => VerifierStatus::UnacceptableProofOptions,
+        Err(_) => VerifierStatus::VerificationFailed,
+    }
+}
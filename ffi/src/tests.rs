@@ -0,0 +1,231 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{VerifierStatus, MAX_PROOF_SIZE};
+use prover::{
+    math::{fields::f128::BaseElement, FieldElement},
+    Air, AirContext, Assertion, EvaluationFrame, ExecutionTrace, FieldExtension, HashFunction,
+    ProofOptions, Serializable, StarkProof, TraceInfo, TransitionConstraintDegree,
+};
+use std::ptr;
+use verifier::AcceptableOptions;
+
+crate::register_airs! {
+    1 => (FibAir, AcceptableOptions::any().with_min_num_queries(28)),
+    2 => (PanicAir, AcceptableOptions::any()),
+}
+
+// REGISTERED VERIFIER TESTS
+// ================================================================================================
+
+#[test]
+fn verify_registered_air() {
+    let (proof, result) = build_fib_proof(64, 28);
+    let proof_bytes = proof.to_bytes();
+    let result_bytes = result.to_bytes();
+    assert_eq!(
+        VerifierStatus::Ok,
+        verify_registered(1, &proof_bytes, &result_bytes)
+    );
+
+    // verification against a wrong result should fail
+    let wrong_result = (result + BaseElement::ONE).to_bytes();
+    assert_eq!(
+        VerifierStatus::VerificationFailed,
+        verify_registered(1, &proof_bytes, &wrong_result)
+    );
+
+    // proofs generated with options which are not acceptable should be rejected
+    let (weak_proof, result) = build_fib_proof(64, 27);
+    assert_eq!(
+        VerifierStatus::UnacceptableProofOptions,
+        verify_registered(1, &weak_proof.to_bytes(), &result.to_bytes())
+    );
+
+    // proofs can be verified only for registered AIRs
+    assert_eq!(
+        VerifierStatus::UnknownAir,
+        verify_registered(3, &proof_bytes, &result_bytes)
+    );
+
+    // panics raised by the AIR should be reported rather than propagated
+    assert_eq!(
+        VerifierStatus::Panic,
+        verify_registered(2, &proof_bytes, &result_bytes)
+    );
+}
+
+#[test]
+fn verify_malformed_inputs() {
+    let (proof, result) = build_fib_proof(64, 28);
+    let proof_bytes = proof.to_bytes();
+    let result_bytes = result.to_bytes();
+
+    let mut extended_proof = proof_bytes.clone();
+    extended_proof.push(0);
+    for bytes in [&proof_bytes[..proof_bytes.len() - 1], &extended_proof] {
+        assert_eq!(
+            VerifierStatus::MalformedProof,
+            verify_registered(1, bytes, &result_bytes)
+        );
+    }
+
+    let mut future_proof = proof_bytes.clone();
+    future_proof[1] = StarkProof::VERSION + 1;
+    assert_eq!(
+        VerifierStatus::UnsupportedProofVersion,
+        verify_registered(1, &future_proof, &result_bytes)
+    );
+
+    let mut extended_result = result_bytes.clone();
+    extended_result.push(0);
+    for bytes in [&result_bytes[1..], &extended_result] {
+        assert_eq!(
+            VerifierStatus::MalformedPublicInputs,
+            verify_registered(1, &proof_bytes, bytes)
+        );
+    }
+
+    let oversized_proof = vec![0u8; MAX_PROOF_SIZE + 1];
+    assert_eq!(
+        VerifierStatus::ProofTooLarge,
+        verify_registered(1, &oversized_proof, &result_bytes)
+    );
+}
+
+// C INTERFACE TESTS
+// ================================================================================================
+
+#[test]
+fn verify_via_c_interface() {
+    let (proof, result) = build_fib_proof(64, 28);
+    let proof_bytes = proof.to_bytes();
+    let result_bytes = result.to_bytes();
+
+    let status = unsafe {
+        winterfell_verify(
+            1,
+            proof_bytes.as_ptr(),
+            proof_bytes.len(),
+            result_bytes.as_ptr(),
+            result_bytes.len(),
+        )
+    };
+    assert_eq!(VerifierStatus::Ok as i32, status);
+
+    // null pointers are accepted only for empty inputs
+    let status = unsafe { winterfell_verify(1, ptr::null(), 1, result_bytes.as_ptr(), 16) };
+    assert_eq!(VerifierStatus::NullPointer as i32, status);
+    let status = unsafe { winterfell_verify(1, ptr::null(), 0, result_bytes.as_ptr(), 16) };
+    assert_eq!(VerifierStatus::MalformedProof as i32, status);
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Generates a proof of computing `n`th term of a Fibonacci sequence using the specified number
+/// of queries, and returns it together with the result of the computation.
+fn build_fib_proof(n: usize, num_queries: usize) -> (StarkProof, BaseElement) {
+    let mut trace = ExecutionTrace::new(2, n / 2);
+    trace.fill(
+        |state| {
+            state[0] = BaseElement::ONE;
+            state[1] = BaseElement::ONE;
+        },
+        |_, state| {
+            state[0] += state[1];
+            state[1] += state[0];
+        },
+    );
+    let result = trace.get(1, n / 2 - 1);
+
+    let options = ProofOptions::new(
+        num_queries,
+        8,
+        0,
+        HashFunction::Blake3_256,
+        FieldExtension::None,
+        4,
+        256,
+    );
+    let proof = prover::prove::<FibAir>(trace, result, options).unwrap();
+    (proof, result)
+}
+
+/// AIR for a Fibonacci sequence computed two terms per step.
+struct FibAir {
+    context: AirContext<BaseElement>,
+    result: BaseElement,
+}
+
+impl Air for FibAir {
+    type BaseElement = BaseElement;
+    type PublicInputs = BaseElement;
+
+    fn new(trace_info: TraceInfo, pub_inputs: BaseElement, options: ProofOptions) -> Self {
+        let degrees = vec![
+            TransitionConstraintDegree::new(1),
+            TransitionConstraintDegree::new(1),
+        ];
+        FibAir {
+            context: AirContext::new(trace_info, degrees, options),
+            result: pub_inputs,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseElement> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseElement>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        result[0] = next[0] - (current[0] + current[1]);
+        result[1] = next[1] - (current[1] + next[0]);
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseElement>> {
+        let last_step = self.last_step();
+        vec![
+            Assertion::single(0, 0, BaseElement::ONE),
+            Assertion::single(1, 0, BaseElement::ONE),
+            Assertion::single(1, last_step, self.result),
+        ]
+    }
+}
+
+/// AIR which panics when instantiated.
+struct PanicAir(AirContext<BaseElement>);
+
+impl Air for PanicAir {
+    type BaseElement = BaseElement;
+    type PublicInputs = BaseElement;
+
+    fn new(_trace_info: TraceInfo, _pub_inputs: BaseElement, _options: ProofOptions) -> Self {
+        panic!("AIR cannot be instantiated");
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseElement> {
+        &self.0
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseElement>>(
+        &self,
+        _frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        _result: &mut [E],
+    ) {
+        unreachable!()
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseElement>> {
+        unreachable!()
+    }
+}
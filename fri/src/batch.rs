@@ -0,0 +1,248 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crypto::{Hasher, RandomCoin, RandomCoinError};
+use math::{log2, FieldElement, StarkField};
+use utils::collections::Vec;
+
+// FRI BATCH
+// ================================================================================================
+/// Describes a set of polynomials with distinct degree bounds which are proven to be of low
+/// degree via a single instance of the FRI protocol.
+///
+/// Rather than running FRI for each polynomial separately, the polynomials are combined into a
+/// single polynomial via a random linear combination, and FRI is executed against the combined
+/// polynomial using the max degree of the batch. To make sure that each polynomial satisfies its
+/// own degree bound (rather than just the max degree of the batch), the degree of every
+/// polynomial is first raised to the max degree of the batch. Specifically, for a polynomial
+/// $f_i$ with degree bound $d_i$, the combined polynomial is computed as:
+///
+/// $$
+/// C(x) = \sum_{i} f_i(x) \cdot (\alpha_i + \beta_i \cdot x^{D - d_i})
+/// $$
+///
+/// where $D$ is the max degree of the batch, and $\alpha_i$, $\beta_i$ are random coefficients
+/// drawn from the public coin after the polynomials have been committed to. If the degree of
+/// any $f_i$ exceeds $d_i$, the degree of $C(x)$ exceeds $D$ with high probability.
+///
+/// The prover combines evaluations of the polynomials over the entire evaluation domain via
+/// [combine_evaluations()](FriBatch::combine_evaluations) and passes the result to
+/// [FriProver::build_layers()](crate::FriProver::build_layers). The verifier combines evaluations
+/// of the polynomials at the queried positions via
+/// [combine_queries()](FriBatch::combine_queries) and passes the result to
+/// [FriVerifier::verify()](crate::FriVerifier::verify). In both cases, FRI is instantiated with
+/// [max_degree()](FriBatch::max_degree) of the batch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FriBatch {
+    max_degree: usize,
+    degrees: Vec<usize>,
+}
+
+impl FriBatch {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new batch of polynomials with the specified degree bounds which is proven against
+    /// the specified max degree.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `degrees` is empty.
+    /// * Any of the `degrees` is greater than `max_degree`.
+    pub fn new(max_degree: usize, degrees: &[usize]) -> Self {
+        assert!(
+            !degrees.is_empty(),
+            "a FRI batch must contain at least one polynomial"
+        );
+        for (i, &degree) in degrees.iter().enumerate() {
+            assert!(
+                degree <= max_degree,
+                "degree bound of polynomial {} cannot exceed {}, but was {}",
+                i,
+                max_degree,
+                degree
+            );
+        }
+
+        FriBatch {
+            max_degree,
+            degrees: degrees.to_vec(),
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the degree against which the combined polynomial is proven.
+    pub fn max_degree(&self) -> usize {
+        self.max_degree
+    }
+
+    /// Returns degree bounds of the polynomials in this batch.
+    pub fn degrees(&self) -> &[usize] {
+        &self.degrees
+    }
+
+    /// Returns the number of polynomials in this batch.
+    pub fn num_polys(&self) -> usize {
+        self.degrees.len()
+    }
+
+    // COEFFICIENTS
+    // --------------------------------------------------------------------------------------------
+
+    /// Draws a pair of random coefficients for each polynomial in this batch from the specified
+    /// public coin.
+    ///
+    /// The coefficients must be drawn after the polynomials have been committed to, and the
+    /// prover and the verifier must draw them from the same state of the public coin.
+    ///
+    /// # Errors
+    /// Returns an error if any of the coefficients could not be drawn from the public coin.
+    pub fn draw_coefficients<B, E, H>(
+        &self,
+        coin: &mut RandomCoin<B, H>,
+    ) -> Result<Vec<(E, E)>, RandomCoinError>
+    where
+        B: StarkField,
+        E: FieldElement<BaseField = B>,
+        H: Hasher,
+    {
+        self.degrees.iter().map(|_| coin.draw_pair()).collect()
+    }
+
+    // COMBINATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns evaluations of the combined polynomial over the evaluation domain.
+    ///
+    /// `evaluations` must contain evaluations of each polynomial in this batch over the
+    /// evaluation domain (in natural order), and `coefficients` must contain a pair of
+    /// coefficients for each polynomial. The evaluation domain is a multiplicative subgroup of
+    /// the field shifted by `domain_offset`.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The number of evaluation vectors or coefficient pairs is not equal to the number of
+    ///   polynomials in this batch.
+    /// * Evaluation vectors are not all of the same length, or their length is not a power of two.
+    pub fn combine_evaluations<B, E>(
+        &self,
+        evaluations: &[Vec<E>],
+        coefficients: &[(E, E)],
+        domain_offset: B,
+    ) -> Vec<E>
+    where
+        B: StarkField,
+        E: FieldElement<BaseField = B>,
+    {
+        self.check_dimensions(evaluations.len(), coefficients.len());
+        let domain_size = evaluations[0].len();
+        assert!(
+            domain_size.is_power_of_two(),
+            "evaluation domain size must be a power of two, but was {}",
+            domain_size
+        );
+        let g = B::get_root_of_unity(log2(domain_size));
+
+        let mut result = E::zeroed_vector(domain_size);
+        for ((values, &degree), &(alpha, beta)) in
+            evaluations.iter().zip(&self.degrees).zip(coefficients)
+        {
+            assert_eq!(
+                domain_size,
+                values.len(),
+                "all evaluation vectors must have the same length"
+            );
+
+            // x^k is computed incrementally as offset^k * (g^k)^i for the i-th domain element
+            let k = ((self.max_degree - degree) as u64).into();
+            let g_k = g.exp(k);
+            let mut x_k = domain_offset.exp(k);
+            for (r, &value) in result.iter_mut().zip(values) {
+                *r += value * (alpha + beta * E::from(x_k));
+                x_k *= g_k;
+            }
+        }
+        result
+    }
+
+    /// Returns evaluations of the combined polynomial at the specified positions of the
+    /// evaluation domain.
+    ///
+    /// `queried_evaluations` must contain evaluations of each polynomial in this batch at the
+    /// specified `positions`, and `coefficients` must contain a pair of coefficients for each
+    /// polynomial. The evaluation domain is a multiplicative subgroup of the field of size
+    /// `domain_size` shifted by `domain_offset`.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The number of evaluation vectors or coefficient pairs is not equal to the number of
+    ///   polynomials in this batch.
+    /// * The length of any of the evaluation vectors is not equal to the number of positions.
+    /// * `domain_size` is not a power of two.
+    pub fn combine_queries<B, E>(
+        &self,
+        queried_evaluations: &[Vec<E>],
+        positions: &[usize],
+        domain_size: usize,
+        coefficients: &[(E, E)],
+        domain_offset: B,
+    ) -> Vec<E>
+    where
+        B: StarkField,
+        E: FieldElement<BaseField = B>,
+    {
+        self.check_dimensions(queried_evaluations.len(), coefficients.len());
+        assert!(
+            domain_size.is_power_of_two(),
+            "evaluation domain size must be a power of two, but was {}",
+            domain_size
+        );
+        let g = B::get_root_of_unity(log2(domain_size));
+        let xs = positions
+            .iter()
+            .map(|&p| g.exp((p as u64).into()) * domain_offset)
+            .collect::<Vec<_>>();
+
+        let mut result = E::zeroed_vector(positions.len());
+        for ((values, &degree), &(alpha, beta)) in queried_evaluations
+            .iter()
+            .zip(&self.degrees)
+            .zip(coefficients)
+        {
+            assert_eq!(
+                positions.len(),
+                values.len(),
+                "number of queried evaluations must be equal to the number of positions"
+            );
+
+            let k = ((self.max_degree - degree) as u64).into();
+            for ((r, &value), &x) in result.iter_mut().zip(values).zip(&xs) {
+                *r += value * (alpha + beta * E::from(x.exp(k)));
+            }
+        }
+        result
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    fn check_dimensions(&self, num_evaluations: usize, num_coefficients: usize) {
+        assert_eq!(
+            self.num_polys(),
+            num_evaluations,
+            "expected evaluations for {} polynomials, but received {}",
+            self.num_polys(),
+            num_evaluations
+        );
+        assert_eq!(
+            self.num_polys(),
+            num_coefficients,
+            "expected coefficients for {} polynomials, but received {}",
+            self.num_polys(),
+            num_coefficients
+        );
+    }
+}
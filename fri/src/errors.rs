@@ -16,8 +16,19 @@ use crypto::RandomCoinError;
 pub enum VerifierError {
     /// Attempt to draw a random value from a public coin failed.
     PublicCoinError(RandomCoinError),
+    /// Number of layer proof-of-work nonces sent by the prover is not consistent with the
+    /// number of layer commitments and the layer grinding factor.
+    NumLayerPowNoncesMismatch(usize, usize),
+    /// Proof-of-work nonce for one of the layers did not satisfy the layer grinding factor.
+    LayerProofOfWorkVerificationFailed(usize),
+    /// Number of layer commitments sent by the prover is not consistent with the number of
+    /// layers implied by the protocol options.
+    NumLayerCommitmentsMismatch(usize, usize),
+    /// Number of out-of-domain evaluations sent by the prover is not consistent with the number
+    /// of STIR rounds implied by the protocol options.
+    NumOodEvaluationsMismatch(usize, usize),
     /// Folding factor specified for the protocol is not supported. Currently, supported folding
-    /// factors are: 4, 8, and 16.
+    /// factors are: 2, 4, 8, and 16.
     UnsupportedFoldingFactor(usize),
     /// Number of query positions does not match the number of provided evaluations.
     NumPositionEvaluationMismatch(usize, usize),
@@ -46,6 +57,18 @@ impl fmt::Display for VerifierError {
             Self::PublicCoinError(err) => {
                 write!(f, "failed to draw a random value from the public coin: {}", err)
             }
+            Self::NumLayerPowNoncesMismatch(expected, actual) => {
+                write!(f, "expected {} layer proof-of-work nonces, but received {}", expected, actual)
+            }
+            Self::LayerProofOfWorkVerificationFailed(layer) => {
+                write!(f, "proof-of-work verification failed at layer {}", layer)
+            }
+            Self::NumLayerCommitmentsMismatch(expected, actual) => {
+                write!(f, "expected {} layer commitments, but received {}", expected, actual)
+            }
+            Self::NumOodEvaluationsMismatch(expected, actual) => {
+                write!(f, "expected {} out-of-domain evaluations, but received {}", expected, actual)
+            }
             Self::UnsupportedFoldingFactor(value) => {
                 write!(f, "folding factor {} is not currently supported", value)
             }
@@ -80,3 +103,13 @@ impl fmt::Display for VerifierError {
         }
     }
 }
+
+#[cfg(feature = "std")]
+impl std::error::Error for VerifierError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::PublicCoinError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
@@ -12,7 +12,6 @@
 use utils::iterators::*;
 
 use math::{
-    batch_inversion,
     fft::{get_inv_twiddles, serial_fft},
     get_power_series_with_offset, polynom, FieldElement, StarkField,
 };
@@ -88,12 +87,61 @@ where
     B: StarkField,
     E: FieldElement<BaseField = B>,
 {
+    let mut result = unsafe { uninit_vector(values.len()) };
+    apply_drp_into(values, domain_offset, alpha, &mut result);
+    result
+}
+
+/// Applies degree-respecting projection to evaluations of a polynomial, and writes the folded
+/// evaluations into the provided `result` slice.
+///
+/// This is equivalent to [apply_drp()], but makes it possible to reuse an existing buffer for
+/// the folded evaluations; for example, the FRI prover writes folded evaluations into the buffer
+/// which held the evaluations of the previous layer. When the crate is compiled with `concurrent`
+/// feature enabled, the evaluations are folded in multiple threads.
+///
+/// ```
+/// # use math::fields::f128::BaseElement;
+/// # use rand_utils::{rand_value, rand_vector};
+/// # use utils::transpose_slice;
+/// # use winter_fri::folding::{apply_drp, apply_drp_into};
+/// # use math::StarkField;
+/// let alpha: BaseElement = rand_value();
+/// let evaluations: Vec<BaseElement> = rand_vector(32);
+/// let transposed_evaluations = transpose_slice::<BaseElement, 4>(&evaluations);
+///
+/// // fold the evaluations into the buffer which holds them
+/// let mut buffer = evaluations.clone();
+/// buffer.truncate(transposed_evaluations.len());
+/// apply_drp_into(&transposed_evaluations, BaseElement::GENERATOR, alpha, &mut buffer);
+///
+/// assert_eq!(apply_drp(&transposed_evaluations, BaseElement::GENERATOR, alpha), buffer);
+/// ```
+///
+/// # Panics
+/// Panics if the length of `result` is not equal to the length of `values`.
+pub fn apply_drp_into<B, E, const N: usize>(
+    values: &[[E; N]],
+    domain_offset: B,
+    alpha: E,
+    result: &mut [E],
+) where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+{
+    assert_eq!(
+        values.len(),
+        result.len(),
+        "expected result slice of length {}, but was {}",
+        values.len(),
+        result.len()
+    );
+
     // build offset inverses and twiddles used during polynomial interpolation
     let inv_offsets = get_inv_offsets(values.len(), domain_offset, N);
     let inv_twiddles = get_inv_twiddles::<B>(N);
     let len_offset = E::inv((N as u64).into());
 
-    let mut result = unsafe { uninit_vector(values.len()) };
     iter_mut!(result)
         .zip(values)
         .zip(inv_offsets)
@@ -113,8 +161,6 @@ where
             // evaluate the polynomial at alpha, and save the result
             *result = polynom::eval(&poly, alpha)
         });
-
-    result
 }
 
 // POSITION FOLDING
@@ -178,13 +224,14 @@ pub fn fold_positions(
 // HELPER FUNCTIONS
 // ================================================================================================
 
+/// Returns inverses of the first `domain_size` elements of the source domain; since the i-th
+/// element of the domain is offset * g^i, its inverse is offset^{-1} * (g^{-1})^i, and thus, no
+/// batch inversion is needed.
 fn get_inv_offsets<B>(domain_size: usize, domain_offset: B, folding_factor: usize) -> Vec<B>
 where
     B: StarkField,
 {
     let n = domain_size * folding_factor;
     let g = B::get_root_of_unity(n.trailing_zeros());
-    let offsets = get_power_series_with_offset(g, domain_offset, domain_size);
-
-    batch_inversion(&offsets)
+    get_power_series_with_offset(g.inv(), domain_offset.inv(), domain_size)
 }
@@ -39,11 +39,56 @@
 //!    This will execute the commit phase of the FRI protocol from the verifier's perspective -
 //!    i.e., the verifier will read FRI layer commitments from the channel, and generates
 //!    random values needed for layer folding.
+//!    When many proofs with the same options and max polynomial degree are verified, a
+//!    [FriVerifierContext] can be built once and used to instantiate a verifier for every proof
+//!    via [with_context()](FriVerifier::with_context()) function instead.
 //! 3. Finally, the query phase of the FRI protocol should be executed via
 //!    [verify()](FriVerifier::verify()) function. Note that query values at the first FRI layer
 //!    are provided to the [verify()](FriVerifier::verify()) function directly. The values at
 //!    remaining layers, the verifier reads from the specified verifier channel.
 //!
+//! # Batching
+//! Multiple polynomials with distinct degree bounds can be proven via a single instance of the
+//! FRI protocol using [FriBatch]. The polynomials are combined into a single polynomial via a
+//! random linear combination in which the degree of each polynomial is first raised to the max
+//! degree of the batch; the prover then executes FRI against evaluations of the combined
+//! polynomial, and the verifier combines evaluations of the polynomials at the queried positions
+//! in the same way before passing them to [verify()](FriVerifier::verify()).
+//!
+//! # STIR
+//! As an alternative to FRI, this crate implements the [STIR](https://eprint.iacr.org/2024/390)
+//! low-degree test via [StirProver] and [StirVerifier]. STIR reduces the degree of the tested
+//! polynomial by the folding factor in every round while reducing the evaluation domain only by a
+//! factor of 2, and thus, requires fewer queries than FRI for the same level of security. STIR is
+//! selected via [FriOptions::with_stir()], and STIR proofs are carried by the same [FriProof]
+//! struct as FRI proofs. Both protocols implement [LowDegreeProver] and [LowDegreeVerifier]
+//! traits, which makes it possible to select the low-degree test at runtime, and to experiment
+//! with alternative low-degree tests.
+//!
+//! For tiny instances and for testing, folding can be skipped altogether via
+//! [FriOptions::with_direct()]: the polynomial is then sent to the verifier in full as
+//! coefficients (see [LowDegreeTest::Direct]).
+//!
+//! If the tested polynomial may turn out to be of lower degree than its degree bound, the prover
+//! can be allowed to stop folding as soon as the degree of the polynomial at the current layer is
+//! smaller than the max remainder degree via [FriOptions::with_early_termination()]. The number
+//! of committed layers is then included in the proof.
+//!
+//! DEEP-style out-of-domain sampling within FRI layers can be enabled via
+//! [FriOptions::with_deep_sampling()]. After every layer is folded, the verifier draws a random
+//! point outside of the evaluation domain, and the prover sends the evaluation of the folded
+//! polynomial at this point; the next layer is then built from the quotient of the folded
+//! polynomial by this point. This improves provable soundness of every query at the cost of one
+//! extra field element per layer.
+//!
+//! # Soundness
+//! Soundness error of a FRI instance can be estimated via [FriSoundness]. Given FRI options
+//! (including the folding schedule), the size of the field, the size of the evaluation domain,
+//! the number of queries, and the grinding factor, it computes the error introduced during the
+//! commit phase and the query phase of the protocol, either under the list-decoding conjecture
+//! or using provable bounds; the provable bounds take out-of-domain sampling within FRI layers
+//! into account.
+//!
 //! # Protocol parameters
 //! The current implementation supports executing FRI protocol with dynamically configurable
 //! parameters including:
@@ -52,13 +97,16 @@
 //! * Extension field,
 //! * Domain blowup factor,
 //! * Hash function (used for Merkle tree commitments),
-//! * Folding factor (used for degree reduction for each FRI layer),
-//! * Maximum size of the last FRI layer.
+//! * Folding factor (used for degree reduction for each FRI layer), which can optionally vary
+//!   from layer to layer according to a folding schedule,
+//! * Maximum size of the last FRI layer,
+//! * Low-degree test (FRI, STIR, or sending the polynomial directly).
 //!
 //! # References
 //! * StarkWare's blog post on [Low Degree Testing](https://medium.com/starkware/low-degree-testing-f7614f5172db)
 //! * [Fast Reed-Solomon Interactive Oracle Proofs of Proximity](https://eccc.weizmann.ac.il/report/2017/134/)
 //! * [DEEP-FRI: Sampling Outside the Box Improves Soundness](https://eprint.iacr.org/2019/336)
+//! * [STIR: Reed-Solomon Proximity Testing with Fewer Queries](https://eprint.iacr.org/2024/390)
 //! * Swastik Kooparty's [talk on DEEP-FRI](https://www.youtube.com/watch?v=txo_kPSn59Y&list=PLcIyXLwiPilWvjvNkhMn283LV370Pk5CT&index=6)
 
 #![cfg_attr(not(feature = "std"), no_std)]
@@ -70,17 +118,28 @@ extern crate alloc;
 pub mod folding;
 
 mod prover;
-pub use prover::{DefaultProverChannel, FriProver, ProverChannel};
+pub use prover::{DefaultProverChannel, FriProver, LowDegreeProver, ProverChannel};
 
 mod verifier;
-pub use verifier::{DefaultVerifierChannel, FriVerifier, VerifierChannel};
+pub use verifier::{
+    DefaultVerifierChannel, FriVerifier, FriVerifierContext, LowDegreeVerifier, VerifierChannel,
+};
+
+mod stir;
+pub use stir::{StirProver, StirVerifier};
 
 mod options;
-pub use options::FriOptions;
+pub use options::{FriOptions, LowDegreeTest, RemainderFormat};
+
+mod soundness;
+pub use soundness::FriSoundness;
 
 mod proof;
 pub use proof::FriProof;
 
+mod batch;
+pub use batch::FriBatch;
+
 mod errors;
 pub use errors::VerifierError;
 
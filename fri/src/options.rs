@@ -3,7 +3,10 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use math::StarkField;
+use math::{log2, StarkField};
+use utils::{
+    collections::Vec, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+};
 
 // FRI OPTIONS
 // ================================================================================================
@@ -12,17 +15,31 @@ use math::StarkField;
 #[derive(Clone, PartialEq, Eq)]
 pub struct FriOptions {
     folding_factor: usize,
+    folding_schedule: Vec<usize>,
     max_remainder_size: usize,
+    remainder_format: RemainderFormat,
     blowup_factor: usize,
+    layer_grinding_factor: u32,
+    low_degree_test: LowDegreeTest,
+    num_queries: usize,
+    early_termination: bool,
+    deep_sampling: bool,
 }
 
 impl FriOptions {
+    /// Maximum number of leading zeros which can be required by a proof-of-work; currently set
+    /// at 64.
+    pub const MAX_GRINDING_FACTOR: u32 = 64;
+
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
     /// Returns a new [FriOptions] struct instantiated with the specified parameters.
     ///
     /// # Panics
     /// Panics if:
     /// * `blowup_factor` is not a power of two.
-    /// * `folding_factor` is not 4, 8, or 16.
+    /// * `folding_factor` is not 2, 4, 8, or 16.
     /// * `max_remainder_size` is not at least twice the size of the `blowup_factor`.
     pub fn new(blowup_factor: usize, folding_factor: usize, max_remainder_size: usize) -> Self {
         // TODO: change panics to errors
@@ -31,24 +48,144 @@ impl FriOptions {
             "blowup factor must be a power of two, but was {}",
             blowup_factor
         );
-        assert!(
-            folding_factor == 4 || folding_factor == 8 || folding_factor == 16,
-            "folding factor {} is not supported",
-            folding_factor
-        );
-        assert!(
-            max_remainder_size >= folding_factor * 2,
-            "expected max remainder size to be at least {}, but was {}",
-            folding_factor * 2,
-            max_remainder_size
-        );
+        validate_folding_factor(folding_factor, max_remainder_size);
         FriOptions {
             folding_factor,
+            folding_schedule: Vec::new(),
             max_remainder_size,
+            remainder_format: RemainderFormat::Evaluations,
             blowup_factor,
+            layer_grinding_factor: 0,
+            low_degree_test: LowDegreeTest::Fri,
+            num_queries: 0,
+            early_termination: false,
+            deep_sampling: false,
         }
     }
 
+    /// Returns these options updated to require a proof-of-work with the specified number of
+    /// leading zeros for every FRI layer.
+    ///
+    /// When layer grinding factor is greater than zero, after committing to each FRI layer the
+    /// prover must find a nonce which, when absorbed into the public coin, results in a seed with
+    /// at least `grinding_factor` leading zeros. The verifier absorbs the same nonce before
+    /// drawing the folding challenge α for the layer and rejects the proof if the proof-of-work
+    /// is not satisfied. Setting layer grinding factor to zero (the default) disables per-layer
+    /// proof-of-work.
+    ///
+    /// # Panics
+    /// Panics if `grinding_factor` is greater than 64.
+    pub fn with_layer_grinding_factor(mut self, grinding_factor: u32) -> Self {
+        assert!(
+            grinding_factor <= Self::MAX_GRINDING_FACTOR,
+            "layer grinding factor cannot be greater than {}, but was {}",
+            Self::MAX_GRINDING_FACTOR,
+            grinding_factor
+        );
+        self.layer_grinding_factor = grinding_factor;
+        self
+    }
+
+    /// Returns these options updated to fold FRI layers according to the specified schedule.
+    ///
+    /// The schedule lists folding factors for the first FRI layers: the first layer is folded
+    /// by `schedule[0]`, the second layer by `schedule[1]` etc. Layers beyond the end of the
+    /// schedule (including the remainder layer) are folded by the `folding_factor` of these
+    /// options. Thus, a schedule such as `[16, 8, 4, 4]` folds early layers aggressively (which
+    /// results in fewer large Merkle trees) and later layers gently. An empty schedule (the
+    /// default) folds all layers by `folding_factor`.
+    ///
+    /// # Panics
+    /// Panics if any of the folding factors in the `schedule` is not 2, 4, 8, or 16, or is such
+    /// that `max_remainder_size` is not at least twice the size of the folding factor.
+    pub fn with_folding_schedule(mut self, schedule: &[usize]) -> Self {
+        for &folding_factor in schedule {
+            validate_folding_factor(folding_factor, self.max_remainder_size);
+        }
+        self.folding_schedule = schedule.to_vec();
+        self
+    }
+
+    /// Returns these options updated to send the remainder (the last FRI layer) to the verifier
+    /// in the specified format.
+    ///
+    /// By default, [RemainderFormat::Evaluations] format is used.
+    pub fn with_remainder_format(mut self, remainder_format: RemainderFormat) -> Self {
+        self.remainder_format = remainder_format;
+        self
+    }
+
+    /// Returns these options updated to allow the prover to stop folding as soon as the degree
+    /// of the polynomial at the current FRI layer is smaller than the max remainder degree.
+    ///
+    /// The max remainder degree is defined by the `max_remainder_size` and the blowup factor:
+    /// it is one less than [fri_remainder_len()](FriOptions::fri_remainder_len). If the tested
+    /// polynomial turns out to be of lower degree than the degree bound (e.g., when constraints
+    /// are of lower degree than the worst case), this results in fewer FRI layers, and thus,
+    /// smaller proofs. The number of layers
+    /// committed to by the prover is included in the proof, and the verifier accepts any number
+    /// of layers up to [num_fri_layers()](FriOptions::num_fri_layers).
+    ///
+    /// With early termination, the remainder is always sent as coefficients regardless of the
+    /// [RemainderFormat] of these options. Early termination applies only when FRI is used as
+    /// the low-degree test.
+    pub fn with_early_termination(mut self) -> Self {
+        self.early_termination = true;
+        self
+    }
+
+    /// Returns these options updated to perform DEEP-style out-of-domain sampling within FRI
+    /// layers (as in [DEEP-FRI](https://eprint.iacr.org/2019/336)).
+    ///
+    /// With out-of-domain sampling, after folding every layer, the verifier draws a random point
+    /// *z* from the entire field, and the prover sends the evaluation *β* of the folded
+    /// polynomial *f* at *z*. The next layer (or the remainder) is then the quotient
+    /// (*f*(*x*) - *β*) / (*x* - *z*) rather than *f* itself, and the verifier checks the
+    /// consistency of every layer with the quotient computed from the previous layer. This
+    /// improves soundness per query at the cost of one extra field element per layer. Out-of-
+    /// domain sampling applies only when FRI is used as the low-degree test.
+    pub fn with_deep_sampling(mut self) -> Self {
+        self.deep_sampling = true;
+        self
+    }
+
+    /// Returns these options updated to use STIR rather than FRI as the low-degree test.
+    ///
+    /// `num_queries` specifies the number of queries made against the first layer in every
+    /// STIR round; the number of queries in subsequent rounds is reduced in proportion to the
+    /// rate improvement of each round (see [LowDegreeTest::Stir]). When STIR is executed as a
+    /// part of the STARK protocol, this is set to the number of STARK queries.
+    ///
+    /// With STIR, the folding factor of each round is defined by the folding factor (and the
+    /// folding schedule) of these options, and the final polynomial is always sent as
+    /// coefficients regardless of the [RemainderFormat] of these options.
+    ///
+    /// # Panics
+    /// Panics if `num_queries` is zero.
+    pub fn with_stir(mut self, num_queries: usize) -> Self {
+        assert!(
+            num_queries > 0,
+            "number of STIR queries must be greater than zero"
+        );
+        self.low_degree_test = LowDegreeTest::Stir;
+        self.num_queries = num_queries;
+        self
+    }
+
+    /// Returns these options updated to skip folding altogether, and to send the entire
+    /// polynomial to the verifier as coefficients (see [LowDegreeTest::Direct]).
+    ///
+    /// The proof then grows linearly with the degree of the polynomial; thus, this is intended
+    /// for tiny instances and for testing.
+    pub fn with_direct(mut self) -> Self {
+        self.low_degree_test = LowDegreeTest::Direct;
+        self.num_queries = 0;
+        self
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
     /// Returns the offset by which the evaluation domain is shifted.
     ///
     /// The domain is shifted by multiplying every element in the domain by this offset.
@@ -67,6 +204,27 @@ impl FriOptions {
         self.folding_factor
     }
 
+    /// Returns folding factors for the first FRI layers as specified via
+    /// [with_folding_schedule()](FriOptions::with_folding_schedule) method.
+    ///
+    /// If the schedule is empty, all layers are folded by the
+    /// [folding_factor()](FriOptions::folding_factor).
+    pub fn folding_schedule(&self) -> &[usize] {
+        &self.folding_schedule
+    }
+
+    /// Returns the factor by which the degree of a polynomial is reduced at the FRI layer with
+    /// the specified index.
+    ///
+    /// This is the folding factor at the specified index in the folding schedule, or
+    /// [folding_factor()](FriOptions::folding_factor) for layers beyond the end of the schedule.
+    pub fn layer_folding_factor(&self, layer_idx: usize) -> usize {
+        self.folding_schedule
+            .get(layer_idx)
+            .copied()
+            .unwrap_or(self.folding_factor)
+    }
+
     /// Returns maximum allowed remainder (last FRI layer) size.
     ///
     /// In combination with `folding_factor` this property defines how many FRI layers are needed
@@ -75,6 +233,18 @@ impl FriOptions {
         self.max_remainder_size
     }
 
+    /// Returns the format in which the remainder (the last FRI layer) is sent to the verifier.
+    ///
+    /// When the low-degree test is [LowDegreeTest::Direct], or when early termination is enabled,
+    /// this is always [RemainderFormat::Coefficients].
+    pub fn remainder_format(&self) -> RemainderFormat {
+        match self.low_degree_test {
+            LowDegreeTest::Direct => RemainderFormat::Coefficients,
+            _ if self.early_termination() => RemainderFormat::Coefficients,
+            _ => self.remainder_format,
+        }
+    }
+
     /// Returns a blowup factor of the evaluation domain.
     ///
     /// Specifically, if the polynomial for which the FRI protocol is executed is of degree `d`
@@ -84,16 +254,61 @@ impl FriOptions {
         self.blowup_factor
     }
 
+    /// Returns the low-degree test executed with these options.
+    pub fn low_degree_test(&self) -> LowDegreeTest {
+        self.low_degree_test
+    }
+
+    /// Returns true if the FRI prover may stop folding as soon as the degree of the polynomial at
+    /// the current layer is smaller than the max remainder degree.
+    ///
+    /// This is always false when the low-degree test is not [LowDegreeTest::Fri].
+    pub fn early_termination(&self) -> bool {
+        self.early_termination && self.low_degree_test == LowDegreeTest::Fri
+    }
+
+    /// Returns true if DEEP-style out-of-domain sampling is performed within FRI layers.
+    ///
+    /// This is always false when the low-degree test is not [LowDegreeTest::Fri].
+    pub fn deep_sampling(&self) -> bool {
+        self.deep_sampling && self.low_degree_test == LowDegreeTest::Fri
+    }
+
+    /// Returns the number of queries made against the first layer of every STIR round.
+    ///
+    /// This is zero when the low-degree test is FRI: FRI layers are queried at positions
+    /// derived from the positions at which the first layer is queried.
+    pub fn num_queries(&self) -> usize {
+        self.num_queries
+    }
+
+    /// Returns the number of leading zeros required by the proof-of-work for each FRI layer.
+    ///
+    /// If zero is returned, no per-layer proof-of-work is performed by the prover or checked by
+    /// the verifier.
+    pub fn layer_grinding_factor(&self) -> u32 {
+        self.layer_grinding_factor
+    }
+
     /// Computes and return the number of FRI layers required for a domain of the specified size.
     ///
     /// The remainder layer (the last FRI layer) is not included in the returned value.
     ///
-    /// The number of layers for a given domain size is defined by the `folding_factor` and
-    /// `max_remainder_size` settings.
+    /// The number of layers for a given domain size is defined by the `folding_factor`,
+    /// `folding_schedule`, and `max_remainder_size` settings. With STIR, this is the number of
+    /// STIR rounds plus one, which is the number of layers opened by the verifier (the final
+    /// polynomial is committed to in the same way as a remainder sent as coefficients). With
+    /// [LowDegreeTest::Direct], this is always zero. With early termination, this is the maximum
+    /// number of FRI layers.
     pub fn num_fri_layers(&self, mut domain_size: usize) -> usize {
+        match self.low_degree_test {
+            LowDegreeTest::Fri => (),
+            LowDegreeTest::Stir => return build_stir_schedule(self, domain_size).len(),
+            LowDegreeTest::Direct => return 0,
+        }
         let mut result = 0;
         while domain_size > self.max_remainder_size {
-            domain_size /= self.folding_factor;
+            domain_size /= self.layer_folding_factor(result);
             result += 1;
         }
         result
@@ -102,12 +317,282 @@ impl FriOptions {
     /// Computes and returns the size of the remainder layer (the last FRI layer) for a domain of
     /// the specified size.
     ///
-    /// The size of the remainder layer for a given domain size is defined by the `folding_factor`
-    /// and `max_remainder_size` settings.
+    /// The size of the remainder layer for a given domain size is defined by the
+    /// `folding_factor`, `folding_schedule`, and `max_remainder_size` settings. With STIR, this
+    /// is the size of the domain of the last STIR layer, and with [LowDegreeTest::Direct], this
+    /// is the size of the domain itself.
     pub fn fri_remainder_size(&self, mut domain_size: usize) -> usize {
+        match self.low_degree_test {
+            LowDegreeTest::Fri => (),
+            LowDegreeTest::Stir => {
+                let schedule = build_stir_schedule(self, domain_size);
+                return schedule.last().expect("STIR schedule is empty").domain_size;
+            }
+            LowDegreeTest::Direct => return domain_size,
+        }
+        let mut layer_idx = 0;
         while domain_size > self.max_remainder_size {
-            domain_size /= self.folding_factor;
+            domain_size /= self.layer_folding_factor(layer_idx);
+            layer_idx += 1;
         }
         domain_size
     }
+
+    /// Computes and returns the number of values which describe the remainder layer (the last
+    /// FRI layer) in a proof for a domain of the specified size.
+    ///
+    /// For remainders sent as evaluations, this is the size of the remainder layer. For
+    /// remainders sent as coefficients, this is the size of the remainder layer divided by the
+    /// blowup factor (but at least one); this is also the maximum degree of the remainder
+    /// polynomial plus one. With STIR, this is the number of coefficients of the final
+    /// polynomial.
+    pub fn fri_remainder_len(&self, domain_size: usize) -> usize {
+        if self.low_degree_test == LowDegreeTest::Stir {
+            let schedule = build_stir_schedule(self, domain_size);
+            return schedule
+                .last()
+                .expect("STIR schedule is empty")
+                .degree_bound;
+        }
+        let remainder_size = self.fri_remainder_size(domain_size);
+        match self.remainder_format() {
+            RemainderFormat::Evaluations => remainder_size,
+            RemainderFormat::Coefficients => (remainder_size / self.blowup_factor).max(1),
+        }
+    }
+
+    /// Returns the size of the domain of the layer with the specified index for a first layer
+    /// domain of the specified size.
+    ///
+    /// With FRI, the domain of every layer is smaller than the domain of the previous layer by
+    /// the folding factor of the previous layer. With STIR, the domain of every layer is half
+    /// the size of the domain of the previous layer.
+    pub fn layer_domain_size(&self, domain_size: usize, layer_idx: usize) -> usize {
+        match self.low_degree_test {
+            LowDegreeTest::Fri | LowDegreeTest::Direct => {
+                (0..layer_idx).fold(domain_size, |size, i| size / self.layer_folding_factor(i))
+            }
+            LowDegreeTest::Stir => domain_size >> layer_idx,
+        }
+    }
+
+    /// Returns the number of queries made against the layer with the specified index when the
+    /// first layer (of the specified size) is queried at `num_queries` positions.
+    ///
+    /// With FRI, every layer is queried at most `num_queries` times. With STIR, the first layer
+    /// is additionally queried by the first STIR round, and every subsequent layer is queried
+    /// only by its own round.
+    pub fn layer_num_queries(
+        &self,
+        domain_size: usize,
+        layer_idx: usize,
+        num_queries: usize,
+    ) -> usize {
+        match self.low_degree_test {
+            LowDegreeTest::Fri | LowDegreeTest::Direct => num_queries,
+            LowDegreeTest::Stir => {
+                let schedule = build_stir_schedule(self, domain_size);
+                let round_queries = schedule[layer_idx].num_queries;
+                if layer_idx == 0 {
+                    num_queries + round_queries
+                } else {
+                    round_queries
+                }
+            }
+        }
+    }
+
+    /// Returns the number of out-of-domain evaluations included in a proof for a domain of the
+    /// specified size.
+    ///
+    /// For FRI, this is the number of FRI layers when out-of-domain sampling is performed
+    /// within FRI layers, and zero otherwise (with early termination, this is the max number of
+    /// out-of-domain evaluations); with STIR, this is the number of STIR rounds.
+    pub fn num_ood_evaluations(&self, domain_size: usize) -> usize {
+        match self.low_degree_test {
+            LowDegreeTest::Fri if self.deep_sampling => self.num_fri_layers(domain_size),
+            LowDegreeTest::Fri | LowDegreeTest::Direct => 0,
+            LowDegreeTest::Stir => build_stir_schedule(self, domain_size).len() - 1,
+        }
+    }
+}
+
+// REMAINDER FORMAT
+// ================================================================================================
+
+/// Defines how the remainder (the last FRI layer) is sent to the verifier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum RemainderFormat {
+    /// The remainder is sent as evaluations over the remainder domain, and is committed to via
+    /// a Merkle tree in the same way as all other FRI layers. To check the degree of the
+    /// remainder, the verifier interpolates the evaluations into a polynomial. This is the
+    /// default format.
+    Evaluations = 1,
+
+    /// The remainder is sent as coefficients of the remainder polynomial, and is committed to
+    /// via a hash of the coefficients. The number of coefficients is the size of the remainder
+    /// layer divided by the blowup factor, and thus, the degree of the remainder is bounded by
+    /// the structure of the proof; the verifier evaluates the polynomial at the queried
+    /// positions directly. This reduces the size of the remainder in the proof by the blowup
+    /// factor, which is most noticeable in proofs for small computations.
+    Coefficients = 2,
+}
+
+impl Serializable for RemainderFormat {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8(*self as u8);
+    }
+}
+
+impl Deserializable for RemainderFormat {
+    /// Reads a remainder format enum from the specified `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        match source.read_u8()? {
+            1 => Ok(RemainderFormat::Evaluations),
+            2 => Ok(RemainderFormat::Coefficients),
+            value => Err(DeserializationError::InvalidValue(format!(
+                "value {} cannot be deserialized as RemainderFormat enum",
+                value
+            ))),
+        }
+    }
+}
+
+// LOW-DEGREE TEST
+// ================================================================================================
+
+/// Defines the protocol used to prove that a committed function is a polynomial of low degree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum LowDegreeTest {
+    /// The FRI protocol: the degree of the polynomial is reduced by the folding factor of every
+    /// layer, and every layer is queried at positions derived from the positions at which the
+    /// first layer is queried. This is the default.
+    Fri = 1,
+
+    /// The [STIR](https://eprint.iacr.org/2024/390) protocol: the degree of the polynomial is
+    /// reduced by the folding factor of every round, but the domain of every round is only half
+    /// the size of the domain of the previous round. Thus, the rate of the code improves with
+    /// every round, and subsequent rounds need fewer queries than the first one for the same
+    /// security level. Specifically, round `i` makes
+    /// `ceil(num_queries * log2(blowup_factor) / rate_bits(i))` queries, where `rate_bits(i)`
+    /// is log2 of the ratio between the domain size and the degree bound of the round.
+    Stir = 2,
+
+    /// No folding: the polynomial is sent to the verifier in full as coefficients, and the
+    /// verifier evaluates it at the queried positions directly. This is equivalent to FRI with
+    /// no layers and the remainder sent as coefficients, and thus, is executed by
+    /// [FriProver](crate::FriProver) and [FriVerifier](crate::FriVerifier). The size of the
+    /// proof is linear in the degree of the polynomial, which makes this test practical only
+    /// for tiny instances and for testing.
+    Direct = 3,
+}
+
+impl Serializable for LowDegreeTest {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8(*self as u8);
+    }
+}
+
+impl Deserializable for LowDegreeTest {
+    /// Reads a low-degree test enum from the specified `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        match source.read_u8()? {
+            1 => Ok(LowDegreeTest::Fri),
+            2 => Ok(LowDegreeTest::Stir),
+            3 => Ok(LowDegreeTest::Direct),
+            value => Err(DeserializationError::InvalidValue(format!(
+                "value {} cannot be deserialized as LowDegreeTest enum",
+                value
+            ))),
+        }
+    }
+}
+
+// STIR SCHEDULE
+// ================================================================================================
+
+/// Parameters of a single STIR layer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct StirLayer {
+    /// Size of the domain over which the function of this layer is committed to.
+    pub domain_size: usize,
+    /// Number of coefficients of the polynomial tested at this layer.
+    pub degree_bound: usize,
+    /// Folding factor applied to the function of this layer.
+    pub folding_factor: usize,
+    /// Number of queries made by the round of this layer (or by the final check).
+    pub num_queries: usize,
+}
+
+impl StirLayer {
+    /// Returns the number of leaves in the commitment Merkle tree of this layer; this is also
+    /// the size of the domain from which the queries for this layer are drawn.
+    pub fn num_leaves(&self) -> usize {
+        self.domain_size / self.folding_factor
+    }
+
+    /// Returns the number of distinct indexes which can be drawn for this layer.
+    pub fn num_query_draws(&self) -> usize {
+        self.num_queries.min(self.num_leaves() - 1)
+    }
+}
+
+/// Returns parameters of STIR layers for a first layer domain of the specified size.
+///
+/// A layer is folded in a STIR round as long as its degree bound is greater than
+/// `max_remainder_size / blowup_factor` and the folded degree bound exceeds the number of points
+/// the next function is constrained at (i.e., the number of round queries plus one); otherwise,
+/// the layer is the last one, and its polynomial is sent to the verifier in full. The returned
+/// vector contains one entry per STIR round followed by an entry for the last layer.
+pub(crate) fn build_stir_schedule(options: &FriOptions, domain_size: usize) -> Vec<StirLayer> {
+    let blowup_factor = options.blowup_factor();
+    let rate_bits = log2(blowup_factor) as usize;
+    let min_degree_bound = (options.max_remainder_size() / blowup_factor).max(1);
+
+    let mut result = Vec::new();
+    let mut domain_size = domain_size;
+    let mut degree_bound = (domain_size / blowup_factor).max(1);
+    loop {
+        let layer_idx = result.len();
+        let folding_factor = options.layer_folding_factor(layer_idx);
+        let layer_rate_bits = (log2(domain_size / degree_bound) as usize).max(1);
+        let num_queries = (options.num_queries() * rate_bits)
+            .div_ceil(layer_rate_bits)
+            .max(1);
+        result.push(StirLayer {
+            domain_size,
+            degree_bound,
+            folding_factor,
+            num_queries,
+        });
+
+        if degree_bound <= min_degree_bound || degree_bound / folding_factor <= num_queries + 1 {
+            return result;
+        }
+        domain_size /= 2;
+        degree_bound /= folding_factor;
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Makes sure the specified folding factor is supported and can be used together with the
+/// specified max remainder size.
+fn validate_folding_factor(folding_factor: usize, max_remainder_size: usize) {
+    assert!(
+        folding_factor == 2 || folding_factor == 4 || folding_factor == 8 || folding_factor == 16,
+        "folding factor {} is not supported",
+        folding_factor
+    );
+    assert!(
+        max_remainder_size >= folding_factor * 2,
+        "expected max remainder size to be at least {}, but was {}",
+        folding_factor * 2,
+        max_remainder_size
+    );
 }
@@ -3,13 +3,26 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use crate::{
+    options::{build_stir_schedule, LowDegreeTest},
+    FriOptions, RemainderFormat,
+};
 use crypto::{BatchMerkleProof, ElementHasher, Hasher};
 use math::{log2, FieldElement};
 use utils::{
-    collections::Vec, string::ToString, ByteReader, ByteWriter, Deserializable,
+    collections::Vec, iter_mut, string::ToString, ByteReader, ByteWriter, Deserializable,
     DeserializationError, Serializable, SliceReader,
 };
 
+#[cfg(feature = "concurrent")]
+use utils::iterators::*;
+
+#[cfg(feature = "serde")]
+use utils::{
+    serde::{Deserialize, Deserializer, Serialize, Serializer},
+    serde_adapter,
+};
+
 // FRI PROOF
 // ================================================================================================
 
@@ -23,7 +36,13 @@ use utils::{
 /// A proof consists of zero or more layers and a remainder. Each layer contains a set of
 /// polynomial evaluations at positions queried by the verifier as well as Merkle authentication
 /// paths for these evaluations (the Merkle paths are compressed into a batch Merkle proof). The
-/// remainder is a list of field elements.
+/// remainder is a list of field elements. If per-layer proof-of-work was required during proof
+/// generation, the proof also contains a proof-of-work nonce for every layer commitment
+/// (including the commitment to the remainder). Proofs generated with STIR as the low-degree
+/// test also contain an out-of-domain evaluation for every STIR round; these are read by the
+/// verifier during the commit phase of the protocol. Proofs generated with early termination
+/// enabled also contain the number of layers committed to by the prover (see
+/// [FriOptions::with_early_termination()]).
 ///
 /// All values in a proof are stored as vectors of bytes. Thus, the values must be parsed before
 /// they can be returned to the user. To do this, [parse_layers()](FriProof::parse_layers())
@@ -32,13 +51,26 @@ use utils::{
 pub struct FriProof {
     layers: Vec<FriProofLayer>,
     remainder: Vec<u8>,
+    pow_nonces: Vec<u64>,
+    ood_evaluations: Vec<u8>,
+    num_committed_layers: Option<u8>,
     num_partitions: u8, // stored as power of 2
 }
 
+/// Bit of the serialized number of partitions which indicates that out-of-domain evaluations
+/// follow the number of partitions; the bit is never set for proofs without such evaluations.
+const OOD_EVALUATIONS_FLAG: u8 = 0x80;
+
+/// Bit of the serialized number of partitions which indicates that the number of layers committed
+/// to by the prover follows the number of partitions (and out-of-domain evaluations, if any); the
+/// bit is set only for proofs generated with early termination.
+const NUM_COMMITTED_LAYERS_FLAG: u8 = 0x40;
+
 impl FriProof {
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
-    /// Creates a new FRI proof from the provided layers and remainder values.
+    /// Creates a new FRI proof from the provided layers, remainder values, and layer
+    /// proof-of-work nonces.
     ///
     /// # Panics
     /// Panics if:
@@ -47,6 +79,7 @@ impl FriProof {
     pub(crate) fn new<E: FieldElement>(
         layers: Vec<FriProofLayer>,
         remainder: Vec<E>,
+        pow_nonces: Vec<u64>,
         num_partitions: usize,
     ) -> Self {
         assert!(
@@ -70,10 +103,34 @@ impl FriProof {
         FriProof {
             layers,
             remainder: remainder.to_bytes(),
+            pow_nonces,
+            ood_evaluations: Vec::new(),
+            num_committed_layers: None,
             num_partitions: num_partitions.trailing_zeros() as u8,
         }
     }
 
+    /// Returns this proof updated to include the number of its layers as the number of layers
+    /// committed to by the prover.
+    ///
+    /// # Panics
+    /// Panics if the number of layers is greater than 255.
+    pub(crate) fn with_num_committed_layers(mut self) -> Self {
+        assert!(
+            self.layers.len() <= u8::MAX as usize,
+            "number of layers cannot exceed 255, but was {}",
+            self.layers.len()
+        );
+        self.num_committed_layers = Some(self.layers.len() as u8);
+        self
+    }
+
+    /// Returns this proof updated to include the specified out-of-domain evaluations.
+    pub(crate) fn with_ood_evaluations<E: FieldElement>(mut self, evaluations: &[E]) -> Self {
+        self.ood_evaluations = evaluations.to_bytes();
+        self
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -82,6 +139,17 @@ impl FriProof {
         self.layers.len()
     }
 
+    /// Returns the number of layers committed to by the prover (excluding the remainder), if this
+    /// number is included in the proof.
+    ///
+    /// The number of layers is included only in proofs generated with early termination enabled
+    /// (see [FriOptions::with_early_termination()]); unlike [num_layers()](FriProof::num_layers),
+    /// it is read during the commit phase of the protocol, and thus, is available even when only
+    /// the commit phase of the proof has been read.
+    pub fn num_committed_layers(&self) -> Option<usize> {
+        self.num_committed_layers.map(|n| n as usize)
+    }
+
     /// Returns the number of remainder elements in this proof.
     ///
     /// The number of elements is computed by dividing the number of remainder bytes by the size
@@ -90,17 +158,57 @@ impl FriProof {
         self.remainder.len() / E::ELEMENT_BYTES
     }
 
+    /// Returns the number of out-of-domain evaluations in this proof.
+    ///
+    /// The number of evaluations is computed by dividing the number of out-of-domain evaluation
+    /// bytes by the size of the field element specified by `E` type parameter. This is always
+    /// zero for proofs generated with FRI as the low-degree test.
+    pub fn num_ood_evaluations<E: FieldElement>(&self) -> usize {
+        self.ood_evaluations.len() / E::ELEMENT_BYTES
+    }
+
     /// Returns the number of partitions used during proof generation.
     pub fn num_partitions(&self) -> usize {
         2usize.pow(self.num_partitions as u32)
     }
 
+    /// Returns proof-of-work nonces for each layer commitment in this proof.
+    ///
+    /// If per-layer proof-of-work was not required during proof generation, an empty slice is
+    /// returned.
+    pub fn layer_pow_nonces(&self) -> &[u64] {
+        &self.pow_nonces
+    }
+
     /// Returns the size of this proof in bytes.
     pub fn size(&self) -> usize {
-        // +1 for number of layers, +1 for remainder length, +1 for number of partitions
-        self.layers
-            .iter()
-            .fold(self.remainder.len() + 3, |acc, layer| acc + layer.size())
+        // +1 for number of nonces, +1 for number of partitions, +2 for the length of
+        // out-of-domain evaluations (if any), +1 for the number of committed layers (if any)
+        let ood_size = if self.ood_evaluations.is_empty() {
+            0
+        } else {
+            self.ood_evaluations.len() + 2
+        };
+        let num_committed_layers_size = self.num_committed_layers.map_or(0, |_| 1);
+        self.layers_size()
+            + self.remainder_size()
+            + self.pow_nonces.len() * 8
+            + ood_size
+            + num_committed_layers_size
+            + 2
+    }
+
+    /// Returns the size of all layers of this proof in bytes; this includes query values and
+    /// Merkle authentication paths for all layers.
+    pub fn layers_size(&self) -> usize {
+        // +1 for number of layers
+        self.layers.iter().fold(1, |acc, layer| acc + layer.size())
+    }
+
+    /// Returns the size of the remainder of this proof in bytes.
+    pub fn remainder_size(&self) -> usize {
+        // +1 for remainder length
+        self.remainder.len() + 1
     }
 
     // PARSING
@@ -109,20 +217,27 @@ impl FriProof {
     /// Decomposes this proof into vectors of query values for each layer and corresponding Merkle
     /// authentication paths for each query (grouped into batch Merkle proofs).
     ///
+    /// Each layer is parsed using the folding factor specified for that layer by the `options`.
+    /// For proofs generated with STIR, every layer is parsed against the domain of the
+    /// corresponding STIR layer, and the number of layers must be equal to the number of layers
+    /// implied by the `options`.
+    ///
     /// # Panics
-    /// Panics if:
-    /// * `domain_size` is not a power of two.
-    /// * `folding_factor` is smaller than two or is not a power of two.
+    /// Panics if `domain_size` is not a power of two.
     ///
     /// # Errors
     /// Returns an error if:
-    /// * This proof is not consistent with the specified `domain_size` and `folding_factor`.
+    /// * This proof is not consistent with the specified `domain_size` and the folding factors
+    ///   specified by the `options`.
+    /// * With early termination, the number of committed layers included in this proof does not
+    ///   match the number of layers, or exceeds the number of layers implied by the `options`;
+    ///   without early termination, the number of committed layers is included in this proof.
     /// * Any of the layers could not be parsed successfully.
     #[allow(clippy::type_complexity)]
     pub fn parse_layers<H, E>(
         self,
         mut domain_size: usize,
-        folding_factor: usize,
+        options: &FriOptions,
     ) -> Result<(Vec<Vec<E>>, Vec<BatchMerkleProof<H>>), DeserializationError>
     where
         E: FieldElement,
@@ -132,11 +247,33 @@ impl FriProof {
             domain_size.is_power_of_two(),
             "domain size must be a power of two"
         );
-        assert!(
-            folding_factor.is_power_of_two(),
-            "folding factor must be a power of two"
-        );
-        assert!(folding_factor > 1, "folding factor must be greater than 1");
+
+        if options.low_degree_test() == LowDegreeTest::Stir {
+            return self.parse_stir_layers(domain_size, options);
+        }
+
+        // with early termination, the proof may contain fewer layers than implied by the options,
+        // but the remainder always consists of the same number of coefficients
+        let max_num_layers = options.num_fri_layers(domain_size);
+        let early_remainder_len = options.fri_remainder_len(domain_size);
+        if options.early_termination() {
+            if self.num_committed_layers != Some(self.layers.len() as u8) {
+                return Err(DeserializationError::InvalidValue(
+                    "number of committed FRI layers does not match the number of layers".into(),
+                ));
+            }
+            if self.layers.len() > max_num_layers {
+                return Err(DeserializationError::InvalidValue(format!(
+                    "FRI proof cannot consist of more than {} layers, but was {}",
+                    max_num_layers,
+                    self.layers.len()
+                )));
+            }
+        } else if self.num_committed_layers.is_some() {
+            return Err(DeserializationError::InvalidValue(
+                "number of committed FRI layers is allowed only with early termination".into(),
+            ));
+        }
 
         let mut layer_proofs = Vec::new();
         let mut layer_queries = Vec::new();
@@ -144,6 +281,7 @@ impl FriProof {
 
         // parse all layers
         for (i, layer) in self.layers.into_iter().enumerate() {
+            let folding_factor = options.layer_folding_factor(i);
             domain_size /= folding_factor;
             let (qv, mp) = layer.parse(domain_size, folding_factor).map_err(|err| {
                 DeserializationError::InvalidValue(format!(
@@ -155,17 +293,43 @@ impl FriProof {
             layer_queries.push(qv);
         }
 
-        // make sure the remaining domain size matches remainder length
-        if domain_size != num_remainder_elements {
+        // make sure the remaining domain size matches remainder length; a remainder sent as
+        // coefficients consists of one coefficient per each `blowup_factor` domain elements
+        let expected_remainder_len = match options.remainder_format() {
+            _ if options.early_termination() => early_remainder_len,
+            RemainderFormat::Evaluations => domain_size,
+            RemainderFormat::Coefficients => (domain_size / options.blowup_factor()).max(1),
+        };
+        if expected_remainder_len != num_remainder_elements {
             return Err(DeserializationError::InvalidValue(format!(
-                "FRI remainder domain size must be {}, but was {}",
-                num_remainder_elements, domain_size,
+                "FRI remainder must consist of {} elements, but was {}",
+                expected_remainder_len, num_remainder_elements,
             )));
         }
 
         Ok((layer_queries, layer_proofs))
     }
 
+    /// Returns a vector of out-of-domain evaluations parsed from this proof.
+    ///
+    /// # Errors
+    /// Returns an error if any of the values could not be parsed correctly, or if not all bytes
+    /// have been consumed while parsing the values.
+    pub fn parse_ood_evaluations<E: FieldElement>(&self) -> Result<Vec<E>, DeserializationError> {
+        let mut reader = SliceReader::new(&self.ood_evaluations);
+        let num_elements = self.num_ood_evaluations::<E>();
+        let evaluations = E::read_batch_from(&mut reader, num_elements).map_err(|err| {
+            DeserializationError::InvalidValue(format!(
+                "failed to parse out-of-domain evaluations: {}",
+                err
+            ))
+        })?;
+        if reader.has_more_bytes() {
+            return Err(DeserializationError::UnconsumedBytes);
+        }
+        Ok(evaluations)
+    }
+
     /// Returns a vector of remainder values (last FRI layer) parsed from this proof.
     ///
     /// # Errors
@@ -191,14 +355,97 @@ impl FriProof {
         }
         Ok(remainder)
     }
-}
 
-// SERIALIZATION / DESERIALIZATION
-// ------------------------------------------------------------------------------------------------
+    /// Decomposes the layers of a proof generated with STIR into vectors of query values and
+    /// corresponding Merkle authentication paths.
+    #[allow(clippy::type_complexity)]
+    fn parse_stir_layers<H, E>(
+        self,
+        domain_size: usize,
+        options: &FriOptions,
+    ) -> Result<(Vec<Vec<E>>, Vec<BatchMerkleProof<H>>), DeserializationError>
+    where
+        E: FieldElement,
+        H: ElementHasher<BaseField = E::BaseField>,
+    {
+        let schedule = build_stir_schedule(options, domain_size);
+        if self.layers.len() != schedule.len() {
+            return Err(DeserializationError::InvalidValue(format!(
+                "STIR proof must consist of {} layers, but was {}",
+                schedule.len(),
+                self.layers.len()
+            )));
+        }
 
-impl Serializable for FriProof {
-    /// Serializes `self` and writes the resulting bytes into the `target` writer.
-    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        // the final polynomial is always sent as coefficients
+        let expected_remainder_len = schedule[schedule.len() - 1].degree_bound;
+        let num_remainder_elements = self.num_remainder_elements::<E>();
+        if expected_remainder_len != num_remainder_elements {
+            return Err(DeserializationError::InvalidValue(format!(
+                "STIR final polynomial must consist of {} coefficients, but was {}",
+                expected_remainder_len, num_remainder_elements,
+            )));
+        }
+
+        let mut layer_proofs = Vec::new();
+        let mut layer_queries = Vec::new();
+        for (i, (layer, stir_layer)) in self.layers.into_iter().zip(schedule).enumerate() {
+            let (qv, mp) = layer
+                .parse(stir_layer.num_leaves(), stir_layer.folding_factor)
+                .map_err(|err| {
+                    DeserializationError::InvalidValue(format!(
+                        "failed to parse STIR layer {}: {}",
+                        i, err
+                    ))
+                })?;
+            layer_proofs.push(mp);
+            layer_queries.push(qv);
+        }
+
+        Ok((layer_queries, layer_proofs))
+    }
+
+    // STREAMING
+    // --------------------------------------------------------------------------------------------
+
+    /// Serializes the part of this proof read by the verifier during the commit phase of the
+    /// protocol (i.e., layer proof-of-work nonces, the number of partitions, and out-of-domain
+    /// evaluations), and writes the resulting bytes into the `target` writer.
+    ///
+    /// Together with [write_query_phase_into()](FriProof::write_query_phase_into), this allows
+    /// a proof to be serialized in the order in which the verifier consumes it.
+    pub fn write_commit_phase_into<W: ByteWriter>(&self, target: &mut W) {
+        // write layer proof-of-work nonces
+        target.write_u8(self.pow_nonces.len() as u8);
+        for &nonce in self.pow_nonces.iter() {
+            target.write_u64(nonce);
+        }
+
+        // write number of partitions; if the proof contains out-of-domain evaluations, this is
+        // indicated by the most significant bit of the number of partitions, and the evaluations
+        // follow it; if the proof contains the number of committed layers, this is indicated by
+        // the next bit, and the number of layers follows the evaluations
+        let mut num_partitions = self.num_partitions;
+        if !self.ood_evaluations.is_empty() {
+            num_partitions |= OOD_EVALUATIONS_FLAG;
+        }
+        if self.num_committed_layers.is_some() {
+            num_partitions |= NUM_COMMITTED_LAYERS_FLAG;
+        }
+        target.write_u8(num_partitions);
+        if !self.ood_evaluations.is_empty() {
+            target.write_u16(self.ood_evaluations.len() as u16);
+            target.write_u8_slice(&self.ood_evaluations);
+        }
+        if let Some(num_committed_layers) = self.num_committed_layers {
+            target.write_u8(num_committed_layers);
+        }
+    }
+
+    /// Serializes the part of this proof read by the verifier during the query phase of the
+    /// protocol (i.e., layers and the remainder), and writes the resulting bytes into the
+    /// `target` writer.
+    pub fn write_query_phase_into<W: ByteWriter>(&self, target: &mut W) {
         // write layers
         target.write_u8(self.layers.len() as u8);
         for layer in self.layers.iter() {
@@ -208,9 +455,72 @@ impl Serializable for FriProof {
         // write remainder
         target.write_u8(self.remainder.len().trailing_zeros() as u8);
         target.write_u8_slice(&self.remainder);
+    }
+
+    /// Reads the part of a proof serialized via
+    /// [write_commit_phase_into()](FriProof::write_commit_phase_into) from the specified `source`.
+    ///
+    /// The returned proof contains neither layers nor the remainder; these must be read via
+    /// [read_query_phase_from()](FriProof::read_query_phase_from) before the proof can be parsed.
+    ///
+    /// # Errors
+    /// Returns an error if valid layer proof-of-work nonces, the number of partitions, and
+    /// out-of-domain evaluations could not be read from the source.
+    pub fn read_commit_phase_from<R: ByteReader>(
+        source: &mut R,
+    ) -> Result<Self, DeserializationError> {
+        let (pow_nonces, num_partitions, ood_evaluations, num_committed_layers) =
+            read_commit_phase(source, false)?;
+        Ok(FriProof {
+            layers: Vec::new(),
+            remainder: Vec::new(),
+            pow_nonces,
+            ood_evaluations,
+            num_committed_layers,
+            num_partitions,
+        })
+    }
 
-        // write number of partitions
-        target.write_u8(self.num_partitions);
+    /// Reads the part of a proof serialized via
+    /// [write_query_phase_into()](FriProof::write_query_phase_into) from the specified `source`
+    /// into this proof.
+    ///
+    /// # Errors
+    /// Returns an error if valid layers and remainder could not be read from the source.
+    pub fn read_query_phase_from<R: ByteReader>(
+        &mut self,
+        source: &mut R,
+    ) -> Result<(), DeserializationError> {
+        let (layers, remainder) = read_query_phase(source, false)?;
+        self.layers = layers;
+        self.remainder = remainder;
+        Ok(())
+    }
+
+    /// Advances the specified `source` past a proof serialized via the [Serializable] trait
+    /// without reading the values of the proof into memory, and returns the number of layers in
+    /// the skipped proof.
+    ///
+    /// The structure of the proof is validated in the same way as when the proof is read via
+    /// the [Deserializable] trait; thus, a proof which is skipped successfully can also be read.
+    ///
+    /// # Errors
+    /// Returns an error if a valid proof could not be read from the source.
+    pub fn skip_from<R: ByteReader>(source: &mut R) -> Result<usize, DeserializationError> {
+        let (layers, _) = read_query_phase(source, true)?;
+        read_commit_phase(source, true)?;
+        Ok(layers.len())
+    }
+}
+
+// SERIALIZATION / DESERIALIZATION
+// ------------------------------------------------------------------------------------------------
+
+impl Serializable for FriProof {
+    /// Serializes `self` and writes the resulting bytes into the `target` writer.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.write_query_phase_into(target);
+        self.write_commit_phase_into(target);
     }
 }
 
@@ -220,25 +530,39 @@ impl Deserializable for FriProof {
     /// # Errors
     /// Returns an error if a valid proof could not be read from the source.
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
-        // read layers
-        let num_layers = source.read_u8()? as usize;
-        let layers = FriProofLayer::read_batch_from(source, num_layers)?;
-
-        // read remainder
-        let remainder_bytes = 2usize.pow(source.read_u8()? as u32);
-        let remainder = source.read_u8_vec(remainder_bytes)?;
-
-        // read number of partitions
-        let num_partitions = source.read_u8()?;
-
+        let (layers, remainder) = read_query_phase(source, false)?;
+        let (pow_nonces, num_partitions, ood_evaluations, num_committed_layers) =
+            read_commit_phase(source, false)?;
         Ok(FriProof {
             layers,
             remainder,
+            pow_nonces,
+            ood_evaluations,
+            num_committed_layers,
             num_partitions,
         })
     }
 }
 
+#[cfg(feature = "serde")]
+impl Serialize for FriProof {
+    /// Serializes `self` into the `serializer` as bytes of its binary serialization.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serde_adapter::serialize(self, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for FriProof {
+    /// Reads a FRI proof from bytes of its binary serialization read from the `deserializer`.
+    ///
+    /// # Errors
+    /// Returns an error if a valid FRI proof could not be read from the `deserializer`.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        serde_adapter::deserialize(deserializer)
+    }
+}
+
 // FRI PROOF LAYER
 // ================================================================================================
 
@@ -303,7 +627,7 @@ impl FriProofLayer {
     {
         // make sure the number of value bytes can be parsed into a whole number of queries
         let num_query_bytes = E::ELEMENT_BYTES * folding_factor;
-        if self.values.len() % num_query_bytes != 0 {
+        if !self.values.len().is_multiple_of(num_query_bytes) {
             return Err(DeserializationError::InvalidValue(format!(
                 "number of value bytes ({}) does not divide into whole number of queries",
                 self.values.len(),
@@ -316,21 +640,23 @@ impl FriProofLayer {
                 "a FRI layer must contain at least one query".to_string(),
             ));
         }
-        let mut hashed_queries = vec![H::Digest::default(); num_queries];
-        let mut query_values = Vec::with_capacity(num_queries * folding_factor);
-
-        // read bytes corresponding to each query, convert them into field elements,
-        // and also hash them to build leaf nodes of the batch Merkle proof
+        // read bytes corresponding to each query and convert them into field elements
         let mut reader = SliceReader::new(&self.values);
-        for query_hash in hashed_queries.iter_mut() {
-            let mut qe = E::read_batch_from(&mut reader, folding_factor)?;
-            *query_hash = H::hash_elements(&qe);
-            query_values.append(&mut qe);
-        }
+        let query_values = E::read_batch_from(&mut reader, num_queries * folding_factor)?;
         if reader.has_more_bytes() {
             return Err(DeserializationError::UnconsumedBytes);
         }
 
+        // hash the values of each query to build leaf nodes of the batch Merkle proof; when
+        // `concurrent` feature is enabled, the queries are hashed in multiple threads
+        let mut hashed_queries = vec![H::Digest::default(); num_queries];
+        iter_mut!(hashed_queries)
+            .enumerate()
+            .for_each(|(i, query_hash)| {
+                let values = &query_values[i * folding_factor..(i + 1) * folding_factor];
+                *query_hash = H::hash_elements(values);
+            });
+
         // build batch Merkle proof
         let mut reader = SliceReader::new(&self.paths);
         let tree_depth = log2(domain_size) as u8;
@@ -365,19 +691,136 @@ impl Deserializable for FriProofLayer {
     /// # Errors
     /// Returns an error if a valid layer could not be read from the specified source.
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
-        // read values
-        let num_value_bytes = source.read_u32()?;
-        if num_value_bytes == 0 {
+        read_layer(source, false)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Reads layers and remainder bytes of a FRI proof from the specified `source`; if `skip` is
+/// true, the bytes are skipped, and the returned layers and remainder are empty.
+fn read_query_phase<R: ByteReader>(
+    source: &mut R,
+    skip: bool,
+) -> Result<(Vec<FriProofLayer>, Vec<u8>), DeserializationError> {
+    // read layers
+    let num_layers = source.read_u8()? as usize;
+    let layers = (0..num_layers)
+        .map(|_| source.read_in_context("FRI layer", |source| read_layer(source, skip)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // read remainder; the number of remainder bytes is serialized as log2 of its value
+    let remainder = source.read_in_context("FRI remainder", |source| {
+        let remainder_bytes = 2usize.pow(read_log2(source, "number of remainder bytes")?);
+        read_bytes(source, remainder_bytes, skip)
+    })?;
+
+    Ok((layers, remainder))
+}
+
+/// Reads layer proof-of-work nonces, log2 of the number of partitions, out-of-domain evaluation
+/// bytes, and the number of committed layers of a FRI proof from the specified `source`; if
+/// `skip` is true, out-of-domain evaluation bytes are skipped, and the returned evaluations are
+/// empty.
+#[allow(clippy::type_complexity)]
+fn read_commit_phase<R: ByteReader>(
+    source: &mut R,
+    skip: bool,
+) -> Result<(Vec<u64>, u8, Vec<u8>, Option<u8>), DeserializationError> {
+    // read layer proof-of-work nonces
+    let num_nonces = source.read_u8()? as usize;
+    let mut pow_nonces = Vec::with_capacity(num_nonces);
+    for _ in 0..num_nonces {
+        pow_nonces.push(source.read_u64()?);
+    }
+
+    // read number of partitions; this is serialized as log2 of its value, with the two most
+    // significant bits indicating whether out-of-domain evaluations and the number of committed
+    // layers follow
+    let num_partitions = source.read_u8()?;
+    let ood_evaluations = if num_partitions & OOD_EVALUATIONS_FLAG != 0 {
+        let num_bytes = source.read_u16()? as usize;
+        if num_bytes == 0 {
             return Err(DeserializationError::InvalidValue(
-                "a FRI proof layer must contain at least one queried evaluation".to_string(),
+                "out-of-domain evaluations cannot be empty".to_string(),
             ));
         }
-        let values = source.read_u8_vec(num_value_bytes as usize)?;
+        read_bytes(source, num_bytes, skip)?
+    } else {
+        Vec::new()
+    };
+    let num_committed_layers = if num_partitions & NUM_COMMITTED_LAYERS_FLAG != 0 {
+        Some(source.read_u8()?)
+    } else {
+        None
+    };
+    let num_partitions = check_log2(
+        num_partitions & !(OOD_EVALUATIONS_FLAG | NUM_COMMITTED_LAYERS_FLAG),
+        "number of partitions",
+    )?;
+
+    Ok((
+        pow_nonces,
+        num_partitions as u8,
+        ood_evaluations,
+        num_committed_layers,
+    ))
+}
 
-        // read paths
-        let num_paths_bytes = source.read_u32()?;
-        let paths = source.read_u8_vec(num_paths_bytes as usize)?;
+/// Reads a single FRI proof layer from the specified `source`; if `skip` is true, the bytes of
+/// the layer are skipped, and the returned layer is empty.
+fn read_layer<R: ByteReader>(
+    source: &mut R,
+    skip: bool,
+) -> Result<FriProofLayer, DeserializationError> {
+    // read values
+    let num_value_bytes = source.read_u32()?;
+    if num_value_bytes == 0 {
+        return Err(DeserializationError::InvalidValue(
+            "a FRI proof layer must contain at least one queried evaluation".to_string(),
+        ));
+    }
+    let values = read_bytes(source, num_value_bytes as usize, skip)?;
+
+    // read paths
+    let num_paths_bytes = source.read_u32()?;
+    let paths = read_bytes(source, num_paths_bytes as usize, skip)?;
+
+    Ok(FriProofLayer { values, paths })
+}
+
+/// Reads the specified number of bytes from the `source`; if `skip` is true, the bytes are
+/// skipped, and an empty vector is returned.
+fn read_bytes<R: ByteReader>(
+    source: &mut R,
+    len: usize,
+    skip: bool,
+) -> Result<Vec<u8>, DeserializationError> {
+    if skip {
+        source.skip_bytes(len)?;
+        Ok(Vec::new())
+    } else {
+        source.read_u8_vec(len)
+    }
+}
+
+/// Reads log2 of a value from the specified `source` and makes sure that the value fits into
+/// `usize`.
+fn read_log2<R: ByteReader>(source: &mut R, name: &str) -> Result<u32, DeserializationError> {
+    check_log2(source.read_u8()?, name)
+}
 
-        Ok(FriProofLayer { values, paths })
+/// Makes sure that a value with the specified log2 fits into `usize`, and returns the log2.
+fn check_log2(value_log2: u8, name: &str) -> Result<u32, DeserializationError> {
+    let value_log2 = value_log2 as u32;
+    if value_log2 >= usize::BITS {
+        return Err(DeserializationError::InvalidValue(format!(
+            "{} cannot be greater than 2^{}, but was 2^{}",
+            name,
+            usize::BITS - 1,
+            value_log2
+        )));
     }
+    Ok(value_log2)
 }
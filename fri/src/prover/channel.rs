@@ -45,6 +45,32 @@ pub trait ProverChannel<E: FieldElement> {
     /// prover, in the non-interactive version, the α is pseudo-randomly generated based on the
     /// values the prover previously wrote into the channel.
     fn draw_fri_alpha(&mut self) -> E;
+
+    /// Determines a nonce which, when hashed with the current state of the channel, results in
+    /// a value with at least `grinding_factor` leading zeros; the nonce is then absorbed into
+    /// the channel and returned.
+    ///
+    /// This method is invoked after each layer commitment only when per-layer proof-of-work is
+    /// enabled (i.e., layer grinding factor in [FriOptions](crate::FriOptions) is not zero).
+    fn grind_fri_layer(&mut self, grinding_factor: u32) -> u64;
+
+    /// Sends a hash of out-of-domain evaluations to the verifier.
+    ///
+    /// This method is invoked only when STIR is used as the low-degree test: in every STIR round,
+    /// the prover evaluates the folded polynomial at a random out-of-domain point, and the
+    /// evaluation must be absorbed into the channel before any further values are drawn from it.
+    fn commit_fri_ood_evaluations(
+        &mut self,
+        evaluations_hash: <<Self as ProverChannel<E>>::Hasher as Hasher>::Digest,
+    );
+
+    /// Returns `num_indexes` integers drawn independently and uniformly at random from the range
+    /// [0, `domain_size`); the returned integers may contain duplicates.
+    ///
+    /// This method is invoked only when STIR is used as the low-degree test: in every STIR round,
+    /// the verifier queries the committed layers at random indexes during the commit phase of
+    /// the protocol.
+    fn draw_fri_query_indexes(&mut self, num_indexes: usize, domain_size: usize) -> Vec<usize>;
 }
 
 // DEFAULT PROVER CHANNEL IMPLEMENTATION
@@ -131,4 +157,21 @@ where
     fn draw_fri_alpha(&mut self) -> E {
         self.public_coin.draw().expect("failed to draw FRI alpha")
     }
+
+    fn grind_fri_layer(&mut self, grinding_factor: u32) -> u64 {
+        let nonce = (1..u64::MAX)
+            .find(|&nonce| self.public_coin.check_leading_zeros(nonce) >= grinding_factor)
+            .expect("nonce not found");
+        self.public_coin.reseed_with_int(nonce);
+        nonce
+    }
+
+    fn commit_fri_ood_evaluations(&mut self, evaluations_hash: H::Digest) {
+        self.public_coin.reseed(evaluations_hash);
+    }
+
+    fn draw_fri_query_indexes(&mut self, num_indexes: usize, domain_size: usize) -> Vec<usize> {
+        self.public_coin
+            .draw_integers_with_replacement(num_indexes, domain_size)
+    }
 }
@@ -4,21 +4,53 @@
 // LICENSE file in the root directory of this source tree.
 
 use crate::{
-    folding::{apply_drp, fold_positions},
+    folding::{apply_drp_into, fold_positions},
     proof::{FriProof, FriProofLayer},
     utils::hash_values,
-    FriOptions,
+    FriOptions, LowDegreeTest, RemainderFormat,
 };
 use core::marker::PhantomData;
 use crypto::{ElementHasher, Hasher, MerkleTree};
-use math::{FieldElement, StarkField};
+use math::{
+    batch_inversion, fft, get_power_series_with_offset, log2, polynom, FieldElement, StarkField,
+};
 use utils::{collections::Vec, flatten_vector_elements, group_slice_elements, transpose_slice};
 
 mod channel;
 pub use channel::{DefaultProverChannel, ProverChannel};
 
 #[cfg(test)]
-mod tests;
+pub(crate) mod tests;
+
+// LOW-DEGREE PROVER
+// ================================================================================================
+
+/// Defines an interface for the prover component of a low-degree test.
+///
+/// This interface is implemented by [FriProver] and by [StirProver](crate::StirProver), and
+/// makes it possible to select the low-degree test at runtime (e.g., based on proof options).
+/// Proof generation is performed in two phases: layers are committed to via
+/// [commit_layers()](LowDegreeProver::commit_layers), and then the proof is built via
+/// [build_proof()](LowDegreeProver::build_proof) for the positions at which the evaluations
+/// supplied to the prover are queried by the verifier.
+pub trait LowDegreeProver<E: FieldElement, C: ProverChannel<E>> {
+    /// Executes the commit phase of the low-degree test against the specified `evaluations`,
+    /// and invokes `on_layer` with the index of every layer right after the layer is built.
+    fn commit_layers(
+        &mut self,
+        channel: &mut C,
+        evaluations: Vec<E>,
+        on_layer: &mut dyn FnMut(usize),
+    );
+
+    /// Executes the query phase of the low-degree test for the specified `positions` and returns
+    /// the resulting proof.
+    fn build_proof(&mut self, positions: &[usize]) -> FriProof;
+
+    /// Returns the number of layers (including the last layer) built during the last execution
+    /// of the commit phase.
+    fn num_layers(&self) -> usize;
+}
 
 // TYPES AND INTERFACES
 // ================================================================================================
@@ -48,6 +80,8 @@ mod tests;
 /// (see [folding](crate::folding)). With every application of the DRP, the degree of the function
 /// *f* (and size of the domain over which it is evaluated) is reduced by the `folding_factor`
 /// until the remaining evaluations fit into a vector of at most `max_remainder_size` elements.
+/// If a folding schedule is specified in [FriOptions], the degree at each layer is reduced by
+/// the folding factor for that layer instead.
 ///
 /// At each layer of reduction, the prover commits to the current set of evaluations. This is done
 /// by building a Merkle tree from the evaluations and sending the root of the tree to the verifier
@@ -66,6 +100,11 @@ mod tests;
 /// The prover keeps all FRI layers (consisting of evaluations and corresponding Merkle trees) in
 /// its internal state.
 ///
+/// The last FRI layer (the remainder) is committed to in the same way as all other layers when
+/// the remainder is sent as evaluations. When the remainder is sent as coefficients (see
+/// [RemainderFormat]), the prover interpolates the remainder into a polynomial, and commits to
+/// it by writing a hash of the polynomial coefficients into the channel instead.
+///
 /// # Query phase
 /// In the query phase, which is executed via [build_proof()](FriProver::build_proof()) function,
 /// the prover receives a set of positions in the domain *D* from the verifier. The prover then
@@ -94,15 +133,47 @@ where
 {
     options: FriOptions,
     layers: Vec<FriLayer<B, E, H>>,
+    remainder: Vec<E>,
+    pow_nonces: Vec<u64>,
+    ood_evaluations: Vec<E>,
     _channel: PhantomData<C>,
 }
 
-struct FriLayer<B: StarkField, E: FieldElement<BaseField = B>, H: Hasher> {
+pub(crate) struct FriLayer<B: StarkField, E: FieldElement<BaseField = B>, H: Hasher> {
     tree: MerkleTree<H>,
     evaluations: Vec<E>,
     _base_field: PhantomData<B>,
 }
 
+impl<B: StarkField, E: FieldElement<BaseField = B>, H: ElementHasher<BaseField = B>>
+    FriLayer<B, E, H>
+{
+    /// Commits to the specified evaluations by transposing them into a matrix of N columns, and
+    /// building a Merkle tree from the rows of this matrix; this way, N values can be de-committed
+    /// with a single Merkle authentication path.
+    pub(crate) fn new<const N: usize>(evaluations: &[E]) -> Self {
+        let transposed_evaluations = transpose_slice::<E, N>(evaluations);
+        let hashed_evaluations = hash_values::<H, E, N>(&transposed_evaluations);
+        let tree =
+            MerkleTree::<H>::new(hashed_evaluations).expect("failed to construct FRI layer tree");
+        FriLayer {
+            tree,
+            evaluations: flatten_vector_elements(transposed_evaluations),
+            _base_field: PhantomData,
+        }
+    }
+
+    /// Returns the root of the Merkle tree of this layer.
+    pub(crate) fn root(&self) -> H::Digest {
+        *self.tree.root()
+    }
+
+    /// Returns the number of evaluations committed to in this layer.
+    pub(crate) fn num_evaluations(&self) -> usize {
+        self.evaluations.len()
+    }
+}
+
 // PROVER IMPLEMENTATION
 // ================================================================================================
 
@@ -120,6 +191,9 @@ where
         FriProver {
             options,
             layers: Vec::new(),
+            remainder: Vec::new(),
+            pow_nonces: Vec::new(),
+            ood_evaluations: Vec::new(),
             _channel: PhantomData,
         }
     }
@@ -128,6 +202,9 @@ where
     // --------------------------------------------------------------------------------------------
 
     /// Returns folding factor for this prover.
+    ///
+    /// If a folding schedule is specified in the options of this prover, this is the folding
+    /// factor for the layers beyond the end of the schedule.
     pub fn folding_factor(&self) -> usize {
         self.options.folding_factor()
     }
@@ -137,15 +214,22 @@ where
         self.options.domain_offset()
     }
 
-    /// Returns number of FRI layers computed during the last execution of the
-    /// [build_layers()](FriProver::build_layers()) method.
+    /// Returns number of FRI layers (including the remainder) computed during the last execution
+    /// of the [build_layers()](FriProver::build_layers()) method.
     pub fn num_layers(&self) -> usize {
-        self.layers.len()
+        if self.remainder.is_empty() {
+            0
+        } else {
+            self.layers.len() + 1
+        }
     }
 
     /// Clears a vector of internally stored layers.
     pub fn reset(&mut self) {
         self.layers.clear();
+        self.remainder.clear();
+        self.pow_nonces.clear();
+        self.ood_evaluations.clear();
     }
 
     // COMMIT PHASE
@@ -157,38 +241,109 @@ where
     /// application of the DRP the degree of the function (and size of the domain) is reduced by
     /// `folding_factor` until the remaining evaluations can fit into a vector of at most
     /// `max_remainder_size`. At each layer of reduction the current evaluations are committed to
-    /// using a Merkle tree, and the root of this tree is written into the channel. After this the
-    /// prover draws a random field element α from the channel, and uses it in the next application
-    /// of the DRP.
+    /// using a Merkle tree, and the root of this tree is written into the channel. If per-layer
+    /// proof-of-work is enabled, the prover then grinds a nonce for the layer via the channel.
+    /// After this the prover draws a random field element α from the channel, and uses it in the
+    /// next application of the DRP. The remaining evaluations (the remainder) are committed to
+    /// in the format specified by the [RemainderFormat] of the options.
+    ///
+    /// If early termination is enabled in the options, folding stops as soon as the degree of
+    /// the polynomial at the current layer is smaller than the max remainder degree. To determine
+    /// the degree, the prover interpolates `evaluations` into a polynomial once before the first
+    /// layer is built.
+    ///
+    /// If out-of-domain sampling is enabled in the options, after every application of the DRP
+    /// the prover draws an out-of-domain point *z* from the channel, and writes a commitment to
+    /// the evaluation of the folded polynomial at *z* into the channel. The next layer is then
+    /// built from the quotient of the folded polynomial by (*x* - *z*).
+    ///
+    /// # Panics
+    /// Panics if the prover state is dirty (the vector of layers is not empty).
+    pub fn build_layers(&mut self, channel: &mut C, evaluations: Vec<E>) {
+        self.build_layers_with_callback(channel, evaluations, |_| {});
+    }
+
+    /// Executes the commit phase of the FRI protocol, and invokes `on_layer` with the index of
+    /// every layer right after the layer has been built.
+    ///
+    /// This works exactly like [build_layers()](FriProver::build_layers()) method, but makes it
+    /// possible to track progress of the commit phase. The last layer reported via `on_layer` is
+    /// the remainder.
     ///
     /// # Panics
     /// Panics if the prover state is dirty (the vector of layers is not empty).
-    pub fn build_layers(&mut self, channel: &mut C, mut evaluations: Vec<E>) {
+    pub fn build_layers_with_callback<F: FnMut(usize)>(
+        &mut self,
+        channel: &mut C,
+        mut evaluations: Vec<E>,
+        mut on_layer: F,
+    ) {
         assert!(
-            self.layers.is_empty(),
+            self.remainder.is_empty(),
             "a prior proof generation request has not been completed yet"
         );
 
-        // reduce the degree by folding_factor at each iteration until the remaining polynomial
-        // is small enough; + 1 is for the remainder
-        for _ in 0..self.options.num_fri_layers(evaluations.len()) + 1 {
-            match self.folding_factor() {
+        // with early termination, folding stops as soon as the degree of the polynomial at the
+        // current layer is smaller than the max remainder degree; the degree is reduced at least
+        // by the folding factor of every layer, and thus, it needs to be computed only once
+        let remainder_len = self.options.fri_remainder_len(evaluations.len());
+        let mut degree = if self.options.early_termination() {
+            Some(get_degree::<B, E>(&evaluations))
+        } else {
+            None
+        };
+
+        // reduce the degree by the folding factor of each layer until the remaining polynomial
+        // is small enough
+        let max_num_layers = self.options.num_fri_layers(evaluations.len());
+        for layer_idx in 0..max_num_layers {
+            let folding_factor = self.options.layer_folding_factor(layer_idx);
+            if let Some(layer_degree) = degree {
+                if layer_degree < remainder_len {
+                    break;
+                }
+                degree = Some(layer_degree / folding_factor);
+            }
+            match folding_factor {
+                2 => self.build_layer::<2>(channel, &mut evaluations),
                 4 => self.build_layer::<4>(channel, &mut evaluations),
                 8 => self.build_layer::<8>(channel, &mut evaluations),
                 16 => self.build_layer::<16>(channel, &mut evaluations),
-                _ => unimplemented!("folding factor {} is not supported", self.folding_factor()),
+                _ => unimplemented!("folding factor {} is not supported", folding_factor),
             }
+            on_layer(layer_idx);
         }
 
-        // make sure remainder length does not exceed max allowed value
-        let last_layer = &self.layers[self.layers.len() - 1];
-        let remainder_size = last_layer.evaluations.len();
+        // make sure remainder length does not exceed max allowed value; with the direct
+        // low-degree test, the remainder is the entire polynomial, and with early termination,
+        // the remainder may be evaluated over a larger domain
+        let num_layers = self.layers.len();
         debug_assert!(
-            remainder_size <= self.options.max_remainder_size(),
+            evaluations.len() <= self.options.max_remainder_size()
+                || self.options.low_degree_test() == LowDegreeTest::Direct
+                || num_layers < max_num_layers,
             "last FRI layer cannot exceed {} elements, but was {} elements",
             self.options.max_remainder_size(),
-            remainder_size
+            evaluations.len()
         );
+
+        // commit to the remainder in the format specified by the options
+        match self.options.remainder_format() {
+            RemainderFormat::Evaluations => {
+                let folding_factor = self.options.layer_folding_factor(num_layers);
+                match folding_factor {
+                    2 => self.build_remainder::<2>(channel, evaluations),
+                    4 => self.build_remainder::<4>(channel, evaluations),
+                    8 => self.build_remainder::<8>(channel, evaluations),
+                    16 => self.build_remainder::<16>(channel, evaluations),
+                    _ => unimplemented!("folding factor {} is not supported", folding_factor),
+                }
+            }
+            RemainderFormat::Coefficients => {
+                self.build_remainder_coefficients(channel, evaluations, remainder_len)
+            }
+        }
+        on_layer(num_layers);
     }
 
     /// Builds a single FRI layer by first committing to the `evaluations`, then drawing a random
@@ -202,12 +357,24 @@ where
         let hashed_evaluations = hash_values::<H, E, N>(&transposed_evaluations);
         let evaluation_tree =
             MerkleTree::<H>::new(hashed_evaluations).expect("failed to construct FRI layer tree");
-        channel.commit_fri_layer(*evaluation_tree.root());
 
         // draw a pseudo-random coefficient from the channel, and use it in degree-respecting
         // projection to reduce the degree of evaluations by N
-        let alpha = channel.draw_fri_alpha();
-        *evaluations = apply_drp(&transposed_evaluations, self.domain_offset(), alpha);
+        // the folded evaluations are written into the buffer which held the evaluations of this
+        // layer; the evaluations themselves are retained in transposed form
+        let alpha = self.commit_layer(channel, *evaluation_tree.root());
+        evaluations.truncate(transposed_evaluations.len());
+        apply_drp_into(
+            &transposed_evaluations,
+            self.domain_offset(),
+            alpha,
+            evaluations,
+        );
+
+        // with out-of-domain sampling, the next layer is a quotient of the folded polynomial
+        if self.options.deep_sampling() {
+            self.apply_ood_sampling(channel, evaluations);
+        }
 
         self.layers.push(FriLayer {
             tree: evaluation_tree,
@@ -216,6 +383,79 @@ where
         });
     }
 
+    /// Draws an out-of-domain point *z* from the channel, commits to the evaluation *β* at *z* of
+    /// the polynomial *f* defined by the `evaluations`, and replaces the `evaluations` with
+    /// evaluations of the quotient (*f*(*x*) - *β*) / (*x* - *z*) over the same domain.
+    fn apply_ood_sampling(&mut self, channel: &mut C, evaluations: &mut [E]) {
+        let z = channel.draw_fri_alpha();
+
+        // interpolate the folded polynomial to evaluate it at the out-of-domain point
+        let domain_size = evaluations.len();
+        let mut poly = evaluations.to_vec();
+        let inv_twiddles = fft::get_inv_twiddles::<B>(domain_size);
+        fft::interpolate_poly_with_offset(&mut poly, &inv_twiddles, self.domain_offset());
+        let ood_evaluation = polynom::eval(&poly, z);
+        channel.commit_fri_ood_evaluations(H::hash_elements(&[ood_evaluation]));
+        self.ood_evaluations.push(ood_evaluation);
+
+        // divide the folded polynomial by (x - z) after subtracting its value at z
+        let domain_generator = B::get_root_of_unity(log2(domain_size));
+        let xs = get_power_series_with_offset(domain_generator, self.domain_offset(), domain_size);
+        let denominators = xs.into_iter().map(|x| E::from(x) - z).collect::<Vec<_>>();
+        let inv_denominators = batch_inversion(&denominators);
+        for (evaluation, inv_denominator) in evaluations.iter_mut().zip(inv_denominators) {
+            *evaluation = (*evaluation - ood_evaluation) * inv_denominator;
+        }
+    }
+
+    /// Commits to the remainder `evaluations` in the same way as to all other FRI layers, and
+    /// keeps the evaluations to be included in the proof.
+    fn build_remainder<const N: usize>(&mut self, channel: &mut C, evaluations: Vec<E>) {
+        let transposed_evaluations = transpose_slice::<E, N>(&evaluations);
+        let hashed_evaluations = hash_values::<H, E, N>(&transposed_evaluations);
+        let remainder_tree =
+            MerkleTree::<H>::new(hashed_evaluations).expect("failed to construct FRI layer tree");
+
+        // the α drawn after the remainder commitment is not used by the prover, but it is drawn
+        // by the verifier; thus, the prover must draw it as well to keep the channels in sync
+        self.commit_layer(channel, *remainder_tree.root());
+        self.remainder = evaluations;
+    }
+
+    /// Interpolates the remainder `evaluations` into a polynomial, commits to the coefficients
+    /// of this polynomial, and keeps the coefficients to be included in the proof.
+    ///
+    /// Only the first `num_coefficients` coefficients are kept; for an honest prover, all other
+    /// coefficients are zeros.
+    fn build_remainder_coefficients(
+        &mut self,
+        channel: &mut C,
+        mut evaluations: Vec<E>,
+        num_coefficients: usize,
+    ) {
+        let inv_twiddles = fft::get_inv_twiddles::<B>(evaluations.len());
+        fft::interpolate_poly_with_offset(&mut evaluations, &inv_twiddles, self.domain_offset());
+        evaluations.truncate(num_coefficients);
+
+        self.commit_layer(channel, H::hash_elements(&evaluations));
+        self.remainder = evaluations;
+    }
+
+    /// Writes the specified layer commitment into the channel, performs proof-of-work for the
+    /// layer if required, and returns the α drawn from the channel for the layer.
+    fn commit_layer(&mut self, channel: &mut C, commitment: H::Digest) -> E {
+        channel.commit_fri_layer(commitment);
+
+        // if required, perform proof-of-work for this layer before drawing the folding challenge
+        let grinding_factor = self.options.layer_grinding_factor();
+        if grinding_factor > 0 {
+            self.pow_nonces
+                .push(channel.grind_fri_layer(grinding_factor));
+        }
+
+        channel.draw_fri_alpha()
+    }
+
     // QUERY PHASE
     // --------------------------------------------------------------------------------------------
     /// Executes query phase of FRI protocol.
@@ -223,61 +463,107 @@ where
     /// For each of the provided `positions`, corresponding evaluations from each of the layers
     /// (excluding the remainder layer) are recorded into the proof together with Merkle
     /// authentication paths from the root of layer commitment trees. For the remainder, we include
-    /// the whole set of evaluations into the proof.
+    /// the whole set of evaluations (or all coefficients of the remainder polynomial, depending
+    /// on the [RemainderFormat] of the options) into the proof.
+    ///
+    /// Positions which fold into the same position of a layer (as well as repeated positions)
+    /// are queried only once, and authentication paths of all queries against a layer share
+    /// their common nodes; the verifier reconstructs the mapping from the original positions.
     ///
     /// # Panics
     /// Panics is the prover state is clean (no FRI layers have been build yet).
     pub fn build_proof(&mut self, positions: &[usize]) -> FriProof {
         assert!(
-            !self.layers.is_empty(),
+            !self.remainder.is_empty(),
             "FRI layers have not been built yet"
         );
         let mut positions = positions.to_vec();
-        let mut domain_size = self.layers[0].evaluations.len();
-        let folding_factor = self.options.folding_factor();
 
-        // for all FRI layers, except the last one, record tree root, determine a set of query
+        // for all FRI layers, except the remainder, record tree root, determine a set of query
         // positions, and query the layer at these positions.
         let mut layers = Vec::with_capacity(self.layers.len());
-        for i in 0..self.layers.len() - 1 {
-            positions = fold_positions(&positions, domain_size, folding_factor);
+        for (i, layer) in self.layers.iter().enumerate() {
+            let folding_factor = self.options.layer_folding_factor(i);
+            positions = fold_positions(&positions, layer.evaluations.len(), folding_factor);
 
             // sort of a static dispatch for folding_factor parameter
             let proof_layer = match folding_factor {
-                4 => query_layer::<B, E, H, 4>(&self.layers[i], &positions),
-                8 => query_layer::<B, E, H, 8>(&self.layers[i], &positions),
-                16 => query_layer::<B, E, H, 16>(&self.layers[i], &positions),
+                2 => query_layer::<B, E, H, 2>(layer, &positions),
+                4 => query_layer::<B, E, H, 4>(layer, &positions),
+                8 => query_layer::<B, E, H, 8>(layer, &positions),
+                16 => query_layer::<B, E, H, 16>(layer, &positions),
                 _ => unimplemented!("folding factor {} is not supported", folding_factor),
             };
 
             layers.push(proof_layer);
-            domain_size /= folding_factor;
         }
 
-        // use the remaining polynomial values directly as proof; last layer values contain
-        // remainder in transposed form - so, we un-transpose it first
-        let last_values = &self.layers[self.layers.len() - 1].evaluations;
-        let mut remainder = E::zeroed_vector(last_values.len());
-        let n = last_values.len() / folding_factor;
-        for i in 0..n {
-            for j in 0..folding_factor {
-                remainder[i + n * j] = last_values[i * folding_factor + j];
-            }
+        // use the remainder (either evaluations or coefficients) directly as proof, and clear
+        // layers so that another proof can be generated
+        let remainder = core::mem::take(&mut self.remainder);
+        let pow_nonces = core::mem::take(&mut self.pow_nonces);
+        let ood_evaluations = core::mem::take(&mut self.ood_evaluations);
+        self.reset();
+
+        // with early termination, the number of layers is not implied by the options, and thus,
+        // is included in the proof
+        let proof =
+            FriProof::new(layers, remainder, pow_nonces, 1).with_ood_evaluations(&ood_evaluations);
+        if self.options.early_termination() {
+            proof.with_num_committed_layers()
+        } else {
+            proof
         }
+    }
+}
 
-        // clear layers so that another proof can be generated
-        self.reset();
+impl<B, E, C, H> LowDegreeProver<E, C> for FriProver<B, E, C, H>
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+    C: ProverChannel<E, Hasher = H>,
+    H: ElementHasher<BaseField = B>,
+{
+    fn commit_layers(
+        &mut self,
+        channel: &mut C,
+        evaluations: Vec<E>,
+        on_layer: &mut dyn FnMut(usize),
+    ) {
+        self.build_layers_with_callback(channel, evaluations, on_layer);
+    }
+
+    fn build_proof(&mut self, positions: &[usize]) -> FriProof {
+        FriProver::build_proof(self, positions)
+    }
 
-        FriProof::new(layers, remainder, 1)
+    fn num_layers(&self) -> usize {
+        FriProver::num_layers(self)
     }
 }
 
 // HELPER FUNCTIONS
 // ================================================================================================
 
+/// Returns the degree of the polynomial defined by the specified evaluations.
+///
+/// The evaluations are interpolated over the non-shifted domain; this does not affect the degree
+/// of the resulting polynomial.
+fn get_degree<B: StarkField, E: FieldElement<BaseField = B>>(evaluations: &[E]) -> usize {
+    let mut poly = evaluations.to_vec();
+    let inv_twiddles = fft::get_inv_twiddles::<B>(poly.len());
+    fft::interpolate_poly(&mut poly, &inv_twiddles);
+    polynom::degree_of(&poly)
+}
+
 /// Builds a single proof layer by querying the evaluations of the passed in FRI layer at the
 /// specified positions.
-fn query_layer<B: StarkField, E: FieldElement<BaseField = B>, H: Hasher, const N: usize>(
+pub(crate) fn query_layer<
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+    H: Hasher,
+    const N: usize,
+>(
     layer: &FriLayer<B, E, H>,
     positions: &[usize],
 ) -> FriProofLayer {
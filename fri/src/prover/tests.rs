@@ -5,8 +5,8 @@
 
 use super::{DefaultProverChannel, FriProver};
 use crate::{
-    verifier::{DefaultVerifierChannel, FriVerifier},
-    FriOptions, FriProof, VerifierError,
+    verifier::{DefaultVerifierChannel, FriVerifier, FriVerifierContext},
+    FriBatch, FriOptions, FriProof, RemainderFormat, VerifierError,
 };
 use crypto::{hashers::Blake3_256, Hasher, RandomCoin};
 use math::{fft, fields::f128::BaseElement, FieldElement};
@@ -22,7 +22,54 @@ fn fri_prove_verify() {
     let trace_length = 4096;
     let lde_blowup = 8;
 
-    let options = FriOptions::new(lde_blowup, 4, 256);
+    for folding_factor in [2, 4, 8, 16] {
+        let options = FriOptions::new(lde_blowup, folding_factor, 256);
+        let mut channel = build_prover_channel(trace_length, &options);
+        let evaluations = build_evaluations(trace_length, lde_blowup);
+
+        // instantiate the prover and generate the proof
+        let mut prover = FriProver::new(options.clone());
+        prover.build_layers(&mut channel, evaluations.clone());
+        let positions = channel.draw_query_positions();
+        let proof = prover.build_proof(&positions);
+
+        // make sure the proof can be verified
+        let commitments = channel.layer_commitments().to_vec();
+        let max_degree = trace_length - 1;
+        let result = verify_proof(
+            proof.clone(),
+            commitments.clone(),
+            &evaluations,
+            max_degree,
+            trace_length * lde_blowup,
+            &positions,
+            &options,
+        );
+        assert!(result.is_ok(), "{:}", result.err().unwrap());
+
+        // make sure proof fails for invalid degree
+        let result = verify_proof(
+            proof,
+            commitments,
+            &evaluations,
+            max_degree - 256,
+            trace_length * lde_blowup,
+            &positions,
+            &options,
+        );
+        assert!(result.is_err());
+    }
+}
+
+#[test]
+fn fri_prove_verify_with_folding_schedule() {
+    let trace_length = 4096;
+    let lde_blowup = 8;
+
+    // the domain of 2^15 elements is folded into 2048, 1024, and 256 elements; the remainder
+    // layer is committed to using the default folding factor
+    let options = FriOptions::new(lde_blowup, 8, 256).with_folding_schedule(&[16, 2, 4]);
+    assert_eq!(3, options.num_fri_layers(trace_length * lde_blowup));
     let mut channel = build_prover_channel(trace_length, &options);
     let evaluations = build_evaluations(trace_length, lde_blowup);
 
@@ -31,6 +78,7 @@ fn fri_prove_verify() {
     prover.build_layers(&mut channel, evaluations.clone());
     let positions = channel.draw_query_positions();
     let proof = prover.build_proof(&positions);
+    assert_eq!(3, proof.num_layers());
 
     // make sure the proof can be verified
     let commitments = channel.layer_commitments().to_vec();
@@ -59,6 +107,484 @@ fn fri_prove_verify() {
     assert!(result.is_err());
 }
 
+#[test]
+fn fri_prove_verify_with_remainder_coefficients() {
+    let trace_length = 4096;
+    let lde_blowup = 8;
+
+    // the remainder of 128 evaluations is sent as 16 coefficients of a polynomial of degree 15
+    let options =
+        FriOptions::new(lde_blowup, 4, 256).with_remainder_format(RemainderFormat::Coefficients);
+    let mut channel = build_prover_channel(trace_length, &options);
+    let evaluations = build_evaluations(trace_length, lde_blowup);
+
+    // instantiate the prover and generate the proof
+    let mut prover = FriProver::new(options.clone());
+    prover.build_layers(&mut channel, evaluations.clone());
+    let positions = channel.draw_query_positions();
+    let proof = prover.build_proof(&positions);
+    let remainder = proof.parse_remainder::<BaseElement>().unwrap();
+    assert_eq!(16, remainder.len());
+
+    // make sure the proof can be verified
+    let commitments = channel.layer_commitments().to_vec();
+    let max_degree = trace_length - 1;
+    let result = verify_proof(
+        proof.clone(),
+        commitments.clone(),
+        &evaluations,
+        max_degree,
+        trace_length * lde_blowup,
+        &positions,
+        &options,
+    );
+    assert!(result.is_ok(), "{:}", result.err().unwrap());
+
+    // make sure proof fails for invalid degree
+    let result = verify_proof(
+        proof.clone(),
+        commitments.clone(),
+        &evaluations,
+        max_degree - 256,
+        trace_length * lde_blowup,
+        &positions,
+        &options,
+    );
+    assert!(result.is_err());
+
+    // make sure proof cannot be parsed as a proof with the remainder sent as evaluations
+    let options = FriOptions::new(lde_blowup, 4, 256);
+    let result = DefaultVerifierChannel::<BaseElement, Blake3>::new(
+        proof,
+        commitments,
+        trace_length * lde_blowup,
+        &options,
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn fri_prove_verify_direct() {
+    let trace_length = 256;
+    let lde_blowup = 8;
+
+    // no layers are built, and the polynomial is sent as 256 coefficients
+    let options = FriOptions::new(lde_blowup, 4, 32).with_direct();
+    assert_eq!(0, options.num_fri_layers(trace_length * lde_blowup));
+    let mut channel = build_prover_channel(trace_length, &options);
+    let evaluations = build_evaluations(trace_length, lde_blowup);
+
+    // instantiate the prover and generate the proof
+    let mut prover = FriProver::new(options.clone());
+    prover.build_layers(&mut channel, evaluations.clone());
+    assert_eq!(1, channel.layer_commitments().len());
+    let positions = channel.draw_query_positions();
+    let proof = prover.build_proof(&positions);
+    assert_eq!(0, proof.num_layers());
+    assert_eq!(trace_length, proof.num_remainder_elements::<BaseElement>());
+
+    // make sure the proof can be verified
+    let commitments = channel.layer_commitments().to_vec();
+    let max_degree = trace_length - 1;
+    let result = verify_proof(
+        proof.clone(),
+        commitments.clone(),
+        &evaluations,
+        max_degree,
+        trace_length * lde_blowup,
+        &positions,
+        &options,
+    );
+    assert!(result.is_ok(), "{:}", result.err().unwrap());
+
+    // make sure proof fails for invalid degree
+    let result = verify_proof(
+        proof,
+        commitments,
+        &evaluations,
+        max_degree - 64,
+        trace_length * lde_blowup,
+        &positions,
+        &options,
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn fri_prove_verify_with_early_termination() {
+    let trace_length = 4096;
+    let lde_blowup = 8;
+    let domain_size = trace_length * lde_blowup;
+
+    // the domain of 2^15 elements requires 4 layers, and the remainder is sent as 16 coefficients
+    let options = FriOptions::new(lde_blowup, 4, 256).with_early_termination();
+    assert_eq!(4, options.num_fri_layers(domain_size));
+    assert_eq!(16, options.fri_remainder_len(domain_size));
+
+    // a polynomial of degree 255 is folded into a polynomial of degree 63 and then 15; thus,
+    // folding stops after 2 layers
+    let evaluations = build_evaluations(256, domain_size / 256);
+    let mut channel = build_prover_channel(trace_length, &options);
+    let mut prover = FriProver::new(options.clone());
+    prover.build_layers(&mut channel, evaluations.clone());
+    assert_eq!(3, channel.layer_commitments().len());
+    let positions = channel.draw_query_positions();
+    let proof = prover.build_proof(&positions);
+    assert_eq!(2, proof.num_layers());
+    assert_eq!(Some(2), proof.num_committed_layers());
+    assert_eq!(16, proof.num_remainder_elements::<BaseElement>());
+
+    // make sure the proof can be verified against the original degree bound
+    let commitments = channel.layer_commitments().to_vec();
+    let result = verify_proof(
+        proof.clone(),
+        commitments.clone(),
+        &evaluations,
+        trace_length - 1,
+        domain_size,
+        &positions,
+        &options,
+    );
+    assert!(result.is_ok(), "{:}", result.err().unwrap());
+
+    // make sure the proof cannot be parsed without early termination
+    let result = DefaultVerifierChannel::<BaseElement, Blake3>::new(
+        proof,
+        commitments,
+        domain_size,
+        &FriOptions::new(lde_blowup, 4, 256).with_remainder_format(RemainderFormat::Coefficients),
+    );
+    assert!(result.is_err());
+
+    // a polynomial of the max degree is folded through all layers
+    let evaluations = build_evaluations(trace_length, lde_blowup);
+    let mut channel = build_prover_channel(trace_length, &options);
+    let mut prover = FriProver::new(options.clone());
+    prover.build_layers(&mut channel, evaluations.clone());
+    let positions = channel.draw_query_positions();
+    let proof = prover.build_proof(&positions);
+    assert_eq!(4, proof.num_layers());
+
+    let commitments = channel.layer_commitments().to_vec();
+    let result = verify_proof(
+        proof,
+        commitments,
+        &evaluations,
+        trace_length - 1,
+        domain_size,
+        &positions,
+        &options,
+    );
+    assert!(result.is_ok(), "{:}", result.err().unwrap());
+}
+
+#[test]
+fn fri_prove_verify_with_deep_sampling() {
+    let trace_length = 4096;
+    let lde_blowup = 8;
+    let domain_size = trace_length * lde_blowup;
+
+    let base_options = FriOptions::new(lde_blowup, 4, 256).with_deep_sampling();
+    let coefficient_options = base_options
+        .clone()
+        .with_remainder_format(RemainderFormat::Coefficients);
+    for options in [base_options, coefficient_options] {
+        let mut channel = build_prover_channel(trace_length, &options);
+        let evaluations = build_evaluations(trace_length, lde_blowup);
+
+        // instantiate the prover and generate the proof; there is an out-of-domain evaluation
+        // for every layer
+        let mut prover = FriProver::new(options.clone());
+        prover.build_layers(&mut channel, evaluations.clone());
+        let positions = channel.draw_query_positions();
+        let proof = prover.build_proof(&positions);
+        assert_eq!(4, proof.num_layers());
+        assert_eq!(4, proof.num_ood_evaluations::<BaseElement>());
+        assert_eq!(4, options.num_ood_evaluations(domain_size));
+
+        // make sure the proof can be verified
+        let commitments = channel.layer_commitments().to_vec();
+        let max_degree = trace_length - 1;
+        let result = verify_proof(
+            proof.clone(),
+            commitments.clone(),
+            &evaluations,
+            max_degree,
+            domain_size,
+            &positions,
+            &options,
+        );
+        assert!(result.is_ok(), "{:}", result.err().unwrap());
+
+        // make sure proof fails for invalid degree
+        let result = verify_proof(
+            proof.clone(),
+            commitments.clone(),
+            &evaluations,
+            max_degree - 256,
+            domain_size,
+            &positions,
+            &options,
+        );
+        assert!(result.is_err());
+
+        // make sure proof fails without out-of-domain sampling
+        let options =
+            FriOptions::new(lde_blowup, 4, 256).with_remainder_format(options.remainder_format());
+        let result = verify_proof(
+            proof,
+            commitments,
+            &evaluations,
+            max_degree,
+            domain_size,
+            &positions,
+            &options,
+        );
+        assert_eq!(Err(VerifierError::NumOodEvaluationsMismatch(0, 4)), result);
+    }
+}
+
+#[test]
+fn fri_verify_with_context() {
+    let trace_length = 1024;
+    let lde_blowup = 8;
+    let domain_size = trace_length * lde_blowup;
+    let options = FriOptions::new(lde_blowup, 4, 32).with_folding_schedule(&[8]);
+    let context = FriVerifierContext::<BaseElement>::new(options.clone(), trace_length - 1);
+    assert_eq!(domain_size, context.domain_size());
+
+    // the generator of every layer domain is consistent with the size of the layer domain
+    for depth in 0..=options.num_fri_layers(domain_size) {
+        let layer_domain_size = options.layer_domain_size(domain_size, depth);
+        let generator = context.layer_generator(depth);
+        assert_eq!(
+            BaseElement::ONE,
+            generator.exp((layer_domain_size as u64).into())
+        );
+        assert_ne!(
+            BaseElement::ONE,
+            generator.exp((layer_domain_size as u64 / 2).into())
+        );
+    }
+    assert_eq!(8, context.layer_folding_roots(0).len());
+    assert_eq!(4, context.layer_folding_roots(1).len());
+
+    // the same context can be used to verify multiple proofs
+    for poly_size in [trace_length, trace_length / 2] {
+        let evaluations = build_evaluations(poly_size, domain_size / poly_size);
+        let mut channel = build_prover_channel(trace_length, &options);
+        let mut prover = FriProver::new(options.clone());
+        prover.build_layers(&mut channel, evaluations.clone());
+        let positions = channel.draw_query_positions();
+        let proof = prover.build_proof(&positions);
+
+        let commitments = channel.layer_commitments().to_vec();
+        let mut verifier_channel = DefaultVerifierChannel::<BaseElement, Blake3>::new(
+            proof,
+            commitments,
+            domain_size,
+            &options,
+        )
+        .unwrap();
+        let mut coin = RandomCoin::<BaseElement, Blake3>::new(&[]);
+        let verifier =
+            FriVerifier::with_context(&mut verifier_channel, &mut coin, &context).unwrap();
+        let queried_evaluations = positions
+            .iter()
+            .map(|&p| evaluations[p])
+            .collect::<Vec<_>>();
+        let result = verifier.verify(&mut verifier_channel, &queried_evaluations, &positions);
+        assert!(result.is_ok(), "{:}", result.err().unwrap());
+    }
+}
+
+#[test]
+fn fri_prove_verify_batch() {
+    let trace_length = 1024;
+    let lde_blowup = 8;
+    let domain_size = trace_length * lde_blowup;
+    let options = FriOptions::new(lde_blowup, 4, 256);
+    let domain_offset = options.domain_offset::<BaseElement>();
+
+    // polynomials of degrees 1023, 511, and 255 are proven against degree 1023
+    let batch = FriBatch::new(trace_length - 1, &[trace_length - 1, 511, 255]);
+    let polys = batch
+        .degrees()
+        .iter()
+        .map(|&degree| build_evaluations_with_offset(degree + 1, domain_size, domain_offset))
+        .collect::<Vec<_>>();
+    let mut coin = RandomCoin::<BaseElement, Blake3>::new(&[1, 2, 3, 4]);
+    let coefficients = batch.draw_coefficients(&mut coin).unwrap();
+    let evaluations = batch.combine_evaluations(&polys, &coefficients, domain_offset);
+
+    // instantiate the prover and generate the proof for the combined polynomial
+    let mut channel = build_prover_channel(trace_length, &options);
+    let mut prover = FriProver::new(options.clone());
+    prover.build_layers(&mut channel, evaluations.clone());
+    let positions = channel.draw_query_positions();
+    let proof = prover.build_proof(&positions);
+
+    // combining queried evaluations should yield evaluations of the combined polynomial
+    let queried_evaluations = polys
+        .iter()
+        .map(|poly| positions.iter().map(|&p| poly[p]).collect())
+        .collect::<Vec<_>>();
+    let combined_evaluations = batch.combine_queries(
+        &queried_evaluations,
+        &positions,
+        domain_size,
+        &coefficients,
+        domain_offset,
+    );
+    for (&position, &evaluation) in positions.iter().zip(&combined_evaluations) {
+        assert_eq!(evaluations[position], evaluation);
+    }
+
+    // make sure the proof can be verified
+    let commitments = channel.layer_commitments().to_vec();
+    let result = verify_proof(
+        proof,
+        commitments,
+        &evaluations,
+        batch.max_degree(),
+        domain_size,
+        &positions,
+        &options,
+    );
+    assert!(result.is_ok(), "{:}", result.err().unwrap());
+
+    // make sure proof fails when one of the polynomials exceeds its degree bound
+    let batch = FriBatch::new(trace_length - 1, &[trace_length - 1, 255, 255]);
+    let evaluations = batch.combine_evaluations(&polys, &coefficients, domain_offset);
+    let mut channel = build_prover_channel(trace_length, &options);
+    let mut prover = FriProver::new(options.clone());
+    prover.build_layers(&mut channel, evaluations.clone());
+    let positions = channel.draw_query_positions();
+    let proof = prover.build_proof(&positions);
+    let commitments = channel.layer_commitments().to_vec();
+    let result = verify_proof(
+        proof,
+        commitments,
+        &evaluations,
+        batch.max_degree(),
+        domain_size,
+        &positions,
+        &options,
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn fri_prove_verify_with_layer_grinding() {
+    let trace_length = 1024;
+    let lde_blowup = 8;
+
+    let options = FriOptions::new(lde_blowup, 4, 256).with_layer_grinding_factor(4);
+    let mut channel = build_prover_channel(trace_length, &options);
+    let evaluations = build_evaluations(trace_length, lde_blowup);
+
+    // instantiate the prover and generate the proof
+    let mut prover = FriProver::new(options.clone());
+    prover.build_layers(&mut channel, evaluations.clone());
+    let positions = channel.draw_query_positions();
+    let proof = prover.build_proof(&positions);
+    let num_nonces = proof.layer_pow_nonces().len();
+    assert_eq!(proof.num_layers() + 1, num_nonces);
+
+    // make sure the proof can be verified
+    let commitments = channel.layer_commitments().to_vec();
+    let max_degree = trace_length - 1;
+    let domain_size = trace_length * lde_blowup;
+    let result = verify_proof(
+        proof.clone(),
+        commitments.clone(),
+        &evaluations,
+        max_degree,
+        domain_size,
+        &positions,
+        &options,
+    );
+    assert!(result.is_ok(), "{:}", result.err().unwrap());
+
+    // make sure the proof is rejected by a verifier which does not expect layer nonces
+    let result = verify_proof(
+        proof,
+        commitments,
+        &evaluations,
+        max_degree,
+        domain_size,
+        &positions,
+        &FriOptions::new(lde_blowup, 4, 256),
+    );
+    assert_eq!(
+        Err(VerifierError::NumLayerPowNoncesMismatch(0, num_nonces)),
+        result
+    );
+}
+
+#[test]
+fn fri_build_layers_with_callback() {
+    let trace_length = 1024;
+    let lde_blowup = 8;
+
+    let options = FriOptions::new(lde_blowup, 4, 256);
+    let evaluations = build_evaluations(trace_length, lde_blowup);
+
+    // layers should be reported in order, and the result should be the same as without callback
+    let mut channel = build_prover_channel(trace_length, &options);
+    let mut prover = FriProver::new(options.clone());
+    let mut layers = Vec::new();
+    prover.build_layers_with_callback(&mut channel, evaluations.clone(), |i| layers.push(i));
+    assert_eq!((0..prover.num_layers()).collect::<Vec<_>>(), layers);
+
+    let mut expected_channel = build_prover_channel(trace_length, &options);
+    let mut expected_prover = FriProver::new(options);
+    expected_prover.build_layers(&mut expected_channel, evaluations);
+    assert_eq!(
+        expected_channel.layer_commitments(),
+        channel.layer_commitments()
+    );
+}
+
+#[test]
+fn fri_build_proof_with_colliding_positions() {
+    let trace_length = 1024;
+    let lde_blowup = 8;
+    let domain_size = trace_length * lde_blowup;
+
+    let options = FriOptions::new(lde_blowup, 4, 256);
+    let mut channel = build_prover_channel(trace_length, &options);
+    let evaluations = build_evaluations(trace_length, lde_blowup);
+    let mut prover = FriProver::new(options.clone());
+    prover.build_layers(&mut channel, evaluations.clone());
+
+    // positions 7 and 7 + domain_size / 4 are folded into the same position in the first layer,
+    // and position 7 is also repeated; none of this should add any values or authentication
+    // paths to the proof
+    let positions = [7, 7 + domain_size / 4, 100, 7];
+    let proof = prover.build_proof(&positions);
+    prover.build_layers(
+        &mut build_prover_channel(trace_length, &options),
+        evaluations.clone(),
+    );
+    let deduplicated_proof = prover.build_proof(&[7, 100]);
+    assert_eq!(deduplicated_proof, proof);
+
+    // the verifier should reconstruct the mapping of the colliding positions to the queried
+    // values of every layer
+    let commitments = channel.layer_commitments().to_vec();
+    let result = verify_proof(
+        proof,
+        commitments,
+        &evaluations,
+        trace_length - 1,
+        domain_size,
+        &positions,
+        &options,
+    );
+    assert!(result.is_ok(), "{:}", result.err().unwrap());
+}
+
 // TEST UTILS
 // ================================================================================================
 
@@ -82,6 +608,18 @@ pub fn build_evaluations(trace_length: usize, lde_blowup: usize) -> Vec<BaseElem
     p
 }
 
+pub fn build_evaluations_with_offset(
+    poly_size: usize,
+    domain_size: usize,
+    domain_offset: BaseElement,
+) -> Vec<BaseElement> {
+    let p = (0..poly_size as u128)
+        .map(BaseElement::new)
+        .collect::<Vec<_>>();
+    let twiddles = fft::get_twiddles::<BaseElement>(poly_size);
+    fft::evaluate_poly_with_offset(&p, &twiddles, domain_offset, domain_size / poly_size)
+}
+
 pub fn verify_proof(
     proof: FriProof,
     commitments: Vec<<Blake3 as Hasher>::Digest>,
@@ -103,14 +641,14 @@ pub fn verify_proof(
         proof,
         commitments,
         domain_size,
-        options.folding_factor(),
+        options,
     )
     .unwrap();
     let mut coin = RandomCoin::<BaseElement, Blake3>::new(&[]);
-    let verifier = FriVerifier::new(&mut channel, &mut coin, options.clone(), max_degree).unwrap();
+    let verifier = FriVerifier::new(&mut channel, &mut coin, options.clone(), max_degree)?;
     let queried_evaluations = positions
         .iter()
         .map(|&p| evaluations[p])
         .collect::<Vec<_>>();
-    verifier.verify(&mut channel, &queried_evaluations, &positions)
+    verifier.verify(&mut channel, &queried_evaluations, positions)
 }
@@ -0,0 +1,293 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{FriOptions, LowDegreeTest};
+use core::f64::consts::FRAC_1_SQRT_2;
+
+// FRI SOUNDNESS
+// ================================================================================================
+/// Soundness error of a FRI instance, i.e., an upper bound on the probability that the verifier
+/// accepts a function which is far from any polynomial of the claimed degree.
+///
+/// The error is computed as a sum of two components:
+///
+/// * Commit phase error - the probability that a folding challenge drawn for any of the layers
+///   maps a function which is far from the code into a function which is close to it. For a
+///   layer with a domain of size `n` folded by a factor of `k`, this is bounded by
+///   `(k - 1) * n / |F|`, where `F` is the field in which FRI is executed. If per-layer
+///   proof-of-work is required, the bound for each layer is reduced by a factor of
+///   `2^layer_grinding_factor`. If out-of-domain sampling is performed within FRI layers, each
+///   layer additionally contributes `d / |F|`, where `d` is the degree bound of the folded
+///   layer, since this is the probability that the out-of-domain point is a point at which a
+///   false evaluation is consistent with the folded layer.
+/// * Query phase error - the probability that none of the queries detects a function which is
+///   far from the code. Each query passes with probability of at most `1 - δ`, where `δ` is the
+///   proximity parameter, and the overall bound is further reduced by a factor of
+///   `2^grinding_factor` due to proof-of-work applied to the query seed.
+///
+/// The proximity parameter depends on the selected regime:
+///
+/// * In the provable regime, `δ = (1 - ρ) / 2` is set to the unique decoding radius of the code
+///   with rate `ρ = 1 / blowup_factor`, and thus, each query passes with probability of at most
+///   `(1 + ρ) / 2`. In this regime, the commit phase bound follows from the proximity gaps
+///   theorem for Reed-Solomon codes in the unique decoding regime.
+/// * In the conjectured regime, `δ` approaches `1 - ρ` as conjectured for the list-decoding
+///   regime (see the ethSTARK documentation), and thus, each query passes with probability of
+///   at most `ρ` (i.e., contributes `log2(blowup_factor)` bits of security).
+///
+/// Out-of-domain sampling within FRI layers (see [FriOptions::with_deep_sampling()]) extends the
+/// provable bound up to the Johnson bound (see DEEP-FRI): in the provable regime, each query
+/// then passes with probability of at most `sqrt(ρ)`. Out-of-domain sampling does not affect the
+/// conjectured bound.
+///
+/// These bounds apply to FRI only: security of the STARK protocol is additionally limited by the
+/// collision resistance of the hash function and by errors introduced by other random challenges
+/// drawn by the verifier.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FriSoundness {
+    commit_phase_error: f64,
+    query_phase_error: f64,
+}
+
+impl FriSoundness {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns soundness error of a FRI instance executed with the specified parameters.
+    ///
+    /// * `options` define the blowup factor, the folding factor of every layer (including the
+    ///   folding schedule), and the per-layer grinding factor.
+    /// * `field_bits` is the number of bits in the modulus of the field in which FRI is executed;
+    ///   for an extension field, this is the number of bits in the base field multiplied by the
+    ///   extension degree.
+    /// * `domain_size` is the size of the domain over which the tested function is evaluated.
+    /// * `num_queries` and `grinding_factor` are the number of queries made against the first
+    ///   layer and the number of leading zeros required from the query seed.
+    /// * `conjectured` selects between the conjectured (list-decoding) regime and the provable
+    ///   (unique decoding) regime.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `domain_size` is not a power of two.
+    /// * The low-degree test specified by the `options` is STIR.
+    pub fn new(
+        options: &FriOptions,
+        field_bits: u32,
+        domain_size: usize,
+        num_queries: usize,
+        grinding_factor: u32,
+        conjectured: bool,
+    ) -> Self {
+        assert!(
+            domain_size.is_power_of_two(),
+            "domain size must be a power of two, but was {}",
+            domain_size
+        );
+        assert_ne!(
+            LowDegreeTest::Stir,
+            options.low_degree_test(),
+            "soundness of STIR cannot be estimated as soundness of FRI"
+        );
+
+        // each layer contributes (k - 1) * n / |F| to the commit phase error, where n is the
+        // size of the layer domain and k is the folding factor of the layer; with out-of-domain
+        // sampling, each layer also contributes d / |F|, where d = n / (k * blowup_factor) is
+        // the degree bound of the folded layer
+        let inv_field_size = exp2_neg(field_bits);
+        let layer_grinding_gain = exp2_neg(options.layer_grinding_factor());
+        let mut commit_phase_error = 0.0;
+        for layer_idx in 0..options.num_fri_layers(domain_size) {
+            let folding_factor = options.layer_folding_factor(layer_idx);
+            let layer_domain_size = options.layer_domain_size(domain_size, layer_idx);
+            commit_phase_error += ((folding_factor - 1) * layer_domain_size) as f64
+                * inv_field_size
+                * layer_grinding_gain;
+            if options.deep_sampling() {
+                let folded_degree = layer_domain_size / folding_factor / options.blowup_factor();
+                commit_phase_error += folded_degree as f64 * inv_field_size;
+            }
+        }
+
+        // each query passes with probability of at most 1 - δ
+        let rate = 1.0 / options.blowup_factor() as f64;
+        let query_pass_probability = if conjectured {
+            rate
+        } else if options.deep_sampling() {
+            sqrt_rate(options.blowup_factor())
+        } else {
+            (1.0 + rate) / 2.0
+        };
+        let mut query_phase_error = exp2_neg(grinding_factor);
+        for _ in 0..num_queries {
+            query_phase_error *= query_pass_probability;
+        }
+
+        FriSoundness {
+            commit_phase_error,
+            query_phase_error,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the part of the soundness error introduced during the commit phase of FRI.
+    pub fn commit_phase_error(&self) -> f64 {
+        self.commit_phase_error
+    }
+
+    /// Returns the part of the soundness error introduced during the query phase of FRI.
+    pub fn query_phase_error(&self) -> f64 {
+        self.query_phase_error
+    }
+
+    /// Returns the overall soundness error of FRI; this is the sum of the commit phase error and
+    /// the query phase error.
+    pub fn error(&self) -> f64 {
+        self.commit_phase_error + self.query_phase_error
+    }
+
+    /// Returns the security level (in bits) implied by the overall soundness error of FRI.
+    ///
+    /// This is `-log2(error)` rounded down, but never more than 1023 bits.
+    pub fn security_level(&self) -> u32 {
+        neg_floor_log2(self.error())
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns 2^(-bits).
+fn exp2_neg(bits: u32) -> f64 {
+    let mut result = 1.0;
+    for _ in 0..bits {
+        result /= 2.0;
+    }
+    result
+}
+
+/// Returns sqrt(1 / blowup_factor) for a blowup factor which is a power of two.
+fn sqrt_rate(blowup_factor: usize) -> f64 {
+    let log_blowup = blowup_factor.trailing_zeros();
+    let result = exp2_neg(log_blowup / 2);
+    if log_blowup % 2 == 1 {
+        result * FRAC_1_SQRT_2
+    } else {
+        result
+    }
+}
+
+/// Returns floor(-log2(value)) for a value in the range (0, 1]; values of 0 and subnormal values
+/// are mapped to 1023.
+///
+/// This reads the exponent directly from the binary representation of the value so that it
+/// can be used in no_std environments.
+fn neg_floor_log2(value: f64) -> u32 {
+    debug_assert!(value <= 1.0, "value must be smaller than or equal to 1");
+    let bits = value.to_bits();
+    let exponent = ((bits >> 52) & 0x7ff) as i64 - 1023;
+    if exponent <= -1023 {
+        return 1023;
+    }
+    // for value = 1.m * 2^e, -log2(value) is in the range (-e - 1, -e], and is equal to -e
+    // only when the mantissa is zero
+    let has_fraction = bits & ((1 << 52) - 1) != 0;
+    (-exponent - has_fraction as i64) as u32
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{neg_floor_log2, FriSoundness};
+    use crate::FriOptions;
+
+    #[test]
+    fn fri_soundness_conjectured() {
+        // domain of 2^20 elements folded by 4 into a remainder of at most 256 elements
+        let options = FriOptions::new(8, 4, 256);
+        let soundness = FriSoundness::new(&options, 128, 1 << 20, 32, 0, true);
+
+        // each query contributes 3 bits
+        assert_eq!(96, neg_floor_log2(soundness.query_phase_error()));
+
+        // the first layer contributes 3 * 2^20 / 2^128, and every next layer a quarter of that
+        assert_eq!(106, neg_floor_log2(soundness.commit_phase_error()));
+        assert_eq!(95, soundness.security_level());
+
+        // grinding is added to the query phase security
+        let soundness = FriSoundness::new(&options, 128, 1 << 20, 32, 16, true);
+        assert_eq!(112, neg_floor_log2(soundness.query_phase_error()));
+        assert_eq!(105, soundness.security_level());
+
+        // per-layer grinding reduces the commit phase error
+        let options = options.with_layer_grinding_factor(8);
+        let soundness = FriSoundness::new(&options, 128, 1 << 20, 32, 16, true);
+        assert_eq!(114, neg_floor_log2(soundness.commit_phase_error()));
+        assert_eq!(111, soundness.security_level());
+    }
+
+    #[test]
+    fn fri_soundness_provable() {
+        let options = FriOptions::new(8, 4, 256);
+        let conjectured = FriSoundness::new(&options, 128, 1 << 20, 32, 0, true);
+        let provable = FriSoundness::new(&options, 128, 1 << 20, 32, 0, false);
+        assert_eq!(
+            conjectured.commit_phase_error(),
+            provable.commit_phase_error()
+        );
+
+        // each query contributes log2(16 / 9) bits, or about 0.83 bits
+        assert_eq!(26, provable.security_level());
+        let provable = FriSoundness::new(&options, 128, 1 << 20, 116, 0, false);
+        assert_eq!(96, provable.security_level());
+    }
+
+    #[test]
+    fn fri_soundness_deep_sampling() {
+        let options = FriOptions::new(16, 4, 256);
+        let deep_options = options.clone().with_deep_sampling();
+
+        // each query contributes 2 bits instead of log2(32 / 17), or about 0.91 bits
+        let provable = FriSoundness::new(&options, 128, 1 << 20, 48, 0, false);
+        let deep = FriSoundness::new(&deep_options, 128, 1 << 20, 48, 0, false);
+        assert_eq!(43, provable.security_level());
+        assert_eq!(96, neg_floor_log2(deep.query_phase_error()));
+        assert_eq!(95, deep.security_level());
+
+        // out-of-domain sampling slightly increases the commit phase error
+        assert!(deep.commit_phase_error() > provable.commit_phase_error());
+
+        // conjectured security of queries is not affected by out-of-domain sampling
+        let conjectured = FriSoundness::new(&options, 128, 1 << 20, 24, 0, true);
+        let deep = FriSoundness::new(&deep_options, 128, 1 << 20, 24, 0, true);
+        assert_eq!(conjectured.query_phase_error(), deep.query_phase_error());
+    }
+
+    #[test]
+    fn fri_soundness_folding_schedule() {
+        // folding the first layer by 16 instead of 4 increases the commit phase error of that
+        // layer by a factor of 5, but removes one layer
+        let options = FriOptions::new(8, 4, 256);
+        let scheduled = options.clone().with_folding_schedule(&[16]);
+        let soundness = FriSoundness::new(&options, 64, 1 << 20, 32, 0, true);
+        let scheduled_soundness = FriSoundness::new(&scheduled, 64, 1 << 20, 32, 0, true);
+        assert!(scheduled_soundness.commit_phase_error() > soundness.commit_phase_error());
+        assert_eq!(
+            soundness.query_phase_error(),
+            scheduled_soundness.query_phase_error()
+        );
+    }
+
+    #[test]
+    fn fri_soundness_direct() {
+        // without folding, there is no commit phase error
+        let options = FriOptions::new(8, 4, 256).with_direct();
+        let soundness = FriSoundness::new(&options, 64, 1 << 20, 32, 0, true);
+        assert_eq!(0.0, soundness.commit_phase_error());
+        assert_eq!(96, soundness.security_level());
+    }
+}
@@ -0,0 +1,127 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Contains an implementation of the STIR low-degree test.
+//!
+//! [STIR](https://eprint.iacr.org/2024/390) (Shift To Improve Rate) is an alternative to FRI in
+//! which the degree of the tested polynomial is reduced by the folding factor `k` in every round,
+//! while the evaluation domain is reduced only by a factor of 2. Thus, the rate of the code the
+//! tested function belongs to improves with every round, and each subsequent round needs fewer
+//! queries than the previous one to achieve the same level of security.
+//!
+//! Given a function *f_i* committed to over domain *L_i* and claimed to be of degree smaller than
+//! *d_i*, a STIR round works as follows:
+//!
+//! 1. The verifier sends a folding randomness *r_fold*, and the prover commits to evaluations of
+//!    the folded polynomial *g_i* (of degree smaller than *d_i / k*) over domain *L_{i+1}* of
+//!    size |*L_i*| / 2.
+//! 2. The verifier sends an out-of-domain point *r_out*, and the prover responds with
+//!    *β = g_i(r_out)*.
+//! 3. The verifier sends a combination randomness *r_comb* and a set of query indexes into the
+//!    commitment to *f_i*. For every index, the verifier opens the *k* values of *f_i* which
+//!    determine the value of *g_i* at a single point of the domain *L_i^k*, and computes this
+//!    value by folding the opened values.
+//! 4. The function tested in the next round is a degree-corrected quotient of *g_i* by the points
+//!    at which the verifier knows the values of *g_i*:
+//!
+//!    *f_{i+1}(x) = (g_i(x) - A(x)) / V(x) * (1 + r_comb * x + ... + (r_comb * x)^{|G|})*
+//!
+//!    where *G* is the set of such points, *A* is the polynomial which interpolates the known
+//!    values of *g_i* over *G*, and *V* is the polynomial which vanishes on *G*. The verifier can
+//!    evaluate *f_{i+1}* at any point of *L_{i+1}* by querying the commitment to *g_i* at that
+//!    point.
+//!
+//! Once the degree of the tested polynomial is small enough, the prover sends the polynomial to
+//! the verifier in full (as coefficients), and the verifier checks that the polynomial agrees
+//! with the last tested function at a set of random points.
+//!
+//! In this implementation, all interaction of a STIR round (including drawing of the query
+//! indexes) happens during the commit phase of the protocol. During the query phase, the first
+//! layer is additionally queried at the positions at which the evaluations of the tested function
+//! are supplied to the verifier, which ties these evaluations to the commitment to the first
+//! layer.
+
+use math::{FieldElement, StarkField};
+use utils::collections::Vec;
+
+mod prover;
+pub use prover::StirProver;
+
+mod verifier;
+pub use verifier::StirVerifier;
+
+#[cfg(test)]
+mod tests;
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Folds a polynomial specified by its `coefficients` by the `folding_factor` using the specified
+/// randomness.
+///
+/// Given f(x) = f_0(x^k) + x * f_1(x^k) + ... + x^{k-1} * f_{k-1}(x^k), the folded polynomial is
+/// g(y) = f_0(y) + r * f_1(y) + ... + r^{k-1} * f_{k-1}(y).
+fn fold_coefficients<E: FieldElement>(coefficients: &[E], folding_factor: usize, r: E) -> Vec<E> {
+    coefficients
+        .chunks(folding_factor)
+        .map(|chunk| chunk.iter().rev().fold(E::ZERO, |acc, &c| acc * r + c))
+        .collect()
+}
+
+/// Returns the point of the domain L_i^k which corresponds to the row with the specified index in
+/// the commitment to the layer with the specified domain.
+fn get_shift_point<B: StarkField, E: FieldElement<BaseField = B>>(
+    index: usize,
+    domain_generator: B,
+    domain_offset: B,
+    folding_factor: usize,
+) -> E {
+    let x = domain_generator.exp((index as u64).into()) * domain_offset;
+    E::from(x.exp((folding_factor as u64).into()))
+}
+
+/// Returns the points of the domain over which a layer is committed to which are grouped into
+/// the row with the specified index in the commitment to the layer.
+fn get_row_points<B: StarkField>(
+    index: usize,
+    domain_generator: B,
+    domain_size: usize,
+    domain_offset: B,
+    folding_factor: usize,
+) -> Vec<B> {
+    let num_rows = domain_size / folding_factor;
+    (0..folding_factor)
+        .map(|m| domain_generator.exp(((index + m * num_rows) as u64).into()) * domain_offset)
+        .collect()
+}
+
+/// Returns the value of the degree correction factor 1 + r * x + ... + (r * x)^num_points.
+fn get_degree_correction<E: FieldElement>(r_comb: E, x: E, num_points: usize) -> E {
+    let rx = r_comb * x;
+    let mut term = E::ONE;
+    let mut result = E::ONE;
+    for _ in 0..num_points {
+        term *= rx;
+        result += term;
+    }
+    result
+}
+
+/// Divides the polynomial specified by its coefficients by (x - a) in place; the remainder of
+/// the division is discarded, and the leading coefficient of the result is set to zero.
+fn divide_by_linear<E: FieldElement>(coefficients: &mut [E], a: E) {
+    let mut carry = E::ZERO;
+    for c in coefficients.iter_mut().rev() {
+        *c += a * carry;
+        core::mem::swap(c, &mut carry);
+    }
+}
+
+/// Sorts the specified indexes and removes duplicates from them.
+fn sort_indexes(mut indexes: Vec<usize>) -> Vec<usize> {
+    indexes.sort_unstable();
+    indexes.dedup();
+    indexes
+}
@@ -0,0 +1,348 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{divide_by_linear, fold_coefficients, get_shift_point, sort_indexes};
+use crate::{
+    options::{build_stir_schedule, StirLayer},
+    proof::FriProof,
+    prover::{query_layer, FriLayer},
+    FriOptions, LowDegreeProver, ProverChannel,
+};
+use core::marker::PhantomData;
+use crypto::ElementHasher;
+use math::{fft, log2, polynom, FieldElement, StarkField};
+use utils::collections::Vec;
+
+// STIR PROVER
+// ================================================================================================
+
+/// Implements the prover component of the STIR low-degree test.
+///
+/// The prover is parametrized in the same way as a [FriProver](crate::FriProver), and proof
+/// generation is also performed in two phases:
+///
+/// 1. During the commit phase, which is executed via
+///    [build_layers()](StirProver::build_layers()) function, the prover executes all STIR
+///    rounds: for every round, the prover commits to evaluations of the folded polynomial over
+///    the next (twice smaller) domain, sends an out-of-domain evaluation of the folded polynomial,
+///    and draws query indexes for the round from the channel. Once the degree of the tested
+///    polynomial is small enough, the prover commits to the coefficients of the polynomial in
+///    the same way as to a FRI remainder sent as coefficients.
+/// 2. During the query phase, which is executed via [build_proof()](StirProver::build_proof())
+///    function, the prover opens every committed layer at the indexes drawn for the
+///    corresponding round; the first layer is also opened at the specified positions.
+///
+/// All values committed to by the prover are kept in its internal state until the proof is
+/// built. Calling [build_layers()](StirProver::build_layers()) when the internal state is
+/// dirty, or calling [build_proof()](StirProver::build_proof()) on a clean state will result
+/// in a panic.
+pub struct StirProver<B, E, C, H>
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+    C: ProverChannel<E, Hasher = H>,
+    H: ElementHasher<BaseField = B>,
+{
+    options: FriOptions,
+    layers: Vec<FriLayer<B, E, H>>,
+    query_indexes: Vec<Vec<usize>>,
+    ood_evaluations: Vec<E>,
+    final_poly: Vec<E>,
+    pow_nonces: Vec<u64>,
+    _channel: PhantomData<C>,
+}
+
+impl<B, E, C, H> StirProver<B, E, C, H>
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+    C: ProverChannel<E, Hasher = H>,
+    H: ElementHasher<BaseField = B>,
+{
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new STIR prover instantiated with the provided `options`.
+    ///
+    /// # Panics
+    /// Panics if the low-degree test specified by the `options` is not STIR.
+    pub fn new(options: FriOptions) -> Self {
+        assert_eq!(
+            crate::LowDegreeTest::Stir,
+            options.low_degree_test(),
+            "STIR prover cannot be instantiated with options for another low-degree test"
+        );
+        StirProver {
+            options,
+            layers: Vec::new(),
+            query_indexes: Vec::new(),
+            ood_evaluations: Vec::new(),
+            final_poly: Vec::new(),
+            pow_nonces: Vec::new(),
+            _channel: PhantomData,
+        }
+    }
+
+    // ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns offset of the domain over which STIR protocol is executed by this prover.
+    pub fn domain_offset(&self) -> B {
+        self.options.domain_offset()
+    }
+
+    /// Returns number of layers (including the final polynomial) computed during the last
+    /// execution of the [build_layers()](StirProver::build_layers()) method.
+    pub fn num_layers(&self) -> usize {
+        if self.final_poly.is_empty() {
+            0
+        } else {
+            self.layers.len() + 1
+        }
+    }
+
+    /// Clears all internally stored layers.
+    pub fn reset(&mut self) {
+        self.layers.clear();
+        self.query_indexes.clear();
+        self.ood_evaluations.clear();
+        self.final_poly.clear();
+        self.pow_nonces.clear();
+    }
+
+    // COMMIT PHASE
+    // --------------------------------------------------------------------------------------------
+
+    /// Executes the commit phase of the STIR protocol against the specified `evaluations` of
+    /// a polynomial over the domain of the first layer.
+    ///
+    /// # Panics
+    /// Panics if the prover state is dirty (the vector of layers is not empty).
+    pub fn build_layers(&mut self, channel: &mut C, evaluations: Vec<E>) {
+        self.build_layers_with_callback(channel, evaluations, |_| {});
+    }
+
+    /// Executes the commit phase of the STIR protocol, and invokes `on_layer` with the index of
+    /// every layer right after the layer has been committed to; the last layer reported via
+    /// `on_layer` is the final polynomial.
+    ///
+    /// # Panics
+    /// Panics if the prover state is dirty (the vector of layers is not empty).
+    pub fn build_layers_with_callback<F: FnMut(usize)>(
+        &mut self,
+        channel: &mut C,
+        evaluations: Vec<E>,
+        mut on_layer: F,
+    ) {
+        assert!(
+            self.final_poly.is_empty(),
+            "a prior proof generation request has not been completed yet"
+        );
+        let domain_offset = self.domain_offset();
+        let schedule = build_stir_schedule(&self.options, evaluations.len());
+
+        // interpolate the evaluations into a polynomial, and commit to the evaluations; for an
+        // honest prover, all coefficients beyond the degree bound of the first layer are zeros
+        let mut poly = evaluations.clone();
+        let inv_twiddles = fft::get_inv_twiddles::<B>(poly.len());
+        fft::interpolate_poly_with_offset(&mut poly, &inv_twiddles, domain_offset);
+        poly.truncate(schedule[0].degree_bound);
+        self.commit_evaluations(channel, &evaluations, schedule[0].folding_factor);
+        on_layer(0);
+
+        for (i, round) in schedule.iter().enumerate().take(schedule.len() - 1) {
+            let next = &schedule[i + 1];
+
+            // fold the polynomial, and commit to its evaluations over the next domain
+            let r_fold = channel.draw_fri_alpha();
+            let folded_poly = fold_coefficients(&poly, round.folding_factor, r_fold);
+            let twiddles = fft::get_twiddles::<B>(folded_poly.len());
+            let folded_evaluations = fft::evaluate_poly_with_offset(
+                &folded_poly,
+                &twiddles,
+                domain_offset,
+                next.domain_size / folded_poly.len(),
+            );
+            self.commit_evaluations(channel, &folded_evaluations, next.folding_factor);
+            on_layer(i + 1);
+
+            // evaluate the folded polynomial at an out-of-domain point
+            let r_out: E = channel.draw_fri_alpha();
+            let ood_evaluation = polynom::eval(&folded_poly, r_out);
+            channel.commit_fri_ood_evaluations(H::hash_elements(&[ood_evaluation]));
+            self.ood_evaluations.push(ood_evaluation);
+
+            // draw the combination randomness and query indexes for this round
+            let r_comb = channel.draw_fri_alpha();
+            let indexes = draw_query_indexes(channel, round);
+
+            // the folded polynomial is known to the verifier at the out-of-domain point and at
+            // the points of the folded domain which correspond to the queried indexes
+            let domain_generator = B::get_root_of_unity(log2(round.domain_size));
+            let mut points = vec![r_out];
+            let mut values = vec![ood_evaluation];
+            for &index in indexes.iter() {
+                let point =
+                    get_shift_point(index, domain_generator, domain_offset, round.folding_factor);
+                if point != r_out {
+                    points.push(point);
+                    values.push(polynom::eval(&folded_poly, point));
+                }
+            }
+            self.query_indexes.push(indexes);
+
+            poly = build_next_poly(&folded_poly, &points, &values, r_comb, next.degree_bound);
+        }
+
+        // commit to the coefficients of the final polynomial, and draw query indexes for the
+        // final check
+        self.commit_layer(channel, H::hash_elements(&poly));
+        let final_layer = schedule.last().expect("STIR schedule is empty");
+        self.query_indexes
+            .push(draw_query_indexes(channel, final_layer));
+        self.final_poly = poly;
+        on_layer(schedule.len());
+    }
+
+    /// Commits to the specified evaluations of a layer polynomial, grouping N evaluations into
+    /// every leaf of the commitment Merkle tree.
+    fn commit_evaluations(&mut self, channel: &mut C, evaluations: &[E], folding_factor: usize) {
+        let layer = match folding_factor {
+            2 => FriLayer::new::<2>(evaluations),
+            4 => FriLayer::new::<4>(evaluations),
+            8 => FriLayer::new::<8>(evaluations),
+            16 => FriLayer::new::<16>(evaluations),
+            _ => unimplemented!("folding factor {} is not supported", folding_factor),
+        };
+        self.commit_layer(channel, layer.root());
+        self.layers.push(layer);
+    }
+
+    /// Writes the specified layer commitment into the channel and performs proof-of-work for the
+    /// layer if required.
+    fn commit_layer(&mut self, channel: &mut C, commitment: H::Digest) {
+        channel.commit_fri_layer(commitment);
+        let grinding_factor = self.options.layer_grinding_factor();
+        if grinding_factor > 0 {
+            self.pow_nonces
+                .push(channel.grind_fri_layer(grinding_factor));
+        }
+    }
+
+    // QUERY PHASE
+    // --------------------------------------------------------------------------------------------
+
+    /// Executes the query phase of the STIR protocol.
+    ///
+    /// Every committed layer is opened at the indexes drawn for the corresponding round during
+    /// the commit phase; the first layer is also opened at the rows which contain the specified
+    /// `positions`. The coefficients of the final polynomial and all out-of-domain evaluations
+    /// are included in the proof in their entirety.
+    ///
+    /// # Panics
+    /// Panics is the prover state is clean (no layers have been built yet).
+    pub fn build_proof(&mut self, positions: &[usize]) -> FriProof {
+        assert!(
+            !self.final_poly.is_empty(),
+            "STIR layers have not been built yet"
+        );
+
+        let mut layers = Vec::with_capacity(self.layers.len());
+        for (i, layer) in self.layers.iter().enumerate() {
+            let folding_factor = self.options.layer_folding_factor(i);
+            let mut indexes = self.query_indexes[i].clone();
+            if i == 0 {
+                let num_rows = layer.num_evaluations() / folding_factor;
+                indexes.extend(positions.iter().map(|&p| p % num_rows));
+                indexes = sort_indexes(indexes);
+            }
+
+            // sort of a static dispatch for folding_factor parameter
+            let proof_layer = match folding_factor {
+                2 => query_layer::<B, E, H, 2>(layer, &indexes),
+                4 => query_layer::<B, E, H, 4>(layer, &indexes),
+                8 => query_layer::<B, E, H, 8>(layer, &indexes),
+                16 => query_layer::<B, E, H, 16>(layer, &indexes),
+                _ => unimplemented!("folding factor {} is not supported", folding_factor),
+            };
+            layers.push(proof_layer);
+        }
+
+        let final_poly = core::mem::take(&mut self.final_poly);
+        let pow_nonces = core::mem::take(&mut self.pow_nonces);
+        let ood_evaluations = core::mem::take(&mut self.ood_evaluations);
+        self.reset();
+
+        FriProof::new(layers, final_poly, pow_nonces, 1).with_ood_evaluations(&ood_evaluations)
+    }
+}
+
+impl<B, E, C, H> LowDegreeProver<E, C> for StirProver<B, E, C, H>
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+    C: ProverChannel<E, Hasher = H>,
+    H: ElementHasher<BaseField = B>,
+{
+    fn commit_layers(
+        &mut self,
+        channel: &mut C,
+        evaluations: Vec<E>,
+        on_layer: &mut dyn FnMut(usize),
+    ) {
+        self.build_layers_with_callback(channel, evaluations, on_layer);
+    }
+
+    fn build_proof(&mut self, positions: &[usize]) -> FriProof {
+        StirProver::build_proof(self, positions)
+    }
+
+    fn num_layers(&self) -> usize {
+        StirProver::num_layers(self)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Draws query indexes for the specified layer from the channel; the returned indexes are
+/// sorted and do not contain duplicates.
+fn draw_query_indexes<E: FieldElement, C: ProverChannel<E>>(
+    channel: &mut C,
+    layer: &StirLayer,
+) -> Vec<usize> {
+    let indexes = channel.draw_fri_query_indexes(layer.num_query_draws(), layer.num_leaves());
+    sort_indexes(indexes)
+}
+
+/// Returns coefficients of the degree-corrected quotient of the `folded_poly` by the specified
+/// points.
+///
+/// The quotient is computed as (g(x) - A(x)) / V(x), where A(x) interpolates `values` over
+/// `points` and V(x) vanishes on `points`; the quotient is then multiplied by
+/// 1 + r * x + ... + (r * x)^|points| to raise its degree to `degree_bound` - 1.
+fn build_next_poly<E: FieldElement>(
+    folded_poly: &[E],
+    points: &[E],
+    values: &[E],
+    r_comb: E,
+    degree_bound: usize,
+) -> Vec<E> {
+    let answers_poly = polynom::interpolate(points, values, false);
+    let mut quotient = polynom::sub(folded_poly, &answers_poly);
+    for &point in points {
+        divide_by_linear(&mut quotient, point);
+    }
+
+    // the coefficients of the degree correction factor are powers of r
+    let mut correction = Vec::with_capacity(points.len() + 1);
+    let mut term = E::ONE;
+    for _ in 0..=points.len() {
+        correction.push(term);
+        term *= r_comb;
+    }
+    let mut result = polynom::mul(&quotient, &correction);
+    result.resize(degree_bound, E::ZERO);
+    result
+}
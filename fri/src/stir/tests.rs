@@ -0,0 +1,165 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{StirProver, StirVerifier};
+use crate::{
+    prover::tests::{build_evaluations, build_prover_channel},
+    DefaultVerifierChannel, FriOptions, FriProof, VerifierError,
+};
+use crypto::{hashers::Blake3_256, Hasher, RandomCoin};
+use math::{fields::f128::BaseElement, FieldElement};
+use utils::{collections::Vec, Deserializable, Serializable, SliceReader};
+
+type Blake3 = Blake3_256<BaseElement>;
+
+// PROVE/VERIFY TESTS
+// ================================================================================================
+
+#[test]
+fn stir_prove_verify() {
+    let trace_length = 4096;
+    let lde_blowup = 8;
+
+    for folding_factor in [2, 4, 8, 16] {
+        let options = FriOptions::new(lde_blowup, folding_factor, 64).with_stir(16);
+        let domain_size = trace_length * lde_blowup;
+        assert!(options.num_ood_evaluations(domain_size) > 0);
+
+        let mut channel = build_prover_channel(trace_length, &options);
+        let evaluations = build_evaluations(trace_length, lde_blowup);
+
+        // instantiate the prover and generate the proof
+        let mut prover = StirProver::new(options.clone());
+        prover.build_layers(&mut channel, evaluations.clone());
+        let positions = channel.draw_query_positions();
+        let proof = prover.build_proof(&positions);
+        assert_eq!(
+            options.num_ood_evaluations(domain_size),
+            proof.num_ood_evaluations::<BaseElement>()
+        );
+
+        // make sure the proof can be verified
+        let commitments = channel.layer_commitments().to_vec();
+        let max_degree = trace_length - 1;
+        let result = verify_proof(
+            proof.clone(),
+            commitments.clone(),
+            &evaluations,
+            max_degree,
+            domain_size,
+            &positions,
+            &options,
+        );
+        assert!(result.is_ok(), "{:}", result.err().unwrap());
+
+        // make sure proof fails for invalid degree
+        let result = verify_proof(
+            proof,
+            commitments,
+            &evaluations,
+            max_degree - 256,
+            domain_size,
+            &positions,
+            &options,
+        );
+        assert!(result.is_err());
+    }
+}
+
+#[test]
+fn stir_prove_verify_high_degree() {
+    let trace_length = 4096;
+    let lde_blowup = 8;
+    let options = FriOptions::new(lde_blowup, 4, 64).with_stir(16);
+
+    // evaluations of a polynomial of degree 2 * trace_length - 1 are tested against degree
+    // trace_length - 1; the domain is the same as for a polynomial of the claimed degree
+    let mut channel = build_prover_channel(trace_length, &options);
+    let evaluations = build_evaluations(trace_length * 2, lde_blowup / 2);
+
+    let mut prover = StirProver::new(options.clone());
+    prover.build_layers(&mut channel, evaluations.clone());
+    let positions = channel.draw_query_positions();
+    let proof = prover.build_proof(&positions);
+
+    let commitments = channel.layer_commitments().to_vec();
+    let result = verify_proof(
+        proof,
+        commitments,
+        &evaluations,
+        trace_length - 1,
+        trace_length * lde_blowup,
+        &positions,
+        &options,
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn stir_verify_invalid_evaluations() {
+    let trace_length = 1024;
+    let lde_blowup = 8;
+    let options = FriOptions::new(lde_blowup, 4, 32).with_stir(16);
+
+    let mut channel = build_prover_channel(trace_length, &options);
+    let evaluations = build_evaluations(trace_length, lde_blowup);
+
+    let mut prover = StirProver::new(options.clone());
+    prover.build_layers(&mut channel, evaluations.clone());
+    let positions = channel.draw_query_positions();
+    let proof = prover.build_proof(&positions);
+
+    // evaluations which do not match the committed function are rejected
+    let mut invalid_evaluations = evaluations;
+    invalid_evaluations[positions[0]] += BaseElement::ONE;
+
+    let commitments = channel.layer_commitments().to_vec();
+    let result = verify_proof(
+        proof,
+        commitments,
+        &invalid_evaluations,
+        trace_length - 1,
+        trace_length * lde_blowup,
+        &positions,
+        &options,
+    );
+    assert_eq!(Err(VerifierError::InvalidLayerFolding(0)), result);
+}
+
+// TEST UTILS
+// ================================================================================================
+
+fn verify_proof(
+    proof: FriProof,
+    commitments: Vec<<Blake3 as Hasher>::Digest>,
+    evaluations: &[BaseElement],
+    max_degree: usize,
+    domain_size: usize,
+    positions: &[usize],
+    options: &FriOptions,
+) -> Result<(), VerifierError> {
+    // test proof serialization / deserialization
+    let mut proof_bytes = Vec::new();
+    proof.write_into(&mut proof_bytes);
+
+    let mut reader = SliceReader::new(&proof_bytes);
+    let proof = FriProof::read_from(&mut reader).unwrap();
+
+    // verify the proof
+    let mut channel = DefaultVerifierChannel::<BaseElement, Blake3>::new(
+        proof,
+        commitments,
+        domain_size,
+        options,
+    )
+    .unwrap();
+    let mut coin = RandomCoin::<BaseElement, Blake3>::new(&[]);
+    let verifier = StirVerifier::new(&mut channel, &mut coin, options.clone(), max_degree)?;
+    let queried_evaluations = positions
+        .iter()
+        .map(|&p| evaluations[p])
+        .collect::<Vec<_>>();
+    verifier.verify(&mut channel, &queried_evaluations, positions)
+}
@@ -0,0 +1,500 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{get_degree_correction, get_row_points, get_shift_point, sort_indexes};
+use crate::{
+    options::{build_stir_schedule, StirLayer},
+    FriOptions, LowDegreeTest, LowDegreeVerifier, VerifierChannel, VerifierError,
+};
+use core::marker::PhantomData;
+use crypto::{ElementHasher, Hasher, PublicCoin};
+use math::{log2, polynom, FieldElement, StarkField};
+use utils::collections::Vec;
+
+// STIR VERIFIER
+// ================================================================================================
+
+/// Implements the verifier component of the STIR low-degree test.
+///
+/// The verifier is parametrized in the same way as a [FriVerifier](crate::FriVerifier), and
+/// proof verification is also performed in two phases:
+///
+/// 1. The commit phase is executed when the verifier is instantiated via
+///    [new()](StirVerifier::new()) function. During this phase, the verifier reads layer
+///    commitments and out-of-domain evaluations from the channel, and replays all STIR rounds to
+///    derive the randomness and the query indexes of every round.
+/// 2. The query phase is executed via [verify()](StirVerifier::verify()) function. During this
+///    phase, the verifier reads the values of every layer at the query indexes of the
+///    corresponding round, and checks that:
+///    * The values are valid against the layer commitments.
+///    * The evaluations supplied to the verifier match the values of the first layer.
+///    * The values of the function tested in every round are consistent with the values of the
+///      function tested in the previous round, the out-of-domain evaluations, and the final
+///      polynomial.
+///    * The degree of the final polynomial is smaller than the degree resulting from reducing
+///      `max_poly_degree` by the folding factor of every round.
+pub struct StirVerifier<B, E, C, H>
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+    C: VerifierChannel<E, Hasher = H>,
+    H: ElementHasher<BaseField = B>,
+{
+    max_poly_degree: usize,
+    final_max_degree: usize,
+    domain_size: usize,
+    schedule: Vec<StirLayer>,
+    layer_commitments: Vec<H::Digest>,
+    folding_randomness: Vec<E>,
+    ood_points: Vec<E>,
+    ood_evaluations: Vec<E>,
+    combination_randomness: Vec<E>,
+    query_indexes: Vec<Vec<usize>>,
+    options: FriOptions,
+    _channel: PhantomData<C>,
+}
+
+impl<B, E, C, H> StirVerifier<B, E, C, H>
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+    C: VerifierChannel<E, Hasher = H>,
+    H: ElementHasher<BaseField = B>,
+{
+    /// Returns a new instance of STIR verifier created from the specified parameters.
+    ///
+    /// The `max_poly_degree` parameter specifies the highest polynomial degree accepted by the
+    /// returned verifier, and in combination with `blowup_factor` from the `options` parameter,
+    /// defines the domain over which the tested polynomial is evaluated.
+    ///
+    /// Creating a STIR verifier executes the commit phase of the STIR protocol from the
+    /// verifier's perspective: the verifier reads layer commitments and out-of-domain evaluations
+    /// from the `channel`, absorbs them into the `public_coin`, and draws the randomness and the
+    /// query indexes for every round from the coin.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The number of layer commitments, proof-of-work nonces, or out-of-domain evaluations
+    ///   read from the channel is not consistent with the `options`.
+    /// * `max_poly_degree` cannot be reduced by the folding factor of every round.
+    /// * An error was encountered while drawing a random value from the coin.
+    /// * Per-layer proof-of-work is required by the `options`, and a proof-of-work nonce read
+    ///   from the channel for any of the layers does not satisfy the layer grinding factor.
+    ///
+    /// # Panics
+    /// Panics if the low-degree test specified by the `options` is not STIR.
+    pub fn new<P>(
+        channel: &mut C,
+        public_coin: &mut P,
+        options: FriOptions,
+        max_poly_degree: usize,
+    ) -> Result<Self, VerifierError>
+    where
+        P: PublicCoin<BaseField = B, Hasher = H>,
+    {
+        assert_eq!(
+            LowDegreeTest::Stir,
+            options.low_degree_test(),
+            "STIR verifier cannot be instantiated with options for another low-degree test"
+        );
+
+        // infer evaluation domain info and parameters of all STIR layers
+        let domain_size = max_poly_degree.next_power_of_two() * options.blowup_factor();
+        let schedule = build_stir_schedule(&options, domain_size);
+        let num_rounds = schedule.len() - 1;
+
+        // read layer commitments, proof-of-work nonces, and out-of-domain evaluations from the
+        // channel; every layer and the final polynomial are committed to separately
+        let layer_commitments = channel.read_fri_layer_commitments();
+        if layer_commitments.len() != num_rounds + 2 {
+            return Err(VerifierError::NumLayerCommitmentsMismatch(
+                num_rounds + 2,
+                layer_commitments.len(),
+            ));
+        }
+
+        let grinding_factor = options.layer_grinding_factor();
+        let layer_pow_nonces = channel.read_fri_layer_pow_nonces();
+        let expected_num_nonces = if grinding_factor > 0 {
+            layer_commitments.len()
+        } else {
+            0
+        };
+        if layer_pow_nonces.len() != expected_num_nonces {
+            return Err(VerifierError::NumLayerPowNoncesMismatch(
+                expected_num_nonces,
+                layer_pow_nonces.len(),
+            ));
+        }
+
+        let ood_evaluations = channel.take_fri_ood_evaluations();
+        if ood_evaluations.len() != num_rounds {
+            return Err(VerifierError::NumOodEvaluationsMismatch(
+                num_rounds,
+                ood_evaluations.len(),
+            ));
+        }
+
+        // make sure the degree can be reduced by the folding factor of every round
+        let mut max_degree_plus_1 = max_poly_degree + 1;
+        for (depth, layer) in schedule[..num_rounds].iter().enumerate() {
+            if !max_degree_plus_1.is_multiple_of(layer.folding_factor) {
+                return Err(VerifierError::DegreeTruncation(
+                    max_degree_plus_1 - 1,
+                    layer.folding_factor,
+                    depth,
+                ));
+            }
+            max_degree_plus_1 /= layer.folding_factor;
+        }
+
+        // replay all STIR rounds
+        let absorb_commitment = |coin: &mut P, depth: usize| {
+            coin.reseed(layer_commitments[depth]);
+            if grinding_factor > 0 {
+                coin.reseed_with_int(layer_pow_nonces[depth]);
+                if coin.leading_zeros() < grinding_factor {
+                    return Err(VerifierError::LayerProofOfWorkVerificationFailed(depth));
+                }
+            }
+            Ok(())
+        };
+
+        let mut folding_randomness = Vec::with_capacity(num_rounds);
+        let mut ood_points = Vec::with_capacity(num_rounds);
+        let mut combination_randomness = Vec::with_capacity(num_rounds);
+        let mut query_indexes = Vec::with_capacity(num_rounds + 1);
+
+        absorb_commitment(public_coin, 0)?;
+        for (i, layer) in schedule[..num_rounds].iter().enumerate() {
+            folding_randomness.push(draw(public_coin)?);
+            absorb_commitment(public_coin, i + 1)?;
+            ood_points.push(draw(public_coin)?);
+            public_coin.reseed(H::hash_elements(&[ood_evaluations[i]]));
+            combination_randomness.push(draw(public_coin)?);
+            query_indexes.push(draw_query_indexes(public_coin, layer));
+        }
+        absorb_commitment(public_coin, num_rounds + 1)?;
+        query_indexes.push(draw_query_indexes(public_coin, &schedule[num_rounds]));
+
+        Ok(StirVerifier {
+            max_poly_degree,
+            final_max_degree: max_degree_plus_1 - 1,
+            domain_size,
+            schedule,
+            layer_commitments,
+            folding_randomness,
+            ood_points,
+            ood_evaluations,
+            combination_randomness,
+            query_indexes,
+            options,
+            _channel: PhantomData,
+        })
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns maximum degree of a polynomial accepted by this verifier.
+    pub fn max_poly_degree(&self) -> usize {
+        self.max_poly_degree
+    }
+
+    /// Returns size of the domain over which a polynomial commitment checked by this verifier
+    /// has been evaluated.
+    pub fn domain_size(&self) -> usize {
+        self.domain_size
+    }
+
+    /// Returns the number of STIR rounds executed by this verifier.
+    pub fn num_rounds(&self) -> usize {
+        self.schedule.len() - 1
+    }
+
+    /// Returns protocol configuration options for this verifier.
+    pub fn options(&self) -> &FriOptions {
+        &self.options
+    }
+
+    /// Returns layer commitments read from the channel during the commit phase; the last
+    /// commitment is the commitment to the final polynomial.
+    pub fn layer_commitments(&self) -> &[H::Digest] {
+        &self.layer_commitments
+    }
+
+    /// Returns folding randomness drawn in every STIR round.
+    pub fn folding_randomness(&self) -> &[E] {
+        &self.folding_randomness
+    }
+
+    /// Returns out-of-domain evaluations read from the channel; one evaluation for every STIR
+    /// round.
+    pub fn ood_evaluations(&self) -> &[E] {
+        &self.ood_evaluations
+    }
+
+    /// Returns indexes of the leaves in each layer commitment Merkle tree which are opened when
+    /// the first layer is queried at the specified `positions`.
+    ///
+    /// Apart from the first layer, the indexes do not depend on `positions`: every layer is
+    /// opened at the query indexes drawn for the corresponding round during the commit phase.
+    pub fn get_layer_query_indexes(&self, positions: &[usize]) -> Vec<Vec<usize>> {
+        let mut result = self.query_indexes.clone();
+        result[0] = self.get_first_layer_indexes(positions);
+        result
+    }
+
+    // VERIFICATION PROCEDURE
+    // --------------------------------------------------------------------------------------------
+
+    /// Executes the query phase of the STIR protocol.
+    ///
+    /// Returns `Ok(())` if values in the `evaluations` slice represent evaluations of a polynomial
+    /// with degree <= `max_poly_degree` at x coordinates specified by the `positions` slice.
+    /// Values of all layers are read from the specified `channel`.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The length of `evaluations` is not equal to the length of `positions`.
+    /// * Decommitments to layer values don't match the commitment value at any of the layers.
+    /// * The `evaluations` don't match the values of the first layer.
+    /// * Values of any of the layers are not consistent with the values of the previous layer,
+    ///   or with the final polynomial.
+    /// * The degree of the final polynomial is greater than the degree implied by
+    ///   `max_poly_degree` reduced by the folding factor of every round.
+    pub fn verify(
+        &self,
+        channel: &mut C,
+        evaluations: &[E],
+        positions: &[usize],
+    ) -> Result<(), VerifierError> {
+        if evaluations.len() != positions.len() {
+            return Err(VerifierError::NumPositionEvaluationMismatch(
+                positions.len(),
+                evaluations.len(),
+            ));
+        }
+        let domain_offset = self.options.domain_offset::<B>();
+
+        // 1 ----- make sure the evaluations match the first layer --------------------------------
+        let first_layer = &self.schedule[0];
+        let num_rows = first_layer.num_leaves();
+        let first_layer_indexes = self.get_first_layer_indexes(positions);
+        let first_layer_rows = read_layer_rows(
+            channel,
+            &first_layer_indexes,
+            &self.layer_commitments[0],
+            first_layer.folding_factor,
+        )?;
+        for (&position, &evaluation) in positions.iter().zip(evaluations) {
+            let row_idx = find_index(&first_layer_indexes, position % num_rows);
+            if first_layer_rows[row_idx][position / num_rows] != evaluation {
+                return Err(VerifierError::InvalidLayerFolding(0));
+            }
+        }
+
+        // values of the function tested in the first round at the rows queried by the round
+        let mut row_values = self.query_indexes[0]
+            .iter()
+            .map(|&index| first_layer_rows[find_index(&first_layer_indexes, index)].clone())
+            .collect::<Vec<_>>();
+
+        // 2 ----- verify STIR rounds -------------------------------------------------------------
+        for (i, layer) in self.schedule[..self.num_rounds()].iter().enumerate() {
+            let domain_generator = B::get_root_of_unity(log2(layer.domain_size));
+
+            // the folded function is known at the out-of-domain point, and at the points which
+            // correspond to the queried rows; values at these points are computed by folding the
+            // rows of the function tested in this round
+            let r_out = self.ood_points[i];
+            let mut points = vec![r_out];
+            let mut values = vec![self.ood_evaluations[i]];
+            for (&index, row) in self.query_indexes[i].iter().zip(row_values.iter()) {
+                let point =
+                    get_shift_point(index, domain_generator, domain_offset, layer.folding_factor);
+                if point == r_out {
+                    continue;
+                }
+                let xs = get_row_points(
+                    index,
+                    domain_generator,
+                    layer.domain_size,
+                    domain_offset,
+                    layer.folding_factor,
+                )
+                .into_iter()
+                .map(E::from)
+                .collect::<Vec<_>>();
+                let row_poly = polynom::interpolate(&xs, row, false);
+                points.push(point);
+                values.push(polynom::eval(&row_poly, self.folding_randomness[i]));
+            }
+            let answers_poly = polynom::interpolate(&points, &values, false);
+
+            // read the folded function at the rows queried by the next round, and compute the
+            // values of the function tested in the next round at these rows
+            let next_layer = &self.schedule[i + 1];
+            let next_indexes = &self.query_indexes[i + 1];
+            let next_rows = read_layer_rows(
+                channel,
+                next_indexes,
+                &self.layer_commitments[i + 1],
+                next_layer.folding_factor,
+            )?;
+            let next_generator = B::get_root_of_unity(log2(next_layer.domain_size));
+            let r_comb = self.combination_randomness[i];
+
+            row_values = Vec::with_capacity(next_indexes.len());
+            for (&index, row) in next_indexes.iter().zip(next_rows) {
+                let xs = get_row_points(
+                    index,
+                    next_generator,
+                    next_layer.domain_size,
+                    domain_offset,
+                    next_layer.folding_factor,
+                );
+                let mut next_row = Vec::with_capacity(row.len());
+                for (&x, folded_value) in xs.iter().zip(row) {
+                    let x = E::from(x);
+                    let vanishing_value = points.iter().fold(E::ONE, |acc, &a| acc * (x - a));
+                    if vanishing_value == E::ZERO {
+                        return Err(VerifierError::InvalidLayerFolding(i + 1));
+                    }
+                    let quotient =
+                        (folded_value - polynom::eval(&answers_poly, x)) / vanishing_value;
+                    next_row.push(quotient * get_degree_correction(r_comb, x, points.len()));
+                }
+                row_values.push(next_row);
+            }
+        }
+
+        // 3 ----- verify the final polynomial ----------------------------------------------------
+        let final_commitment = self.layer_commitments.last().unwrap();
+        let coefficients = channel.read_remainder_coefficients(final_commitment)?;
+
+        let final_layer = &self.schedule[self.num_rounds()];
+        let domain_generator = B::get_root_of_unity(log2(final_layer.domain_size));
+        let final_indexes = &self.query_indexes[self.num_rounds()];
+        for (&index, row) in final_indexes.iter().zip(row_values) {
+            let xs = get_row_points(
+                index,
+                domain_generator,
+                final_layer.domain_size,
+                domain_offset,
+                final_layer.folding_factor,
+            );
+            for (&x, value) in xs.iter().zip(row) {
+                if polynom::eval(&coefficients, E::from(x)) != value {
+                    return Err(VerifierError::InvalidRemainderFolding);
+                }
+            }
+        }
+
+        // the number of coefficients is bounded by the proof structure, but the degree may still
+        // need to be checked against a smaller max degree
+        if self.final_max_degree < polynom::degree_of(&coefficients) {
+            return Err(VerifierError::RemainderDegreeMismatch(
+                self.final_max_degree,
+            ));
+        }
+        Ok(())
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns indexes of the rows of the first layer which are opened when the first layer is
+    /// queried at the specified positions and by the first STIR round.
+    fn get_first_layer_indexes(&self, positions: &[usize]) -> Vec<usize> {
+        let num_rows = self.schedule[0].num_leaves();
+        let mut indexes = self.query_indexes[0].clone();
+        indexes.extend(positions.iter().map(|&p| p % num_rows));
+        sort_indexes(indexes)
+    }
+}
+
+impl<B, E, C, H> LowDegreeVerifier<E, C> for StirVerifier<B, E, C, H>
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+    C: VerifierChannel<E, Hasher = H>,
+    H: ElementHasher<BaseField = B>,
+{
+    fn layer_commitments(&self) -> &[H::Digest] {
+        StirVerifier::layer_commitments(self)
+    }
+
+    fn layer_alphas(&self) -> &[E] {
+        self.folding_randomness()
+    }
+
+    fn domain_size(&self) -> usize {
+        StirVerifier::domain_size(self)
+    }
+
+    fn get_layer_query_indexes(&self, positions: &[usize]) -> Vec<Vec<usize>> {
+        StirVerifier::get_layer_query_indexes(self, positions)
+    }
+
+    fn verify(
+        &self,
+        channel: &mut C,
+        evaluations: &[E],
+        positions: &[usize],
+    ) -> Result<(), VerifierError> {
+        StirVerifier::verify(self, channel, evaluations, positions)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Draws a random field element from the specified public coin.
+fn draw<E: FieldElement, P: PublicCoin<BaseField = E::BaseField>>(
+    public_coin: &mut P,
+) -> Result<E, VerifierError> {
+    public_coin.draw().map_err(VerifierError::PublicCoinError)
+}
+
+/// Draws query indexes for the specified layer from the public coin; the returned indexes are
+/// sorted and do not contain duplicates.
+fn draw_query_indexes<P: PublicCoin>(public_coin: &mut P, layer: &StirLayer) -> Vec<usize> {
+    let indexes =
+        public_coin.draw_integers_with_replacement(layer.num_query_draws(), layer.num_leaves());
+    sort_indexes(indexes)
+}
+
+/// Reads rows of layer values at the specified indexes from the channel, and makes sure they are
+/// valid against the specified layer commitment.
+fn read_layer_rows<E: FieldElement, C: VerifierChannel<E>>(
+    channel: &mut C,
+    indexes: &[usize],
+    commitment: &<C::Hasher as Hasher>::Digest,
+    folding_factor: usize,
+) -> Result<Vec<Vec<E>>, VerifierError> {
+    let rows = match folding_factor {
+        2 => to_rows(channel.read_layer_queries::<2>(indexes, commitment)?),
+        4 => to_rows(channel.read_layer_queries::<4>(indexes, commitment)?),
+        8 => to_rows(channel.read_layer_queries::<8>(indexes, commitment)?),
+        16 => to_rows(channel.read_layer_queries::<16>(indexes, commitment)?),
+        _ => return Err(VerifierError::UnsupportedFoldingFactor(folding_factor)),
+    };
+    if rows.len() != indexes.len() {
+        return Err(VerifierError::LayerCommitmentMismatch);
+    }
+    Ok(rows)
+}
+
+fn to_rows<E: FieldElement, const N: usize>(values: Vec<[E; N]>) -> Vec<Vec<E>> {
+    values.into_iter().map(|row| row.to_vec()).collect()
+}
+
+/// Returns the position of the specified index in a sorted list of indexes which contains it.
+fn find_index(indexes: &[usize], index: usize) -> usize {
+    indexes
+        .binary_search(&index)
+        .expect("index is not in the list of indexes")
+}
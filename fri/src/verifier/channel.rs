@@ -3,7 +3,7 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::{utils::hash_values, FriProof, VerifierError};
+use crate::{utils::hash_values, FriOptions, FriProof, VerifierError};
 use crypto::{BatchMerkleProof, ElementHasher, Hasher, MerkleTree};
 use math::FieldElement;
 use utils::{collections::Vec, group_vector_elements, transpose_slice, DeserializationError};
@@ -40,6 +40,19 @@ pub trait VerifierChannel<E: FieldElement> {
         &mut self,
     ) -> Vec<<<Self as VerifierChannel<E>>::Hasher as Hasher>::Digest>;
 
+    /// Reads and removes from the channel proof-of-work nonces sent by the prover for each FRI
+    /// layer commitment.
+    ///
+    /// If per-layer proof-of-work was not performed by the prover, an empty vector should be
+    /// returned.
+    fn read_fri_layer_pow_nonces(&mut self) -> Vec<u64>;
+
+    /// Reads and removes from the channel out-of-domain evaluations sent by the prover.
+    ///
+    /// Out-of-domain evaluations are sent only when STIR is used as the low-degree test (one
+    /// evaluation per STIR round); otherwise, an empty vector should be returned.
+    fn take_fri_ood_evaluations(&mut self) -> Vec<E>;
+
     /// Reads and removes from the channel evaluations of the polynomial at the queried positions
     /// for the next FRI layer.
     ///
@@ -119,6 +132,25 @@ pub trait VerifierChannel<E: FieldElement> {
 
         Ok(remainder)
     }
+
+    /// Returns coefficients of the FRI remainder polynomial read from this channel.
+    ///
+    /// This is used when the remainder is sent as coefficients rather than evaluations, and
+    /// also checks whether the coefficients are valid against the provided commitment.
+    ///
+    /// # Errors
+    /// Returns an error if the hash of the coefficients read from the channel does not match
+    /// the specified `commitment`.
+    fn read_remainder_coefficients(
+        &mut self,
+        commitment: &<<Self as VerifierChannel<E>>::Hasher as Hasher>::Digest,
+    ) -> Result<Vec<E>, VerifierError> {
+        let coefficients = self.take_fri_remainder();
+        if *commitment != Self::Hasher::hash_elements(&coefficients) {
+            return Err(VerifierError::RemainderCommitmentMismatch);
+        }
+        Ok(coefficients)
+    }
 }
 
 // DEFAULT VERIFIER CHANNEL IMPLEMENTATION
@@ -132,6 +164,8 @@ pub trait VerifierChannel<E: FieldElement> {
 /// production use cases as well.
 pub struct DefaultVerifierChannel<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> {
     layer_commitments: Vec<H::Digest>,
+    layer_pow_nonces: Vec<u64>,
+    ood_evaluations: Vec<E>,
     layer_proofs: Vec<BatchMerkleProof<H>>,
     layer_queries: Vec<Vec<E>>,
     remainder: Vec<E>,
@@ -151,16 +185,19 @@ where
         proof: FriProof,
         layer_commitments: Vec<H::Digest>,
         domain_size: usize,
-        folding_factor: usize,
+        options: &FriOptions,
     ) -> Result<Self, DeserializationError> {
         let num_partitions = proof.num_partitions();
+        let layer_pow_nonces = proof.layer_pow_nonces().to_vec();
+        let ood_evaluations = proof.parse_ood_evaluations()?;
 
         let remainder = proof.parse_remainder()?;
-        let (layer_queries, layer_proofs) =
-            proof.parse_layers::<H, E>(domain_size, folding_factor)?;
+        let (layer_queries, layer_proofs) = proof.parse_layers::<H, E>(domain_size, options)?;
 
         Ok(DefaultVerifierChannel {
             layer_commitments,
+            layer_pow_nonces,
+            ood_evaluations,
             layer_proofs,
             layer_queries,
             remainder,
@@ -184,6 +221,14 @@ where
         self.layer_commitments.drain(..).collect()
     }
 
+    fn read_fri_layer_pow_nonces(&mut self) -> Vec<u64> {
+        self.layer_pow_nonces.drain(..).collect()
+    }
+
+    fn take_fri_ood_evaluations(&mut self) -> Vec<E> {
+        self.ood_evaluations.drain(..).collect()
+    }
+
     fn take_next_fri_layer_proof(&mut self) -> BatchMerkleProof<H> {
         self.layer_proofs.remove(0)
     }
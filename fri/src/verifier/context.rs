@@ -0,0 +1,120 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::FriOptions;
+use math::{log2, StarkField};
+use utils::collections::Vec;
+
+// FRI VERIFIER CONTEXT
+// ================================================================================================
+
+/// Domain parameters of a FRI verifier which depend only on the protocol options and the max
+/// degree of the tested polynomial.
+///
+/// Building a context computes the domain generator of every FRI layer and the roots of unity
+/// used to fold every layer. A context can be reused to verify any number of proofs with the
+/// same options and max degree via [FriVerifier::with_context()](crate::FriVerifier::with_context);
+/// this way, services which verify many proofs with identical domain parameters do the setup
+/// work only once.
+#[derive(Clone)]
+pub struct FriVerifierContext<B: StarkField> {
+    options: FriOptions,
+    max_poly_degree: usize,
+    domain_size: usize,
+    domain_offset: B,
+    layer_generators: Vec<B>,
+    layer_folding_roots: Vec<Vec<B>>,
+}
+
+impl<B: StarkField> FriVerifierContext<B> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new context for verifying proofs that a polynomial is of degree at most
+    /// `max_poly_degree` with the specified `options`.
+    ///
+    /// The domain of the first layer is computed by rounding `max_poly_degree` to the next power
+    /// of two and multiplying the result by the blowup factor from the `options`. Parameters are
+    /// computed for the max number of FRI layers implied by the `options` (and for the
+    /// remainder layer).
+    pub fn new(options: FriOptions, max_poly_degree: usize) -> Self {
+        let domain_size = max_poly_degree.next_power_of_two() * options.blowup_factor();
+        let domain_offset = options.domain_offset();
+        let num_layers = options.num_fri_layers(domain_size);
+
+        // the generator of each layer domain is the generator of the previous layer domain
+        // raised to the power of the folding factor of the previous layer
+        let mut layer_generators = Vec::with_capacity(num_layers + 1);
+        let mut layer_folding_roots = Vec::with_capacity(num_layers);
+        let mut generator = B::get_root_of_unity(log2(domain_size));
+        let mut layer_domain_size = domain_size;
+        for depth in 0..num_layers {
+            let folding_factor = options.layer_folding_factor(depth);
+            layer_generators.push(generator);
+            layer_folding_roots.push(
+                (0..folding_factor)
+                    .map(|i| {
+                        generator.exp(((layer_domain_size / folding_factor * i) as u64).into())
+                    })
+                    .collect(),
+            );
+            generator = generator.exp((folding_factor as u32).into());
+            layer_domain_size /= folding_factor;
+        }
+        layer_generators.push(generator);
+
+        FriVerifierContext {
+            options,
+            max_poly_degree,
+            domain_size,
+            domain_offset,
+            layer_generators,
+            layer_folding_roots,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns protocol configuration options for this context.
+    pub fn options(&self) -> &FriOptions {
+        &self.options
+    }
+
+    /// Returns maximum degree of a polynomial accepted by verifiers using this context.
+    pub fn max_poly_degree(&self) -> usize {
+        self.max_poly_degree
+    }
+
+    /// Returns size of the domain of the first FRI layer.
+    pub fn domain_size(&self) -> usize {
+        self.domain_size
+    }
+
+    /// Returns the offset by which the domains of all FRI layers are shifted.
+    pub fn domain_offset(&self) -> B {
+        self.domain_offset
+    }
+
+    /// Returns the generator of the domain of the layer at the specified `depth`.
+    ///
+    /// # Panics
+    /// Panics if `depth` is greater than the max number of FRI layers implied by the options
+    /// of this context (the remainder layer is at the max depth).
+    pub fn layer_generator(&self, depth: usize) -> B {
+        self.layer_generators[depth]
+    }
+
+    /// Returns roots of unity used to fold the layer at the specified `depth`.
+    ///
+    /// For a layer with folding factor N, these are the N roots of unity which map an element of
+    /// the folded domain to the N elements of the layer domain which fold into it.
+    ///
+    /// # Panics
+    /// Panics if `depth` is not smaller than the max number of FRI layers implied by the options
+    /// of this context.
+    pub fn layer_folding_roots(&self, depth: usize) -> &[B] {
+        &self.layer_folding_roots[depth]
+    }
+}
@@ -5,15 +5,63 @@
 
 //! Contains an implementation of FRI verifier and associated components.
 
-use crate::{folding::fold_positions, utils::map_positions_to_indexes, FriOptions, VerifierError};
-use core::{convert::TryInto, marker::PhantomData, mem};
-use crypto::{ElementHasher, RandomCoin};
-use math::{fft, log2, polynom, FieldElement, StarkField};
-use utils::collections::Vec;
+use crate::{
+    folding::fold_positions, utils::map_positions_to_indexes, FriOptions, RemainderFormat,
+    VerifierError,
+};
+use core::{convert::TryInto, marker::PhantomData};
+use crypto::{ElementHasher, Hasher, PublicCoin};
+use math::{batch_inversion, fft, polynom, FieldElement, StarkField};
+use utils::{collections::Vec, iter};
+
+#[cfg(feature = "concurrent")]
+use utils::iterators::*;
 
 mod channel;
 pub use channel::{DefaultVerifierChannel, VerifierChannel};
 
+mod context;
+pub use context::FriVerifierContext;
+
+// LOW-DEGREE VERIFIER
+// ================================================================================================
+
+/// Defines an interface for the verifier component of a low-degree test.
+///
+/// This interface is implemented by [FriVerifier] and by [StirVerifier](crate::StirVerifier),
+/// and makes it possible to select the low-degree test at runtime (e.g., based on proof
+/// options). The commit phase of the test is executed when the verifier is instantiated; thus,
+/// this interface covers only the query phase of the test and the values the verifier derived
+/// during the commit phase.
+pub trait LowDegreeVerifier<E: FieldElement, C: VerifierChannel<E>> {
+    /// Returns layer commitments read from the channel during the commit phase, in the order in
+    /// which the layers were committed to by the prover.
+    fn layer_commitments(&self) -> &[<C::Hasher as Hasher>::Digest];
+
+    /// Returns the folding challenges drawn during the commit phase.
+    fn layer_alphas(&self) -> &[E];
+
+    /// Returns size of the domain over which a polynomial checked by this verifier has been
+    /// evaluated.
+    fn domain_size(&self) -> usize;
+
+    /// Returns indexes of the leaves in each layer commitment Merkle tree which are opened when
+    /// the first layer is queried at the specified `positions`.
+    fn get_layer_query_indexes(&self, positions: &[usize]) -> Vec<Vec<usize>>;
+
+    /// Executes the query phase of the low-degree test, and returns `Ok(())` if the `evaluations`
+    /// at the specified `positions` are evaluations of a polynomial of low degree.
+    ///
+    /// # Errors
+    /// Returns an error if the proof read from the `channel` is not valid.
+    fn verify(
+        &self,
+        channel: &mut C,
+        evaluations: &[E],
+        positions: &[usize],
+    ) -> Result<(), VerifierError>;
+}
+
 // FRI VERIFIER
 // ================================================================================================
 /// Implements the verifier component of the FRI protocol.
@@ -41,7 +89,9 @@ pub use channel::{DefaultVerifierChannel, VerifierChannel};
 /// from the prover (via [VerifierChannel]). After each received commitment, the verifier
 /// draws a random value α from the entire field, and sends it to the prover. In the
 /// non-interactive version of the protocol, α values are derived pseudo-randomly from FRI
-/// layer commitments.
+/// layer commitments. If per-layer proof-of-work is required by the protocol options, the
+/// verifier also checks the proof-of-work nonce sent by the prover for each layer before
+/// drawing α for that layer.
 ///
 /// # Query phase
 /// During the query phase, which is executed via [verify()](FriVerifier::verify()) function,
@@ -53,8 +103,8 @@ pub use channel::{DefaultVerifierChannel, VerifierChannel};
 /// * The evaluations are consistent across FRI layers (i.e., the degree-respecting projection
 ///   was applied correctly).
 /// * The degree of the polynomial implied by evaluations at the last FRI layer (the remainder)
-///   is smaller than the degree resulting from reducing degree *d* by `folding_factor` at each
-///   FRI layer.
+///   is smaller than the degree resulting from reducing degree *d* by the folding factor of
+///   each FRI layer.
 pub struct FriVerifier<B, E, C, H>
 where
     B: StarkField,
@@ -62,12 +112,11 @@ where
     C: VerifierChannel<E, Hasher = H>,
     H: ElementHasher<BaseField = B>,
 {
-    max_poly_degree: usize,
-    domain_size: usize,
-    domain_generator: B,
+    context: FriVerifierContext<B>,
     layer_commitments: Vec<H::Digest>,
     layer_alphas: Vec<E>,
-    options: FriOptions,
+    layer_ood_points: Vec<E>,
+    layer_ood_evaluations: Vec<E>,
     num_partitions: usize,
     _channel: PhantomData<C>,
 }
@@ -88,58 +137,156 @@ where
     /// Creating a FRI verifier executes the commit phase of the FRI protocol from the verifier's
     /// perspective. Specifically, the verifier reads FRI layer commitments from the `channel`,
     /// and for each commitment, updates the `public_coin` with this commitment and then draws
-    /// a random value α from the coin.
+    /// a random value α from the coin. Any implementation of [PublicCoin] can be used as the
+    /// coin, as long as the prover derived α values in the same way.
     ///
     /// The verifier stores layer commitments and corresponding α values in its internal state,
     /// and, thus, an instance of FRI verifier can be used to verify only a single proof.
     ///
     /// # Errors
     /// Returns an error if:
+    /// * The number of layer commitments read from the channel is not consistent with the
+    ///   number of FRI layers implied by the `options`; with early termination, any number of
+    ///   layers up to this number is accepted.
+    /// * Out-of-domain sampling is performed within FRI layers according to the `options`, and
+    ///   the number of out-of-domain evaluations read from the channel is not equal to the number
+    ///   of FRI layers; or, out-of-domain evaluations were read without out-of-domain sampling.
     /// * `max_poly_degree` is inconsistent with the number of FRI layers read from the channel
     ///   and `folding_factor` specified in the `options` parameter.
     /// * An error was encountered while drawing a random α value from the coin.
-    pub fn new(
+    /// * Per-layer proof-of-work is required by the `options`, and a proof-of-work nonce read
+    ///   from the channel for any of the layers does not satisfy the layer grinding factor.
+    pub fn new<P>(
         channel: &mut C,
-        public_coin: &mut RandomCoin<B, H>,
+        public_coin: &mut P,
         options: FriOptions,
         max_poly_degree: usize,
-    ) -> Result<Self, VerifierError> {
-        // infer evaluation domain info
-        let domain_size = max_poly_degree.next_power_of_two() * options.blowup_factor();
-        let domain_generator = B::get_root_of_unity(log2(domain_size));
+    ) -> Result<Self, VerifierError>
+    where
+        P: PublicCoin<BaseField = B, Hasher = H>,
+    {
+        let context = FriVerifierContext::new(options, max_poly_degree);
+        Self::with_context(channel, public_coin, &context)
+    }
+
+    /// Returns a new instance of FRI verifier created from the specified `context`.
+    ///
+    /// This works exactly like [new()](FriVerifier::new) method, but the domain parameters are
+    /// taken from a [FriVerifierContext] rather than computed from the options; thus, a single
+    /// context can be used to instantiate verifiers for many proofs with the same options and
+    /// max polynomial degree.
+    ///
+    /// # Errors
+    /// Returns an error for the same reasons as [new()](FriVerifier::new) method.
+    pub fn with_context<P>(
+        channel: &mut C,
+        public_coin: &mut P,
+        context: &FriVerifierContext<B>,
+    ) -> Result<Self, VerifierError>
+    where
+        P: PublicCoin<BaseField = B, Hasher = H>,
+    {
+        let options = context.options();
+        let domain_size = context.domain_size();
+        let max_poly_degree = context.max_poly_degree();
 
         let num_partitions = channel.read_fri_num_partitions();
 
-        // read layer commitments from the channel and use them to build a list of alphas
+        // read layer commitments from the channel and use them to build a list of alphas; with
+        // early termination, the prover may commit to fewer layers than implied by the options
         let layer_commitments = channel.read_fri_layer_commitments();
+        let expected_num_commitments = options.num_fri_layers(domain_size) + 1;
+        let num_commitments = layer_commitments.len();
+        if num_commitments == 0
+            || num_commitments > expected_num_commitments
+            || (num_commitments < expected_num_commitments && !options.early_termination())
+        {
+            return Err(VerifierError::NumLayerCommitmentsMismatch(
+                expected_num_commitments,
+                num_commitments,
+            ));
+        }
+
+        // read layer proof-of-work nonces from the channel; if per-layer proof-of-work is
+        // required, there must be a nonce for every layer commitment
+        let grinding_factor = options.layer_grinding_factor();
+        let layer_pow_nonces = channel.read_fri_layer_pow_nonces();
+        let expected_num_nonces = if grinding_factor > 0 {
+            layer_commitments.len()
+        } else {
+            0
+        };
+        if layer_pow_nonces.len() != expected_num_nonces {
+            return Err(VerifierError::NumLayerPowNoncesMismatch(
+                expected_num_nonces,
+                layer_pow_nonces.len(),
+            ));
+        }
+
+        // read out-of-domain evaluations from the channel; if out-of-domain sampling is
+        // performed within FRI layers, there must be an evaluation for every layer but the
+        // remainder
+        let layer_ood_evaluations = channel.take_fri_ood_evaluations();
+        let expected_num_ood_evaluations = if options.deep_sampling() {
+            num_commitments - 1
+        } else {
+            0
+        };
+        if layer_ood_evaluations.len() != expected_num_ood_evaluations {
+            return Err(VerifierError::NumOodEvaluationsMismatch(
+                expected_num_ood_evaluations,
+                layer_ood_evaluations.len(),
+            ));
+        }
+
         let mut layer_alphas = Vec::with_capacity(layer_commitments.len());
+        let mut layer_ood_points = Vec::with_capacity(layer_ood_evaluations.len());
         let mut max_degree_plus_1 = max_poly_degree + 1;
         for (depth, commitment) in layer_commitments.iter().enumerate() {
             public_coin.reseed(*commitment);
+
+            // make sure the proof-of-work for this layer is satisfied
+            if grinding_factor > 0 {
+                public_coin.reseed_with_int(layer_pow_nonces[depth]);
+                if public_coin.leading_zeros() < grinding_factor {
+                    return Err(VerifierError::LayerProofOfWorkVerificationFailed(depth));
+                }
+            }
+
             let alpha = public_coin.draw().map_err(VerifierError::PublicCoinError)?;
             layer_alphas.push(alpha);
 
+            // draw the out-of-domain point for the folded layer, and update the coin with the
+            // evaluation at this point sent by the prover
+            if let Some(&ood_evaluation) = layer_ood_evaluations.get(depth) {
+                let z = public_coin.draw().map_err(VerifierError::PublicCoinError)?;
+                layer_ood_points.push(z);
+                public_coin.reseed(H::hash_elements(&[ood_evaluation]));
+            }
+
             // make sure the degree can be reduced by the folding factor at all layers
-            // but the remainder layer
+            // but the remainder layer; with out-of-domain sampling, the degree bound is rounded
+            // up instead (see get_quotient_degree_bound())
+            let folding_factor = options.layer_folding_factor(depth);
             if depth != layer_commitments.len() - 1
-                && max_degree_plus_1 % options.folding_factor() != 0
+                && !options.deep_sampling()
+                && !max_degree_plus_1.is_multiple_of(folding_factor)
             {
                 return Err(VerifierError::DegreeTruncation(
                     max_degree_plus_1 - 1,
-                    options.folding_factor(),
+                    folding_factor,
                     depth,
                 ));
             }
-            max_degree_plus_1 /= options.folding_factor();
+            max_degree_plus_1 /= folding_factor;
         }
 
         Ok(FriVerifier {
-            max_poly_degree,
-            domain_size,
-            domain_generator,
+            context: context.clone(),
             layer_commitments,
             layer_alphas,
-            options,
+            layer_ood_points,
+            layer_ood_evaluations,
             num_partitions,
             _channel: PhantomData,
         })
@@ -148,9 +295,17 @@ where
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
+    /// Returns the number of FRI layers (excluding the remainder) committed to by the prover.
+    ///
+    /// This is the number of layers implied by the options, unless the prover terminated folding
+    /// early.
+    pub fn num_layers(&self) -> usize {
+        self.layer_commitments.len() - 1
+    }
+
     /// Returns maximum degree of a polynomial accepted by this verifier.
     pub fn max_poly_degree(&self) -> usize {
-        self.max_poly_degree
+        self.context.max_poly_degree()
     }
 
     /// Returns size of the domain over which a polynomial commitment checked by this verifier
@@ -159,7 +314,7 @@ where
     /// The domain size can be computed by rounding `max_poly_degree` to the next power of two
     /// and multiplying the result by the `blowup_factor` from the protocol options.
     pub fn domain_size(&self) -> usize {
-        self.domain_size
+        self.context.domain_size()
     }
 
     /// Returns number of partitions used during FRI proof generation.
@@ -171,7 +326,42 @@ where
 
     /// Returns protocol configuration options for this verifier.
     pub fn options(&self) -> &FriOptions {
-        &self.options
+        self.context.options()
+    }
+
+    /// Returns FRI layer commitments read from the channel during the commit phase, in the order
+    /// in which the layers were committed to by the prover.
+    pub fn layer_commitments(&self) -> &[H::Digest] {
+        &self.layer_commitments
+    }
+
+    /// Returns α values drawn after each FRI layer commitment during the commit phase.
+    pub fn layer_alphas(&self) -> &[E] {
+        &self.layer_alphas
+    }
+
+    /// Returns indexes of the leaves in each FRI layer commitment Merkle tree which are opened
+    /// when the first FRI layer is queried at the specified `positions`.
+    ///
+    /// For each layer (excluding the remainder), the indexes are listed in the same order as the
+    /// corresponding query values and authentication paths are read from the channel.
+    pub fn get_layer_query_indexes(&self, positions: &[usize]) -> Vec<Vec<usize>> {
+        let mut domain_size = self.domain_size();
+        let mut positions = positions.to_vec();
+
+        let mut result = Vec::new();
+        for depth in 0..self.num_layers() {
+            let folding_factor = self.options().layer_folding_factor(depth);
+            positions = fold_positions(&positions, domain_size, folding_factor);
+            result.push(map_positions_to_indexes(
+                &positions,
+                domain_size,
+                folding_factor,
+                self.num_partitions,
+            ));
+            domain_size /= folding_factor;
+        }
+        result
     }
 
     // VERIFICATION PROCEDURE
@@ -212,102 +402,84 @@ where
             ));
         }
 
-        // static dispatch for folding factor parameter
-        let folding_factor = self.options.folding_factor();
-        match folding_factor {
-            4 => self.verify_generic::<4>(channel, evaluations, positions),
-            8 => self.verify_generic::<8>(channel, evaluations, positions),
-            16 => self.verify_generic::<16>(channel, evaluations, positions),
-            _ => Err(VerifierError::UnsupportedFoldingFactor(folding_factor)),
-        }
-    }
-
-    /// This is the actual implementation of the verification procedure described above, but it
-    /// also takes folding factor as a generic parameter N.
-    fn verify_generic<const N: usize>(
-        &self,
-        channel: &mut C,
-        evaluations: &[E],
-        positions: &[usize],
-    ) -> Result<(), VerifierError> {
-        // pre-compute roots of unity used in computing x coordinates in the folded domain
-        let folding_roots = (0..N)
-            .map(|i| {
-                self.domain_generator
-                    .exp(((self.domain_size / N * i) as u64).into())
-            })
-            .collect::<Vec<_>>();
-
         // 1 ----- verify the recursive components of the FRI proof -----------------------------------
-        let mut domain_generator = self.domain_generator;
-        let mut domain_size = self.domain_size;
-        let mut max_degree_plus_1 = self.max_poly_degree + 1;
+        let mut domain_size = self.domain_size();
+        let mut max_degree_plus_1 = self.max_poly_degree() + 1;
         let mut positions = positions.to_vec();
         let mut evaluations = evaluations.to_vec();
 
-        for depth in 0..self.options.num_fri_layers(self.domain_size) {
-            // determine which evaluations were queried in the folded layer
-            let mut folded_positions =
-                fold_positions(&positions, domain_size, self.options.folding_factor());
-            // determine where these evaluations are in the commitment Merkle tree
-            let position_indexes = map_positions_to_indexes(
-                &folded_positions,
-                domain_size,
-                self.options.folding_factor(),
-                self.num_partitions,
-            );
-            // read query values from the specified indexes in the Merkle tree
-            let layer_commitment = self.layer_commitments[depth];
-            // TODO: add layer depth to the potential error message
-            let layer_values = channel.read_layer_queries(&position_indexes, &layer_commitment)?;
-            let query_values =
-                get_query_values::<E, N>(&layer_values, &positions, &folded_positions, domain_size);
-            if evaluations != query_values {
-                return Err(VerifierError::InvalidLayerFolding(depth));
-            }
-
-            // build a set of x coordinates for each row polynomial
+        let num_layers = self.num_layers();
+        for depth in 0..num_layers {
+            // static dispatch for the folding factor of this layer
+            let folding_factor = self.options().layer_folding_factor(depth);
             #[rustfmt::skip]
-            let xs = folded_positions.iter().map(|&i| {
-                let xe = domain_generator.exp((i as u64).into()) * self.options.domain_offset();
-                folding_roots.iter()
-                    .map(|&r| E::from(xe * r))
-                    .collect::<Vec<_>>().try_into().unwrap()
-            })
-            .collect::<Vec<_>>();
+            let (folded_positions, folded_evaluations) = match folding_factor {
+                2 => self.verify_layer::<2>(channel, depth, domain_size, &positions, &evaluations)?,
+                4 => self.verify_layer::<4>(channel, depth, domain_size, &positions, &evaluations)?,
+                8 => self.verify_layer::<8>(channel, depth, domain_size, &positions, &evaluations)?,
+                16 => self.verify_layer::<16>(channel, depth, domain_size, &positions, &evaluations)?,
+                _ => return Err(VerifierError::UnsupportedFoldingFactor(folding_factor)),
+            };
+
+            // with out-of-domain sampling, the next layer is a quotient of the folded layer, and
+            // thus, its degree is smaller by one than the degree of the folded layer
+            domain_size /= folding_factor;
+            let folded_evaluations = if self.layer_ood_points.is_empty() {
+                // make sure next degree reduction does not result in degree truncation
+                if !max_degree_plus_1.is_multiple_of(folding_factor) {
+                    return Err(VerifierError::DegreeTruncation(
+                        max_degree_plus_1 - 1,
+                        folding_factor,
+                        depth,
+                    ));
+                }
+                max_degree_plus_1 /= folding_factor;
+                folded_evaluations
+            } else {
+                max_degree_plus_1 = get_quotient_degree_bound(max_degree_plus_1, folding_factor);
+                self.get_quotient_evaluations(depth, &folded_positions, folded_evaluations)
+            };
 
-            // interpolate x and y values into row polynomials
-            let row_polys = polynom::interpolate_batch(&xs, &layer_values);
-
-            // calculate the pseudo-random value used for linear combination in layer folding
-            let alpha = self.layer_alphas[depth];
+            // update variables for the next iteration of the loop
+            positions = folded_positions;
+            evaluations = folded_evaluations;
+        }
 
-            // check that when the polynomials are evaluated at alpha, the result is equal to
-            // the corresponding column value
-            evaluations = row_polys.iter().map(|p| polynom::eval(p, alpha)).collect();
+        // 2 ----- verify the remainder of the FRI proof ----------------------------------------------
 
-            // make sure next degree reduction does not result in degree truncation
-            if max_degree_plus_1 % N != 0 {
-                return Err(VerifierError::DegreeTruncation(
-                    max_degree_plus_1 - 1,
-                    N,
-                    depth,
-                ));
+        let remainder_commitment = self.layer_commitments.last().unwrap();
+        if self.options().remainder_format() == RemainderFormat::Coefficients {
+            // read remainder coefficients from the channel and make sure the remainder polynomial
+            // evaluates to the columns of the previous layer at the queried positions
+            let coefficients = channel.read_remainder_coefficients(remainder_commitment)?;
+            let domain_generator = self.context.layer_generator(num_layers);
+            let domain_offset = self.context.domain_offset();
+            for (&position, evaluation) in positions.iter().zip(evaluations) {
+                let x = domain_generator.exp((position as u64).into()) * domain_offset;
+                if polynom::eval(&coefficients, E::from(x)) != evaluation {
+                    return Err(VerifierError::InvalidRemainderFolding);
+                }
             }
 
-            // update variables for the next iteration of the loop
-            domain_generator = domain_generator.exp((N as u32).into());
-            max_degree_plus_1 /= N;
-            domain_size /= N;
-            mem::swap(&mut positions, &mut folded_positions);
+            // the number of coefficients is bounded by the proof structure, but the degree may
+            // still need to be checked against a smaller max degree
+            let max_degree = max_degree_plus_1 - 1;
+            if max_degree < polynom::degree_of(&coefficients) {
+                return Err(VerifierError::RemainderDegreeMismatch(max_degree));
+            }
+            return Ok(());
         }
 
-        // 2 ----- verify the remainder of the FRI proof ----------------------------------------------
-
         // read the remainder from the channel and make sure it matches with the columns
         // of the previous layer
-        let remainder_commitment = self.layer_commitments.last().unwrap();
-        let remainder = channel.read_remainder::<N>(remainder_commitment)?;
+        let folding_factor = self.options().layer_folding_factor(num_layers);
+        let remainder = match folding_factor {
+            2 => channel.read_remainder::<2>(remainder_commitment)?,
+            4 => channel.read_remainder::<4>(remainder_commitment)?,
+            8 => channel.read_remainder::<8>(remainder_commitment)?,
+            16 => channel.read_remainder::<16>(remainder_commitment)?,
+            _ => return Err(VerifierError::UnsupportedFoldingFactor(folding_factor)),
+        };
         for (&position, evaluation) in positions.iter().zip(evaluations) {
             if remainder[position] != evaluation {
                 return Err(VerifierError::InvalidRemainderFolding);
@@ -317,6 +489,124 @@ where
         // make sure the remainder values satisfy the degree
         verify_remainder(remainder, max_degree_plus_1 - 1)
     }
+
+    /// Returns evaluations of the quotient (*f*(*x*) - *β*) / (*x* - *z*) at the specified
+    /// `positions` of the layer following the layer at the specified `depth`, where *f* is the
+    /// folded layer with the specified `evaluations`, *z* is the out-of-domain point drawn for
+    /// the folded layer, and *β* is the evaluation of *f* at *z* sent by the prover.
+    fn get_quotient_evaluations(
+        &self,
+        depth: usize,
+        positions: &[usize],
+        evaluations: Vec<E>,
+    ) -> Vec<E> {
+        let z = self.layer_ood_points[depth];
+        let ood_evaluation = self.layer_ood_evaluations[depth];
+        let domain_generator = self.context.layer_generator(depth + 1);
+        let domain_offset = self.context.domain_offset();
+
+        let denominators = positions
+            .iter()
+            .map(|&p| E::from(domain_generator.exp((p as u64).into()) * domain_offset) - z)
+            .collect::<Vec<_>>();
+        batch_inversion(&denominators)
+            .into_iter()
+            .zip(evaluations)
+            .map(|(inv_denominator, evaluation)| (evaluation - ood_evaluation) * inv_denominator)
+            .collect()
+    }
+
+    /// Verifies decommitments to the FRI layer at the specified `depth`, and folds the layer by
+    /// the folding factor N.
+    ///
+    /// `domain_size` is the size of the domain of the layer, and `positions` and `evaluations`
+    /// specify the queried positions in this domain and the evaluations at these positions. Returns the positions queried in the folded layer together with evaluations
+    /// of the folded layer at these positions.
+    fn verify_layer<const N: usize>(
+        &self,
+        channel: &mut C,
+        depth: usize,
+        domain_size: usize,
+        positions: &[usize],
+        evaluations: &[E],
+    ) -> Result<(Vec<usize>, Vec<E>), VerifierError> {
+        // roots of unity used in computing x coordinates in the folded domain are pre-computed
+        // in the verifier context
+        let domain_generator = self.context.layer_generator(depth);
+        let folding_roots = self.context.layer_folding_roots(depth);
+        let domain_offset = self.context.domain_offset();
+
+        // determine which evaluations were queried in the folded layer
+        let folded_positions = fold_positions(positions, domain_size, N);
+        // determine where these evaluations are in the commitment Merkle tree
+        let position_indexes =
+            map_positions_to_indexes(&folded_positions, domain_size, N, self.num_partitions);
+        // read query values from the specified indexes in the Merkle tree
+        let layer_commitment = self.layer_commitments[depth];
+        // TODO: add layer depth to the potential error message
+        let layer_values = channel.read_layer_queries(&position_indexes, &layer_commitment)?;
+        let query_values =
+            get_query_values::<E, N>(&layer_values, positions, &folded_positions, domain_size);
+        if evaluations != query_values {
+            return Err(VerifierError::InvalidLayerFolding(depth));
+        }
+
+        // build a set of x coordinates for each row polynomial; when `concurrent` feature is
+        // enabled, this and other per-query computations are done in multiple threads
+        #[rustfmt::skip]
+        let xs = iter!(folded_positions).map(|&i| {
+            let xe = domain_generator.exp((i as u64).into()) * domain_offset;
+            folding_roots.iter()
+                .map(|&r| E::from(xe * r))
+                .collect::<Vec<_>>().try_into().unwrap()
+        })
+        .collect::<Vec<_>>();
+
+        // interpolate x and y values into row polynomials
+        let row_polys = polynom::interpolate_batch(&xs, &layer_values);
+
+        // calculate the pseudo-random value used for linear combination in layer folding
+        let alpha = self.layer_alphas[depth];
+
+        // check that when the polynomials are evaluated at alpha, the result is equal to
+        // the corresponding column value
+        let folded_evaluations = iter!(row_polys).map(|p| polynom::eval(p, alpha)).collect();
+
+        Ok((folded_positions, folded_evaluations))
+    }
+}
+
+impl<B, E, C, H> LowDegreeVerifier<E, C> for FriVerifier<B, E, C, H>
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+    C: VerifierChannel<E, Hasher = H>,
+    H: ElementHasher<BaseField = B>,
+{
+    fn layer_commitments(&self) -> &[H::Digest] {
+        FriVerifier::layer_commitments(self)
+    }
+
+    fn layer_alphas(&self) -> &[E] {
+        FriVerifier::layer_alphas(self)
+    }
+
+    fn domain_size(&self) -> usize {
+        FriVerifier::domain_size(self)
+    }
+
+    fn get_layer_query_indexes(&self, positions: &[usize]) -> Vec<Vec<usize>> {
+        FriVerifier::get_layer_query_indexes(self, positions)
+    }
+
+    fn verify(
+        &self,
+        channel: &mut C,
+        evaluations: &[E],
+        positions: &[usize],
+    ) -> Result<(), VerifierError> {
+        FriVerifier::verify(self, channel, evaluations, positions)
+    }
 }
 
 // REMAINDER DEGREE VERIFICATION
@@ -348,6 +638,18 @@ fn verify_remainder<B: StarkField, E: FieldElement<BaseField = B>>(
 
 // HELPER FUNCTIONS
 // ================================================================================================
+
+/// Returns the number of coefficients of the quotient of a polynomial with `num_coefficients`
+/// coefficients folded by the `folding_factor`.
+///
+/// Folding a polynomial with n coefficients results in a polynomial with at most ceil(n / k)
+/// coefficients, and dividing it by (x - z) removes one more coefficient. A polynomial which
+/// folds into a constant is divided into the zero polynomial; however, at least one coefficient
+/// is retained so that the degree bound remains well defined.
+fn get_quotient_degree_bound(num_coefficients: usize, folding_factor: usize) -> usize {
+    (num_coefficients.div_ceil(folding_factor) - 1).max(1)
+}
+
 fn get_query_values<E: FieldElement, const N: usize>(
     values: &[[E; N]],
     positions: &[usize],
@@ -356,15 +658,13 @@ fn get_query_values<E: FieldElement, const N: usize>(
 ) -> Vec<E> {
     let row_length = domain_size / N;
 
-    let mut result = Vec::new();
-    for position in positions {
-        let idx = folded_positions
-            .iter()
-            .position(|&v| v == position % row_length)
-            .unwrap();
-        let value = values[idx][position / row_length];
-        result.push(value);
-    }
-
-    result
+    iter!(positions)
+        .map(|position| {
+            let idx = folded_positions
+                .iter()
+                .position(|&v| v == position % row_length)
+                .unwrap();
+            values[idx][position / row_length]
+        })
+        .collect()
 }
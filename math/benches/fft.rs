@@ -39,13 +39,7 @@ fn fft_evaluate_poly(c: &mut Criterion) {
         let twiddles: Vec<BaseElement> = fft::get_twiddles(size / blowup_factor);
         group.bench_function(BenchmarkId::new("with_offset", size), |bench| {
             bench.iter_with_large_drop(|| {
-                let result = fft::evaluate_poly_with_offset(
-                    &p,
-                    &twiddles,
-                    BaseElement::GENERATOR,
-                    blowup_factor,
-                );
-                result
+                fft::evaluate_poly_with_offset(&p, &twiddles, BaseElement::GENERATOR, blowup_factor)
             });
         });
     }
@@ -77,7 +71,7 @@ fn fft_interpolate_poly(c: &mut Criterion) {
         group.bench_function(BenchmarkId::new("simple", size), |bench| {
             bench.iter_batched_ref(
                 || p.clone(),
-                |mut p| fft::interpolate_poly(&mut p, &inv_twiddles),
+                |p| fft::interpolate_poly(p, &inv_twiddles),
                 BatchSize::LargeInput,
             );
         });
@@ -89,9 +83,7 @@ fn fft_interpolate_poly(c: &mut Criterion) {
         group.bench_function(BenchmarkId::new("with_offset", size), |bench| {
             bench.iter_batched_ref(
                 || p.clone(),
-                |mut p| {
-                    fft::interpolate_poly_with_offset(&mut p, &inv_twiddles, BaseElement::GENERATOR)
-                },
+                |p| fft::interpolate_poly_with_offset(p, &inv_twiddles, BaseElement::GENERATOR),
                 BatchSize::LargeInput,
             );
         });
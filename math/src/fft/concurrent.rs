@@ -7,7 +7,8 @@ use crate::{
     field::{FieldElement, StarkField},
     utils::log2,
 };
-use utils::{collections::Vec, iterators::*, rayon, uninit_vector};
+use core::mem::MaybeUninit;
+use utils::{assume_init_slice, iterators::*, rayon};
 
 // POLYNOMIAL EVALUATION
 // ================================================================================================
@@ -19,32 +20,31 @@ pub fn evaluate_poly<B: StarkField, E: FieldElement<BaseField = B>>(p: &mut [E],
     permute(p);
 }
 
-/// Evaluates polynomial `p` using FFT algorithm and returns the result. The polynomial is
-/// evaluated over domain specified by `twiddles`, expanded by the `blowup_factor`, and shifted
-/// by the `domain_offset`.
+/// Evaluates polynomial `p` using FFT algorithm and writes the result into `result`. The
+/// polynomial is evaluated over domain specified by `twiddles`, expanded by the `blowup_factor`,
+/// and shifted by the `domain_offset`.
 pub fn evaluate_poly_with_offset<B: StarkField, E: FieldElement<BaseField = B>>(
     p: &[E],
     twiddles: &[B],
     domain_offset: B,
     blowup_factor: usize,
-) -> Vec<E> {
+    result: &mut [MaybeUninit<E>],
+) {
     let domain_size = p.len() * blowup_factor;
     let g = B::get_root_of_unity(log2(domain_size));
-    let mut result = unsafe { uninit_vector(domain_size) };
 
     result
-        .as_mut_slice()
         .par_chunks_mut(p.len())
         .enumerate()
         .for_each(|(i, chunk)| {
             let idx = super::permute_index(blowup_factor, i) as u64;
             let offset = E::from(g.exp(idx.into()) * domain_offset);
-            clone_and_shift(p, chunk, offset);
+            let chunk = clone_and_shift(p, chunk, offset);
             split_radix_fft(chunk, twiddles);
         });
 
-    permute(&mut result);
-    result
+    // SAFETY: all chunks of the result were written by clone_and_shift()
+    permute(unsafe { assume_init_slice(result) });
 }
 
 // POLYNOMIAL INTERPOLATION
@@ -83,8 +83,8 @@ where
         .for_each(|(i, batch)| {
             let mut offset = domain_offset.exp(((i * batch_size) as u64).into()) * inv_len;
             for coeff in batch.iter_mut() {
-                *coeff = *coeff * offset;
-                offset = offset * domain_offset;
+                *coeff *= offset;
+                offset *= domain_offset;
             }
         });
 }
@@ -141,7 +141,7 @@ pub(super) fn split_radix_fft<B: StarkField, E: FieldElement<BaseField = B>>(
     // apply inner FFTs
     values
         .par_chunks_mut(outer_len)
-        .for_each(|row| super::serial::fft_in_place(row, &twiddles, stretch, stretch, 0));
+        .for_each(|row| super::serial::fft_in_place(row, twiddles, stretch, stretch, 0));
 
     // transpose inner x inner x stretch square matrix
     transpose_square_stretch(values, inner_len, stretch);
@@ -156,11 +156,11 @@ pub(super) fn split_radix_fft<B: StarkField, E: FieldElement<BaseField = B>>(
                 let inner_twiddle = g.exp((i as u32).into());
                 let mut outer_twiddle = inner_twiddle;
                 for element in row.iter_mut().skip(1) {
-                    *element = *element * outer_twiddle;
-                    outer_twiddle = outer_twiddle * inner_twiddle;
+                    *element *= outer_twiddle;
+                    outer_twiddle *= inner_twiddle;
                 }
             }
-            super::serial::fft_in_place(row, &twiddles, 1, 1, 0)
+            super::serial::fft_in_place(row, twiddles, 1, 1, 0)
         });
 }
 
@@ -178,7 +178,7 @@ fn transpose_square_stretch<T>(matrix: &mut [T], size: usize, stretch: usize) {
 
 fn transpose_square_1<T>(matrix: &mut [T], size: usize) {
     debug_assert_eq!(matrix.len(), size * size);
-    if size % 2 != 0 {
+    if !size.is_multiple_of(2) {
         unimplemented!("odd sizes are not supported");
     }
 
@@ -214,7 +214,13 @@ fn transpose_square_2<T>(matrix: &mut [T], size: usize) {
 // HELPER FUNCTIONS
 // ================================================================================================
 
-fn clone_and_shift<E: FieldElement>(source: &[E], destination: &mut [E], offset: E) {
+/// Writes `source[i] * offset^i` into `destination[i]` for all `i`, and returns the written
+/// values; `source` and `destination` are assumed to have the same length.
+fn clone_and_shift<'a, E: FieldElement>(
+    source: &[E],
+    destination: &'a mut [MaybeUninit<E>],
+    offset: E,
+) -> &'a mut [E] {
     let batch_size = source.len() / rayon::current_num_threads().next_power_of_two();
     source
         .par_chunks(batch_size)
@@ -223,8 +229,11 @@ fn clone_and_shift<E: FieldElement>(source: &[E], destination: &mut [E], offset:
         .for_each(|(i, (source, destination))| {
             let mut factor = offset.exp(((i * batch_size) as u64).into());
             for (s, d) in source.iter().zip(destination.iter_mut()) {
-                *d = *s * factor;
-                factor = factor * offset;
+                d.write(*s * factor);
+                factor *= offset;
             }
         });
+    // SAFETY: source and destination have the same length, and thus, all values of destination
+    // were written above
+    unsafe { assume_init_slice(destination) }
 }
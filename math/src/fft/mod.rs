@@ -21,7 +21,8 @@ mod serial;
 #[cfg(feature = "concurrent")]
 mod concurrent;
 
-use utils::collections::Vec;
+use core::mem::MaybeUninit;
+use utils::{assume_init_buffer, assume_init_slice, collections::Vec, uninit_buffer};
 
 #[cfg(test)]
 mod tests;
@@ -172,6 +173,42 @@ pub fn evaluate_poly_with_offset<B, E>(
     domain_offset: B,
     blowup_factor: usize,
 ) -> Vec<E>
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+{
+    let mut result = uninit_buffer(p.len() * blowup_factor);
+    evaluate_poly_with_offset_into(p, twiddles, domain_offset, blowup_factor, &mut result);
+    // SAFETY: all values of the result have been written by evaluate_poly_with_offset_into()
+    unsafe { assume_init_buffer(result) }
+}
+
+/// Evaluates a polynomial on all points of the specified (shifted) domain using the FFT algorithm,
+/// and writes the results into the provided slice.
+///
+/// This function is identical to [evaluate_poly_with_offset()], except that the evaluations are
+/// written into `result` rather than into a newly allocated vector; thus, the memory for the
+/// evaluations can be allocated (or reused) by the caller. Values of `result` do not need to be
+/// initialized, and once the function returns, all of them are initialized; the initialized
+/// values are returned as a slice.
+///
+/// When `concurrent` feature is enabled, the evaluation is done in multiple threads.
+///
+/// # Panics
+/// Panics if:
+/// * Length of `p` is not a power of two.
+/// * `blowup_factor` is not a power of two.
+/// * Length of `twiddles` is not `p.len()` / 2.
+/// * Field specified by `B` does not contain a multiplicative subgroup of size `p.len()`.
+/// * `domain_offset` is ZERO.
+/// * Length of `result` is not `p.len()` * `blowup_factor`.
+pub fn evaluate_poly_with_offset_into<'a, B, E>(
+    p: &[E],
+    twiddles: &[B],
+    domain_offset: B,
+    blowup_factor: usize,
+    result: &'a mut [MaybeUninit<E>],
+) -> &'a mut [E]
 where
     B: StarkField,
     E: FieldElement<BaseField = B>,
@@ -197,24 +234,25 @@ where
         p.len() * blowup_factor
     );
     assert_ne!(domain_offset, B::ZERO, "domain offset cannot be zero");
-
-    // assign a dummy value here to make the compiler happy
-    #[allow(unused_assignments)]
-    let mut result = Vec::new();
+    assert_eq!(
+        p.len() * blowup_factor,
+        result.len(),
+        "invalid result length: expected {} but was {}",
+        p.len() * blowup_factor,
+        result.len()
+    );
 
     // when `concurrent` feature is enabled, run the concurrent version of the function; unless
     // the polynomial is small, then don't bother with the concurrent version
     if cfg!(feature = "concurrent") && p.len() >= MIN_CONCURRENT_SIZE {
         #[cfg(feature = "concurrent")]
-        {
-            result =
-                concurrent::evaluate_poly_with_offset(p, twiddles, domain_offset, blowup_factor);
-        }
+        concurrent::evaluate_poly_with_offset(p, twiddles, domain_offset, blowup_factor, result);
     } else {
-        result = serial::evaluate_poly_with_offset(p, twiddles, domain_offset, blowup_factor);
+        serial::evaluate_poly_with_offset(p, twiddles, domain_offset, blowup_factor, result);
     }
 
-    result
+    // SAFETY: both versions of the function write all values of the result
+    unsafe { assume_init_slice(result) }
 }
 
 // POLYNOMIAL INTERPOLATION
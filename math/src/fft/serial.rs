@@ -7,7 +7,8 @@ use crate::{
     field::{FieldElement, StarkField},
     utils::log2,
 };
-use utils::{collections::Vec, uninit_vector};
+use core::mem::MaybeUninit;
+use utils::assume_init_slice;
 
 // CONSTANTS
 // ================================================================================================
@@ -28,23 +29,22 @@ where
 }
 
 /// Evaluates polynomial `p` over the domain of length `p.len()` * `blowup_factor` shifted by
-/// `domain_offset` in the field specified `B` using the FFT algorithm and returns the result.
+/// `domain_offset` in the field specified `B` using the FFT algorithm and writes the result
+/// into `result`.
 pub fn evaluate_poly_with_offset<B, E>(
     p: &[E],
     twiddles: &[B],
     domain_offset: B,
     blowup_factor: usize,
-) -> Vec<E>
-where
+    result: &mut [MaybeUninit<E>],
+) where
     B: StarkField,
     E: FieldElement<BaseField = B>,
 {
     let domain_size = p.len() * blowup_factor;
     let g = B::get_root_of_unity(log2(domain_size));
-    let mut result = unsafe { uninit_vector(domain_size) };
 
     result
-        .as_mut_slice()
         .chunks_mut(p.len())
         .enumerate()
         .for_each(|(i, chunk)| {
@@ -52,14 +52,16 @@ where
             let offset = E::from(g.exp(idx.into()) * domain_offset);
             let mut factor = E::ONE;
             for (d, c) in chunk.iter_mut().zip(p.iter()) {
-                *d = *c * factor;
+                d.write(*c * factor);
                 factor *= offset;
             }
+            // SAFETY: the chunk has the same length as p, and thus, all its values were written
+            let chunk = unsafe { assume_init_slice(chunk) };
             fft_in_place(chunk, twiddles, 1, 1, 0);
         });
 
-    permute(&mut result);
-    result
+    // SAFETY: all chunks of the result were written above
+    permute(unsafe { assume_init_slice(result) });
 }
 
 // POLYNOMIAL INTERPOLATION
@@ -13,7 +13,7 @@ use core::{
 use utils::{
     collections::Vec,
     string::{String, ToString},
-    AsBytes, ByteReader, ByteWriter, Deserializable, DeserializationError, Randomizable,
+    AsBytes, ByteReader, ByteWriter, Deserializable, DeserializationError, FromBytes, Randomizable,
     Serializable,
 };
 
@@ -34,7 +34,7 @@ impl<B: StarkField> QuadExtensionA<B> {
     /// of the source vector.
     fn base_to_quad_vector(source: Vec<B>) -> Vec<Self> {
         debug_assert!(
-            source.len() % 2 == 0,
+            source.len().is_multiple_of(2),
             "source vector length must be divisible by two, but was {}",
             source.len()
         );
@@ -79,7 +79,7 @@ impl<B: StarkField> FieldElement for QuadExtensionA<B> {
     }
 
     unsafe fn bytes_as_elements(bytes: &[u8]) -> Result<&[Self], DeserializationError> {
-        if bytes.len() % Self::ELEMENT_BYTES != 0 {
+        if !bytes.len().is_multiple_of(Self::ELEMENT_BYTES) {
             return Err(DeserializationError::InvalidValue(format!(
                 "number of bytes ({}) does not divide into whole number of field elements",
                 bytes.len(),
@@ -90,7 +90,7 @@ impl<B: StarkField> FieldElement for QuadExtensionA<B> {
         let len = bytes.len() / Self::ELEMENT_BYTES;
 
         // make sure the bytes are aligned on the boundary consistent with base element alignment
-        if (p as usize) % Self::BaseField::ELEMENT_BYTES != 0 {
+        if !(p as usize).is_multiple_of(Self::BaseField::ELEMENT_BYTES) {
             return Err(DeserializationError::InvalidValue(
                 "slice memory alignment is not valid for this field element type".to_string(),
             ));
@@ -237,7 +237,7 @@ impl<B: StarkField> From<u8> for QuadExtensionA<B> {
     }
 }
 
-impl<'a, B: StarkField> TryFrom<&'a [u8]> for QuadExtensionA<B> {
+impl<B: StarkField> TryFrom<&[u8]> for QuadExtensionA<B> {
     type Error = String;
 
     /// Converts a slice of bytes into a field element; returns error if the value encoded in bytes
@@ -266,12 +266,29 @@ impl<'a, B: StarkField> TryFrom<&'a [u8]> for QuadExtensionA<B> {
 
 impl<B: StarkField> AsBytes for QuadExtensionA<B> {
     fn as_bytes(&self) -> &[u8] {
-        // TODO: take endianness into account
         let self_ptr: *const Self = self;
         unsafe { slice::from_raw_parts(self_ptr as *const u8, Self::ELEMENT_BYTES) }
     }
 }
 
+impl<B: StarkField> FromBytes for QuadExtensionA<B> {
+    fn read_slice_from_bytes(bytes: &[u8]) -> Result<Vec<Self>, DeserializationError> {
+        if !bytes.len().is_multiple_of(Self::ELEMENT_BYTES) {
+            return Err(DeserializationError::InvalidValue(format!(
+                "number of bytes ({}) does not divide into whole number of field elements",
+                bytes.len(),
+            )));
+        }
+
+        // an extension element is represented by its two base elements one after another
+        let elements = B::read_slice_from_bytes(bytes)?;
+        Ok(elements
+            .chunks_exact(2)
+            .map(|chunk| Self(chunk[0], chunk[1]))
+            .collect())
+    }
+}
+
 // SERIALIZATION / DESERIALIZATION
 // ------------------------------------------------------------------------------------------------
 
@@ -295,7 +312,7 @@ impl<B: StarkField> Deserializable for QuadExtensionA<B> {
 
 #[cfg(test)]
 mod tests {
-    use super::{DeserializationError, FieldElement, QuadExtensionA, Vec};
+    use super::{DeserializationError, FieldElement, FromBytes, QuadExtensionA, Vec};
     use crate::field::f128::BaseElement;
     use rand_utils::{rand_value, rand_vector};
 
@@ -365,9 +382,9 @@ mod tests {
 
         // test random values
         let x: Vec<QuadExtensionA<BaseElement>> = rand_vector(1000);
-        for i in 0..x.len() {
-            let y = QuadExtensionA::<BaseElement>::inv(x[i]);
-            assert_eq!(QuadExtensionA::<BaseElement>::ONE, x[i] * y);
+        for &x in x.iter() {
+            let y = QuadExtensionA::<BaseElement>::inv(x);
+            assert_eq!(QuadExtensionA::<BaseElement>::ONE, x * y);
         }
     }
 
@@ -437,6 +454,22 @@ mod tests {
         assert!(matches!(result, Err(DeserializationError::InvalidValue(_))));
     }
 
+    #[test]
+    fn read_slice_from_bytes() {
+        let elements = vec![
+            QuadExtensionA(BaseElement::new(1), BaseElement::new(2)),
+            QuadExtensionA(BaseElement::new(3), BaseElement::new(4)),
+        ];
+        let bytes = QuadExtensionA::<BaseElement>::elements_as_bytes(&elements);
+
+        let result = QuadExtensionA::<BaseElement>::read_slice_from_bytes(bytes).unwrap();
+        assert_eq!(elements, result);
+
+        // bytes must divide into whole extension elements
+        let result = QuadExtensionA::<BaseElement>::read_slice_from_bytes(&bytes[..48]);
+        assert!(matches!(result, Err(DeserializationError::InvalidValue(_))));
+    }
+
     // UTILITIES
     // --------------------------------------------------------------------------------------------
 
@@ -24,7 +24,7 @@ use core::{
 use utils::{
     collections::Vec,
     string::{String, ToString},
-    AsBytes, ByteReader, ByteWriter, Deserializable, DeserializationError, Randomizable,
+    AsBytes, ByteReader, ByteWriter, Deserializable, DeserializationError, FromBytes, Randomizable,
     Serializable,
 };
 
@@ -82,14 +82,13 @@ impl FieldElement for BaseElement {
     }
 
     fn elements_as_bytes(elements: &[Self]) -> &[u8] {
-        // TODO: take endianness into account
         let p = elements.as_ptr();
         let len = elements.len() * Self::ELEMENT_BYTES;
         unsafe { slice::from_raw_parts(p as *const u8, len) }
     }
 
     unsafe fn bytes_as_elements(bytes: &[u8]) -> Result<&[Self], DeserializationError> {
-        if bytes.len() % Self::ELEMENT_BYTES != 0 {
+        if !bytes.len().is_multiple_of(Self::ELEMENT_BYTES) {
             return Err(DeserializationError::InvalidValue(format!(
                 "number of bytes ({}) does not divide into whole number of field elements",
                 bytes.len(),
@@ -99,7 +98,7 @@ impl FieldElement for BaseElement {
         let p = bytes.as_ptr();
         let len = bytes.len() / Self::ELEMENT_BYTES;
 
-        if (p as usize) % mem::align_of::<u128>() != 0 {
+        if !(p as usize).is_multiple_of(mem::align_of::<u128>()) {
             return Err(DeserializationError::InvalidValue(
                 "slice memory alignment is not valid for this field element type".to_string(),
             ));
@@ -292,7 +291,7 @@ impl From<[u8; 16]> for BaseElement {
     }
 }
 
-impl<'a> TryFrom<&'a [u8]> for BaseElement {
+impl TryFrom<&[u8]> for BaseElement {
     type Error = String;
 
     /// Converts a slice of bytes into a field element; returns error if the value encoded in bytes
@@ -315,12 +314,37 @@ impl<'a> TryFrom<&'a [u8]> for BaseElement {
 
 impl AsBytes for BaseElement {
     fn as_bytes(&self) -> &[u8] {
-        // TODO: take endianness into account
         let self_ptr: *const BaseElement = self;
         unsafe { slice::from_raw_parts(self_ptr as *const u8, BaseElement::ELEMENT_BYTES) }
     }
 }
 
+impl FromBytes for BaseElement {
+    fn read_slice_from_bytes(bytes: &[u8]) -> Result<Vec<Self>, DeserializationError> {
+        if !bytes.len().is_multiple_of(ELEMENT_BYTES) {
+            return Err(DeserializationError::InvalidValue(format!(
+                "number of bytes ({}) does not divide into whole number of field elements",
+                bytes.len(),
+            )));
+        }
+
+        bytes
+            .chunks_exact(ELEMENT_BYTES)
+            .map(|chunk| {
+                let value = u128::from_ne_bytes(chunk.try_into().expect("invalid chunk length"));
+                if value >= M {
+                    return Err(DeserializationError::InvalidValue(format!(
+                        "invalid field element: value {} is greater than or equal to the field \
+                        modulus",
+                        value
+                    )));
+                }
+                Ok(BaseElement(value))
+            })
+            .collect()
+    }
+}
+
 // SERIALIZATION / DESERIALIZATION
 // ------------------------------------------------------------------------------------------------
 
@@ -74,7 +74,7 @@ fn mul() {
     assert_eq!(BaseElement::from(m - 2), t * BaseElement::from(2u8));
     assert_eq!(BaseElement::from(m - 4), t * BaseElement::from(4u8));
 
-    let t = (m + 1) / 2;
+    let t = m.div_ceil(2);
     assert_eq!(
         BaseElement::ONE,
         BaseElement::from(t) * BaseElement::from(2u8)
@@ -104,9 +104,9 @@ fn inv() {
 
     // test random values
     let x: Vec<BaseElement> = rand_vector(1000);
-    for i in 0..x.len() {
-        let y = BaseElement::inv(x[i]);
-        assert_eq!(BaseElement::ONE, x[i] * y);
+    for &x in x.iter() {
+        let y = BaseElement::inv(x);
+        assert_eq!(BaseElement::ONE, x * y);
     }
 }
 
@@ -188,6 +188,26 @@ fn bytes_as_elements() {
     assert!(matches!(result, Err(DeserializationError::InvalidValue(_))));
 }
 
+#[test]
+fn read_slice_from_bytes() {
+    let elements: Vec<BaseElement> = rand_vector(16);
+    let bytes = BaseElement::elements_as_bytes(&elements);
+
+    // bytes are read back regardless of their alignment
+    let mut shifted = vec![0u8; bytes.len() + 1];
+    shifted[1..].copy_from_slice(bytes);
+    let result = BaseElement::read_slice_from_bytes(&shifted[1..]).unwrap();
+    assert_eq!(elements, result);
+
+    let result = BaseElement::read_slice_from_bytes(&bytes[..65]);
+    assert!(matches!(result, Err(DeserializationError::InvalidValue(_))));
+
+    // values greater than or equal to the modulus are not valid
+    let bytes = M.to_ne_bytes();
+    let result = BaseElement::read_slice_from_bytes(&bytes);
+    assert!(matches!(result, Err(DeserializationError::InvalidValue(_))));
+}
+
 #[test]
 fn read_elements_from() {
     let bytes: Vec<u8> = vec![
@@ -207,24 +227,21 @@ fn read_elements_from() {
     let result = BaseElement::read_batch_from(&mut reader, 4);
     assert!(result.is_ok());
     assert_eq!(expected, result.unwrap());
-    assert_eq!(false, reader.has_more_bytes());
+    assert!(!reader.has_more_bytes());
 
     // partial number of elements
     let mut reader = SliceReader::new(&bytes[..65]);
     let result = BaseElement::read_batch_from(&mut reader, 4);
     assert!(result.is_ok());
     assert_eq!(expected, result.unwrap());
-    assert_eq!(true, reader.has_more_bytes());
+    assert!(reader.has_more_bytes());
 
     // invalid element
     let mut reader = SliceReader::new(&bytes[16..]);
     let result = BaseElement::read_batch_from(&mut reader, 4);
     assert!(result.is_err());
-    match result {
-        Err(err) => {
-            assert!(matches!(err, DeserializationError::InvalidValue(_)));
-        }
-        _ => (),
+    if let Err(err) = result {
+        assert!(matches!(err, DeserializationError::InvalidValue(_)));
     }
 }
 
@@ -252,6 +269,6 @@ impl BaseElement {
         let bytes = value.to_bytes_le();
         let mut buffer = [0u8; 16];
         buffer[0..bytes.len()].copy_from_slice(&bytes);
-        BaseElement::try_from(buffer).unwrap()
+        BaseElement::from(buffer)
     }
 }
@@ -3,7 +3,9 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{AsBytes, BaseElement, DeserializationError, FieldElement, Serializable, StarkField};
+use super::{
+    AsBytes, BaseElement, DeserializationError, FieldElement, FromBytes, Serializable, StarkField,
+};
 use core::convert::TryFrom;
 use num_bigint::BigUint;
 use proptest::prelude::*;
@@ -67,7 +69,7 @@ fn mul() {
     assert_eq!(BaseElement::from(m - 2), t * BaseElement::from(2u8));
     assert_eq!(BaseElement::from(m - 4), t * BaseElement::from(4u8));
 
-    let t = (m + 1) / 2;
+    let t = m.div_ceil(2);
     assert_eq!(
         BaseElement::ONE,
         BaseElement::from(t) * BaseElement::from(2u8)
@@ -208,6 +210,26 @@ fn bytes_as_elements() {
     assert!(matches!(result, Err(DeserializationError::InvalidValue(_))));
 }
 
+#[test]
+fn read_slice_from_bytes() {
+    let elements = rand_utils::rand_vector::<BaseElement>(16);
+    let bytes = BaseElement::elements_as_bytes(&elements);
+
+    // bytes are read back regardless of their alignment
+    let mut shifted = vec![0u8; bytes.len() + 1];
+    shifted[1..].copy_from_slice(bytes);
+    let result = BaseElement::read_slice_from_bytes(&shifted[1..]).unwrap();
+    assert_eq!(elements, result);
+
+    let result = BaseElement::read_slice_from_bytes(&bytes[..33]);
+    assert!(matches!(result, Err(DeserializationError::InvalidValue(_))));
+
+    // values outside of [0, 2M) range are not valid internal representations
+    let bytes = (2 * super::M).to_ne_bytes();
+    let result = BaseElement::read_slice_from_bytes(&bytes);
+    assert!(matches!(result, Err(DeserializationError::InvalidValue(_))));
+}
+
 // INITIALIZATION
 // ------------------------------------------------------------------------------------------------
 
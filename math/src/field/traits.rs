@@ -12,7 +12,8 @@ use core::{
     },
 };
 use utils::{
-    collections::Vec, AsBytes, Deserializable, DeserializationError, Randomizable, Serializable,
+    collections::Vec, AsBytes, Deserializable, DeserializationError, FromBytes, Randomizable,
+    Serializable,
 };
 
 // FIELD ELEMENT
@@ -54,6 +55,7 @@ pub trait FieldElement:
     + From<u8>
     + for<'a> TryFrom<&'a [u8]>
     + AsBytes
+    + FromBytes
     + Randomizable
     + Serializable
     + Deserializable
@@ -145,7 +147,9 @@ pub trait FieldElement:
     ///
     /// The elements may be in the internal representation rather than in the canonical
     /// representation. This conversion is intended to be zero-copy (i.e. by re-interpreting the
-    /// underlying memory).
+    /// underlying memory), and thus, the bytes are in the native byte order of the target
+    /// platform. The elements can be safely read back from the bytes via
+    /// [FromBytes::read_slice_from_bytes()].
     fn elements_as_bytes(elements: &[Self]) -> &[u8];
 
     /// Converts a list of bytes into a list of field elements.
@@ -67,6 +67,7 @@
 //! * `fft` module:
 //!   - [evaluate_poly()](fft::evaluate_poly())
 //!   - [evaluate_poly_with_offset()](fft::evaluate_poly_with_offset())
+//!   - [evaluate_poly_with_offset_into()](fft::evaluate_poly_with_offset_into())
 //!   - [interpolate_poly()](fft::interpolate_poly())
 //!   - [interpolate_poly_with_offset()][fft::interpolate_poly_with_offset()]
 //!   - [get_twiddles()](fft::get_twiddles())
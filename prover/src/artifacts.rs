@@ -0,0 +1,235 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{DefaultProverChannel, ProverChannel};
+use air::{
+    proof::{Context, Queries, StarkProof},
+    Air, ConstraintCompositionCoefficients, DeepCompositionCoefficients, EvaluationFrame,
+};
+use crypto::{ElementHasher, Hasher, Transcript};
+use fri::{self, FriProof};
+use math::FieldElement;
+use utils::{collections::Vec, Serializable};
+
+// PROVER ARTIFACTS
+// ================================================================================================
+/// Intermediate values computed and drawn by the prover during proof generation.
+///
+/// Artifacts are returned alongside the proof by
+/// [prove_with_artifacts()](crate::prove_with_artifacts) function. All of these values can be
+/// derived from the proof itself, but doing so requires parsing the proof and replaying the
+/// transcript of the protocol; artifacts make them directly available to tools which consume
+/// the proof in some other way (e.g., to builders of witnesses for recursive verification
+/// circuits).
+///
+/// Commitments are serialized digests of the hash function specified by the proof options, and
+/// field elements are elements of the field `E` in which the protocol was executed.
+#[derive(Debug, Clone)]
+pub struct ProverArtifacts<E: FieldElement> {
+    /// Roots of the Merkle trees built from the rows of the extended execution trace; one root
+    /// for each trace segment, in the order of the segments.
+    pub trace_roots: Vec<Vec<u8>>,
+    /// Coefficients drawn to build the constraint composition polynomial.
+    pub constraint_composition_coeffs: ConstraintCompositionCoefficients<E>,
+    /// Root of the Merkle tree built from the evaluations of the constraint composition
+    /// polynomial.
+    pub constraint_root: Vec<u8>,
+    /// Out-of-domain point z.
+    pub ood_point: E,
+    /// Evaluations of trace polynomials at z (the current row of the frame) and at z * g (the
+    /// next row of the frame), where g is the generator of the trace domain.
+    pub ood_frame: EvaluationFrame<E>,
+    /// Evaluations of constraint composition polynomial columns at z.
+    pub ood_constraint_evaluations: Vec<E>,
+    /// Coefficients drawn to build the DEEP composition polynomial.
+    pub deep_composition_coeffs: DeepCompositionCoefficients<E>,
+    /// Roots of the Merkle trees built for FRI layers (including the remainder), in the order
+    /// in which the layers were committed to.
+    pub fri_layer_roots: Vec<Vec<u8>>,
+    /// Values of alpha drawn after each of the FRI layers was committed to; alpha drawn after a
+    /// layer is committed to is used to fold this layer into the next one.
+    pub fri_alphas: Vec<E>,
+    /// Positions in the LDE domain at which the proof was queried.
+    pub query_positions: Vec<usize>,
+    /// Transcript of all values absorbed into and drawn from the public coin by the prover;
+    /// for a valid proof, this is the same as the transcript recorded by the verifier.
+    pub transcript: Transcript,
+}
+
+impl<E: FieldElement> ProverArtifacts<E> {
+    /// Returns an empty set of artifacts; the values are filled in as they are sent over or
+    /// drawn from an [ArtifactChannel].
+    fn new(trace_width: usize) -> Self {
+        ProverArtifacts {
+            trace_roots: Vec::new(),
+            constraint_composition_coeffs: ConstraintCompositionCoefficients {
+                transition: Vec::new(),
+                boundary: Vec::new(),
+            },
+            constraint_root: Vec::new(),
+            ood_point: E::ZERO,
+            ood_frame: EvaluationFrame::new(trace_width),
+            ood_constraint_evaluations: Vec::new(),
+            deep_composition_coeffs: DeepCompositionCoefficients {
+                trace: Vec::new(),
+                constraints: Vec::new(),
+                degree: (E::ZERO, E::ZERO),
+                columns: Vec::new(),
+            },
+            fri_layer_roots: Vec::new(),
+            fri_alphas: Vec::new(),
+            query_positions: Vec::new(),
+            transcript: Transcript::default(),
+        }
+    }
+}
+
+// ARTIFACT CHANNEL
+// ================================================================================================
+/// A prover channel which records values sent over and drawn from a [DefaultProverChannel]
+/// into [ProverArtifacts]; the transcript of the protocol is not changed, but it is recorded
+/// into the artifacts as well.
+pub(super) struct ArtifactChannel<'a, A, E, H>
+where
+    A: Air,
+    E: FieldElement<BaseField = A::BaseElement>,
+    H: ElementHasher<BaseField = A::BaseElement>,
+{
+    inner: DefaultProverChannel<A, E, H>,
+    artifacts: &'a mut ProverArtifacts<E>,
+}
+
+impl<'a, A, E, H> ArtifactChannel<'a, A, E, H>
+where
+    A: Air,
+    E: FieldElement<BaseField = A::BaseElement>,
+    H: ElementHasher<BaseField = A::BaseElement>,
+{
+    /// Creates a new channel for the specified `air` and public inputs which records values
+    /// into the specified `artifacts`.
+    pub fn new(air: &A, pub_inputs_bytes: Vec<u8>, artifacts: &'a mut ProverArtifacts<E>) -> Self {
+        ArtifactChannel {
+            inner: DefaultProverChannel::with_transcript(air, pub_inputs_bytes),
+            artifacts,
+        }
+    }
+
+    /// Returns an empty set of artifacts for a proof of the computation described by `air`.
+    pub fn new_artifacts(air: &A) -> ProverArtifacts<E> {
+        ProverArtifacts::new(air.trace_info().width())
+    }
+}
+
+impl<'a, A, E, H> ProverChannel<A, E> for ArtifactChannel<'a, A, E, H>
+where
+    A: Air,
+    E: FieldElement<BaseField = A::BaseElement>,
+    H: ElementHasher<BaseField = A::BaseElement>,
+{
+    fn context(&self) -> &Context {
+        self.inner.context()
+    }
+
+    fn commit_trace(&mut self, trace_root: H::Digest) {
+        self.artifacts.trace_roots.push(trace_root.to_bytes());
+        self.inner.commit_trace(trace_root);
+    }
+
+    fn get_constraint_composition_coeffs(
+        &mut self,
+        air: &A,
+    ) -> ConstraintCompositionCoefficients<E> {
+        let coefficients = self.inner.get_constraint_composition_coeffs(air);
+        self.artifacts.constraint_composition_coeffs = coefficients.clone();
+        coefficients
+    }
+
+    fn commit_constraints(&mut self, constraint_root: H::Digest) {
+        self.artifacts.constraint_root = constraint_root.to_bytes();
+        self.inner.commit_constraints(constraint_root);
+    }
+
+    fn get_ood_point(&mut self) -> E {
+        let z = self.inner.get_ood_point();
+        self.artifacts.ood_point = z;
+        z
+    }
+
+    fn send_ood_evaluation_frame(&mut self, frame: &EvaluationFrame<E>) {
+        self.artifacts.ood_frame = frame.clone();
+        self.inner.send_ood_evaluation_frame(frame);
+    }
+
+    fn send_ood_constraint_evaluations(&mut self, evaluations: &[E]) {
+        self.artifacts.ood_constraint_evaluations = evaluations.to_vec();
+        self.inner.send_ood_constraint_evaluations(evaluations);
+    }
+
+    fn get_deep_composition_coeffs(&mut self, air: &A) -> DeepCompositionCoefficients<E> {
+        let coefficients = self.inner.get_deep_composition_coeffs(air);
+        self.artifacts.deep_composition_coeffs = coefficients.clone();
+        coefficients
+    }
+
+    fn grind_query_seed(&mut self) {
+        self.inner.grind_query_seed();
+    }
+
+    fn get_query_positions(&mut self, air: &A) -> Vec<usize> {
+        let positions = self.inner.get_query_positions(air);
+        self.artifacts.query_positions = positions.clone();
+        positions
+    }
+
+    fn build_proof(
+        self,
+        trace_queries: Vec<Queries>,
+        constraint_queries: Queries,
+        committed_column_queries: Vec<Queries>,
+        fri_proof: FriProof,
+    ) -> StarkProof {
+        if let Some(transcript) = self.inner.transcript() {
+            self.artifacts.transcript = transcript.clone();
+        }
+        self.inner.build_proof(
+            trace_queries,
+            constraint_queries,
+            committed_column_queries,
+            fri_proof,
+        )
+    }
+}
+
+impl<'a, A, E, H> fri::ProverChannel<E> for ArtifactChannel<'a, A, E, H>
+where
+    A: Air,
+    E: FieldElement<BaseField = A::BaseElement>,
+    H: ElementHasher<BaseField = A::BaseElement>,
+{
+    type Hasher = H;
+
+    fn commit_fri_layer(&mut self, layer_root: <H as Hasher>::Digest) {
+        self.artifacts.fri_layer_roots.push(layer_root.to_bytes());
+        self.inner.commit_fri_layer(layer_root);
+    }
+
+    fn draw_fri_alpha(&mut self) -> E {
+        let alpha = self.inner.draw_fri_alpha();
+        self.artifacts.fri_alphas.push(alpha);
+        alpha
+    }
+
+    fn grind_fri_layer(&mut self, grinding_factor: u32) -> u64 {
+        self.inner.grind_fri_layer(grinding_factor)
+    }
+
+    fn commit_fri_ood_evaluations(&mut self, evaluations_hash: <H as Hasher>::Digest) {
+        self.inner.commit_fri_ood_evaluations(evaluations_hash);
+    }
+
+    fn draw_fri_query_indexes(&mut self, num_indexes: usize, domain_size: usize) -> Vec<usize> {
+        self.inner.draw_fri_query_indexes(num_indexes, domain_size)
+    }
+}
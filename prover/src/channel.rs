@@ -8,7 +8,7 @@ use air::{
     Air, ConstraintCompositionCoefficients, DeepCompositionCoefficients, EvaluationFrame,
 };
 use core::marker::PhantomData;
-use crypto::{ElementHasher, RandomCoin};
+use crypto::{ElementHasher, Hasher, RandomCoin, Transcript};
 use fri::{self, FriProof};
 use math::FieldElement;
 use utils::{collections::Vec, Serializable};
@@ -16,28 +16,104 @@ use utils::{collections::Vec, Serializable};
 #[cfg(feature = "concurrent")]
 use utils::iterators::*;
 
-// TYPES AND INTERFACES
+// PROVER CHANNEL TRAIT
 // ================================================================================================
+/// Defines an interface for a channel over which a prover communicates with a verifier.
+///
+/// The prover uses this channel to send commitments and out-of-domain evaluations to the
+/// verifier, and to draw random values (composition coefficients, the out-of-domain point, and
+/// query positions) from the verifier. In the non-interactive version of the protocol, the
+/// random values are drawn pseudo-randomly based on everything the prover has written into the
+/// channel up to this point; thus, the channel defines the transcript of the protocol.
+///
+/// [DefaultProverChannel] implements the transcript expected by the Winterfell verifier. A
+/// custom channel can be used to observe the transcript, or to extend it - e.g., to absorb
+/// commitments of a companion protocol between the stages of proof generation. Proofs generated
+/// with a channel which changes the transcript cannot be verified by the Winterfell verifier
+/// unless the verifier is extended in the same way. A custom channel is supplied to the prover
+/// via [prove_with_channel()](crate::prove_with_channel) function.
+///
+/// The methods of this trait are invoked by the prover in the order in which they are listed
+/// below; FRI layer commitments are sent via the [fri::ProverChannel] super-trait after the
+/// DEEP composition coefficients are drawn and before the query seed is grinded.
+pub trait ProverChannel<A, E>: fri::ProverChannel<E>
+where
+    A: Air,
+    E: FieldElement<BaseField = A::BaseElement>,
+{
+    /// Returns the context of the proof being generated.
+    fn context(&self) -> &Context;
+
+    /// Commits the prover to a segment of the extended execution trace.
+    ///
+    /// This method is invoked once for every trace segment defined by the proof options, in the
+    /// order of the segments.
+    fn commit_trace(&mut self, trace_root: <Self::Hasher as Hasher>::Digest);
+
+    /// Returns a set of coefficients for constructing a constraint composition polynomial drawn
+    /// from the channel.
+    fn get_constraint_composition_coeffs(
+        &mut self,
+        air: &A,
+    ) -> ConstraintCompositionCoefficients<E>;
+
+    /// Commits the prover to the evaluations of the constraint composition polynomial.
+    fn commit_constraints(&mut self, constraint_root: <Self::Hasher as Hasher>::Digest);
+
+    /// Returns an out-of-domain point drawn from the channel.
+    fn get_ood_point(&mut self) -> E;
+
+    /// Sends the out-of-domain evaluation frame of the execution trace to the verifier.
+    fn send_ood_evaluation_frame(&mut self, frame: &EvaluationFrame<E>);
+
+    /// Sends the evaluations of constraint composition polynomial columns at the out-of-domain
+    /// point to the verifier.
+    fn send_ood_constraint_evaluations(&mut self, evaluations: &[E]);
+
+    /// Returns a set of coefficients for constructing a DEEP composition polynomial drawn from
+    /// the channel.
+    fn get_deep_composition_coeffs(&mut self, air: &A) -> DeepCompositionCoefficients<E>;
 
-pub struct ProverChannel<'a, A, E, H>
+    /// Determines a nonce which, when absorbed into the channel, results in a state with the
+    /// number of leading zeros required by the grinding factor of the proof options.
+    fn grind_query_seed(&mut self);
+
+    /// Returns a set of positions in the LDE domain against which the evaluations of trace and
+    /// constraint composition polynomials should be queried.
+    fn get_query_positions(&mut self, air: &A) -> Vec<usize>;
+
+    /// Builds a proof from the previously committed values as well as values passed into
+    /// this method.
+    fn build_proof(
+        self,
+        trace_queries: Vec<Queries>,
+        constraint_queries: Queries,
+        committed_column_queries: Vec<Queries>,
+        fri_proof: FriProof,
+    ) -> StarkProof;
+}
+
+// DEFAULT PROVER CHANNEL
+// ================================================================================================
+/// Provides a default implementation of the [ProverChannel] trait.
+///
+/// This channel implements the transcript expected by the Winterfell verifier.
+pub struct DefaultProverChannel<A, E, H>
 where
     A: Air,
     E: FieldElement<BaseField = A::BaseElement>,
     H: ElementHasher<BaseField = A::BaseElement>,
 {
-    air: &'a A,
     public_coin: RandomCoin<A::BaseElement, H>,
     context: Context,
     commitments: Commitments,
     ood_frame: OodFrame,
     pow_nonce: u64,
+    _air: PhantomData<A>,
     _field_element: PhantomData<E>,
 }
 
-// PROVER CHANNEL IMPLEMENTATION
-// ================================================================================================
-
-impl<'a, A, E, H> ProverChannel<'a, A, E, H>
+impl<A, E, H> DefaultProverChannel<A, E, H>
 where
     A: Air,
     E: FieldElement<BaseField = A::BaseElement>,
@@ -46,7 +122,19 @@ where
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
     /// Creates a new prover channel for the specified `air` and public inputs.
-    pub fn new(air: &'a A, pub_inputs_bytes: Vec<u8>) -> Self {
+    pub fn new(air: &A, pub_inputs_bytes: Vec<u8>) -> Self {
+        Self::build(air, pub_inputs_bytes, false)
+    }
+
+    /// Creates a new prover channel for the specified `air` and public inputs which records all
+    /// values absorbed into and drawn from its public coin into a [Transcript].
+    pub fn with_transcript(air: &A, pub_inputs_bytes: Vec<u8>) -> Self {
+        Self::build(air, pub_inputs_bytes, true)
+    }
+
+    /// Creates a new prover channel, recording the transcript of its public coin if
+    /// `record_transcript` is set to true.
+    fn build(air: &A, pub_inputs_bytes: Vec<u8>, record_transcript: bool) -> Self {
         let context = Context::new::<A::BaseElement>(air.trace_info(), air.options().clone());
 
         // build a seed for the public coin; the initial seed is the hash of public inputs and proof
@@ -55,35 +143,105 @@ where
         let mut coin_seed = pub_inputs_bytes;
         context.write_into(&mut coin_seed);
 
-        ProverChannel {
-            air,
-            public_coin: RandomCoin::new(&coin_seed),
+        let public_coin = if record_transcript {
+            RandomCoin::with_transcript(&coin_seed)
+        } else {
+            RandomCoin::new(&coin_seed)
+        };
+
+        DefaultProverChannel {
+            public_coin,
             context,
             commitments: Commitments::default(),
             ood_frame: OodFrame::default(),
             pow_nonce: 0,
+            _air: PhantomData,
             _field_element: PhantomData,
         }
     }
 
-    // COMMITMENT METHODS
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the transcript of the public coin of this channel, or None if the channel was not
+    /// instantiated via [with_transcript()](DefaultProverChannel::with_transcript).
+    pub fn transcript(&self) -> Option<&Transcript> {
+        self.public_coin.transcript()
+    }
+
+    // TRANSCRIPT EXTENSION
     // --------------------------------------------------------------------------------------------
 
-    /// Commits the prover the extended execution trace.
-    pub fn commit_trace(&mut self, trace_root: H::Digest) {
+    /// Reseeds the public coin of this channel with the specified `digest`.
+    ///
+    /// This can be used by custom channels wrapping this channel to absorb additional data
+    /// (e.g., commitments of a companion protocol) into the transcript. The digest is not
+    /// included in the proof; a verifier must absorb the same digest at the same point of the
+    /// protocol to verify the resulting proof.
+    pub fn reseed(&mut self, digest: H::Digest) {
+        self.public_coin.reseed(digest);
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the smallest nonce, which when hashed with the current seed of the public coin
+    /// results in a new seed with the number of leading zeros equal to or greater than
+    /// `grinding_factor`.
+    ///
+    /// Many nonces satisfy this condition; always selecting the smallest one makes the proof
+    /// independent of the number of threads used to search for the nonce.
+    fn find_pow_nonce(&self, grinding_factor: u32) -> u64 {
+        #[cfg(not(feature = "concurrent"))]
+        let nonce = (1..u64::MAX)
+            .find(|&nonce| self.public_coin.check_leading_zeros(nonce) >= grinding_factor)
+            .expect("nonce not found");
+
+        #[cfg(feature = "concurrent")]
+        let nonce = (1..u64::MAX)
+            .into_par_iter()
+            .find_first(|&nonce| self.public_coin.check_leading_zeros(nonce) >= grinding_factor)
+            .expect("nonce not found");
+
+        nonce
+    }
+}
+
+impl<A, E, H> ProverChannel<A, E> for DefaultProverChannel<A, E, H>
+where
+    A: Air,
+    E: FieldElement<BaseField = A::BaseElement>,
+    H: ElementHasher<BaseField = A::BaseElement>,
+{
+    fn context(&self) -> &Context {
+        &self.context
+    }
+
+    fn commit_trace(&mut self, trace_root: H::Digest) {
         self.commitments.add::<H>(&trace_root);
         self.public_coin.reseed(trace_root);
     }
 
-    /// Commits the prover to the evaluations of the constraint composition polynomial.
-    pub fn commit_constraints(&mut self, constraint_root: H::Digest) {
+    fn get_constraint_composition_coeffs(
+        &mut self,
+        air: &A,
+    ) -> ConstraintCompositionCoefficients<E> {
+        air.get_constraint_composition_coefficients(&mut self.public_coin)
+            .expect("failed to draw composition coefficients")
+    }
+
+    fn commit_constraints(&mut self, constraint_root: H::Digest) {
         self.commitments.add::<H>(&constraint_root);
         self.public_coin.reseed(constraint_root);
     }
 
+    fn get_ood_point(&mut self) -> E {
+        self.public_coin.draw().expect("failed to draw OOD point")
+    }
+
     /// Saves the out-of-domain evaluation frame. This also reseeds the public coin with the
     /// hashes of the evaluation frame states.
-    pub fn send_ood_evaluation_frame(&mut self, frame: &EvaluationFrame<E>) {
+    fn send_ood_evaluation_frame(&mut self, frame: &EvaluationFrame<E>) {
         self.ood_frame.set_evaluation_frame(frame);
         self.public_coin.reseed(H::hash_elements(frame.current()));
         self.public_coin.reseed(H::hash_elements(frame.next()));
@@ -91,74 +249,36 @@ where
 
     /// Saves the evaluations of constraint composition polynomial columns at the out-of-domain
     /// point. This also reseeds the public coin wit the hash of the evaluations.
-    pub fn send_ood_constraint_evaluations(&mut self, evaluations: &[E]) {
+    fn send_ood_constraint_evaluations(&mut self, evaluations: &[E]) {
         self.ood_frame.set_constraint_evaluations(evaluations);
         self.public_coin.reseed(H::hash_elements(evaluations));
     }
 
-    // PUBLIC COIN METHODS
-    // --------------------------------------------------------------------------------------------
-
-    /// Returns a set of coefficients for constructing a constraint composition polynomial drawn
-    /// from the public coin.
-    pub fn get_constraint_composition_coeffs(&mut self) -> ConstraintCompositionCoefficients<E> {
-        self.air
-            .get_constraint_composition_coefficients(&mut self.public_coin)
-            .expect("failed to draw composition coefficients")
-    }
-
-    /// Returns an out-of-domain point drawn from the public coin.
-    pub fn get_ood_point(&mut self) -> E {
-        self.public_coin.draw().expect("failed to draw OOD point")
-    }
-
-    /// Returns a set of coefficients for constructing a DEEP composition polynomial drawn from
-    /// the public coin.
-    pub fn get_deep_composition_coeffs(&mut self) -> DeepCompositionCoefficients<E> {
-        self.air
-            .get_deep_composition_coefficients(&mut self.public_coin)
+    fn get_deep_composition_coeffs(&mut self, air: &A) -> DeepCompositionCoefficients<E> {
+        air.get_deep_composition_coefficients(&mut self.public_coin)
             .expect("failed to draw DEEP composition coefficients")
     }
 
-    /// Returns a set of positions in the LDE domain against which the evaluations of trace and
-    /// constraint composition polynomials should be queried.
-    pub fn get_query_positions(&mut self) -> Vec<usize> {
-        let num_queries = self.context.options().num_queries();
-        let lde_domain_size = self.context.lde_domain_size();
-        self.public_coin
-            .draw_integers(num_queries, lde_domain_size)
-            .expect("failed to draw query position")
-    }
-
     /// Determines a nonce, which when hashed with the current seed of the public coin results
     /// in a new seed with the number of leading zeros equal to the grinding_factor specified
     /// in the proof options.
-    pub fn grind_query_seed(&mut self) {
+    fn grind_query_seed(&mut self) {
         let grinding_factor = self.context.options().grinding_factor();
-
-        #[cfg(not(feature = "concurrent"))]
-        let nonce = (1..u64::MAX)
-            .find(|&nonce| self.public_coin.check_leading_zeros(nonce) >= grinding_factor)
-            .expect("nonce not found");
-
-        #[cfg(feature = "concurrent")]
-        let nonce = (1..u64::MAX)
-            .into_par_iter()
-            .find_any(|&nonce| self.public_coin.check_leading_zeros(nonce) >= grinding_factor)
-            .expect("nonce not found");
-
+        let nonce = self.find_pow_nonce(grinding_factor);
         self.pow_nonce = nonce;
         self.public_coin.reseed_with_int(nonce);
     }
 
-    // PROOF BUILDER
-    // --------------------------------------------------------------------------------------------
-    /// Builds a proof from the previously committed values as well as values passed into
-    /// this method.
-    pub fn build_proof(
+    fn get_query_positions(&mut self, air: &A) -> Vec<usize> {
+        air.get_query_positions(&mut self.public_coin)
+            .expect("failed to draw query position")
+    }
+
+    fn build_proof(
         self,
-        trace_queries: Queries,
+        trace_queries: Vec<Queries>,
         constraint_queries: Queries,
+        committed_column_queries: Vec<Queries>,
         fri_proof: FriProof,
     ) -> StarkProof {
         StarkProof {
@@ -167,6 +287,7 @@ where
             ood_frame: self.ood_frame,
             trace_queries,
             constraint_queries,
+            committed_column_queries,
             fri_proof,
             pow_nonce: self.pow_nonce,
         }
@@ -176,7 +297,7 @@ where
 // FRI PROVER CHANNEL IMPLEMENTATION
 // ================================================================================================
 
-impl<'a, A, E, H> fri::ProverChannel<E> for ProverChannel<'a, A, E, H>
+impl<A, E, H> fri::ProverChannel<E> for DefaultProverChannel<A, E, H>
 where
     A: Air,
     E: FieldElement<BaseField = A::BaseElement>,
@@ -194,4 +315,23 @@ where
     fn draw_fri_alpha(&mut self) -> E {
         self.public_coin.draw().expect("failed to draw FRI alpha")
     }
+
+    /// Determines a proof-of-work nonce for the last committed FRI layer and updates the public
+    /// coin with it.
+    fn grind_fri_layer(&mut self, grinding_factor: u32) -> u64 {
+        let nonce = self.find_pow_nonce(grinding_factor);
+        self.public_coin.reseed_with_int(nonce);
+        nonce
+    }
+
+    /// Updates the public coin with a hash of out-of-domain evaluations of a STIR round.
+    fn commit_fri_ood_evaluations(&mut self, evaluations_hash: H::Digest) {
+        self.public_coin.reseed(evaluations_hash);
+    }
+
+    /// Returns query indexes for a STIR round drawn from the public coin.
+    fn draw_fri_query_indexes(&mut self, num_indexes: usize, domain_size: usize) -> Vec<usize> {
+        self.public_coin
+            .draw_integers_with_replacement(num_indexes, domain_size)
+    }
 }
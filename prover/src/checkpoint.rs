@@ -0,0 +1,518 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    build_trace_polys,
+    constraints::CompositionPoly,
+    distributed::{merge_row_hashes, TraceCommitmentJob},
+    trace::{Column, TracePolyTable},
+    ProverError, ProvingMode, Trace,
+};
+use air::{proof::Context, HashFunction, ProofOptions};
+use crypto::{
+    hashers::{Blake3_192, Blake3_256, Sha3_256},
+    Hasher, MerkleTree,
+};
+use math::{FieldElement, StarkField};
+use utils::{
+    collections::Vec, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+    SliceReader,
+};
+
+#[cfg(feature = "mmap")]
+use super::trace::MappedColumn;
+
+// CHECKPOINT STAGE
+// ================================================================================================
+/// Defines the proof generation stage after which a [ProverCheckpoint] was created.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckpointStage {
+    /// The execution trace has been interpolated into trace polynomials, and the prover has
+    /// committed to the extended execution trace.
+    TraceCommitted = 1,
+    /// In addition to the above, constraints have been evaluated and merged into the constraint
+    /// composition polynomial, and the prover has committed to the evaluations of this
+    /// polynomial.
+    ConstraintsCommitted = 2,
+}
+
+// PROVER CHECKPOINT
+// ================================================================================================
+/// Intermediate state of the prover saved after one of the expensive proof generation stages.
+///
+/// Checkpoints are created by [prove_with_checkpoints()](crate::prove_with_checkpoints) function
+/// and can be written to disk via [to_bytes()](ProverCheckpoint::to_bytes) method. If proof
+/// generation is interrupted, it can be resumed from the last checkpoint by passing the
+/// checkpoint back into `prove_with_checkpoints()` function; the resumed prover skips all stages
+/// completed before the checkpoint was created, and generates exactly the same proof as an
+/// uninterrupted prover.
+///
+/// A checkpoint contains:
+/// * Coefficients of trace polynomials, and hashes of the rows of the extended execution trace
+///   (leaves of the Merkle trees of all trace segments).
+/// * For [CheckpointStage::ConstraintsCommitted] stage, also coefficients of constraint
+///   composition polynomial columns, and leaves of the constraint Merkle tree.
+///
+/// Thus, a checkpoint is somewhat larger than the execution trace itself. Evaluations of trace
+/// and composition polynomials over the LDE domain are not saved; these are recomputed from the
+/// polynomials when proof generation is resumed. FRI layers are not saved either.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProverCheckpoint {
+    context: Context,
+    stage: CheckpointStage,
+    pub_inputs: Vec<u8>,
+    trace_polys: Vec<u8>,
+    trace_leaves: Vec<u8>,
+    constraint_polys: Vec<u8>,
+    constraint_leaves: Vec<u8>,
+}
+
+impl ProverCheckpoint {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new checkpoint created after the prover has committed to the extended
+    /// execution trace.
+    pub(super) fn new<B: StarkField, H: Hasher>(
+        context: Context,
+        pub_inputs: Vec<u8>,
+        trace_polys: &TracePolyTable<B>,
+        trace_trees: &[MerkleTree<H>],
+    ) -> Self {
+        let mut leaf_bytes = Vec::new();
+        for trace_tree in trace_trees.iter() {
+            H::Digest::write_batch_into(trace_tree.leaves(), &mut leaf_bytes);
+        }
+
+        Self::from_trace_commitment(
+            context,
+            pub_inputs,
+            trace_polys.iter().map(|poly| &poly[..]),
+            leaf_bytes,
+        )
+    }
+
+    /// Returns a new checkpoint created after the prover has committed to the extended
+    /// execution trace; `trace_leaves` must contain serialized leaves of the Merkle trees of all
+    /// trace segments, one tree after another.
+    pub(super) fn from_trace_commitment<'a, B: StarkField + 'a>(
+        context: Context,
+        pub_inputs: Vec<u8>,
+        trace_polys: impl Iterator<Item = &'a [B]>,
+        trace_leaves: Vec<u8>,
+    ) -> Self {
+        let mut poly_bytes = Vec::new();
+        for poly in trace_polys {
+            B::write_batch_into(poly, &mut poly_bytes);
+        }
+
+        Self::from_serialized_trace(context, pub_inputs, poly_bytes, trace_leaves)
+    }
+
+    /// Returns a new checkpoint created after the prover has committed to the extended
+    /// execution trace; `trace_polys` must contain serialized coefficients of trace polynomials,
+    /// and `trace_leaves` must contain serialized leaves of the Merkle trees of all trace
+    /// segments, one tree after another.
+    pub(super) fn from_serialized_trace(
+        context: Context,
+        pub_inputs: Vec<u8>,
+        trace_polys: Vec<u8>,
+        trace_leaves: Vec<u8>,
+    ) -> Self {
+        ProverCheckpoint {
+            context,
+            stage: CheckpointStage::TraceCommitted,
+            pub_inputs,
+            trace_polys,
+            trace_leaves,
+            constraint_polys: Vec::new(),
+            constraint_leaves: Vec::new(),
+        }
+    }
+
+    /// Saves the constraint composition polynomial and the constraint commitment into this
+    /// checkpoint, and advances the checkpoint to [CheckpointStage::ConstraintsCommitted] stage.
+    pub(super) fn add_constraints<B, E, H>(
+        &mut self,
+        composition_poly: &CompositionPoly<B, E>,
+        constraint_tree: &MerkleTree<H>,
+    ) where
+        B: StarkField,
+        E: FieldElement<BaseField = B>,
+        H: Hasher,
+    {
+        let mut poly_bytes = Vec::new();
+        for column in composition_poly.columns() {
+            E::write_batch_into(column, &mut poly_bytes);
+        }
+
+        let mut leaf_bytes = Vec::new();
+        H::Digest::write_batch_into(constraint_tree.leaves(), &mut leaf_bytes);
+
+        self.stage = CheckpointStage::ConstraintsCommitted;
+        self.constraint_polys = poly_bytes;
+        self.constraint_leaves = leaf_bytes;
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the stage after which this checkpoint was created.
+    pub fn stage(&self) -> CheckpointStage {
+        self.stage
+    }
+
+    /// Returns the context of the proof being generated when this checkpoint was created.
+    pub fn context(&self) -> &Context {
+        &self.context
+    }
+
+    // STATE READER
+    // --------------------------------------------------------------------------------------------
+    /// Reads the prover state saved in this checkpoint.
+    ///
+    /// Trace polynomials are placed into memory-mapped columns if `mode` is
+    /// `ProvingMode::MemoryMapped`, and onto the heap otherwise.
+    ///
+    /// # Errors
+    /// Returns an error if this checkpoint was created for a proof with a different `context`
+    /// or different public inputs, or if the state saved in this checkpoint is malformed.
+    pub(super) fn read_state<B, E, H>(
+        self,
+        context: &Context,
+        pub_inputs: &[u8],
+        mode: ProvingMode,
+    ) -> Result<CheckpointState<B, E, H>, ProverError>
+    where
+        B: StarkField,
+        E: FieldElement<BaseField = B>,
+        H: Hasher,
+    {
+        if &self.context != context || self.pub_inputs != pub_inputs {
+            return Err(ProverError::InvalidCheckpoint);
+        }
+
+        let trace_length = context.trace_length();
+        let lde_domain_size = context.lde_domain_size();
+
+        // read trace polynomials one at a time
+        let mut reader = SliceReader::new(&self.trace_polys);
+        let mut columns = Vec::with_capacity(context.trace_width());
+        for _ in 0..context.trace_width() {
+            let poly = read_elements::<_, B>(&mut reader, trace_length)?;
+            columns.push(build_column(poly, mode));
+        }
+        ensure_consumed(&reader)?;
+        let num_segments = context
+            .options()
+            .trace_segments(context.trace_width())
+            .len();
+        let trace_trees = read_trees::<H>(&self.trace_leaves, num_segments, lde_domain_size)?;
+
+        // read composition polynomial columns; the number of columns is implied by the number
+        // of saved coefficients
+        let constraints = match self.stage {
+            CheckpointStage::TraceCommitted => None,
+            CheckpointStage::ConstraintsCommitted => {
+                let column_bytes = trace_length * E::ELEMENT_BYTES;
+                if self.constraint_polys.is_empty()
+                    || !self.constraint_polys.len().is_multiple_of(column_bytes)
+                {
+                    return Err(ProverError::InvalidCheckpoint);
+                }
+                let mut reader = SliceReader::new(&self.constraint_polys);
+                let mut columns = Vec::new();
+                for _ in 0..self.constraint_polys.len() / column_bytes {
+                    columns.push(read_elements::<_, E>(&mut reader, trace_length)?);
+                }
+                let composition_poly = CompositionPoly::from_columns(columns);
+                let constraint_tree =
+                    read_trees::<H>(&self.constraint_leaves, 1, lde_domain_size)?.remove(0);
+                Some((composition_poly, constraint_tree))
+            }
+        };
+
+        Ok(CheckpointState {
+            trace_polys: TracePolyTable::new(columns),
+            trace_trees,
+            constraints,
+        })
+    }
+
+    // SERIALIZATION / DESERIALIZATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Serializes this checkpoint into a vector of bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut result = Vec::new();
+        self.context.write_into(&mut result);
+        result.write_u8(self.stage as u8);
+        write_bytes(&mut result, &self.pub_inputs);
+        write_bytes(&mut result, &self.trace_polys);
+        write_bytes(&mut result, &self.trace_leaves);
+        write_bytes(&mut result, &self.constraint_polys);
+        write_bytes(&mut result, &self.constraint_leaves);
+        result
+    }
+
+    /// Returns a checkpoint read from the specified `source`.
+    ///
+    /// # Errors
+    /// Returns an error if a valid checkpoint could not be read from the specified `source`.
+    pub fn from_bytes(source: &[u8]) -> Result<Self, DeserializationError> {
+        let mut source = SliceReader::new(source);
+        let checkpoint = ProverCheckpoint {
+            context: Context::read_from(&mut source)?,
+            stage: match source.read_u8()? {
+                1 => CheckpointStage::TraceCommitted,
+                2 => CheckpointStage::ConstraintsCommitted,
+                value => {
+                    return Err(DeserializationError::InvalidValue(format!(
+                        "value {} cannot be deserialized as CheckpointStage enum",
+                        value
+                    )))
+                }
+            },
+            pub_inputs: read_bytes(&mut source)?,
+            trace_polys: read_bytes(&mut source)?,
+            trace_leaves: read_bytes(&mut source)?,
+            constraint_polys: read_bytes(&mut source)?,
+            constraint_leaves: read_bytes(&mut source)?,
+        };
+        if source.has_more_bytes() {
+            return Err(DeserializationError::UnconsumedBytes);
+        }
+        Ok(checkpoint)
+    }
+}
+
+// TRACE COMMITMENT
+// ================================================================================================
+/// Commitment to the extended execution trace which can be reused for generating proofs later.
+///
+/// A trace commitment is created via [TraceCommitment::new()] function, which interpolates the
+/// execution trace into trace polynomials, extends them over the LDE domain, and builds a Merkle
+/// tree from the rows of each segment of the extended trace. The roots of these trees can be
+/// published right away (e.g., for data availability reasons), and the proof can be generated later via
+/// [prove_with_commitment()](crate::prove_with_commitment) function. The same commitment can be
+/// used to generate proofs for different public inputs over the same execution trace.
+///
+/// Unlike a [ProverCheckpoint], a trace commitment does not depend on public inputs, but it
+/// depends on proof options; thus, proofs generated from a commitment use the options specified
+/// when the commitment was created. Similarly to a checkpoint, a commitment contains
+/// coefficients of trace polynomials and leaves of the trace Merkle trees, and it can be written
+/// to disk (or sent to another machine) via [to_bytes()](TraceCommitment::to_bytes) method.
+/// Evaluations of trace polynomials over the LDE domain are not kept; these are recomputed when
+/// a proof is generated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceCommitment {
+    context: Context,
+    roots: Vec<Vec<u8>>,
+    trace_polys: Vec<u8>,
+    trace_leaves: Vec<u8>,
+}
+
+impl TraceCommitment {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Commits to the specified execution `trace` extended as defined by the specified proof
+    /// `options`, and returns the resulting commitment.
+    pub fn new<T: Trace>(trace: T, options: ProofOptions) -> Self {
+        let context = Context::new::<T::BaseField>(&trace.get_info(), options);
+        let trace_polys = build_trace_polys(trace);
+
+        // extend trace polynomials over the LDE domain one coset at a time, and hash the rows
+        // of each coset
+        let results = TraceCommitmentJob::build_all_for_context(&context)
+            .iter()
+            .map(|job| job.execute(&trace_polys))
+            .collect();
+        let trace_leaves = merge_row_hashes::<T::BaseField>(&context, results);
+
+        let num_trees = context
+            .options()
+            .trace_segments(context.trace_width())
+            .len();
+        let num_leaves = context.lde_domain_size();
+        let roots = match context.options().hash_fn() {
+            HashFunction::Blake3_256 => {
+                get_roots::<Blake3_256<T::BaseField>>(&trace_leaves, num_trees, num_leaves)
+            }
+            HashFunction::Blake3_192 => {
+                get_roots::<Blake3_192<T::BaseField>>(&trace_leaves, num_trees, num_leaves)
+            }
+            HashFunction::Sha3_256 => {
+                get_roots::<Sha3_256<T::BaseField>>(&trace_leaves, num_trees, num_leaves)
+            }
+        };
+
+        let mut poly_bytes = Vec::new();
+        for poly in trace_polys.iter() {
+            T::BaseField::write_batch_into(poly, &mut poly_bytes);
+        }
+
+        TraceCommitment {
+            context,
+            roots,
+            trace_polys: poly_bytes,
+            trace_leaves,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the context of the proofs which can be generated from this commitment.
+    pub fn context(&self) -> &Context {
+        &self.context
+    }
+
+    /// Returns the serialized roots of the trace Merkle trees, one for each trace segment; these
+    /// are the same values as the trace commitments included in the proofs generated from this
+    /// commitment.
+    pub fn roots(&self) -> &[Vec<u8>] {
+        &self.roots
+    }
+
+    /// Returns a checkpoint from which a proof for the specified public inputs can be generated.
+    pub(super) fn to_checkpoint(&self, pub_inputs: Vec<u8>) -> ProverCheckpoint {
+        ProverCheckpoint::from_serialized_trace(
+            self.context.clone(),
+            pub_inputs,
+            self.trace_polys.clone(),
+            self.trace_leaves.clone(),
+        )
+    }
+
+    // SERIALIZATION / DESERIALIZATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Serializes this commitment into a vector of bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut result = Vec::new();
+        self.context.write_into(&mut result);
+        result.write_u8(self.roots.len() as u8);
+        for root in self.roots.iter() {
+            write_bytes(&mut result, root);
+        }
+        write_bytes(&mut result, &self.trace_polys);
+        write_bytes(&mut result, &self.trace_leaves);
+        result
+    }
+
+    /// Returns a commitment read from the specified `source`.
+    ///
+    /// # Errors
+    /// Returns an error if a valid commitment could not be read from the specified `source`.
+    pub fn from_bytes(source: &[u8]) -> Result<Self, DeserializationError> {
+        let mut source = SliceReader::new(source);
+        let commitment = TraceCommitment {
+            context: Context::read_from(&mut source)?,
+            roots: {
+                let num_roots = source.read_u8()? as usize;
+                let mut roots = Vec::with_capacity(num_roots);
+                for _ in 0..num_roots {
+                    roots.push(read_bytes(&mut source)?);
+                }
+                roots
+            },
+            trace_polys: read_bytes(&mut source)?,
+            trace_leaves: read_bytes(&mut source)?,
+        };
+        if source.has_more_bytes() {
+            return Err(DeserializationError::UnconsumedBytes);
+        }
+        Ok(commitment)
+    }
+}
+
+// CHECKPOINT STATE
+// ================================================================================================
+/// Prover state read from a [ProverCheckpoint].
+pub(super) struct CheckpointState<B, E, H>
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+    H: Hasher,
+{
+    pub trace_polys: TracePolyTable<B>,
+    pub trace_trees: Vec<MerkleTree<H>>,
+    pub constraints: Option<(CompositionPoly<B, E>, MerkleTree<H>)>,
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Writes the length of `bytes` followed by the `bytes` themselves into the `target`. The
+/// length is written as a u64 value because saved polynomials may exceed 4 GB.
+fn write_bytes<W: ByteWriter>(target: &mut W, bytes: &[u8]) {
+    target.write_u8_slice(&(bytes.len() as u64).to_le_bytes());
+    target.write_u8_slice(bytes);
+}
+
+/// Reads a sequence of bytes prefixed with its length from the `source`.
+fn read_bytes<R: ByteReader>(source: &mut R) -> Result<Vec<u8>, DeserializationError> {
+    let num_bytes = source.read_u64()?;
+    source.read_u8_vec(num_bytes as usize)
+}
+
+/// Reads `num_elements` field elements from the `source`.
+fn read_elements<R: ByteReader, E: FieldElement>(
+    source: &mut R,
+    num_elements: usize,
+) -> Result<Vec<E>, ProverError> {
+    E::read_batch_from(source, num_elements).map_err(|_| ProverError::InvalidCheckpoint)
+}
+
+/// Builds `num_trees` Merkle trees from leaves serialized into `bytes`; leaves of each tree
+/// follow the leaves of the previous tree.
+fn read_trees<H: Hasher>(
+    bytes: &[u8],
+    num_trees: usize,
+    num_leaves: usize,
+) -> Result<Vec<MerkleTree<H>>, ProverError> {
+    let mut reader = SliceReader::new(bytes);
+    let mut trees = Vec::with_capacity(num_trees);
+    for _ in 0..num_trees {
+        let leaves = H::Digest::read_batch_from(&mut reader, num_leaves)
+            .map_err(|_| ProverError::InvalidCheckpoint)?;
+        trees.push(MerkleTree::new(leaves).map_err(|_| ProverError::InvalidCheckpoint)?);
+    }
+    ensure_consumed(&reader)?;
+    Ok(trees)
+}
+
+/// Returns the serialized roots of Merkle trees built from the specified serialized leaves.
+fn get_roots<H: Hasher>(leaves: &[u8], num_trees: usize, num_leaves: usize) -> Vec<Vec<u8>> {
+    read_trees::<H>(leaves, num_trees, num_leaves)
+        .expect("failed to build trace Merkle trees")
+        .iter()
+        .map(|tree| {
+            let mut result = Vec::new();
+            tree.root().write_into(&mut result);
+            result
+        })
+        .collect()
+}
+
+/// Returns an error if the `reader` has any bytes left.
+fn ensure_consumed(reader: &SliceReader) -> Result<(), ProverError> {
+    if reader.has_more_bytes() {
+        return Err(ProverError::InvalidCheckpoint);
+    }
+    Ok(())
+}
+
+/// Places the specified polynomial into a trace column appropriate for the proving `mode`.
+fn build_column<B: StarkField>(poly: Vec<B>, mode: ProvingMode) -> Column<B> {
+    match mode {
+        #[cfg(feature = "mmap")]
+        ProvingMode::MemoryMapped => {
+            let mut column = Column::Mapped(MappedColumn::new(poly.len()));
+            column.copy_from_slice(&poly);
+            column
+        }
+        _ => Column::from(poly),
+    }
+}
@@ -70,7 +70,7 @@ impl<A: Air, E: FieldElement<BaseField = A::BaseElement>> DeepCompositionPoly<A,
     /// Note that evaluations of T_i(z) and T_i(z * g) are passed in via the `ood_frame` parameter.
     pub fn add_trace_polys(
         &mut self,
-        trace_polys: TracePolyTable<A::BaseElement>,
+        trace_polys: &TracePolyTable<A::BaseElement>,
         ood_frame: EvaluationFrame<E>,
     ) {
         assert!(self.coefficients.is_empty());
@@ -87,7 +87,6 @@ impl<A: Air, E: FieldElement<BaseField = A::BaseElement>> DeepCompositionPoly<A,
 
         // combine trace polynomials into 2 composition polynomials T'(x) and T''(x), and if
         // we are using a field extension, also T'''(x)
-        let polys = trace_polys.into_vec();
         let mut t1_composition = E::zeroed_vector(trace_length);
         let mut t2_composition = E::zeroed_vector(trace_length);
         let mut t3_composition = if self.field_extension {
@@ -95,12 +94,12 @@ impl<A: Air, E: FieldElement<BaseField = A::BaseElement>> DeepCompositionPoly<A,
         } else {
             Vec::new()
         };
-        for (i, poly) in polys.into_iter().enumerate() {
+        for (i, poly) in trace_polys.iter().enumerate() {
             // compute T'(x) = T(x) - T(z), multiply it by a pseudo-random coefficient,
             // and add the result into composition polynomial
             acc_poly(
                 &mut t1_composition,
-                &poly,
+                poly,
                 trace_state1[i],
                 self.cc.trace[i].0,
             );
@@ -109,7 +108,7 @@ impl<A: Air, E: FieldElement<BaseField = A::BaseElement>> DeepCompositionPoly<A,
             // and add the result into composition polynomial
             acc_poly(
                 &mut t2_composition,
-                &poly,
+                poly,
                 trace_state2[i],
                 self.cc.trace[i].1,
             );
@@ -119,7 +118,7 @@ impl<A: Air, E: FieldElement<BaseField = A::BaseElement>> DeepCompositionPoly<A,
             if self.field_extension {
                 acc_poly(
                     &mut t3_composition,
-                    &poly,
+                    poly,
                     trace_state1[i].conjugate(),
                     self.cc.trace[i].2,
                 );
@@ -248,7 +247,7 @@ fn merge_trace_compositions<E: FieldElement>(mut polys: Vec<Vec<E>>, divisors: V
 }
 
 /// Computes (P(x) - value) * k and saves the result into the accumulator
-fn acc_poly<B, E>(accumulator: &mut Vec<E>, poly: &[B], value: E, k: E)
+fn acc_poly<B, E>(accumulator: &mut [E], poly: &[B], value: E, k: E)
 where
     B: StarkField,
     E: FieldElement<BaseField = B>,
@@ -42,7 +42,32 @@ impl<B: StarkField, E: FieldElement<BaseField = B>> BoundaryConstraintGroup<B, E
         };
 
         for constraint in group.constraints() {
-            if constraint.poly().len() == 1 {
+            if let Some((trace_length, steps)) = constraint.range() {
+                // the value polynomial of a range constraint evaluates to the asserted values at
+                // the asserted steps and to zeros at all other steps; thus, it is interpolated
+                // over the entire trace domain via inverse FFT, and is then evaluated over the
+                // constraint evaluation domain the same way as large polynomials
+                let mut poly = B::zeroed_vector(trace_length);
+                poly[steps].copy_from_slice(constraint.poly());
+                fft::interpolate_poly(&mut poly, &fft::get_inv_twiddles(trace_length));
+
+                let twiddles = twiddle_map
+                    .entry(trace_length)
+                    .or_insert_with(|| fft::get_twiddles(trace_length));
+                let values = fft::evaluate_poly_with_offset(
+                    &poly,
+                    twiddles,
+                    air.domain_offset(),
+                    air.ce_domain_size() / trace_length,
+                );
+
+                result.large_poly_constraints.push(LargePolyConstraint {
+                    register: constraint.register(),
+                    values,
+                    step_offset: 0,
+                    coefficients: *constraint.cc(),
+                });
+            } else if constraint.poly().len() == 1 {
                 result.single_value_constraints.push(SingleValueConstraint {
                     register: constraint.register(),
                     value: constraint.poly()[0],
@@ -53,6 +53,24 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> ConstraintComm
         }
     }
 
+    /// Returns a commitment to the evaluations of the constraint composition polynomial built
+    /// from the evaluations and a previously constructed Merkle tree for these evaluations.
+    pub fn from_tree(evaluations: Vec<Vec<E>>, commitment: MerkleTree<H>) -> Self {
+        assert!(
+            !evaluations.is_empty(),
+            "Constraint evaluations cannot be empty"
+        );
+        assert_eq!(
+            evaluations[0].len(),
+            commitment.leaves().len(),
+            "number of evaluations must be equal to the number of leaves in the Merkle tree"
+        );
+        ConstraintCommitment {
+            evaluations,
+            commitment,
+        }
+    }
+
     /// Returns the root of the commitment Merkle tree.
     pub fn root(&self) -> H::Digest {
         *self.commitment.root()
@@ -64,6 +82,11 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> ConstraintComm
         self.commitment.depth()
     }
 
+    /// Returns the commitment Merkle tree.
+    pub fn tree(&self) -> &MerkleTree<H> {
+        &self.commitment
+    }
+
     /// Returns constraint evaluations at the specified positions along with Merkle authentication
     /// paths from the root of the commitment to these evaluations.
     pub fn query(self, positions: &[usize]) -> Queries {
@@ -54,6 +54,29 @@ impl<B: StarkField, E: FieldElement<BaseField = B>> CompositionPoly<B, E> {
         }
     }
 
+    /// Returns a composition polynomial consisting of the specified column polynomials.
+    ///
+    /// # Panics
+    /// Panics if `columns` is empty, or if the columns are not all of the same length.
+    pub fn from_columns(columns: Vec<Vec<E>>) -> Self {
+        assert!(
+            !columns.is_empty(),
+            "composition polynomial must contain at least one column"
+        );
+        for column in columns.iter() {
+            assert_eq!(
+                column.len(),
+                columns[0].len(),
+                "all composition polynomial columns must have the same length"
+            );
+        }
+
+        CompositionPoly {
+            columns,
+            _base_field: PhantomData,
+        }
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -68,6 +91,11 @@ impl<B: StarkField, E: FieldElement<BaseField = B>> CompositionPoly<B, E> {
         self.columns[0].len()
     }
 
+    /// Returns coefficients of individual column polynomials.
+    pub fn columns(&self) -> &[Vec<E>] {
+        &self.columns
+    }
+
     /// Returns the degree of individual column polynomial.
     #[allow(unused)]
     pub fn column_degree(&self) -> usize {
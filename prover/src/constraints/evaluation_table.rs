@@ -5,93 +5,178 @@
 
 use super::{CompositionPoly, ProverError, StarkDomain};
 use air::ConstraintDivisor;
+use core::{mem::MaybeUninit, ops::Range};
 use math::{batch_inversion, fft, FieldElement, StarkField};
-use utils::{batch_iter_mut, collections::Vec, iter_mut, uninit_vector};
+use utils::{assume_init_buffer, batch_iter_mut, collections::Vec, iter, iter_mut, uninit_buffer};
 
 #[cfg(feature = "concurrent")]
 use utils::iterators::*;
 
-#[cfg(not(debug_assertions))]
-use core::marker::PhantomData;
+#[cfg(debug_assertions)]
+use utils::{string::ToString, table::fragment_columns};
 
 // CONSTANTS
 // ================================================================================================
 
-const MIN_FRAGMENT_SIZE: usize = 16;
+pub const MIN_FRAGMENT_SIZE: usize = 16;
 
 // CONSTRAINT EVALUATION TABLE
 // ================================================================================================
-
-pub struct ConstraintEvaluationTable<B: StarkField, E: FieldElement<BaseField = B>> {
-    evaluations: Vec<Vec<E>>,
-    divisors: Vec<ConstraintDivisor<B>>,
+/// Constraint evaluations over the constraint evaluation domain combined into evaluations of a
+/// single constraint composition polynomial.
+///
+/// Constraint evaluations are written into the table one row at a time via
+/// [EvaluationTableFragment::update_row()] method. Each row contains a merged evaluation of
+/// transition constraints, and merged evaluations of boundary constraint groups (one per group
+/// divisor); as a row is written, every value in it is divided by its divisor and the results
+/// are summed up into a single value of the combined column. Thus, only the combined column is
+/// kept in memory, rather than a column for every divisor. In debug mode, values for every
+/// divisor are kept as well so that degrees of the individual columns can be validated.
+///
+/// Memory for the combined column is not initialized when the table is created. Instead, the
+/// table keeps track of rows written via [EvaluationTableFragment::update_row()] method, and the
+/// combined column is read only after all rows have been written; if any row was skipped, an
+/// error is returned.
+pub struct ConstraintEvaluationTable<'a, B: StarkField, E: FieldElement<BaseField = B>> {
+    combined: Vec<MaybeUninit<E>>,
+    evaluated_rows: Vec<bool>,
+    inv_divisors: Vec<&'a [B]>,
+    exclusions: Vec<Option<B>>,
     domain_offset: B,
     trace_length: usize,
+    step_stride: usize,
+    step_offset: usize,
 
+    #[cfg(debug_assertions)]
+    divisors: Vec<ConstraintDivisor<B>>,
+    #[cfg(debug_assertions)]
+    evaluations: Vec<Vec<E>>,
     #[cfg(debug_assertions)]
     t_evaluations: Vec<Vec<B>>,
     #[cfg(debug_assertions)]
     t_expected_degrees: Vec<usize>,
 }
 
-impl<B: StarkField, E: FieldElement<BaseField = B>> ConstraintEvaluationTable<B, E> {
+impl<'a, B: StarkField, E: FieldElement<BaseField = B>> ConstraintEvaluationTable<'a, B, E> {
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
-    /// Returns a new constraint evaluation table with number of columns equal to the number of
-    /// specified divisors, and number of rows equal to the size of constraint evaluation domain.
+    /// Returns a new constraint evaluation table for the specified divisors with number of rows
+    /// equal to the size of constraint evaluation domain.
+    ///
+    /// Inverse evaluations of divisors over the constraint evaluation domain are read from (and
+    /// added to) the provided `divisor_cache`.
+    ///
+    /// # Panics
+    /// Panics if any of the divisors has more than one exclusion point, or has an exclusion point
+    /// and a numerator with more than one term.
     #[cfg(not(debug_assertions))]
-    pub fn new(domain: &StarkDomain<B>, divisors: Vec<ConstraintDivisor<B>>) -> Self {
-        let num_columns = divisors.len();
+    pub fn new(
+        domain: &StarkDomain<B>,
+        divisors: Vec<ConstraintDivisor<B>>,
+        divisor_cache: &'a mut DivisorCache<B>,
+    ) -> Self {
         let num_rows = domain.ce_domain_size();
+        let exclusions = get_exclusions(&divisors);
+        let inv_divisors = divisor_cache.get_inv_evaluations(&divisors, num_rows, domain.offset());
         ConstraintEvaluationTable {
-            evaluations: unsafe { (0..num_columns).map(|_| uninit_vector(num_rows)).collect() },
-            divisors,
+            combined: uninit_buffer(num_rows),
+            evaluated_rows: vec![false; num_rows],
+            inv_divisors,
+            exclusions,
             domain_offset: domain.offset(),
             trace_length: domain.trace_length(),
+            step_stride: 1,
+            step_offset: 0,
         }
     }
 
     /// Similar to the as above constructor but used in debug mode. In debug mode we also want
     /// to keep track of all evaluated transition constraints so that we can verify that their
-    /// expected degrees match their actual degrees.
+    /// expected degrees match their actual degrees, and of evaluations for every divisor so that
+    /// we can verify their post-division degrees.
     #[cfg(debug_assertions)]
     pub fn new(
         domain: &StarkDomain<B>,
         divisors: Vec<ConstraintDivisor<B>>,
+        divisor_cache: &'a mut DivisorCache<B>,
         transition_constraint_degrees: Vec<usize>,
     ) -> Self {
-        let num_columns = divisors.len();
         let num_rows = domain.ce_domain_size();
+        let num_columns = divisors.len();
         let num_t_columns = transition_constraint_degrees.len();
+        let exclusions = get_exclusions(&divisors);
+        let inv_divisors = divisor_cache.get_inv_evaluations(&divisors, num_rows, domain.offset());
         ConstraintEvaluationTable {
-            evaluations: unsafe { (0..num_columns).map(|_| uninit_vector(num_rows)).collect() },
-            divisors,
+            combined: uninit_buffer(num_rows),
+            evaluated_rows: vec![false; num_rows],
+            inv_divisors,
+            exclusions,
             domain_offset: domain.offset(),
             trace_length: domain.trace_length(),
-            t_evaluations: unsafe {
-                (0..num_t_columns)
-                    .map(|_| uninit_vector(num_rows))
-                    .collect()
-            },
+            step_stride: 1,
+            step_offset: 0,
+            divisors,
+            evaluations: (0..num_columns)
+                .map(|_| E::zeroed_vector(num_rows))
+                .collect(),
+            t_evaluations: (0..num_t_columns)
+                .map(|_| B::zeroed_vector(num_rows))
+                .collect(),
             t_expected_degrees: transition_constraint_degrees,
         }
     }
 
+    /// Returns a new table for holding constraint evaluations over a single coset of the
+    /// constraint evaluation domain.
+    ///
+    /// The constraint evaluation domain consists of `num_rows / trace_length` cosets of the trace
+    /// domain, and row `i` of the coset table corresponds to row `i * num_cosets + coset_idx` of
+    /// this table. A coset table cannot be used for constraint composition; instead, its contents
+    /// should be copied into this table via [update_coset()](ConstraintEvaluationTable::update_coset)
+    /// method.
+    pub fn new_coset_table(&self, coset_idx: usize) -> Self {
+        let num_rows = self.trace_length;
+        let num_cosets = self.num_rows() / num_rows;
+        debug_assert!(coset_idx < num_cosets, "coset index out of bounds");
+        ConstraintEvaluationTable {
+            combined: uninit_buffer(num_rows),
+            evaluated_rows: vec![false; num_rows],
+            inv_divisors: self.inv_divisors.clone(),
+            exclusions: self.exclusions.clone(),
+            domain_offset: self.domain_offset,
+            trace_length: num_rows,
+            step_stride: num_cosets,
+            step_offset: coset_idx,
+            #[cfg(debug_assertions)]
+            divisors: Vec::new(),
+            #[cfg(debug_assertions)]
+            evaluations: (0..self.evaluations.len())
+                .map(|_| E::zeroed_vector(num_rows))
+                .collect(),
+            #[cfg(debug_assertions)]
+            t_evaluations: (0..self.t_evaluations.len())
+                .map(|_| B::zeroed_vector(num_rows))
+                .collect(),
+            #[cfg(debug_assertions)]
+            t_expected_degrees: Vec::new(),
+        }
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
     /// Returns the number of rows in this table. This is the same as the size of the constraint
     /// evaluation domain.
     pub fn num_rows(&self) -> usize {
-        self.evaluations[0].len()
+        self.evaluated_rows.len()
     }
 
-    /// Returns number of columns in this table. The first column always contains the value of
-    /// combined transition constraint evaluations; the remaining columns contain values of
-    /// assertion constraint evaluations combined based on common divisors.
+    /// Returns number of values in every row written into this table. The first value is always
+    /// the combined transition constraint evaluation; the remaining values are assertion
+    /// constraint evaluations combined based on common divisors.
     #[allow(dead_code)]
     pub fn num_columns(&self) -> usize {
-        self.evaluations.len()
+        self.inv_divisors.len()
     }
 
     // TABLE FRAGMENTS
@@ -99,42 +184,61 @@ impl<B: StarkField, E: FieldElement<BaseField = B>> ConstraintEvaluationTable<B,
 
     /// Break the table into the number of specified fragments. All fragments can be updated
     /// independently - e.g. in different threads.
-    pub fn fragments(&mut self, num_fragments: usize) -> Vec<EvaluationTableFragment<B, E>> {
-        let fragment_size = self.num_rows() / num_fragments;
+    pub fn fragments(
+        &mut self,
+        num_fragments: usize,
+    ) -> Vec<EvaluationTableFragment<'_, 'a, B, E>> {
+        let num_rows = self.num_rows();
+        let fragment_size = num_rows / num_fragments;
         assert!(
-            fragment_size >= MIN_FRAGMENT_SIZE,
+            fragment_size >= MIN_FRAGMENT_SIZE || num_fragments == 1,
             "fragment size must be at least {}, but was {}",
             MIN_FRAGMENT_SIZE,
             fragment_size
         );
 
-        // break evaluations into fragments
-        let mut evaluation_data = (0..num_fragments).map(|_| Vec::new()).collect::<Vec<_>>();
-        self.evaluations.iter_mut().for_each(|column| {
-            for (i, fragment) in column.chunks_mut(fragment_size).enumerate() {
-                evaluation_data[i].push(fragment);
-            }
-        });
+        let combined = self.combined.chunks_mut(fragment_size);
+        let evaluated_rows = self.evaluated_rows.chunks_mut(fragment_size);
+        let inv_divisors = &self.inv_divisors;
+        let exclusions = &self.exclusions;
+        let (step_stride, step_offset) = (self.step_stride, self.step_offset);
 
         #[cfg(debug_assertions)]
         let result = {
-            // in debug mode, also break individual transition evaluations into fragments
-            let mut t_evaluation_data = (0..num_fragments).map(|_| Vec::new()).collect::<Vec<_>>();
-            self.t_evaluations.iter_mut().for_each(|column| {
-                for (i, fragment) in column.chunks_mut(fragment_size).enumerate() {
-                    t_evaluation_data[i].push(fragment);
-                }
-            });
+            // in debug mode, also break evaluations for individual divisors and individual
+            // transition evaluations into fragments
+            let evaluation_data = fragment_columns(
+                self.evaluations
+                    .iter_mut()
+                    .map(|column| column.as_mut_slice()),
+                num_rows,
+                fragment_size,
+            );
+            let t_evaluation_data = fragment_columns(
+                self.t_evaluations
+                    .iter_mut()
+                    .map(|column| column.as_mut_slice()),
+                num_rows,
+                fragment_size,
+            );
 
-            evaluation_data
-                .into_iter()
-                .zip(t_evaluation_data)
+            combined
+                .zip(evaluated_rows)
+                .zip(evaluation_data.into_iter().zip(t_evaluation_data))
                 .enumerate()
                 .map(
-                    |(i, (evaluations, t_evaluations))| EvaluationTableFragment {
-                        offset: i * fragment_size,
-                        evaluations,
-                        t_evaluations,
+                    move |(i, ((combined, evaluated_rows), (evaluations, t_evaluations)))| {
+                        EvaluationTableFragment {
+                            offset: i * fragment_size,
+                            combined,
+                            evaluated_rows,
+                            inv_divisors,
+                            exclusions,
+                            step_stride,
+                            step_offset,
+                            evaluations,
+                            t_evaluations,
+                        }
                     },
                 )
                 .collect()
@@ -142,48 +246,114 @@ impl<B: StarkField, E: FieldElement<BaseField = B>> ConstraintEvaluationTable<B,
 
         #[cfg(not(debug_assertions))]
         let result = {
-            evaluation_data
-                .into_iter()
+            combined
+                .zip(evaluated_rows)
                 .enumerate()
-                .map(|(i, evaluations)| EvaluationTableFragment {
-                    offset: i * fragment_size,
-                    evaluations,
-                    _base_field: PhantomData,
-                })
+                .map(
+                    move |(i, (combined, evaluated_rows))| EvaluationTableFragment {
+                        offset: i * fragment_size,
+                        combined,
+                        evaluated_rows,
+                        inv_divisors,
+                        exclusions,
+                        step_stride,
+                        step_offset,
+                    },
+                )
                 .collect()
         };
 
         result
     }
 
+    /// Copies evaluations from the specified `coset` table into this table. Row `i` of the coset
+    /// table is copied into row `i * num_cosets + coset_idx` of this table, where `num_cosets` is
+    /// the number of rows in this table divided by the number of rows in the coset table.
+    ///
+    /// # Errors
+    /// Returns an error if not all rows of the `coset` table have been written; the error
+    /// identifies the first such row by its index in this table.
+    pub fn update_coset(&mut self, coset_idx: usize, coset: Self) -> Result<(), ProverError> {
+        let num_cosets = self.num_rows() / coset.num_rows();
+        debug_assert!(coset_idx < num_cosets, "coset index out of bounds");
+        #[cfg(debug_assertions)]
+        {
+            for (column, coset_column) in self.evaluations.iter_mut().zip(coset.evaluations.iter())
+            {
+                for (i, &value) in coset_column.iter().enumerate() {
+                    column[i * num_cosets + coset_idx] = value;
+                }
+            }
+            for (column, coset_column) in self
+                .t_evaluations
+                .iter_mut()
+                .zip(coset.t_evaluations.iter())
+            {
+                for (i, &value) in coset_column.iter().enumerate() {
+                    column[i * num_cosets + coset_idx] = value;
+                }
+            }
+        }
+
+        let ce_domain_size = self.num_rows();
+        let coset_column =
+            into_combined_column(coset.combined, &coset.evaluated_rows).map_err(|row| {
+                ProverError::UnevaluatedConstraintRow {
+                    row: row * num_cosets + coset_idx,
+                    ce_domain_size,
+                }
+            })?;
+
+        for (i, value) in coset_column.into_iter().enumerate() {
+            self.combined[i * num_cosets + coset_idx] = MaybeUninit::new(value);
+            self.evaluated_rows[i * num_cosets + coset_idx] = true;
+        }
+
+        Ok(())
+    }
+
     // CONSTRAINT COMPOSITION
     // --------------------------------------------------------------------------------------------
-    /// Divides constraint evaluation columns by their respective divisor (in evaluation form),
-    /// combines the results into a single column, and interpolates this column into a composition
-    /// polynomial in coefficient form.
-    pub fn into_poly(self) -> Result<CompositionPoly<B, E>, ProverError> {
-        let domain_offset = self.domain_offset;
-
-        // allocate memory for the combined polynomial
-        let mut combined_poly = E::zeroed_vector(self.num_rows());
-
-        // iterate over all columns of the constraint evaluation table, divide each column
-        // by the evaluations of its corresponding divisor, and add all resulting evaluations
-        // together into a single vector
-        for (column, divisor) in self.evaluations.into_iter().zip(self.divisors.iter()) {
-            // in debug mode, make sure post-division degree of each column matches the expected
-            // degree
-            #[cfg(debug_assertions)]
-            validate_column_degree(&column, divisor, domain_offset, column.len() - 1)?;
+    /// Interpolates the combined column of this table into a composition polynomial in
+    /// coefficient form.
+    ///
+    /// # Errors
+    /// Returns an error if not all rows of this table have been written, or, in debug mode, if
+    /// post-division degrees of the evaluations for individual divisors do not match the expected
+    /// degree.
+    ///
+    /// `domain` must be the domain over which the constraints were evaluated; inverse twiddles
+    /// cached in the domain are used for interpolation.
+    pub fn into_poly(self, domain: &StarkDomain<B>) -> Result<CompositionPoly<B, E>, ProverError> {
+        let ce_domain_size = self.num_rows();
+        let mut combined_poly =
+            into_combined_column(self.combined, &self.evaluated_rows).map_err(|row| {
+                ProverError::UnevaluatedConstraintRow {
+                    row,
+                    ce_domain_size,
+                }
+            })?;
 
-            // divide the column by the divisor and accumulate the result into combined_poly
-            acc_column(column, divisor, self.domain_offset, &mut combined_poly);
+        // in debug mode, make sure post-division degree of each column matches the expected
+        // degree
+        #[cfg(debug_assertions)]
+        for (i, (column, divisor)) in self
+            .evaluations
+            .iter()
+            .zip(self.divisors.iter())
+            .enumerate()
+        {
+            validate_column_degree(i, column, divisor, self.domain_offset, column.len() - 1)?;
         }
 
-        // at this point, combined_poly contains evaluations of the combined constraint polynomial;
-        // we interpolate this polynomial to transform it into coefficient form.
-        let inv_twiddles = fft::get_inv_twiddles::<B>(combined_poly.len());
-        fft::interpolate_poly_with_offset(&mut combined_poly, &inv_twiddles, domain_offset);
+        // the combined column contains evaluations of the combined constraint polynomial; we
+        // interpolate this polynomial to transform it into coefficient form.
+        debug_assert_eq!(combined_poly.len(), domain.ce_domain_size());
+        fft::interpolate_poly_with_offset(
+            &mut combined_poly,
+            domain.ce_inv_twiddles(),
+            self.domain_offset,
+        );
 
         Ok(CompositionPoly::new(combined_poly, self.trace_length))
     }
@@ -191,8 +361,11 @@ impl<B: StarkField, E: FieldElement<BaseField = B>> ConstraintEvaluationTable<B,
     // DEBUG HELPERS
     // --------------------------------------------------------------------------------------------
 
+    /// Checks that actual degrees of all transition constraint polynomials match their expected
+    /// degrees, and returns an error identifying the first constraint for which this is not
+    /// the case.
     #[cfg(debug_assertions)]
-    pub fn validate_transition_degrees(&mut self) {
+    pub fn validate_transition_degrees(&mut self) -> Result<(), ProverError> {
         // collect actual degrees for all transition constraints by interpolating saved
         // constraint evaluations into polynomials and checking their degree; also
         // determine max transition constraint degree
@@ -209,11 +382,20 @@ impl<B: StarkField, E: FieldElement<BaseField = B>> ConstraintEvaluationTable<B,
         }
 
         // make sure expected and actual degrees are equal
-        if self.t_expected_degrees != actual_degrees {
-            panic!(
-                "transition constraint degrees didn't match\nexpected: {:>3?}\nactual:   {:>3?}",
-                self.t_expected_degrees, actual_degrees
-            );
+        for (i, (&expected, &actual)) in self
+            .t_expected_degrees
+            .iter()
+            .zip(actual_degrees.iter())
+            .enumerate()
+        {
+            if expected != actual {
+                return Err(ProverError::MismatchedTransitionConstraintDegree {
+                    constraint: i,
+                    expected,
+                    actual,
+                    trace_length: self.trace_length,
+                });
+            }
         }
 
         // make sure evaluation domain size does not exceed the size required by max degree
@@ -226,24 +408,30 @@ impl<B: StarkField, E: FieldElement<BaseField = B>> ConstraintEvaluationTable<B,
                 self.num_rows()
             );
         }
+
+        Ok(())
     }
 }
 
 // TABLE FRAGMENTS
 // ================================================================================================
 
-pub struct EvaluationTableFragment<'a, B: StarkField, E: FieldElement<BaseField = B>> {
+pub struct EvaluationTableFragment<'t, 'a, B: StarkField, E: FieldElement<BaseField = B>> {
     offset: usize,
-    evaluations: Vec<&'a mut [E]>,
+    combined: &'t mut [MaybeUninit<E>],
+    evaluated_rows: &'t mut [bool],
+    inv_divisors: &'t [&'a [B]],
+    exclusions: &'t [Option<B>],
+    step_stride: usize,
+    step_offset: usize,
 
     #[cfg(debug_assertions)]
-    t_evaluations: Vec<&'a mut [B]>,
-
-    #[cfg(not(debug_assertions))]
-    _base_field: PhantomData<B>,
+    evaluations: Vec<&'t mut [E]>,
+    #[cfg(debug_assertions)]
+    t_evaluations: Vec<&'t mut [B]>,
 }
 
-impl<'a, B: StarkField, E: FieldElement<BaseField = B>> EvaluationTableFragment<'a, B, E> {
+impl<'t, 'a, B: StarkField, E: FieldElement<BaseField = B>> EvaluationTableFragment<'t, 'a, B, E> {
     /// Returns the row at which the fragment starts.
     pub fn offset(&self) -> usize {
         self.offset
@@ -251,19 +439,51 @@ impl<'a, B: StarkField, E: FieldElement<BaseField = B>> EvaluationTableFragment<
 
     /// Returns the number of evaluation rows in the fragment.
     pub fn num_rows(&self) -> usize {
-        self.evaluations[0].len()
+        self.evaluated_rows.len()
     }
 
-    /// Returns the number of columns in every evaluation row.
+    /// Returns the number of values in every evaluation row.
     pub fn num_columns(&self) -> usize {
-        self.evaluations.len()
+        self.inv_divisors.len()
     }
 
-    /// Updates a single row in the fragment with provided data.
-    pub fn update_row(&mut self, row_idx: usize, row_data: &[E]) {
+    /// Divides every value in `row_data` by its divisor evaluated at `x`, adds the results
+    /// together into the combined column at the specified row, and marks the row as evaluated.
+    ///
+    /// `x` must be the point of the constraint evaluation domain which corresponds to the row.
+    ///
+    /// # Panics
+    /// Panics if the length of `row_data` is not equal to the number of columns in the fragment.
+    pub fn update_row(&mut self, row_idx: usize, x: B, row_data: &[E]) {
+        assert_eq!(
+            row_data.len(),
+            self.num_columns(),
+            "row data must contain a value for every column"
+        );
+
+        // determine the step in the constraint evaluation domain which corresponds to the row
+        let step = (self.offset + row_idx) * self.step_stride + self.step_offset;
+
+        // for divisors of the form (x^a - b), the value is divided by computing value * z, where
+        // z = 1 / (x^a - b) and has already been computed; for divisors of the form
+        // (x^a - 1) / (x - b), the value is divided by computing value * (x - b) * z.
+        let mut result = E::ZERO;
+        for ((&value, z), exclusion) in row_data.iter().zip(self.inv_divisors).zip(self.exclusions)
+        {
+            // determine which value of z corresponds to the current domain point
+            let mut z = z[step % z.len()];
+            if let Some(b) = exclusion {
+                z *= x - *b;
+            }
+            result += value * E::from(z);
+        }
+        self.combined[row_idx] = MaybeUninit::new(result);
+
+        #[cfg(debug_assertions)]
         for (column, &value) in self.evaluations.iter_mut().zip(row_data) {
             column[row_idx] = value;
         }
+        self.evaluated_rows[row_idx] = true;
     }
 
     /// Updates transition evaluations row with the provided data; available only in debug mode.
@@ -275,73 +495,150 @@ impl<'a, B: StarkField, E: FieldElement<BaseField = B>> EvaluationTableFragment<
     }
 }
 
-// HELPER FUNCTIONS
+// DIVISOR CACHE
 // ================================================================================================
+/// Inverse evaluations of constraint divisor numerators over a constraint evaluation domain.
+///
+/// Computing these evaluations requires a batch inversion over the domain for every divisor;
+/// for proofs of computations with the same trace shape, divisors of transition constraints
+/// (and usually, of boundary constraints as well) are identical, and thus the evaluations can be
+/// reused. At most [DivisorCache::MAX_ENTRIES] evaluations are kept; when this number is
+/// exceeded, the evaluations added to the cache first are evicted.
+pub struct DivisorCache<B: StarkField> {
+    entries: Vec<(ConstraintDivisor<B>, usize, B, Vec<B>)>,
+}
 
-#[allow(clippy::many_single_char_names)]
-fn acc_column<B: StarkField, E: FieldElement<BaseField = B>>(
-    column: Vec<E>,
-    divisor: &ConstraintDivisor<B>,
-    domain_offset: B,
-    result: &mut [E],
-) {
-    let numerator = divisor.numerator();
-    assert_eq!(numerator.len(), 1, "complex divisors are not yet supported");
-    assert!(
-        divisor.exclude().len() <= 1,
-        "multiple exclusion points are not yet supported"
-    );
+impl<B: StarkField> DivisorCache<B> {
+    /// Maximum number of divisors for which inverse evaluations are cached.
+    pub const MAX_ENTRIES: usize = 64;
 
-    // compute inverse evaluations of the divisor's numerator, which has the form (x^a - b)
-    let domain_size = column.len();
-    let z = get_inv_evaluation(divisor, domain_size, domain_offset);
-
-    // divide column values by the divisor; for boundary constraints this computed simply as
-    // multiplication of column value by the inverse of divisor numerator; for transition
-    // constraints, it is computed similarly, but the result is also multiplied by the divisor's
-    // denominator (exclusion point).
-    if divisor.exclude().is_empty() {
-        // the column represents merged evaluations of boundary constraints, and divisor has the
-        // form of (x^a - b); thus to divide the column by the divisor, we compute: value * z,
-        // where z = 1 / (x^a - 1) and has already been computed above.
-        iter_mut!(result, 1024)
-            .zip(column)
-            .enumerate()
-            .for_each(|(i, (acc_value, value))| {
-                // determine which value of z corresponds to the current domain point
-                let z = E::from(z[i % z.len()]);
-                // compute value * z and add it to the result
-                *acc_value += value * z;
-            });
-    } else {
-        // the column represents merged evaluations of transition constraints, and divisor has the
-        // form of (x^a - 1) / (x - b); thus, to divide the column by the divisor, we compute:
-        // value * (x - b) * z, where z = 1 / (x^a - 1) and has already been computed above.
-
-        // set up variables for computing x at every point in the domain
-        let g = B::get_root_of_unity(domain_size.trailing_zeros());
-        let b = divisor.exclude()[0];
-
-        batch_iter_mut!(
-            result,
-            128, // min batch size
-            |batch: &mut [E], batch_offset: usize| {
-                let mut x = domain_offset * g.exp((batch_offset as u64).into());
-                for (i, acc_value) in batch.iter_mut().enumerate() {
-                    // compute value of (x - b) and compute next value of x
-                    let e = x - b;
-                    x *= g;
-                    // determine which value of z corresponds to the current domain point
-                    let z = z[i % z.len()];
-                    // compute value * (x - b) * z and add it to the result
-                    *acc_value += column[batch_offset + i] * E::from(z * e);
-                }
+    /// Returns a new empty divisor cache.
+    pub fn new() -> Self {
+        DivisorCache {
+            entries: Vec::new(),
+        }
+    }
+
+    /// Returns the number of divisors for which inverse evaluations are cached.
+    #[allow(dead_code)]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns inverse evaluations of the numerators of the specified divisors over the domain of
+    /// the specified size and offset; the evaluations are computed and cached if they are not in
+    /// the cache yet.
+    ///
+    /// Evaluations for the requested divisors are never evicted by this method; thus, the cache
+    /// may temporarily hold more than [DivisorCache::MAX_ENTRIES] entries if more divisors than
+    /// that are requested at once.
+    pub fn get_inv_evaluations(
+        &mut self,
+        divisors: &[ConstraintDivisor<B>],
+        domain_size: usize,
+        domain_offset: B,
+    ) -> Vec<&[B]> {
+        for divisor in divisors {
+            if self.position(divisor, domain_size, domain_offset).is_none() {
+                let z = get_inv_evaluation(divisor, domain_size, domain_offset);
+                self.entries
+                    .push((divisor.clone(), domain_size, domain_offset, z));
             }
-        );
+        }
+
+        // evict the oldest entries which are not needed for the requested divisors
+        while self.entries.len() > Self::MAX_ENTRIES {
+            let evicted = self.entries.iter().position(|(d, size, offset, _)| {
+                *size != domain_size || *offset != domain_offset || !divisors.contains(d)
+            });
+            match evicted {
+                Some(idx) => self.entries.remove(idx),
+                None => break,
+            };
+        }
+
+        let cache: &Self = self;
+        divisors
+            .iter()
+            .map(|divisor| {
+                let idx = cache
+                    .position(divisor, domain_size, domain_offset)
+                    .expect("divisor evaluations not cached");
+                cache.entries[idx].3.as_slice()
+            })
+            .collect()
+    }
+
+    /// Returns the position of the entry for the specified divisor and domain.
+    fn position(
+        &self,
+        divisor: &ConstraintDivisor<B>,
+        domain_size: usize,
+        domain_offset: B,
+    ) -> Option<usize> {
+        self.entries.iter().position(|(d, size, offset, _)| {
+            d == divisor && *size == domain_size && *offset == domain_offset
+        })
     }
 }
 
-/// Computes evaluations of the divisor's numerator over the domain of the specified size and offset.
+impl<B: StarkField> Default for DivisorCache<B> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Converts the specified combined column into a vector of values without copying.
+///
+/// If any of the `evaluated_rows` has not been written, returns the index of the first such row
+/// as an error.
+fn into_combined_column<E: FieldElement>(
+    column: Vec<MaybeUninit<E>>,
+    evaluated_rows: &[bool],
+) -> Result<Vec<E>, usize> {
+    if let Some(row) = evaluated_rows.iter().position(|&evaluated| !evaluated) {
+        return Err(row);
+    }
+
+    // every value in the combined column has been written via update_row() or update_coset(),
+    // as these methods write the value of a row before marking it as evaluated
+    Ok(unsafe { assume_init_buffer(column) })
+}
+
+/// Returns exclusion points of the specified divisors.
+///
+/// # Panics
+/// Panics if any of the divisors has more than one exclusion point, or has an exclusion point
+/// and a range term.
+fn get_exclusions<B: StarkField>(divisors: &[ConstraintDivisor<B>]) -> Vec<Option<B>> {
+    divisors
+        .iter()
+        .map(|divisor| {
+            assert!(
+                divisor.exclude().len() <= 1,
+                "multiple exclusion points are not yet supported"
+            );
+            assert!(
+                divisor.range().is_none() || divisor.exclude().is_empty(),
+                "exclusion points are not yet supported for range divisors"
+            );
+            divisor.exclude().first().copied()
+        })
+        .collect()
+}
+
+/// Computes inverse evaluations of the divisor's numerator over the domain of the specified size
+/// and offset.
+///
+/// For numerators of the form (x^a - b), evaluations repeat every domain_size / a steps, and
+/// thus, only the first domain_size / a evaluations are returned.
+///
+/// # Panics
+/// Panics if the divisor has more than one numerator term, or if it has a numerator term and a
+/// range term.
 #[allow(clippy::many_single_char_names)]
 fn get_inv_evaluation<B: StarkField>(
     divisor: &ConstraintDivisor<B>,
@@ -349,6 +646,19 @@ fn get_inv_evaluation<B: StarkField>(
     domain_offset: B,
 ) -> Vec<B> {
     let numerator = divisor.numerator();
+    if let Some((trace_length, steps)) = divisor.range() {
+        assert!(
+            numerator.is_empty(),
+            "range divisors with additional numerator terms are not yet supported"
+        );
+        return get_inv_range_evaluation(trace_length, steps, domain_size, domain_offset);
+    }
+    assert_eq!(
+        numerator.len(),
+        1,
+        "multi-term divisors are not yet supported"
+    );
+
     let a = numerator[0].0 as u64; // numerator degree
     let b = numerator[0].1;
 
@@ -356,29 +666,86 @@ fn get_inv_evaluation<B: StarkField>(
     let g = B::get_root_of_unity(domain_size.trailing_zeros()).exp(a.into());
 
     // compute x^a - b for all x
-    let mut evaluations = unsafe { uninit_vector(n) };
+    let mut evaluations = uninit_buffer(n);
     batch_iter_mut!(
         &mut evaluations,
         128, // min batch size
-        |batch: &mut [B], batch_offset: usize| {
+        |batch: &mut [MaybeUninit<B>], batch_offset: usize| {
             let mut x = domain_offset.exp(a.into()) * g.exp((batch_offset as u64).into());
             for evaluation in batch.iter_mut() {
-                *evaluation = x - b;
+                evaluation.write(x - b);
                 x *= g;
             }
         }
     );
+    // SAFETY: the batches cover all evaluations, and every evaluation was written above
+    let evaluations = unsafe { assume_init_buffer(evaluations) };
 
     // compute 1 / (x^a - b)
     batch_inversion(&evaluations)
 }
 
+/// Computes inverse evaluations of a range numerator prod(x - g^i) for i in the specified steps
+/// over the domain of the specified size and offset; g is the generator of the trace domain.
+///
+/// Denoting by E_m the evaluations of prod(x - g^(a + j)) for j in 0..m, the product for 2m
+/// steps is obtained from E_m as E_2m(x) = E_m(x) * g^(m^2) * E_m(x * g^-m). Since multiplying
+/// x by g^-m shifts the evaluations by m * blowup positions, this takes a single pass over the
+/// domain, and thus, all evaluations are computed in O(domain_size * log(steps.len())) time.
+fn get_inv_range_evaluation<B: StarkField>(
+    trace_length: usize,
+    steps: Range<usize>,
+    domain_size: usize,
+    domain_offset: B,
+) -> Vec<B> {
+    debug_assert!(
+        domain_size >= trace_length,
+        "domain is smaller than the trace"
+    );
+    let blowup = domain_size / trace_length;
+    let g = B::get_root_of_unity(trace_length.trailing_zeros());
+    let domain = math::get_power_series_with_offset(
+        B::get_root_of_unity(domain_size.trailing_zeros()),
+        domain_offset,
+        domain_size,
+    );
+
+    // build up the product bit by bit starting from the most significant bit of the number of
+    // steps; m is the number of terms accumulated so far
+    let num_steps = steps.len();
+    let mut evaluations = vec![B::ONE; domain_size];
+    let mut m = 0;
+    for bit in (0..usize::BITS - num_steps.leading_zeros()).rev() {
+        if m > 0 {
+            let mut shifted = evaluations.clone();
+            shifted.rotate_right((m * blowup) % domain_size);
+            let factor = g.exp((m as u64).into()).exp((m as u64).into());
+            iter_mut!(evaluations)
+                .zip(shifted)
+                .for_each(|(evaluation, shifted)| *evaluation *= factor * shifted);
+            m *= 2;
+        }
+        if (num_steps >> bit) & 1 == 1 {
+            let root = g.exp(((steps.start + m) as u64).into());
+            iter_mut!(evaluations)
+                .zip(iter!(domain))
+                .for_each(|(evaluation, &x)| *evaluation *= x - root);
+            m += 1;
+        }
+    }
+    debug_assert_eq!(m, num_steps);
+
+    batch_inversion(&evaluations)
+}
+
 // DEBUG HELPERS
 // ================================================================================================
 
-/// Makes sure that the post-division degree of the polynomial matches the expected degree
+/// Makes sure that the post-division degree of the polynomial matches the expected degree; the
+/// index of the column is used only to identify the column in the returned error.
 #[cfg(debug_assertions)]
 fn validate_column_degree<B: StarkField, E: FieldElement<BaseField = B>>(
+    column_idx: usize,
     column: &[E],
     divisor: &ConstraintDivisor<B>,
     domain_offset: B,
@@ -404,11 +771,203 @@ fn validate_column_degree<B: StarkField, E: FieldElement<BaseField = B>>(
     fft::interpolate_poly_with_offset(&mut evaluations, &inv_twiddles, domain_offset);
     let poly = evaluations;
 
-    if expected_degree != math::polynom::degree_of(&poly) {
-        return Err(ProverError::MismatchedConstraintPolynomialDegree(
-            expected_degree,
-            math::polynom::degree_of(&poly),
-        ));
+    let actual_degree = math::polynom::degree_of(&poly);
+    if expected_degree != actual_degree {
+        return Err(ProverError::MismatchedConstraintPolynomialDegree {
+            column: column_idx,
+            divisor: divisor.to_string(),
+            expected: expected_degree,
+            actual: actual_degree,
+            ce_domain_size: column.len(),
+        });
     }
     Ok(())
 }
+
+// TESTS
+// ================================================================================================
+
+#[cfg(all(test, debug_assertions))]
+mod tests {
+    use super::{
+        get_inv_evaluation, ConstraintEvaluationTable, DivisorCache, ProverError, StarkDomain,
+    };
+    use crate::tests::MockAir;
+    use crate::ProverStage;
+    use air::{Air, Assertion, ConstraintDivisor};
+    use math::{
+        fft, fields::f128::BaseElement, get_power_series_with_offset, FieldElement, StarkField,
+    };
+
+    #[test]
+    fn validate_transition_degrees() {
+        // mock AIR declares a single transition constraint of degree 2
+        let trace_length = 16;
+        let air = MockAir::with_trace_length(trace_length);
+        let domain = StarkDomain::new(&air);
+        let expected_degree = 2 * (trace_length - 1);
+
+        // constraint evaluations of the expected degree are accepted
+        let mut cache = DivisorCache::new();
+        let mut table = build_table(&air, &domain, &mut cache, expected_degree);
+        assert_eq!(Ok(()), table.validate_transition_degrees());
+
+        // constraint evaluations of a lower degree are reported for the offending constraint
+        let mut cache = DivisorCache::new();
+        let mut table = build_table(&air, &domain, &mut cache, trace_length - 1);
+        assert_eq!(
+            Err(ProverError::MismatchedTransitionConstraintDegree {
+                constraint: 0,
+                expected: expected_degree,
+                actual: trace_length - 1,
+                trace_length,
+            }),
+            table.validate_transition_degrees()
+        );
+        let err = table.validate_transition_degrees().unwrap_err();
+        assert_eq!(ProverStage::ConstraintEvaluation, err.stage());
+    }
+
+    #[test]
+    fn skipped_rows_are_detected() {
+        let trace_length = 16;
+        let air = MockAir::with_trace_length(trace_length);
+        let domain = StarkDomain::new(&air);
+        let num_rows = domain.ce_domain_size();
+
+        // a table in which a row was not written cannot be turned into a polynomial
+        let mut cache = DivisorCache::new();
+        let mut table = new_table(&air, &domain, &mut cache);
+        let mut fragments = table.fragments(1);
+        for i in (0..num_rows).filter(|&i| i != 5) {
+            fragments[0].update_row(i, BaseElement::ONE, &[BaseElement::ZERO]);
+        }
+        drop(fragments);
+        assert_eq!(
+            Err(ProverError::UnevaluatedConstraintRow {
+                row: 5,
+                ce_domain_size: num_rows
+            }),
+            table.into_poly(&domain).map(|_| ())
+        );
+
+        // a coset table in which a row was not written cannot be copied into the full table
+        let mut cache = DivisorCache::new();
+        let mut table = new_table(&air, &domain, &mut cache);
+        let mut coset_table = table.new_coset_table(1);
+        let mut fragments = coset_table.fragments(1);
+        for i in 1..trace_length {
+            fragments[0].update_row(i, BaseElement::ONE, &[BaseElement::ZERO]);
+        }
+        drop(fragments);
+        assert_eq!(
+            Err(ProverError::UnevaluatedConstraintRow {
+                row: 1,
+                ce_domain_size: num_rows
+            }),
+            table.update_coset(1, coset_table)
+        );
+    }
+
+    #[test]
+    fn divisor_cache() {
+        let air = MockAir::with_trace_length(16);
+        let domain = StarkDomain::new(&air);
+        let domain_size = domain.ce_domain_size();
+        let offset = domain.offset();
+        let divisor = air.transition_constraint_divisor();
+
+        let divisors = vec![divisor.clone()];
+
+        // evaluations are computed once and then reused
+        let mut cache = DivisorCache::new();
+        let expected = get_inv_evaluation(&divisor, domain_size, offset);
+        assert_eq!(
+            vec![expected.as_slice()],
+            cache.get_inv_evaluations(&divisors, domain_size, offset)
+        );
+        assert_eq!(
+            vec![expected.as_slice()],
+            cache.get_inv_evaluations(&divisors, domain_size, offset)
+        );
+        assert_eq!(1, cache.len());
+
+        // the same divisor over a different domain is cached separately
+        cache.get_inv_evaluations(&divisors, domain_size * 2, offset);
+        assert_eq!(2, cache.len());
+
+        // the oldest entries are evicted once the cache is full
+        for i in 0..DivisorCache::<BaseElement>::MAX_ENTRIES {
+            let other_offset = offset + BaseElement::new(i as u128 + 1);
+            cache.get_inv_evaluations(&divisors, domain_size, other_offset);
+        }
+        assert_eq!(DivisorCache::<BaseElement>::MAX_ENTRIES, cache.len());
+        assert_eq!(
+            vec![expected.as_slice()],
+            cache.get_inv_evaluations(&divisors, domain_size, offset)
+        );
+        assert_eq!(DivisorCache::<BaseElement>::MAX_ENTRIES, cache.len());
+    }
+
+    fn new_table<'a>(
+        air: &MockAir,
+        domain: &StarkDomain<BaseElement>,
+        divisor_cache: &'a mut DivisorCache<BaseElement>,
+    ) -> ConstraintEvaluationTable<'a, BaseElement, BaseElement> {
+        let divisors = vec![air.transition_constraint_divisor()];
+        let t_degrees = air
+            .transition_constraint_degrees()
+            .iter()
+            .map(|d| d.get_evaluation_degree(air.trace_length()))
+            .collect();
+        ConstraintEvaluationTable::new(domain, divisors, divisor_cache, t_degrees)
+    }
+
+    fn build_table<'a>(
+        air: &MockAir,
+        domain: &StarkDomain<BaseElement>,
+        divisor_cache: &'a mut DivisorCache<BaseElement>,
+        degree: usize,
+    ) -> ConstraintEvaluationTable<'a, BaseElement, BaseElement> {
+        let mut table = new_table(air, domain, divisor_cache);
+
+        // evaluate x^degree over the constraint evaluation domain
+        let num_rows = domain.ce_domain_size();
+        let mut evaluations = vec![BaseElement::ZERO; num_rows];
+        evaluations[degree] = BaseElement::ONE;
+        fft::evaluate_poly(&mut evaluations, &fft::get_twiddles(num_rows));
+
+        let g = BaseElement::get_root_of_unity(num_rows.trailing_zeros());
+        let mut x = domain.offset();
+        let mut fragments = table.fragments(1);
+        for (i, &value) in evaluations.iter().enumerate() {
+            fragments[0].update_row(i, x, &[value]);
+            fragments[0].update_transition_evaluations(i, &[value]);
+            x *= g;
+        }
+        drop(fragments);
+
+        table
+    }
+
+    #[test]
+    fn inv_range_evaluation() {
+        let trace_length = 32;
+        let domain_size = 128_usize;
+        let offset = BaseElement::GENERATOR;
+        let g = BaseElement::get_root_of_unity(domain_size.trailing_zeros());
+        let domain = get_power_series_with_offset(g, offset, domain_size);
+
+        for (first_step, num_steps) in [(0, 1), (3, 5), (7, 12), (0, 31), (1, 31), (0, 32)] {
+            let values = vec![BaseElement::ONE; num_steps];
+            let assertion = Assertion::range(0, first_step, first_step + num_steps - 1, values);
+            let divisor = ConstraintDivisor::from_assertion(&assertion, trace_length);
+
+            let expected = domain
+                .iter()
+                .map(|&x| divisor.evaluate_at(x).inv())
+                .collect::<Vec<_>>();
+            assert_eq!(expected, get_inv_evaluation(&divisor, domain_size, offset));
+        }
+    }
+}
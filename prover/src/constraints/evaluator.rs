@@ -4,19 +4,27 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{
-    evaluation_table::EvaluationTableFragment, BoundaryConstraintGroup, ConstraintEvaluationTable,
-    PeriodicValueTable, StarkDomain, TraceTable,
+    evaluation_table::EvaluationTableFragment, BoundaryConstraintGroup, ChunkedTraceTable,
+    ConstraintEvaluationTable, DivisorCache, FragmentScheduling, PeriodicValueTable, ProverError,
+    ProverObserver, StarkDomain, TraceLde, TraceTable,
 };
 use air::{
     Air, ConstraintCompositionCoefficients, ConstraintDivisor, EvaluationFrame,
     TransitionConstraintGroup,
 };
-use math::FieldElement;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use math::{FieldElement, StarkField};
 use utils::{
     collections::{BTreeMap, Vec},
     iter_mut,
 };
 
+#[cfg(feature = "concurrent")]
+use super::evaluation_table::MIN_FRAGMENT_SIZE;
+
+#[cfg(feature = "concurrent")]
+use std::time::Instant;
+
 #[cfg(feature = "concurrent")]
 use utils::{iterators::*, rayon};
 
@@ -26,6 +34,15 @@ use utils::{iterators::*, rayon};
 #[cfg(feature = "concurrent")]
 const MIN_CONCURRENT_DOMAIN_SIZE: usize = 8192;
 
+/// Number of rows evaluated during the calibration pass of auto-tuned fragment scheduling.
+#[cfg(feature = "concurrent")]
+const CALIBRATION_ROWS: usize = 64;
+
+/// Time (in nanoseconds) it should take to evaluate a single fragment under auto-tuned fragment
+/// scheduling.
+#[cfg(feature = "concurrent")]
+const TARGET_FRAGMENT_TIME_NS: u128 = 100_000;
+
 // CONSTRAINT EVALUATOR
 // ================================================================================================
 
@@ -93,11 +110,32 @@ impl<'a, A: Air, E: FieldElement<BaseField = A::BaseElement>> ConstraintEvaluato
     /// Evaluates constraints against the provided extended execution trace. Constraints are
     /// evaluated over a constraint evaluation domain. This is an optimization because constraint
     /// evaluation domain can be many times smaller than the full LDE domain.
-    pub fn evaluate(
+    ///
+    /// If the extended trace is chunked, constraints are evaluated over one coset of the
+    /// constraint evaluation domain at a time, such that rows of the extended trace need to be
+    /// computed only for a single coset at a time.
+    ///
+    /// Evaluations are divided by their divisors and combined into a single column as they are
+    /// written into the evaluation table; inverse evaluations of divisors are read from (and
+    /// added to) the provided `divisor_cache`.
+    ///
+    /// The evaluation table is broken into fragments evaluated in parallel as specified by
+    /// `scheduling`.
+    ///
+    /// Progress of the evaluation is reported to the `observer` every time a fragment of the
+    /// evaluation table (or a coset, for chunked traces) has been evaluated.
+    ///
+    /// In debug mode, this also checks that actual degrees of transition constraints match
+    /// their declared degrees, and returns an error identifying the mismatched constraint
+    /// otherwise.
+    pub fn evaluate<'c>(
         &self,
-        trace: &TraceTable<A::BaseElement>,
+        trace: &TraceLde<A::BaseElement>,
         domain: &StarkDomain<A::BaseElement>,
-    ) -> ConstraintEvaluationTable<A::BaseElement, E> {
+        divisor_cache: &'c mut DivisorCache<A::BaseElement>,
+        scheduling: FragmentScheduling,
+        observer: &impl ProverObserver,
+    ) -> Result<ConstraintEvaluationTable<'c, A::BaseElement, E>, ProverError> {
         assert_eq!(
             trace.len(),
             domain.lde_domain_size(),
@@ -107,38 +145,55 @@ impl<'a, A: Air, E: FieldElement<BaseField = A::BaseElement>> ConstraintEvaluato
         // memory to hold all transition constraint evaluations (before they are merged into a
         // single value) so that we can check their degree late
         #[cfg(not(debug_assertions))]
-        let mut evaluation_table =
-            ConstraintEvaluationTable::<A::BaseElement, E>::new(domain, self.divisors.clone());
+        let mut evaluation_table = ConstraintEvaluationTable::<A::BaseElement, E>::new(
+            domain,
+            self.divisors.clone(),
+            divisor_cache,
+        );
         #[cfg(debug_assertions)]
         let mut evaluation_table = ConstraintEvaluationTable::<A::BaseElement, E>::new(
             domain,
             self.divisors.clone(),
+            divisor_cache,
             self.transition_constraint_degrees.to_vec(),
         );
 
-        // when `concurrent` feature is enabled, break the evaluation table into multiple fragments
-        // to evaluate them into multiple threads; unless the constraint evaluation domain is small,
-        // then don't bother with concurrent evaluation
-
-        #[cfg(not(feature = "concurrent"))]
-        let num_fragments = 1;
-
-        #[cfg(feature = "concurrent")]
-        let num_fragments = if domain.ce_domain_size() >= MIN_CONCURRENT_DOMAIN_SIZE {
-            rayon::current_num_threads().next_power_of_two()
-        } else {
-            1
-        };
-
-        let mut fragments = evaluation_table.fragments(num_fragments);
-        iter_mut!(fragments).for_each(|fragment| self.evaluate_fragment(trace, domain, fragment));
+        match trace {
+            TraceLde::Table(trace) => {
+                let lde_shift = domain.ce_to_lde_blowup().trailing_zeros();
+                let fragment_size = self.get_fragment_size(
+                    scheduling,
+                    domain,
+                    domain.ce_domain_size(),
+                    trace.width(),
+                    |step, frame| trace.read_frame_into(step << lde_shift, frame),
+                );
+                let num_fragments = get_num_fragments(domain.ce_domain_size(), fragment_size);
+                let num_evaluated = AtomicUsize::new(0);
+                let mut fragments = evaluation_table.fragments(num_fragments);
+                iter_mut!(fragments).for_each(|fragment| {
+                    self.evaluate_fragment(trace, domain, fragment);
+                    let n = num_evaluated.fetch_add(1, Ordering::Relaxed) + 1;
+                    observer.on_constraints_evaluated(n * 100 / num_fragments);
+                });
+            }
+            TraceLde::Chunked(trace) | TraceLde::Discarded(trace) => {
+                let num_cosets = domain.trace_to_ce_blowup();
+                for coset_idx in 0..num_cosets {
+                    let mut coset_table = evaluation_table.new_coset_table(coset_idx);
+                    self.evaluate_coset(trace, domain, coset_idx, scheduling, &mut coset_table);
+                    evaluation_table.update_coset(coset_idx, coset_table)?;
+                    observer.on_constraints_evaluated((coset_idx + 1) * 100 / num_cosets);
+                }
+            }
+        }
 
         // when in debug mode, make sure expected transition constraint degrees align with
         // actual degrees we got during constraint evaluation
         #[cfg(debug_assertions)]
-        evaluation_table.validate_transition_degrees();
+        evaluation_table.validate_transition_degrees()?;
 
-        evaluation_table
+        Ok(evaluation_table)
     }
 
     // EVALUATION HELPERS
@@ -173,27 +228,153 @@ impl<'a, A: Air, E: FieldElement<BaseField = A::BaseElement>> ConstraintEvaluato
             // evaluation domain, into a step in LDE domain, in case these domains are different
             trace.read_frame_into(step << lde_shift, &mut ev_frame);
 
-            // evaluate transition constraints and save the merged result the first slot of the
-            // evaluations buffer
-            evaluations[0] =
-                self.evaluate_transition_constraints(&ev_frame, x, step, &mut t_evaluations);
-
-            // when in debug mode, save transition constraint evaluations
+            // evaluate constraints and record the result in the evaluation table
+            self.evaluate_row(&ev_frame, x, step, &mut t_evaluations, &mut evaluations);
             #[cfg(debug_assertions)]
-            fragment.update_transition_evaluations(step, &t_evaluations);
-
-            // evaluate boundary constraints; the results go into remaining slots of the
-            // evaluations buffer
-            self.evaluate_boundary_constraints(ev_frame.current(), x, step, &mut evaluations[1..]);
-
-            // record the result in the evaluation table
-            fragment.update_row(i, &evaluations);
+            fragment.update_transition_evaluations(i, &t_evaluations);
+            fragment.update_row(i, x, &evaluations);
 
             // update x to the next value
             x *= g;
         }
     }
 
+    /// Evaluates constraints over a single coset of the constraint evaluation domain, and
+    /// writes the results into the specified `coset_table` with one row per point of the coset.
+    ///
+    /// The constraint evaluation domain consists of `ce_blowup` cosets of the trace domain, such
+    /// that step `i` of the coset at index `coset_idx` corresponds to step
+    /// `i * ce_blowup + coset_idx` of the constraint evaluation domain. Thus, the next row of
+    /// the execution trace for every step of the coset is located at the next step of the same
+    /// coset.
+    fn evaluate_coset(
+        &self,
+        trace: &ChunkedTraceTable<A::BaseElement>,
+        domain: &StarkDomain<A::BaseElement>,
+        coset_idx: usize,
+        scheduling: FragmentScheduling,
+        coset_table: &mut ConstraintEvaluationTable<A::BaseElement, E>,
+    ) {
+        let ce_blowup = domain.trace_to_ce_blowup();
+        let trace_length = domain.trace_length();
+
+        // evaluate trace polynomials over the coset
+        let coset_offset =
+            domain.offset() * domain.ce_domain_generator().exp((coset_idx as u64).into());
+        let coset = trace.evaluate_coset(coset_offset);
+
+        let fragment_size = self.get_fragment_size(
+            scheduling,
+            domain,
+            trace_length,
+            coset.len(),
+            |row, frame| read_coset_frame(&coset, row, frame),
+        );
+        let num_fragments = get_num_fragments(trace_length, fragment_size);
+        let mut fragments = coset_table.fragments(num_fragments);
+        iter_mut!(fragments).for_each(|fragment| {
+            let mut ev_frame = EvaluationFrame::new(coset.len());
+            let mut evaluations = vec![E::ZERO; fragment.num_columns()];
+            let mut t_evaluations =
+                vec![A::BaseElement::ZERO; self.air.num_transition_constraints()];
+
+            // pre-compute values needed to determine x coordinates in the coset; the coset is
+            // a shifted trace domain, thus x is updated by the trace domain generator
+            let g = domain.ce_domain_generator().exp((ce_blowup as u64).into());
+            let mut x = coset_offset * g.exp((fragment.offset() as u64).into());
+
+            for i in 0..fragment.num_rows() {
+                let row = i + fragment.offset();
+                let step = row * ce_blowup + coset_idx;
+
+                // read current and next rows of the coset into the frame buffer
+                read_coset_frame(&coset, row, &mut ev_frame);
+
+                // evaluate constraints and record the result in the coset table
+                self.evaluate_row(&ev_frame, x, step, &mut t_evaluations, &mut evaluations);
+                #[cfg(debug_assertions)]
+                fragment.update_transition_evaluations(i, &t_evaluations);
+                fragment.update_row(i, x, &evaluations);
+
+                x *= g;
+            }
+        });
+    }
+
+    /// Returns the number of rows in the fragments into which a table with the specified number
+    /// of rows should be broken under the specified `scheduling`, or None if the table should
+    /// be broken into one fragment per thread.
+    ///
+    /// For auto-tuned scheduling, constraints are evaluated over the first few rows of the
+    /// table, and the fragment size is derived from the time this takes; frames for these rows
+    /// are read via `read_frame`, and the results are discarded.
+    #[allow(unused_variables)]
+    fn get_fragment_size(
+        &self,
+        scheduling: FragmentScheduling,
+        domain: &StarkDomain<A::BaseElement>,
+        num_rows: usize,
+        trace_width: usize,
+        read_frame: impl Fn(usize, &mut EvaluationFrame<A::BaseElement>),
+    ) -> Option<usize> {
+        match scheduling {
+            FragmentScheduling::Static => None,
+            FragmentScheduling::WorkStealing(fragment_size) => Some(fragment_size),
+            #[cfg(not(feature = "concurrent"))]
+            FragmentScheduling::AutoTuned => None,
+            #[cfg(feature = "concurrent")]
+            FragmentScheduling::AutoTuned => {
+                if num_rows < MIN_CONCURRENT_DOMAIN_SIZE {
+                    return None;
+                }
+
+                let mut ev_frame = EvaluationFrame::new(trace_width);
+                let mut evaluations = vec![E::ZERO; self.divisors.len()];
+                let mut t_evaluations =
+                    vec![A::BaseElement::ZERO; self.air.num_transition_constraints()];
+
+                // time evaluation of constraints over the calibration rows; the values of x do
+                // not need to match the rows as the results are discarded
+                let g = domain.ce_domain_generator();
+                let mut x = domain.offset();
+                let now = Instant::now();
+                for row in 0..CALIBRATION_ROWS {
+                    read_frame(row, &mut ev_frame);
+                    self.evaluate_row(&ev_frame, x, row, &mut t_evaluations, &mut evaluations);
+                    x *= g;
+                }
+                let row_time = now.elapsed().as_nanos() / CALIBRATION_ROWS as u128;
+
+                // make sure there are at least as many fragments as there are threads
+                let max_fragment_size = num_rows / rayon::current_num_threads().next_power_of_two();
+                let fragment_size = (TARGET_FRAGMENT_TIME_NS / row_time.max(1)) as usize;
+                Some(fragment_size.min(max_fragment_size))
+            }
+        }
+    }
+
+    /// Evaluates transition and boundary constraints against the specified evaluation `frame`
+    /// at the specified step of the constraint evaluation domain. The merged result of
+    /// transition constraints is saved into the first slot of the `evaluations` buffer, while
+    /// the results for boundary constraint groups go into the remaining slots. Individual
+    /// transition constraint evaluations are saved into the `t_evaluations` buffer.
+    fn evaluate_row(
+        &self,
+        frame: &EvaluationFrame<A::BaseElement>,
+        x: A::BaseElement,
+        step: usize,
+        t_evaluations: &mut [A::BaseElement],
+        evaluations: &mut [E],
+    ) {
+        // evaluate transition constraints and save the merged result the first slot of the
+        // evaluations buffer
+        evaluations[0] = self.evaluate_transition_constraints(frame, x, step, t_evaluations);
+
+        // evaluate boundary constraints; the results go into remaining slots of the
+        // evaluations buffer
+        self.evaluate_boundary_constraints(frame.current(), x, step, &mut evaluations[1..]);
+    }
+
     /// Evaluates transition constraints at the specified step of the execution trace. `step` is
     /// the step in the constraint evaluation, and `x` is the corresponding domain value. That
     /// is, x = s * g^step, where g is the generator of the constraint evaluation domain, and s
@@ -251,3 +432,45 @@ impl<'a, A: Air, E: FieldElement<BaseField = A::BaseElement>> ConstraintEvaluato
         }
     }
 }
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the number of fragments into which a table with the specified number of rows should
+/// be broken for constraint evaluation.
+///
+/// When `concurrent` feature is enabled, the table is broken into multiple fragments to evaluate
+/// them in multiple threads; unless the table is small, then don't bother with concurrent
+/// evaluation. If `fragment_size` is specified, it is rounded up to the next power of two (but
+/// no smaller than [MIN_FRAGMENT_SIZE]) and the table is broken into fragments of this size;
+/// otherwise, the table is broken into one fragment per thread.
+#[allow(unused_variables)]
+fn get_num_fragments(num_rows: usize, fragment_size: Option<usize>) -> usize {
+    #[cfg(not(feature = "concurrent"))]
+    return 1;
+
+    #[cfg(feature = "concurrent")]
+    if num_rows >= MIN_CONCURRENT_DOMAIN_SIZE {
+        match fragment_size {
+            Some(fragment_size) => {
+                let fragment_size = fragment_size.next_power_of_two().max(MIN_FRAGMENT_SIZE);
+                num_rows / fragment_size.min(num_rows)
+            }
+            None => rayon::current_num_threads().next_power_of_two(),
+        }
+    } else {
+        1
+    }
+}
+
+/// Reads the current and the next rows at the specified row of a trace `coset` into the `frame`;
+/// at the end of the coset, the next row wraps around to the first row.
+fn read_coset_frame<B: StarkField>(coset: &[Vec<B>], row: usize, frame: &mut EvaluationFrame<B>) {
+    let next_row = (row + 1) % coset[0].len();
+    for (register, value) in coset.iter().zip(frame.current_mut()) {
+        *value = register[row];
+    }
+    for (register, value) in coset.iter().zip(frame.next_mut()) {
+        *value = register[next_row];
+    }
+}
@@ -3,7 +3,10 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{trace::TraceTable, ProverError, StarkDomain};
+use super::{
+    trace::{ChunkedTraceTable, TraceLde, TraceTable},
+    FragmentScheduling, ProverError, ProverObserver, StarkDomain,
+};
 
 mod boundary;
 use boundary::BoundaryConstraintGroup;
@@ -18,7 +21,7 @@ mod composition_poly;
 pub use composition_poly::CompositionPoly;
 
 mod evaluation_table;
-pub use evaluation_table::ConstraintEvaluationTable;
+pub use evaluation_table::{ConstraintEvaluationTable, DivisorCache};
 
 mod commitment;
 pub use commitment::ConstraintCommitment;
@@ -0,0 +1,154 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{constraints::DivisorCache, BufferPool, FragmentScheduling, StarkDomain};
+use air::Air;
+use math::StarkField;
+use utils::collections::Vec;
+
+#[cfg(not(feature = "std"))]
+use alloc::boxed::Box;
+
+// PROVER CONTEXT
+// ================================================================================================
+/// Precomputed values which can be reused across many proofs of computations with the same trace
+/// shape.
+///
+/// Before a proof is generated, the prover builds twiddles and inverse twiddles for the trace and
+/// constraint evaluation domains, and, when the constraint composition polynomial is built,
+/// inverse evaluations of constraint divisors over the constraint evaluation domain. For proofs
+/// of many small computations, these values take a noticeable fraction of proof generation time;
+/// a context caches them keyed by trace length, constraint evaluation domain size, LDE domain
+/// size, and domain offset, so that they are computed only once for every shape. Proofs
+/// generated via [prove_with_context()](crate::prove_with_context) are identical to the proofs
+/// generated via [prove()](crate::prove).
+///
+/// Buffers for Merkle tree leaves are not cached, as they are moved into the Merkle trees which
+/// are then included into the proof.
+///
+/// A context holds values for all shapes it has been used with; if the number of distinct
+/// shapes is large, the context can be emptied via [ProverContext::clear()].
+///
+/// A context also defines how the constraint evaluation table is broken into fragments which
+/// are evaluated in parallel (see [FragmentScheduling]); by default, the table is broken into
+/// one fragment per thread.
+///
+/// Lastly, a context can hold a [BufferPool] from which buffers for the extended execution trace
+/// are taken, and to which they are returned once the proof is built; by default, these buffers
+/// are allocated and freed for every proof.
+pub struct ProverContext<B: StarkField> {
+    shapes: Vec<PrecomputedShape<B>>,
+    fragment_scheduling: FragmentScheduling,
+    buffer_pool: Option<Box<dyn BufferPool<B>>>,
+}
+
+struct PrecomputedShape<B: StarkField> {
+    trace_length: usize,
+    ce_domain_size: usize,
+    lde_domain_size: usize,
+    domain_offset: B,
+    domain: StarkDomain<B>,
+    divisors: DivisorCache<B>,
+}
+
+impl<B: StarkField> ProverContext<B> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new empty prover context.
+    pub fn new() -> Self {
+        ProverContext {
+            shapes: Vec::new(),
+            fragment_scheduling: FragmentScheduling::Static,
+            buffer_pool: None,
+        }
+    }
+
+    /// Returns this context updated to schedule constraint evaluation as specified by
+    /// `fragment_scheduling`.
+    pub fn with_fragment_scheduling(mut self, fragment_scheduling: FragmentScheduling) -> Self {
+        self.fragment_scheduling = fragment_scheduling;
+        self
+    }
+
+    /// Returns this context updated to take buffers for the extended execution trace from the
+    /// specified `buffer_pool`.
+    pub fn with_buffer_pool(mut self, buffer_pool: impl BufferPool<B> + 'static) -> Self {
+        self.buffer_pool = Some(Box::new(buffer_pool));
+        self
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of trace shapes for which values are cached in this context.
+    pub fn num_shapes(&self) -> usize {
+        self.shapes.len()
+    }
+
+    /// Returns the schedule for constraint evaluation defined by this context.
+    pub fn fragment_scheduling(&self) -> FragmentScheduling {
+        self.fragment_scheduling
+    }
+
+    /// Removes all cached values from this context.
+    pub fn clear(&mut self) {
+        self.shapes.clear();
+    }
+
+    // CACHE ACCESS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the domain and the divisor cache for the trace shape of the specified `air`
+    /// together with the buffer pool of this context; if the shape has not been seen by this
+    /// context before, the domain is built and the divisor cache is empty.
+    #[allow(clippy::type_complexity)]
+    pub(crate) fn get_or_build<A: Air<BaseElement = B>>(
+        &mut self,
+        air: &A,
+    ) -> (
+        &StarkDomain<B>,
+        &mut DivisorCache<B>,
+        Option<&mut dyn BufferPool<B>>,
+    ) {
+        let trace_length = air.trace_length();
+        let ce_domain_size = air.ce_domain_size();
+        let lde_domain_size = air.lde_domain_size();
+        let domain_offset = air.domain_offset();
+
+        let position = self.shapes.iter().position(|shape| {
+            shape.trace_length == trace_length
+                && shape.ce_domain_size == ce_domain_size
+                && shape.lde_domain_size == lde_domain_size
+                && shape.domain_offset == domain_offset
+        });
+        let idx = match position {
+            Some(idx) => idx,
+            None => {
+                self.shapes.push(PrecomputedShape {
+                    trace_length,
+                    ce_domain_size,
+                    lde_domain_size,
+                    domain_offset,
+                    domain: StarkDomain::new(air),
+                    divisors: DivisorCache::new(),
+                });
+                self.shapes.len() - 1
+            }
+        };
+
+        let shape = &mut self.shapes[idx];
+        (
+            &shape.domain,
+            &mut shape.divisors,
+            self.buffer_pool.as_deref_mut().map(|pool| pool as _),
+        )
+    }
+}
+
+impl<B: StarkField> Default for ProverContext<B> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
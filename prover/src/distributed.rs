@@ -0,0 +1,331 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{trace::hash_rows, ProverCheckpoint, Trace};
+use air::{proof::Context, Air, HashFunction};
+use crypto::{
+    hashers::{Blake3_192, Blake3_256, Sha3_256},
+    ElementHasher, Hasher,
+};
+use math::{fft, log2, StarkField};
+use utils::{
+    collections::Vec, iter, iter_mut, ByteReader, ByteWriter, Deserializable, DeserializationError,
+    Serializable,
+};
+
+#[cfg(feature = "concurrent")]
+use utils::iterators::*;
+
+// TRACE COMMITMENT JOB
+// ================================================================================================
+/// A unit of work needed to commit to the extended execution trace which can be executed on a
+/// separate machine.
+///
+/// The LDE domain consists of `blowup` cosets of the trace domain, such that row `i` of the
+/// extended trace belongs to coset `i % blowup`. A job evaluates all trace polynomials over a
+/// single coset, and hashes the resulting rows separately for each trace segment. Thus, a coordinator can commit to the extended
+/// trace as follows:
+///
+/// 1. Interpolate the execution trace into trace polynomials via [build_trace_polys()].
+/// 2. Create a job for every coset of the LDE domain via [TraceCommitmentJob::build_all()],
+///    and send the jobs together with the trace polynomials to workers.
+/// 3. Execute the jobs on the workers via [TraceCommitmentJob::execute()], and send the
+///    results back to the coordinator.
+/// 4. Assemble the results into a [ProverCheckpoint] via [merge_trace_commitments()], and
+///    generate the proof from this checkpoint via [prove_with_checkpoints()](crate::prove_with_checkpoints).
+///
+/// The resulting proof is exactly the same as the proof generated on a single machine. Jobs
+/// and their results can be sent over the wire via [Serializable] and [Deserializable] traits.
+///
+/// Constraint evaluation is not distributed in this way, as it depends on randomness drawn
+/// after the prover has committed to the extended trace; it is executed by the coordinator.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceCommitmentJob {
+    context: Context,
+    coset_idx: usize,
+}
+
+impl TraceCommitmentJob {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns jobs for all cosets of the LDE domain of the computation described by the
+    /// specified `air`.
+    pub fn build_all<A: Air>(air: &A) -> Vec<Self> {
+        let context = Context::new::<A::BaseElement>(air.trace_info(), air.options().clone());
+        Self::build_all_for_context(&context)
+    }
+
+    /// Returns jobs for all cosets of the LDE domain of the computation described by the
+    /// specified `context`.
+    pub(super) fn build_all_for_context(context: &Context) -> Vec<Self> {
+        (0..context.options().blowup_factor())
+            .map(|coset_idx| TraceCommitmentJob {
+                context: context.clone(),
+                coset_idx,
+            })
+            .collect()
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the index of the LDE domain coset processed by this job.
+    pub fn coset_idx(&self) -> usize {
+        self.coset_idx
+    }
+
+    // JOB EXECUTION
+    // --------------------------------------------------------------------------------------------
+    /// Evaluates the specified trace polynomials over the LDE domain coset of this job, and
+    /// returns hashes of the resulting rows of the extended trace for each trace segment.
+    ///
+    /// # Panics
+    /// Panics if the number or the size of `trace_polys` does not match the execution trace
+    /// for which this job was created.
+    pub fn execute<B: StarkField>(&self, trace_polys: &[Vec<B>]) -> TraceCommitmentJobResult {
+        let trace_length = self.context.trace_length();
+        assert_eq!(
+            trace_polys.len(),
+            self.context.trace_width(),
+            "number of trace polynomials must be {}, but was {}",
+            self.context.trace_width(),
+            trace_polys.len()
+        );
+        for poly in trace_polys.iter() {
+            assert_eq!(
+                poly.len(),
+                trace_length,
+                "all trace polynomials must have size {}",
+                trace_length
+            );
+        }
+
+        // the coset offset is computed as `s * g^coset_idx`, where `s` is the LDE domain offset
+        // and `g` is the generator of the LDE domain
+        let g = B::get_root_of_unity(log2(self.context.lde_domain_size()));
+        let coset_offset =
+            self.context.options().domain_offset::<B>() * g.exp((self.coset_idx as u64).into());
+
+        let twiddles = fft::get_twiddles::<B>(trace_length);
+        let coset = iter!(trace_polys)
+            .map(|poly| fft::evaluate_poly_with_offset(poly, &twiddles, coset_offset, 1))
+            .collect::<Vec<_>>();
+
+        let segments = self
+            .context
+            .options()
+            .trace_segments(self.context.trace_width());
+        let mut row_hashes = Vec::new();
+        for segment in segments {
+            let segment = &coset[segment];
+            match self.context.options().hash_fn() {
+                HashFunction::Blake3_256 => {
+                    serialize_row_hashes::<Blake3_256<B>>(segment, &mut row_hashes)
+                }
+                HashFunction::Blake3_192 => {
+                    serialize_row_hashes::<Blake3_192<B>>(segment, &mut row_hashes)
+                }
+                HashFunction::Sha3_256 => {
+                    serialize_row_hashes::<Sha3_256<B>>(segment, &mut row_hashes)
+                }
+            }
+        }
+
+        TraceCommitmentJobResult {
+            coset_idx: self.coset_idx,
+            row_hashes,
+        }
+    }
+}
+
+impl Serializable for TraceCommitmentJob {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.context.write_into(target);
+        target.write_u32(self.coset_idx as u32);
+    }
+}
+
+impl Deserializable for TraceCommitmentJob {
+    /// Reads a trace commitment job from the specified `source` and returns the result.
+    ///
+    /// # Errors
+    /// Returns an error if a valid job could not be read from the specified `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let context = Context::read_from(source)?;
+        let coset_idx = source.read_u32()? as usize;
+        if coset_idx >= context.options().blowup_factor() {
+            return Err(DeserializationError::InvalidValue(format!(
+                "coset index must be smaller than {}, but was {}",
+                context.options().blowup_factor(),
+                coset_idx
+            )));
+        }
+        Ok(TraceCommitmentJob { context, coset_idx })
+    }
+}
+
+// TRACE COMMITMENT JOB RESULT
+// ================================================================================================
+/// Hashes of the rows of the extended execution trace over a single coset of the LDE domain,
+/// as computed by [TraceCommitmentJob::execute()]; hashes of all rows of a trace segment follow
+/// hashes of the rows of the previous segment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceCommitmentJobResult {
+    coset_idx: usize,
+    row_hashes: Vec<u8>,
+}
+
+impl TraceCommitmentJobResult {
+    /// Returns the index of the LDE domain coset for which this result was computed.
+    pub fn coset_idx(&self) -> usize {
+        self.coset_idx
+    }
+}
+
+impl Serializable for TraceCommitmentJobResult {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u32(self.coset_idx as u32);
+        target.write_u8_slice(&(self.row_hashes.len() as u64).to_le_bytes());
+        target.write_u8_slice(&self.row_hashes);
+    }
+}
+
+impl Deserializable for TraceCommitmentJobResult {
+    /// Reads a trace commitment job result from the specified `source` and returns the result.
+    ///
+    /// # Errors
+    /// Returns an error if a valid result could not be read from the specified `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let coset_idx = source.read_u32()? as usize;
+        let num_bytes = source.read_u64()? as usize;
+        let row_hashes = source.read_u8_vec(num_bytes)?;
+        Ok(TraceCommitmentJobResult {
+            coset_idx,
+            row_hashes,
+        })
+    }
+}
+
+// COORDINATOR FUNCTIONS
+// ================================================================================================
+
+/// Interpolates all registers of the specified trace into polynomials over the trace domain.
+///
+/// The polynomials are returned in coefficient form, and can be passed to
+/// [TraceCommitmentJob::execute()] and [merge_trace_commitments()].
+pub fn build_trace_polys<T: Trace>(trace: T) -> Vec<Vec<T::BaseField>> {
+    let inv_twiddles = fft::get_inv_twiddles::<T::BaseField>(trace.length());
+    let mut polys = trace.into_columns();
+    iter_mut!(polys)
+        .for_each(|register_trace| fft::interpolate_poly(register_trace, &inv_twiddles));
+    polys
+}
+
+/// Assembles results of trace commitment jobs into a checkpoint from which proof generation
+/// can be resumed via [prove_with_checkpoints()](crate::prove_with_checkpoints).
+///
+/// `air` and `pub_inputs` must be the same as the ones for which the jobs were created, and
+/// `trace_polys` must be the polynomials passed to the jobs. Results can be provided in any
+/// order.
+///
+/// # Panics
+/// Panics if:
+/// * `results` do not contain exactly one result for every coset of the LDE domain.
+/// * Any of the results contains a number of row hashes different from the trace length.
+pub fn merge_trace_commitments<A: Air>(
+    air: &A,
+    pub_inputs: &A::PublicInputs,
+    trace_polys: &[Vec<A::BaseElement>],
+    results: Vec<TraceCommitmentJobResult>,
+) -> ProverCheckpoint {
+    let context = Context::new::<A::BaseElement>(air.trace_info(), air.options().clone());
+    let leaves = merge_row_hashes::<A::BaseElement>(&context, results);
+
+    let mut pub_inputs_bytes = Vec::new();
+    pub_inputs.write_into(&mut pub_inputs_bytes);
+    ProverCheckpoint::from_trace_commitment(
+        context,
+        pub_inputs_bytes,
+        trace_polys.iter().map(|poly| poly.as_slice()),
+        leaves,
+    )
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Assembles row hashes from the results of trace commitment jobs for the computation described
+/// by the specified `context` into serialized leaves of the trace Merkle trees; leaves of the
+/// tree for each trace segment follow leaves of the tree for the previous segment.
+///
+/// # Panics
+/// Panics if:
+/// * `results` do not contain exactly one result for every coset of the LDE domain.
+/// * Any of the results contains a number of row hashes different from the trace length times
+///   the number of trace segments.
+pub(super) fn merge_row_hashes<B: StarkField>(
+    context: &Context,
+    mut results: Vec<TraceCommitmentJobResult>,
+) -> Vec<u8> {
+    let blowup = context.options().blowup_factor();
+    let trace_length = context.trace_length();
+    let digest_size = get_digest_size::<B>(context.options().hash_fn());
+    let num_segments = context
+        .options()
+        .trace_segments(context.trace_width())
+        .len();
+
+    results.sort_by_key(|result| result.coset_idx);
+    assert!(
+        results.len() == blowup && results.iter().enumerate().all(|(i, r)| r.coset_idx == i),
+        "expected exactly one result for each of {} LDE domain cosets",
+        blowup
+    );
+    for result in results.iter() {
+        assert_eq!(
+            result.row_hashes.len(),
+            num_segments * trace_length * digest_size,
+            "result for coset {} must contain {} row hashes",
+            result.coset_idx,
+            num_segments * trace_length
+        );
+    }
+
+    // row `i` of coset `j` is row `i * blowup + j` of the extended trace
+    let mut leaves = Vec::with_capacity(num_segments * trace_length * blowup * digest_size);
+    for segment_idx in 0..num_segments {
+        for i in segment_idx * trace_length..(segment_idx + 1) * trace_length {
+            for result in results.iter() {
+                leaves
+                    .extend_from_slice(&result.row_hashes[i * digest_size..(i + 1) * digest_size]);
+            }
+        }
+    }
+    leaves
+}
+
+/// Returns the number of bytes in a serialized digest of the specified hash function.
+pub(super) fn get_digest_size<B: StarkField>(hash_fn: HashFunction) -> usize {
+    match hash_fn {
+        HashFunction::Blake3_256 => digest_size::<Blake3_256<B>>(),
+        HashFunction::Blake3_192 => digest_size::<Blake3_192<B>>(),
+        HashFunction::Sha3_256 => digest_size::<Sha3_256<B>>(),
+    }
+}
+
+/// Hashes rows of the specified segment of a coset of the extended trace, and writes the
+/// serialized hashes into the `target`.
+fn serialize_row_hashes<H: ElementHasher>(segment: &[Vec<H::BaseField>], target: &mut Vec<u8>) {
+    H::Digest::write_batch_into(&hash_rows::<H>(segment), target);
+}
+
+/// Returns the number of bytes in a serialized digest of the specified hash function.
+fn digest_size<H: Hasher>() -> usize {
+    let mut bytes = Vec::new();
+    H::Digest::default().write_into(&mut bytes);
+    bytes.len()
+}
@@ -15,10 +15,17 @@ pub struct StarkDomain<B: StarkField> {
     /// vector is half the length of the trace domain size.
     trace_twiddles: Vec<B>,
 
+    /// Inverse twiddles which can be used to interpolate polynomials in the trace domain.
+    trace_inv_twiddles: Vec<B>,
+
     /// Twiddles which can be used to evaluate polynomials in the constraint evaluation domain.
     /// Length of this vector is half the length of constraint evaluation domain size.
     ce_twiddles: Vec<B>,
 
+    /// Inverse twiddles which can be used to interpolate polynomials in the constraint
+    /// evaluation domain.
+    ce_inv_twiddles: Vec<B>,
+
     /// LDE domain size / constraint evaluation domain size
     ce_to_lde_blowup: usize,
 
@@ -32,11 +39,11 @@ pub struct StarkDomain<B: StarkField> {
 impl<B: StarkField> StarkDomain<B> {
     /// Returns a new STARK domain initialized with the provided `context`.
     pub fn new<A: Air<BaseElement = B>>(air: &A) -> Self {
-        let trace_twiddles = fft::get_twiddles(air.trace_length());
-        let ce_twiddles = fft::get_twiddles(air.ce_domain_size());
         StarkDomain {
-            trace_twiddles,
-            ce_twiddles,
+            trace_twiddles: fft::get_twiddles(air.trace_length()),
+            trace_inv_twiddles: fft::get_inv_twiddles(air.trace_length()),
+            ce_twiddles: fft::get_twiddles(air.ce_domain_size()),
+            ce_inv_twiddles: fft::get_inv_twiddles(air.ce_domain_size()),
             ce_to_lde_blowup: air.lde_domain_size() / air.ce_domain_size(),
             domain_offset: air.domain_offset(),
         }
@@ -55,6 +62,11 @@ impl<B: StarkField> StarkDomain<B> {
         &self.trace_twiddles
     }
 
+    /// Returns inverse twiddles which can be used to interpolate trace polynomials.
+    pub fn trace_inv_twiddles(&self) -> &[B] {
+        &self.trace_inv_twiddles
+    }
+
     /// Returns blowup factor from trace to constraint evaluation domain.
     #[allow(dead_code)]
     pub fn trace_to_ce_blowup(&self) -> usize {
@@ -80,10 +92,16 @@ impl<B: StarkField> StarkDomain<B> {
     }
 
     /// Returns twiddles which can be used to evaluate constraint polynomials.
+    #[allow(dead_code)]
     pub fn ce_twiddles(&self) -> &[B] {
         &self.ce_twiddles
     }
 
+    /// Returns inverse twiddles which can be used to interpolate constraint polynomials.
+    pub fn ce_inv_twiddles(&self) -> &[B] {
+        &self.ce_inv_twiddles
+    }
+
     /// Returns blowup factor from constraint evaluation to LDE domain.
     pub fn ce_to_lde_blowup(&self) -> usize {
         self.ce_to_lde_blowup
@@ -6,29 +6,210 @@
 //! Contains common error types for prover and verifier.
 
 use core::fmt;
+use math::StarkField;
+use utils::string::String;
 
 // PROVER ERROR
 // ================================================================================================
 /// Represents an error returned by the prover during an execution of the protocol.
+///
+/// Every error is tagged with the [ProverStage] at which it occurred (see
+/// [ProverError::stage()]), and carries indices of the offending constraints, columns, or rows
+/// together with the parameters of the domain over which they were evaluated.
 #[derive(Debug, PartialEq)]
 pub enum ProverError {
     /// This error occurs when a transition constraint evaluated over a specific execution trace
-    /// does not evaluate to zero at any of the steps.
-    UnsatisfiedTransitionConstraintError(usize),
-    /// This error occurs when polynomials built from the columns of a constraint evaluation
-    /// table do not all have the same degree.
-    MismatchedConstraintPolynomialDegree(usize, usize),
+    /// does not evaluate to zero at one of the steps.
+    UnsatisfiedTransitionConstraint {
+        /// Index of the constraint which was not satisfied.
+        constraint: usize,
+        /// Step of the trace at which the constraint did not evaluate to zero.
+        step: usize,
+    },
+    /// This error occurs when a polynomial built from a column of a constraint evaluation table
+    /// does not have the expected degree after division by its divisor. This check is performed
+    /// only in debug mode.
+    MismatchedConstraintPolynomialDegree {
+        /// Index of the column in the constraint evaluation table; column 0 contains merged
+        /// transition constraints, and column `i` contains merged boundary constraints of the
+        /// `i - 1`th boundary constraint group.
+        column: usize,
+        /// Divisor of the constraints merged into the column.
+        divisor: String,
+        /// Expected degree of the column polynomial after division.
+        expected: usize,
+        /// Actual degree of the column polynomial after division.
+        actual: usize,
+        /// Size of the constraint evaluation domain.
+        ce_domain_size: usize,
+    },
+    /// This error occurs when the actual degree of a transition constraint polynomial does not
+    /// match the degree implied by the constraint's degree descriptor. This check is performed
+    /// only in debug mode.
+    MismatchedTransitionConstraintDegree {
+        /// Index of the constraint.
+        constraint: usize,
+        /// Degree of the constraint polynomial implied by its degree descriptor.
+        expected: usize,
+        /// Actual degree of the constraint polynomial.
+        actual: usize,
+        /// Length of the execution trace.
+        trace_length: usize,
+    },
+    /// This error occurs when proof generation is resumed from a checkpoint which was created
+    /// for a different computation, different public inputs, or different proof options, or
+    /// when the state saved in the checkpoint is malformed.
+    InvalidCheckpoint,
+    /// This error occurs when a proof is generated from a trace commitment which was created for
+    /// an execution trace over a different field, or when the commitment is malformed.
+    InvalidTraceCommitment,
+    /// This error occurs when values of a register do not match the commitment to a public
+    /// column tied to this register, or when the register does not exist.
+    MismatchedColumnCommitment {
+        /// Index of the register tied to the column.
+        register: usize,
+    },
+    /// This error occurs when a row of a constraint evaluation table was not written during
+    /// constraint evaluation.
+    UnevaluatedConstraintRow {
+        /// Index of the first row which was not written.
+        row: usize,
+        /// Size of the constraint evaluation domain.
+        ce_domain_size: usize,
+    },
+}
+
+impl ProverError {
+    /// Returns the stage of proof generation at which this error occurred.
+    pub fn stage(&self) -> ProverStage {
+        match self {
+            Self::InvalidCheckpoint
+            | Self::InvalidTraceCommitment
+            | Self::MismatchedColumnCommitment { .. } => ProverStage::Setup,
+            Self::UnsatisfiedTransitionConstraint { .. }
+            | Self::MismatchedTransitionConstraintDegree { .. }
+            | Self::UnevaluatedConstraintRow { .. } => ProverStage::ConstraintEvaluation,
+            Self::MismatchedConstraintPolynomialDegree { .. } => ProverStage::ConstraintComposition,
+        }
+    }
 }
 
 impl fmt::Display for ProverError {
     #[rustfmt::skip]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} failed: ", self.stage())?;
         match self {
-            Self::UnsatisfiedTransitionConstraintError(step) => {
-                write!(f, "a transition constraint was not satisfied at step {}", step)
+            Self::UnsatisfiedTransitionConstraint { constraint, step } => {
+                write!(f, "transition constraint {} was not satisfied at step {}", constraint, step)
+            }
+            Self::MismatchedConstraintPolynomialDegree { column, divisor, expected, actual, ce_domain_size } => {
+                write!(f, "after division by {}, column {} of the constraint evaluation table over domain of size {} has degree {}; expected {}", divisor, column, ce_domain_size, actual, expected)
+            }
+            Self::MismatchedTransitionConstraintDegree { constraint, expected, actual, trace_length } => {
+                let (diff, direction) = if actual > expected {
+                    (actual - expected, "higher")
+                } else {
+                    (expected - actual, "lower")
+                };
+                write!(f, "degree of transition constraint {} is {} than declared; expected {}, but was {} (off by {}) for trace length {}", constraint, direction, expected, actual, diff, trace_length)
+            }
+            Self::InvalidCheckpoint => {
+                write!(f, "the checkpoint does not match the proof being generated or is malformed")
+            }
+            Self::InvalidTraceCommitment => {
+                write!(f, "the trace commitment does not match the computation or is malformed")
+            }
+            Self::MismatchedColumnCommitment { register } => {
+                write!(f, "register {} does not match its column commitment", register)
+            }
+            Self::UnevaluatedConstraintRow { row, ce_domain_size } => {
+                write!(f, "constraints were not evaluated at row {} of the constraint evaluation table with {} rows", row, ce_domain_size)
+            }
+        }
+    }
+}
+
+// PROVER STAGE
+// ================================================================================================
+/// Defines the stage of proof generation at which a [ProverError] occurred.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProverStage {
+    /// Setting up the state of the prover, including restoring the state from a checkpoint or
+    /// from a trace commitment, and checking registers against committed public columns.
+    Setup,
+    /// Evaluating constraints over the constraint evaluation domain.
+    ConstraintEvaluation,
+    /// Combining constraint evaluations into the constraint composition polynomial.
+    ConstraintComposition,
+}
+
+impl fmt::Display for ProverStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Setup => write!(f, "prover setup"),
+            Self::ConstraintEvaluation => write!(f, "constraint evaluation"),
+            Self::ConstraintComposition => write!(f, "constraint composition"),
+        }
+    }
+}
+
+// TRACE VALIDATION ERROR
+// ================================================================================================
+/// Represents an error returned when an execution trace is not valid against an AIR.
+#[derive(Debug, PartialEq)]
+pub enum TraceValidationError<B: StarkField> {
+    /// This error occurs when the width of the execution trace is different from the trace
+    /// width expected by the AIR. The error contains the expected and the actual width.
+    MismatchedTraceWidth(usize, usize),
+    /// This error occurs when a value in the execution trace does not match the value expected
+    /// by an assertion.
+    UnsatisfiedAssertion {
+        /// Register against which the assertion was made.
+        register: usize,
+        /// Step of the trace at which the assertion does not hold.
+        step: usize,
+        /// Value expected by the assertion.
+        expected: B,
+        /// Actual value in the execution trace.
+        actual: B,
+    },
+    /// This error occurs when a transition constraint does not evaluate to zero at a step of the
+    /// execution trace.
+    UnsatisfiedTransitionConstraint {
+        /// Index of the constraint which was not satisfied.
+        constraint: usize,
+        /// Step of the trace at which the constraint was evaluated against the current and the
+        /// next rows of the trace.
+        step: usize,
+        /// The value to which the constraint evaluated.
+        value: B,
+    },
+    /// This error occurs when values of a register do not repeat the cycle declared by the
+    /// trace. The error contains the index of the register and the declared cycle length.
+    MismatchedRegisterCycle(usize, usize),
+    /// This error occurs when values of a register do not match the commitment to a public
+    /// column tied to this register. The error contains the index of the register.
+    MismatchedColumnCommitment(usize),
+}
+
+impl<B: StarkField> fmt::Display for TraceValidationError<B> {
+    #[rustfmt::skip]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MismatchedTraceWidth(expected, actual) => {
+                write!(f, "inconsistent trace width: expected {}, but was {}", expected, actual)
+            }
+            Self::UnsatisfiedAssertion { register, step, expected, actual } => {
+                write!(f, "trace does not satisfy assertion trace({}, {}) == {}; actual value was {}", register, step, expected, actual)
+            }
+            Self::UnsatisfiedTransitionConstraint { constraint, step, value } => {
+                write!(f, "transition constraint {} did not evaluate to ZERO at step {}; it evaluated to {}", constraint, step, value)
+            }
+            Self::MismatchedRegisterCycle(register, cycle_length) => {
+                write!(f, "register {} does not repeat a cycle of length {}", register, cycle_length)
             }
-            Self::MismatchedConstraintPolynomialDegree(expected, actual) => {
-                write!(f, "the constraint polynomial's components do not all have the same degree; expected {}, but was {}", expected, actual)
+            Self::MismatchedColumnCommitment(register) => {
+                write!(f, "register {} does not match its column commitment", register)
             }
         }
     }
@@ -12,7 +12,17 @@
 //! When the crate is compiled with `concurrent` feature enabled, proof generation will be
 //! performed in multiple threads (usually, as many threads as there are logical cores on the
 //! machine). The number of threads can be configured via `RAYON_NUM_THREADS` environment
-//! variable.
+//! variable. Alternatively, proofs can be generated in a dedicated thread pool via
+//! [prove_in_pool()] function; this is useful when several proofs are generated concurrently
+//! in the same process. To track progress of proof generation, use [prove_with_observer()]
+//! function. When the crate is compiled with `profiling` feature enabled, per-stage wall time,
+//! memory usage, and number of hashed bytes can be obtained via [prove_with_profile()] function.
+//!
+//! Proof generation is deterministic: the same execution trace, public inputs, and proof options
+//! always result in byte-for-byte identical proofs. In particular, proofs do not depend on the
+//! number of threads used to generate them, on whether `concurrent` feature is enabled, or on the
+//! selected [ProvingMode] and [FragmentScheduling]. Thus, a proof can be re-generated on a
+//! different machine and compared against the original one byte by byte.
 //!
 //! # Usage
 //! To generate a proof that a computation was executed correctly, you'll need to do the
@@ -20,7 +30,8 @@
 //!
 //! 1. Define an *algebraic intermediate representation* (AIR) for your computation. This can
 //!    be done by implementing [Air] trait.
-//! 2. Execute your computation and record its execution trace in [ExecutionTrace] struct.
+//! 2. Execute your computation and record its execution trace in [ExecutionTrace] struct, or
+//!    in a custom struct implementing [Trace] trait.
 //! 3. Execute [prove()] function and supply the AIR of your computation together with its
 //!    execution trace as input parameters. The function will produce a instance of [StarkProof]
 //!    as an output.
@@ -40,28 +51,45 @@
 #[macro_use]
 extern crate alloc;
 
+#[cfg(not(feature = "std"))]
+use alloc::boxed::Box;
+
 pub use air::{
-    proof::StarkProof, Air, AirContext, Assertion, BoundaryConstraint, BoundaryConstraintGroup,
-    ConstraintCompositionCoefficients, ConstraintDivisor, DeepCompositionCoefficients,
-    EvaluationFrame, FieldExtension, HashFunction, ProofOptions, TraceInfo,
-    TransitionConstraintDegree, TransitionConstraintGroup,
+    proof::{self, StarkProof},
+    Air, AirContext, Assertion, BatchAir, BoundaryConstraint, BoundaryConstraintGroup,
+    CommittedColumn, CommittedInputsAir, ConstraintCompositionCoefficients, ConstraintDivisor,
+    ConstraintReport, DeepCompositionCoefficients, DistinctSampler, EvaluationFrame,
+    FieldExtension, HashFunction, PeriodicMask, ProofOptions, QuerySampler, QuerySampling,
+    StratifiedSampler, TraceInfo, TracePadding, TransitionConstraintDegree,
+    TransitionConstraintGroup, UniformSampler,
 };
 pub use utils::{
-    iterators, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+    cbor, iterators, table, ByteReader, ByteWriter, Deserializable, DeserializationError,
+    Serializable, SliceReader,
 };
 
-use fri::FriProver;
+#[cfg(feature = "concurrent")]
+pub use utils::rayon;
+
+pub use fri;
+use fri::{FriProver, LowDegreeProver, LowDegreeTest, StirProver};
 use utils::collections::Vec;
 
 pub use math;
 use math::{fft::infer_degree, FieldElement, StarkField};
 
 pub use crypto;
+pub use crypto::Transcript;
 use crypto::{
     hashers::{Blake3_192, Blake3_256, Sha3_256},
     ElementHasher,
 };
 
+use air::proof::Context;
+
+#[cfg(feature = "std")]
+pub use utils::WriteAdapter;
+
 #[cfg(feature = "std")]
 use log::debug;
 #[cfg(feature = "std")]
@@ -79,14 +107,50 @@ mod composer;
 use composer::DeepCompositionPoly;
 
 mod trace;
-use trace::TracePolyTable;
-pub use trace::{ExecutionTrace, ExecutionTraceFragment};
+use trace::{
+    validate_trace, BatchTrace, Column, CommittedRegisters, TraceLde, TracePolyTable, TraceSource,
+};
+pub use trace::{ExecutionTrace, ExecutionTraceFragment, Trace};
+
+mod mode;
+pub use mode::ProvingMode;
+
+mod observer;
+pub use observer::ProverObserver;
+
+#[cfg(feature = "profiling")]
+mod profiler;
+#[cfg(feature = "profiling")]
+use profiler::Profiler;
+#[cfg(feature = "profiling")]
+pub use profiler::{ProverProfile, StageProfile};
+
+mod context;
+pub use context::ProverContext;
+
+mod scheduling;
+pub use scheduling::FragmentScheduling;
+
+mod pool;
+pub use pool::{BufferPool, RecyclingPool};
+
+mod checkpoint;
+pub use checkpoint::{CheckpointStage, ProverCheckpoint, TraceCommitment};
+
+mod distributed;
+pub use distributed::{
+    build_trace_polys, merge_trace_commitments, TraceCommitmentJob, TraceCommitmentJobResult,
+};
 
 mod channel;
-use channel::ProverChannel;
+pub use channel::{DefaultProverChannel, ProverChannel};
+
+mod artifacts;
+use artifacts::ArtifactChannel;
+pub use artifacts::ProverArtifacts;
 
 mod errors;
-pub use errors::ProverError;
+pub use errors::{ProverError, ProverStage, TraceValidationError};
 
 #[cfg(test)]
 pub mod tests;
@@ -97,9 +161,10 @@ pub mod tests;
 ///
 /// Function parameters have the following meanings:
 /// * `AIR` is a type implementing [Air] trait for the computation. Among other things, it defines
-///    algebraic constraints which define the computation.
+///   algebraic constraints which define the computation.
 /// * `trace` is an execution trace of the computation executed against some set of inputs. These
-///   inputs may include both public and private inputs.
+///   inputs may include both public and private inputs. The trace can be an [ExecutionTrace], or
+///   any other type implementing [Trace] trait.
 /// * `pub_inputs` is the set of public inputs against which the computation was executed. These
 ///   these inputs will need to be shared with the verifier in order for them to verify the proof.
 /// * `options` defines basic protocol parameters such as: number of queries, blowup factor,
@@ -109,11 +174,472 @@ pub mod tests;
 /// The function returns a [StarkProof] attesting that the specified `trace` is a valid execution
 /// trace of the computation described by the specified `AIR` and generated using the specified
 /// public inputs.
-#[rustfmt::skip]
+///
+/// The extended execution trace is kept in memory in its entirety; to generate proofs for
+/// traces which are too big for this, use [prove_with_mode()] function.
 pub fn prove<AIR: Air>(
-    trace: ExecutionTrace<AIR::BaseElement>,
+    trace: impl Trace<BaseField = AIR::BaseElement>,
+    pub_inputs: AIR::PublicInputs,
+    options: ProofOptions,
+) -> Result<StarkProof, ProverError> {
+    prove_with_mode::<AIR>(trace, pub_inputs, options, ProvingMode::InMemory)
+}
+
+/// Checks whether the specified execution `trace` is valid against the computation described by
+/// the specified `AIR` for the specified public inputs.
+///
+/// This function performs the same checks the prover performs in debug mode before generating a
+/// proof, but it is available in release mode as well, and returns a description of the first
+/// failed check instead of panicking. Specifically, it checks that:
+/// * The width of the `trace` is the width expected by the AIR.
+/// * All assertions of the AIR hold against the `trace`.
+/// * All transition constraints of the AIR evaluate to zero at all steps of the `trace` except
+///   for the last one.
+/// * Registers tied to committed public columns match the column commitments.
+///
+/// Thus, it can be used as a pre-flight check before proof generation. Note that this is a
+/// relatively expensive operation as transition constraints are evaluated at every step of the
+/// trace in a single thread; still, it is much cheaper than generating a proof. Proof `options`
+/// are needed only to instantiate the AIR and to build column commitments.
+///
+/// # Errors
+/// Returns an error describing the first check which failed; checks are performed in the order
+/// listed above, and steps of the trace are checked in order.
+pub fn validate<AIR: Air>(
+    trace: &impl Trace<BaseField = AIR::BaseElement>,
+    pub_inputs: AIR::PublicInputs,
+    options: ProofOptions,
+) -> Result<(), TraceValidationError<AIR::BaseElement>> {
+    let air = AIR::new(trace.get_info(), pub_inputs, options);
+    validate_trace(trace, &air)
+}
+
+/// Returns a single STARK proof attesting to correct executions of many instances of the same
+/// computation.
+///
+/// The execution `traces` of all instances are stacked side by side into a single trace, and the
+/// proof is generated against [BatchAir] for the specified `AIR`; thus, all instances share
+/// commitments, FRI proof, and queries. The `pub_inputs` must contain public inputs of every
+/// instance in the same order as `traces`. Such a proof can be verified via `verify_batch()`
+/// function of the verifier crate.
+///
+/// Batching is most effective for small computations where the fixed cost of a proof dominates
+/// the cost of proving a single instance.
+///
+/// # Panics
+/// Panics if:
+/// * `traces` is empty, or the number of `traces` differs from the number of `pub_inputs`.
+/// * The traces differ in width, length, or metadata.
+/// * The combined width of all traces is greater than 255.
+pub fn prove_batch<AIR: Air, T: Trace<BaseField = AIR::BaseElement>>(
+    traces: Vec<T>,
+    pub_inputs: Vec<AIR::PublicInputs>,
+    options: ProofOptions,
+) -> Result<StarkProof, ProverError> {
+    assert_eq!(
+        traces.len(),
+        pub_inputs.len(),
+        "number of traces must be equal to the number of public inputs"
+    );
+    prove::<BatchAir<AIR>>(BatchTrace::new(traces), pub_inputs, options)
+}
+
+/// Returns a STARK proof attesting to a correct execution of a computation which can be verified
+/// against a commitment to the public inputs rather than against the inputs themselves.
+///
+/// The commitment to `pub_inputs` is computed via [CommittedInputsAir::commit_to_inputs()]
+/// function, the AIR is instantiated from this commitment, and the commitment is used in place
+/// of the public inputs to seed the public coin. Such a proof can be verified via
+/// `verify_with_input_commitment()` function of the verifier crate. Otherwise, this function
+/// works just like [prove()] function.
+pub fn prove_with_input_commitment<AIR: CommittedInputsAir>(
+    trace: impl Trace<BaseField = AIR::BaseElement>,
+    pub_inputs: AIR::PublicInputs,
+    options: ProofOptions,
+) -> Result<StarkProof, ProverError> {
+    // serialize the commitment to public inputs; it will be included in the seed for the public
+    // coin instead of the public inputs
+    let commitment = AIR::commit_to_inputs(&pub_inputs, &options);
+    let commitment_bytes = commitment.to_bytes();
+
+    // create an instance of AIR from the commitment; this way, the prover and the verifier are
+    // guaranteed to work with the same instance of the computation
+    let air = AIR::from_input_commitment(trace.get_info(), commitment, options);
+
+    #[cfg(debug_assertions)]
+    trace.validate(&air);
+
+    dispatch_proof(
+        air,
+        Some(trace),
+        commitment_bytes,
+        ProvingMode::InMemory,
+        &(),
+        None,
+        None,
+        None,
+    )
+}
+
+/// Returns a STARK proof attesting to a correct execution of a computation using the specified
+/// proving `mode`.
+///
+/// This function works just like [prove()] function, but also takes a [ProvingMode] parameter
+/// which defines how the low-degree extension of the execution trace is held in memory. Proofs
+/// generated in all modes are identical.
+pub fn prove_with_mode<AIR: Air>(
+    trace: impl Trace<BaseField = AIR::BaseElement>,
+    pub_inputs: AIR::PublicInputs,
+    options: ProofOptions,
+    mode: ProvingMode,
+) -> Result<StarkProof, ProverError> {
+    prove_with_observer::<AIR>(trace, pub_inputs, options, mode, &())
+}
+
+/// Returns a STARK proof attesting to a correct execution of a computation, and reports progress
+/// of proof generation to the specified `observer`.
+///
+/// This function works just like [prove_with_mode()] function, but also notifies the `observer`
+/// as proof generation stages are completed. See [ProverObserver] for the list of
+/// notifications.
+pub fn prove_with_observer<AIR: Air>(
+    trace: impl Trace<BaseField = AIR::BaseElement>,
+    pub_inputs: AIR::PublicInputs,
+    options: ProofOptions,
+    mode: ProvingMode,
+    observer: &impl ProverObserver,
+) -> Result<StarkProof, ProverError> {
+    prove_internal::<AIR>(trace, pub_inputs, options, mode, observer, None, None, None)
+}
+
+/// Returns a STARK proof attesting to a correct execution of a computation reusing values
+/// precomputed for earlier proofs.
+///
+/// This function works just like [prove_with_mode()] function, but takes domain twiddles and
+/// inverse evaluations of constraint divisors from the specified `context` instead of computing
+/// them anew; values which are not yet in the context are computed and added to it. Thus, when
+/// many proofs of computations with the same trace shape are generated using the same context,
+/// these values are computed only once. Proofs generated via this function are identical to the
+/// proofs generated via [prove()] function. See [ProverContext] for more details.
+///
+/// The `context` also defines how constraint evaluation is scheduled across threads; see
+/// [ProverContext::with_fragment_scheduling()].
+pub fn prove_with_context<AIR: Air>(
+    trace: impl Trace<BaseField = AIR::BaseElement>,
     pub_inputs: AIR::PublicInputs,
     options: ProofOptions,
+    mode: ProvingMode,
+    context: &mut ProverContext<AIR::BaseElement>,
+) -> Result<StarkProof, ProverError> {
+    prove_internal::<AIR>(
+        trace,
+        pub_inputs,
+        options,
+        mode,
+        &(),
+        None,
+        None,
+        Some(context),
+    )
+}
+
+/// Returns a STARK proof attesting to a correct execution of a computation generated using the
+/// specified prover `channel`.
+///
+/// This function works just like [prove_with_mode()] function, but instead of instantiating the
+/// AIR from public inputs and proof options, it takes an already instantiated `air`, and all
+/// commitments, out-of-domain evaluations, and random values go through the provided `channel`.
+/// A [DefaultProverChannel] created for the same `air` and public inputs results in the same
+/// proof as [prove()] function; a custom channel can be used to observe or extend the transcript
+/// of the protocol. See [ProverChannel] for more details.
+///
+/// # Panics
+/// Panics if:
+/// * The context of the `channel` does not match the trace info and proof options of the `air`.
+/// * The extension field `E` does not match the field extension specified by the proof options.
+pub fn prove_with_channel<AIR, E, H, C>(
+    air: AIR,
+    trace: impl Trace<BaseField = AIR::BaseElement>,
+    channel: C,
+    mode: ProvingMode,
+) -> Result<StarkProof, ProverError>
+where
+    AIR: Air,
+    E: FieldElement<BaseField = AIR::BaseElement>,
+    H: ElementHasher<BaseField = AIR::BaseElement>,
+    C: ProverChannel<AIR, E, Hasher = H>,
+{
+    assert_eq!(
+        channel.context(),
+        &Context::new::<AIR::BaseElement>(air.trace_info(), air.options().clone()),
+        "channel context does not match the AIR"
+    );
+    assert_eq!(
+        E::ELEMENT_BYTES,
+        AIR::BaseElement::ELEMENT_BYTES * air.options().field_extension().degree() as usize,
+        "field element type does not match field extension {:?}",
+        air.options().field_extension()
+    );
+
+    // make sure the specified trace is valid against the AIR; as in prove_internal(), this is
+    // done in debug mode only.
+    #[cfg(debug_assertions)]
+    trace.validate(&air);
+
+    generate_proof_with_channel::<AIR, E, H, C>(
+        air,
+        Some(trace),
+        channel,
+        Vec::new(),
+        mode,
+        &(),
+        None,
+        None,
+        None,
+    )
+}
+
+/// Returns a STARK proof attesting to a correct execution of a computation together with
+/// intermediate values computed and drawn by the prover.
+///
+/// This function works just like [prove_with_mode()] function, but also records commitments,
+/// out-of-domain evaluations, and random values drawn during proof generation, and returns them
+/// alongside the proof as [ProverArtifacts]. The field `E` in which the values are represented
+/// must be the field defined by the field extension of the proof `options`.
+///
+/// # Panics
+/// Panics if the extension field `E` does not match the field extension specified by the proof
+/// `options`.
+pub fn prove_with_artifacts<AIR, E>(
+    trace: impl Trace<BaseField = AIR::BaseElement>,
+    pub_inputs: AIR::PublicInputs,
+    options: ProofOptions,
+    mode: ProvingMode,
+) -> Result<(StarkProof, ProverArtifacts<E>), ProverError>
+where
+    AIR: Air,
+    E: FieldElement<BaseField = AIR::BaseElement>,
+{
+    assert_eq!(
+        E::ELEMENT_BYTES,
+        AIR::BaseElement::ELEMENT_BYTES * options.field_extension().degree() as usize,
+        "field element type does not match field extension {:?}",
+        options.field_extension()
+    );
+
+    let mut pub_inputs_bytes = Vec::new();
+    pub_inputs.write_into(&mut pub_inputs_bytes);
+    let air = AIR::new(trace.get_info(), pub_inputs, options);
+
+    // as in prove_internal(), the trace is validated in debug mode only
+    #[cfg(debug_assertions)]
+    trace.validate(&air);
+
+    match air.options().hash_fn() {
+        HashFunction::Blake3_256 => generate_proof_with_artifacts::<AIR, E, Blake3_256<_>>(
+            air,
+            trace,
+            pub_inputs_bytes,
+            mode,
+        ),
+        HashFunction::Blake3_192 => generate_proof_with_artifacts::<AIR, E, Blake3_192<_>>(
+            air,
+            trace,
+            pub_inputs_bytes,
+            mode,
+        ),
+        HashFunction::Sha3_256 => {
+            generate_proof_with_artifacts::<AIR, E, Sha3_256<_>>(air, trace, pub_inputs_bytes, mode)
+        }
+    }
+}
+
+/// Returns a STARK proof attesting to a correct execution of a computation; the intermediate
+/// state of the prover is saved into checkpoints during proof generation.
+///
+/// This function works just like [prove_with_mode()] function, but also invokes `on_checkpoint`
+/// with a [ProverCheckpoint] after every expensive proof generation stage (see
+/// [CheckpointStage]). The checkpoint can be serialized and written to disk, and if proof
+/// generation is interrupted, it can be resumed by calling this function again with the same
+/// parameters and the last checkpoint passed in as `resume_from`. The resumed prover skips all
+/// stages completed before the checkpoint was created, and generates a proof identical to the
+/// one an uninterrupted prover would have generated. Only checkpoints for stages completed after
+/// the `resume_from` checkpoint are passed to `on_checkpoint`.
+///
+/// The execution `trace` must be provided even when proof generation is resumed; the trace is
+/// used to instantiate the AIR, and, in debug mode, to validate the trace.
+///
+/// # Errors
+/// In addition to errors returned by [prove()] function, returns an error if `resume_from`
+/// checkpoint was created for a different computation, different public inputs, or different
+/// proof options.
+pub fn prove_with_checkpoints<AIR: Air>(
+    trace: impl Trace<BaseField = AIR::BaseElement>,
+    pub_inputs: AIR::PublicInputs,
+    options: ProofOptions,
+    mode: ProvingMode,
+    resume_from: Option<ProverCheckpoint>,
+    mut on_checkpoint: impl FnMut(&ProverCheckpoint),
+) -> Result<StarkProof, ProverError> {
+    prove_internal::<AIR>(
+        trace,
+        pub_inputs,
+        options,
+        mode,
+        &(),
+        resume_from,
+        Some(&mut on_checkpoint),
+        None,
+    )
+}
+
+/// Returns a STARK proof attesting to a correct execution of a computation generated from a
+/// commitment to its execution trace.
+///
+/// This function works just like [prove_with_mode()] function, but instead of an execution
+/// trace, it takes a [TraceCommitment] created earlier (possibly on a different machine) via
+/// [TraceCommitment::new()] function. The prover skips interpolation of the execution trace and
+/// building of the trace Merkle tree, and uses the proof options specified when the commitment
+/// was created. The same commitment can be used to generate many proofs; proofs generated this
+/// way are identical to the proofs generated via [prove()] function for the same trace.
+///
+/// Since the execution trace is not available, it is not validated against the AIR even in debug
+/// mode; [validate()] function can be used to check the trace before the commitment is created.
+///
+/// # Errors
+/// In addition to errors returned by [prove()] function, returns
+/// [ProverError::InvalidTraceCommitment] if the commitment was created for a trace over a field
+/// different from the base field of the `AIR`, or if the commitment is malformed.
+pub fn prove_with_commitment<AIR: Air>(
+    commitment: &TraceCommitment,
+    pub_inputs: AIR::PublicInputs,
+    mode: ProvingMode,
+) -> Result<StarkProof, ProverError> {
+    let mut pub_inputs_bytes = Vec::new();
+    pub_inputs.write_into(&mut pub_inputs_bytes);
+
+    // instantiate the AIR for the trace and options of the commitment, and make sure the
+    // commitment was created for the same field
+    let context = commitment.context();
+    let air = AIR::new(
+        context.get_trace_info(),
+        pub_inputs,
+        context.options().clone(),
+    );
+    if &Context::new::<AIR::BaseElement>(air.trace_info(), air.options().clone()) != context {
+        return Err(ProverError::InvalidTraceCommitment);
+    }
+
+    // the commitment is passed to the prover as a checkpoint created after the prover has
+    // committed to the extended execution trace
+    let checkpoint = commitment.to_checkpoint(pub_inputs_bytes.clone());
+    dispatch_proof(
+        air,
+        None::<ExecutionTrace<AIR::BaseElement>>,
+        pub_inputs_bytes,
+        mode,
+        &(),
+        Some(checkpoint),
+        None,
+        None,
+    )
+    .map_err(|err| match err {
+        ProverError::InvalidCheckpoint => ProverError::InvalidTraceCommitment,
+        err => err,
+    })
+}
+
+/// Returns a STARK proof attesting to a correct execution of a computation generated from the
+/// polynomials of its execution trace.
+///
+/// This function works just like [prove_with_mode()] function, but instead of an execution
+/// trace, it takes `trace_polys` - polynomials in coefficient form which evaluate to the values
+/// of trace registers over the trace domain - together with `trace_info` describing the trace.
+/// This is useful when trace polynomials are produced directly by a witness generator; the
+/// prover skips interpolation of the execution trace, and the resulting proof is identical to
+/// the proof generated via [prove()] function for the execution trace described by these
+/// polynomials. Polynomials of an existing execution trace can be built via
+/// [build_trace_polys()] function.
+///
+/// The polynomials are kept on the heap regardless of the proving `mode`. In debug mode, the
+/// polynomials are evaluated over the trace domain, and the resulting execution trace is
+/// validated against the AIR.
+///
+/// # Panics
+/// Panics if the number of `trace_polys` is different from the trace width specified by
+/// `trace_info`, or if any of the polynomials has a number of coefficients different from the
+/// trace length specified by `trace_info`.
+pub fn prove_from_polys<AIR: Air>(
+    trace_info: TraceInfo,
+    trace_polys: Vec<Vec<AIR::BaseElement>>,
+    pub_inputs: AIR::PublicInputs,
+    options: ProofOptions,
+    mode: ProvingMode,
+) -> Result<StarkProof, ProverError> {
+    assert_eq!(
+        trace_polys.len(),
+        trace_info.width(),
+        "number of trace polynomials must be {}, but was {}",
+        trace_info.width(),
+        trace_polys.len()
+    );
+    for poly in trace_polys.iter() {
+        assert_eq!(
+            poly.len(),
+            trace_info.length(),
+            "all trace polynomials must have size {}",
+            trace_info.length()
+        );
+    }
+
+    let mut pub_inputs_bytes = Vec::new();
+    pub_inputs.write_into(&mut pub_inputs_bytes);
+    let air = AIR::new(trace_info, pub_inputs, options);
+
+    // the execution trace is not available; thus, in debug mode, we rebuild it from trace
+    // polynomials to make sure it is valid against the AIR
+    #[cfg(debug_assertions)]
+    {
+        let twiddles = math::fft::get_twiddles::<AIR::BaseElement>(air.trace_length());
+        let registers = trace_polys
+            .iter()
+            .map(|poly| {
+                let mut register = poly.clone();
+                math::fft::evaluate_poly(&mut register, &twiddles);
+                register
+            })
+            .collect();
+        let mut trace = ExecutionTrace::init(registers);
+        trace.set_meta(air.trace_info().meta().to_vec());
+        trace.validate(&air);
+    }
+
+    let trace_polys = TracePolyTable::new(trace_polys.into_iter().map(Column::from).collect());
+    dispatch_proof(
+        air,
+        Some(trace_polys),
+        pub_inputs_bytes,
+        mode,
+        &(),
+        None,
+        None,
+        None,
+    )
+}
+
+/// Instantiates the AIR for the specified trace and public inputs, and generates a proof using
+/// a version of the generic proof generation procedure appropriate for the proof options.
+#[rustfmt::skip]
+#[allow(clippy::too_many_arguments)]
+fn prove_internal<AIR: Air>(
+    trace: impl Trace<BaseField = AIR::BaseElement>,
+    pub_inputs: AIR::PublicInputs,
+    options: ProofOptions,
+    mode: ProvingMode,
+    observer: &impl ProverObserver,
+    resume_from: Option<ProverCheckpoint>,
+    on_checkpoint: Option<&mut dyn FnMut(&ProverCheckpoint)>,
+    prover_context: Option<&mut ProverContext<AIR::BaseElement>>,
 ) -> Result<StarkProof, ProverError> {
     // serialize public inputs; these will be included in the seed for the public coin
     let mut pub_inputs_bytes = Vec::new();
@@ -130,42 +656,122 @@ pub fn prove<AIR: Air>(
     #[cfg(debug_assertions)]
     trace.validate(&air);
 
+    dispatch_proof(air, Some(trace), pub_inputs_bytes, mode, observer, resume_from, on_checkpoint, prover_context)
+}
+
+/// Generates a proof for the specified `air` using a version of the generic proof generation
+/// procedure appropriate for the proof options; `trace` can be omitted only if proof generation
+/// is resumed from a checkpoint.
+#[rustfmt::skip]
+#[allow(clippy::too_many_arguments)]
+fn dispatch_proof<AIR: Air>(
+    air: AIR,
+    trace: Option<impl TraceSource<AIR::BaseElement>>,
+    pub_inputs_bytes: Vec<u8>,
+    mode: ProvingMode,
+    observer: &impl ProverObserver,
+    resume_from: Option<ProverCheckpoint>,
+    on_checkpoint: Option<&mut dyn FnMut(&ProverCheckpoint)>,
+    prover_context: Option<&mut ProverContext<AIR::BaseElement>>,
+) -> Result<StarkProof, ProverError> {
     // figure out which version of the generic proof generation procedure to run. this is a sort
     // of static dispatch for selecting two generic parameter: extension field and hash function.
     match air.options().field_extension() {
         FieldExtension::None => match air.options().hash_fn() {
             HashFunction::Blake3_256 => generate_proof::
                 <AIR, AIR::BaseElement, Blake3_256<AIR::BaseElement>>
-                (air, trace, pub_inputs_bytes),
+                (air, trace, pub_inputs_bytes, mode, observer, resume_from, on_checkpoint, prover_context),
             HashFunction::Blake3_192 => generate_proof::
                 <AIR, AIR::BaseElement, Blake3_192<AIR::BaseElement>>
-                (air, trace, pub_inputs_bytes),
+                (air, trace, pub_inputs_bytes, mode, observer, resume_from, on_checkpoint, prover_context),
             HashFunction::Sha3_256 => generate_proof::
                 <AIR, AIR::BaseElement, Sha3_256<AIR::BaseElement>>
-                (air, trace, pub_inputs_bytes)
+                (air, trace, pub_inputs_bytes, mode, observer, resume_from, on_checkpoint, prover_context)
         },
         FieldExtension::Quadratic => match air.options().hash_fn() {
             HashFunction::Blake3_256 => generate_proof::
                 <AIR, <AIR::BaseElement as StarkField>::QuadExtension, Blake3_256<AIR::BaseElement>>
-                (air, trace, pub_inputs_bytes),
+                (air, trace, pub_inputs_bytes, mode, observer, resume_from, on_checkpoint, prover_context),
             HashFunction::Blake3_192 => generate_proof::
                 <AIR, <AIR::BaseElement as StarkField>::QuadExtension, Blake3_192<AIR::BaseElement>>
-                (air, trace, pub_inputs_bytes),
+                (air, trace, pub_inputs_bytes, mode, observer, resume_from, on_checkpoint, prover_context),
             HashFunction::Sha3_256 => generate_proof::
                 <AIR, <AIR::BaseElement as StarkField>::QuadExtension, Sha3_256<AIR::BaseElement>>
-                (air, trace, pub_inputs_bytes),
+                (air, trace, pub_inputs_bytes, mode, observer, resume_from, on_checkpoint, prover_context),
         },
     }
 }
 
+/// Returns a STARK proof attesting to a correct execution of a computation together with a
+/// profile of resources used to generate the proof.
+///
+/// This function works just like [prove_with_mode()] function, but also records wall time,
+/// increase of peak memory usage, and number of bytes hashed for every proof generation stage.
+/// The profile is returned alongside the proof; stages are also logged at `debug` level.
+///
+/// This function is available only when `profiling` feature is enabled, and only on Unix
+/// systems.
+#[cfg(feature = "profiling")]
+pub fn prove_with_profile<AIR: Air>(
+    trace: impl Trace<BaseField = AIR::BaseElement>,
+    pub_inputs: AIR::PublicInputs,
+    options: ProofOptions,
+    mode: ProvingMode,
+) -> Result<(StarkProof, ProverProfile), ProverError> {
+    let profiler = Profiler::new();
+    let proof = prove_with_observer::<AIR>(trace, pub_inputs, options, mode, &profiler)?;
+    let profile = profiler.into_profile();
+    for stage in profile.stages.iter() {
+        debug!(
+            "Stage '{}' took {} ms, increased peak RSS by {} KB, and hashed {} bytes",
+            stage.name,
+            stage.time.as_millis(),
+            stage.peak_rss_delta / 1024,
+            stage.hashed_bytes
+        );
+    }
+    Ok((proof, profile))
+}
+
+/// Returns a STARK proof attesting to a correct execution of a computation; the proof is
+/// generated in the specified thread `pool`.
+///
+/// This function works just like [prove_with_mode()] function, but instead of the global
+/// thread pool, all parallel work is performed in the provided `pool`. This makes it possible to
+/// partition CPU cores explicitly between several proofs generated concurrently in the same
+/// process. A pool with a given number of threads can be created as follows:
+///
+/// ```ignore
+/// let pool = rayon::ThreadPoolBuilder::new().num_threads(4).build().unwrap();
+/// ```
+#[cfg(feature = "concurrent")]
+pub fn prove_in_pool<AIR: Air>(
+    trace: impl Trace<BaseField = AIR::BaseElement> + Send,
+    pub_inputs: AIR::PublicInputs,
+    options: ProofOptions,
+    mode: ProvingMode,
+    pool: &rayon::ThreadPool,
+) -> Result<StarkProof, ProverError>
+where
+    AIR::PublicInputs: Send,
+{
+    pool.install(|| prove_with_mode::<AIR>(trace, pub_inputs, options, mode))
+}
+
 // PROOF GENERATION PROCEDURE
 // ================================================================================================
 /// Performs the actual proof generation procedure, generating the proof that the provided
 /// execution `trace` is valid against the provided `air`.
+#[allow(clippy::too_many_arguments)]
 fn generate_proof<A, E, H>(
     air: A,
-    trace: ExecutionTrace<A::BaseElement>,
+    trace: Option<impl TraceSource<A::BaseElement>>,
     pub_inputs_bytes: Vec<u8>,
+    mode: ProvingMode,
+    observer: &impl ProverObserver,
+    resume_from: Option<ProverCheckpoint>,
+    on_checkpoint: Option<&mut dyn FnMut(&ProverCheckpoint)>,
+    prover_context: Option<&mut ProverContext<A::BaseElement>>,
 ) -> Result<StarkProof, ProverError>
 where
     A: Air,
@@ -175,46 +781,188 @@ where
     // create a channel which is used to simulate interaction between the prover and the verifier;
     // the channel will be used to commit to values and to draw randomness that should come from
     // the verifier.
-    let mut channel = ProverChannel::<A, E, H>::new(&air, pub_inputs_bytes);
+    let channel = DefaultProverChannel::<A, E, H>::new(&air, pub_inputs_bytes.clone());
+    generate_proof_with_channel::<A, E, H, _>(
+        air,
+        trace,
+        channel,
+        pub_inputs_bytes,
+        mode,
+        observer,
+        resume_from,
+        on_checkpoint,
+        prover_context,
+    )
+}
+
+/// Generates a proof for the specified `air` using a channel which records prover artifacts,
+/// and returns the proof together with the artifacts.
+fn generate_proof_with_artifacts<A, E, H>(
+    air: A,
+    trace: impl Trace<BaseField = A::BaseElement>,
+    pub_inputs_bytes: Vec<u8>,
+    mode: ProvingMode,
+) -> Result<(StarkProof, ProverArtifacts<E>), ProverError>
+where
+    A: Air,
+    E: FieldElement<BaseField = A::BaseElement>,
+    H: ElementHasher<BaseField = A::BaseElement>,
+{
+    let mut artifacts = ArtifactChannel::<A, E, H>::new_artifacts(&air);
+    let channel = ArtifactChannel::<A, E, H>::new(&air, pub_inputs_bytes.clone(), &mut artifacts);
+    let proof = generate_proof_with_channel::<A, E, H, _>(
+        air,
+        Some(trace),
+        channel,
+        pub_inputs_bytes,
+        mode,
+        &(),
+        None,
+        None,
+        None,
+    )?;
+    Ok((proof, artifacts))
+}
+
+/// Performs the actual proof generation procedure, using the specified `channel` to commit to
+/// values and to draw randomness.
+#[allow(clippy::too_many_arguments)]
+fn generate_proof_with_channel<A, E, H, C>(
+    air: A,
+    trace: Option<impl TraceSource<A::BaseElement>>,
+    mut channel: C,
+    pub_inputs_bytes: Vec<u8>,
+    mode: ProvingMode,
+    observer: &impl ProverObserver,
+    resume_from: Option<ProverCheckpoint>,
+    mut on_checkpoint: Option<&mut dyn FnMut(&ProverCheckpoint)>,
+    prover_context: Option<&mut ProverContext<A::BaseElement>>,
+) -> Result<StarkProof, ProverError>
+where
+    A: Air,
+    E: FieldElement<BaseField = A::BaseElement>,
+    H: ElementHasher<BaseField = A::BaseElement>,
+    C: ProverChannel<A, E, Hasher = H>,
+{
+    // if proof generation is resumed from a checkpoint, read the prover state saved in the
+    // checkpoint; stages completed before the checkpoint was created are skipped below, but
+    // the channel still goes through all the same steps so that the same randomness is drawn
+    let resumed_stage = resume_from.as_ref().map(|checkpoint| checkpoint.stage());
+    let (saved_trace, saved_constraints) = match resume_from {
+        Some(checkpoint) => {
+            #[cfg(feature = "std")]
+            let stage = checkpoint.stage();
+            let state = checkpoint.read_state::<A::BaseElement, E, H>(
+                channel.context(),
+                &pub_inputs_bytes,
+                mode,
+            )?;
+            #[cfg(feature = "std")]
+            debug!(
+                "Resumed proof generation from a checkpoint created after {:?} stage",
+                stage
+            );
+            (
+                Some((state.trace_polys, state.trace_trees)),
+                state.constraints,
+            )
+        }
+        None => (None, None),
+    };
 
     // 1 ----- extend execution trace -------------------------------------------------------------
 
-    // build computation domain; this is used later for polynomial evaluations
+    // build computation domain; this is used later for polynomial evaluations. if a prover
+    // context was provided, the domain is built only if the context has not seen a computation
+    // of the same shape before.
     #[cfg(feature = "std")]
     let now = Instant::now();
-    let domain = StarkDomain::new(&air);
+    let mut local_context = None;
+    let prover_context = match prover_context {
+        Some(prover_context) => prover_context,
+        None => local_context.insert(ProverContext::new()),
+    };
+    let fragment_scheduling = prover_context.fragment_scheduling();
+    let (domain, divisor_cache, mut buffer_pool) = prover_context.get_or_build(&air);
     #[cfg(feature = "std")]
     debug!(
-        "Built domain of 2^{} elements in {} ms",
+        "Obtained domain of 2^{} elements in {} ms",
         log2(domain.lde_domain_size()),
         now.elapsed().as_millis()
     );
 
     // extend the execution trace; this interpolates each register of the trace into a polynomial,
     // and then evaluates the polynomial over the LDE domain; each of the trace polynomials has
-    // degree = trace_length - 1
-    let (extended_trace, trace_polys) = trace.extend(&domain);
+    // degree = trace_length - 1. in chunked mode, the polynomials are evaluated over the LDE
+    // domain one coset at a time whenever the extended trace is accessed. when resuming from a
+    // checkpoint, trace polynomials are read from the checkpoint instead.
+    #[cfg(feature = "std")]
+    let now = Instant::now();
+    let (trace_polys, saved_trace_trees) = match saved_trace {
+        Some((trace_polys, trace_trees)) => {
+            drop(trace);
+            (trace_polys, Some(trace_trees))
+        }
+        None => {
+            let trace = trace.expect("execution trace is required to generate a proof");
+            (trace.into_polys(domain, mode), None)
+        }
+    };
+
+    // make sure registers tied to committed public columns match the column commitments; the
+    // evaluations of these registers are opened to the verifier later on
+    let committed_registers = CommittedRegisters::<_, H>::new(&air, &trace_polys, domain)?;
+
+    let extended_trace = TraceLde::new(&trace_polys, domain, mode, buffer_pool.as_deref_mut());
     #[cfg(feature = "std")]
     debug!(
-        "Extended execution trace of {} registers from 2^{} to 2^{} steps ({}x blowup) in {} ms",
+        "Extended execution trace of {} registers from 2^{} to 2^{} steps ({}x blowup, {:?} mode) in {} ms",
         extended_trace.width(),
         log2(trace_polys.poly_size()),
         log2(extended_trace.len()),
         extended_trace.blowup(),
+        mode,
         now.elapsed().as_millis()
     );
+    observer.on_trace_extended();
 
     // 2 ----- commit to the extended execution trace ---------------------------------------------
+    // the trace is split into segments of columns as defined by the proof options, and each
+    // segment is committed to via a separate Merkle tree; roots of the trees are sent to the
+    // verifier in the order of the segments.
     #[cfg(feature = "std")]
     let now = Instant::now();
-    let trace_tree = extended_trace.build_commitment::<H>();
-    channel.commit_trace(*trace_tree.root());
+    let trace_segments = air.options().trace_segments(air.trace_width());
+    let trace_trees = match saved_trace_trees {
+        Some(trace_trees) => trace_trees,
+        None => extended_trace.build_commitment::<H>(&trace_segments),
+    };
+    for trace_tree in trace_trees.iter() {
+        channel.commit_trace(*trace_tree.root());
+    }
     #[cfg(feature = "std")]
     debug!(
-        "Committed to extended execution trace by building a Merkle tree of depth {} in {} ms",
-        trace_tree.depth(),
+        "Committed to extended execution trace by building {} Merkle tree(s) of depth {} in {} ms",
+        trace_trees.len(),
+        trace_trees[0].depth(),
         now.elapsed().as_millis()
     );
+    observer.on_trace_committed();
+
+    // save a checkpoint if requested; the checkpoint is kept around so that constraint
+    // commitment can be added to it later
+    let mut checkpoint = on_checkpoint.as_mut().map(|on_checkpoint| {
+        let checkpoint = ProverCheckpoint::new(
+            channel.context().clone(),
+            pub_inputs_bytes,
+            &trace_polys,
+            &trace_trees,
+        );
+        if resumed_stage.is_none() {
+            on_checkpoint(&checkpoint);
+        }
+        checkpoint
+    });
 
     // 3 ----- evaluate constraints ---------------------------------------------------------------
     // evaluate constraints specified by the AIR over the constraint evaluation domain, and compute
@@ -222,17 +970,41 @@ where
     // this step evaluates only constraint numerators, thus, only constraints with identical
     // denominators are merged together. the results are saved into a constraint evaluation table
     // where each column contains merged evaluations of constraints with identical denominators.
-    #[cfg(feature = "std")]
-    let now = Instant::now();
-    let constraint_coeffs = channel.get_constraint_composition_coeffs();
-    let evaluator = ConstraintEvaluator::new(&air, constraint_coeffs);
-    let constraint_evaluations = evaluator.evaluate(&extended_trace, &domain);
-    #[cfg(feature = "std")]
-    debug!(
-        "Evaluated constraints over domain of 2^{} elements in {} ms",
-        log2(constraint_evaluations.num_rows()),
-        now.elapsed().as_millis()
-    );
+    //
+    // when resuming from a checkpoint which contains a constraint commitment, constraints are
+    // not evaluated; composition coefficients are still drawn to keep the channel in sync.
+    let constraint_coeffs = channel.get_constraint_composition_coeffs(&air);
+    let constraint_evaluations = if saved_constraints.is_none() {
+        #[cfg(feature = "std")]
+        let now = Instant::now();
+        let evaluator = ConstraintEvaluator::new(&air, constraint_coeffs);
+        let constraint_evaluations = evaluator.evaluate(
+            &extended_trace,
+            domain,
+            divisor_cache,
+            fragment_scheduling,
+            observer,
+        )?;
+        #[cfg(feature = "std")]
+        debug!(
+            "Evaluated constraints over domain of 2^{} elements in {} ms",
+            log2(constraint_evaluations.num_rows()),
+            now.elapsed().as_millis()
+        );
+        Some(constraint_evaluations)
+    } else {
+        observer.on_constraints_evaluated(100);
+        None
+    };
+
+    // in transient mode, the extended trace is needed after this point only to answer queries;
+    // thus, it is discarded here, and the queried rows are recomputed from trace polynomials
+    let extended_trace = match mode {
+        ProvingMode::Transient => {
+            extended_trace.discard(&trace_polys, domain, buffer_pool.as_deref_mut())
+        }
+        _ => extended_trace,
+    };
 
     // 4 ----- commit to constraint evaluations ---------------------------------------------------
 
@@ -242,21 +1014,30 @@ where
     // - interpolate the column into a polynomial in coefficient form
     // - "break" the polynomial into a set of column polynomials each of degree equal to
     //   trace_length - 1
-    #[cfg(feature = "std")]
-    let now = Instant::now();
-    let composition_poly = constraint_evaluations.into_poly()?;
-    #[cfg(feature = "std")]
-    debug!(
-        "Converted constraint evaluations into {} composition polynomial columns of degree {} in {} ms",
-        composition_poly.num_columns(),
-        composition_poly.column_degree(),
-        now.elapsed().as_millis()
-    );
+    // when resuming from a checkpoint, the polynomial is read from the checkpoint instead.
+    let (composition_poly, saved_constraint_tree) = match saved_constraints {
+        Some((composition_poly, constraint_tree)) => (composition_poly, Some(constraint_tree)),
+        None => {
+            #[cfg(feature = "std")]
+            let now = Instant::now();
+            let composition_poly = constraint_evaluations
+                .expect("constraints have not been evaluated")
+                .into_poly(domain)?;
+            #[cfg(feature = "std")]
+            debug!(
+                "Converted constraint evaluations into {} composition polynomial columns of degree {} in {} ms",
+                composition_poly.num_columns(),
+                composition_poly.column_degree(),
+                now.elapsed().as_millis()
+            );
+            (composition_poly, None)
+        }
+    };
 
     // then, evaluate composition polynomial columns over the LDE domain
     #[cfg(feature = "std")]
     let now = Instant::now();
-    let composed_evaluations = composition_poly.evaluate(&domain);
+    let composed_evaluations = composition_poly.evaluate(domain);
     #[cfg(feature = "std")]
     debug!(
         "Evaluated composition polynomial columns over LDE domain (2^{} elements) in {} ms",
@@ -267,7 +1048,10 @@ where
     // finally, commit to the composition polynomial evaluations
     #[cfg(feature = "std")]
     let now = Instant::now();
-    let constraint_commitment = ConstraintCommitment::<E, H>::new(composed_evaluations);
+    let constraint_commitment = match saved_constraint_tree {
+        Some(tree) => ConstraintCommitment::<E, H>::from_tree(composed_evaluations, tree),
+        None => ConstraintCommitment::<E, H>::new(composed_evaluations),
+    };
     channel.commit_constraints(constraint_commitment.root());
     #[cfg(feature = "std")]
     debug!(
@@ -275,6 +1059,17 @@ where
         constraint_commitment.tree_depth(),
         now.elapsed().as_millis()
     );
+    observer.on_constraints_committed();
+
+    // save a checkpoint if requested; this is the last checkpoint, and thus, it is not needed
+    // after this
+    if let (Some(on_checkpoint), Some(checkpoint)) = (on_checkpoint, checkpoint.as_mut()) {
+        checkpoint.add_constraints(&composition_poly, constraint_commitment.tree());
+        if resumed_stage != Some(CheckpointStage::ConstraintsCommitted) {
+            on_checkpoint(checkpoint);
+        }
+    }
+    drop(checkpoint);
 
     // 5 ----- build DEEP composition polynomial --------------------------------------------------
     #[cfg(feature = "std")]
@@ -300,11 +1095,15 @@ where
 
     // draw random coefficients to use during DEEP polynomial composition, and use them to
     // initialize the DEEP composition polynomial
-    let deep_coefficients = channel.get_deep_composition_coeffs();
+    let deep_coefficients = channel.get_deep_composition_coeffs(&air);
     let mut deep_composition_poly = DeepCompositionPoly::new(&air, z, deep_coefficients);
 
     // combine all trace polynomials together and merge them into the DEEP composition polynomial
-    deep_composition_poly.add_trace_polys(trace_polys, ood_frame);
+    deep_composition_poly.add_trace_polys(&trace_polys, ood_frame);
+
+    // registers tied to committed public columns are equal to these columns, and thus, the
+    // terms which tie them together in the DEEP composition polynomial are all zeros; these
+    // terms are added only by the verifier to make sure the registers match the columns
 
     // merge columns of constraint composition polynomial into the DEEP composition polynomial;
     deep_composition_poly.add_composition_poly(composition_poly, ood_evaluations);
@@ -326,7 +1125,7 @@ where
     // 6 ----- evaluate DEEP composition polynomial over LDE domain -------------------------------
     #[cfg(feature = "std")]
     let now = Instant::now();
-    let deep_evaluations = deep_composition_poly.evaluate(&domain);
+    let deep_evaluations = deep_composition_poly.evaluate(domain);
     // we check the following condition in debug mode only because infer_degree is an expensive
     // operation
     debug_assert_eq!(
@@ -339,12 +1138,20 @@ where
         log2(domain.lde_domain_size()),
         now.elapsed().as_millis()
     );
+    observer.on_deep_composition_built();
 
     // 7 ----- compute FRI layers for the composition polynomial ----------------------------------
     #[cfg(feature = "std")]
     let now = Instant::now();
-    let mut fri_prover = FriProver::new(air.options().to_fri_options());
-    fri_prover.build_layers(&mut channel, deep_evaluations);
+    // the low-degree test (FRI or STIR) is selected based on the proof options
+    let fri_options = air.options().to_fri_options();
+    let mut fri_prover: Box<dyn LowDegreeProver<E, C> + '_> = match fri_options.low_degree_test() {
+        LowDegreeTest::Fri | LowDegreeTest::Direct => Box::new(FriProver::new(fri_options)),
+        LowDegreeTest::Stir => Box::new(StirProver::new(fri_options)),
+    };
+    fri_prover.commit_layers(&mut channel, deep_evaluations, &mut |layer_idx| {
+        observer.on_fri_layer(layer_idx)
+    });
     #[cfg(feature = "std")]
     debug!(
         "Computed {} FRI layers from composition polynomial evaluations in {} ms",
@@ -360,7 +1167,7 @@ where
     channel.grind_query_seed();
 
     // generate pseudo-random query positions
-    let query_positions = channel.get_query_positions();
+    let query_positions = channel.get_query_positions(&air);
     #[cfg(feature = "std")]
     debug!(
         "Determined {} query positions in {} ms",
@@ -377,17 +1184,32 @@ where
 
     // query the execution trace at the selected position; for each query, we need the
     // state of the trace at that position + Merkle authentication path
-    let trace_queries = extended_trace.query(trace_tree, &query_positions);
+    let trace_queries = extended_trace.query(&trace_segments, trace_trees, &query_positions);
 
     // query the constraint commitment at the selected positions; for each query, we need just
     // a Merkle authentication path. this is because constraint evaluations for each step are
     // merged into a single value and Merkle authentication paths contain these values already
     let constraint_queries = constraint_commitment.query(&query_positions);
 
+    // query registers which are tied to committed public columns at the selected positions;
+    // the verifier checks these queries against column commitments it already knows, and then
+    // ties the queried values to the registers during DEEP composition
+    let committed_column_queries = committed_registers.query(&query_positions);
+
+    // the extended trace is no longer needed; if a buffer pool was provided, the buffers of the
+    // extended trace are returned to it
+    extended_trace.release(buffer_pool);
+
     // build the proof object
-    let proof = channel.build_proof(trace_queries, constraint_queries, fri_proof);
+    let proof = channel.build_proof(
+        trace_queries,
+        constraint_queries,
+        committed_column_queries,
+        fri_proof,
+    );
     #[cfg(feature = "std")]
     debug!("Built proof object in {} ms", now.elapsed().as_millis());
+    observer.on_proof_built();
 
     Ok(proof)
 }
@@ -0,0 +1,67 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+// PROVING MODE
+// ================================================================================================
+/// Defines how the prover holds the low-degree extension of an execution trace in memory.
+///
+/// The proving mode affects only the time and memory needed to generate a proof; proofs
+/// generated in all modes are identical.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProvingMode {
+    /// The execution trace is extended over the entire LDE domain, and the extended trace is kept
+    /// in memory until the proof is built. This is the fastest mode, but the extended trace
+    /// requires `blowup_factor` times as much memory as the execution trace itself.
+    InMemory,
+    /// The execution trace is extended over the entire LDE domain as in [ProvingMode::InMemory]
+    /// mode, but the extended trace is then transposed, so that it is stored row by row rather
+    /// than register by register.
+    ///
+    /// Constraint evaluation and trace commitment read the extended trace one row at a time;
+    /// when the trace is stored register by register, reading a row touches a different cache
+    /// line for every register. Thus, for wide traces, this mode speeds up constraint evaluation
+    /// at the cost of a transposition pass, during which the extended trace takes up twice as
+    /// much memory. The transposition pays off only for traces with more than ~100 registers.
+    RowMajor,
+    /// The execution trace is kept in memory only in the form of trace polynomials, and these
+    /// polynomials are evaluated over one coset of the LDE domain at a time whenever rows of the
+    /// extended trace are needed. Specifically:
+    ///
+    /// * to commit to the extended trace, each coset is evaluated and its rows are hashed into
+    ///   Merkle tree leaves;
+    /// * to evaluate constraints, each coset of the constraint evaluation domain is evaluated
+    ///   again and constraints are evaluated over it;
+    /// * to answer queries, only the cosets containing queried rows are evaluated again.
+    ///
+    /// Thus, memory needed for the extended trace is reduced to about twice the size of the
+    /// execution trace, at the cost of extra passes over trace polynomials. Memory needed for
+    /// constraint evaluations, trace Merkle tree, and FRI layers is not affected by this mode.
+    Chunked,
+    /// The execution trace is extended over the entire LDE domain and kept in memory as in
+    /// [ProvingMode::InMemory] mode, but only until constraints are evaluated. After that, the
+    /// extended trace is discarded, and the rows needed to answer queries are recomputed by
+    /// evaluating trace polynomials directly at the queried points.
+    ///
+    /// Thus, the extended trace does not take up memory while the DEEP composition polynomial
+    /// and FRI layers are built, at the cost of evaluating every trace polynomial at every
+    /// query position (i.e., `num_queries * trace_width` evaluations of polynomials of degree
+    /// `trace_length - 1`). Registers tied to committed columns are extended again in full.
+    Transient,
+    /// Trace polynomials and the extended execution trace are stored in memory-mapped temporary
+    /// files rather than on the heap. The files are created in the directory returned by
+    /// [std::env::temp_dir()] (on Unix systems, this can be changed via `TMPDIR` environment
+    /// variable), and are removed as soon as they are mapped into memory.
+    ///
+    /// This allows the operating system to move parts of the extended trace between memory and
+    /// disk as needed, and thus, makes it possible to generate proofs for traces which are
+    /// larger than the available memory, though at a significantly lower speed. Registers of the
+    /// execution trace are read one at a time via [Trace::get_column()](crate::Trace::get_column)
+    /// method; to keep the execution trace itself out of the heap, it can be created via
+    /// [ExecutionTrace::new_mapped()](crate::ExecutionTrace::new_mapped) function.
+    ///
+    /// This mode is available only when `mmap` feature is enabled, and only on Unix systems.
+    #[cfg(feature = "mmap")]
+    MemoryMapped,
+}
@@ -0,0 +1,62 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+// PROVER OBSERVER
+// ================================================================================================
+/// Receives notifications about progress of proof generation.
+///
+/// An observer can be passed to [prove_with_observer()](crate::prove_with_observer) function to
+/// drive progress bars, watchdogs etc. while a proof is being generated. All methods have empty
+/// default implementations, and thus, an observer needs to implement only the notifications it
+/// is interested in. The unit type `()` implements this trait as an observer which ignores all
+/// notifications.
+///
+/// Notifications are delivered in the order in which proof generation stages are executed:
+///
+/// 1. [on_trace_extended()](ProverObserver::on_trace_extended)
+/// 2. [on_trace_committed()](ProverObserver::on_trace_committed)
+/// 3. [on_constraints_evaluated()](ProverObserver::on_constraints_evaluated), possibly many times
+/// 4. [on_constraints_committed()](ProverObserver::on_constraints_committed)
+/// 5. [on_deep_composition_built()](ProverObserver::on_deep_composition_built)
+/// 6. [on_fri_layer()](ProverObserver::on_fri_layer), once for every FRI layer
+/// 7. [on_proof_built()](ProverObserver::on_proof_built)
+///
+/// Notifications are invoked on the thread generating the proof, except for constraint
+/// evaluation progress which, when `concurrent` feature is enabled, may be reported from
+/// multiple threads at the same time. Thus, an observer must be [Sync], and should use interior
+/// mutability (e.g., atomics) to record progress. Notifications should return quickly, as proof
+/// generation is blocked until they return.
+pub trait ProverObserver: Sync {
+    /// Invoked after the execution trace has been extended over the LDE domain.
+    fn on_trace_extended(&self) {}
+
+    /// Invoked after the prover has committed to the extended execution trace.
+    fn on_trace_committed(&self) {}
+
+    /// Invoked every time a part of the constraint evaluation domain has been evaluated;
+    /// `percent` is the share of the domain evaluated so far, and is equal to 100 once all
+    /// constraints have been evaluated.
+    ///
+    /// When `concurrent` feature is enabled, notifications for different parts of the domain
+    /// may arrive out of order.
+    fn on_constraints_evaluated(&self, _percent: usize) {}
+
+    /// Invoked after the prover has committed to the evaluations of the constraint composition
+    /// polynomial.
+    fn on_constraints_committed(&self) {}
+
+    /// Invoked after the DEEP composition polynomial has been built and evaluated over the LDE
+    /// domain.
+    fn on_deep_composition_built(&self) {}
+
+    /// Invoked after the FRI layer at the specified index has been built; the last reported
+    /// layer is the FRI remainder.
+    fn on_fri_layer(&self, _layer_idx: usize) {}
+
+    /// Invoked after the proof has been built.
+    fn on_proof_built(&self) {}
+}
+
+impl ProverObserver for () {}
@@ -0,0 +1,161 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use core::mem::MaybeUninit;
+use math::StarkField;
+use utils::{collections::Vec, into_uninit_buffer, uninit_buffer};
+
+// BUFFER POOL
+// ================================================================================================
+/// Defines a source of buffers for the columns of the extended execution trace.
+///
+/// A pool can be attached to a [ProverContext](crate::ProverContext) via
+/// [ProverContext::with_buffer_pool()](crate::ProverContext::with_buffer_pool). The prover then
+/// takes buffers for the columns of the extended execution trace (and, for
+/// [ProvingMode::RowMajor](crate::ProvingMode::RowMajor), for the transposed trace) from the
+/// pool, and returns them to the pool once the extended trace is no longer needed. Thus, when
+/// many proofs are generated in the same process, these buffers can be reused rather than
+/// allocated and freed for every proof.
+///
+/// The extended execution trace is usually the largest structure built by the prover; other
+/// structures, such as constraint evaluation tables, the composition polynomial, and FRI layers,
+/// are not taken from the pool. Buffers are regular vectors, and thus, their memory always
+/// comes from the global allocator; a pool can only decide when this memory is reused. To back
+/// the buffers with memory allocated in some special way (e.g., from huge pages), the global
+/// allocator needs to be changed.
+pub trait BufferPool<B: StarkField> {
+    /// Returns a vector of exactly `len` elements. The values of the elements do not need to be
+    /// initialized, as they are written by the prover before they are read.
+    fn take(&mut self, len: usize) -> Vec<MaybeUninit<B>>;
+
+    /// Returns the specified `buffer` to this pool once the prover no longer needs it.
+    fn release(&mut self, buffer: Vec<B>);
+}
+
+// RECYCLING POOL
+// ================================================================================================
+/// A [BufferPool] which keeps up to a specified number of released buffers, and hands them out
+/// again for requests which fit into their capacity.
+///
+/// When no retained buffer is large enough, a new buffer is allocated. When the pool is full,
+/// the smallest of the retained buffers and the released buffer is freed.
+pub struct RecyclingPool<B: StarkField> {
+    buffers: Vec<Vec<MaybeUninit<B>>>,
+    max_buffers: usize,
+}
+
+impl<B: StarkField> RecyclingPool<B> {
+    /// Returns a new empty pool which retains at most `max_buffers` buffers.
+    pub fn new(max_buffers: usize) -> Self {
+        RecyclingPool {
+            buffers: Vec::new(),
+            max_buffers,
+        }
+    }
+
+    /// Returns the number of buffers currently retained by this pool.
+    pub fn num_buffers(&self) -> usize {
+        self.buffers.len()
+    }
+
+    /// Frees all buffers retained by this pool.
+    pub fn clear(&mut self) {
+        self.buffers.clear();
+    }
+}
+
+impl<B: StarkField> BufferPool<B> for RecyclingPool<B> {
+    fn take(&mut self, len: usize) -> Vec<MaybeUninit<B>> {
+        // pick the smallest retained buffer which is large enough, so that larger buffers are
+        // kept for larger requests
+        let position = self
+            .buffers
+            .iter()
+            .enumerate()
+            .filter(|(_, buffer)| buffer.capacity() >= len)
+            .min_by_key(|(_, buffer)| buffer.capacity())
+            .map(|(i, _)| i);
+
+        match position {
+            Some(i) => {
+                let mut buffer = self.buffers.swap_remove(i);
+                buffer.resize_with(len, MaybeUninit::uninit);
+                buffer
+            }
+            None => uninit_buffer(len),
+        }
+    }
+
+    fn release(&mut self, buffer: Vec<B>) {
+        let buffer = into_uninit_buffer(buffer);
+        if self.buffers.len() < self.max_buffers {
+            self.buffers.push(buffer);
+            return;
+        }
+
+        // the pool is full; replace the smallest retained buffer if the released one is larger
+        let smallest = self
+            .buffers
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, buffer)| buffer.capacity())
+            .map(|(i, _)| i);
+        if let Some(i) = smallest {
+            if self.buffers[i].capacity() < buffer.capacity() {
+                self.buffers[i] = buffer;
+            }
+        }
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{BufferPool, RecyclingPool};
+    use core::mem::MaybeUninit;
+    use math::{fields::f128::BaseElement, FieldElement};
+    use utils::{assume_init_buffer, collections::Vec};
+
+    #[test]
+    fn recycling_pool() {
+        let mut pool = RecyclingPool::<BaseElement>::new(2);
+        let a = take_zeroed(&mut pool, 16);
+        let b = take_zeroed(&mut pool, 64);
+        let c = take_zeroed(&mut pool, 32);
+        assert_eq!(16, a.len());
+        assert_eq!(0, pool.num_buffers());
+
+        // only two buffers are retained; the smallest one is dropped
+        let (b_ptr, c_ptr) = (b.as_ptr(), c.as_ptr());
+        pool.release(a);
+        pool.release(b);
+        pool.release(c);
+        assert_eq!(2, pool.num_buffers());
+
+        // the smallest buffer which fits is reused
+        let d = take_zeroed(&mut pool, 20);
+        assert_eq!(20, d.len());
+        assert_eq!(c_ptr, d.as_ptr());
+        let e = take_zeroed(&mut pool, 8);
+        assert_eq!(8, e.len());
+        assert_eq!(b_ptr, e.as_ptr());
+
+        // when no retained buffer fits, a new one is allocated
+        pool.release(e);
+        let f = take_zeroed(&mut pool, 128);
+        assert_eq!(128, f.len());
+        assert_eq!(1, pool.num_buffers());
+    }
+
+    /// Takes a buffer from the pool and initializes it with zeros.
+    fn take_zeroed(pool: &mut RecyclingPool<BaseElement>, len: usize) -> Vec<BaseElement> {
+        let mut buffer = pool.take(len);
+        buffer.fill(MaybeUninit::new(BaseElement::ZERO));
+        // SAFETY: all values of the buffer were initialized above
+        unsafe { assume_init_buffer(buffer) }
+    }
+}
@@ -0,0 +1,163 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::ProverObserver;
+use core::time::Duration;
+use std::{mem, sync::Mutex, time::Instant};
+
+// PROVER PROFILE
+// ================================================================================================
+/// Resources used by the prover while generating a single proof, broken down by proof
+/// generation stages.
+///
+/// A profile can be obtained via [prove_with_profile()](crate::prove_with_profile) function.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProverProfile {
+    /// Profiles of proof generation stages in the order in which the stages were executed.
+    pub stages: Vec<StageProfile>,
+}
+
+impl ProverProfile {
+    /// Returns the total wall time of proof generation.
+    pub fn total_time(&self) -> Duration {
+        self.stages.iter().map(|stage| stage.time).sum()
+    }
+
+    /// Returns the total number of bytes hashed during proof generation.
+    pub fn total_hashed_bytes(&self) -> u64 {
+        self.stages.iter().map(|stage| stage.hashed_bytes).sum()
+    }
+}
+
+/// Resources used by the prover during a single proof generation stage.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StageProfile {
+    /// Name of the stage.
+    pub name: &'static str,
+    /// Wall time of the stage.
+    pub time: Duration,
+    /// Increase of the peak resident set size of the process during the stage (in bytes). This
+    /// is zero for stages which did not use more memory than the process has used before.
+    pub peak_rss_delta: usize,
+    /// Number of bytes hashed during the stage.
+    ///
+    /// This is measured via a counter shared by all threads in the process, and thus, includes
+    /// bytes hashed by other threads which were using the same hash functions at the same time.
+    pub hashed_bytes: u64,
+}
+
+// PROFILER
+// ================================================================================================
+/// A prover observer which records resources used by the prover between successive
+/// notifications.
+pub struct Profiler {
+    state: Mutex<ProfilerState>,
+}
+
+struct ProfilerState {
+    stages: Vec<StageProfile>,
+    stage_start: Instant,
+    peak_rss: usize,
+    hashed_bytes: u64,
+}
+
+impl Profiler {
+    /// Returns a new profiler; the first stage is assumed to start when this function is called.
+    pub fn new() -> Self {
+        Profiler {
+            state: Mutex::new(ProfilerState {
+                stages: Vec::new(),
+                stage_start: Instant::now(),
+                peak_rss: get_peak_rss(),
+                hashed_bytes: crypto::num_hashed_bytes(),
+            }),
+        }
+    }
+
+    /// Returns the profile of all stages recorded by this profiler.
+    pub fn into_profile(self) -> ProverProfile {
+        let state = self.state.into_inner().expect("profiler state is poisoned");
+        ProverProfile {
+            stages: state.stages,
+        }
+    }
+
+    /// Records resources used since the end of the previous stage as a stage with the specified
+    /// name. If the previous stage has the same name, the resources are added to it instead.
+    fn end_stage(&self, name: &'static str) {
+        let mut state = self.state.lock().expect("profiler state is poisoned");
+        let stage_start = mem::replace(&mut state.stage_start, Instant::now());
+        let peak_rss = mem::replace(&mut state.peak_rss, get_peak_rss());
+        let hashed_bytes = mem::replace(&mut state.hashed_bytes, crypto::num_hashed_bytes());
+
+        let stage = StageProfile {
+            name,
+            time: state.stage_start - stage_start,
+            peak_rss_delta: state.peak_rss.saturating_sub(peak_rss),
+            hashed_bytes: state.hashed_bytes - hashed_bytes,
+        };
+
+        match state.stages.last_mut() {
+            Some(last) if last.name == name => {
+                last.time += stage.time;
+                last.peak_rss_delta += stage.peak_rss_delta;
+                last.hashed_bytes += stage.hashed_bytes;
+            }
+            _ => state.stages.push(stage),
+        }
+    }
+}
+
+impl ProverObserver for Profiler {
+    fn on_trace_extended(&self) {
+        self.end_stage("extend execution trace");
+    }
+
+    fn on_trace_committed(&self) {
+        self.end_stage("commit to execution trace");
+    }
+
+    fn on_constraints_evaluated(&self, percent: usize) {
+        if percent == 100 {
+            self.end_stage("evaluate constraints");
+        }
+    }
+
+    fn on_constraints_committed(&self) {
+        self.end_stage("commit to constraint evaluations");
+    }
+
+    fn on_deep_composition_built(&self) {
+        self.end_stage("build DEEP composition polynomial");
+    }
+
+    fn on_fri_layer(&self, _layer_idx: usize) {
+        self.end_stage("build FRI layers");
+    }
+
+    fn on_proof_built(&self) {
+        self.end_stage("build proof object");
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the peak resident set size of the current process in bytes.
+fn get_peak_rss() -> usize {
+    let mut usage = unsafe { mem::zeroed::<libc::rusage>() };
+    let result = unsafe { libc::getrusage(libc::RUSAGE_SELF, &mut usage) };
+    if result != 0 {
+        return 0;
+    }
+
+    // on macOS, the peak resident set size is reported in bytes, and on other Unix systems,
+    // in kilobytes
+    if cfg!(target_os = "macos") {
+        usage.ru_maxrss as usize
+    } else {
+        usage.ru_maxrss as usize * 1024
+    }
+}
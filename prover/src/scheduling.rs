@@ -0,0 +1,31 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+// FRAGMENT SCHEDULING
+// ================================================================================================
+/// Defines how the constraint evaluation table is broken into fragments which are evaluated in
+/// parallel.
+///
+/// Fragment scheduling affects only the time needed to evaluate constraints, and only when the
+/// crate is compiled with `concurrent` feature enabled; proofs generated with all schedules are
+/// identical. Tables with fewer than 8192 rows are always evaluated as a single fragment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FragmentScheduling {
+    /// The table is broken into one fragment per thread (rounded up to the next power of two).
+    /// This keeps scheduling overhead to a minimum, but if some of the threads run on slower
+    /// cores (or are preempted by other work), all other threads wait for them to finish.
+    Static,
+    /// The table is broken into fragments of the specified number of rows (rounded up to the
+    /// next power of two, and to no fewer than 16 rows). Once a thread is done with its
+    /// fragments, it steals fragments not yet evaluated by other threads; thus, smaller fragments
+    /// lead to a more even load at the cost of more scheduling overhead.
+    WorkStealing(usize),
+    /// Similar to [FragmentScheduling::WorkStealing], but the fragment size is determined by a
+    /// calibration pass: the prover measures time it takes to evaluate constraints for a small
+    /// sample of rows, and selects the fragment size such that evaluating a single fragment takes
+    /// roughly 100 microseconds. The table is always broken into at least as many fragments as
+    /// there are threads.
+    AutoTuned,
+}
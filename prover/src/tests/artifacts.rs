@@ -0,0 +1,61 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{build_sum_options, SumAir, SumInstance};
+use crate::ProvingMode;
+use crypto::hashers::Blake3_256;
+use math::{fields::f128::BaseElement, StarkField};
+use utils::{collections::Vec, Serializable};
+
+// PROOF ARTIFACTS TESTS
+// ================================================================================================
+
+#[test]
+fn prove_with_artifacts() {
+    type QuadElement = <BaseElement as StarkField>::QuadExtension;
+
+    let sum = SumInstance::new(63, build_sum_options(true));
+    let proof = sum.prove();
+
+    // the proof generated alongside artifacts should be the same as the regular proof
+    let (artifacts_proof, artifacts) = crate::prove_with_artifacts::<SumAir, QuadElement>(
+        sum.build_trace(),
+        sum.pub_inputs(),
+        sum.options.clone(),
+        ProvingMode::InMemory,
+    )
+    .unwrap();
+    assert_eq!(proof.to_bytes(), artifacts_proof.to_bytes());
+
+    // commitments in the artifacts should match commitments in the proof
+    let num_fri_layers = proof.fri_proof.num_layers();
+    let (trace_roots, constraint_root, fri_roots) = proof
+        .commitments
+        .clone()
+        .parse::<Blake3_256<BaseElement>>(1, num_fri_layers)
+        .unwrap();
+    assert_eq!(vec![trace_roots[0].to_bytes()], artifacts.trace_roots);
+    assert_eq!(constraint_root.to_bytes(), artifacts.constraint_root);
+    assert_eq!(
+        fri_roots.iter().map(|r| r.to_bytes()).collect::<Vec<_>>(),
+        artifacts.fri_layer_roots
+    );
+    assert_eq!(artifacts.fri_layer_roots.len(), artifacts.fri_alphas.len());
+
+    // out-of-domain evaluations in the artifacts should match the ones in the proof
+    let num_evaluations = artifacts.ood_constraint_evaluations.len();
+    let (ood_frame, ood_evaluations) = proof
+        .ood_frame
+        .clone()
+        .parse::<QuadElement>(proof.context.trace_width(), num_evaluations)
+        .unwrap();
+    assert_eq!(ood_frame.current(), artifacts.ood_frame.current());
+    assert_eq!(ood_frame.next(), artifacts.ood_frame.next());
+    assert_eq!(ood_evaluations, artifacts.ood_constraint_evaluations);
+    assert_eq!(
+        proof.options().num_queries(),
+        artifacts.query_positions.len()
+    );
+}
@@ -0,0 +1,161 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{build_options, build_sum_options, SumAir, SumInstance};
+use crate::{
+    CheckpointStage, ProverCheckpoint, ProverError, ProvingMode, TraceCommitment,
+    TraceCommitmentJob, TraceCommitmentJobResult,
+};
+use air::Air;
+use crypto::hashers::Blake3_256;
+use math::fields::f128::BaseElement;
+use utils::{collections::Vec, Deserializable, Serializable, SliceReader};
+
+// CHECKPOINT TESTS
+// ================================================================================================
+
+#[test]
+fn prove_with_checkpoints() {
+    let sum = SumInstance::new(63, build_sum_options(true));
+    let proof = sum.prove();
+
+    for mode in [ProvingMode::InMemory, ProvingMode::Chunked] {
+        // generate a proof while saving checkpoints; this should not affect the proof
+        let mut checkpoints = Vec::new();
+        let checkpointed_proof = crate::prove_with_checkpoints::<SumAir>(
+            sum.build_trace(),
+            sum.pub_inputs(),
+            sum.options.clone(),
+            mode,
+            None,
+            |checkpoint| checkpoints.push(checkpoint.to_bytes()),
+        )
+        .unwrap();
+        assert_eq!(proof.to_bytes(), checkpointed_proof.to_bytes());
+        assert_eq!(2, checkpoints.len());
+
+        // resuming from either checkpoint should result in the same proof, and only the
+        // checkpoints after the resumed one should be saved
+        let stages = [
+            CheckpointStage::TraceCommitted,
+            CheckpointStage::ConstraintsCommitted,
+        ];
+        for (i, (bytes, stage)) in checkpoints.iter().zip(stages).enumerate() {
+            let checkpoint = ProverCheckpoint::from_bytes(bytes).unwrap();
+            assert_eq!(stage, checkpoint.stage());
+
+            let mut num_checkpoints = 0;
+            let resumed_proof = crate::prove_with_checkpoints::<SumAir>(
+                sum.build_trace(),
+                sum.pub_inputs(),
+                sum.options.clone(),
+                mode,
+                Some(checkpoint),
+                |_| num_checkpoints += 1,
+            )
+            .unwrap();
+            assert_eq!(proof.to_bytes(), resumed_proof.to_bytes());
+            assert_eq!(checkpoints.len() - i - 1, num_checkpoints);
+        }
+    }
+}
+
+#[test]
+fn prove_with_wrong_checkpoint() {
+    let sum = SumInstance::new(63, build_sum_options(false));
+
+    let mut checkpoint = None;
+    crate::prove_with_checkpoints::<SumAir>(
+        sum.build_trace(),
+        sum.pub_inputs(),
+        sum.options.clone(),
+        ProvingMode::InMemory,
+        None,
+        |c| checkpoint = Some(c.clone()),
+    )
+    .unwrap();
+
+    // a checkpoint cannot be used to generate a proof with different proof options
+    let options = build_options();
+    let result = crate::prove_with_checkpoints::<SumAir>(
+        sum.build_trace(),
+        sum.pub_inputs(),
+        options,
+        ProvingMode::InMemory,
+        checkpoint,
+        |_| {},
+    );
+    assert_eq!(Err(ProverError::InvalidCheckpoint), result.map(|_| ()));
+}
+
+// TRACE COMMITMENT TESTS
+// ================================================================================================
+
+#[test]
+fn prove_with_trace_commitment() {
+    let sum = SumInstance::new(63, build_sum_options(true));
+    let proof = sum.prove();
+
+    // commit to the trace before public inputs are known; the root of the commitment should be
+    // the same as the trace commitment in the proof
+    let commitment = TraceCommitment::new(sum.build_trace(), sum.options.clone());
+    let (trace_roots, _, _) = proof
+        .commitments
+        .clone()
+        .parse::<Blake3_256<BaseElement>>(1, proof.fri_proof.num_layers())
+        .unwrap();
+    assert_eq!(vec![trace_roots[0].to_bytes()], commitment.roots());
+
+    // proofs generated from the commitment, including after it was sent over the wire, should
+    // be identical to the proof generated from the trace
+    let commitment = TraceCommitment::from_bytes(&commitment.to_bytes()).unwrap();
+    for &mode in [ProvingMode::InMemory, ProvingMode::Chunked].iter() {
+        let committed_proof =
+            crate::prove_with_commitment::<SumAir>(&commitment, sum.pub_inputs(), mode).unwrap();
+        assert_eq!(proof.to_bytes(), committed_proof.to_bytes());
+    }
+}
+
+// DISTRIBUTED TRACE COMMITMENT TESTS
+// ================================================================================================
+
+#[test]
+fn prove_with_distributed_trace_commitment() {
+    let sum = SumInstance::new(63, build_sum_options(false));
+    let proof = sum.prove();
+
+    // build jobs on the coordinator
+    let trace = sum.build_trace();
+    let air = SumAir::new(trace.get_info(), sum.pub_inputs(), sum.options.clone());
+    let jobs = TraceCommitmentJob::build_all(&air);
+    assert_eq!(sum.options.blowup_factor(), jobs.len());
+    let trace_polys = crate::build_trace_polys(trace);
+
+    // execute jobs as if on remote workers, sending jobs and results over the wire in reverse
+    // order
+    let mut results = Vec::new();
+    for job in jobs.iter().rev() {
+        let job = TraceCommitmentJob::read_from(&mut SliceReader::new(&job.to_bytes())).unwrap();
+        let result = job.execute(&trace_polys);
+        assert_eq!(job.coset_idx(), result.coset_idx());
+        results.push(
+            TraceCommitmentJobResult::read_from(&mut SliceReader::new(&result.to_bytes())).unwrap(),
+        );
+    }
+
+    // merge the results and finish proof generation on the coordinator
+    let checkpoint = crate::merge_trace_commitments(&air, &sum.pub_inputs(), &trace_polys, results);
+    assert_eq!(CheckpointStage::TraceCommitted, checkpoint.stage());
+    let distributed_proof = crate::prove_with_checkpoints::<SumAir>(
+        sum.build_trace(),
+        sum.pub_inputs(),
+        sum.options.clone(),
+        ProvingMode::InMemory,
+        Some(checkpoint),
+        |_| {},
+    )
+    .unwrap();
+    assert_eq!(proof.to_bytes(), distributed_proof.to_bytes());
+}
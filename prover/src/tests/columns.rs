@@ -0,0 +1,40 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{build_sum_options, build_sum_trace, tweak_values, SumAir, SumInstance};
+use crate::{ProverError, ProvingMode, TraceCommitment};
+
+// COMMITTED COLUMN TESTS
+// ================================================================================================
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "register 0 does not match its column commitment")]
+fn prove_trace_does_not_match_column() {
+    let sum = SumInstance::new(63, build_sum_options(false));
+
+    // build the trace from a different set of values which have the same sum
+    let trace = build_sum_trace(&tweak_values(&sum.values));
+    let _ = crate::prove::<SumAir>(trace, sum.pub_inputs(), sum.options.clone());
+}
+
+#[test]
+fn prove_with_commitment_trace_does_not_match_column() {
+    let sum = SumInstance::new(63, build_sum_options(false));
+
+    // the trace is not validated when proving from a trace commitment; the prover must still
+    // refuse to generate a proof for a register which does not match its column commitment
+    let trace = build_sum_trace(&tweak_values(&sum.values));
+    let commitment = TraceCommitment::new(trace, sum.options.clone());
+    let result = crate::prove_with_commitment::<SumAir>(
+        &commitment,
+        sum.pub_inputs(),
+        ProvingMode::InMemory,
+    );
+    assert_eq!(
+        Some(ProverError::MismatchedColumnCommitment { register: 0 }),
+        result.err()
+    );
+}
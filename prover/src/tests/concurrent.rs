@@ -0,0 +1,70 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{build_sum_options, SumAir, SumInstance};
+use crate::{rayon, ProvingMode};
+use air::{FieldExtension, HashFunction, ProofOptions};
+use utils::collections::Vec;
+
+// THREAD POOL TESTS
+// ================================================================================================
+
+#[test]
+fn prove_in_pool() {
+    let sum = SumInstance::new(63, build_sum_options(false));
+    let proof = sum.prove();
+
+    // a proof generated in a dedicated thread pool should be identical to a proof generated in
+    // the global thread pool
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(2)
+        .build()
+        .unwrap();
+    let pool_proof = crate::prove_in_pool::<SumAir>(
+        sum.build_trace(),
+        sum.pub_inputs(),
+        sum.options.clone(),
+        ProvingMode::InMemory,
+        &pool,
+    )
+    .unwrap();
+    assert_eq!(proof.to_bytes(), pool_proof.to_bytes());
+}
+
+#[test]
+fn prove_with_any_thread_count() {
+    // grinding makes the prover search for proof-of-work nonces in parallel; many nonces are
+    // valid, but the same one must be selected regardless of the number of threads
+    let options = ProofOptions::new(
+        28,
+        8,
+        16,
+        HashFunction::Blake3_256,
+        FieldExtension::None,
+        4,
+        256,
+    )
+    .with_fri_layer_grinding_factor(8);
+    let sum = SumInstance::new(63, options);
+
+    let mut proofs = Vec::new();
+    for &num_threads in [1, 3, 8].iter() {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(num_threads)
+            .build()
+            .unwrap();
+        let proof = crate::prove_in_pool::<SumAir>(
+            sum.build_trace(),
+            sum.pub_inputs(),
+            sum.options.clone(),
+            ProvingMode::InMemory,
+            &pool,
+        )
+        .unwrap();
+        proofs.push(proof.to_bytes());
+    }
+    assert_eq!(proofs[0], proofs[1]);
+    assert_eq!(proofs[0], proofs[2]);
+}
@@ -0,0 +1,126 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{build_sum_options, SumAir, SumInstance};
+use crate::{BufferPool, FragmentScheduling, ProverContext, ProvingMode, RecyclingPool};
+use core::mem::MaybeUninit;
+use math::fields::f128::BaseElement;
+use std::sync::{Arc, Mutex};
+use utils::collections::Vec;
+
+// PROVER CONTEXT TESTS
+// ================================================================================================
+
+#[test]
+fn prove_with_context() {
+    let mut context = ProverContext::new();
+
+    // proofs generated with a shared context should be identical to proofs generated without it
+    for i in 0..3u128 {
+        let values = (0..63u128).map(|j| BaseElement::new(j * i + 1)).collect();
+        let sum = SumInstance::with_values(values, build_sum_options(true));
+        let proof = sum.prove();
+        let context_proof = crate::prove_with_context::<SumAir>(
+            sum.build_trace(),
+            sum.pub_inputs(),
+            sum.options.clone(),
+            ProvingMode::InMemory,
+            &mut context,
+        )
+        .unwrap();
+        assert_eq!(proof.to_bytes(), context_proof.to_bytes());
+    }
+
+    // all traces have the same shape, and thus only one set of values should have been cached
+    assert_eq!(1, context.num_shapes());
+}
+
+#[test]
+fn prove_with_fragment_scheduling() {
+    // the trace needs to be long enough for constraint evaluation to be split into fragments
+    let values = (0..8191u128).map(BaseElement::new).collect();
+    let sum = SumInstance::with_values(values, build_sum_options(false));
+
+    // proofs generated with all fragment schedules should be identical
+    let proof = sum.prove();
+    let schedules = [
+        FragmentScheduling::WorkStealing(16),
+        FragmentScheduling::WorkStealing(1000),
+        FragmentScheduling::AutoTuned,
+    ];
+    for &scheduling in schedules.iter() {
+        for &mode in [ProvingMode::InMemory, ProvingMode::Chunked].iter() {
+            let mut context = ProverContext::new().with_fragment_scheduling(scheduling);
+            let scheduled_proof = crate::prove_with_context::<SumAir>(
+                sum.build_trace(),
+                sum.pub_inputs(),
+                sum.options.clone(),
+                mode,
+                &mut context,
+            )
+            .unwrap();
+            assert_eq!(proof.to_bytes(), scheduled_proof.to_bytes());
+        }
+    }
+}
+
+#[test]
+fn prove_with_buffer_pool() {
+    let sum = SumInstance::new(63, build_sum_options(false));
+    let proof = sum.prove();
+
+    // proofs generated with buffers taken from a pool should be identical to proofs generated
+    // without a pool, and all buffers should be returned to the pool
+    let counts = Arc::new(Mutex::new((0, 0)));
+    let mut context = ProverContext::new().with_buffer_pool(CountingPool {
+        inner: RecyclingPool::new(8),
+        counts: counts.clone(),
+    });
+    for &mode in [
+        ProvingMode::InMemory,
+        ProvingMode::RowMajor,
+        ProvingMode::Transient,
+        ProvingMode::Chunked,
+    ]
+    .iter()
+    {
+        let pooled_proof = crate::prove_with_context::<SumAir>(
+            sum.build_trace(),
+            sum.pub_inputs(),
+            sum.options.clone(),
+            mode,
+            &mut context,
+        )
+        .unwrap();
+        assert_eq!(proof.to_bytes(), pooled_proof.to_bytes());
+
+        let (taken, released) = *counts.lock().unwrap();
+        assert_eq!(taken, released);
+    }
+    // the trace has 2 registers; in row-major mode, an extra buffer is taken for the rows,
+    // and in chunked mode, no buffers are taken
+    assert_eq!(2 + 3 + 2, counts.lock().unwrap().0);
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// A pool which counts buffers taken from and returned to a recycling pool.
+struct CountingPool {
+    inner: RecyclingPool<BaseElement>,
+    counts: Arc<Mutex<(usize, usize)>>,
+}
+
+impl BufferPool<BaseElement> for CountingPool {
+    fn take(&mut self, len: usize) -> Vec<MaybeUninit<BaseElement>> {
+        self.counts.lock().unwrap().0 += 1;
+        self.inner.take(len)
+    }
+
+    fn release(&mut self, buffer: Vec<BaseElement>) {
+        self.counts.lock().unwrap().1 += 1;
+        self.inner.release(buffer);
+    }
+}
@@ -3,13 +3,22 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::ExecutionTrace;
+use crate::{ExecutionTrace, StarkProof};
 use air::{
-    Air, AirContext, Assertion, EvaluationFrame, FieldExtension, HashFunction, ProofOptions,
-    TraceInfo, TransitionConstraintDegree,
+    Air, AirContext, Assertion, CommittedColumn, EvaluationFrame, FieldExtension, HashFunction,
+    ProofOptions, TraceInfo, TransitionConstraintDegree,
 };
 use math::{fields::f128::BaseElement, FieldElement, StarkField};
-use utils::collections::Vec;
+use utils::{collections::Vec, ByteWriter, Serializable};
+
+mod artifacts;
+mod checkpoint;
+mod columns;
+mod context;
+mod observer;
+
+#[cfg(feature = "concurrent")]
+mod concurrent;
 
 // FIBONACCI TRACE BUILDER
 // ================================================================================================
@@ -39,56 +48,20 @@ pub struct MockAir {
 
 impl MockAir {
     pub fn with_trace_length(trace_length: usize) -> Self {
-        Self::new(
-            TraceInfo::new(4, trace_length),
-            (),
-            ProofOptions::new(
-                32,
-                8,
-                0,
-                HashFunction::Blake3_256,
-                FieldExtension::None,
-                4,
-                256,
-            ),
-        )
+        Self::new(TraceInfo::new(4, trace_length), (), build_options())
     }
 
     pub fn with_periodic_columns(
         column_values: Vec<Vec<BaseElement>>,
         trace_length: usize,
     ) -> Self {
-        let mut result = Self::new(
-            TraceInfo::new(4, trace_length),
-            (),
-            ProofOptions::new(
-                32,
-                8,
-                0,
-                HashFunction::Blake3_256,
-                FieldExtension::None,
-                4,
-                256,
-            ),
-        );
+        let mut result = Self::new(TraceInfo::new(4, trace_length), (), build_options());
         result.periodic_columns = column_values;
         result
     }
 
     pub fn with_assertions(assertions: Vec<Assertion<BaseElement>>, trace_length: usize) -> Self {
-        let mut result = Self::new(
-            TraceInfo::new(4, trace_length),
-            (),
-            ProofOptions::new(
-                32,
-                8,
-                0,
-                HashFunction::Blake3_256,
-                FieldExtension::None,
-                4,
-                256,
-            ),
-        );
+        let mut result = Self::new(TraceInfo::new(4, trace_length), (), build_options());
         result.assertions = assertions;
         result
     }
@@ -99,7 +72,7 @@ impl Air for MockAir {
     type PublicInputs = ();
 
     fn new(trace_info: TraceInfo, _pub_inputs: (), _options: ProofOptions) -> Self {
-        let context = build_context(trace_info, 8);
+        let context = build_context(trace_info);
         MockAir {
             context,
             assertions: Vec::new(),
@@ -128,19 +101,177 @@ impl Air for MockAir {
     }
 }
 
+// SUM AIR
+// ================================================================================================
+
+/// Public inputs of [SumAir]: a commitment to the summed values and the sum of the values.
+pub struct SumInputs {
+    pub commitment: Vec<u8>,
+    pub result: BaseElement,
+}
+
+impl Serializable for SumInputs {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8_slice(&self.commitment);
+        target.write(self.result);
+    }
+}
+
+/// AIR for summing up values of a committed column; the first register contains the values,
+/// and the second register accumulates them.
+pub struct SumAir {
+    context: AirContext<BaseElement>,
+    commitment: Vec<u8>,
+    result: BaseElement,
+}
+
+impl Air for SumAir {
+    type BaseElement = BaseElement;
+    type PublicInputs = SumInputs;
+
+    fn new(trace_info: TraceInfo, pub_inputs: SumInputs, options: ProofOptions) -> Self {
+        let degrees = vec![TransitionConstraintDegree::new(1)];
+        SumAir {
+            context: AirContext::new(trace_info, degrees, options),
+            commitment: pub_inputs.commitment,
+            result: pub_inputs.result,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseElement> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseElement>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        result[0] = next[1] - (current[1] + current[0]);
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseElement>> {
+        let last_step = self.last_step();
+        vec![
+            Assertion::single(1, 0, BaseElement::ZERO),
+            Assertion::single(1, last_step, self.result),
+        ]
+    }
+
+    fn get_committed_columns(&self) -> Vec<CommittedColumn> {
+        vec![CommittedColumn::new(0, self.commitment.clone())]
+    }
+}
+
+/// An instance of the sum computation defined by [SumAir].
+pub struct SumInstance {
+    pub options: ProofOptions,
+    pub values: Vec<BaseElement>,
+    pub commitment: Vec<u8>,
+    pub result: BaseElement,
+}
+
+impl SumInstance {
+    /// Returns an instance which sums up `num_values` values; `num_values + 1` must be a power
+    /// of two.
+    pub fn new(num_values: usize, options: ProofOptions) -> Self {
+        let values = (0..num_values as u128)
+            .map(|i| BaseElement::new(i * i + 1))
+            .collect();
+        Self::with_values(values, options)
+    }
+
+    /// Returns an instance which sums up the specified values.
+    pub fn with_values(values: Vec<BaseElement>, options: ProofOptions) -> Self {
+        let commitment = CommittedColumn::build_commitment(&build_sum_column(&values), &options);
+        let result = values.iter().fold(BaseElement::ZERO, |acc, &v| acc + v);
+        SumInstance {
+            options,
+            values,
+            commitment,
+            result,
+        }
+    }
+
+    pub fn pub_inputs(&self) -> SumInputs {
+        SumInputs {
+            commitment: self.commitment.clone(),
+            result: self.result,
+        }
+    }
+
+    pub fn build_trace(&self) -> ExecutionTrace<BaseElement> {
+        build_sum_trace(&self.values)
+    }
+
+    pub fn prove(&self) -> StarkProof {
+        crate::prove::<SumAir>(self.build_trace(), self.pub_inputs(), self.options.clone()).unwrap()
+    }
+}
+
+/// Builds an execution trace for summing up the specified values; the values are padded with a
+/// single zero so that the number of steps is a power of two.
+pub fn build_sum_trace(values: &[BaseElement]) -> ExecutionTrace<BaseElement> {
+    let column = build_sum_column(values);
+    let mut trace = ExecutionTrace::new(2, column.len());
+    trace.fill(
+        |state| {
+            state[0] = column[0];
+            state[1] = BaseElement::ZERO;
+        },
+        |step, state| {
+            state[1] += state[0];
+            state[0] = column[step + 1];
+        },
+    );
+    trace
+}
+
+/// Returns values of the committed column for the specified values.
+pub fn build_sum_column(values: &[BaseElement]) -> Vec<BaseElement> {
+    let mut column = values.to_vec();
+    column.push(BaseElement::ZERO);
+    column
+}
+
+/// Returns a copy of the specified values, with two of the values changed such that the sum of
+/// the values remains the same.
+pub fn tweak_values(values: &[BaseElement]) -> Vec<BaseElement> {
+    let mut values = values.to_vec();
+    values[0] += BaseElement::ONE;
+    values[1] -= BaseElement::ONE;
+    values
+}
+
+pub fn build_sum_options(use_extension_field: bool) -> ProofOptions {
+    let extension = if use_extension_field {
+        FieldExtension::Quadratic
+    } else {
+        FieldExtension::None
+    };
+    ProofOptions::new(28, 8, 0, HashFunction::Blake3_256, extension, 4, 256)
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
-fn build_context<B: StarkField>(trace_info: TraceInfo, blowup_factor: usize) -> AirContext<B> {
-    let options = ProofOptions::new(
+pub fn build_options() -> ProofOptions {
+    ProofOptions::new(
         32,
-        blowup_factor,
+        8,
         0,
         HashFunction::Blake3_256,
         FieldExtension::None,
         4,
         256,
-    );
+    )
+}
+
+fn build_context<B: StarkField>(trace_info: TraceInfo) -> AirContext<B> {
+    let options = build_options();
     let t_degrees = vec![TransitionConstraintDegree::new(2)];
     AirContext::new(trace_info, t_degrees, options)
 }
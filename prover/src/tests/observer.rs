@@ -0,0 +1,132 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{build_sum_options, SumAir, SumInstance};
+use crate::{ProverObserver, ProvingMode};
+use std::sync::Mutex;
+use utils::{collections::Vec, string::String};
+
+// OBSERVER TESTS
+// ================================================================================================
+
+#[test]
+fn prove_with_observer() {
+    let sum = SumInstance::new(63, build_sum_options(false));
+    let proof = sum.prove();
+
+    for mode in [ProvingMode::InMemory, ProvingMode::Chunked] {
+        let observer = EventLog::default();
+        let observed_proof = crate::prove_with_observer::<SumAir>(
+            sum.build_trace(),
+            sum.pub_inputs(),
+            sum.options.clone(),
+            mode,
+            &observer,
+        )
+        .unwrap();
+        assert_eq!(proof.to_bytes(), observed_proof.to_bytes());
+
+        // constraint evaluation progress may be reported several times and out of order, but
+        // must reach 100 percent before constraints are committed to
+        let mut events = observer.0.into_inner().unwrap();
+        let num_evaluated = events.iter().filter(|e| e.starts_with("evaluated")).count();
+        assert!(num_evaluated > 0);
+        assert!(events.contains(&"evaluated 100".to_string()));
+        let first = events
+            .iter()
+            .position(|e| e.starts_with("evaluated"))
+            .unwrap();
+        events.drain(first..first + num_evaluated);
+
+        let num_fri_layers = proof.fri_proof.num_layers() + 1;
+        let mut expected = vec!["trace extended", "trace committed", "constraints committed"]
+            .into_iter()
+            .map(String::from)
+            .collect::<Vec<_>>();
+        expected.push("deep composition built".to_string());
+        expected.extend((0..num_fri_layers).map(|i| format!("fri layer {}", i)));
+        expected.push("proof built".to_string());
+        assert_eq!(expected, events);
+    }
+}
+
+#[test]
+#[cfg(feature = "profiling")]
+fn prove_with_profile() {
+    let sum = SumInstance::new(63, build_sum_options(false));
+    let proof = sum.prove();
+
+    let (profiled_proof, profile) = crate::prove_with_profile::<SumAir>(
+        sum.build_trace(),
+        sum.pub_inputs(),
+        sum.options.clone(),
+        ProvingMode::InMemory,
+    )
+    .unwrap();
+    assert_eq!(proof.to_bytes(), profiled_proof.to_bytes());
+
+    let stages = profile.stages.iter().map(|s| s.name).collect::<Vec<_>>();
+    assert_eq!(
+        vec![
+            "extend execution trace",
+            "commit to execution trace",
+            "evaluate constraints",
+            "commit to constraint evaluations",
+            "build DEEP composition polynomial",
+            "build FRI layers",
+            "build proof object",
+        ],
+        stages
+    );
+
+    // committing to the trace requires hashing every row of the extended trace; the trace has
+    // 2 registers, is extended by a factor of 8, and each element is 16 bytes
+    let trace_length = proof.trace_length();
+    assert!(profile.stages[1].hashed_bytes >= (trace_length * 8 * 2 * 16) as u64);
+    assert!(profile.total_hashed_bytes() > 0);
+}
+
+// HELPER TYPES
+// ================================================================================================
+
+/// Records all notifications received from the prover.
+#[derive(Default)]
+struct EventLog(Mutex<Vec<String>>);
+
+impl EventLog {
+    fn record(&self, event: String) {
+        self.0.lock().unwrap().push(event);
+    }
+}
+
+impl ProverObserver for EventLog {
+    fn on_trace_extended(&self) {
+        self.record("trace extended".to_string());
+    }
+
+    fn on_trace_committed(&self) {
+        self.record("trace committed".to_string());
+    }
+
+    fn on_constraints_evaluated(&self, percent: usize) {
+        self.record(format!("evaluated {}", percent));
+    }
+
+    fn on_constraints_committed(&self) {
+        self.record("constraints committed".to_string());
+    }
+
+    fn on_deep_composition_built(&self) {
+        self.record("deep composition built".to_string());
+    }
+
+    fn on_fri_layer(&self, layer_idx: usize) {
+        self.record(format!("fri layer {}", layer_idx));
+    }
+
+    fn on_proof_built(&self) {
+        self.record("proof built".to_string());
+    }
+}
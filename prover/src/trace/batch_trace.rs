@@ -0,0 +1,85 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::Trace;
+use utils::collections::Vec;
+
+// BATCH TRACE
+// ================================================================================================
+/// Execution traces of a batch of computations stacked side by side.
+///
+/// Registers of trace `i` occupy registers `i * w..(i + 1) * w` of this trace, where `w` is the
+/// width of a single trace. This is the trace layout expected by [BatchAir](air::BatchAir).
+pub struct BatchTrace<T: Trace> {
+    traces: Vec<T>,
+}
+
+impl<T: Trace> BatchTrace<T> {
+    /// Returns a trace consisting of the specified traces stacked side by side.
+    ///
+    /// # Panics
+    /// Panics if `traces` is empty, or if the traces differ in width, length, unpadded length,
+    /// or metadata.
+    pub fn new(traces: Vec<T>) -> Self {
+        assert!(
+            !traces.is_empty(),
+            "a batch must contain at least one trace"
+        );
+        let info = traces[0].get_info();
+        for (i, trace) in traces.iter().enumerate().skip(1) {
+            assert_eq!(
+                info,
+                trace.get_info(),
+                "trace {} in the batch has a different shape than trace 0",
+                i
+            );
+        }
+        BatchTrace { traces }
+    }
+}
+
+impl<T: Trace> Trace for BatchTrace<T> {
+    type BaseField = T::BaseField;
+
+    fn width(&self) -> usize {
+        self.traces.iter().map(|trace| trace.width()).sum()
+    }
+
+    fn length(&self) -> usize {
+        self.traces[0].length()
+    }
+
+    fn meta(&self) -> &[u8] {
+        self.traces[0].meta()
+    }
+
+    fn read_row_into(&self, step: usize, target: &mut [Self::BaseField]) {
+        let width = self.traces[0].width();
+        for (trace, target) in self.traces.iter().zip(target.chunks_mut(width)) {
+            trace.read_row_into(step, target);
+        }
+    }
+
+    fn get_column(&self, register: usize) -> Vec<Self::BaseField> {
+        let width = self.traces[0].width();
+        self.traces[register / width].get_column(register % width)
+    }
+
+    fn unpadded_length(&self) -> usize {
+        self.traces[0].unpadded_length()
+    }
+
+    fn cycle_length(&self, register: usize) -> usize {
+        let width = self.traces[0].width();
+        self.traces[register / width].cycle_length(register % width)
+    }
+
+    fn into_columns(self) -> Vec<Vec<Self::BaseField>> {
+        self.traces
+            .into_iter()
+            .flat_map(|trace| trace.into_columns())
+            .collect()
+    }
+}
@@ -0,0 +1,209 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{trace_table::query_segments, StarkDomain, TracePolyTable};
+use air::proof::Queries;
+use core::{mem, ops::Range};
+use crypto::{ElementHasher, Hasher, MerkleTree};
+use math::{log2, FieldElement, StarkField};
+use utils::{batch_iter_mut, collections::Vec, iter_mut};
+
+#[cfg(feature = "concurrent")]
+use utils::iterators::*;
+
+// CHUNKED TRACE TABLE
+// ================================================================================================
+/// A low-degree extension of an execution trace which is never fully materialized in memory.
+///
+/// The LDE domain consists of `blowup` cosets of the trace domain, such that row `i` of the
+/// extended trace belongs to coset `i % blowup`. Instead of keeping all rows of the extended
+/// trace in memory, this table keeps only the trace polynomials, and evaluates them over a single
+/// coset at a time whenever rows of the extended trace are needed. Thus, the memory needed to
+/// hold the extended trace is reduced from `blowup` times the size of the trace to about twice
+/// the size of the trace, at the cost of evaluating trace polynomials several times.
+pub struct ChunkedTraceTable<'a, B: StarkField> {
+    polys: &'a TracePolyTable<B>,
+    domain: &'a StarkDomain<B>,
+}
+
+impl<'a, B: StarkField> ChunkedTraceTable<'a, B> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Creates a new chunked trace table for the specified trace polynomials.
+    pub fn new(polys: &'a TracePolyTable<B>, domain: &'a StarkDomain<B>) -> Self {
+        assert_eq!(
+            polys.poly_size(),
+            domain.trace_length(),
+            "inconsistent trace length"
+        );
+        ChunkedTraceTable { polys, domain }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns number of registers in the extended trace.
+    pub fn width(&self) -> usize {
+        self.polys.num_polys()
+    }
+
+    /// Returns the number of states in the extended trace.
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        self.domain.lde_domain_size()
+    }
+
+    /// Returns blowup factor which was used to extend original trace into this trace.
+    pub fn blowup(&self) -> usize {
+        self.domain.trace_to_lde_blowup()
+    }
+
+    // COSET EVALUATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Evaluates all trace polynomials over the trace domain shifted by the specified
+    /// `coset_offset`, and returns the results as a set of register traces.
+    ///
+    /// Step `i` of the returned traces corresponds to the point `coset_offset * g^i`, where `g`
+    /// is the generator of the trace domain.
+    pub fn evaluate_coset(&self, coset_offset: B) -> Vec<Vec<B>> {
+        self.polys
+            .evaluate_with_offset(self.domain.trace_twiddles(), coset_offset, 1)
+    }
+
+    // TRACE COMMITMENT
+    // --------------------------------------------------------------------------------------------
+    /// Builds a Merkle tree out of the rows of the extended trace for each of the specified
+    /// trace `segments` (hash of the segment's values in each row becomes a leaf in the tree of
+    /// the segment).
+    ///
+    /// The rows are computed one coset of the LDE domain at a time; the rows of each coset are
+    /// hashed for all segments, and the trees of all segments are built in parallel when
+    /// `concurrent` feature is enabled.
+    pub fn build_commitment<H: ElementHasher<BaseField = B>>(
+        &self,
+        segments: &[Range<usize>],
+    ) -> Vec<MerkleTree<H>> {
+        let blowup = self.blowup();
+        let mut hashed_states = vec![vec![H::Digest::default(); self.len()]; segments.len()];
+
+        for coset_idx in 0..blowup {
+            let coset = self.evaluate_coset(self.get_coset_offset(coset_idx));
+            for (segment, segment_hashes) in segments.iter().zip(hashed_states.iter_mut()) {
+                let coset_hashes = hash_rows::<H>(&coset[segment.clone()]);
+
+                // row `i` of the coset is row `i * blowup + coset_idx` of the extended trace
+                for (i, &row_hash) in coset_hashes.iter().enumerate() {
+                    segment_hashes[i * blowup + coset_idx] = row_hash;
+                }
+            }
+        }
+
+        iter_mut!(hashed_states)
+            .map(|segment_hashes| {
+                MerkleTree::new(mem::take(segment_hashes))
+                    .expect("failed to construct trace Merkle tree")
+            })
+            .collect()
+    }
+
+    // QUERY TRACE
+    // --------------------------------------------------------------------------------------------
+    /// Returns rows of the extended trace at the specified positions split into the specified
+    /// `segments`, along with Merkle authentication paths from the roots of the segment
+    /// `commitments` to these rows.
+    ///
+    /// Only the cosets of the LDE domain which contain queried rows are evaluated.
+    pub fn query<H: Hasher>(
+        &self,
+        segments: &[Range<usize>],
+        commitments: Vec<MerkleTree<H>>,
+        positions: &[usize],
+    ) -> Vec<Queries> {
+        let blowup = self.blowup();
+        let mut trace_states = vec![Vec::new(); positions.len()];
+        for coset_idx in 0..blowup {
+            if positions.iter().all(|&p| p % blowup != coset_idx) {
+                continue;
+            }
+
+            let coset = self.evaluate_coset(self.get_coset_offset(coset_idx));
+            for (&p, state) in positions.iter().zip(trace_states.iter_mut()) {
+                if p % blowup == coset_idx {
+                    *state = coset.iter().map(|r| r[p / blowup]).collect();
+                }
+            }
+        }
+
+        query_segments(self.len(), segments, commitments, trace_states, positions)
+    }
+
+    /// Returns rows of the extended trace at the specified positions split into the specified
+    /// `segments`, along with Merkle authentication paths from the roots of the segment
+    /// `commitments` to these rows.
+    ///
+    /// Unlike [ChunkedTraceTable::query()], no cosets are evaluated; instead, all trace
+    /// polynomials are evaluated directly at the point of the LDE domain corresponding to each
+    /// of the queried rows.
+    pub fn query_points<H: Hasher>(
+        &self,
+        segments: &[Range<usize>],
+        commitments: Vec<MerkleTree<H>>,
+        positions: &[usize],
+    ) -> Vec<Queries> {
+        // row `i` of the extended trace corresponds to the point `s * g^i`, where `s` is the LDE
+        // domain offset and `g` is the generator of the LDE domain
+        let g = B::get_root_of_unity(log2(self.len()));
+        let trace_states = positions
+            .iter()
+            .map(|&p| {
+                let x = self.domain.offset() * g.exp((p as u64).into());
+                self.polys.evaluate_at(x)
+            })
+            .collect();
+
+        query_segments(self.len(), segments, commitments, trace_states, positions)
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the offset of the coset of the LDE domain at the specified index. This is
+    /// computed as `s * g^coset_idx`, where `s` is the LDE domain offset and `g` is the generator
+    /// of the LDE domain.
+    fn get_coset_offset(&self, coset_idx: usize) -> B {
+        let g = B::get_root_of_unity(log2(self.len()));
+        self.domain.offset() * g.exp((coset_idx as u64).into())
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns hashes of all rows of a table described by the specified `registers`; rows are
+/// hashed the same way as rows of the extended trace are hashed in a trace table.
+pub fn hash_rows<H: ElementHasher>(registers: &[Vec<H::BaseField>]) -> Vec<H::Digest> {
+    let mut row_hashes = vec![H::Digest::default(); registers[0].len()];
+    batch_iter_mut!(
+        &mut row_hashes,
+        128, // min batch size
+        |batch: &mut [H::Digest], batch_offset: usize| {
+            let mut row = vec![H::BaseField::ZERO; registers.len()];
+            for (i, row_hash) in batch.iter_mut().enumerate() {
+                read_row_into(registers, i + batch_offset, &mut row);
+                *row_hash = H::hash_elements(&row);
+            }
+        }
+    );
+    row_hashes
+}
+
+/// Copies values of all `registers` at the specified `step` into the `row` slice.
+#[inline(always)]
+fn read_row_into<B: StarkField>(registers: &[Vec<B>], step: usize, row: &mut [B]) {
+    for (register, value) in registers.iter().zip(row.iter_mut()) {
+        *value = register[step];
+    }
+}
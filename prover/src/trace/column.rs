@@ -0,0 +1,201 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use core::ops::{Deref, DerefMut};
+use math::StarkField;
+use utils::collections::Vec;
+
+#[cfg(feature = "mmap")]
+use std::{
+    fs::{self, OpenOptions},
+    mem::size_of,
+    os::unix::io::AsRawFd,
+    process,
+    ptr::{self, NonNull},
+    slice,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+// TRACE COLUMN
+// ================================================================================================
+/// A column of field elements which holds values of a single register of an execution trace,
+/// or coefficients of a single trace polynomial.
+///
+/// A column is stored either on the heap, or, when `mmap` feature is enabled, in a
+/// memory-mapped file. Either way, the column can be accessed as a slice of elements.
+pub enum Column<B: StarkField> {
+    Heap(Vec<B>),
+    #[cfg(feature = "mmap")]
+    Mapped(MappedColumn<B>),
+}
+
+impl<B: StarkField> Column<B> {
+    /// Converts this column into a vector; values of a memory-mapped column are copied into
+    /// a newly allocated vector.
+    pub fn into_vec(self) -> Vec<B> {
+        match self {
+            Column::Heap(values) => values,
+            #[cfg(feature = "mmap")]
+            Column::Mapped(values) => values.to_vec(),
+        }
+    }
+}
+
+impl<B: StarkField> From<Vec<B>> for Column<B> {
+    fn from(values: Vec<B>) -> Self {
+        Column::Heap(values)
+    }
+}
+
+impl<B: StarkField> Deref for Column<B> {
+    type Target = [B];
+
+    fn deref(&self) -> &[B] {
+        match self {
+            Column::Heap(values) => values,
+            #[cfg(feature = "mmap")]
+            Column::Mapped(values) => values,
+        }
+    }
+}
+
+impl<B: StarkField> DerefMut for Column<B> {
+    fn deref_mut(&mut self) -> &mut [B] {
+        match self {
+            Column::Heap(values) => values,
+            #[cfg(feature = "mmap")]
+            Column::Mapped(values) => values,
+        }
+    }
+}
+
+// MEMORY-MAPPED COLUMN
+// ================================================================================================
+#[cfg(feature = "mmap")]
+static NEXT_FILE_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// A column of elements stored in a memory-mapped temporary file.
+///
+/// The file is created in the directory returned by [std::env::temp_dir()] (on Unix systems,
+/// this can be changed via `TMPDIR` environment variable), and is removed from the file system
+/// right after it is mapped into memory. Thus, the disk space used by the file is released as
+/// soon as the column is dropped, and the operating system can move pages of the column between
+/// memory and disk as needed.
+#[cfg(feature = "mmap")]
+pub struct MappedColumn<B: StarkField> {
+    ptr: NonNull<B>,
+    len: usize,
+}
+
+#[cfg(feature = "mmap")]
+impl<B: StarkField> MappedColumn<B> {
+    /// Returns a new memory-mapped column of the specified length with all elements set to
+    /// zero bytes; since field elements are represented by integers, this is a valid
+    /// representation of some field element (usually, ZERO).
+    ///
+    /// # Panics
+    /// Panics if `len` is zero, or if the backing file could not be created or mapped into
+    /// memory.
+    pub fn new(len: usize) -> Self {
+        assert!(len > 0, "memory-mapped column cannot be empty");
+        let num_bytes = len * size_of::<B>();
+
+        // create a new file and extend it to the required size; the file is filled with zeros
+        let path = std::env::temp_dir().join(format!(
+            "winterfell-{}-{}.col",
+            process::id(),
+            NEXT_FILE_ID.fetch_add(1, Ordering::Relaxed)
+        ));
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create_new(true)
+            .open(&path)
+            .unwrap_or_else(|err| panic!("failed to create file {}: {}", path.display(), err));
+        let result = file.set_len(num_bytes as u64);
+
+        // map the file into memory; the mapping remains valid after the file is closed and
+        // removed from the file system
+        let ptr = result.map(|_| unsafe {
+            libc::mmap(
+                ptr::null_mut(),
+                num_bytes,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_SHARED,
+                file.as_raw_fd(),
+                0,
+            )
+        });
+        fs::remove_file(&path).ok();
+        let ptr = ptr.unwrap_or_else(|err| panic!("failed to resize file: {}", err));
+        assert!(
+            ptr != libc::MAP_FAILED,
+            "failed to map file into memory: {}",
+            std::io::Error::last_os_error()
+        );
+
+        MappedColumn {
+            ptr: NonNull::new(ptr as *mut B).expect("memory mapping cannot be null"),
+            len,
+        }
+    }
+}
+
+#[cfg(feature = "mmap")]
+impl<B: StarkField> Drop for MappedColumn<B> {
+    fn drop(&mut self) {
+        unsafe {
+            libc::munmap(
+                self.ptr.as_ptr() as *mut libc::c_void,
+                self.len * size_of::<B>(),
+            );
+        }
+    }
+}
+
+#[cfg(feature = "mmap")]
+impl<B: StarkField> Deref for MappedColumn<B> {
+    type Target = [B];
+
+    fn deref(&self) -> &[B] {
+        unsafe { slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+#[cfg(feature = "mmap")]
+impl<B: StarkField> DerefMut for MappedColumn<B> {
+    fn deref_mut(&mut self) -> &mut [B] {
+        unsafe { slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+// the column exclusively owns its memory mapping, and thus, can be sent and shared between
+// threads in the same way as a vector
+#[cfg(feature = "mmap")]
+unsafe impl<B: StarkField> Send for MappedColumn<B> {}
+#[cfg(feature = "mmap")]
+unsafe impl<B: StarkField> Sync for MappedColumn<B> {}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(all(test, feature = "mmap"))]
+mod tests {
+    use super::{Column, MappedColumn};
+    use math::{fields::f128::BaseElement, FieldElement};
+
+    #[test]
+    fn mapped_column() {
+        let mut column = Column::Mapped(MappedColumn::<BaseElement>::new(1024));
+        assert_eq!(1024, column.len());
+        assert!(column.iter().all(|&v| v == BaseElement::ZERO));
+
+        for (i, value) in column.iter_mut().enumerate() {
+            *value = BaseElement::new(i as u128);
+        }
+        let expected = (0..1024).map(BaseElement::new).collect::<Vec<_>>();
+        assert_eq!(expected, column.into_vec());
+    }
+}
@@ -0,0 +1,90 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::TracePolyTable;
+use crate::{ProverError, StarkDomain};
+use air::{proof::Queries, Air, CommittedColumn};
+use crypto::{ElementHasher, MerkleTree};
+use math::{fft, StarkField};
+use utils::{collections::Vec, Serializable};
+
+// COMMITTED REGISTERS
+// ================================================================================================
+/// Evaluations of the registers tied to committed public columns over the LDE domain, together
+/// with the Merkle trees built from these evaluations.
+///
+/// The trees are built once per proof; they are used to make sure the registers match the column
+/// commitments, and to open the columns to the verifier at the queried positions.
+pub struct CommittedRegisters<B: StarkField, H: ElementHasher<BaseField = B>> {
+    evaluations: Vec<Vec<B>>,
+    trees: Vec<MerkleTree<H>>,
+}
+
+impl<B: StarkField, H: ElementHasher<BaseField = B>> CommittedRegisters<B, H> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Evaluates polynomials of the registers tied to committed columns of the specified `air`
+    /// over the LDE domain, and makes sure the Merkle trees built from the resulting evaluations
+    /// match the column commitments.
+    ///
+    /// # Errors
+    /// Returns [ProverError::MismatchedColumnCommitment] if a register tied to a committed
+    /// column does not exist, or if the commitment to the evaluations of the register is
+    /// different from the commitment to the column.
+    pub fn new<A>(
+        air: &A,
+        trace_polys: &TracePolyTable<B>,
+        domain: &StarkDomain<B>,
+    ) -> Result<Self, ProverError>
+    where
+        A: Air<BaseElement = B>,
+    {
+        let columns = air.get_committed_columns();
+        let mut evaluations = Vec::with_capacity(columns.len());
+        let mut trees = Vec::with_capacity(columns.len());
+        for column in columns {
+            let register = column.register();
+            if register >= trace_polys.num_polys() {
+                return Err(ProverError::MismatchedColumnCommitment { register });
+            }
+
+            let register_evaluations = fft::evaluate_poly_with_offset(
+                trace_polys.get_poly(register),
+                domain.trace_twiddles(),
+                domain.offset(),
+                domain.trace_to_lde_blowup(),
+            );
+            let tree = CommittedColumn::build_tree::<H>(&register_evaluations);
+            if column.commitment() != &tree.root().to_bytes()[..] {
+                return Err(ProverError::MismatchedColumnCommitment { register });
+            }
+
+            evaluations.push(register_evaluations);
+            trees.push(tree);
+        }
+
+        Ok(CommittedRegisters { evaluations, trees })
+    }
+
+    // QUERIES
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns evaluations of every register at the specified positions of the LDE domain
+    /// together with Merkle authentication paths from the column commitments to these
+    /// evaluations.
+    pub fn query(&self, positions: &[usize]) -> Vec<Queries> {
+        self.evaluations
+            .iter()
+            .zip(self.trees.iter())
+            .map(|(evaluations, tree)| {
+                let values = positions.iter().map(|&i| vec![evaluations[i]]).collect();
+                let proof = tree
+                    .prove_batch(positions)
+                    .expect("failed to generate a Merkle proof for committed column queries");
+                Queries::new(proof, values)
+            })
+            .collect()
+    }
+}
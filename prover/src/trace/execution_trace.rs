@@ -3,10 +3,13 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{StarkDomain, TracePolyTable, TraceTable};
-use air::{Air, EvaluationFrame, TraceInfo};
-use math::{fft, log2, polynom, StarkField};
-use utils::{collections::Vec, iter_mut, uninit_vector};
+use super::{Column, Trace};
+use air::{TraceInfo, TracePadding};
+use core::iter;
+use math::{log2, StarkField};
+use utils::{
+    collections::Vec, table::fragment_columns, uninit_vector, ByteWriter, DeserializationError,
+};
 
 #[cfg(not(feature = "concurrent"))]
 use utils::collections::vec;
@@ -14,6 +17,9 @@ use utils::collections::vec;
 #[cfg(feature = "concurrent")]
 use utils::{iterators::*, rayon};
 
+#[cfg(feature = "mmap")]
+use super::MappedColumn;
+
 // CONSTANTS
 // ================================================================================================
 
@@ -51,6 +57,20 @@ const MIN_FRAGMENT_LENGTH: usize = 2;
 /// This function work just like [ExecutionTrace::new()] function, but also takes a metadata
 /// parameter which can be an arbitrary sequence of bytes up to 64KB in size.
 ///
+/// # Padded traces
+/// For computations whose meaningful length is not a power of two, you can use
+/// [ExecutionTrace::init_padded()] or [ExecutionTrace::init_padded_with()] functions. These
+/// functions append padding rows to the provided register traces until the length of the trace
+/// reaches the next power of two. The number of meaningful rows is recorded in the trace info,
+/// and is available to the AIR via [Air::last_step()](crate::Air::last_step) method.
+///
+/// A trace can also be padded to a fixed length via [ExecutionTrace::init_padded_to()] or
+/// [ExecutionTrace::init_padded_to_with()] functions. This way, computations of different sizes
+/// can be proven using traces of the same length (e.g., by a proving service which supports a
+/// single trace length). The number of meaningful rows is included in the proof context, and
+/// thus, assertions placed at [Air::last_step()](crate::Air::last_step) bind the proof to the
+/// effective length of the computation.
+///
 /// # Concurrent trace generation
 /// For computations which consist of many small independent computations, we can generate the
 /// execution trace of the entire computation by building fragments of the trace in parallel,
@@ -62,9 +82,16 @@ const MIN_FRAGMENT_LENGTH: usize = 2;
 /// [fill()](ExecutionTraceFragment::fill) method to fill all fragments with data in parallel.
 /// The semantics of the fragment's [ExecutionTraceFragment::fill()] method are identical to the
 /// semantics of the [ExecutionTrace::fill()] method.
+///
+/// If every fragment of the trace is filled by the same pair of closures, you can also use
+/// [fill_fragments()](ExecutionTrace::fill_fragments) method, which breaks the trace into the
+/// specified number of fragments and passes the index of each fragment to the closures, so that
+/// every fragment can start from its own initial state.
 pub struct ExecutionTrace<B: StarkField> {
-    trace: Vec<Vec<B>>,
+    trace: Vec<Column<B>>,
+    unpadded_length: usize,
     meta: Vec<u8>,
+    cycle_lengths: Vec<usize>,
 }
 
 impl<B: StarkField> ExecutionTrace<B> {
@@ -98,49 +125,54 @@ impl<B: StarkField> ExecutionTrace<B> {
     ///   field `B`, or is not a power of two.
     /// * Length of `meta` is greater than 65535;
     pub fn with_meta(width: usize, length: usize, meta: Vec<u8>) -> Self {
-        assert!(
-            width > 0,
-            "execution trace must consist of at least one register"
-        );
-        assert!(
-            width <= TraceInfo::MAX_TRACE_WIDTH,
-            "execution trace width cannot be greater than {}, but was {}",
-            TraceInfo::MAX_TRACE_WIDTH,
-            width
-        );
-        assert!(
-            length >= TraceInfo::MIN_TRACE_LENGTH,
-            "execution trace must be at lest {} steps long, but was {}",
-            TraceInfo::MIN_TRACE_LENGTH,
-            length
-        );
-        assert!(
-            length.is_power_of_two(),
-            "execution trace length must be a power of 2"
-        );
-        assert!(
-            log2(length) as u32 <= B::TWO_ADICITY,
-            "execution trace length cannot exceed 2^{} steps, but was 2^{}",
-            B::TWO_ADICITY,
-            log2(length)
-        );
-        assert!(
-            meta.len() <= TraceInfo::MAX_META_LENGTH,
-            "number of metadata bytes cannot be greater than {}, but was {}",
-            TraceInfo::MAX_META_LENGTH,
-            meta.len()
-        );
-
-        let registers = unsafe { (0..width).map(|_| uninit_vector(length)).collect() };
+        validate_dimensions::<B>(width, length, &meta);
+        let registers = unsafe {
+            (0..width)
+                .map(|_| Column::from(uninit_vector(length)))
+                .collect()
+        };
         ExecutionTrace {
             trace: registers,
+            unpadded_length: length,
             meta,
+            cycle_lengths: Vec::new(),
+        }
+    }
+
+    /// Creates a new execution trace of the specified width and length, and stores all registers
+    /// of the trace in memory-mapped files.
+    ///
+    /// This works just like [ExecutionTrace::new()] function, but instead of allocating memory
+    /// for the registers on the heap, creates a temporary file for every register and maps it
+    /// into memory. The files are created in the directory returned by [std::env::temp_dir()]
+    /// and are removed as soon as they are mapped. Thus, the operating system can move parts of
+    /// the trace between memory and disk as needed, which makes it possible to build traces
+    /// which are larger than the available memory.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `width` is zero or greater than 255.
+    /// * `length` is smaller than 8, greater than biggest multiplicative subgroup in the field
+    ///   `B`, or is not a power of two.
+    /// * A file for any of the registers could not be created or mapped into memory.
+    #[cfg(feature = "mmap")]
+    pub fn new_mapped(width: usize, length: usize) -> Self {
+        validate_dimensions::<B>(width, length, &[]);
+        let registers = (0..width)
+            .map(|_| Column::Mapped(MappedColumn::new(length)))
+            .collect();
+        ExecutionTrace {
+            trace: registers,
+            unpadded_length: length,
+            meta: vec![],
+            cycle_lengths: Vec::new(),
         }
     }
 
     /// Creates a new execution trace from a list of provided register traces.
     ///
-    /// The provides `registers` vector is expected to contain register traces.
+    /// The provides `registers` vector is expected to contain register traces. The registers
+    /// are moved into the execution trace without copying.
     ///
     /// # Panics
     /// Panics if:
@@ -149,45 +181,260 @@ impl<B: StarkField> ExecutionTrace<B> {
     ///   multiplicative subgroup in the field `B`, or is not a power of two.
     /// * Number of elements is not identical for all registers.
     pub fn init(registers: Vec<Vec<B>>) -> Self {
+        let trace_length = registers.first().map_or(0, |register| register.len());
+        validate_dimensions::<B>(registers.len(), trace_length, &[]);
+        for register in registers.iter() {
+            assert_eq!(
+                register.len(),
+                trace_length,
+                "all register traces must have the same length"
+            );
+        }
+
+        ExecutionTrace {
+            trace: registers.into_iter().map(Column::from).collect(),
+            unpadded_length: trace_length,
+            meta: vec![],
+            cycle_lengths: Vec::new(),
+        }
+    }
+
+    /// Creates a new execution trace from the provided columns, where each column contains
+    /// values of a single register at all steps of the trace.
+    ///
+    /// This is the same as [ExecutionTrace::init()]: the columns are moved into the execution
+    /// trace without copying, and thus, this is the most efficient way to build a trace from
+    /// register values computed elsewhere.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The `columns` vector is empty or has over 255 columns.
+    /// * Number of elements in any of the columns is smaller than 8, greater than the biggest
+    ///   multiplicative subgroup in the field `B`, or is not a power of two.
+    /// * Number of elements is not identical for all columns.
+    pub fn from_columns(columns: Vec<Vec<B>>) -> Self {
+        Self::init(columns)
+    }
+
+    /// Creates a new execution trace of the specified width from the byte representation of its
+    /// columns written via [ExecutionTrace::write_bytes_into()].
+    ///
+    /// Every value is validated as it is read (see [FromBytes](utils::FromBytes)), and thus,
+    /// `bytes` may come from an untrusted source, such as a file on disk. The values are
+    /// copied, and thus, `bytes` do not need to be aligned in memory. Metadata and cycle lengths
+    /// of the original trace are not restored, and the entire trace is treated as meaningful
+    /// (i.e., unpadded).
+    ///
+    /// # Errors
+    /// Returns an error if `bytes` do not divide into `width` columns of whole field elements,
+    /// or if any of the values is not a valid field element.
+    ///
+    /// # Panics
+    /// Panics if `width` is zero or the trace dimensions are not valid, in the same way as
+    /// [ExecutionTrace::init()] does.
+    pub fn from_bytes(bytes: &[u8], width: usize) -> Result<Self, DeserializationError> {
         assert!(
-            !registers.is_empty(),
+            width > 0,
             "execution trace must consist of at least one register"
         );
+        let column_bytes = bytes.len() / width;
+        if !bytes.len().is_multiple_of(width) || !column_bytes.is_multiple_of(B::ELEMENT_BYTES) {
+            return Err(DeserializationError::InvalidValue(format!(
+                "number of bytes ({}) does not divide into {} columns of whole field elements",
+                bytes.len(),
+                width
+            )));
+        }
+
+        let columns = bytes
+            .chunks_exact(column_bytes)
+            .map(B::read_slice_from_bytes)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self::init(columns))
+    }
+
+    /// Creates a new execution trace from the provided rows, where each row contains values of
+    /// all registers at a single step of the trace.
+    ///
+    /// Values of every row are moved into the register traces as the rows are read from the
+    /// iterator; thus, rows can be generated lazily, and no more than a single row is kept in
+    /// memory in addition to the trace itself.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `rows` is empty, or the rows are empty or have over 255 elements.
+    /// * Number of rows is smaller than 8, greater than the biggest multiplicative subgroup in
+    ///   the field `B`, or is not a power of two.
+    /// * Number of elements is not identical for all rows.
+    pub fn from_rows<I>(rows: I) -> Self
+    where
+        I: IntoIterator<Item = Vec<B>>,
+    {
+        let mut rows = rows.into_iter();
+        let first_row = rows
+            .next()
+            .expect("execution trace must contain at least one row");
+        let width = first_row.len();
+
+        let capacity = rows.size_hint().0 + 1;
+        let mut registers = (0..width)
+            .map(|_| Vec::with_capacity(capacity))
+            .collect::<Vec<_>>();
+        for (step, row) in iter::once(first_row).chain(rows).enumerate() {
+            assert_eq!(
+                row.len(),
+                width,
+                "all rows must have {} elements, but row {} had {}",
+                width,
+                step,
+                row.len()
+            );
+            for (register, value) in registers.iter_mut().zip(row) {
+                register.push(value);
+            }
+        }
+
+        Self::init(registers)
+    }
+
+    /// Creates a new execution trace from a list of provided register traces, and pads the
+    /// registers to the next power of two as specified by the `padding` policy.
+    ///
+    /// Unlike with [ExecutionTrace::init()], the length of the provided registers does not need
+    /// to be a power of two. If the registers contain fewer than 8 rows, they are padded to 8
+    /// rows.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The `registers` vector is empty or has over 255 registers.
+    /// * Number of elements in any of the registers is zero, or the padded length is greater than
+    ///   the biggest multiplicative subgroup in the field `B`.
+    /// * Number of elements is not identical for all registers.
+    pub fn init_padded(registers: Vec<Vec<B>>, padding: TracePadding) -> Self {
+        match padding {
+            TracePadding::RepeatLastRow => Self::init_padded_with(registers, |_, _| {}),
+            TracePadding::Zeros => Self::init_padded_with(registers, |_, state| {
+                state.iter_mut().for_each(|v| *v = B::ZERO)
+            }),
+        }
+    }
+
+    /// Creates a new execution trace from a list of provided register traces, and pads the
+    /// registers to the next power of two using the provided `update` closure.
+    ///
+    /// The `update` closure has the same semantics as the `update` closure of the
+    /// [fill()](ExecutionTrace::fill) method: it receives the index of the last updated row and a
+    /// mutable reference to the last updated state, which is initialized to the last meaningful
+    /// row of the trace. The contents of the state are copied into the next padding row after the
+    /// closure returns.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The `registers` vector is empty or has over 255 registers.
+    /// * Number of elements in any of the registers is zero, or the padded length is greater than
+    ///   the biggest multiplicative subgroup in the field `B`.
+    /// * Number of elements is not identical for all registers.
+    pub fn init_padded_with<U>(registers: Vec<Vec<B>>, update: U) -> Self
+    where
+        U: Fn(usize, &mut [B]),
+    {
+        let trace_length = registers
+            .first()
+            .map_or(0, |register| register.len())
+            .next_power_of_two()
+            .max(TraceInfo::MIN_TRACE_LENGTH);
+        Self::init_padded_to_with(registers, trace_length, update)
+    }
+
+    /// Creates a new execution trace from a list of provided register traces, and pads the
+    /// registers to the specified `trace_length` as specified by the `padding` policy.
+    ///
+    /// This is useful when computations of different sizes need to be proven using traces of
+    /// the same length; the number of meaningful rows is recorded in the trace info.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The `registers` vector is empty or has over 255 registers.
+    /// * Number of elements in any of the registers is zero or is greater than `trace_length`.
+    /// * Number of elements is not identical for all registers.
+    /// * `trace_length` is smaller than 8, is not a power of two, or is greater than the biggest
+    ///   multiplicative subgroup in the field `B`.
+    pub fn init_padded_to(
+        registers: Vec<Vec<B>>,
+        trace_length: usize,
+        padding: TracePadding,
+    ) -> Self {
+        match padding {
+            TracePadding::RepeatLastRow => {
+                Self::init_padded_to_with(registers, trace_length, |_, _| {})
+            }
+            TracePadding::Zeros => {
+                Self::init_padded_to_with(registers, trace_length, |_, state| {
+                    state.iter_mut().for_each(|v| *v = B::ZERO)
+                })
+            }
+        }
+    }
+
+    /// Creates a new execution trace from a list of provided register traces, and pads the
+    /// registers to the specified `trace_length` using the provided `update` closure.
+    ///
+    /// The `update` closure has the same semantics as for
+    /// [init_padded_with()](ExecutionTrace::init_padded_with) function.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The `registers` vector is empty or has over 255 registers.
+    /// * Number of elements in any of the registers is zero or is greater than `trace_length`.
+    /// * Number of elements is not identical for all registers.
+    /// * `trace_length` is smaller than 8, is not a power of two, or is greater than the biggest
+    ///   multiplicative subgroup in the field `B`.
+    pub fn init_padded_to_with<U>(
+        mut registers: Vec<Vec<B>>,
+        trace_length: usize,
+        update: U,
+    ) -> Self
+    where
+        U: Fn(usize, &mut [B]),
+    {
         assert!(
-            registers.len() <= TraceInfo::MAX_TRACE_WIDTH,
-            "execution trace width cannot be greater than {}, but was {}",
-            TraceInfo::MAX_TRACE_WIDTH,
-            registers.len()
-        );
-        let trace_length = registers[0].len();
-        assert!(
-            trace_length >= TraceInfo::MIN_TRACE_LENGTH,
-            "execution trace must be at lest {} steps long, but was {}",
-            TraceInfo::MIN_TRACE_LENGTH,
-            trace_length
+            !registers.is_empty(),
+            "execution trace must consist of at least one register"
         );
+        let unpadded_length = registers[0].len();
         assert!(
-            trace_length.is_power_of_two(),
-            "execution trace length must be a power of 2"
+            unpadded_length > 0,
+            "execution trace must contain at least one row"
         );
         assert!(
-            log2(trace_length) as u32 <= B::TWO_ADICITY,
-            "execution trace length cannot exceed 2^{} steps, but was 2^{}",
-            B::TWO_ADICITY,
-            log2(trace_length)
+            unpadded_length <= trace_length,
+            "execution trace cannot contain more than {} rows, but had {}",
+            trace_length,
+            unpadded_length
         );
         for register in registers.iter() {
             assert_eq!(
                 register.len(),
-                trace_length,
+                unpadded_length,
                 "all register traces must have the same length"
             );
         }
 
-        ExecutionTrace {
-            trace: registers,
-            meta: vec![],
+        // build padding rows by applying the update closure to the last meaningful row
+        let mut state = registers
+            .iter()
+            .map(|register| register[unpadded_length - 1])
+            .collect::<Vec<_>>();
+        for step in unpadded_length - 1..trace_length - 1 {
+            update(step, &mut state);
+            for (register, &value) in registers.iter_mut().zip(state.iter()) {
+                register.push(value);
+            }
         }
+
+        let mut trace = Self::init(registers);
+        trace.unpadded_length = unpadded_length;
+        trace
     }
 
     // DATA MUTATORS
@@ -218,6 +465,36 @@ impl<B: StarkField> ExecutionTrace<B> {
         self.meta = meta
     }
 
+    /// Declares that the specified `register` repeats a cycle of `cycle_length` values over the
+    /// entire trace (e.g., a selector register, or a constant register for which the cycle
+    /// length is 1).
+    ///
+    /// When the extended execution trace is kept in memory, the prover stores only a single
+    /// cycle of the extended values of such registers. Whether the register actually repeats the
+    /// declared cycle is checked when the trace is validated against an AIR.
+    ///
+    /// # Panics
+    /// Panics if `register` is out of bounds for this execution trace, or if `cycle_length` is
+    /// not a power of two smaller than or equal to the length of the trace.
+    pub fn set_cycle_length(&mut self, register: usize, cycle_length: usize) {
+        assert!(
+            register < self.width(),
+            "register index must be smaller than {}, but was {}",
+            self.width(),
+            register
+        );
+        assert!(
+            cycle_length.is_power_of_two() && cycle_length <= self.length(),
+            "cycle length must be a power of two smaller than or equal to {}, but was {}",
+            self.length(),
+            cycle_length
+        );
+        if self.cycle_lengths.is_empty() {
+            self.cycle_lengths = vec![self.length(); self.width()];
+        }
+        self.cycle_lengths[register] = cycle_length;
+    }
+
     /// Fill all rows in the execution trace.
     ///
     /// The rows are filled by executing the provided closures as follows:
@@ -244,6 +521,47 @@ impl<B: StarkField> ExecutionTrace<B> {
         }
     }
 
+    /// Fill all rows in the execution trace by breaking it into the specified number of equally
+    /// sized fragments and filling each fragment independently.
+    ///
+    /// The semantics of the closures are the same as for the [fill()](ExecutionTrace::fill)
+    /// method, except that both closures also receive the index of the fragment being filled as
+    /// the first parameter:
+    /// - `init` closure is used to initialize the first row of every fragment.
+    /// - `update` closure is used to populate all subsequent rows of every fragment; the index
+    ///   of the last updated row is relative to the start of the fragment.
+    ///
+    /// When the `concurrent` feature is enabled, the fragments are filled in parallel.
+    ///
+    /// # Panics
+    /// Panics if `num_fragments` is not a power of two, or if it is greater than half of the
+    /// trace length.
+    pub fn fill_fragments<I, U>(&mut self, num_fragments: usize, init: I, update: U)
+    where
+        I: Fn(usize, &mut [B]) + Send + Sync,
+        U: Fn(usize, usize, &mut [B]) + Send + Sync,
+    {
+        assert!(
+            num_fragments.is_power_of_two(),
+            "number of fragments must be a power of 2, but was {}",
+            num_fragments
+        );
+        assert!(
+            num_fragments <= self.length() / MIN_FRAGMENT_LENGTH,
+            "number of fragments cannot exceed {}, but was {}",
+            self.length() / MIN_FRAGMENT_LENGTH,
+            num_fragments
+        );
+        let fragment_length = self.length() / num_fragments;
+        self.fragments(fragment_length).for_each(|mut fragment| {
+            let index = fragment.index();
+            fragment.fill(
+                |state| init(index, state),
+                |step, state| update(index, step, state),
+            );
+        });
+    }
+
     /// Updates a single row in the execution trace with provided data.
     pub fn update_row(&mut self, step: usize, state: &[B]) {
         for (register, &value) in self.trace.iter_mut().zip(state) {
@@ -251,6 +569,78 @@ impl<B: StarkField> ExecutionTrace<B> {
         }
     }
 
+    /// Updates rows of an already filled execution trace at the specified steps.
+    ///
+    /// Steps are visited in the order in which they are returned by `steps`; thus, rows can be
+    /// updated in reverse order by passing in a reversed range, e.g. `(0..n).rev()`. For every
+    /// step, the `update` closure receives the index of the step and a mutable reference to the
+    /// row at this step; the contents of the row are copied back into the trace after the closure
+    /// returns.
+    ///
+    /// This can be used to execute post-processing passes over the trace (e.g., to annotate
+    /// rows with values which depend on later rows) without updating the trace one cell at a
+    /// time.
+    ///
+    /// # Panics
+    /// Panics if any of the steps is out of bounds for this execution trace.
+    pub fn update_rows<I, U>(&mut self, steps: I, mut update: U)
+    where
+        I: IntoIterator<Item = usize>,
+        U: FnMut(usize, &mut [B]),
+    {
+        let mut row = vec![B::ZERO; self.width()];
+        for step in steps {
+            self.read_row_into(step, &mut row);
+            update(step, &mut row);
+            self.update_row(step, &row);
+        }
+    }
+
+    /// Updates windows of consecutive rows of an already filled execution trace starting at the
+    /// specified steps.
+    ///
+    /// This works like [update_rows()](ExecutionTrace::update_rows) method, but for every step,
+    /// the `update` closure receives a mutable reference to a window of `window_length` rows
+    /// starting at this step; the first row of the window is the row at the step itself. All
+    /// rows of the window are copied back into the trace after the closure returns, and thus,
+    /// updates made to a row are visible to the windows visited afterwards.
+    ///
+    /// For example, a backward pass in which every row is updated based on the following row can
+    /// be executed as follows:
+    ///
+    /// ```ignore
+    /// trace.update_windows((0..trace.length() - 1).rev(), 2, |_, window| {
+    ///     window[0][1] = window[1][1] + window[0][0];
+    /// });
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if `window_length` is zero, or if any of the windows extends beyond the end of
+    /// this execution trace.
+    pub fn update_windows<I, U>(&mut self, steps: I, window_length: usize, mut update: U)
+    where
+        I: IntoIterator<Item = usize>,
+        U: FnMut(usize, &mut [Vec<B>]),
+    {
+        assert!(window_length > 0, "window length must be greater than 0");
+        let mut window = vec![vec![B::ZERO; self.width()]; window_length];
+        for step in steps {
+            assert!(
+                step + window_length <= self.length(),
+                "window of {} rows starting at step {} extends beyond the end of the trace",
+                window_length,
+                step
+            );
+            for (i, row) in window.iter_mut().enumerate() {
+                self.read_row_into(step + i, row);
+            }
+            update(step, &mut window);
+            for (i, row) in window.iter().enumerate() {
+                self.update_row(step + i, row);
+            }
+        }
+    }
+
     // FRAGMENTS
     // --------------------------------------------------------------------------------------------
 
@@ -266,7 +656,7 @@ impl<B: StarkField> ExecutionTrace<B> {
     pub fn fragments(
         &mut self,
         fragment_length: usize,
-    ) -> vec::IntoIter<ExecutionTraceFragment<B>> {
+    ) -> vec::IntoIter<ExecutionTraceFragment<'_, B>> {
         self.build_fragments(fragment_length).into_iter()
     }
 
@@ -282,13 +672,13 @@ impl<B: StarkField> ExecutionTrace<B> {
     pub fn fragments(
         &mut self,
         fragment_length: usize,
-    ) -> rayon::vec::IntoIter<ExecutionTraceFragment<B>> {
+    ) -> rayon::vec::IntoIter<ExecutionTraceFragment<'_, B>> {
         self.build_fragments(fragment_length).into_par_iter()
     }
 
     /// Returns a vector of trace fragments each covering the number of steps specified by the
     /// `fragment_length` parameter.
-    fn build_fragments(&mut self, fragment_length: usize) -> Vec<ExecutionTraceFragment<B>> {
+    fn build_fragments(&mut self, fragment_length: usize) -> Vec<ExecutionTraceFragment<'_, B>> {
         assert!(
             fragment_length >= MIN_FRAGMENT_LENGTH,
             "fragment length must be at least {}, but was {}",
@@ -305,14 +695,12 @@ impl<B: StarkField> ExecutionTrace<B> {
             fragment_length.is_power_of_two(),
             "fragment length must be a power of 2"
         );
-        let num_fragments = self.length() / fragment_length;
-
-        let mut fragment_data = (0..num_fragments).map(|_| Vec::new()).collect::<Vec<_>>();
-        self.trace.iter_mut().for_each(|column| {
-            for (i, fragment) in column.chunks_mut(fragment_length).enumerate() {
-                fragment_data[i].push(fragment);
-            }
-        });
+        let length = self.length();
+        let fragment_data = fragment_columns(
+            self.trace.iter_mut().map(|column| &mut column[..]),
+            length,
+            fragment_length,
+        );
 
         fragment_data
             .into_iter()
@@ -331,6 +719,7 @@ impl<B: StarkField> ExecutionTrace<B> {
     /// Returns trace info for this execution trace.
     pub fn get_info(&self) -> TraceInfo {
         TraceInfo::with_meta(self.width(), self.length(), self.meta.clone())
+            .with_unpadded_length(self.unpadded_length)
     }
 
     /// Returns number of registers in the trace table.
@@ -343,6 +732,12 @@ impl<B: StarkField> ExecutionTrace<B> {
         self.trace[0].len()
     }
 
+    /// Returns the number of meaningful states in this trace table, not counting the states
+    /// which were added as padding.
+    pub fn unpadded_length(&self) -> usize {
+        self.unpadded_length
+    }
+
     /// Returns value of the cell the specified `register` at the specified `step`.
     pub fn get(&self, register: usize, step: usize) -> B {
         self.trace[register][step]
@@ -365,106 +760,64 @@ impl<B: StarkField> ExecutionTrace<B> {
         &self.meta
     }
 
-    // VALIDATION
+    // BYTE CONVERSIONS
     // --------------------------------------------------------------------------------------------
 
-    /// Checks if this execution trace is valid against the specified AIR, and panics if not.
+    /// Writes values of this trace into the specified `target` one register after another.
     ///
-    /// NOTE: this is a very expensive operation and is intended for use only in debug mode.
-    pub fn validate<A: Air<BaseElement = B>>(&self, air: &A) {
-        // TODO: eventually, this should return errors instead of panicking
+    /// Values are written in their internal representation via
+    /// [FieldElement::elements_as_bytes()](math::FieldElement::elements_as_bytes), i.e., by
+    /// copying the memory of every register as is. This is much faster than serializing the
+    /// values, but the bytes are in the native byte order of the target platform; thus, this
+    /// is intended for persisting traces to disk temporarily (e.g., for traces stored in
+    /// memory-mapped files, or for checkpointing proof generation), rather than for exchanging
+    /// traces between machines. The trace can be restored via [ExecutionTrace::from_bytes()].
+    pub fn write_bytes_into<W: ByteWriter>(&self, target: &mut W) {
+        for register in self.trace.iter() {
+            target.write_u8_slice(B::elements_as_bytes(register));
+        }
+    }
+}
 
-        // make sure the width align; if they don't something went terribly wrong
-        assert_eq!(
-            self.width(),
-            air.trace_width(),
-            "inconsistent trace width: expected {}, but was {}",
-            self.width(),
-            air.trace_width()
-        );
+// TRACE TRAIT IMPLEMENTATION
+// ================================================================================================
 
-        // --- 1. make sure the assertions are valid ----------------------------------------------
-        for assertion in air.get_assertions() {
-            assertion.apply(self.length(), |step, value| {
-                assert!(
-                    value == self.get(assertion.register(), step),
-                    "trace does not satisfy assertion trace({}, {}) == {}",
-                    assertion.register(),
-                    step,
-                    value
-                );
-            });
-        }
+impl<B: StarkField> Trace for ExecutionTrace<B> {
+    type BaseField = B;
 
-        // --- 2. make sure this trace satisfies all transition constraints -----------------------
+    fn width(&self) -> usize {
+        self.trace.len()
+    }
 
-        // collect the info needed to build periodic values for a specific step
-        let g = air.trace_domain_generator();
-        let periodic_values_polys = air.get_periodic_column_polys();
-        let mut periodic_values = vec![B::ZERO; periodic_values_polys.len()];
+    fn length(&self) -> usize {
+        self.trace[0].len()
+    }
 
-        // initialize buffers to hold evaluation frames and results of constraint evaluations
-        let mut x = B::ONE;
-        let mut ev_frame = EvaluationFrame::new(self.width());
-        let mut evaluations = vec![B::ZERO; air.num_transition_constraints()];
+    fn unpadded_length(&self) -> usize {
+        self.unpadded_length
+    }
 
-        for step in 0..self.length() - 1 {
-            // build periodic values
-            for (p, v) in periodic_values_polys.iter().zip(periodic_values.iter_mut()) {
-                let num_cycles = air.trace_length() / p.len();
-                let x = x.exp((num_cycles as u32).into());
-                *v = polynom::eval(p, x);
-            }
+    fn meta(&self) -> &[u8] {
+        &self.meta
+    }
 
-            // build evaluation frame
-            self.read_row_into(step, ev_frame.current_mut());
-            self.read_row_into(step + 1, ev_frame.next_mut());
-
-            // evaluate transition constraints
-            air.evaluate_transition(&ev_frame, &periodic_values, &mut evaluations);
-
-            // make sure all constraints evaluated to ZERO
-            for (i, &evaluation) in evaluations.iter().enumerate() {
-                assert!(
-                    evaluation == B::ZERO,
-                    "transition constraint {} did not evaluate to ZERO at step {}",
-                    i,
-                    step
-                );
-            }
+    fn read_row_into(&self, step: usize, target: &mut [B]) {
+        ExecutionTrace::read_row_into(self, step, target)
+    }
 
-            // update x coordinate of the domain
-            x *= g;
+    fn cycle_length(&self, register: usize) -> usize {
+        match self.cycle_lengths.get(register) {
+            Some(&cycle_length) => cycle_length,
+            None => self.length(),
         }
     }
 
-    // LOW-DEGREE EXTENSION
-    // --------------------------------------------------------------------------------------------
-    /// Extends all registers of the trace table to the length of the LDE domain.
-    ///
-    /// The extension is done by first interpolating each register into a polynomial over the
-    /// trace domain, and then evaluating the polynomial over the LDE domain.
-    pub fn extend(mut self, domain: &StarkDomain<B>) -> (TraceTable<B>, TracePolyTable<B>) {
-        assert_eq!(
-            self.length(),
-            domain.trace_length(),
-            "inconsistent trace length"
-        );
-        // build and cache trace twiddles for FFT interpolation; we do it here so that we
-        // don't have to rebuild these twiddles for every register.
-        let inv_twiddles = fft::get_inv_twiddles::<B>(domain.trace_length());
-
-        // extend all registers; the extension procedure first interpolates register traces into
-        // polynomials (in-place), then evaluates these polynomials over a larger domain, and
-        // then returns extended evaluations.
-        let extended_trace = iter_mut!(self.trace)
-            .map(|register_trace| extend_register(register_trace, domain, &inv_twiddles))
-            .collect();
+    fn get_column(&self, register: usize) -> Vec<B> {
+        self.trace[register].to_vec()
+    }
 
-        (
-            TraceTable::new(extended_trace, domain.trace_to_lde_blowup()),
-            TracePolyTable::new(self.trace),
-        )
+    fn into_columns(self) -> Vec<Vec<B>> {
+        self.trace.into_iter().map(Column::into_vec).collect()
     }
 }
 
@@ -551,20 +904,39 @@ impl<'a, B: StarkField> ExecutionTraceFragment<'a, B> {
 // HELPER FUNCTIONS
 // ================================================================================================
 
-#[inline(always)]
-fn extend_register<B: StarkField>(
-    trace: &mut [B],
-    domain: &StarkDomain<B>,
-    inv_twiddles: &[B],
-) -> Vec<B> {
-    let domain_offset = domain.offset();
-    let twiddles = domain.trace_twiddles();
-    let blowup_factor = domain.trace_to_lde_blowup();
-
-    // interpolate register trace into a polynomial; we do this over the un-shifted trace_domain
-    fft::interpolate_poly(trace, inv_twiddles);
-
-    // evaluate the polynomial over extended domain; the domain may be shifted by the
-    // domain_offset
-    fft::evaluate_poly_with_offset(trace, twiddles, domain_offset, blowup_factor)
+/// Makes sure that an execution trace of the specified dimensions and with the specified
+/// metadata can be used to generate a proof.
+fn validate_dimensions<B: StarkField>(width: usize, length: usize, meta: &[u8]) {
+    assert!(
+        width > 0,
+        "execution trace must consist of at least one register"
+    );
+    assert!(
+        width <= TraceInfo::MAX_TRACE_WIDTH,
+        "execution trace width cannot be greater than {}, but was {}",
+        TraceInfo::MAX_TRACE_WIDTH,
+        width
+    );
+    assert!(
+        length >= TraceInfo::MIN_TRACE_LENGTH,
+        "execution trace must be at lest {} steps long, but was {}",
+        TraceInfo::MIN_TRACE_LENGTH,
+        length
+    );
+    assert!(
+        length.is_power_of_two(),
+        "execution trace length must be a power of 2"
+    );
+    assert!(
+        log2(length) as u32 <= B::TWO_ADICITY,
+        "execution trace length cannot exceed 2^{} steps, but was 2^{}",
+        B::TWO_ADICITY,
+        log2(length)
+    );
+    assert!(
+        meta.len() <= TraceInfo::MAX_META_LENGTH,
+        "number of metadata bytes cannot be greater than {}, but was {}",
+        TraceInfo::MAX_META_LENGTH,
+        meta.len()
+    );
 }
@@ -3,7 +3,20 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::StarkDomain;
+use super::{BufferPool, ProvingMode, StarkDomain, TraceValidationError};
+use air::{proof::Queries, Air, CommittedColumn, EvaluationFrame, TraceInfo};
+use core::ops::Range;
+use crypto::{ElementHasher, Hasher, MerkleTree};
+use math::{fft, polynom, FieldElement, StarkField};
+use utils::{collections::Vec, iter_mut, uninit_buffer};
+
+#[cfg(feature = "concurrent")]
+use utils::iterators::*;
+
+mod column;
+pub use column::Column;
+#[cfg(feature = "mmap")]
+pub use column::MappedColumn;
 
 mod trace_table;
 pub use trace_table::TraceTable;
@@ -11,8 +24,497 @@ pub use trace_table::TraceTable;
 mod poly_table;
 pub use poly_table::TracePolyTable;
 
+mod chunked_table;
+pub use chunked_table::{hash_rows, ChunkedTraceTable};
+
+mod batch_trace;
+pub use batch_trace::BatchTrace;
+
+mod committed;
+pub use committed::CommittedRegisters;
+
 mod execution_trace;
 pub use execution_trace::{ExecutionTrace, ExecutionTraceFragment};
 
 #[cfg(test)]
 mod tests;
+
+// TRACE TRAIT
+// ================================================================================================
+/// Defines an execution trace of a computation.
+///
+/// The prover accesses an execution trace only via this trait. Thus, in addition to the
+/// [ExecutionTrace] struct, which keeps the entire trace in memory, a computation can supply
+/// its own trace type - e.g., a trace which generates its registers lazily, or keeps them in a
+/// compressed form.
+///
+/// During proof generation, the prover consumes the trace via [into_columns()](Trace::into_columns)
+/// method, which by default requests values of every register via
+/// [get_column()](Trace::get_column) method, and then extends each register into the low-degree
+/// extension domain. Rows of the trace are read via [read_row_into()](Trace::read_row_into)
+/// method only when the trace is validated against an AIR in debug mode.
+pub trait Trace: Sized + Sync {
+    /// Base field for this execution trace.
+    type BaseField: StarkField;
+
+    // REQUIRED METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns number of registers in this trace.
+    fn width(&self) -> usize;
+
+    /// Returns the number of steps in this trace; this must be a power of two.
+    fn length(&self) -> usize;
+
+    /// Returns metadata associated with this trace.
+    fn meta(&self) -> &[u8];
+
+    /// Reads a single row of this trace at the specified `step` into the specified `target`.
+    fn read_row_into(&self, step: usize, target: &mut [Self::BaseField]);
+
+    /// Returns values of the specified `register` at all steps of this trace.
+    fn get_column(&self, register: usize) -> Vec<Self::BaseField>;
+
+    // PROVIDED METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of meaningful steps in this trace, not counting the steps which were
+    /// added as padding.
+    ///
+    /// The default implementation assumes that the trace is not padded.
+    fn unpadded_length(&self) -> usize {
+        self.length()
+    }
+
+    /// Returns the length of the cycle of values which the specified `register` repeats over
+    /// the entire trace; e.g., a selector register which repeats the values `[1, 0, 0, 0]` has
+    /// cycle length 4, and a constant register has cycle length 1. The cycle length must be a
+    /// power of two.
+    ///
+    /// Low-degree extensions of registers which repeat short cycles repeat short cycles as well;
+    /// thus, when the extended trace is kept in memory, the prover stores only a single cycle of
+    /// the extended values of such registers rather than the full extended register.
+    ///
+    /// The default implementation returns the length of the trace (i.e., the register does not
+    /// repeat a shorter cycle).
+    fn cycle_length(&self, _register: usize) -> usize {
+        self.length()
+    }
+
+    /// Returns trace info for this trace.
+    fn get_info(&self) -> TraceInfo {
+        TraceInfo::with_meta(self.width(), self.length(), self.meta().to_vec())
+            .with_unpadded_length(self.unpadded_length())
+    }
+
+    /// Consumes this trace and returns values of all its registers.
+    ///
+    /// The default implementation requests the values of every register via
+    /// [get_column()](Trace::get_column) method; traces which keep their registers in memory
+    /// should override this method to avoid copying register values.
+    fn into_columns(self) -> Vec<Vec<Self::BaseField>> {
+        (0..self.width()).map(|i| self.get_column(i)).collect()
+    }
+
+    /// Checks if this trace is valid against the specified AIR, and panics if not.
+    ///
+    /// NOTE: this is a very expensive operation and is intended for use only in debug mode. To
+    /// validate a trace in release mode and get a description of the failure, use
+    /// [validate()](crate::validate) function.
+    fn validate<A: Air<BaseElement = Self::BaseField>>(&self, air: &A) {
+        if let Err(err) = validate_trace(self, air) {
+            panic!("{}", err);
+        }
+    }
+}
+
+// TRACE VALIDATION
+// ================================================================================================
+/// Checks if the specified trace is valid against the specified AIR.
+///
+/// This checks that the trace has the width expected by the AIR, that all assertions hold, that
+/// all transition constraints evaluate to zero at all steps (except for the last one), that
+/// registers repeat the cycles declared by the trace, and that registers tied to committed public
+/// columns match the column commitments. The first failed check is returned as an error.
+pub fn validate_trace<A, T>(trace: &T, air: &A) -> Result<(), TraceValidationError<A::BaseElement>>
+where
+    A: Air,
+    T: Trace<BaseField = A::BaseElement>,
+{
+    // make sure the width align; if they don't something went terribly wrong
+    if trace.width() != air.trace_width() {
+        return Err(TraceValidationError::MismatchedTraceWidth(
+            air.trace_width(),
+            trace.width(),
+        ));
+    }
+
+    // --- 1. make sure the assertions are valid --------------------------------------------------
+    let mut row = vec![A::BaseElement::ZERO; trace.width()];
+    for assertion in air.get_assertions() {
+        let mut result = Ok(());
+        assertion.apply(trace.length(), |step, value| {
+            if result.is_ok() {
+                trace.read_row_into(step, &mut row);
+                if value != row[assertion.register()] {
+                    result = Err(TraceValidationError::UnsatisfiedAssertion {
+                        register: assertion.register(),
+                        step,
+                        expected: value,
+                        actual: row[assertion.register()],
+                    });
+                }
+            }
+        });
+        result?;
+    }
+
+    // --- 2. make sure this trace satisfies all transition constraints ---------------------------
+
+    // collect the info needed to build periodic values for a specific step
+    let g = air.trace_domain_generator();
+    let periodic_values_polys = air.get_periodic_column_polys();
+    let mut periodic_values = vec![A::BaseElement::ZERO; periodic_values_polys.len()];
+
+    // initialize buffers to hold evaluation frames and results of constraint evaluations
+    let mut x = A::BaseElement::ONE;
+    let mut ev_frame = EvaluationFrame::new(trace.width());
+    let mut evaluations = vec![A::BaseElement::ZERO; air.num_transition_constraints()];
+
+    for step in 0..trace.length() - 1 {
+        // build periodic values
+        for (p, v) in periodic_values_polys.iter().zip(periodic_values.iter_mut()) {
+            let num_cycles = air.trace_length() / p.len();
+            let x = x.exp((num_cycles as u32).into());
+            *v = polynom::eval(p, x);
+        }
+
+        // build evaluation frame
+        trace.read_row_into(step, ev_frame.current_mut());
+        trace.read_row_into(step + 1, ev_frame.next_mut());
+
+        // evaluate transition constraints
+        air.evaluate_transition(&ev_frame, &periodic_values, &mut evaluations);
+
+        // make sure all constraints evaluated to ZERO
+        for (i, &evaluation) in evaluations.iter().enumerate() {
+            if evaluation != A::BaseElement::ZERO {
+                return Err(TraceValidationError::UnsatisfiedTransitionConstraint {
+                    constraint: i,
+                    step,
+                    value: evaluation,
+                });
+            }
+        }
+
+        // update x coordinate of the domain
+        x *= g;
+    }
+
+    // --- 3. make sure registers repeat the cycles declared by the trace -------------------------
+    for register in 0..trace.width() {
+        let cycle_length = trace.cycle_length(register);
+        if cycle_length < trace.length() {
+            let values = trace.get_column(register);
+            if !cycle_length.is_power_of_two()
+                || values
+                    .iter()
+                    .enumerate()
+                    .any(|(step, &value)| value != values[step % cycle_length])
+            {
+                return Err(TraceValidationError::MismatchedRegisterCycle(
+                    register,
+                    cycle_length,
+                ));
+            }
+        }
+    }
+
+    // --- 4. make sure registers tied to committed columns match the commitments -----------------
+    for column in air.get_committed_columns() {
+        let values = trace.get_column(column.register());
+        if CommittedColumn::build_commitment(&values, air.options()) != column.commitment() {
+            return Err(TraceValidationError::MismatchedColumnCommitment(
+                column.register(),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+// TRACE SOURCE
+// ================================================================================================
+/// Defines a source from which the prover obtains trace polynomials.
+///
+/// Execution traces are interpolated into trace polynomials; trace polynomials supplied to the
+/// prover directly are used as is.
+pub trait TraceSource<B: StarkField> {
+    /// Consumes this source and returns trace polynomials over the specified `domain`.
+    fn into_polys(self, domain: &StarkDomain<B>, mode: ProvingMode) -> TracePolyTable<B>;
+}
+
+impl<T: Trace> TraceSource<T::BaseField> for T {
+    fn into_polys(
+        self,
+        domain: &StarkDomain<T::BaseField>,
+        mode: ProvingMode,
+    ) -> TracePolyTable<T::BaseField> {
+        interpolate_trace(self, domain, mode)
+    }
+}
+
+impl<B: StarkField> TraceSource<B> for TracePolyTable<B> {
+    fn into_polys(self, domain: &StarkDomain<B>, _mode: ProvingMode) -> TracePolyTable<B> {
+        assert_eq!(
+            self.poly_size(),
+            domain.trace_length(),
+            "inconsistent trace length"
+        );
+        self
+    }
+}
+
+// TRACE INTERPOLATION
+// ================================================================================================
+/// Interpolates all registers of the specified trace into polynomials over the trace domain.
+///
+/// Each of the resulting trace polynomials has degree `trace_length - 1`. The trace is consumed
+/// and memory holding its registers is reused to hold the polynomials, unless the polynomials
+/// are to be stored in memory-mapped files as specified by the proving `mode`.
+pub fn interpolate_trace<T: Trace>(
+    trace: T,
+    domain: &StarkDomain<T::BaseField>,
+    mode: ProvingMode,
+) -> TracePolyTable<T::BaseField> {
+    assert_eq!(
+        trace.length(),
+        domain.trace_length(),
+        "inconsistent trace length"
+    );
+    // inverse twiddles for FFT interpolation are cached in the domain so that we don't have
+    // to rebuild them for every register
+    let inv_twiddles = domain.trace_inv_twiddles();
+    let cycle_lengths = (0..trace.width()).map(|i| trace.cycle_length(i)).collect();
+
+    let columns = match mode {
+        // copy registers into memory-mapped columns one at a time, so that at most one register
+        // is kept on the heap, and then interpolate them in place
+        #[cfg(feature = "mmap")]
+        ProvingMode::MemoryMapped => (0..trace.width())
+            .map(|i| {
+                let mut column = Column::Mapped(MappedColumn::new(trace.length()));
+                column.copy_from_slice(&trace.get_column(i));
+                fft::interpolate_poly(&mut column, inv_twiddles);
+                column
+            })
+            .collect(),
+        // interpolate register traces into polynomials in place
+        _ => {
+            let mut columns = trace.into_columns();
+            iter_mut!(columns)
+                .for_each(|register_trace| fft::interpolate_poly(register_trace, inv_twiddles));
+            columns.into_iter().map(Column::from).collect()
+        }
+    };
+
+    TracePolyTable::new(columns).with_cycle_lengths(cycle_lengths)
+}
+
+// LOW-DEGREE EXTENSION
+// ================================================================================================
+/// Extends all trace polynomials to the length of the LDE domain by evaluating them over the
+/// LDE domain.
+///
+/// The entire extended trace is kept in memory, except for registers which repeat short cycles;
+/// for these, only a single cycle of extended values is kept. If `buffer_pool` is provided, the
+/// extended registers are written into buffers taken from the pool.
+pub fn extend_trace<B: StarkField>(
+    trace_polys: &TracePolyTable<B>,
+    domain: &StarkDomain<B>,
+    buffer_pool: Option<&mut (dyn BufferPool<B> + '_)>,
+) -> TraceTable<B> {
+    let blowup = domain.trace_to_lde_blowup();
+    let column_lengths = (0..trace_polys.num_polys()).map(|i| trace_polys.cycle_length(i) * blowup);
+
+    // buffers are allocated (or taken from the pool) before the evaluation starts, as the pool
+    // cannot be accessed from multiple threads
+    let buffers = match buffer_pool {
+        Some(pool) => column_lengths.map(|len| pool.take(len)).collect::<Vec<_>>(),
+        None => column_lengths.map(uninit_buffer).collect(),
+    };
+    let extended_trace = trace_polys.evaluate_with_offset_into(
+        domain.trace_twiddles(),
+        domain.offset(),
+        blowup,
+        buffers,
+    );
+    TraceTable::new(
+        extended_trace.into_iter().map(Column::from).collect(),
+        blowup,
+    )
+}
+
+/// Extends all trace polynomials to the length of the LDE domain, and stores the extended
+/// registers in memory-mapped files.
+///
+/// Every register is extended directly in its memory-mapped column, and thus, no more than
+/// half of a single extended register is allocated on the heap.
+#[cfg(feature = "mmap")]
+pub fn extend_trace_mapped<B: StarkField>(
+    trace_polys: &TracePolyTable<B>,
+    domain: &StarkDomain<B>,
+) -> TraceTable<B> {
+    let lde_domain_size = domain.lde_domain_size();
+    let twiddles = fft::get_twiddles::<B>(lde_domain_size);
+
+    let extended_trace = trace_polys
+        .iter()
+        .enumerate()
+        .map(|(i, poly)| {
+            // a single cycle of a register which repeats short cycles is kept on the heap
+            if trace_polys.cycle_length(i) < poly.len() {
+                let blowup = domain.trace_to_lde_blowup();
+                return Column::from(trace_polys.evaluate_cycle(i, domain.offset(), blowup));
+            }
+
+            // compute coefficients of p(s * x), where s is the domain offset; evaluating this
+            // polynomial over the LDE domain is the same as evaluating p(x) over the shifted
+            // LDE domain
+            let mut column = Column::Mapped(MappedColumn::new(lde_domain_size));
+            let mut offset = B::ONE;
+            for (value, &coefficient) in column.iter_mut().zip(poly.iter()) {
+                *value = coefficient * offset;
+                offset *= domain.offset();
+            }
+            column[poly.len()..].fill(B::ZERO);
+
+            fft::evaluate_poly(&mut column, &twiddles);
+            column
+        })
+        .collect();
+
+    TraceTable::new(extended_trace, domain.trace_to_lde_blowup())
+}
+
+/// A low-degree extension of an execution trace.
+///
+/// Depending on the [ProvingMode], the extended trace is either kept in memory (or in
+/// memory-mapped files) in its entirety, or is computed from trace polynomials one coset of the
+/// LDE domain at a time. Once the extended trace is discarded, its rows are computed from trace
+/// polynomials one row at a time.
+pub enum TraceLde<'a, B: StarkField> {
+    Table(TraceTable<B>),
+    Chunked(ChunkedTraceTable<'a, B>),
+    Discarded(ChunkedTraceTable<'a, B>),
+}
+
+impl<'a, B: StarkField> TraceLde<'a, B> {
+    /// Returns a low-degree extension of the specified trace polynomials built according to
+    /// the specified proving `mode`.
+    ///
+    /// If `buffer_pool` is provided, an extended trace kept in memory is written into buffers
+    /// taken from the pool; these buffers can be returned to the pool via [TraceLde::release()].
+    pub fn new(
+        trace_polys: &'a TracePolyTable<B>,
+        domain: &'a StarkDomain<B>,
+        mode: ProvingMode,
+        mut buffer_pool: Option<&mut (dyn BufferPool<B> + '_)>,
+    ) -> Self {
+        match mode {
+            ProvingMode::InMemory | ProvingMode::Transient => {
+                TraceLde::Table(extend_trace(trace_polys, domain, buffer_pool))
+            }
+            ProvingMode::RowMajor => TraceLde::Table(
+                extend_trace(trace_polys, domain, buffer_pool.as_deref_mut())
+                    .into_row_major(buffer_pool),
+            ),
+            ProvingMode::Chunked => TraceLde::Chunked(ChunkedTraceTable::new(trace_polys, domain)),
+            #[cfg(feature = "mmap")]
+            ProvingMode::MemoryMapped => TraceLde::Table(extend_trace_mapped(trace_polys, domain)),
+        }
+    }
+
+    /// Discards the extended trace; after this, rows of the extended trace are computed by
+    /// evaluating the specified `trace_polys` at individual points of the LDE domain.
+    ///
+    /// This should be used only when the extended trace is needed solely to answer queries.
+    /// Buffers of the discarded trace are returned to the `buffer_pool`, if one is provided.
+    pub fn discard(
+        self,
+        trace_polys: &'a TracePolyTable<B>,
+        domain: &'a StarkDomain<B>,
+        buffer_pool: Option<&mut (dyn BufferPool<B> + '_)>,
+    ) -> Self {
+        self.release(buffer_pool);
+        TraceLde::Discarded(ChunkedTraceTable::new(trace_polys, domain))
+    }
+
+    /// Destroys this extended trace; if `buffer_pool` is provided, buffers of an extended trace
+    /// kept on the heap are returned to the pool.
+    pub fn release(self, buffer_pool: Option<&mut (dyn BufferPool<B> + '_)>) {
+        if let (TraceLde::Table(table), Some(pool)) = (self, buffer_pool) {
+            table
+                .into_buffers()
+                .into_iter()
+                .for_each(|buffer| pool.release(buffer));
+        }
+    }
+
+    /// Returns number of registers in the extended trace.
+    #[allow(dead_code)]
+    pub fn width(&self) -> usize {
+        match self {
+            TraceLde::Table(table) => table.width(),
+            TraceLde::Chunked(table) | TraceLde::Discarded(table) => table.width(),
+        }
+    }
+
+    /// Returns the number of states in the extended trace.
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        match self {
+            TraceLde::Table(table) => table.len(),
+            TraceLde::Chunked(table) | TraceLde::Discarded(table) => table.len(),
+        }
+    }
+
+    /// Returns blowup factor which was used to extend original trace into this trace.
+    #[allow(dead_code)]
+    pub fn blowup(&self) -> usize {
+        match self {
+            TraceLde::Table(table) => table.blowup(),
+            TraceLde::Chunked(table) | TraceLde::Discarded(table) => table.blowup(),
+        }
+    }
+
+    /// Builds a Merkle tree out of the rows of the extended trace for each of the specified
+    /// trace `segments`.
+    pub fn build_commitment<H: ElementHasher<BaseField = B>>(
+        &self,
+        segments: &[Range<usize>],
+    ) -> Vec<MerkleTree<H>> {
+        match self {
+            TraceLde::Table(table) => table.build_commitment(segments),
+            TraceLde::Chunked(table) | TraceLde::Discarded(table) => {
+                table.build_commitment(segments)
+            }
+        }
+    }
+
+    /// Returns rows of the extended trace at the specified positions split into the specified
+    /// `segments`, along with Merkle authentication paths from the roots of the segment
+    /// `commitments` to these rows.
+    pub fn query<H: Hasher>(
+        &self,
+        segments: &[Range<usize>],
+        commitments: Vec<MerkleTree<H>>,
+        positions: &[usize],
+    ) -> Vec<Queries> {
+        match self {
+            TraceLde::Table(table) => table.query(segments, commitments, positions),
+            TraceLde::Chunked(table) => table.query(segments, commitments, positions),
+            TraceLde::Discarded(table) => table.query_points(segments, commitments, positions),
+        }
+    }
+}
@@ -3,20 +3,25 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use super::Column;
 use air::EvaluationFrame;
-use math::{log2, polynom, FieldElement, StarkField};
-use utils::{collections::Vec, iter};
+use core::mem::MaybeUninit;
+use math::{fft, log2, polynom, FieldElement, StarkField};
+use utils::{assume_init_buffer, assume_init_slice, collections::Vec, iter, iter_mut};
 
 #[cfg(feature = "concurrent")]
 use utils::iterators::*;
 
 // POLYNOMIAL TABLE
 // ================================================================================================
-pub struct TracePolyTable<B: StarkField>(Vec<Vec<B>>);
+pub struct TracePolyTable<B: StarkField> {
+    polys: Vec<Column<B>>,
+    cycle_lengths: Vec<usize>,
+}
 
 impl<B: StarkField> TracePolyTable<B> {
     /// Creates a new table of trace polynomials from the provided vectors.
-    pub fn new(polys: Vec<Vec<B>>) -> Self {
+    pub fn new(polys: Vec<Column<B>>) -> Self {
         assert!(
             !polys.is_empty(),
             "trace polynomial table must contain at least one polynomial"
@@ -34,17 +39,144 @@ impl<B: StarkField> TracePolyTable<B> {
             );
         }
 
-        TracePolyTable(polys)
+        let cycle_lengths = vec![poly_size; polys.len()];
+        TracePolyTable {
+            polys,
+            cycle_lengths,
+        }
+    }
+
+    /// Records the lengths of value cycles repeated by the registers described by the
+    /// polynomials in this table; a register which does not repeat a shorter cycle has cycle
+    /// length equal to the trace length.
+    ///
+    /// A polynomial of a register which repeats a cycle of length `c` has non-zero coefficients
+    /// only at indexes which are multiples of `poly_size() / c`. The cycle length is recorded
+    /// only if the polynomial has this form; otherwise, the full trace length is recorded.
+    ///
+    /// # Panics
+    /// Panics if the number of `cycle_lengths` is different from the number of polynomials, or
+    /// if any of the cycle lengths is not a power of two smaller than or equal to `poly_size()`.
+    pub fn with_cycle_lengths(mut self, cycle_lengths: Vec<usize>) -> Self {
+        assert_eq!(
+            self.num_polys(),
+            cycle_lengths.len(),
+            "inconsistent number of cycle lengths"
+        );
+        let poly_size = self.poly_size();
+        for (poly, (&cycle_length, result)) in self
+            .polys
+            .iter()
+            .zip(cycle_lengths.iter().zip(self.cycle_lengths.iter_mut()))
+        {
+            assert!(
+                cycle_length.is_power_of_two() && cycle_length <= poly_size,
+                "cycle length must be a power of two smaller than or equal to {}, but was {}",
+                poly_size,
+                cycle_length
+            );
+            let stride = poly_size / cycle_length;
+            let is_cyclic = poly
+                .iter()
+                .enumerate()
+                .all(|(i, &coefficient)| i % stride == 0 || coefficient == B::ZERO);
+            *result = if is_cyclic { cycle_length } else { poly_size };
+        }
+        self
     }
 
     /// Returns the size of each polynomial - i.e. size of a vector needed to hold a polynomial.
     pub fn poly_size(&self) -> usize {
-        self.0[0].len()
+        self.polys[0].len()
+    }
+
+    /// Returns the length of the cycle of values repeated by the register described by the
+    /// polynomial at the specified index; this is equal to `poly_size()` for registers which do
+    /// not repeat a shorter cycle.
+    pub fn cycle_length(&self, idx: usize) -> usize {
+        self.cycle_lengths[idx]
     }
 
     /// Evaluates all trace polynomials the the specified point `x`.
     pub fn evaluate_at<E: FieldElement<BaseField = B>>(&self, x: E) -> Vec<E> {
-        iter!(self.0).map(|p| polynom::eval(p, x)).collect()
+        iter!(self.polys).map(|p| polynom::eval(p, x)).collect()
+    }
+
+    /// Evaluates all trace polynomials over a domain of size `poly_size() * blowup_factor`
+    /// shifted by the specified `domain_offset`, and returns the resulting evaluations.
+    ///
+    /// `twiddles` must be twiddles of the trace domain.
+    pub fn evaluate_with_offset(
+        &self,
+        twiddles: &[B],
+        domain_offset: B,
+        blowup_factor: usize,
+    ) -> Vec<Vec<B>> {
+        iter!(self.polys)
+            .map(|p| fft::evaluate_poly_with_offset(p, twiddles, domain_offset, blowup_factor))
+            .collect()
+    }
+
+    /// Evaluates all trace polynomials over a domain of size `poly_size() * blowup_factor`
+    /// shifted by the specified `domain_offset`, writes the resulting evaluations into the
+    /// provided `columns` (one column per polynomial), and returns the initialized columns.
+    ///
+    /// Polynomials of registers which repeat a cycle shorter than the trace are evaluated only
+    /// over the first `cycle_length() * blowup_factor` points of the domain, as evaluations over
+    /// the rest of the domain repeat these; the corresponding columns must have this length.
+    ///
+    /// `twiddles` must be twiddles of the trace domain.
+    pub fn evaluate_with_offset_into(
+        &self,
+        twiddles: &[B],
+        domain_offset: B,
+        blowup_factor: usize,
+        mut columns: Vec<Vec<MaybeUninit<B>>>,
+    ) -> Vec<Vec<B>> {
+        assert_eq!(
+            self.num_polys(),
+            columns.len(),
+            "inconsistent number of columns"
+        );
+        iter_mut!(columns)
+            .zip(iter!(self.polys))
+            .zip(iter!(self.cycle_lengths))
+            .for_each(|((column, p), &cycle_length)| {
+                if cycle_length < p.len() {
+                    evaluate_cycle_into(p, cycle_length, domain_offset, blowup_factor, column);
+                } else {
+                    fft::evaluate_poly_with_offset_into(
+                        p,
+                        twiddles,
+                        domain_offset,
+                        blowup_factor,
+                        column,
+                    );
+                }
+            });
+
+        // SAFETY: all values of every column have been written by the evaluation functions
+        columns
+            .into_iter()
+            .map(|column| unsafe { assume_init_buffer(column) })
+            .collect()
+    }
+
+    /// Evaluates the polynomial at the specified index over the first
+    /// `cycle_length(idx) * blowup_factor` points of a domain of size
+    /// `poly_size() * blowup_factor` shifted by the specified `domain_offset`.
+    #[cfg(feature = "mmap")]
+    pub fn evaluate_cycle(&self, idx: usize, domain_offset: B, blowup_factor: usize) -> Vec<B> {
+        let mut result = utils::uninit_buffer(self.cycle_lengths[idx] * blowup_factor);
+        evaluate_cycle_into(
+            &self.polys[idx],
+            self.cycle_lengths[idx],
+            domain_offset,
+            blowup_factor,
+            &mut result,
+        );
+        // SAFETY: all values of the result have been written by evaluate_cycle_into()
+        unsafe { assume_init_buffer(result) }
     }
 
     /// Returns an out-of-domain evaluation frame constructed by evaluating trace polynomials
@@ -56,17 +188,62 @@ impl<B: StarkField> TracePolyTable<B> {
 
     /// Returns the number of trace polynomials in the table.
     pub fn num_polys(&self) -> usize {
-        self.0.len()
+        self.polys.len()
     }
 
     /// Returns a trace polynomial at the specified index.
-    #[cfg(test)]
     pub fn get_poly(&self, idx: usize) -> &[B] {
-        &self.0[idx]
+        &self.polys[idx]
     }
 
-    /// Converts this table into a vector of polynomials.
-    pub fn into_vec(self) -> Vec<Vec<B>> {
-        self.0
+    /// Returns an iterator over all trace polynomials in the table.
+    pub fn iter(&self) -> core::slice::Iter<'_, Column<B>> {
+        self.polys.iter()
     }
 }
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Evaluates polynomial `p` of a register which repeats a cycle of the specified length over the
+/// first `cycle_length * blowup_factor` points of a domain of size `p.len() * blowup_factor`
+/// shifted by `domain_offset`, and writes the evaluations into `result`.
+///
+/// Such a polynomial has the form p(x) = q(x^k), where k = p.len() / cycle_length. Thus, for the
+/// domain generator g, p(s * g^i) = q(s^k * (g^k)^i), and since g^k generates a subgroup of size
+/// `cycle_length * blowup_factor`, it is sufficient to evaluate q over this subgroup shifted by
+/// s^k; evaluations over the rest of the domain repeat these.
+///
+/// Values of `result` do not need to be initialized; once the function returns, all of them
+/// are initialized.
+fn evaluate_cycle_into<B: StarkField>(
+    p: &[B],
+    cycle_length: usize,
+    domain_offset: B,
+    blowup_factor: usize,
+    result: &mut [MaybeUninit<B>],
+) {
+    let stride = p.len() / cycle_length;
+    assert_eq!(
+        cycle_length * blowup_factor,
+        result.len(),
+        "invalid result length: expected {} but was {}",
+        cycle_length * blowup_factor,
+        result.len()
+    );
+
+    // compute coefficients of q(s^k * x)
+    let offset = domain_offset.exp((stride as u64).into());
+    let mut power = B::ONE;
+    for (value, &coefficient) in result.iter_mut().zip(p.iter().step_by(stride)) {
+        value.write(coefficient * power);
+        power *= offset;
+    }
+    result[cycle_length..].fill(MaybeUninit::new(B::ZERO));
+
+    // SAFETY: the first cycle_length values were written from the coefficients of q, and the
+    // rest were filled with zeros
+    let result = unsafe { assume_init_slice(result) };
+    let twiddles = fft::get_twiddles::<B>(result.len());
+    fft::evaluate_poly(result, &twiddles);
+}
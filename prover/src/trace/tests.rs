@@ -3,10 +3,15 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use super::{extend_trace, interpolate_trace, ChunkedTraceTable};
 use crate::{
-    tests::{build_fib_trace, MockAir},
-    StarkDomain,
+    tests::{
+        build_fib_trace, build_sum_options, build_sum_trace, tweak_values, MockAir, SumAir,
+        SumInputs, SumInstance,
+    },
+    ExecutionTrace, ProvingMode, StarkDomain, Trace, TracePadding, TraceValidationError,
 };
+use air::Air;
 use crypto::{hashers::Blake3_256, ElementHasher, MerkleTree};
 use math::{
     fields::f128::BaseElement, get_power_series, get_power_series_with_offset, log2, polynom,
@@ -37,6 +42,209 @@ fn new_trace_table() {
     assert_eq!(expected, trace.get_register(1));
 }
 
+#[test]
+fn init_padded_trace_table() {
+    let registers = vec![
+        (1..=10u32).map(BaseElement::from).collect::<Vec<_>>(),
+        (11..=20u32).map(BaseElement::from).collect::<Vec<_>>(),
+    ];
+
+    // padding with the last row
+    let trace = ExecutionTrace::init_padded(registers.clone(), TracePadding::RepeatLastRow);
+    assert_eq!(16, trace.length());
+    assert_eq!(10, trace.unpadded_length());
+    assert_eq!(10, trace.get_info().unpadded_length());
+    assert_eq!(&registers[0], &trace.get_register(0)[..10]);
+    assert!(trace.get_register(1)[10..]
+        .iter()
+        .all(|&v| v == BaseElement::from(20u32)));
+
+    // padding with a custom transition
+    let trace = ExecutionTrace::init_padded_with(registers, |_, state| {
+        state[0] += BaseElement::ONE;
+    });
+    assert_eq!(16, trace.length());
+    assert_eq!(BaseElement::from(11u32), trace.get(0, 10));
+    assert_eq!(BaseElement::from(16u32), trace.get(0, 15));
+    assert_eq!(BaseElement::from(20u32), trace.get(1, 15));
+
+    // short traces are padded to the minimum trace length
+    let trace = ExecutionTrace::init_padded(vec![vec![BaseElement::ONE; 3]], TracePadding::Zeros);
+    assert_eq!(8, trace.length());
+    assert_eq!(BaseElement::ZERO, trace.get(0, 3));
+}
+
+#[test]
+fn init_padded_to_length_trace_table() {
+    let registers = vec![
+        (1..=10u32).map(BaseElement::from).collect::<Vec<_>>(),
+        (11..=20u32).map(BaseElement::from).collect::<Vec<_>>(),
+    ];
+
+    // traces of different lengths can be padded to the same fixed length
+    let trace = ExecutionTrace::init_padded_to(registers.clone(), 64, TracePadding::Zeros);
+    assert_eq!(64, trace.length());
+    assert_eq!(10, trace.get_info().unpadded_length());
+    assert_eq!(&registers[1], &trace.get_register(1)[..10]);
+    assert!(trace.get_register(0)[10..]
+        .iter()
+        .all(|&v| v == BaseElement::ZERO));
+
+    let short_registers = registers.iter().map(|r| r[..3].to_vec()).collect();
+    let trace = ExecutionTrace::init_padded_to_with(short_registers, 64, |_, state| {
+        state[1] += state[0];
+    });
+    assert_eq!(64, trace.length());
+    assert_eq!(3, trace.get_info().unpadded_length());
+    assert_eq!(BaseElement::from(13u32 + 3 * 61), trace.get(1, 63));
+
+    // a trace which fills the fixed length entirely is not padded
+    let full_registers = vec![(0..16u32).map(BaseElement::from).collect::<Vec<_>>()];
+    let trace = ExecutionTrace::init_padded_to(full_registers, 16, TracePadding::RepeatLastRow);
+    assert_eq!(16, trace.get_info().unpadded_length());
+}
+
+#[test]
+#[should_panic(expected = "execution trace cannot contain more than 8 rows, but had 10")]
+fn init_padded_to_shorter_length() {
+    let registers = vec![(1..=10u32).map(BaseElement::from).collect::<Vec<_>>()];
+    ExecutionTrace::init_padded_to(registers, 8, TracePadding::Zeros);
+}
+
+#[test]
+fn trace_from_columns_and_rows() {
+    let columns = vec![
+        (0..16u32).map(BaseElement::from).collect::<Vec<_>>(),
+        (16..32u32).map(BaseElement::from).collect::<Vec<_>>(),
+    ];
+
+    // columns are moved into the trace without copying
+    let trace = ExecutionTrace::from_columns(columns.clone());
+    assert_eq!(2, trace.width());
+    assert_eq!(16, trace.length());
+    assert_eq!(&columns[1][..], trace.get_register(1));
+
+    let column = columns[0].clone();
+    let column_ptr = column.as_ptr();
+    let moved = ExecutionTrace::from_columns(vec![column]);
+    assert_eq!(column_ptr, moved.get_register(0).as_ptr());
+
+    // a trace built from rows should be the same as the trace built from columns
+    let rows = (0..16).map(|i| vec![columns[0][i], columns[1][i]]);
+    let row_trace = ExecutionTrace::from_rows(rows);
+    assert_eq!(trace.get_register(0), row_trace.get_register(0));
+    assert_eq!(trace.get_register(1), row_trace.get_register(1));
+}
+
+#[test]
+fn trace_to_and_from_bytes() {
+    let trace = build_fib_trace(16);
+    let mut bytes = Vec::new();
+    trace.write_bytes_into(&mut bytes);
+    assert_eq!(2 * 8 * BaseElement::ELEMENT_BYTES, bytes.len());
+
+    let restored = ExecutionTrace::<BaseElement>::from_bytes(&bytes, 2).unwrap();
+    assert_eq!(trace.get_register(0), restored.get_register(0));
+    assert_eq!(trace.get_register(1), restored.get_register(1));
+
+    // bytes must divide into the specified number of columns of whole elements
+    assert!(ExecutionTrace::<BaseElement>::from_bytes(&bytes[1..], 2).is_err());
+    assert!(ExecutionTrace::<BaseElement>::from_bytes(&bytes, 3).is_err());
+
+    // invalid field elements are rejected
+    bytes[..BaseElement::ELEMENT_BYTES].copy_from_slice(&[0xff; 16]);
+    assert!(ExecutionTrace::<BaseElement>::from_bytes(&bytes, 2).is_err());
+}
+
+#[test]
+#[should_panic(expected = "all rows must have 2 elements, but row 3 had 1")]
+fn trace_from_rows_inconsistent_width() {
+    let rows = (0..16u32).map(|i| {
+        if i == 3 {
+            vec![BaseElement::from(i)]
+        } else {
+            vec![BaseElement::from(i); 2]
+        }
+    });
+    ExecutionTrace::from_rows(rows);
+}
+
+#[test]
+#[should_panic(expected = "execution trace length must be a power of 2")]
+fn trace_from_rows_invalid_length() {
+    ExecutionTrace::from_rows((0..12u32).map(|i| vec![BaseElement::from(i)]));
+}
+
+#[test]
+fn update_trace_rows() {
+    let mut trace = ExecutionTrace::init(vec![
+        (0..16u32).map(BaseElement::from).collect::<Vec<_>>(),
+        vec![BaseElement::ZERO; 16],
+    ]);
+
+    // forward pass over a subset of rows
+    trace.update_rows(4..8, |step, row| {
+        row[1] = BaseElement::from(step as u32) + row[0];
+    });
+    assert_eq!(BaseElement::ZERO, trace.get(1, 3));
+    assert_eq!(BaseElement::from(8u32), trace.get(1, 4));
+    assert_eq!(BaseElement::from(14u32), trace.get(1, 7));
+    assert_eq!(BaseElement::ZERO, trace.get(1, 8));
+
+    // backward pass in which every row depends on the following row; register 1 becomes a
+    // suffix sum of register 0
+    trace.update_rows(15..16, |_, row| row[1] = row[0]);
+    trace.update_windows((0..15).rev(), 2, |_, window| {
+        window[0][1] = window[0][0] + window[1][1];
+    });
+    for step in 0..16 {
+        let expected = (step..16u32).sum::<u32>();
+        assert_eq!(BaseElement::from(expected), trace.get(1, step as usize));
+    }
+}
+
+#[test]
+#[should_panic(
+    expected = "window of 2 rows starting at step 15 extends beyond the end of the trace"
+)]
+fn update_trace_windows_out_of_bounds() {
+    let mut trace = ExecutionTrace::init(vec![vec![BaseElement::ZERO; 16]]);
+    trace.update_windows(14..16, 2, |_, _| {});
+}
+
+#[test]
+fn fill_trace_fragments() {
+    // every fragment is an independent counter which starts at the index of the fragment
+    let mut trace = ExecutionTrace::new(2, 32);
+    trace.fill_fragments(
+        4,
+        |index, state| {
+            state[0] = BaseElement::from(index as u32);
+            state[1] = BaseElement::ZERO;
+        },
+        |_, step, state| {
+            state[0] += BaseElement::ONE;
+            state[1] = BaseElement::from(step as u32 + 1);
+        },
+    );
+
+    for step in 0..32 {
+        let (index, offset) = (step / 8, step % 8);
+        assert_eq!(
+            BaseElement::from((index + offset) as u32),
+            trace.get(0, step)
+        );
+        assert_eq!(BaseElement::from(offset as u32), trace.get(1, step));
+    }
+}
+
+#[test]
+#[should_panic(expected = "number of fragments cannot exceed 4, but was 8")]
+fn fill_trace_fragments_too_many() {
+    let mut trace = ExecutionTrace::<BaseElement>::new(1, 8);
+    trace.fill_fragments(8, |_, _| {}, |_, _, _| {});
+}
+
 #[test]
 fn extend_trace_table() {
     // build and extend trace table
@@ -44,7 +252,8 @@ fn extend_trace_table() {
     let air = MockAir::with_trace_length(trace_length);
     let trace = build_fib_trace(trace_length * 2);
     let domain = StarkDomain::new(&air);
-    let (extended_trace, trace_polys) = trace.extend(&domain);
+    let trace_polys = interpolate_trace(trace, &domain, ProvingMode::InMemory);
+    let extended_trace = extend_trace(&trace_polys, &domain, None);
 
     assert_eq!(2, extended_trace.width());
     assert_eq!(64, extended_trace.len());
@@ -72,11 +281,76 @@ fn extend_trace_table() {
     let lde_domain = build_lde_domain(domain.lde_domain_size());
     assert_eq!(
         trace_polys.get_poly(0),
-        polynom::interpolate(&lde_domain, extended_trace.get_register(0), true)
+        polynom::interpolate(&lde_domain, &extended_trace.get_register(0), true)
     );
     assert_eq!(
         trace_polys.get_poly(1),
-        polynom::interpolate(&lde_domain, extended_trace.get_register(1), true)
+        polynom::interpolate(&lde_domain, &extended_trace.get_register(1), true)
+    );
+}
+
+#[test]
+fn extend_custom_trace() {
+    // a trace which generates its registers only when they are requested by the prover
+    struct LazyTrace(usize);
+
+    impl Trace for LazyTrace {
+        type BaseField = BaseElement;
+
+        fn width(&self) -> usize {
+            2
+        }
+
+        fn length(&self) -> usize {
+            self.0
+        }
+
+        fn meta(&self) -> &[u8] {
+            &[]
+        }
+
+        fn read_row_into(&self, step: usize, target: &mut [BaseElement]) {
+            target[0] = BaseElement::from(step as u32);
+            target[1] = BaseElement::from(step as u32 * 2);
+        }
+
+        fn get_column(&self, register: usize) -> Vec<BaseElement> {
+            let mut row = [BaseElement::ZERO; 2];
+            (0..self.0)
+                .map(|step| {
+                    self.read_row_into(step, &mut row);
+                    row[register]
+                })
+                .collect()
+        }
+    }
+
+    let trace_length = 8;
+    let air = MockAir::with_trace_length(trace_length);
+    let domain = StarkDomain::new(&air);
+    let trace = LazyTrace(trace_length);
+    assert_eq!(trace_length, trace.get_info().length());
+    assert_eq!(trace_length, trace.get_info().unpadded_length());
+
+    // extending a custom trace should be the same as extending an equivalent execution trace
+    let expected = ExecutionTrace::init(vec![trace.get_column(0), trace.get_column(1)]);
+    let extended_trace = extend_trace(
+        &interpolate_trace(trace, &domain, ProvingMode::InMemory),
+        &domain,
+        None,
+    );
+    let expected_trace = extend_trace(
+        &interpolate_trace(expected, &domain, ProvingMode::InMemory),
+        &domain,
+        None,
+    );
+    assert_eq!(
+        expected_trace.get_register(0),
+        extended_trace.get_register(0)
+    );
+    assert_eq!(
+        expected_trace.get_register(1),
+        extended_trace.get_register(1)
     );
 }
 
@@ -87,10 +361,15 @@ fn commit_trace_table() {
     let air = MockAir::with_trace_length(trace_length);
     let trace = build_fib_trace(trace_length * 2);
     let domain = StarkDomain::new(&air);
-    let (extended_trace, _) = trace.extend(&domain);
+    let extended_trace = extend_trace(
+        &interpolate_trace(trace, &domain, ProvingMode::InMemory),
+        &domain,
+        None,
+    );
 
     // commit to the trace
-    let trace_tree = extended_trace.build_commitment::<Blake3>();
+    let segments = air.options().trace_segments(extended_trace.width());
+    let trace_tree = extended_trace.build_commitment::<Blake3>(&segments);
 
     // build Merkle tree from trace rows
     let mut hashed_states = Vec::new();
@@ -106,7 +385,297 @@ fn commit_trace_table() {
     let expected_tree = MerkleTree::<Blake3>::new(hashed_states).unwrap();
 
     // compare the result
-    assert_eq!(expected_tree.root(), trace_tree.root())
+    assert_eq!(expected_tree.root(), trace_tree[0].root())
+}
+
+#[test]
+fn commit_and_query_trace_segments() {
+    let trace_length = 16;
+    let air = MockAir::with_trace_length(trace_length);
+    let domain = StarkDomain::new(&air);
+    let trace = ExecutionTrace::init(
+        (0..5u32)
+            .map(|i| {
+                (0..trace_length as u32)
+                    .map(|j| BaseElement::from(i * 31 + j * j))
+                    .collect()
+            })
+            .collect(),
+    );
+    let trace_polys = interpolate_trace(trace, &domain, ProvingMode::InMemory);
+    let extended_trace = extend_trace(&trace_polys, &domain, None);
+    let segments = [0..2, 2..4, 4..5];
+
+    // each segment should be committed to via a Merkle tree built from its values in every row
+    let trace_trees = extended_trace.build_commitment::<Blake3>(&segments);
+    assert_eq!(segments.len(), trace_trees.len());
+    for (segment, trace_tree) in segments.iter().zip(trace_trees.iter()) {
+        let hashed_states = (0..extended_trace.len())
+            .map(|i| {
+                let state = segment
+                    .clone()
+                    .map(|j| extended_trace.get(j, i))
+                    .collect::<Vec<_>>();
+                Blake3::hash_elements(&state)
+            })
+            .collect();
+        let expected_tree = MerkleTree::<Blake3>::new(hashed_states).unwrap();
+        assert_eq!(expected_tree.root(), trace_tree.root());
+    }
+
+    // all table layouts should produce the same segment commitments and queries
+    let positions = [1, 6, 19, 64, 127];
+    let chunked_trace = ChunkedTraceTable::new(&trace_polys, &domain);
+    let chunked_trees = chunked_trace.build_commitment::<Blake3>(&segments);
+    let row_major_trace = extend_trace(&trace_polys, &domain, None).into_row_major(None);
+    let row_major_trees = row_major_trace.build_commitment::<Blake3>(&segments);
+    for ((trace_tree, chunked_tree), row_major_tree) in trace_trees
+        .iter()
+        .zip(chunked_trees.iter())
+        .zip(row_major_trees.iter())
+    {
+        assert_eq!(trace_tree.root(), chunked_tree.root());
+        assert_eq!(trace_tree.root(), row_major_tree.root());
+    }
+    let trace_queries = extended_trace.query(&segments, trace_trees, &positions);
+    assert_eq!(
+        trace_queries,
+        chunked_trace.query(&segments, chunked_trees, &positions)
+    );
+    assert_eq!(
+        trace_queries,
+        chunked_trace.query_points(
+            &segments,
+            chunked_trace.build_commitment::<Blake3>(&segments),
+            &positions
+        )
+    );
+    assert_eq!(
+        trace_queries,
+        row_major_trace.query(&segments, row_major_trees, &positions)
+    );
+}
+
+#[test]
+fn commit_and_query_chunked_trace_table() {
+    let trace_length = 16;
+    let air = MockAir::with_trace_length(trace_length);
+    let trace = build_fib_trace(trace_length * 2);
+    let domain = StarkDomain::new(&air);
+    let trace_polys = interpolate_trace(trace, &domain, ProvingMode::InMemory);
+
+    // a chunked table should be committed to the same way as a fully extended table
+    let extended_trace = extend_trace(&trace_polys, &domain, None);
+    let chunked_trace = ChunkedTraceTable::new(&trace_polys, &domain);
+    assert_eq!(extended_trace.len(), chunked_trace.len());
+    let segments = air.options().trace_segments(extended_trace.width());
+    let trace_tree = extended_trace.build_commitment::<Blake3>(&segments);
+    let chunked_tree = chunked_trace.build_commitment::<Blake3>(&segments);
+    assert_eq!(trace_tree[0].root(), chunked_tree[0].root());
+
+    // and queries against both tables should be the same as well, regardless of whether the
+    // queried rows are computed by cosets or one by one
+    let positions = [1, 6, 19, 64, 127];
+    assert_eq!(
+        chunked_trace.query(
+            &segments,
+            chunked_trace.build_commitment::<Blake3>(&segments),
+            &positions
+        ),
+        chunked_trace.query_points(
+            &segments,
+            chunked_trace.build_commitment::<Blake3>(&segments),
+            &positions
+        )
+    );
+    assert_eq!(
+        extended_trace.query(&segments, trace_tree, &positions),
+        chunked_trace.query(&segments, chunked_tree, &positions)
+    );
+}
+
+#[test]
+fn commit_and_query_row_major_trace_table() {
+    let trace_length = 16;
+    let air = MockAir::with_trace_length(trace_length);
+    let trace = build_fib_trace(trace_length * 2);
+    let domain = StarkDomain::new(&air);
+    let trace_polys = interpolate_trace(trace, &domain, ProvingMode::InMemory);
+
+    // a table converted into row-major layout should contain the same values
+    let extended_trace = extend_trace(&trace_polys, &domain, None);
+    let row_major_trace = extend_trace(&trace_polys, &domain, None).into_row_major(None);
+    assert!(!extended_trace.is_row_major());
+    assert!(row_major_trace.is_row_major());
+    assert_eq!(extended_trace.width(), row_major_trace.width());
+    assert_eq!(extended_trace.len(), row_major_trace.len());
+    for i in 0..extended_trace.width() {
+        assert_eq!(
+            extended_trace.get_register(i),
+            row_major_trace.get_register(i)
+        );
+    }
+
+    // and it should be committed to and queried the same way
+    let segments = air.options().trace_segments(extended_trace.width());
+    let trace_tree = extended_trace.build_commitment::<Blake3>(&segments);
+    let row_major_tree = row_major_trace.build_commitment::<Blake3>(&segments);
+    assert_eq!(trace_tree[0].root(), row_major_tree[0].root());
+
+    let positions = [1, 6, 19, 64, 127];
+    assert_eq!(
+        extended_trace.query(&segments, trace_tree, &positions),
+        row_major_trace.query(&segments, row_major_tree, &positions)
+    );
+}
+
+#[test]
+fn extend_cyclic_trace_table() {
+    let trace_length = 16;
+    let air = MockAir::with_trace_length(trace_length);
+    let domain = StarkDomain::new(&air);
+
+    // build a trace with a regular register, a selector register, a constant register, and a
+    // register of all zeros
+    let build_trace = || {
+        ExecutionTrace::init(vec![
+            (0..trace_length as u32)
+                .map(|i| BaseElement::from(i * i + 3))
+                .collect(),
+            (0..trace_length)
+                .map(|i| BaseElement::from((i % 4 == 0) as u32))
+                .collect(),
+            vec![BaseElement::from(7u32); trace_length],
+            vec![BaseElement::ZERO; trace_length],
+        ])
+    };
+    let mut cyclic_trace = build_trace();
+    cyclic_trace.set_cycle_length(1, 4);
+    cyclic_trace.set_cycle_length(2, 1);
+    cyclic_trace.set_cycle_length(3, 1);
+    assert!(super::validate_trace(&cyclic_trace, &air).is_ok());
+
+    let trace_polys = interpolate_trace(build_trace(), &domain, ProvingMode::InMemory);
+    let cyclic_polys = interpolate_trace(cyclic_trace, &domain, ProvingMode::InMemory);
+    assert_eq!(trace_length, cyclic_polys.cycle_length(0));
+    assert_eq!(4, cyclic_polys.cycle_length(1));
+    assert_eq!(1, cyclic_polys.cycle_length(2));
+    assert_eq!(1, cyclic_polys.cycle_length(3));
+
+    // extended registers should be the same regardless of whether cycles were declared
+    let extended_trace = extend_trace(&trace_polys, &domain, None);
+    let cyclic_extended_trace = extend_trace(&cyclic_polys, &domain, None);
+    assert_eq!(extended_trace.len(), cyclic_extended_trace.len());
+    for i in 0..extended_trace.width() {
+        assert_eq!(
+            extended_trace.get_register(i),
+            cyclic_extended_trace.get_register(i)
+        );
+    }
+
+    // and the tables should be committed to and queried the same way, in both layouts
+    let positions = [1, 6, 19, 64, 127];
+    let segments = air.options().trace_segments(extended_trace.width());
+    let trace_tree = extended_trace.build_commitment::<Blake3>(&segments);
+    let cyclic_tree = cyclic_extended_trace.build_commitment::<Blake3>(&segments);
+    assert_eq!(trace_tree[0].root(), cyclic_tree[0].root());
+    let row_major_trace = cyclic_extended_trace.into_row_major(None);
+    for i in 0..extended_trace.width() {
+        assert_eq!(
+            extended_trace.get_register(i),
+            row_major_trace.get_register(i)
+        );
+    }
+    assert_eq!(
+        extended_trace.query(&segments, trace_tree, &positions),
+        row_major_trace.query(&segments, cyclic_tree, &positions)
+    );
+
+    // a register which does not repeat the declared cycle is extended in full, and the trace
+    // fails validation
+    let mut invalid_trace = build_trace();
+    invalid_trace.set_cycle_length(0, 2);
+    assert_eq!(
+        Err(crate::TraceValidationError::MismatchedRegisterCycle(0, 2)),
+        super::validate_trace(&invalid_trace, &air)
+    );
+    let invalid_polys = interpolate_trace(invalid_trace, &domain, ProvingMode::InMemory);
+    assert_eq!(trace_length, invalid_polys.cycle_length(0));
+}
+
+#[test]
+#[cfg(feature = "mmap")]
+fn extend_mapped_trace_table() {
+    let trace_length = 16;
+    let air = MockAir::with_trace_length(trace_length);
+    let domain = StarkDomain::new(&air);
+
+    // build the same trace on the heap and in memory-mapped files
+    let trace = build_fib_trace(trace_length * 2);
+    let mut mapped_trace = ExecutionTrace::new_mapped(trace.width(), trace.length());
+    for i in 0..trace.length() {
+        mapped_trace.update_row(i, &[trace.get(0, i), trace.get(1, i)]);
+    }
+
+    // extending the trace in memory-mapped files should give the same result
+    let trace_polys = interpolate_trace(trace, &domain, ProvingMode::InMemory);
+    let extended_trace = extend_trace(&trace_polys, &domain, None);
+    let mapped_polys = interpolate_trace(mapped_trace, &domain, ProvingMode::MemoryMapped);
+    let mapped_extended_trace = super::extend_trace_mapped(&mapped_polys, &domain);
+    for i in 0..2 {
+        assert_eq!(trace_polys.get_poly(i), mapped_polys.get_poly(i));
+        assert_eq!(
+            extended_trace.get_register(i),
+            mapped_extended_trace.get_register(i)
+        );
+    }
+}
+
+#[test]
+fn validate_trace() {
+    let sum = SumInstance::new(63, build_sum_options(false));
+    let options = sum.options.clone();
+
+    // a valid trace passes validation
+    let trace = sum.build_trace();
+    assert_eq!(
+        Ok(()),
+        crate::validate::<SumAir>(&trace, sum.pub_inputs(), options.clone())
+    );
+
+    // a wrong result violates the assertion at the last step
+    let wrong_inputs = SumInputs {
+        commitment: sum.commitment.clone(),
+        result: sum.result + BaseElement::ONE,
+    };
+    assert_eq!(
+        Err(TraceValidationError::UnsatisfiedAssertion {
+            register: 1,
+            step: 63,
+            expected: wrong_inputs.result,
+            actual: sum.result,
+        }),
+        crate::validate::<SumAir>(&trace, wrong_inputs, options.clone())
+    );
+
+    // a broken accumulator violates the transition constraint
+    let mut broken_trace = sum.build_trace();
+    broken_trace.set(1, 10, BaseElement::ZERO);
+    assert_eq!(
+        Err(TraceValidationError::UnsatisfiedTransitionConstraint {
+            constraint: 0,
+            step: 9,
+            value: BaseElement::ZERO - broken_trace.get(1, 9) - broken_trace.get(0, 9),
+        }),
+        crate::validate::<SumAir>(&broken_trace, sum.pub_inputs(), options.clone())
+    );
+
+    // values which have the same sum but do not match the column commitment are detected
+    let tweaked_trace = build_sum_trace(&tweak_values(&sum.values));
+    assert_eq!(
+        Err(TraceValidationError::MismatchedColumnCommitment(0)),
+        crate::validate::<SumAir>(&tweaked_trace, sum.pub_inputs(), options)
+    );
 }
 
 // HELPER FUNCTIONS
@@ -3,27 +3,137 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use super::{BufferPool, Column};
 use air::{proof::Queries, EvaluationFrame};
+use core::{mem::MaybeUninit, ops::Range};
 use crypto::{ElementHasher, Hasher, MerkleTree};
 use math::StarkField;
-use utils::{batch_iter_mut, collections::Vec, uninit_vector};
+use utils::{
+    assume_init_buffer, batch_iter_mut,
+    collections::Vec,
+    iter,
+    table::{TableView, TableViewMut},
+    uninit_buffer,
+};
 
 #[cfg(feature = "concurrent")]
 use utils::iterators::*;
 
+// CONSTANTS
+// ================================================================================================
+
+/// Number of rows transposed together when a table is converted into row-major layout; rows of
+/// a single tile should fit into L2 cache for reasonably wide traces.
+const TRANSPOSE_TILE_SIZE: usize = 256;
+
 // TRACE TABLE
 // ================================================================================================
+/// An extended execution trace kept in memory (or in memory-mapped files) in its entirety.
+///
+/// The table is stored either column by column, or, after [TraceTable::into_row_major()] has
+/// been called, row by row. Row-major layout makes reading full rows (e.g. during constraint
+/// evaluation and trace commitment) much more cache-friendly for wide traces.
+///
+/// In column-major layout, a column can be shorter than the table; this is the case for
+/// registers which repeat short cycles, and for which only a single cycle of extended values is
+/// stored. Values of such columns are read at `step % column.len()`. In row-major layout, all
+/// values are stored explicitly.
 pub struct TraceTable<B: StarkField> {
-    data: Vec<Vec<B>>,
+    data: TableData<B>,
+    num_rows: usize,
     blowup: usize,
 }
 
+enum TableData<B: StarkField> {
+    ColumnMajor(Vec<Column<B>>),
+    RowMajor { rows: Vec<B>, width: usize },
+}
+
 impl<B: StarkField> TraceTable<B> {
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
     /// Creates a new trace table from a list of provided register traces.
-    pub(super) fn new(data: Vec<Vec<B>>, blowup: usize) -> Self {
-        TraceTable { data, blowup }
+    ///
+    /// The number of rows in the table is equal to the length of the longest register trace;
+    /// lengths of all other register traces must divide it.
+    pub(super) fn new(data: Vec<Column<B>>, blowup: usize) -> Self {
+        let num_rows = data.iter().map(|column| column.len()).max().unwrap_or(0);
+        debug_assert!(
+            data.iter().all(|column| num_rows % column.len() == 0),
+            "column lengths must divide the number of rows"
+        );
+        TraceTable {
+            data: TableData::ColumnMajor(data),
+            num_rows,
+            blowup,
+        }
+    }
+
+    /// Converts this table into row-major layout; if the table is already in row-major layout,
+    /// it is returned unchanged.
+    ///
+    /// The registers are transposed into a newly allocated vector; thus, while the conversion
+    /// is in progress, the table takes up twice as much memory. If `buffer_pool` is provided,
+    /// the vector is taken from the pool, and the buffers of the registers are returned to it.
+    pub fn into_row_major(self, mut buffer_pool: Option<&mut (dyn BufferPool<B> + '_)>) -> Self {
+        let columns = match self.data {
+            TableData::ColumnMajor(columns) => columns,
+            TableData::RowMajor { .. } => return self,
+        };
+
+        let width = columns.len();
+        let num_values = self.num_rows * width;
+        let mut rows = match buffer_pool.as_deref_mut() {
+            Some(pool) => pool.take(num_values),
+            None => uninit_buffer(num_values),
+        };
+        batch_iter_mut!(
+            &mut rows,
+            TRANSPOSE_TILE_SIZE * width, // min batch size
+            |batch: &mut [MaybeUninit<B>], batch_offset: usize| {
+                let first_row = batch_offset / width;
+                let batch = TableViewMut::new(batch, batch.len() / width, width, width);
+                for (k, mut tile) in batch
+                    .into_fragments(TRANSPOSE_TILE_SIZE)
+                    .into_iter()
+                    .enumerate()
+                {
+                    let tile_offset = first_row + k * TRANSPOSE_TILE_SIZE;
+                    for (i, register) in columns.iter().enumerate() {
+                        // columns are either full-length, or hold a single cycle of values; in
+                        // the latter case, the length of a column is a power of two
+                        let mask = register.len() - 1;
+                        for (k, row) in tile.rows_mut().enumerate() {
+                            row[i].write(register[(tile_offset + k) & mask]);
+                        }
+                    }
+                }
+            }
+        );
+        // SAFETY: every row of every tile has been written above, one value per register, and
+        // the tiles cover the entire buffer
+        let rows = unsafe { assume_init_buffer(rows) };
+
+        if let Some(pool) = buffer_pool {
+            for buffer in into_heap_buffers(columns) {
+                pool.release(buffer);
+            }
+        }
+
+        TraceTable {
+            data: TableData::RowMajor { rows, width },
+            num_rows: self.num_rows,
+            blowup: self.blowup,
+        }
+    }
+
+    /// Destroys this table and returns the heap-allocated vectors which hold its values;
+    /// values stored in memory-mapped files are not returned.
+    pub fn into_buffers(self) -> Vec<Vec<B>> {
+        match self.data {
+            TableData::ColumnMajor(columns) => into_heap_buffers(columns),
+            TableData::RowMajor { rows, .. } => vec![rows],
+        }
     }
 
     // PUBLIC ACCESSORS
@@ -31,13 +141,16 @@ impl<B: StarkField> TraceTable<B> {
 
     /// Returns number of registers in the trace table.
     pub fn width(&self) -> usize {
-        self.data.len()
+        match &self.data {
+            TableData::ColumnMajor(columns) => columns.len(),
+            TableData::RowMajor { width, .. } => *width,
+        }
     }
 
     /// Returns the number of states in this trace table.
     #[allow(clippy::len_without_is_empty)]
     pub fn len(&self) -> usize {
-        self.data[0].len()
+        self.num_rows
     }
 
     /// Returns blowup factor which was used to extend original trace into this trace.
@@ -45,21 +158,62 @@ impl<B: StarkField> TraceTable<B> {
         self.blowup
     }
 
+    /// Returns true if this table is stored in row-major layout.
+    #[cfg(test)]
+    pub fn is_row_major(&self) -> bool {
+        matches!(self.data, TableData::RowMajor { .. })
+    }
+
     /// Returns value in the specified `register` at the specified `step`.
+    #[cfg(test)]
     pub fn get(&self, register: usize, step: usize) -> B {
-        self.data[register][step]
+        match &self.data {
+            TableData::ColumnMajor(columns) => columns[register][step % columns[register].len()],
+            TableData::RowMajor { rows, width } => {
+                *TableView::new(rows, self.num_rows, *width, *width).get(step, register)
+            }
+        }
     }
 
     /// Returns the entire register trace for the register at the specified index.
     #[cfg(test)]
-    pub fn get_register(&self, idx: usize) -> &[B] {
-        &self.data[idx]
+    pub fn get_register(&self, idx: usize) -> Vec<B> {
+        match &self.data {
+            TableData::ColumnMajor(columns) => columns[idx]
+                .iter()
+                .copied()
+                .cycle()
+                .take(self.num_rows)
+                .collect(),
+            TableData::RowMajor { rows, width } => {
+                TableView::new(rows, self.num_rows, *width, *width)
+                    .column(idx)
+                    .copied()
+                    .collect()
+            }
+        }
     }
 
     /// Copies values of all registers at the specified `step` into the `destination` slice.
     pub fn read_row_into(&self, step: usize, row: &mut [B]) {
-        for (register, value) in self.data.iter().zip(row.iter_mut()) {
-            *value = register[step];
+        self.read_columns_into(step, 0..self.width(), row);
+    }
+
+    /// Copies values of the specified range of registers at the specified `step` into the
+    /// `destination` slice.
+    pub fn read_columns_into(&self, step: usize, registers: Range<usize>, row: &mut [B]) {
+        match &self.data {
+            TableData::ColumnMajor(columns) => {
+                // lengths of all columns are powers of two; thus, masking the step is the same
+                // as reducing it modulo the column length
+                for (register, value) in columns[registers].iter().zip(row.iter_mut()) {
+                    *value = register[step & (register.len() - 1)];
+                }
+            }
+            TableData::RowMajor { rows, width } => {
+                TableView::new(rows, self.num_rows, *width, *width)
+                    .read_row_into(step, registers, row);
+            }
         }
     }
 
@@ -74,56 +228,123 @@ impl<B: StarkField> TraceTable<B> {
 
     // TRACE COMMITMENT
     // --------------------------------------------------------------------------------------------
-    /// Builds a Merkle tree out of trace table rows (hash of each row becomes a leaf in the tree).
-    pub fn build_commitment<H: ElementHasher<BaseField = B>>(&self) -> MerkleTree<H> {
-        // allocate vector to store row hashes
-        let mut hashed_states = unsafe { uninit_vector::<H::Digest>(self.len()) };
-
-        // iterate though table rows, hashing each row; the hashing is done by first copying
-        // the state into trace_state buffer to avoid unneeded allocations, and then by applying
-        // the hash function to the buffer.
-        batch_iter_mut!(
-            &mut hashed_states,
-            128, // min batch size
-            |batch: &mut [H::Digest], batch_offset: usize| {
-                let mut trace_state = vec![B::ZERO; self.width()];
-                for (i, row_hash) in batch.iter_mut().enumerate() {
-                    self.read_row_into(i + batch_offset, &mut trace_state);
-                    *row_hash = H::hash_elements(&trace_state);
-                }
-            }
-        );
+    /// Builds a Merkle tree out of trace table rows for each of the specified trace `segments`
+    /// (hash of the segment's values in each row becomes a leaf in the tree of the segment).
+    ///
+    /// When `concurrent` feature is enabled, trees for different segments are built in parallel.
+    pub fn build_commitment<H: ElementHasher<BaseField = B>>(
+        &self,
+        segments: &[Range<usize>],
+    ) -> Vec<MerkleTree<H>> {
+        iter!(segments)
+            .map(|segment| {
+                // allocate vector to store row hashes
+                let mut hashed_states = vec![H::Digest::default(); self.len()];
 
-        // build Merkle tree out of hashed rows
-        MerkleTree::new(hashed_states).expect("failed to construct trace Merkle tree")
+                // iterate though table rows, hashing the segment of each row; the hashing is done
+                // by first copying the segment into trace_state buffer to avoid unneeded
+                // allocations, and then by applying the hash function to the buffer.
+                batch_iter_mut!(
+                    &mut hashed_states,
+                    128, // min batch size
+                    |batch: &mut [H::Digest], batch_offset: usize| {
+                        let mut trace_state = vec![B::ZERO; segment.len()];
+                        for (i, row_hash) in batch.iter_mut().enumerate() {
+                            self.read_columns_into(
+                                i + batch_offset,
+                                segment.clone(),
+                                &mut trace_state,
+                            );
+                            *row_hash = H::hash_elements(&trace_state);
+                        }
+                    }
+                );
+
+                // build Merkle tree out of hashed rows
+                MerkleTree::new(hashed_states).expect("failed to construct trace Merkle tree")
+            })
+            .collect()
     }
 
     // QUERY TRACE
     // --------------------------------------------------------------------------------------------
-    /// Returns trace table rows at the specified positions along with Merkle authentication paths
-    /// from the `commitment` root to these rows.
-    pub fn query<H: Hasher>(&self, commitment: MerkleTree<H>, positions: &[usize]) -> Queries {
-        assert_eq!(
-            self.len(),
-            commitment.leaves().len(),
-            "inconsistent trace table commitment"
-        );
-
+    /// Returns trace table rows at the specified positions split into the specified `segments`,
+    /// along with Merkle authentication paths from the roots of the segment `commitments` to
+    /// these rows.
+    pub fn query<H: Hasher>(
+        &self,
+        segments: &[Range<usize>],
+        commitments: Vec<MerkleTree<H>>,
+        positions: &[usize],
+    ) -> Vec<Queries> {
         // allocate memory for queried trace states
         let mut trace_states = Vec::with_capacity(positions.len());
 
         // copy values from the trace table at the specified positions into rows
         // and append the rows to trace_states
         for &i in positions.iter() {
-            let row = self.data.iter().map(|r| r[i]).collect();
+            let mut row = vec![B::ZERO; self.width()];
+            self.read_row_into(i, &mut row);
             trace_states.push(row);
         }
 
-        // build Merkle authentication paths to the leaves specified by positions
-        let trace_proof = commitment
-            .prove_batch(positions)
-            .expect("failed to generate a Merkle proof for trace queries");
-
-        Queries::new(trace_proof, trace_states)
+        query_segments(self.len(), segments, commitments, trace_states, positions)
     }
 }
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Splits the specified rows of an extended trace with `num_rows` rows into the specified
+/// `segments`, and returns the values of each segment together with Merkle authentication paths
+/// from the root of the segment's commitment to these values.
+pub(super) fn query_segments<B: StarkField, H: Hasher>(
+    num_rows: usize,
+    segments: &[Range<usize>],
+    commitments: Vec<MerkleTree<H>>,
+    trace_states: Vec<Vec<B>>,
+    positions: &[usize],
+) -> Vec<Queries> {
+    assert_eq!(
+        segments.len(),
+        commitments.len(),
+        "inconsistent number of trace segment commitments"
+    );
+
+    segments
+        .iter()
+        .zip(commitments)
+        .map(|(segment, commitment)| {
+            assert_eq!(
+                num_rows,
+                commitment.leaves().len(),
+                "inconsistent trace table commitment"
+            );
+            let segment_states = trace_states
+                .iter()
+                .map(|state| state[segment.clone()].to_vec())
+                .collect();
+
+            // build Merkle authentication paths to the leaves specified by positions
+            let segment_proof = commitment
+                .prove_batch(positions)
+                .expect("failed to generate a Merkle proof for trace queries");
+
+            Queries::new(segment_proof, segment_states)
+        })
+        .collect()
+}
+
+/// Returns the heap-allocated vectors which hold values of the specified columns; columns
+/// stored in memory-mapped files are dropped.
+#[allow(clippy::unnecessary_filter_map)] // columns can be memory-mapped only with `mmap` feature
+fn into_heap_buffers<B: StarkField>(columns: Vec<Column<B>>) -> Vec<Vec<B>> {
+    columns
+        .into_iter()
+        .filter_map(|column| match column {
+            Column::Heap(buffer) => Some(buffer),
+            #[cfg(feature = "mmap")]
+            Column::Mapped(_) => None,
+        })
+        .collect()
+}
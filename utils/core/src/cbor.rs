@@ -0,0 +1,272 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Canonical CBOR ([RFC 8949](https://www.rfc-editor.org/rfc/rfc8949)) encoding of values.
+//!
+//! Only the subset of CBOR needed to encode STARK proofs and public inputs is supported: unsigned
+//! integers, byte strings, arrays, and maps with unsigned integer keys. All values are encoded
+//! following the core deterministic encoding requirements of RFC 8949:
+//! * Integers and lengths are encoded in the shortest possible form.
+//! * Arrays, maps, and byte strings always have definite lengths.
+//! * Keys of a map are written in ascending order.
+//!
+//! [CborReader] accepts only values encoded this way, and map keys are read by position; thus,
+//! every value has exactly one encoding, and the encoding can be hashed to identify the value.
+
+use crate::{
+    collections::Vec, ByteReader, Deserializable, DeserializationError, Serializable, SliceReader,
+};
+
+// CONSTANTS
+// ================================================================================================
+
+const MAJOR_TYPE_UINT: u8 = 0;
+const MAJOR_TYPE_BYTES: u8 = 2;
+const MAJOR_TYPE_ARRAY: u8 = 4;
+const MAJOR_TYPE_MAP: u8 = 5;
+
+/// Additional information values indicating that the argument follows the initial byte in the
+/// next 1, 2, 4, or 8 bytes respectively.
+const ONE_BYTE: u8 = 24;
+const TWO_BYTES: u8 = 25;
+const FOUR_BYTES: u8 = 26;
+const EIGHT_BYTES: u8 = 27;
+
+// CBOR WRITER
+// ================================================================================================
+
+/// Writes values into a vector of bytes using canonical CBOR encoding.
+#[derive(Debug, Default)]
+pub struct CborWriter(Vec<u8>);
+
+impl CborWriter {
+    /// Returns a new writer with an empty output.
+    pub fn new() -> Self {
+        CborWriter(Vec::new())
+    }
+
+    /// Returns the bytes written into this writer.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.0
+    }
+
+    /// Writes an unsigned integer.
+    pub fn write_uint(&mut self, value: u64) {
+        self.write_head(MAJOR_TYPE_UINT, value);
+    }
+
+    /// Writes a byte string.
+    pub fn write_bytes(&mut self, bytes: &[u8]) {
+        self.write_head(MAJOR_TYPE_BYTES, bytes.len() as u64);
+        self.0.extend_from_slice(bytes);
+    }
+
+    /// Writes the binary serialization of the specified `value` as a byte string.
+    pub fn write_serializable<S: Serializable>(&mut self, value: &S) {
+        self.write_bytes(&value.to_bytes());
+    }
+
+    /// Writes the header of an array with the specified number of items; the items must be
+    /// written right after the header.
+    pub fn write_array_header(&mut self, num_items: usize) {
+        self.write_head(MAJOR_TYPE_ARRAY, num_items as u64);
+    }
+
+    /// Writes the header of a map with the specified number of entries; the entries must be
+    /// written right after the header, each as a key followed by a value, with keys written via
+    /// [write_uint()](Self::write_uint) in ascending order.
+    pub fn write_map_header(&mut self, num_entries: usize) {
+        self.write_head(MAJOR_TYPE_MAP, num_entries as u64);
+    }
+
+    /// Writes the initial byte of a data item with the specified major type followed by the
+    /// argument in the shortest possible form.
+    fn write_head(&mut self, major_type: u8, argument: u64) {
+        let major_type = major_type << 5;
+        if argument < ONE_BYTE as u64 {
+            self.0.push(major_type | argument as u8);
+        } else if argument <= u8::MAX as u64 {
+            self.0.push(major_type | ONE_BYTE);
+            self.0.push(argument as u8);
+        } else if argument <= u16::MAX as u64 {
+            self.0.push(major_type | TWO_BYTES);
+            self.0.extend_from_slice(&(argument as u16).to_be_bytes());
+        } else if argument <= u32::MAX as u64 {
+            self.0.push(major_type | FOUR_BYTES);
+            self.0.extend_from_slice(&(argument as u32).to_be_bytes());
+        } else {
+            self.0.push(major_type | EIGHT_BYTES);
+            self.0.extend_from_slice(&argument.to_be_bytes());
+        }
+    }
+}
+
+// CBOR READER
+// ================================================================================================
+
+/// Reads values encoded via [CborWriter] from a slice of bytes.
+///
+/// Values which are not encoded canonically (e.g., integers not encoded in the shortest form,
+/// or values of indefinite length) are rejected.
+pub struct CborReader<'a> {
+    source: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> CborReader<'a> {
+    /// Returns a new reader of the specified `source`.
+    pub fn new(source: &'a [u8]) -> Self {
+        CborReader { source, pos: 0 }
+    }
+
+    /// Returns an unsigned integer read from `self`.
+    ///
+    /// # Errors
+    /// Returns an error if the next value is not a canonically encoded unsigned integer.
+    pub fn read_uint(&mut self) -> Result<u64, DeserializationError> {
+        self.read_head(MAJOR_TYPE_UINT, "an unsigned integer")
+    }
+
+    /// Returns a byte string read from `self`.
+    ///
+    /// # Errors
+    /// Returns an error if the next value is not a canonically encoded byte string.
+    pub fn read_bytes(&mut self) -> Result<Vec<u8>, DeserializationError> {
+        let len = self.read_head(MAJOR_TYPE_BYTES, "a byte string")?;
+        if len > (self.source.len() - self.pos) as u64 {
+            return Err(DeserializationError::UnexpectedEOF);
+        }
+        let end_pos = self.pos + len as usize;
+        let result = self.source[self.pos..end_pos].to_vec();
+        self.pos = end_pos;
+        Ok(result)
+    }
+
+    /// Returns a value deserialized from a byte string read from `self`.
+    ///
+    /// # Errors
+    /// Returns an error if the next value is not a canonically encoded byte string, if a valid
+    /// value could not be read from the byte string, or if not all bytes of the byte string were
+    /// consumed by the value.
+    pub fn read_deserializable<D: Deserializable>(&mut self) -> Result<D, DeserializationError> {
+        let bytes = self.read_bytes()?;
+        let mut source = SliceReader::new(&bytes);
+        let value = D::read_from(&mut source)?;
+        if source.has_more_bytes() {
+            return Err(DeserializationError::UnconsumedBytes);
+        }
+        Ok(value)
+    }
+
+    /// Returns the number of items of an array read from `self`.
+    ///
+    /// # Errors
+    /// Returns an error if the next value is not a header of a canonically encoded array.
+    pub fn read_array_header(&mut self) -> Result<usize, DeserializationError> {
+        let num_items = self.read_head(MAJOR_TYPE_ARRAY, "an array")?;
+        self.check_num_items(num_items)
+    }
+
+    /// Returns the number of entries of a map read from `self`.
+    ///
+    /// # Errors
+    /// Returns an error if the next value is not a header of a canonically encoded map.
+    pub fn read_map_header(&mut self) -> Result<usize, DeserializationError> {
+        let num_entries = self.read_head(MAJOR_TYPE_MAP, "a map")?;
+        self.check_num_items(num_entries)
+    }
+
+    /// Reads a map key from `self` and makes sure it is equal to the `expected` key.
+    ///
+    /// # Errors
+    /// Returns an error if the next value is not a canonically encoded unsigned integer, or if
+    /// it is not equal to the `expected` key.
+    pub fn read_map_key(&mut self, expected: u64) -> Result<(), DeserializationError> {
+        let key = self.read_uint()?;
+        if key != expected {
+            return Err(DeserializationError::InvalidValue(format!(
+                "expected map key {}, but was {}",
+                expected, key
+            )));
+        }
+        Ok(())
+    }
+
+    /// Returns true if there are more bytes left to be read from `self`.
+    pub fn has_more_bytes(&self) -> bool {
+        self.pos < self.source.len()
+    }
+
+    // HELPER FUNCTIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Reads the initial byte of a data item of the specified major type followed by its
+    /// argument, and makes sure the argument is encoded in the shortest possible form.
+    fn read_head(&mut self, major_type: u8, name: &str) -> Result<u64, DeserializationError> {
+        let initial_byte = self.read_u8()?;
+        if initial_byte >> 5 != major_type {
+            return Err(DeserializationError::InvalidValue(format!(
+                "expected {}, but found a value of major type {}",
+                name,
+                initial_byte >> 5
+            )));
+        }
+
+        let (argument, min_value) = match initial_byte & 0x1f {
+            info if info < ONE_BYTE => return Ok(info as u64),
+            ONE_BYTE => (self.read_u8()? as u64, ONE_BYTE as u64),
+            TWO_BYTES => (
+                u16::from_be_bytes(self.read_array()?) as u64,
+                u8::MAX as u64 + 1,
+            ),
+            FOUR_BYTES => (
+                u32::from_be_bytes(self.read_array()?) as u64,
+                u16::MAX as u64 + 1,
+            ),
+            EIGHT_BYTES => (u64::from_be_bytes(self.read_array()?), u32::MAX as u64 + 1),
+            info => {
+                return Err(DeserializationError::InvalidValue(format!(
+                    "additional information {} is not allowed in canonical encoding",
+                    info
+                )))
+            }
+        };
+        if argument < min_value {
+            return Err(DeserializationError::InvalidValue(format!(
+                "{} is not encoded in the shortest form",
+                argument
+            )));
+        }
+        Ok(argument)
+    }
+
+    /// Makes sure the number of items in an array or a map does not exceed the number of
+    /// remaining bytes; every item takes at least one byte.
+    fn check_num_items(&self, num_items: u64) -> Result<usize, DeserializationError> {
+        if num_items > (self.source.len() - self.pos) as u64 {
+            return Err(DeserializationError::UnexpectedEOF);
+        }
+        Ok(num_items as usize)
+    }
+
+    fn read_u8(&mut self) -> Result<u8, DeserializationError> {
+        let byte = *self
+            .source
+            .get(self.pos)
+            .ok_or(DeserializationError::UnexpectedEOF)?;
+        self.pos += 1;
+        Ok(byte)
+    }
+
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N], DeserializationError> {
+        let mut result = [0; N];
+        if N > self.source.len() - self.pos {
+            return Err(DeserializationError::UnexpectedEOF);
+        }
+        result.copy_from_slice(&self.source[self.pos..self.pos + N]);
+        self.pos += N;
+        Ok(result)
+    }
+}
@@ -8,6 +8,13 @@
 //! When `std` feature is enabled, this module exports collections from the Rust standard library.
 //! When `alloc` feature is enabled, same collected are provided without relying on the Rust
 //! standard library.
+//!
+//! This module also provides [BoundedVec], a vector with a fixed maximum capacity. When
+//! `arrayvec` feature is enabled, bounded vectors store their values inline and never allocate
+//! memory.
+
+mod bounded;
+pub use bounded::{BoundedVec, CapacityError};
 
 #[cfg(not(feature = "std"))]
 pub use alloc::collections::{BTreeMap, BTreeSet};
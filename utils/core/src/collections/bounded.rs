@@ -0,0 +1,186 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use core::{
+    fmt,
+    ops::{Deref, DerefMut},
+};
+
+#[cfg(not(feature = "arrayvec"))]
+use super::Vec;
+
+// BOUNDED VECTOR
+// ================================================================================================
+
+/// A vector which can hold at most `N` values.
+///
+/// When `arrayvec` feature is enabled, the values are stored inline (in an
+/// [ArrayVec](arrayvec::ArrayVec)), and thus, a bounded vector never allocates memory; this makes
+/// it suitable for targets without a memory allocator. Otherwise, the values are stored in a
+/// [Vec] which grows as values are added, and the capacity limit is enforced in the same way.
+/// The interface of the vector is identical for both backends, and thus, the backend can be
+/// selected without changing the code which uses the vector.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BoundedVec<T, const N: usize> {
+    #[cfg(feature = "arrayvec")]
+    values: arrayvec::ArrayVec<T, N>,
+    #[cfg(not(feature = "arrayvec"))]
+    values: Vec<T>,
+}
+
+impl<T, const N: usize> BoundedVec<T, N> {
+    /// The maximum number of values in this vector.
+    pub const CAPACITY: usize = N;
+
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new empty vector.
+    pub fn new() -> Self {
+        BoundedVec {
+            #[cfg(feature = "arrayvec")]
+            values: arrayvec::ArrayVec::new(),
+            #[cfg(not(feature = "arrayvec"))]
+            values: Vec::new(),
+        }
+    }
+
+    /// Returns a new vector containing the values yielded by the specified iterator.
+    ///
+    /// # Errors
+    /// Returns an error if the iterator yields more than `N` values.
+    pub fn try_from_iter<I: IntoIterator<Item = T>>(iter: I) -> Result<Self, CapacityError> {
+        let mut result = Self::new();
+        for value in iter {
+            result.try_push(value)?;
+        }
+        Ok(result)
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the maximum number of values in this vector.
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns true if no more values can be added to this vector.
+    pub fn is_full(&self) -> bool {
+        self.values.len() == N
+    }
+
+    /// Returns the values of this vector as a slice.
+    pub fn as_slice(&self) -> &[T] {
+        &self.values
+    }
+
+    /// Returns the values of this vector as a mutable slice.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.values
+    }
+
+    // DATA MUTATORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Appends the specified value to the end of this vector.
+    ///
+    /// # Errors
+    /// Returns an error if this vector already contains `N` values; in such a case, the vector
+    /// is not modified.
+    pub fn try_push(&mut self, value: T) -> Result<(), CapacityError> {
+        if self.is_full() {
+            return Err(CapacityError { capacity: N });
+        }
+        self.values.push(value);
+        Ok(())
+    }
+
+    /// Removes the last value from this vector and returns it, or returns None if this vector
+    /// is empty.
+    pub fn pop(&mut self) -> Option<T> {
+        self.values.pop()
+    }
+
+    /// Removes the value at the specified `index` from this vector and returns it; all values
+    /// after the removed value are shifted to the left.
+    ///
+    /// # Panics
+    /// Panics if `index` is out of bounds.
+    pub fn remove(&mut self, index: usize) -> T {
+        self.values.remove(index)
+    }
+
+    /// Removes all values from this vector.
+    pub fn clear(&mut self) {
+        self.values.clear();
+    }
+}
+
+impl<T, const N: usize> Default for BoundedVec<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> Deref for BoundedVec<T, N> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T, const N: usize> DerefMut for BoundedVec<T, N> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+impl<T, const N: usize> IntoIterator for BoundedVec<T, N> {
+    type Item = T;
+    #[cfg(feature = "arrayvec")]
+    type IntoIter = arrayvec::IntoIter<T, N>;
+    #[cfg(not(feature = "arrayvec"))]
+    type IntoIter = super::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.values.into_iter()
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a BoundedVec<T, N> {
+    type Item = &'a T;
+    type IntoIter = core::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.values.iter()
+    }
+}
+
+// CAPACITY ERROR
+// ================================================================================================
+
+/// An error returned when a value is added to a [BoundedVec] which is already full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CapacityError {
+    capacity: usize,
+}
+
+impl CapacityError {
+    /// Returns the capacity of the vector which could not hold the value.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+}
+
+impl fmt::Display for CapacityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "vector cannot hold more than {} values", self.capacity)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CapacityError {}
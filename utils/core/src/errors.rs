@@ -6,6 +6,9 @@
 use crate::string::String;
 use core::fmt;
 
+#[cfg(not(feature = "std"))]
+use alloc::boxed::Box;
+
 // DESERIALIZATION ERROR
 // ================================================================================================
 
@@ -18,8 +21,32 @@ pub enum DeserializationError {
     UnexpectedEOF,
     /// Deserialization has finished but not all bytes have been consumed.
     UnconsumedBytes,
+    /// The input was serialized using a format version which is not supported; the version and
+    /// the latest supported version are specified.
+    UnsupportedVersion(u8, u8),
+    /// A value read from the input exceeds a limit imposed by the caller; the name of the limit,
+    /// the value, and the limit are specified.
+    LimitExceeded(&'static str, usize, usize),
     /// An unknown error has occurred.
     UnknownError(String),
+    /// An error occurred while reading a field of a value; the name of the field, the byte offset
+    /// in the input at which the field starts, and the error are specified.
+    ///
+    /// This error is returned only by readers which track byte offsets (e.g.,
+    /// [SliceReader](crate::SliceReader)); see
+    /// [ByteReader::read_in_context()](crate::ByteReader::read_in_context) for more info.
+    Context(&'static str, usize, Box<DeserializationError>),
+}
+
+impl DeserializationError {
+    /// Returns the error wrapped into this error by [Context](Self::Context) variants; if this
+    /// error is not a [Context](Self::Context) error, `self` is returned.
+    pub fn root_cause(&self) -> &Self {
+        match self {
+            Self::Context(_, _, err) => err.root_cause(),
+            err => err,
+        }
+    }
 }
 
 impl fmt::Display for DeserializationError {
@@ -35,9 +62,21 @@ impl fmt::Display for DeserializationError {
             Self::UnconsumedBytes => {
                 write!(f, "not all bytes were consumed")
             }
+            Self::UnsupportedVersion(version, latest) => {
+                write!(f, "format version {} is not supported; the latest supported version is {}", version, latest)
+            }
+            Self::LimitExceeded(name, value, limit) => {
+                write!(f, "{} cannot exceed {}, but was {}", name, limit, value)
+            }
             Self::UnknownError(err_msg) => {
                 write!(f, "unknown error: {}", err_msg)
             }
+            Self::Context(field, offset, err) => {
+                write!(f, "failed to read {} at byte offset {}: {}", field, offset, err)
+            }
         }
     }
 }
+
+#[cfg(feature = "std")]
+impl std::error::Error for DeserializationError {}
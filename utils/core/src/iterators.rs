@@ -5,8 +5,9 @@
 
 //! Components needed for parallel iterators.
 //!
-//! When `concurrent` feature is enabled, this module re-exports `rayon::prelude`. Otherwise,
-//! this is an empty module.
+//! When `concurrent` feature is enabled, this module re-exports `rayon::prelude`. In all cases,
+//! this module contains [chunks_mut_indexed_par()] function, which processes a slice in
+//! multiple threads when `concurrent` feature is enabled.
 
 #[cfg(feature = "concurrent")]
 pub use rayon::{current_num_threads as rayon_num_threads, prelude::*};
@@ -78,6 +79,10 @@ macro_rules! iter_mut {
 /// batch in a separate thread; otherwise, the entire slice is processed as a single batch
 /// in one thread. Optionally, `min_batch_size` can be used to specify the minimum size of
 /// the resulting batches.
+///
+/// The `concurrent` feature is checked for the crate in which this macro is invoked; crates which
+/// do not define the feature should use
+/// [chunks_mut_indexed_par()](crate::iterators::chunks_mut_indexed_par) function instead.
 #[macro_export]
 macro_rules! batch_iter_mut {
     ($e: expr, $c: expr) => {
@@ -115,3 +120,60 @@ macro_rules! batch_iter_mut {
         $c($e, 0);
     };
 }
+
+// CHUNKED PROCESSING
+// ================================================================================================
+
+/// Applies the procedure `f` to the provided slice either in a single thread or in multiple
+/// threads based on whether `concurrent` feature of this crate is enabled.
+///
+/// When `concurrent` feature is enabled, the slice is broken into chunks of equal size (except
+/// possibly the last one) such that there is one chunk per thread, with the number of threads
+/// rounded up to the next power of two, and `f`
+/// is applied to each chunk in a separate thread; otherwise, `f` is applied to the entire slice
+/// as a single chunk. `f` receives a chunk together with the index of the first element of the
+/// chunk in the slice. If the chunks would be smaller than `min_chunk_size`, the entire slice is
+/// processed as a single chunk.
+///
+/// This applies the same strategy as [batch_iter_mut!](crate::batch_iter_mut) macro used by the
+/// prover. However, the macro checks whether `concurrent` feature is enabled for the crate in
+/// which it is invoked, while this function checks the feature of this crate; thus, the function
+/// can be used by crates (e.g., external AIR or trace builders) which do not define the feature
+/// themselves.
+///
+/// # Example
+/// ```
+/// # use winter_utils::iterators::chunks_mut_indexed_par;
+/// let mut values = vec![0_u64; 1024];
+/// chunks_mut_indexed_par(&mut values, 64, |chunk: &mut [u64], offset: usize| {
+///     for (i, value) in chunk.iter_mut().enumerate() {
+///         *value = (offset + i) as u64 * 2;
+///     }
+/// });
+///
+/// assert!(values.iter().enumerate().all(|(i, &value)| value == i as u64 * 2));
+/// ```
+pub fn chunks_mut_indexed_par<T, F>(values: &mut [T], min_chunk_size: usize, f: F)
+where
+    T: Send,
+    F: Fn(&mut [T], usize) + Sync,
+{
+    #[cfg(feature = "concurrent")]
+    {
+        let chunk_size = values.len() / rayon_num_threads().next_power_of_two();
+        if chunk_size < min_chunk_size.max(1) {
+            f(values, 0);
+        } else {
+            values
+                .par_chunks_mut(chunk_size)
+                .enumerate()
+                .for_each(|(i, chunk)| f(chunk, i * chunk_size));
+        }
+    }
+
+    #[cfg(not(feature = "concurrent"))]
+    {
+        let _ = min_chunk_size;
+        f(values, 0);
+    }
+}
@@ -12,7 +12,10 @@
 #[macro_use]
 extern crate alloc;
 
-use core::{convert::TryInto, mem, slice};
+use core::{convert::TryInto, mem, mem::MaybeUninit, slice};
+
+#[cfg(not(feature = "std"))]
+use alloc::boxed::Box;
 
 pub mod collections;
 use collections::Vec;
@@ -22,6 +25,10 @@ use string::ToString;
 
 pub mod iterators;
 
+pub mod cbor;
+
+pub mod table;
+
 mod errors;
 pub use errors::DeserializationError;
 
@@ -34,6 +41,15 @@ use iterators::*;
 #[cfg(feature = "concurrent")]
 pub use rayon;
 
+#[cfg(feature = "serde")]
+pub mod serde_adapter;
+
+#[cfg(feature = "serde")]
+pub use serde;
+
+#[cfg(feature = "derive")]
+pub use derive::{Deserializable, Serializable};
+
 // SERIALIZABLE
 // ================================================================================================
 
@@ -76,42 +92,65 @@ impl Serializable for () {
     fn write_into<W: ByteWriter>(&self, _target: &mut W) {}
 }
 
-impl<T: Serializable> Serializable for Vec<T> {
+impl Serializable for u8 {
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
-        T::write_batch_into(self, target);
+        target.write_u8(*self);
+    }
+
+    fn write_batch_into<W: ByteWriter>(source: &[Self], target: &mut W) {
+        target.write_u8_slice(source);
     }
 }
 
-impl<T: Serializable> Serializable for &Vec<T> {
+impl Serializable for u16 {
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
-        T::write_batch_into(self, target);
+        target.write_u16(*self);
     }
 }
 
-impl<T: Serializable, const N: usize> Serializable for Vec<[T; N]> {
+impl Serializable for u32 {
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
-        let source = flatten_slice_elements(self);
-        T::write_batch_into(source, target);
+        target.write_u32(*self);
     }
 }
 
-impl<T: Serializable, const N: usize> Serializable for &Vec<[T; N]> {
+impl Serializable for u64 {
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
-        let source = flatten_slice_elements(self);
-        T::write_batch_into(source, target);
+        target.write_u64(*self);
     }
 }
 
-impl<T: Serializable> Serializable for &[T] {
+impl Serializable for u128 {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8_slice(&self.to_le_bytes());
+    }
+}
+
+impl<T: Serializable, const N: usize> Serializable for [T; N] {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        T::write_batch_into(self, target);
+    }
+
+    fn write_batch_into<W: ByteWriter>(source: &[Self], target: &mut W) {
+        T::write_batch_into(flatten_slice_elements(source), target);
+    }
+}
+
+impl<T: Serializable> Serializable for Vec<T> {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        T::write_batch_into(self, target);
+    }
+}
+
+impl<T: Serializable> Serializable for &Vec<T> {
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
         T::write_batch_into(self, target);
     }
 }
 
-impl<T: Serializable, const N: usize> Serializable for &[[T; N]] {
+impl<T: Serializable> Serializable for &[T] {
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
-        let source = flatten_slice_elements(self);
-        T::write_batch_into(source, target);
+        T::write_batch_into(self, target);
     }
 }
 
@@ -160,6 +199,59 @@ pub trait Deserializable: Sized {
     }
 }
 
+impl Deserializable for () {
+    fn read_from<R: ByteReader>(_source: &mut R) -> Result<Self, DeserializationError> {
+        Ok(())
+    }
+}
+
+impl Deserializable for u8 {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        source.read_u8()
+    }
+
+    fn read_batch_from<R: ByteReader>(
+        source: &mut R,
+        num_elements: usize,
+    ) -> Result<Vec<Self>, DeserializationError> {
+        source.read_u8_vec(num_elements)
+    }
+}
+
+impl Deserializable for u16 {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        source.read_u16()
+    }
+}
+
+impl Deserializable for u32 {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        source.read_u32()
+    }
+}
+
+impl Deserializable for u64 {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        source.read_u64()
+    }
+}
+
+impl Deserializable for u128 {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        source.read_u128()
+    }
+}
+
+impl<T: Deserializable, const N: usize> Deserializable for [T; N] {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let elements = T::read_batch_from(source, N)?;
+        // the conversion cannot fail because exactly N elements were read
+        Ok(elements
+            .try_into()
+            .unwrap_or_else(|_| unreachable!("number of elements must be {}", N)))
+    }
+}
+
 // BYTE READER
 // ================================================================================================
 
@@ -207,6 +299,40 @@ pub trait ByteReader {
 
     /// Returns true if there are more bytes left to be read from `self`.
     fn has_more_bytes(&self) -> bool;
+
+    /// Advances `self` by the specified number of bytes without returning them.
+    ///
+    /// The default implementation reads the bytes via [read_u8_vec()](Self::read_u8_vec) and
+    /// discards them; readers which can skip bytes without copying them should override this
+    /// method.
+    ///
+    /// # Errors
+    /// Returns a [DeserializationError] if the specified number of bytes could not be read from
+    /// `self`.
+    fn skip_bytes(&mut self, len: usize) -> Result<(), DeserializationError> {
+        self.read_u8_vec(len).map(|_| ())
+    }
+
+    /// Reads a field with the specified name from `self` using the `read` function, and returns
+    /// the result.
+    ///
+    /// Readers which track byte offsets (e.g., [SliceReader]) wrap an error returned by `read`
+    /// into [DeserializationError::Context] error specifying the name of the field and the
+    /// offset at which the field starts; fields can be nested, in which case the errors are
+    /// nested as well. The default implementation returns the result of `read` as is.
+    ///
+    /// # Errors
+    /// Returns an error if `read` returns an error.
+    fn read_in_context<T, F>(
+        &mut self,
+        _field: &'static str,
+        read: F,
+    ) -> Result<T, DeserializationError>
+    where
+        F: FnOnce(&mut Self) -> Result<T, DeserializationError>,
+    {
+        read(self)
+    }
 }
 
 // SLICE READER
@@ -223,6 +349,11 @@ impl<'a> SliceReader<'a> {
     pub fn new(source: &'a [u8]) -> Self {
         SliceReader { source, pos: 0 }
     }
+
+    /// Returns the number of bytes read from the underlying slice so far.
+    pub fn position(&self) -> usize {
+        self.pos
+    }
 }
 
 impl<'a> ByteReader for SliceReader<'a> {
@@ -301,7 +432,7 @@ impl<'a> ByteReader for SliceReader<'a> {
     }
 
     fn read_u8_vec(&mut self, len: usize) -> Result<Vec<u8>, DeserializationError> {
-        let end_pos = self.pos + len as usize;
+        let end_pos = self.pos + len;
         if end_pos > self.source.len() {
             return Err(DeserializationError::UnexpectedEOF);
         }
@@ -325,6 +456,302 @@ impl<'a> ByteReader for SliceReader<'a> {
     fn has_more_bytes(&self) -> bool {
         self.pos < self.source.len()
     }
+
+    fn skip_bytes(&mut self, len: usize) -> Result<(), DeserializationError> {
+        if len > self.source.len() - self.pos {
+            return Err(DeserializationError::UnexpectedEOF);
+        }
+        self.pos += len;
+        Ok(())
+    }
+
+    fn read_in_context<T, F>(
+        &mut self,
+        field: &'static str,
+        read: F,
+    ) -> Result<T, DeserializationError>
+    where
+        F: FnOnce(&mut Self) -> Result<T, DeserializationError>,
+    {
+        let offset = self.pos;
+        read(self).map_err(|err| DeserializationError::Context(field, offset, Box::new(err)))
+    }
+}
+
+// READ ADAPTER
+// ================================================================================================
+
+/// Number of bytes requested from the underlying source by [ReadAdapter] at a time.
+#[cfg(feature = "std")]
+const READ_ADAPTER_CHUNK_SIZE: usize = 4096;
+
+/// Implements [ByteReader] trait for a source implementing `std::io::Read` trait.
+///
+/// Bytes are requested from the source in chunks of up to 4 KB, and bytes which have already
+/// been read by the adapter are discarded when the next chunk is requested. Thus, the adapter
+/// buffers at most one value being read plus one chunk, which makes it possible to deserialize
+/// large structures (e.g., proofs) incrementally as they are received. To be able to tell whether
+/// the source is exhausted, the adapter keeps at least one unread byte buffered unless the
+/// source has reached its end.
+///
+/// An I/O error returned by the source is reported via [DeserializationError::UnknownError] by
+/// the read which required the missing bytes.
+#[cfg(feature = "std")]
+pub struct ReadAdapter<R: std::io::Read> {
+    source: R,
+    buffer: Vec<u8>,
+    pos: usize,
+    error: Option<String>,
+}
+
+#[cfg(feature = "std")]
+impl<R: std::io::Read> ReadAdapter<R> {
+    /// Creates a new read adapter for the specified source.
+    pub fn new(source: R) -> Self {
+        let mut adapter = ReadAdapter {
+            source,
+            buffer: Vec::new(),
+            pos: 0,
+            error: None,
+        };
+        adapter.prefetch();
+        adapter
+    }
+
+    /// Returns an array of `N` bytes read from the source.
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N], DeserializationError> {
+        self.ensure(N)?;
+        let mut result = [0u8; N];
+        result.copy_from_slice(&self.buffer[self.pos..self.pos + N]);
+        self.pos += N;
+        self.prefetch();
+        Ok(result)
+    }
+
+    /// Reads chunks from the source until at least `n` unread bytes are buffered.
+    fn ensure(&mut self, n: usize) -> Result<(), DeserializationError> {
+        while self.buffer.len() - self.pos < n {
+            if let Some(err) = self.error.take() {
+                return Err(DeserializationError::UnknownError(err));
+            }
+            match self.fill() {
+                Ok(0) => return Err(DeserializationError::UnexpectedEOF),
+                Ok(_) => (),
+                Err(err) => return Err(DeserializationError::UnknownError(err)),
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads the next chunk from the source if all buffered bytes have been read; an I/O error
+    /// is retained until the next read.
+    fn prefetch(&mut self) {
+        if self.pos == self.buffer.len() && self.error.is_none() {
+            if let Err(err) = self.fill() {
+                self.error = Some(err);
+            }
+        }
+    }
+
+    /// Discards bytes which have already been read, and appends the next chunk read from the
+    /// source to the buffer; returns the number of bytes in the chunk.
+    fn fill(&mut self) -> Result<usize, String> {
+        self.buffer.drain(..self.pos);
+        self.pos = 0;
+
+        let mut chunk = [0u8; READ_ADAPTER_CHUNK_SIZE];
+        loop {
+            match self.source.read(&mut chunk) {
+                Ok(n) => {
+                    self.buffer.extend_from_slice(&chunk[..n]);
+                    return Ok(n);
+                }
+                Err(err) if err.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err.to_string()),
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R: std::io::Read> ByteReader for ReadAdapter<R> {
+    fn read_u8(&mut self) -> Result<u8, DeserializationError> {
+        Ok(self.read_array::<1>()?[0])
+    }
+
+    fn read_u16(&mut self) -> Result<u16, DeserializationError> {
+        Ok(u16::from_le_bytes(self.read_array()?))
+    }
+
+    fn read_u32(&mut self) -> Result<u32, DeserializationError> {
+        Ok(u32::from_le_bytes(self.read_array()?))
+    }
+
+    fn read_u64(&mut self) -> Result<u64, DeserializationError> {
+        Ok(u64::from_le_bytes(self.read_array()?))
+    }
+
+    fn read_u128(&mut self) -> Result<u128, DeserializationError> {
+        Ok(u128::from_le_bytes(self.read_array()?))
+    }
+
+    fn read_u8_vec(&mut self, len: usize) -> Result<Vec<u8>, DeserializationError> {
+        self.ensure(len)?;
+        let result = self.buffer[self.pos..self.pos + len].to_vec();
+        self.pos += len;
+        self.prefetch();
+        Ok(result)
+    }
+
+    fn read_u8_array<const N: usize>(&mut self) -> Result<[u8; N], DeserializationError> {
+        self.read_array()
+    }
+
+    fn has_more_bytes(&self) -> bool {
+        self.pos < self.buffer.len()
+    }
+}
+
+// BUF READ ADAPTER
+// ================================================================================================
+
+/// Implements [ByteReader] trait for a source implementing `std::io::BufRead` trait.
+///
+/// Unlike [ReadAdapter], this adapter does not maintain a buffer of its own: values are copied
+/// directly from the internal buffer of the source, and only the bytes which make up the values
+/// read so far are consumed from the source. Thus, once a value has been read, the source can
+/// be retrieved via [into_inner()](Self::into_inner) and used to read whatever follows the
+/// value. Byte vectors are read incrementally; thus, memory for a vector is allocated only as
+/// its bytes are received.
+///
+/// To be able to tell whether the source is exhausted, the adapter fills the buffer of the
+/// source after every read. An I/O error returned by the source is reported via
+/// [DeserializationError::UnknownError] by the read which required the missing bytes.
+#[cfg(feature = "std")]
+pub struct BufReadAdapter<R: std::io::BufRead> {
+    source: R,
+    has_more_bytes: bool,
+    error: Option<String>,
+}
+
+#[cfg(feature = "std")]
+impl<R: std::io::BufRead> BufReadAdapter<R> {
+    /// Creates a new read adapter for the specified source.
+    pub fn new(source: R) -> Self {
+        let mut adapter = BufReadAdapter {
+            source,
+            has_more_bytes: false,
+            error: None,
+        };
+        adapter.peek();
+        adapter
+    }
+
+    /// Returns the underlying source; bytes which have not been read by this adapter remain in
+    /// the source.
+    pub fn into_inner(self) -> R {
+        self.source
+    }
+
+    /// Returns an array of `N` bytes read from the source.
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N], DeserializationError> {
+        let mut result = [0u8; N];
+        let mut num_read = 0;
+        while num_read < N {
+            let n = self.read_available(|bytes| {
+                let n = bytes.len().min(N - num_read);
+                result[num_read..num_read + n].copy_from_slice(&bytes[..n]);
+                n
+            })?;
+            num_read += n;
+        }
+        self.peek();
+        Ok(result)
+    }
+
+    /// Passes the bytes buffered by the source to `consume_fn`, and consumes as many of them
+    /// from the source as `consume_fn` returns; returns an error if no bytes are available.
+    fn read_available<F: FnOnce(&[u8]) -> usize>(
+        &mut self,
+        consume_fn: F,
+    ) -> Result<usize, DeserializationError> {
+        if let Some(err) = self.error.take() {
+            return Err(DeserializationError::UnknownError(err));
+        }
+        let n = loop {
+            match self.source.fill_buf() {
+                Ok([]) => return Err(DeserializationError::UnexpectedEOF),
+                Ok(bytes) => break consume_fn(bytes),
+                Err(err) if err.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(DeserializationError::UnknownError(err.to_string())),
+            }
+        };
+        self.source.consume(n);
+        Ok(n)
+    }
+
+    /// Fills the buffer of the source to find out whether more bytes are available; an I/O
+    /// error is retained until the next read.
+    fn peek(&mut self) {
+        loop {
+            match self.source.fill_buf() {
+                Ok(bytes) => {
+                    self.has_more_bytes = !bytes.is_empty();
+                    return;
+                }
+                Err(err) if err.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(err) => {
+                    self.has_more_bytes = false;
+                    self.error = Some(err.to_string());
+                    return;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R: std::io::BufRead> ByteReader for BufReadAdapter<R> {
+    fn read_u8(&mut self) -> Result<u8, DeserializationError> {
+        Ok(self.read_array::<1>()?[0])
+    }
+
+    fn read_u16(&mut self) -> Result<u16, DeserializationError> {
+        Ok(u16::from_le_bytes(self.read_array()?))
+    }
+
+    fn read_u32(&mut self) -> Result<u32, DeserializationError> {
+        Ok(u32::from_le_bytes(self.read_array()?))
+    }
+
+    fn read_u64(&mut self) -> Result<u64, DeserializationError> {
+        Ok(u64::from_le_bytes(self.read_array()?))
+    }
+
+    fn read_u128(&mut self) -> Result<u128, DeserializationError> {
+        Ok(u128::from_le_bytes(self.read_array()?))
+    }
+
+    fn read_u8_vec(&mut self, len: usize) -> Result<Vec<u8>, DeserializationError> {
+        let mut result = Vec::new();
+        while result.len() < len {
+            self.read_available(|bytes| {
+                let n = bytes.len().min(len - result.len());
+                result.extend_from_slice(&bytes[..n]);
+                n
+            })?;
+        }
+        self.peek();
+        Ok(result)
+    }
+
+    fn read_u8_array<const N: usize>(&mut self) -> Result<[u8; N], DeserializationError> {
+        self.read_array()
+    }
+
+    fn has_more_bytes(&self) -> bool {
+        self.has_more_bytes
+    }
 }
 
 // BYTE WRITER
@@ -393,10 +820,74 @@ impl ByteWriter for Vec<u8> {
     }
 }
 
+// WRITE ADAPTER
+// ================================================================================================
+
+/// Implements [ByteWriter] trait for a target implementing `std::io::Write` trait.
+///
+/// This makes it possible to serialize large structures (e.g., proofs) directly into files or
+/// sockets without buffering the serialized structure in memory first. Bytes are passed to the
+/// target as they are written; thus, for targets which are expensive to write to, the target
+/// should be wrapped into `std::io::BufWriter`.
+///
+/// Unlike other writers, this adapter does not panic if bytes could not be written into the
+/// target. Instead, the first I/O error is retained, all subsequent writes are ignored, and the
+/// error is returned by [finish()](Self::finish).
+#[cfg(feature = "std")]
+pub struct WriteAdapter<W: std::io::Write> {
+    target: W,
+    error: Option<std::io::Error>,
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write> WriteAdapter<W> {
+    /// Creates a new write adapter for the specified target.
+    pub fn new(target: W) -> Self {
+        WriteAdapter {
+            target,
+            error: None,
+        }
+    }
+
+    /// Flushes the target and returns it.
+    ///
+    /// # Errors
+    /// Returns an error if any of the writes into the target failed, or if the target could not
+    /// be flushed.
+    pub fn finish(mut self) -> std::io::Result<W> {
+        if let Some(err) = self.error.take() {
+            return Err(err);
+        }
+        self.target.flush()?;
+        Ok(self.target)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write> ByteWriter for WriteAdapter<W> {
+    fn write_u8(&mut self, value: u8) {
+        self.write_u8_slice(&[value]);
+    }
+
+    fn write_u8_slice(&mut self, values: &[u8]) {
+        if self.error.is_none() {
+            if let Err(err) = self.target.write_all(values) {
+                self.error = Some(err);
+            }
+        }
+    }
+}
+
 // AS BYTES
 // ================================================================================================
 
 /// Defines a zero-copy representation of `Self` as a sequence of bytes.
+///
+/// Since the representation re-interprets the memory of `self`, multi-byte values are written in
+/// the native byte order of the target platform (little-endian on all platforms supported by
+/// Winterfell). Thus, bytes produced on one platform can be converted back into values via
+/// [FromBytes] on platforms with the same byte order only; for a portable representation, use
+/// [Serializable] instead.
 pub trait AsBytes {
     /// Returns a byte representation of `self`.
     ///
@@ -423,6 +914,26 @@ impl<const N: usize> AsBytes for [[u8; N]] {
     }
 }
 
+// FROM BYTES
+// ================================================================================================
+
+/// Defines conversion of byte representations produced via [AsBytes] back into values of `Self`.
+///
+/// Unlike re-interpreting bytes as values in place, the conversion copies the values and
+/// validates every one of them. Thus, it is safe to use with arbitrary bytes (e.g., bytes read
+/// back from disk), and places no requirements on the memory alignment of the bytes. The bytes
+/// are expected to be in the native byte order of the target platform, as described in
+/// [AsBytes].
+pub trait FromBytes: Sized {
+    /// Returns values read from the specified `bytes`, which must contain byte representations of
+    /// the values (as produced by [AsBytes::as_bytes()]) one after another.
+    ///
+    /// # Errors
+    /// Returns an error if the length of `bytes` does not divide into a whole number of values,
+    /// or if any of the values is not valid.
+    fn read_slice_from_bytes(bytes: &[u8]) -> Result<Vec<Self>, DeserializationError>;
+}
+
 // VECTOR FUNCTIONS
 // ================================================================================================
 
@@ -433,12 +944,63 @@ impl<const N: usize> AsBytes for [[u8; N]] {
 ///
 /// # Safety
 /// Using values from the returned vector before initializing them will lead to undefined behavior.
+#[allow(clippy::uninit_vec)]
 pub unsafe fn uninit_vector<T>(length: usize) -> Vec<T> {
     let mut vector = Vec::with_capacity(length);
     vector.set_len(length);
     vector
 }
 
+/// Returns a vector of the specified length with uninitialized values.
+///
+/// Unlike [uninit_vector()], this function is safe, as values of the returned vector are
+/// wrapped into [MaybeUninit] and cannot be read before they are written. Once all values
+/// have been written, the vector can be converted into a vector of values without copying via
+/// [assume_init_buffer()].
+pub fn uninit_buffer<T>(length: usize) -> Vec<MaybeUninit<T>> {
+    let mut buffer = Vec::with_capacity(length);
+    // SAFETY: uninitialized memory is a valid value of MaybeUninit
+    unsafe { buffer.set_len(length) };
+    buffer
+}
+
+/// Converts a vector of values into a vector of [MaybeUninit] values without copying; this is
+/// useful for reusing the memory of a vector whose values are no longer needed.
+///
+/// Values of the vector are not dropped.
+pub fn into_uninit_buffer<T>(vector: Vec<T>) -> Vec<MaybeUninit<T>> {
+    let mut vector = mem::ManuallyDrop::new(vector);
+    // SAFETY: MaybeUninit<T> has the same layout as T
+    unsafe {
+        Vec::from_raw_parts(
+            vector.as_mut_ptr() as *mut MaybeUninit<T>,
+            vector.len(),
+            vector.capacity(),
+        )
+    }
+}
+
+/// Converts a vector of [MaybeUninit] values into a vector of values without copying.
+///
+/// # Safety
+/// All values in the `buffer` must have been initialized.
+pub unsafe fn assume_init_buffer<T>(buffer: Vec<MaybeUninit<T>>) -> Vec<T> {
+    let mut buffer = mem::ManuallyDrop::new(buffer);
+    Vec::from_raw_parts(
+        buffer.as_mut_ptr() as *mut T,
+        buffer.len(),
+        buffer.capacity(),
+    )
+}
+
+/// Converts a slice of [MaybeUninit] values into a slice of values.
+///
+/// # Safety
+/// All values in the `buffer` must have been initialized.
+pub unsafe fn assume_init_slice<T>(buffer: &mut [MaybeUninit<T>]) -> &mut [T] {
+    slice::from_raw_parts_mut(buffer.as_mut_ptr() as *mut T, buffer.len())
+}
+
 // GROUPING / UN-GROUPING FUNCTIONS
 // ================================================================================================
 
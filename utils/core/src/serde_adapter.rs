@@ -0,0 +1,152 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Functions for implementing serde `Serialize` and `Deserialize` traits via [Serializable] and
+//! [Deserializable] traits.
+//!
+//! Values are encoded with serde as their binary serialization: human-readable formats (e.g.,
+//! JSON) receive the bytes as a hex string, while all other formats (e.g., CBOR) receive them as
+//! a byte string. Thus, values embedded into serde documents are validated in exactly the same
+//! way as values read from bytes.
+
+use crate::{
+    collections::Vec, string::String, ByteReader, Deserializable, DeserializationError,
+    Serializable, SliceReader,
+};
+use core::fmt;
+use serde::{
+    de::{Error, SeqAccess, Visitor},
+    Deserializer, Serializer,
+};
+
+// SERIALIZATION
+// ================================================================================================
+
+/// Serializes the specified `value` into the `serializer` as bytes of its binary serialization.
+pub fn serialize<T: Serializable, S: Serializer>(
+    value: &T,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serialize_bytes(&value.to_bytes(), serializer)
+}
+
+/// Serializes the specified `bytes` into the `serializer`.
+///
+/// For human-readable formats, the bytes are serialized as a lowercase hex string; for all other
+/// formats, the bytes are serialized as a byte string.
+pub fn serialize_bytes<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+    if serializer.is_human_readable() {
+        serializer.serialize_str(&to_hex(bytes))
+    } else {
+        serializer.serialize_bytes(bytes)
+    }
+}
+
+// DESERIALIZATION
+// ================================================================================================
+
+/// Reads bytes from the `deserializer` and returns a value deserialized from them.
+///
+/// # Errors
+/// Returns an error if bytes could not be read from the `deserializer`, if a valid value could
+/// not be read from the bytes, or if not all bytes were consumed by the value.
+pub fn deserialize<'de, T: Deserializable, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<T, D::Error> {
+    let bytes = deserialize_bytes(deserializer)?;
+    let mut source = SliceReader::new(&bytes);
+    let value = T::read_from(&mut source).map_err(D::Error::custom)?;
+    if source.has_more_bytes() {
+        return Err(D::Error::custom(DeserializationError::UnconsumedBytes));
+    }
+    Ok(value)
+}
+
+/// Reads bytes serialized via [serialize_bytes()] from the `deserializer`.
+///
+/// For human-readable formats, bytes are expected to be a hex string, but a sequence of bytes is
+/// also accepted; for all other formats, bytes are expected to be a byte string.
+///
+/// # Errors
+/// Returns an error if the `deserializer` does not contain a valid hex string or byte string.
+pub fn deserialize_bytes<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_any(BytesVisitor)
+    } else {
+        deserializer.deserialize_byte_buf(BytesVisitor)
+    }
+}
+
+// BYTES VISITOR
+// ================================================================================================
+
+struct BytesVisitor;
+
+impl<'de> Visitor<'de> for BytesVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a hex string or a byte string")
+    }
+
+    fn visit_str<E: Error>(self, value: &str) -> Result<Self::Value, E> {
+        from_hex(value).map_err(E::custom)
+    }
+
+    fn visit_bytes<E: Error>(self, value: &[u8]) -> Result<Self::Value, E> {
+        Ok(value.to_vec())
+    }
+
+    fn visit_byte_buf<E: Error>(self, value: Vec<u8>) -> Result<Self::Value, E> {
+        Ok(value)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut result = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(byte) = seq.next_element::<u8>()? {
+            result.push(byte);
+        }
+        Ok(result)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+fn to_hex(bytes: &[u8]) -> String {
+    let mut result = String::with_capacity(bytes.len() * 2);
+    for &byte in bytes {
+        result.push(HEX_DIGITS[(byte >> 4) as usize] as char);
+        result.push(HEX_DIGITS[(byte & 0xf) as usize] as char);
+    }
+    result
+}
+
+fn from_hex(value: &str) -> Result<Vec<u8>, DeserializationError> {
+    let value = value.as_bytes();
+    if !value.len().is_multiple_of(2) {
+        return Err(DeserializationError::InvalidValue(
+            "hex string must have an even number of digits".into(),
+        ));
+    }
+    value
+        .chunks(2)
+        .map(|pair| Ok((hex_digit(pair[0])? << 4) | hex_digit(pair[1])?))
+        .collect()
+}
+
+fn hex_digit(digit: u8) -> Result<u8, DeserializationError> {
+    match digit {
+        b'0'..=b'9' => Ok(digit - b'0'),
+        b'a'..=b'f' => Ok(digit - b'a' + 10),
+        b'A'..=b'F' => Ok(digit - b'A' + 10),
+        _ => Err(DeserializationError::InvalidValue(format!(
+            "'{}' is not a valid hex digit",
+            digit as char
+        ))),
+    }
+}
@@ -0,0 +1,317 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Views of two-dimensional tables stored in flat buffers, and helpers for breaking tables into
+//! fragments which can be processed independently (e.g., in different threads).
+//!
+//! A [TableView] (or a [TableViewMut]) wraps a flat buffer holding a table of `num_rows` rows
+//! and `num_cols` columns in row-major order: the row at index `i` starts at position
+//! `i * stride` of the buffer, where `stride` is at least `num_cols`. Tables stored column by
+//! column in separately allocated columns can be broken into fragments via [fragment_columns()].
+
+use crate::collections::Vec;
+use core::ops::Range;
+
+// TABLE VIEW
+// ================================================================================================
+
+/// A read-only view of a table stored in row-major order in a flat buffer.
+#[derive(Debug)]
+pub struct TableView<'a, T> {
+    data: &'a [T],
+    num_rows: usize,
+    num_cols: usize,
+    stride: usize,
+}
+
+impl<'a, T> TableView<'a, T> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a view of a table with the specified number of rows and columns stored in the
+    /// `data` buffer, with rows starting `stride` elements apart.
+    ///
+    /// # Panics
+    /// Panics if `num_cols` is greater than `stride`, or if `data` is too short to hold the
+    /// table.
+    pub fn new(data: &'a [T], num_rows: usize, num_cols: usize, stride: usize) -> Self {
+        check_dimensions(data.len(), num_rows, num_cols, stride);
+        TableView {
+            data,
+            num_rows,
+            num_cols,
+            stride,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of rows in this table.
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    /// Returns the number of columns in this table.
+    pub fn num_cols(&self) -> usize {
+        self.num_cols
+    }
+
+    /// Returns the distance between the starts of two consecutive rows in the underlying buffer.
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    /// Returns the value at the specified `row` and `col`.
+    ///
+    /// # Panics
+    /// Panics if `row` or `col` are out of bounds.
+    pub fn get(&self, row: usize, col: usize) -> &'a T {
+        &self.row(row)[col]
+    }
+
+    /// Returns the values of the specified `row`.
+    ///
+    /// # Panics
+    /// Panics if `row` is out of bounds.
+    pub fn row(&self, row: usize) -> &'a [T] {
+        assert!(
+            row < self.num_rows,
+            "row index must be smaller than {}, but was {}",
+            self.num_rows,
+            row
+        );
+        let start = row * self.stride;
+        &self.data[start..start + self.num_cols]
+    }
+
+    /// Returns an iterator over the rows of this table.
+    pub fn rows(&self) -> impl Iterator<Item = &'a [T]> + 'a {
+        let view = *self;
+        (0..view.num_rows).map(move |row| view.row(row))
+    }
+
+    /// Returns an iterator over the values of the specified column of this table.
+    ///
+    /// # Panics
+    /// Panics if `col` is out of bounds.
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &'a T> + 'a {
+        assert!(
+            col < self.num_cols,
+            "column index must be smaller than {}, but was {}",
+            self.num_cols,
+            col
+        );
+        self.data[col..]
+            .iter()
+            .step_by(self.stride.max(1))
+            .take(self.num_rows)
+    }
+
+    /// Copies values of the specified range of columns in the specified `row` into the `target`
+    /// slice.
+    ///
+    /// # Panics
+    /// Panics if `row` or `cols` are out of bounds, or if the length of `target` is not equal
+    /// to the length of `cols`.
+    pub fn read_row_into(&self, row: usize, cols: Range<usize>, target: &mut [T])
+    where
+        T: Copy,
+    {
+        target.copy_from_slice(&self.row(row)[cols]);
+    }
+}
+
+// views are copied regardless of whether `T` can be copied, and thus, these traits cannot be
+// derived
+impl<T> Clone for TableView<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for TableView<'_, T> {}
+
+// MUTABLE TABLE VIEW
+// ================================================================================================
+
+/// A mutable view of a table stored in row-major order in a flat buffer.
+///
+/// A view can be broken into fragments covering disjoint ranges of rows via
+/// [into_fragments()](TableViewMut::into_fragments); the fragments can then be updated
+/// independently (e.g., in different threads).
+#[derive(Debug)]
+pub struct TableViewMut<'a, T> {
+    data: &'a mut [T],
+    num_rows: usize,
+    num_cols: usize,
+    stride: usize,
+}
+
+impl<'a, T> TableViewMut<'a, T> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a mutable view of a table with the specified number of rows and columns stored in
+    /// the `data` buffer, with rows starting `stride` elements apart.
+    ///
+    /// # Panics
+    /// Panics if `num_cols` is greater than `stride`, or if `data` is too short to hold the
+    /// table.
+    pub fn new(data: &'a mut [T], num_rows: usize, num_cols: usize, stride: usize) -> Self {
+        check_dimensions(data.len(), num_rows, num_cols, stride);
+        TableViewMut {
+            data,
+            num_rows,
+            num_cols,
+            stride,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of rows in this table.
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    /// Returns the number of columns in this table.
+    pub fn num_cols(&self) -> usize {
+        self.num_cols
+    }
+
+    /// Returns the distance between the starts of two consecutive rows in the underlying buffer.
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    /// Returns a read-only view of this table.
+    pub fn as_view(&self) -> TableView<'_, T> {
+        TableView {
+            data: self.data,
+            num_rows: self.num_rows,
+            num_cols: self.num_cols,
+            stride: self.stride,
+        }
+    }
+
+    /// Returns mutable values of the specified `row`.
+    ///
+    /// # Panics
+    /// Panics if `row` is out of bounds.
+    pub fn row_mut(&mut self, row: usize) -> &mut [T] {
+        assert!(
+            row < self.num_rows,
+            "row index must be smaller than {}, but was {}",
+            self.num_rows,
+            row
+        );
+        let start = row * self.stride;
+        &mut self.data[start..start + self.num_cols]
+    }
+
+    /// Returns an iterator over mutable rows of this table.
+    pub fn rows_mut(&mut self) -> impl Iterator<Item = &mut [T]> + '_ {
+        let num_cols = self.num_cols;
+        self.data
+            .chunks_mut(self.stride.max(1))
+            .take(self.num_rows)
+            .map(move |row| &mut row[..num_cols])
+    }
+
+    // FRAGMENTS
+    // --------------------------------------------------------------------------------------------
+
+    /// Breaks this table into fragments of `fragment_length` rows each; the last fragment
+    /// contains the remaining rows if the number of rows is not divisible by `fragment_length`.
+    ///
+    /// # Panics
+    /// Panics if `fragment_length` is zero.
+    pub fn into_fragments(self, fragment_length: usize) -> Vec<TableViewMut<'a, T>> {
+        assert!(
+            fragment_length > 0,
+            "fragment length must be greater than zero"
+        );
+        let (num_rows, num_cols, stride) = (self.num_rows, self.num_cols, self.stride);
+        let num_fragments = num_rows.div_ceil(fragment_length);
+        self.data
+            .chunks_mut((fragment_length * stride).max(1))
+            .take(num_fragments)
+            .enumerate()
+            .map(|(i, data)| TableViewMut {
+                data,
+                num_rows: fragment_length.min(num_rows - i * fragment_length),
+                num_cols,
+                stride,
+            })
+            .collect()
+    }
+}
+
+// COLUMN FRAGMENTS
+// ================================================================================================
+
+/// Breaks a table stored column by column into fragments of `fragment_length` rows each, and
+/// returns the fragments; every fragment contains a slice of each column.
+///
+/// The number of fragments is determined by `num_rows` (rather than by the columns), so that a
+/// table without columns is broken into the same number of fragments as any other table with
+/// the same number of rows. The last fragment contains the remaining rows if `num_rows` is not
+/// divisible by `fragment_length`.
+///
+/// # Panics
+/// Panics if `fragment_length` is zero, or if the length of any of the columns is not equal to
+/// `num_rows`.
+pub fn fragment_columns<'a, T, I>(
+    columns: I,
+    num_rows: usize,
+    fragment_length: usize,
+) -> Vec<Vec<&'a mut [T]>>
+where
+    T: 'a,
+    I: IntoIterator<Item = &'a mut [T]>,
+{
+    assert!(
+        fragment_length > 0,
+        "fragment length must be greater than zero"
+    );
+    let num_fragments = num_rows.div_ceil(fragment_length);
+    let mut result = (0..num_fragments).map(|_| Vec::new()).collect::<Vec<_>>();
+    for column in columns {
+        assert_eq!(
+            num_rows,
+            column.len(),
+            "all columns must contain {} rows, but a column contained {}",
+            num_rows,
+            column.len()
+        );
+        for (i, fragment) in column.chunks_mut(fragment_length).enumerate() {
+            result[i].push(fragment);
+        }
+    }
+    result
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Makes sure a buffer of the specified length can hold a table with the specified dimensions.
+fn check_dimensions(data_len: usize, num_rows: usize, num_cols: usize, stride: usize) {
+    assert!(
+        num_cols <= stride,
+        "number of columns cannot exceed stride {}, but was {}",
+        stride,
+        num_cols
+    );
+    let min_len = match num_rows {
+        0 => 0,
+        _ => (num_rows - 1) * stride + num_cols,
+    };
+    assert!(
+        data_len >= min_len,
+        "buffer must contain at least {} elements, but contained {}",
+        min_len,
+        data_len
+    );
+}
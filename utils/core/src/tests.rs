@@ -3,7 +3,16 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{collections::Vec, ByteReader, ByteWriter, Serializable, SliceReader};
+#[cfg(feature = "serde")]
+use super::serde_adapter;
+use super::{
+    cbor::{CborReader, CborWriter},
+    collections::{BoundedVec, Vec},
+    table::{fragment_columns, TableView, TableViewMut},
+    ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable, SliceReader,
+};
+#[cfg(feature = "std")]
+use super::{BufReadAdapter, ReadAdapter, WriteAdapter};
 
 // VECTOR UTILS TESTS
 // ================================================================================================
@@ -28,9 +37,142 @@ fn group_vector_elements() {
     }
 }
 
+#[test]
+fn uninit_buffer() {
+    let mut buffer = super::uninit_buffer::<u64>(8);
+    assert_eq!(8, buffer.len());
+    for (i, value) in buffer.iter_mut().enumerate() {
+        value.write(i as u64);
+    }
+    let ptr = buffer.as_ptr() as *const u64;
+    let vector = unsafe { super::assume_init_buffer(buffer) };
+    assert_eq!((0..8).collect::<Vec<u64>>(), vector);
+    assert_eq!(ptr, vector.as_ptr());
+
+    // memory of a vector can be reused without copying
+    let buffer = super::into_uninit_buffer(vector);
+    assert_eq!(8, buffer.len());
+    assert_eq!(ptr, buffer.as_ptr() as *const u64);
+}
+
+// BOUNDED VECTOR TESTS
+// ================================================================================================
+
+#[test]
+fn bounded_vec() {
+    let mut values = BoundedVec::<u64, 4>::new();
+    assert!(values.is_empty());
+    assert_eq!(4, values.capacity());
+
+    for i in 0..4 {
+        values.try_push(i).unwrap();
+    }
+    assert!(values.is_full());
+    assert_eq!(&[0, 1, 2, 3], values.as_slice());
+
+    // a full vector is not modified by a push
+    let err = values.try_push(4).unwrap_err();
+    assert_eq!(4, err.capacity());
+    assert_eq!(4, values.len());
+
+    assert_eq!(Some(3), values.pop());
+    values[0] = 5;
+    assert_eq!(1, values.remove(1));
+    assert_eq!(vec![5, 2], values.into_iter().collect::<Vec<_>>());
+
+    // vectors can be built from iterators which yield no more values than the capacity
+    let values = BoundedVec::<u64, 4>::try_from_iter(0..4).unwrap();
+    assert_eq!(6, values.iter().sum::<u64>());
+    assert!(BoundedVec::<u64, 4>::try_from_iter(0..5).is_err());
+}
+
+// TABLE TESTS
+// ================================================================================================
+
+#[test]
+fn table_view() {
+    // a table with 3 rows and 2 columns stored with stride 3
+    let data = [1_u64, 2, 0, 3, 4, 0, 5, 6];
+    let table = TableView::new(&data, 3, 2, 3);
+
+    assert_eq!(
+        (3, 2, 3),
+        (table.num_rows(), table.num_cols(), table.stride())
+    );
+    assert_eq!(4, *table.get(1, 1));
+    assert_eq!(&[5, 6], table.row(2));
+    assert_eq!(
+        vec![&[1, 2][..], &[3, 4], &[5, 6]],
+        table.rows().collect::<Vec<_>>()
+    );
+    assert_eq!(vec![2, 4, 6], table.column(1).copied().collect::<Vec<_>>());
+
+    let mut target = [0_u64; 1];
+    table.read_row_into(1, 1..2, &mut target);
+    assert_eq!([4], target);
+}
+
+#[test]
+#[should_panic(expected = "buffer must contain at least 8 elements, but contained 7")]
+fn table_view_short_buffer() {
+    TableView::new(&[0_u64; 7], 3, 2, 3);
+}
+
+#[test]
+fn table_view_fragments() {
+    // a table with 5 rows and 2 columns is broken into fragments of 2, 2, and 1 rows
+    let mut data = vec![0_usize; 10];
+    let table = TableViewMut::new(&mut data, 5, 2, 2);
+    let fragments = table.into_fragments(2);
+    assert_eq!(
+        vec![2, 2, 1],
+        fragments.iter().map(|f| f.num_rows()).collect::<Vec<_>>()
+    );
+    for (i, mut fragment) in fragments.into_iter().enumerate() {
+        for (j, row) in fragment.rows_mut().enumerate() {
+            row.copy_from_slice(&[i, j]);
+        }
+    }
+    assert_eq!(vec![0, 0, 0, 1, 1, 0, 1, 1, 2, 0], data);
+
+    // fragmenting columns
+    let mut columns = [vec![0_usize; 5], vec![0_usize; 5]];
+    let fragments = fragment_columns(columns.iter_mut().map(|c| c.as_mut_slice()), 5, 2);
+    assert_eq!(3, fragments.len());
+    for (i, fragment) in fragments.into_iter().enumerate() {
+        assert_eq!(2, fragment.len());
+        fragment.into_iter().flatten().for_each(|value| *value = i);
+    }
+    assert_eq!(vec![0, 0, 1, 1, 2], columns[1]);
+
+    // a table without columns is still broken into fragments
+    let fragments = fragment_columns(Vec::<&mut [usize]>::new(), 5, 2);
+    assert_eq!(3, fragments.len());
+}
+
 // SLICE READER TESTS
 // ================================================================================================
 
+#[test]
+fn chunks_mut_indexed_par() {
+    // every element should be processed exactly once, with the offset of its chunk
+    let mut values = vec![0_usize; 1000];
+    super::iterators::chunks_mut_indexed_par(&mut values, 1, |chunk: &mut [usize], offset| {
+        for (i, value) in chunk.iter_mut().enumerate() {
+            *value += offset + i + 1;
+        }
+    });
+    assert!(values.iter().enumerate().all(|(i, &value)| value == i + 1));
+
+    // slices smaller than the minimum chunk size should be processed as a single chunk
+    let mut values = vec![0_usize; 16];
+    super::iterators::chunks_mut_indexed_par(&mut values, 17, |chunk: &mut [usize], offset| {
+        assert_eq!((16, 0), (chunk.len(), offset));
+        chunk.iter_mut().for_each(|value| *value = 1);
+    });
+    assert!(values.iter().all(|&value| value == 1));
+}
+
 #[test]
 fn read_u8() {
     let source = [1u8, 3, 5, 7];
@@ -88,15 +230,212 @@ fn read_u8_vec() {
     assert!(a.read_u8_vec(2).is_err());
 }
 
-// SERIALIZATION TESTS
+#[test]
+fn read_in_context() {
+    let source = [1u8, 2, 3, 4, 5];
+    let mut a = SliceReader::new(&source);
+    a.read_u8().unwrap();
+
+    // errors should be wrapped into nested contexts specifying where the fields start
+    let err = a
+        .read_in_context("outer", |a| {
+            a.read_u16()?;
+            a.read_in_context("inner", |a| a.read_u32())
+        })
+        .unwrap_err();
+    let expected = DeserializationError::Context(
+        "outer",
+        1,
+        Box::new(DeserializationError::Context(
+            "inner",
+            3,
+            Box::new(DeserializationError::UnexpectedEOF),
+        )),
+    );
+    assert_eq!(expected, err);
+    assert_eq!(&DeserializationError::UnexpectedEOF, err.root_cause());
+    assert_eq!(
+        "failed to read outer at byte offset 1: failed to read inner at byte offset 3: \
+        unexpected EOF",
+        err.to_string()
+    );
+
+    // successful reads should not be affected
+    let mut a = SliceReader::new(&source);
+    assert_eq!(Ok(1), a.read_in_context("value", |a| a.read_u8()));
+    assert_eq!(1, a.position());
+}
+
+#[test]
+fn skip_bytes() {
+    let source = [1u8, 2, 3, 4, 5, 6, 7, 8];
+    let mut a = SliceReader::new(&source);
+
+    a.skip_bytes(2).unwrap();
+    assert_eq!(2, a.position());
+    assert_eq!(3, a.read_u8().unwrap());
+    a.skip_bytes(4).unwrap();
+    assert_eq!(7, a.position());
+    assert_eq!(Err(DeserializationError::UnexpectedEOF), a.skip_bytes(2));
+    assert_eq!(7, a.position());
+    a.skip_bytes(1).unwrap();
+    assert!(!a.has_more_bytes());
+}
+
+// READ ADAPTER TESTS
 // ================================================================================================
 
-impl Serializable for u128 {
-    fn write_into<W: crate::ByteWriter>(&self, target: &mut W) {
-        target.write_u8_slice(&self.to_le_bytes());
+#[cfg(feature = "std")]
+#[test]
+fn read_adapter() {
+    let mut source = Vec::new();
+    source.write_u8(1);
+    source.write_u16(12345);
+    source.write_u32(123456789);
+    source.write_u64(1234567891011);
+    source.write_u8_slice(&12345678910111213u128.to_le_bytes());
+    source.write_u8_slice(&[7u8; 5000]);
+
+    // read the source one byte at a time to make sure values spanning reads are assembled
+    let mut a = ReadAdapter::new(ByteByByte(&source));
+    assert!(a.has_more_bytes());
+    assert_eq!(1, a.read_u8().unwrap());
+    assert_eq!(12345, a.read_u16().unwrap());
+    assert_eq!(123456789, a.read_u32().unwrap());
+    assert_eq!(1234567891011, a.read_u64().unwrap());
+    assert_eq!(12345678910111213, a.read_u128().unwrap());
+    assert_eq!(vec![7u8; 4999], a.read_u8_vec(4999).unwrap());
+    assert!(a.has_more_bytes());
+    assert_eq!([7u8], a.read_u8_array::<1>().unwrap());
+    assert!(!a.has_more_bytes());
+    assert_eq!(Err(DeserializationError::UnexpectedEOF), a.read_u8());
+
+    // values which span chunk boundaries should be read correctly
+    let mut a = ReadAdapter::new(&source[..]);
+    assert_eq!(source[..4500].to_vec(), a.read_u8_vec(4500).unwrap());
+    assert_eq!(
+        source[4500..].to_vec(),
+        a.read_u8_vec(source.len() - 4500).unwrap()
+    );
+    assert!(!a.has_more_bytes());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn read_adapter_error() {
+    // I/O errors should be reported by the read which requires the missing bytes
+    let mut a = ReadAdapter::new(FailingReader);
+    assert!(!a.has_more_bytes());
+    assert_eq!(
+        Err(DeserializationError::UnknownError(
+            "connection reset".to_string()
+        )),
+        a.read_u8()
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn buf_read_adapter() {
+    let mut source = Vec::new();
+    source.write_u8(1);
+    source.write_u16(12345);
+    source.write_u32(123456789);
+    source.write_u64(1234567891011);
+    source.write_u8_slice(&12345678910111213u128.to_le_bytes());
+    source.write_u8_slice(&[7u8; 5000]);
+
+    // use a small buffer to make sure values spanning buffer boundaries are assembled
+    let mut a = BufReadAdapter::new(std::io::BufReader::with_capacity(3, &source[..]));
+    assert!(a.has_more_bytes());
+    assert_eq!(1, a.read_u8().unwrap());
+    assert_eq!(12345, a.read_u16().unwrap());
+    assert_eq!(123456789, a.read_u32().unwrap());
+    assert_eq!(1234567891011, a.read_u64().unwrap());
+    assert_eq!(12345678910111213, a.read_u128().unwrap());
+    assert_eq!(vec![7u8; 4999], a.read_u8_vec(4999).unwrap());
+    assert!(a.has_more_bytes());
+    assert_eq!([7u8], a.read_u8_array::<1>().unwrap());
+    assert!(!a.has_more_bytes());
+    assert_eq!(Err(DeserializationError::UnexpectedEOF), a.read_u8());
+    assert_eq!(Err(DeserializationError::UnexpectedEOF), a.read_u8_vec(1));
+
+    // bytes which were not read should remain in the source
+    let mut a = BufReadAdapter::new(&source[..]);
+    assert_eq!(1, a.read_u8().unwrap());
+    assert_eq!(&source[1..], a.into_inner());
+
+    // I/O errors should be reported by the read which requires the missing bytes
+    let mut a = BufReadAdapter::new(std::io::BufReader::new(FailingReader));
+    assert!(!a.has_more_bytes());
+    assert_eq!(
+        Err(DeserializationError::UnknownError(
+            "connection reset".to_string()
+        )),
+        a.read_u8()
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn write_adapter() {
+    let mut expected = Vec::new();
+    expected.write_u8(1);
+    expected.write_u32(123456789);
+    expected.write(234567u128);
+
+    let mut a = WriteAdapter::new(Vec::new());
+    a.write_u8(1);
+    a.write_u32(123456789);
+    a.write(234567u128);
+    assert_eq!(expected, a.finish().unwrap());
+
+    // the first I/O error should be returned by finish()
+    let mut target = [0u8; 4];
+    let mut a = WriteAdapter::new(&mut target[..]);
+    a.write_u32(123456789);
+    a.write_u8(1);
+    a.write_u8(2);
+    assert_eq!(
+        std::io::ErrorKind::WriteZero,
+        a.finish().unwrap_err().kind()
+    );
+    assert_eq!(123456789u32.to_le_bytes(), target);
+}
+
+/// Reader which returns bytes of the underlying slice one at a time.
+#[cfg(feature = "std")]
+struct ByteByByte<'a>(&'a [u8]);
+
+#[cfg(feature = "std")]
+impl<'a> std::io::Read for ByteByByte<'a> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.0.is_empty() || buf.is_empty() {
+            return Ok(0);
+        }
+        buf[0] = self.0[0];
+        self.0 = &self.0[1..];
+        Ok(1)
+    }
+}
+
+/// Reader which always fails.
+#[cfg(feature = "std")]
+struct FailingReader;
+
+#[cfg(feature = "std")]
+impl std::io::Read for FailingReader {
+    fn read(&mut self, _buf: &mut [u8]) -> std::io::Result<usize> {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::ConnectionReset,
+            "connection reset",
+        ))
     }
 }
 
+// SERIALIZATION TESTS
+// ================================================================================================
+
 #[test]
 fn write_serializable() {
     let mut target: Vec<u8> = Vec::new();
@@ -147,3 +486,284 @@ fn write_serializable_array_batch() {
         assert_eq!(i, reader.read_u128().unwrap());
     }
 }
+
+#[test]
+fn read_deserializable() {
+    let mut target: Vec<u8> = Vec::new();
+    target.write(1u8);
+    target.write(2u16);
+    target.write(3u32);
+    target.write(4u64);
+    target.write(5u128);
+    target.write([[6u64, 7], [8, 9]]);
+    assert_eq!(1 + 2 + 4 + 8 + 16 + 32, target.len());
+
+    let mut reader = SliceReader::new(&target);
+    assert_eq!(1u8, u8::read_from(&mut reader).unwrap());
+    assert_eq!(2u16, u16::read_from(&mut reader).unwrap());
+    assert_eq!(3u32, u32::read_from(&mut reader).unwrap());
+    assert_eq!(4u64, u64::read_from(&mut reader).unwrap());
+    assert_eq!(5u128, u128::read_from(&mut reader).unwrap());
+    assert_eq!(
+        [[6u64, 7], [8, 9]],
+        <[[u64; 2]; 2]>::read_from(&mut reader).unwrap()
+    );
+    assert!(!reader.has_more_bytes());
+    assert_eq!(
+        Err(DeserializationError::UnexpectedEOF),
+        <[u64; 2]>::read_from(&mut SliceReader::new(&target[..15]))
+    );
+}
+
+#[cfg(feature = "derive")]
+#[test]
+fn derive_serialization() {
+    #[derive(Debug, PartialEq, Serializable, Deserializable)]
+    #[serialization(crate = "crate")]
+    struct Named<T> {
+        a: u8,
+        b: [T; 2],
+        c: Value,
+    }
+
+    #[derive(Debug, PartialEq, Serializable, Deserializable)]
+    #[serialization(crate = "crate")]
+    struct Unnamed(u32, u16);
+
+    #[derive(Debug, PartialEq, Serializable, Deserializable)]
+    #[serialization(crate = "crate")]
+    enum Message {
+        Empty,
+        Pair(u8, Unnamed),
+        Record { id: u64, value: Named<u16> },
+    }
+
+    // fields should be written in the order of declaration without any metadata
+    let value = Named {
+        a: 1,
+        b: [2u64, 3],
+        c: Value(4),
+    };
+    let mut expected: Vec<u8> = Vec::new();
+    expected.write(1u8);
+    expected.write([2u64, 3]);
+    expected.write(4u128);
+    assert_eq!(expected, value.to_bytes());
+    assert_eq!(
+        value,
+        Named::<u64>::read_from(&mut SliceReader::new(&expected)).unwrap()
+    );
+
+    // enum variants should be prefixed with the index of the variant
+    let messages = vec![
+        Message::Empty,
+        Message::Pair(5, Unnamed(6, 7)),
+        Message::Record {
+            id: 8,
+            value: Named {
+                a: 9,
+                b: [10, 11],
+                c: Value(12),
+            },
+        },
+    ];
+    let mut expected: Vec<u8> = Vec::new();
+    expected.write(0u8);
+    expected.write(1u8);
+    expected.write(5u8);
+    expected.write(6u32);
+    expected.write(7u16);
+    expected.write(2u8);
+    expected.write(8u64);
+    expected.write(9u8);
+    expected.write([10u16, 11]);
+    expected.write(12u128);
+    assert_eq!(expected, messages.to_bytes());
+    assert_eq!(
+        messages,
+        Message::read_batch_from(&mut SliceReader::new(&expected), 3).unwrap()
+    );
+
+    // fields which could not be read should be reported
+    assert_eq!(
+        Err(DeserializationError::Context(
+            "b",
+            1,
+            Box::new(DeserializationError::UnexpectedEOF)
+        )),
+        Named::<u64>::read_from(&mut SliceReader::new(&[1, 2, 3]))
+    );
+
+    // unknown variants should be rejected
+    assert_eq!(
+        Err(DeserializationError::InvalidValue(
+            "invalid variant index for Message: 3".into()
+        )),
+        Message::read_from(&mut SliceReader::new(&[3]))
+    );
+}
+
+// CBOR TESTS
+// ================================================================================================
+
+#[test]
+fn cbor_encoding() {
+    // expected encodings are taken from appendix A of RFC 8949
+    let mut writer = CborWriter::new();
+    for value in [0, 23, 24, 100, 1000, 1000000, 1000000000000] {
+        writer.write_uint(value);
+    }
+    writer.write_bytes(&[1, 2, 3, 4]);
+    writer.write_array_header(3);
+    for value in [1, 2, 3] {
+        writer.write_uint(value);
+    }
+    writer.write_map_header(2);
+    for value in [1, 2, 3, 4] {
+        writer.write_uint(value);
+    }
+    let expected: Vec<u8> = [
+        &[0x00, 0x17, 0x18, 0x18, 0x18, 0x64, 0x19, 0x03, 0xe8][..],
+        &[0x1a, 0x00, 0x0f, 0x42, 0x40],
+        &[0x1b, 0x00, 0x00, 0x00, 0xe8, 0xd4, 0xa5, 0x10, 0x00],
+        &[0x44, 0x01, 0x02, 0x03, 0x04],
+        &[0x83, 0x01, 0x02, 0x03],
+        &[0xa2, 0x01, 0x02, 0x03, 0x04],
+    ]
+    .concat();
+    let bytes = writer.into_bytes();
+    assert_eq!(expected, bytes);
+
+    let mut reader = CborReader::new(&bytes);
+    for value in [0, 23, 24, 100, 1000, 1000000, 1000000000000] {
+        assert_eq!(value, reader.read_uint().unwrap());
+    }
+    assert_eq!(vec![1, 2, 3, 4], reader.read_bytes().unwrap());
+    assert_eq!(3, reader.read_array_header().unwrap());
+    for value in [1, 2, 3] {
+        assert_eq!(value, reader.read_uint().unwrap());
+    }
+    assert_eq!(2, reader.read_map_header().unwrap());
+    reader.read_map_key(1).unwrap();
+    assert_eq!(2, reader.read_uint().unwrap());
+    reader.read_map_key(3).unwrap();
+    assert_eq!(4, reader.read_uint().unwrap());
+    assert!(!reader.has_more_bytes());
+
+    // map keys must be read in the expected order
+    assert!(CborReader::new(&[0x03]).read_map_key(1).is_err());
+}
+
+#[test]
+fn cbor_serializable() {
+    let mut writer = CborWriter::new();
+    writer.write_serializable(&Value(123456));
+    let bytes = writer.into_bytes();
+    assert_eq!(17, bytes.len());
+    assert_eq!(
+        Value(123456),
+        CborReader::new(&bytes).read_deserializable().unwrap()
+    );
+
+    // all bytes of the byte string must be consumed
+    let mut writer = CborWriter::new();
+    writer.write_bytes(&[0; 17]);
+    let result = CborReader::new(&writer.into_bytes()).read_deserializable::<Value>();
+    assert_eq!(Err(DeserializationError::UnconsumedBytes), result);
+}
+
+#[test]
+fn cbor_non_canonical() {
+    // integers and lengths not encoded in the shortest form
+    assert!(CborReader::new(&[0x18, 0x17]).read_uint().is_err());
+    assert!(CborReader::new(&[0x19, 0x00, 0xff]).read_uint().is_err());
+    assert!(CborReader::new(&[0x58, 0x01, 0x00]).read_bytes().is_err());
+
+    // values of indefinite length
+    assert!(CborReader::new(&[0x5f, 0x41, 0x00, 0xff])
+        .read_bytes()
+        .is_err());
+    assert!(CborReader::new(&[0x9f, 0x01, 0xff])
+        .read_array_header()
+        .is_err());
+
+    // values of unexpected types
+    assert!(CborReader::new(&[0x41, 0x00]).read_uint().is_err());
+    assert!(CborReader::new(&[0x20]).read_uint().is_err());
+
+    // truncated values
+    assert_eq!(
+        Err(DeserializationError::UnexpectedEOF),
+        CborReader::new(&[0x19, 0x01]).read_uint()
+    );
+    assert_eq!(
+        Err(DeserializationError::UnexpectedEOF),
+        CborReader::new(&[0x44, 0x01]).read_bytes()
+    );
+    assert_eq!(
+        Err(DeserializationError::UnexpectedEOF),
+        CborReader::new(&[0x9a, 0xff, 0xff, 0xff, 0xff]).read_array_header()
+    );
+}
+
+// SERDE ADAPTER TESTS
+// ================================================================================================
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_adapter_json() {
+    // values should be serialized into human-readable formats as hex strings
+    let mut json = Vec::new();
+    serde_adapter::serialize(&0x0102u128, &mut serde_json::Serializer::new(&mut json)).unwrap();
+    assert_eq!(b"\"02010000000000000000000000000000\"".to_vec(), json);
+
+    let mut deserializer = serde_json::Deserializer::from_slice(&json);
+    let value: u128 = serde_adapter::deserialize(&mut deserializer).unwrap();
+    assert_eq!(0x0102u128, value);
+
+    // a sequence of bytes should also be accepted
+    let mut deserializer =
+        serde_json::Deserializer::from_str("[2, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]");
+    let value: u128 = serde_adapter::deserialize(&mut deserializer).unwrap();
+    assert_eq!(0x0102u128, value);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_adapter_json_error() {
+    fn deserialize(json: &str) -> Result<u128, serde_json::Error> {
+        serde_adapter::deserialize(&mut serde_json::Deserializer::from_str(json))
+    }
+
+    // upper-case hex digits are accepted
+    assert_eq!(
+        0xabu128,
+        deserialize("\"AB000000000000000000000000000000\"").unwrap()
+    );
+
+    // invalid hex strings
+    assert!(deserialize("\"ab00000000000000000000000000000\"").is_err());
+    assert!(deserialize("\"xy000000000000000000000000000000\"").is_err());
+
+    // too few and too many bytes
+    assert!(deserialize("\"ab\"").is_err());
+    assert!(deserialize("\"ab00000000000000000000000000000000\"").is_err());
+}
+
+// TEST HELPERS
+// ================================================================================================
+
+#[derive(Debug, PartialEq)]
+struct Value(u128);
+
+impl Serializable for Value {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8_slice(&self.0.to_le_bytes());
+    }
+}
+
+impl Deserializable for Value {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Ok(Value(source.read_u128()?))
+    }
+}
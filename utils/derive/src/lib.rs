@@ -0,0 +1,309 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! This crate contains derive macros for `Serializable` and `Deserializable` traits of
+//! `winter-utils` crate.
+//!
+//! The macros are re-exported by `winter-utils` (and `winterfell`) crate when `derive` feature is
+//! enabled, and should be used via these crates.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{
+    ext::IdentExt, parse_macro_input, parse_quote, Data, DeriveInput, Error, Fields, Generics,
+    Ident, Lit, Meta, NestedMeta, Path,
+};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Name of the attribute used to configure the derived implementations.
+const ATTRIBUTE_NAME: &str = "serialization";
+
+/// Path to the crate defining the serialization traits used when no path is specified via the
+/// attribute.
+const DEFAULT_CRATE_PATH: &str = "::winter_utils";
+
+// DERIVE MACROS
+// ================================================================================================
+
+/// Derives `Serializable` trait for a struct or an enum.
+///
+/// Fields are written in the order in which they are declared; every field must implement
+/// `Serializable` trait. For enums, the index of the variant is written as a single byte before
+/// the fields of the variant.
+///
+/// The path to the crate exporting the traits can be specified via
+/// `#[serialization(crate = "path")]` attribute; by default, `winter_utils` crate is used.
+#[proc_macro_derive(Serializable, attributes(serialization))]
+pub fn derive_serializable(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_serializable(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Derives `Deserializable` trait for a struct or an enum.
+///
+/// Fields are read in the order in which they are declared; every field must implement
+/// `Deserializable` trait. For enums, the index of the variant is read as a single byte before
+/// the fields of the variant, and an unknown index results in an error.
+///
+/// Every field is read via `ByteReader::read_in_context()` function using the name of the field
+/// (or its index for tuple fields); thus, readers which track byte offsets report which field
+/// could not be read.
+///
+/// The path to the crate exporting the traits can be specified via
+/// `#[serialization(crate = "path")]` attribute; by default, `winter_utils` crate is used.
+#[proc_macro_derive(Deserializable, attributes(serialization))]
+pub fn derive_deserializable(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_deserializable(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+// SERIALIZABLE
+// ================================================================================================
+
+fn expand_serializable(input: &DeriveInput) -> Result<TokenStream2, Error> {
+    let krate = crate_path(input)?;
+    let name = &input.ident;
+    let generics = add_trait_bounds(&input.generics, &parse_quote!(#krate::Serializable));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let bindings = field_bindings(&data.fields);
+            let pattern = fields_pattern(&data.fields, &bindings);
+            quote! {
+                let #name #pattern = self;
+                #(#krate::Serializable::write_into(#bindings, target);)*
+            }
+        }
+        Data::Enum(data) => {
+            check_num_variants(input, data.variants.len())?;
+            let arms = data.variants.iter().enumerate().map(|(index, variant)| {
+                let variant_name = &variant.ident;
+                let index = index as u8;
+                let bindings = field_bindings(&variant.fields);
+                let pattern = fields_pattern(&variant.fields, &bindings);
+                quote! {
+                    #name::#variant_name #pattern => {
+                        #krate::ByteWriter::write_u8(target, #index);
+                        #(#krate::Serializable::write_into(#bindings, target);)*
+                    }
+                }
+            });
+            quote! {
+                match self {
+                    #(#arms)*
+                }
+            }
+        }
+        Data::Union(_) => {
+            return Err(Error::new_spanned(
+                name,
+                "Serializable cannot be derived for unions",
+            ))
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics #krate::Serializable for #name #ty_generics #where_clause {
+            fn write_into<W: #krate::ByteWriter>(&self, target: &mut W) {
+                #body
+            }
+        }
+    })
+}
+
+// DESERIALIZABLE
+// ================================================================================================
+
+fn expand_deserializable(input: &DeriveInput) -> Result<TokenStream2, Error> {
+    let krate = crate_path(input)?;
+    let name = &input.ident;
+    let generics = add_trait_bounds(&input.generics, &parse_quote!(#krate::Deserializable));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let constructor = read_fields(&krate, quote!(#name), &data.fields);
+            quote!(::core::result::Result::Ok(#constructor))
+        }
+        Data::Enum(data) => {
+            check_num_variants(input, data.variants.len())?;
+            let arms = data.variants.iter().enumerate().map(|(index, variant)| {
+                let variant_name = &variant.ident;
+                let index = index as u8;
+                let constructor =
+                    read_fields(&krate, quote!(#name::#variant_name), &variant.fields);
+                quote!(#index => ::core::result::Result::Ok(#constructor),)
+            });
+            let error_message = format!("invalid variant index for {}: ", name);
+            quote! {
+                match #krate::ByteReader::read_u8(source)? {
+                    #(#arms)*
+                    index => {
+                        // the message is built via core traits only, as the crate exporting the
+                        // traits may not export string types
+                        let mut message = ::core::convert::From::from(#error_message);
+                        let _ = ::core::fmt::Write::write_fmt(
+                            &mut message,
+                            ::core::format_args!("{}", index),
+                        );
+                        ::core::result::Result::Err(
+                            #krate::DeserializationError::InvalidValue(message),
+                        )
+                    }
+                }
+            }
+        }
+        Data::Union(_) => {
+            return Err(Error::new_spanned(
+                name,
+                "Deserializable cannot be derived for unions",
+            ))
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics #krate::Deserializable for #name #ty_generics #where_clause {
+            fn read_from<R: #krate::ByteReader>(
+                source: &mut R,
+            ) -> ::core::result::Result<Self, #krate::DeserializationError> {
+                #body
+            }
+        }
+    })
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the path to the crate exporting the serialization traits; the path is read from
+/// `#[serialization(crate = "path")]` attribute of the `input`, if present.
+fn crate_path(input: &DeriveInput) -> Result<Path, Error> {
+    let mut result = None;
+    for attr in input.attrs.iter() {
+        if !attr.path.is_ident(ATTRIBUTE_NAME) {
+            continue;
+        }
+        let nested = match attr.parse_meta()? {
+            Meta::List(list) => list.nested,
+            meta => {
+                return Err(Error::new_spanned(
+                    meta,
+                    "expected #[serialization(crate = \"...\")]",
+                ))
+            }
+        };
+        for item in nested {
+            match item {
+                NestedMeta::Meta(Meta::NameValue(name_value))
+                    if name_value.path.is_ident("crate") =>
+                {
+                    match &name_value.lit {
+                        Lit::Str(path) if result.is_none() => result = Some(path.parse()?),
+                        Lit::Str(_) => {
+                            return Err(Error::new_spanned(
+                                name_value,
+                                "crate path is specified more than once",
+                            ))
+                        }
+                        lit => return Err(Error::new_spanned(lit, "crate path must be a string")),
+                    }
+                }
+                item => {
+                    return Err(Error::new_spanned(
+                        item,
+                        "unknown serialization attribute; expected `crate = \"...\"`",
+                    ))
+                }
+            }
+        }
+    }
+    Ok(result.unwrap_or_else(|| syn::parse_str(DEFAULT_CRATE_PATH).expect("invalid default path")))
+}
+
+/// Returns the `generics` with the specified `bound` added to every type parameter.
+fn add_trait_bounds(generics: &Generics, bound: &Path) -> Generics {
+    let mut generics = generics.clone();
+    for param in generics.type_params_mut() {
+        param.bounds.push(parse_quote!(#bound));
+    }
+    generics
+}
+
+/// Returns an error if the enum specified by `input` has too many variants for the index of a
+/// variant to be serialized as a single byte.
+fn check_num_variants(input: &DeriveInput, num_variants: usize) -> Result<(), Error> {
+    if num_variants > u8::MAX as usize + 1 {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "serialization cannot be derived for enums with more than 256 variants",
+        ));
+    }
+    Ok(())
+}
+
+/// Returns names of variables to which the specified `fields` are bound when they are written.
+fn field_bindings(fields: &Fields) -> Vec<Ident> {
+    (0..fields.len())
+        .map(|i| format_ident!("field_{}", i, span = Span::call_site()))
+        .collect()
+}
+
+/// Returns a pattern which binds the specified `fields` to variables with the specified names.
+fn fields_pattern(fields: &Fields, bindings: &[Ident]) -> TokenStream2 {
+    match fields {
+        Fields::Named(named) => {
+            let names = named.named.iter().map(|field| &field.ident);
+            quote!({ #(#names: #bindings),* })
+        }
+        Fields::Unnamed(_) => quote!(( #(#bindings),* )),
+        Fields::Unit => quote!(),
+    }
+}
+
+/// Returns an expression which constructs a value of the struct or the enum variant at the
+/// specified `path` by reading the specified `fields` in the order in which they are declared.
+///
+/// Every field is read in the context of its name (or its index for tuple fields), so that
+/// readers which track byte offsets can report which field could not be read.
+fn read_fields(krate: &Path, path: TokenStream2, fields: &Fields) -> TokenStream2 {
+    let read = |field: String| {
+        quote! {
+            #krate::ByteReader::read_in_context(
+                source,
+                #field,
+                #krate::Deserializable::read_from,
+            )?
+        }
+    };
+    match fields {
+        Fields::Named(named) => {
+            let names = named.named.iter().map(|field| &field.ident);
+            let reads = named.named.iter().map(|field| {
+                read(
+                    field
+                        .ident
+                        .as_ref()
+                        .expect("named field")
+                        .unraw()
+                        .to_string(),
+                )
+            });
+            quote!(#path { #(#names: #reads),* })
+        }
+        Fields::Unnamed(unnamed) => {
+            let reads = (0..unnamed.unnamed.len()).map(|index| read(index.to_string()));
+            quote!(#path ( #(#reads),* ))
+        }
+        Fields::Unit => quote!(#path),
+    }
+}
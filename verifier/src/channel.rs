@@ -3,41 +3,91 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::VerifierError;
-use air::{proof::StarkProof, Air, EvaluationFrame};
+use crate::{
+    diagnostics::{get_invalid_positions, FailedCheck},
+    VerifierError,
+};
+use air::{
+    proof::{Commitments, Context, OodFrame, Queries, StarkProof},
+    Air, CommittedColumn, EvaluationFrame, TraceInfo,
+};
+use core::ops::Range;
 use crypto::{BatchMerkleProof, ElementHasher, MerkleTree};
-use fri::VerifierChannel as FriVerifierChannel;
+use fri::{FriOptions, FriProof, VerifierChannel as FriVerifierChannel};
 use math::{FieldElement, StarkField};
-use utils::{collections::Vec, string::ToString};
+use utils::{
+    collections::{BoundedVec, Vec},
+    iter,
+    string::ToString,
+    ByteReader, Deserializable, DeserializationError,
+};
+
+#[cfg(feature = "concurrent")]
+use utils::iterators::*;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Maximum number of trace segments; every segment contains at least one column, and thus, the
+/// number of segments cannot exceed the maximum width of an execution trace.
+pub const MAX_TRACE_SEGMENTS: usize = TraceInfo::MAX_TRACE_WIDTH;
+
+/// Maximum number of committed columns; the number of committed column queries is serialized as
+/// a single byte.
+pub const MAX_COMMITTED_COLUMNS: usize = u8::MAX as usize;
+
+/// Maximum number of FRI layers; every layer reduces the size of the evaluation domain by at
+/// least a factor of two, and thus, the number of layers cannot exceed the number of bits in the
+/// size of the LDE domain.
+pub const MAX_FRI_LAYERS: usize = usize::BITS as usize;
 
 // TYPES AND INTERFACES
 // ================================================================================================
 
+/// Holds the values sent by the prover and hands them out to the verifier.
+///
+/// Values which come in one batch per trace segment, committed column, or FRI layer are held in
+/// [BoundedVec]s with capacities defined by the constants above; thus, when `arrayvec` feature
+/// is enabled, these batches are held inline. Values of individual queries (e.g., trace states
+/// and authentication paths) are still held in [Vec]s, as their number depends on the number of
+/// queries and on the width of the trace.
 pub struct VerifierChannel<B, E, H>
 where
     B: StarkField,
     E: FieldElement<BaseField = B>,
     H: ElementHasher<BaseField = B>,
 {
+    // query shape; queries are parsed only once query positions are known, as the number of
+    // distinct positions depends on the query sampling strategy
+    lde_domain_size: usize,
+    trace_segments: BoundedVec<Range<usize>, MAX_TRACE_SEGMENTS>,
+    ce_blowup_factor: usize,
+    fri_options: FriOptions,
     // trace queries
-    trace_root: H::Digest,
-    trace_proof: BatchMerkleProof<H>,
-    trace_states: Option<Vec<Vec<B>>>,
+    trace_roots: BoundedVec<H::Digest, MAX_TRACE_SEGMENTS>,
+    trace_queries: Option<BoundedVec<Queries, MAX_TRACE_SEGMENTS>>,
     // constraint queries
     constraint_root: H::Digest,
-    constraint_proof: BatchMerkleProof<H>,
-    constraint_evaluations: Option<Vec<Vec<E>>>,
+    constraint_queries: Option<Queries>,
+    // committed column queries
+    committed_column_queries: Option<BoundedVec<Queries, MAX_COMMITTED_COLUMNS>>,
     // FRI proof
-    fri_roots: Option<Vec<H::Digest>>,
-    fri_layer_proofs: Vec<BatchMerkleProof<H>>,
-    fri_layer_queries: Vec<Vec<E>>,
+    fri_roots: Option<BoundedVec<H::Digest, MAX_FRI_LAYERS>>,
+    fri_proof: Option<FriProof>,
+    fri_layer_pow_nonces: Option<BoundedVec<u64, MAX_FRI_LAYERS>>,
+    fri_ood_evaluations: Option<Vec<E>>,
+    fri_layer_proofs: BoundedVec<BatchMerkleProof<H>, MAX_FRI_LAYERS>,
+    fri_layer_queries: BoundedVec<Vec<E>, MAX_FRI_LAYERS>,
     fri_remainder: Option<Vec<E>>,
     fri_num_partitions: usize,
+    fri_num_layers: usize,
     // out-of-domain evaluation
     ood_frame: Option<EvaluationFrame<E>>,
     ood_evaluations: Option<Vec<E>>,
     // query proof-of-work
     pow_nonce: u64,
+    // details of the last failed query check
+    failed_check: Option<FailedCheck<B>>,
 }
 
 // VERIFIER CHANNEL IMPLEMENTATION
@@ -49,93 +99,253 @@ where
     E: FieldElement<BaseField = B>,
     H: ElementHasher<BaseField = B>,
 {
-    // CONSTRUCTOR
+    // CONSTRUCTORS
     // --------------------------------------------------------------------------------------------
     /// Creates and returns a new verifier channel initialized from the specified `proof`.
     pub fn new<A: Air<BaseElement = B>>(air: &A, proof: StarkProof) -> Result<Self, VerifierError> {
+        let mut channel = Self::with_commit_phase(
+            air,
+            &proof.context,
+            proof.commitments,
+            proof.ood_frame,
+            proof.fri_proof,
+            proof.pow_nonce,
+        )?;
+        channel.set_queries(
+            air,
+            proof.trace_queries,
+            proof.constraint_queries,
+            proof.committed_column_queries,
+        )?;
+        channel.set_fri_query_phase()?;
+        Ok(channel)
+    }
+
+    /// Creates and returns a new verifier channel initialized with the values sent by the prover
+    /// during the commit phase of the protocol.
+    ///
+    /// Queries must be supplied to the channel via [read_queries_from()](Self::read_queries_from)
+    /// and [read_fri_query_phase_from()](Self::read_fri_query_phase_from) methods before they
+    /// can be read from the channel; `fri_proof` is expected to contain only the values read via
+    /// `FriProof::read_commit_phase_from()`.
+    pub fn with_commit_phase<A: Air<BaseElement = B>>(
+        air: &A,
+        context: &Context,
+        commitments: Commitments,
+        ood_frame: OodFrame,
+        fri_proof: FriProof,
+        pow_nonce: u64,
+    ) -> Result<Self, VerifierError> {
         // make AIR and proof base fields are the same
-        if B::get_modulus_le_bytes() != proof.context.field_modulus_bytes() {
+        if B::get_modulus_le_bytes() != context.field_modulus_bytes() {
             return Err(VerifierError::InconsistentBaseField);
         }
 
         let lde_domain_size = air.lde_domain_size();
-        let num_queries = air.options().num_queries();
         let fri_options = air.options().to_fri_options();
+        let trace_segments =
+            BoundedVec::try_from_iter(air.options().trace_segments(air.trace_width()))
+                .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
 
         // --- parse commitments ------------------------------------------------------------------
-        let (trace_root, constraint_root, fri_roots) = proof
-            .commitments
-            .parse::<H>(fri_options.num_fri_layers(lde_domain_size))
+        // with early termination, the number of FRI layers is included in the FRI proof; the
+        // number is checked against the max number of layers by the FRI verifier
+        let num_fri_layers = if fri_options.early_termination() {
+            fri_proof.num_committed_layers().ok_or_else(|| {
+                VerifierError::ProofDeserializationError(
+                    "number of committed FRI layers is missing".to_string(),
+                )
+            })?
+        } else {
+            fri_options.num_fri_layers(lde_domain_size)
+        };
+        let (trace_roots, constraint_root, fri_roots) = commitments
+            .parse::<H>(trace_segments.len(), num_fri_layers)
             .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
-
-        // --- parse trace queries ----------------------------------------------------------------
-        let (trace_proof, trace_states) = proof
-            .trace_queries
-            .parse::<H, B>(lde_domain_size, num_queries, air.trace_width())
-            .map_err(|err| {
-                VerifierError::ProofDeserializationError(format!(
-                    "trace query deserialization failed: {}",
-                    err.to_string()
-                ))
-            })?;
-
-        // --- parse constraint evaluation queries ------------------------------------------------
-        let (constraint_proof, constraint_evaluations) = proof
-            .constraint_queries
-            .parse::<H, E>(lde_domain_size, num_queries, air.ce_blowup_factor())
-            .map_err(|err| {
-                VerifierError::ProofDeserializationError(format!(
-                    "constraint evaluation query deserialization failed: {}",
-                    err.to_string()
-                ))
-            })?;
-
-        // --- parse FRI proofs -------------------------------------------------------------------
-        let fri_num_partitions = proof.fri_proof.num_partitions();
-        let fri_remainder = proof
-            .fri_proof
-            .parse_remainder()
+        let trace_roots = BoundedVec::try_from_iter(trace_roots)
             .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
-        let (fri_layer_queries, fri_layer_proofs) = proof
-            .fri_proof
-            .parse_layers::<H, E>(lde_domain_size, fri_options.folding_factor())
+        let fri_roots = BoundedVec::try_from_iter(fri_roots)
             .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+        let fri_layer_pow_nonces =
+            BoundedVec::try_from_iter(fri_proof.layer_pow_nonces().iter().copied())
+                .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
 
         // --- parse out-of-domain evaluation frame -----------------------------------------------
-        let (ood_frame, ood_evaluations) = proof
-            .ood_frame
+        let (ood_frame, ood_evaluations) = ood_frame
             .parse(air.trace_width(), air.ce_blowup_factor())
             .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
 
+        // --- parse out-of-domain evaluations of the low-degree test -----------------------------
+        let fri_ood_evaluations = fri_proof
+            .parse_ood_evaluations()
+            .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+
         Ok(VerifierChannel {
+            // query shape
+            lde_domain_size,
+            trace_segments,
+            ce_blowup_factor: air.ce_blowup_factor(),
+            fri_options,
             // trace queries
-            trace_root,
-            trace_proof,
-            trace_states: Some(trace_states),
+            trace_roots,
+            trace_queries: None,
             // constraint queries
             constraint_root,
-            constraint_proof,
-            constraint_evaluations: Some(constraint_evaluations),
+            constraint_queries: None,
+            // committed column queries
+            committed_column_queries: None,
             // FRI proof
             fri_roots: Some(fri_roots),
-            fri_layer_proofs,
-            fri_layer_queries,
-            fri_remainder: Some(fri_remainder),
-            fri_num_partitions,
+            fri_layer_pow_nonces: Some(fri_layer_pow_nonces),
+            fri_ood_evaluations: Some(fri_ood_evaluations),
+            fri_num_partitions: fri_proof.num_partitions(),
+            fri_proof: Some(fri_proof),
+            fri_layer_proofs: BoundedVec::new(),
+            fri_layer_queries: BoundedVec::new(),
+            fri_remainder: None,
+            fri_num_layers: 0,
             // out-of-domain evaluation
             ood_frame: Some(ood_frame),
             ood_evaluations: Some(ood_evaluations),
             // query seed
-            pow_nonce: proof.pow_nonce,
+            pow_nonce,
+            failed_check: None,
         })
     }
 
+    /// Reads the values sent by the prover during the commit phase of the protocol from the
+    /// specified `source`, and returns a new verifier channel initialized with these values.
+    ///
+    /// The values are expected to be serialized in the same order as they are serialized by
+    /// `StarkProof::to_streaming_bytes()` method, and the proof context is expected to have been
+    /// read from the `source` already.
+    pub fn read_commit_phase_from<A: Air<BaseElement = B>, R: ByteReader>(
+        air: &A,
+        context: &Context,
+        source: &mut R,
+    ) -> Result<Self, VerifierError> {
+        let read = |source: &mut R| -> Result<_, DeserializationError> {
+            let commitments = Commitments::read_from(source)?;
+            let ood_frame = OodFrame::read_from(source)?;
+            let fri_proof = FriProof::read_commit_phase_from(source)?;
+            let pow_nonce = source.read_u64()?;
+            Ok((commitments, ood_frame, fri_proof, pow_nonce))
+        };
+        let (commitments, ood_frame, fri_proof, pow_nonce) = read(source)
+            .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+        Self::with_commit_phase(air, context, commitments, ood_frame, fri_proof, pow_nonce)
+    }
+
+    // QUERY READERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Reads trace, constraint, and committed column queries from the specified `source` and
+    /// supplies them to this channel.
+    ///
+    /// The queries are expected to be serialized in the same order as they are serialized by
+    /// `StarkProof::to_streaming_bytes()` method.
+    pub fn read_queries_from<A: Air<BaseElement = B>, R: ByteReader>(
+        &mut self,
+        air: &A,
+        source: &mut R,
+    ) -> Result<(), VerifierError> {
+        let read = |source: &mut R| -> Result<_, DeserializationError> {
+            let num_segments = source.read_u8()? as usize;
+            let trace_queries = Queries::read_batch_from(source, num_segments)?;
+            let constraint_queries = Queries::read_from(source)?;
+            let num_columns = source.read_u8()? as usize;
+            let committed_column_queries = Queries::read_batch_from(source, num_columns)?;
+            Ok((trace_queries, constraint_queries, committed_column_queries))
+        };
+        let (trace_queries, constraint_queries, committed_column_queries) = read(source)
+            .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+        self.set_queries(
+            air,
+            trace_queries,
+            constraint_queries,
+            committed_column_queries,
+        )
+    }
+
+    /// Reads FRI layer queries and the FRI remainder from the specified `source` and supplies
+    /// them to this channel.
+    pub fn read_fri_query_phase_from<R: ByteReader>(
+        &mut self,
+        source: &mut R,
+    ) -> Result<(), VerifierError> {
+        self.fri_proof
+            .as_mut()
+            .expect("FRI query phase already read")
+            .read_query_phase_from(source)
+            .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+        self.set_fri_query_phase()
+    }
+
+    /// Makes sure the number of trace and committed column queries is consistent with the
+    /// specified `air`, and supplies the queries to this channel.
+    fn set_queries<A: Air<BaseElement = B>>(
+        &mut self,
+        air: &A,
+        trace_queries: Vec<Queries>,
+        constraint_queries: Queries,
+        committed_column_queries: Vec<Queries>,
+    ) -> Result<(), VerifierError> {
+        // --- check trace queries ----------------------------------------------------------------
+        if trace_queries.len() != self.trace_segments.len() {
+            return Err(VerifierError::ProofDeserializationError(format!(
+                "expected queries for {} trace segments, but was {}",
+                self.trace_segments.len(),
+                trace_queries.len()
+            )));
+        }
+
+        // --- check committed column queries -----------------------------------------------------
+        let num_committed_columns = air.get_committed_columns().len();
+        if committed_column_queries.len() != num_committed_columns {
+            return Err(VerifierError::ProofDeserializationError(format!(
+                "expected queries for {} committed columns, but was {}",
+                num_committed_columns,
+                committed_column_queries.len()
+            )));
+        }
+
+        let trace_queries = BoundedVec::try_from_iter(trace_queries)
+            .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+        let committed_column_queries = BoundedVec::try_from_iter(committed_column_queries)
+            .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+
+        self.trace_queries = Some(trace_queries);
+        self.constraint_queries = Some(constraint_queries);
+        self.committed_column_queries = Some(committed_column_queries);
+        Ok(())
+    }
+
+    /// Parses layers and remainder of the FRI proof held by this channel.
+    fn set_fri_query_phase(&mut self) -> Result<(), VerifierError> {
+        let fri_proof = self.fri_proof.take().expect("FRI query phase already read");
+        let fri_remainder = fri_proof
+            .parse_remainder()
+            .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+        let (fri_layer_queries, fri_layer_proofs) = fri_proof
+            .parse_layers::<H, E>(self.lde_domain_size, &self.fri_options)
+            .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+
+        self.fri_num_layers = fri_layer_proofs.len();
+        self.fri_layer_proofs = BoundedVec::try_from_iter(fri_layer_proofs)
+            .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+        self.fri_layer_queries = BoundedVec::try_from_iter(fri_layer_queries)
+            .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+        self.fri_remainder = Some(fri_remainder);
+        Ok(())
+    }
+
     // DATA READERS
     // --------------------------------------------------------------------------------------------
 
-    /// Returns execution trace commitment sent by the prover.
-    pub fn read_trace_commitment(&self) -> H::Digest {
-        self.trace_root
+    /// Returns execution trace commitments sent by the prover; one commitment for each trace
+    /// segment.
+    pub fn read_trace_commitments(&self) -> BoundedVec<H::Digest, MAX_TRACE_SEGMENTS> {
+        self.trace_roots.clone()
     }
 
     /// Returns constraint evaluation commitment sent by the prover.
@@ -161,17 +371,57 @@ where
     }
 
     /// Returns trace states at the specified positions of the LDE domain. This also checks if
-    /// the trace states are valid against the trace commitment sent by the prover.
+    /// the values of each trace segment are valid against the commitment to this segment sent
+    /// by the prover.
     pub fn read_trace_states(
         &mut self,
         positions: &[usize],
-        commitment: &H::Digest,
+        commitments: &[H::Digest],
     ) -> Result<Vec<Vec<B>>, VerifierError> {
-        // make sure the states included in the proof correspond to the trace commitment
-        MerkleTree::verify_batch(commitment, positions, &self.trace_proof)
-            .map_err(|_| VerifierError::TraceQueryDoesNotMatchCommitment)?;
+        let queries = self.trace_queries.take().expect("already read");
+        let segments = queries
+            .into_iter()
+            .zip(self.trace_segments.iter())
+            .map(|(queries, segment)| {
+                queries
+                    .parse::<H, B>(self.lde_domain_size, positions.len(), segment.len())
+                    .map_err(|err| {
+                        VerifierError::ProofDeserializationError(format!(
+                            "trace query deserialization failed: {}",
+                            err
+                        ))
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // make sure the states included in the proof correspond to the segment commitments;
+        // when `concurrent` feature is enabled, segments are checked in multiple threads
+        let is_valid = iter!(segments)
+            .zip(commitments)
+            .map(|((proof, _), commitment)| {
+                MerkleTree::verify_batch(commitment, positions, proof).is_ok()
+            })
+            .collect::<Vec<_>>();
+        if let Some(segment_idx) = is_valid.iter().position(|&is_valid| !is_valid) {
+            let commitment = &commitments[segment_idx];
+            let (segment_proof, _) = &segments[segment_idx];
+            self.failed_check = Some(FailedCheck::TraceQueries {
+                segment: segment_idx,
+                positions: get_invalid_positions(commitment, positions, segment_proof),
+            });
+            return Err(VerifierError::TraceQueryDoesNotMatchCommitment);
+        }
 
-        Ok(self.trace_states.take().expect("already read"))
+        // segments are ordered by column index; thus, appending values of each segment to the
+        // states reassembles full rows of the extended trace
+        let mut trace_states = vec![Vec::new(); positions.len()];
+        for (_, segment_states) in segments {
+            for (state, segment_state) in trace_states.iter_mut().zip(segment_states) {
+                state.extend_from_slice(&segment_state);
+            }
+        }
+
+        Ok(trace_states)
     }
 
     /// Returns constraint evaluations at the specified positions of the LDE domain. This also
@@ -182,10 +432,89 @@ where
         positions: &[usize],
         commitment: &H::Digest,
     ) -> Result<Vec<Vec<E>>, VerifierError> {
-        MerkleTree::verify_batch(commitment, positions, &self.constraint_proof)
-            .map_err(|_| VerifierError::ConstraintQueryDoesNotMatchCommitment)?;
+        let (constraint_proof, constraint_evaluations) = self
+            .constraint_queries
+            .take()
+            .expect("already read")
+            .parse::<H, E>(self.lde_domain_size, positions.len(), self.ce_blowup_factor)
+            .map_err(|err| {
+                VerifierError::ProofDeserializationError(format!(
+                    "constraint evaluation query deserialization failed: {}",
+                    err
+                ))
+            })?;
 
-        Ok(self.constraint_evaluations.take().expect("already read"))
+        if MerkleTree::verify_batch(commitment, positions, &constraint_proof).is_err() {
+            self.failed_check = Some(FailedCheck::ConstraintQueries {
+                positions: get_invalid_positions(commitment, positions, &constraint_proof),
+            });
+            return Err(VerifierError::ConstraintQueryDoesNotMatchCommitment);
+        }
+
+        Ok(constraint_evaluations)
+    }
+
+    /// Returns values of the committed columns at the specified positions of the LDE domain.
+    /// This also checks if the values are valid against the commitments to these columns.
+    pub fn read_committed_column_values(
+        &mut self,
+        positions: &[usize],
+        columns: &[CommittedColumn],
+    ) -> Result<Vec<Vec<B>>, VerifierError> {
+        let queries = self.committed_column_queries.take().expect("already read");
+        let mut parsed_columns = Vec::with_capacity(queries.len());
+        for (column, queries) in columns.iter().zip(queries) {
+            let (column_proof, column_values) = queries
+                .parse::<H, B>(self.lde_domain_size, positions.len(), 1)
+                .map_err(|err| {
+                    VerifierError::ProofDeserializationError(format!(
+                        "committed column query deserialization failed: {}",
+                        err
+                    ))
+                })?;
+            let commitment = column.parse_commitment::<H>().map_err(|_| {
+                VerifierError::CommittedColumnQueryDoesNotMatchCommitment(column.register())
+            })?;
+            parsed_columns.push((commitment, column_proof, column_values));
+        }
+
+        // make sure the values included in the proof correspond to the column commitments; when
+        // `concurrent` feature is enabled, columns are checked in multiple threads
+        let is_valid = iter!(parsed_columns)
+            .map(|(commitment, proof, _)| {
+                MerkleTree::verify_batch(commitment, positions, proof).is_ok()
+            })
+            .collect::<Vec<_>>();
+        if let Some(column_idx) = is_valid.iter().position(|&is_valid| !is_valid) {
+            let (commitment, column_proof, _) = &parsed_columns[column_idx];
+            let register = columns[column_idx].register();
+            self.failed_check = Some(FailedCheck::CommittedColumnQueries {
+                register,
+                positions: get_invalid_positions(commitment, positions, column_proof),
+            });
+            return Err(VerifierError::CommittedColumnQueryDoesNotMatchCommitment(
+                register,
+            ));
+        }
+
+        Ok(parsed_columns
+            .into_iter()
+            .map(|(_, _, column_values)| column_values.into_iter().flatten().collect())
+            .collect())
+    }
+
+    // DIAGNOSTICS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns details of the last query check which failed while reading values from this
+    /// channel, if any.
+    pub fn take_failed_check(&mut self) -> Option<FailedCheck<B>> {
+        self.failed_check.take()
+    }
+
+    /// Returns the number of FRI layer proofs which have been read from this channel so far.
+    pub fn num_read_fri_layers(&self) -> usize {
+        self.fri_num_layers - self.fri_layer_proofs.len()
     }
 }
 
@@ -205,7 +534,23 @@ where
     }
 
     fn read_fri_layer_commitments(&mut self) -> Vec<H::Digest> {
-        self.fri_roots.take().expect("already read")
+        self.fri_roots
+            .take()
+            .expect("already read")
+            .into_iter()
+            .collect()
+    }
+
+    fn read_fri_layer_pow_nonces(&mut self) -> Vec<u64> {
+        self.fri_layer_pow_nonces
+            .take()
+            .expect("already read")
+            .into_iter()
+            .collect()
+    }
+
+    fn take_fri_ood_evaluations(&mut self) -> Vec<E> {
+        self.fri_ood_evaluations.take().expect("already read")
     }
 
     fn take_next_fri_layer_proof(&mut self) -> BatchMerkleProof<H> {
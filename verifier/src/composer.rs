@@ -3,19 +3,26 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use air::{Air, DeepCompositionCoefficients, EvaluationFrame, FieldExtension};
-use math::FieldElement;
-use utils::collections::Vec;
+use air::{Air, CommittedColumn, DeepCompositionCoefficients, EvaluationFrame, FieldExtension};
+use math::{batch_inversion, FieldElement};
+use utils::{collections::Vec, iter};
+
+#[cfg(feature = "concurrent")]
+use utils::iterators::*;
 
 // DEEP COMPOSER
 // ================================================================================================
-
+/// Computes values of the DEEP composition polynomial at the queried positions.
+///
+/// Values at different positions are computed independently; when `concurrent` feature is
+/// enabled, they are computed in multiple threads.
 pub struct DeepComposer<A: Air, E: FieldElement + From<A::BaseElement>> {
     field_extension: FieldExtension,
     cc: DeepCompositionCoefficients<E>,
     x_coordinates: Vec<A::BaseElement>,
     z: E,
     next_z: E,
+    trace_length: usize,
 }
 
 impl<A: Air, E: FieldElement + From<A::BaseElement>> DeepComposer<A, E> {
@@ -29,8 +36,7 @@ impl<A: Air, E: FieldElement + From<A::BaseElement>> DeepComposer<A, E> {
         // compute LDE domain coordinates for all query positions
         let g_lde = air.lde_domain_generator();
         let domain_offset = air.domain_offset();
-        let x_coordinates: Vec<A::BaseElement> = query_positions
-            .iter()
+        let x_coordinates: Vec<A::BaseElement> = iter!(query_positions)
             .map(|&p| g_lde.exp((p as u64).into()) * domain_offset)
             .collect();
 
@@ -40,6 +46,7 @@ impl<A: Air, E: FieldElement + From<A::BaseElement>> DeepComposer<A, E> {
             x_coordinates,
             z,
             next_z: z * E::from(air.trace_domain_generator()),
+            trace_length: air.trace_length(),
         }
     }
 
@@ -72,34 +79,83 @@ impl<A: Air, E: FieldElement + From<A::BaseElement>> DeepComposer<A, E> {
         // z as well as conjugate of z itself
         let conjugate_values = get_conjugate_values(self.field_extension, trace_at_z1, self.z);
 
-        let mut result = Vec::with_capacity(queried_trace_states.len());
-        for (registers, &x) in queried_trace_states.iter().zip(&self.x_coordinates) {
-            let x = E::from(x);
-            let mut composition = E::ZERO;
-            for (i, &value) in registers.iter().enumerate() {
-                let value = E::from(value);
-                // compute T'_i(x) = (T_i(x) - T_i(z)) / (x - z)
-                let t1 = (value - trace_at_z1[i]) / (x - self.z);
-                // multiply it by a pseudo-random coefficient, and add the result to T(x)
-                composition += t1 * self.cc.trace[i].0;
-
-                // compute T''_i(x) = (T_i(x) - T_i(z * g)) / (x - z * g)
-                let t2 = (value - trace_at_z2[i]) / (x - self.next_z);
-                // multiply it by a pseudo-random coefficient, and add the result to T(x)
-                composition += t2 * self.cc.trace[i].1;
-
-                // when extension field is enabled compute
-                // T'''_i(x) = (T_i(x) - T_i(z_conjugate)) / (x - z_conjugate)
-                if let Some((z_conjugate, ref trace_at_z1_conjugates)) = conjugate_values {
-                    let t3 = (value - trace_at_z1_conjugates[i]) / (x - z_conjugate);
-                    composition += t3 * self.cc.trace[i].2;
+        iter!(queried_trace_states)
+            .zip(&self.x_coordinates)
+            .map(|(registers, &x)| {
+                let x = E::from(x);
+                let mut composition = E::ZERO;
+                for (i, &value) in registers.iter().enumerate() {
+                    let value = E::from(value);
+                    // compute T'_i(x) = (T_i(x) - T_i(z)) / (x - z)
+                    let t1 = (value - trace_at_z1[i]) / (x - self.z);
+                    // multiply it by a pseudo-random coefficient, and add the result to T(x)
+                    composition += t1 * self.cc.trace[i].0;
+
+                    // compute T''_i(x) = (T_i(x) - T_i(z * g)) / (x - z * g)
+                    let t2 = (value - trace_at_z2[i]) / (x - self.next_z);
+                    // multiply it by a pseudo-random coefficient, and add the result to T(x)
+                    composition += t2 * self.cc.trace[i].1;
+
+                    // when extension field is enabled compute
+                    // T'''_i(x) = (T_i(x) - T_i(z_conjugate)) / (x - z_conjugate)
+                    if let Some((z_conjugate, ref trace_at_z1_conjugates)) = conjugate_values {
+                        let t3 = (value - trace_at_z1_conjugates[i]) / (x - z_conjugate);
+                        composition += t3 * self.cc.trace[i].2;
+                    }
                 }
-            }
+                composition
+            })
+            .collect()
+    }
 
-            result.push(composition);
+    /// For each queried trace state, ties the registers to the committed public columns as
+    /// follows:
+    ///
+    /// - For a register r tied to a column with values C(x) at the queried positions, compute
+    ///   K(x) = cc * (T_r(x) - C(x)) / (x^n - 1), where cc is the coefficient for the column drawn
+    ///   from the public coin, and n is the length of the trace.
+    /// - Then, add together K(x) for all committed columns.
+    ///
+    /// K(x) is zero when the register is equal to the column; otherwise, K(x) is not a polynomial
+    /// as T_r(x) - C(x) is of degree smaller than n, and thus, the DEEP composition polynomial
+    /// fails the low-degree test.
+    ///
+    /// Note that values C(x) are received from the prover and passed into this function via the
+    /// `column_values` parameter (one list per column); these values must be checked against the
+    /// column commitments before this function is called.
+    pub fn compose_committed_columns(
+        &self,
+        queried_trace_states: &[Vec<A::BaseElement>],
+        columns: &[CommittedColumn],
+        column_values: &[Vec<A::BaseElement>],
+    ) -> Vec<E> {
+        assert_eq!(queried_trace_states.len(), self.x_coordinates.len());
+        if columns.is_empty() {
+            return vec![E::ZERO; self.x_coordinates.len()];
         }
 
-        result
+        // compute 1 / (x^n - 1) for all queried x; x is in the LDE domain, which is offset from
+        // the trace domain, and thus, x^n - 1 is never zero
+        let divisors = iter!(self.x_coordinates)
+            .map(|&x| x.exp((self.trace_length as u64).into()) - A::BaseElement::ONE)
+            .collect::<Vec<_>>();
+        let divisors = batch_inversion(&divisors);
+
+        iter!(queried_trace_states)
+            .zip(&divisors)
+            .enumerate()
+            .map(|(i, (registers, &divisor))| {
+                let mut composition = E::ZERO;
+                for ((column, values), &cc) in
+                    columns.iter().zip(column_values).zip(&self.cc.columns)
+                {
+                    // compute cc * (T_r(x) - C(x)) / (x^n - 1)
+                    let value = registers[column.register()];
+                    composition += cc * E::from((value - values[i]) * divisor);
+                }
+                composition
+            })
+            .collect()
     }
 
     /// For each queried set of composition polynomial column evaluations, combine evaluations
@@ -121,24 +177,23 @@ impl<A: Air, E: FieldElement + From<A::BaseElement>> DeepComposer<A, E> {
     ) -> Vec<E> {
         assert_eq!(queried_evaluations.len(), self.x_coordinates.len());
 
-        let mut result = Vec::with_capacity(queried_evaluations.len());
-
         // compute z^m
         let num_evaluation_columns = ood_evaluations.len() as u32;
         let z_m = self.z.exp(num_evaluation_columns.into());
 
-        for (query_values, &x) in queried_evaluations.iter().zip(&self.x_coordinates) {
-            let mut composition = E::ZERO;
-            for (i, &evaluation) in query_values.iter().enumerate() {
-                // compute H'_i(x) = (H_i(x) - H(z^m)) / (x - z^m)
-                let h_i = (evaluation - ood_evaluations[i]) / (E::from(x) - z_m);
-                // multiply it by a pseudo-random coefficient, and add the result to H(x)
-                composition += h_i * self.cc.constraints[i];
-            }
-            result.push(composition);
-        }
-
-        result
+        iter!(queried_evaluations)
+            .zip(&self.x_coordinates)
+            .map(|(query_values, &x)| {
+                let mut composition = E::ZERO;
+                for (i, &evaluation) in query_values.iter().enumerate() {
+                    // compute H'_i(x) = (H_i(x) - H(z^m)) / (x - z^m)
+                    let h_i = (evaluation - ood_evaluations[i]) / (E::from(x) - z_m);
+                    // multiply it by a pseudo-random coefficient, and add the result to H(x)
+                    composition += h_i * self.cc.constraints[i];
+                }
+                composition
+            })
+            .collect()
     }
 
     /// Combines trace and constraint compositions together, and also rases the degree of the
@@ -150,18 +205,19 @@ impl<A: Air, E: FieldElement + From<A::BaseElement>> DeepComposer<A, E> {
         assert_eq!(t_composition.len(), self.x_coordinates.len());
         assert_eq!(c_composition.len(), self.x_coordinates.len());
 
-        let mut result = Vec::with_capacity(self.x_coordinates.len());
-        for ((&x, t), c) in self.x_coordinates.iter().zip(t_composition).zip(c_composition) {
-            // compute C(x) by adding the two compositions together
-            let composition = t + c;
-
-            // raise the degree of C(x) by computing C'(x) = C(x) * (cc_0 + x * cc_1), where
-            // cc_0 and cc_1 are the coefficients for the random linear combination drawn from
-            // the public coin.
-            result.push(composition * (self.cc.degree.0 + E::from(x) * self.cc.degree.1));
-        }
-
-        result
+        iter!(self.x_coordinates)
+            .zip(t_composition)
+            .zip(c_composition)
+            .map(|((&x, t), c)| {
+                // compute C(x) by adding the two compositions together
+                let composition = t + c;
+
+                // raise the degree of C(x) by computing C'(x) = C(x) * (cc_0 + x * cc_1), where
+                // cc_0 and cc_1 are the coefficients for the random linear combination drawn from
+                // the public coin.
+                composition * (self.cc.degree.0 + E::from(x) * self.cc.degree.1)
+            })
+            .collect()
     }
 }
 
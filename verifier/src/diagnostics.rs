@@ -0,0 +1,216 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::VerifierError;
+use core::fmt;
+use crypto::{BatchMerkleProof, Hasher, MerkleTree};
+use math::{FieldElement, StarkField};
+use utils::collections::Vec;
+
+// VERIFICATION DIAGNOSTICS
+// ================================================================================================
+/// Describes why a STARK proof failed verification.
+///
+/// Diagnostics are returned by [verify_with_diagnostics()](crate::verify_with_diagnostics)
+/// function and are intended to localize incompatibilities between a prover and a verifier
+/// (e.g., a difference in constraint evaluation or in hashing of Merkle tree leaves). In
+/// addition to the error which would be returned by [verify()](crate::verify) function, they
+/// contain the step of the protocol at which verification failed and, for checks which involve
+/// many values, details of the values which did not pass the check.
+#[derive(Debug, PartialEq)]
+pub struct VerificationDiagnostics<B: StarkField> {
+    /// Error which would be returned by [verify()](crate::verify) function for the same proof.
+    pub error: VerifierError,
+    /// Step of the protocol at which verification failed.
+    pub stage: VerificationStage,
+    /// Details of the failed check, if available.
+    pub failed_check: Option<FailedCheck<B>>,
+}
+
+impl<B: StarkField> fmt::Display for VerificationDiagnostics<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} failed: {}", self.stage, self.error)?;
+        if let Some(check) = &self.failed_check {
+            write!(f, " ({})", check)?;
+        }
+        Ok(())
+    }
+}
+
+// VERIFICATION STAGE
+// ================================================================================================
+/// Defines the step of the verification protocol at which a proof was rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VerificationStage {
+    /// Parsing of the proof and instantiation of the AIR.
+    Setup,
+    /// Reading of trace and constraint commitments and drawing of composition coefficients.
+    Commitments,
+    /// Checking consistency of constraint evaluations at the out-of-domain point.
+    OodConsistency,
+    /// Reading of FRI layer commitments, including FRI layer proof-of-work.
+    FriCommitments,
+    /// Checking of query seed proof-of-work and drawing of query positions.
+    QueryProofOfWork,
+    /// Checking of trace, constraint, and committed column queries against their commitments.
+    Queries,
+    /// Verification of the FRI proof against the DEEP composition polynomial evaluations.
+    LowDegreeProof,
+}
+
+impl fmt::Display for VerificationStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Setup => write!(f, "verifier setup"),
+            Self::Commitments => write!(f, "commitment reading"),
+            Self::OodConsistency => write!(f, "out-of-domain consistency check"),
+            Self::FriCommitments => write!(f, "FRI commitment reading"),
+            Self::QueryProofOfWork => write!(f, "query proof-of-work check"),
+            Self::Queries => write!(f, "query verification"),
+            Self::LowDegreeProof => write!(f, "low-degree proof verification"),
+        }
+    }
+}
+
+// FAILED CHECK
+// ================================================================================================
+/// Details of a check which a STARK proof did not pass.
+///
+/// Field elements are represented by elements of the base field of the computation, in the
+/// order defined by `FieldElement::as_base_elements()`. Query positions are positions in the LDE
+/// domain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FailedCheck<B: StarkField> {
+    /// Constraints evaluated over the out-of-domain frame did not match the evaluation of the
+    /// constraint composition polynomial derived from the column evaluations sent by the prover.
+    OodConstraintEvaluations {
+        /// Random linear combination of constraints evaluated over the out-of-domain frame.
+        constraint_evaluation: Vec<B>,
+        /// Evaluation of the constraint composition polynomial derived from its columns.
+        composition_evaluation: Vec<B>,
+    },
+    /// Authentication paths of trace queries at the specified positions did not resolve to the
+    /// commitment of the specified trace segment.
+    TraceQueries {
+        /// Index of the trace segment.
+        segment: usize,
+        /// Positions with invalid authentication paths.
+        positions: Vec<usize>,
+    },
+    /// Authentication paths of constraint evaluation queries at the specified positions did not
+    /// resolve to the constraint commitment.
+    ConstraintQueries {
+        /// Positions with invalid authentication paths.
+        positions: Vec<usize>,
+    },
+    /// Authentication paths of queries of the column committed to the specified register did not
+    /// resolve to the commitment of the column at the specified positions.
+    CommittedColumnQueries {
+        /// Register to which the column is tied.
+        register: usize,
+        /// Positions with invalid authentication paths.
+        positions: Vec<usize>,
+    },
+    /// Queries of the specified FRI layer did not match the commitment to this layer.
+    FriLayerQueries {
+        /// Index of the FRI layer.
+        layer: usize,
+    },
+}
+
+impl<B: StarkField> fmt::Display for FailedCheck<B> {
+    #[rustfmt::skip]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OodConstraintEvaluations { constraint_evaluation, composition_evaluation } => {
+                write!(f, "constraint evaluation {:?} does not match composition evaluation {:?}", constraint_evaluation, composition_evaluation)
+            }
+            Self::TraceQueries { segment, positions } => {
+                write!(f, "invalid paths for trace segment {} at positions {:?}", segment, positions)
+            }
+            Self::ConstraintQueries { positions } => {
+                write!(f, "invalid constraint evaluation paths at positions {:?}", positions)
+            }
+            Self::CommittedColumnQueries { register, positions } => {
+                write!(f, "invalid paths for committed column of register {} at positions {:?}", register, positions)
+            }
+            Self::FriLayerQueries { layer } => {
+                write!(f, "queries do not match commitment of FRI layer {}", layer)
+            }
+        }
+    }
+}
+
+// DIAGNOSTICS BUILDER
+// ================================================================================================
+/// Tracks progress of the verification procedure and records details of failed checks.
+pub struct DiagnosticsBuilder<B: StarkField> {
+    stage: VerificationStage,
+    failed_check: Option<FailedCheck<B>>,
+}
+
+impl<B: StarkField> DiagnosticsBuilder<B> {
+    /// Returns a new builder which starts at the setup stage.
+    pub fn new() -> Self {
+        DiagnosticsBuilder {
+            stage: VerificationStage::Setup,
+            failed_check: None,
+        }
+    }
+
+    /// Records that verification has advanced to the specified `stage`.
+    pub fn enter(&mut self, stage: VerificationStage) {
+        self.stage = stage;
+    }
+
+    /// Records details of the check which failed at the current stage.
+    pub fn record_failed_check(&mut self, check: Option<FailedCheck<B>>) {
+        self.failed_check = check;
+    }
+
+    /// Records out-of-domain evaluations which did not match.
+    pub fn record_ood_mismatch<E: FieldElement<BaseField = B>>(
+        &mut self,
+        constraint_evaluation: E,
+        composition_evaluation: E,
+    ) {
+        self.failed_check = Some(FailedCheck::OodConstraintEvaluations {
+            constraint_evaluation: E::as_base_elements(&[constraint_evaluation]).to_vec(),
+            composition_evaluation: E::as_base_elements(&[composition_evaluation]).to_vec(),
+        });
+    }
+
+    /// Consumes this builder and returns diagnostics for the specified `error`.
+    pub fn into_diagnostics(self, error: VerifierError) -> VerificationDiagnostics<B> {
+        VerificationDiagnostics {
+            error,
+            stage: self.stage,
+            failed_check: self.failed_check,
+        }
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns positions for which individual authentication paths unpacked from the batch `proof`
+/// do not resolve to the specified `root`.
+///
+/// If the paths cannot be unpacked from the proof, all positions are returned.
+pub fn get_invalid_positions<H: Hasher>(
+    root: &H::Digest,
+    positions: &[usize],
+    proof: &BatchMerkleProof<H>,
+) -> Vec<usize> {
+    match proof.get_paths(positions) {
+        Ok(paths) => positions
+            .iter()
+            .zip(paths.iter())
+            .filter(|(&position, path)| MerkleTree::<H>::verify(*root, position, path).is_err())
+            .map(|(&position, _)| position)
+            .collect(),
+        Err(_) => positions.to_vec(),
+    }
+}
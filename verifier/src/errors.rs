@@ -8,6 +8,9 @@
 use core::fmt;
 use utils::string::String;
 
+#[cfg(not(feature = "std"))]
+use alloc::boxed::Box;
+
 // VERIFIER ERROR
 // ================================================================================================
 /// Represents an error returned by the verifier during an execution of the protocol.
@@ -30,6 +33,12 @@ pub enum VerifierError {
     /// This error occurs when Merkle authentication paths of constraint evaluation queries do not
     /// resolve to the constraint evaluation commitment included in the proof.
     ConstraintQueryDoesNotMatchCommitment,
+    /// This error occurs when Merkle authentication paths of committed column queries do not
+    /// resolve to the commitment of the column tied to the specified register.
+    CommittedColumnQueryDoesNotMatchCommitment(usize),
+    /// This error occurs when a committed column is tied to a register which does not exist in
+    /// the execution trace.
+    InvalidCommittedColumnRegister(usize),
     /// This error occurs when the proof-of-work nonce hashed with the current state of the public
     /// coin resolves to a value which does not meet the proof-of-work threshold specified by the
     // proof options.
@@ -38,6 +47,26 @@ pub enum VerifierError {
     /// constraint evaluation queries do not represent a polynomial of the degree expected by the
     /// verifier.
     FriVerificationFailed(fri::VerifierError),
+    /// This error occurs when the trace width of a batch proof is not a multiple of the number
+    /// of instances in the batch.
+    InconsistentBatchSize,
+    /// This error occurs when the LDE domain implied by the proof context is larger than the
+    /// largest multiplicative subgroup of the base field; the log2 of the domain size and the
+    /// two-adicity of the field are reported.
+    LdeDomainTooLarge(u32, u32),
+    /// This error occurs when the domain of the low-degree test instantiated for the proof is
+    /// not the LDE domain of the computation; the size of the LDE domain and the size of the
+    /// low-degree test domain are reported.
+    InconsistentLowDegreeDomain(usize, usize),
+    /// This error occurs when one of the proofs verified together via `verify_many()` function
+    /// is invalid; the index of the first invalid proof is reported together with the reason.
+    ProofVerificationFailed(usize, Box<VerifierError>),
+    /// This error occurs when options with which the proof was generated are not among the
+    /// options acceptable to the verifier.
+    UnacceptableProofOptions,
+    /// This error occurs when a proof read by the verifier was serialized using a format version
+    /// newer than the latest version supported by the verifier; both versions are reported.
+    UnsupportedProofVersion(u8, u8),
 }
 
 impl fmt::Display for VerifierError {
@@ -62,12 +91,47 @@ impl fmt::Display for VerifierError {
             Self::ConstraintQueryDoesNotMatchCommitment => {
                 write!(f, "constraint query did not match the commitment")
             }
+            Self::CommittedColumnQueryDoesNotMatchCommitment(register) => {
+                write!(f, "committed column query for register {} did not match the commitment", register)
+            }
+            Self::InvalidCommittedColumnRegister(register) => {
+                write!(f, "committed column is tied to register {} which is not in the trace", register)
+            }
             Self::QuerySeedProofOfWorkVerificationFailed => {
                 write!(f, "query seed proof-of-work verification failed")
             }
             Self::FriVerificationFailed(err) => {
                 write!(f, "verification of low-degree proof failed: {}", err)
             }
+            Self::InconsistentBatchSize => {
+                write!(f, "trace width of the proof is not a multiple of the number of instances in the batch")
+            }
+            Self::LdeDomainTooLarge(domain_size_log2, two_adicity) => {
+                write!(f, "LDE domain of size 2^{} is not supported by a base field with two-adicity {}", domain_size_log2, two_adicity)
+            }
+            Self::InconsistentLowDegreeDomain(lde_domain_size, domain_size) => {
+                write!(f, "low-degree test domain of size {} is inconsistent with LDE domain of size {}", domain_size, lde_domain_size)
+            }
+            Self::ProofVerificationFailed(index, err) => {
+                write!(f, "verification of proof {} failed: {}", index, err)
+            }
+            Self::UnacceptableProofOptions => {
+                write!(f, "options of the proof are not acceptable to the verifier")
+            }
+            Self::UnsupportedProofVersion(version, latest) => {
+                write!(f, "proof version {} is not supported; the latest supported version is {}", version, latest)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for VerifierError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::FriVerificationFailed(err) => Some(err),
+            Self::ProofVerificationFailed(_, err) => Some(err.as_ref()),
+            _ => None,
         }
     }
 }
@@ -13,7 +13,15 @@
 //! 1. Define an *algebraic intermediate representation* (AIR) for you computation. This can be
 //!    done by implementing [Air] trait.
 //! 2. Execute [verify()] function and supply the AIR of your computation together with the
-//!    [StarkProof] and related public inputs as parameters.
+//!    [StarkProof], related public inputs, and the set of [AcceptableOptions] as parameters.
+//!
+//! # Acceptable options
+//! Proof options are chosen by the prover and are included in the proof. To make sure that a
+//! proof provides the soundness expected by the verifier, [verify()] function checks options of
+//! the proof against the specified [AcceptableOptions] before verifying the proof. Acceptable
+//! options can be defined as an explicit set of options, or as a set of restrictions on the
+//! number of queries, blowup factor, grinding factor, hash function, and security level of a
+//! proof.
 //!
 //! # Performance
 //! Proof verification is extremely fast and is nearly independent of the complexity of the
@@ -25,6 +33,13 @@
 //! asserted values. But for the impact to be noticeable, the number of asserted values would
 //! need to be in tens of thousands. And even for hundreds of thousands of asserted values, the
 //! verification time should not exceed 50 ms.
+//!
+//! # Proof-of-work
+//! Proof-of-work requirements are specified via [ProofOptions] and are enforced by the verifier
+//! as a part of the protocol: the query seed must satisfy the grinding factor before query
+//! positions are drawn, and, if FRI layer grinding is enabled, every FRI layer commitment must be
+//! accompanied by a nonce satisfying the FRI layer grinding factor before the folding challenge
+//! for that layer is drawn. Proofs which fail either check are rejected.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -32,29 +47,45 @@
 #[macro_use]
 extern crate alloc;
 
+use air::proof::Context;
 pub use air::{
-    proof::StarkProof, Air, AirContext, Assertion, BoundaryConstraint, BoundaryConstraintGroup,
-    ConstraintCompositionCoefficients, ConstraintDivisor, DeepCompositionCoefficients,
-    EvaluationFrame, FieldExtension, HashFunction, ProofOptions, TraceInfo,
-    TransitionConstraintDegree, TransitionConstraintGroup,
+    proof::{ProofLimits, StarkProof},
+    Air, AirContext, Assertion, BatchAir, BoundaryConstraint, BoundaryConstraintGroup,
+    CommittedColumn, CommittedInputsAir, ConstraintCompositionCoefficients, ConstraintDivisor,
+    ConstraintReport, DeepCompositionCoefficients, DistinctSampler, EvaluationFrame,
+    FieldExtension, HashFunction, PeriodicMask, ProofOptions, QuerySampler, QuerySampling,
+    StratifiedSampler, TraceInfo, TracePadding, TransitionConstraintDegree,
+    TransitionConstraintGroup, UniformSampler,
 };
 
 pub use math;
-use math::{FieldElement, StarkField};
+use math::{add_in_place, FieldElement, StarkField};
 
-use utils::collections::Vec;
-pub use utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+#[cfg(not(feature = "std"))]
+use alloc::boxed::Box;
+#[cfg(feature = "concurrent")]
+use utils::iterators::*;
+use utils::{
+    collections::{BoundedVec, Vec},
+    string::ToString,
+};
+#[cfg(feature = "std")]
+pub use utils::{BufReadAdapter, ReadAdapter};
+pub use utils::{
+    ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable, SliceReader,
+};
 
 pub use crypto;
 use crypto::{
     hashers::{Blake3_192, Blake3_256, Sha3_256},
     ElementHasher, RandomCoin,
 };
+pub use crypto::{PublicCoin, Transcript};
 
-use fri::FriVerifier;
+use fri::{FriVerifier, LowDegreeTest, LowDegreeVerifier, StirVerifier};
 
 mod channel;
-use channel::VerifierChannel;
+use channel::{VerifierChannel, MAX_TRACE_SEGMENTS};
 
 mod evaluator;
 use evaluator::evaluate_constraints;
@@ -65,6 +96,20 @@ use composer::DeepComposer;
 mod errors;
 pub use errors::VerifierError;
 
+mod witness;
+use witness::WitnessBuilder;
+pub use witness::{ProofWitness, QueryWitness};
+
+mod output;
+pub use output::VerificationOutput;
+
+mod options;
+pub use options::AcceptableOptions;
+
+mod diagnostics;
+use diagnostics::DiagnosticsBuilder;
+pub use diagnostics::{FailedCheck, VerificationDiagnostics, VerificationStage};
+
 // VERIFIER
 // ================================================================================================
 /// Verifies that the specified computation was executed correctly against the specified inputs.
@@ -73,101 +118,896 @@ pub use errors::VerifierError;
 /// `proof` attests to the correct execution of the computation against public inputs specified
 /// by `pub_inputs`. If the verification is successful, `Ok(())` is returned.
 ///
+/// Before the proof is verified, options with which the proof was generated are checked against
+/// `acceptable_options`; proofs generated with options which are not acceptable are rejected.
+///
 /// # Errors
-/// Returns an error if combination of the provided proof and public inputs does not attest to
-/// a correct execution of the computation. This could happen for many various reasons, including:
+/// Returns an error if options of the proof are not acceptable, or if combination of the
+/// provided proof and public inputs does not attest to a correct execution of the computation.
+/// This could happen for many various reasons, including:
 /// - The specified proof was generated for a different computation.
 /// - The specified proof was generated for this computation but for different public inputs.
-#[rustfmt::skip]
 pub fn verify<AIR: Air>(
     proof: StarkProof,
     pub_inputs: AIR::PublicInputs,
+    acceptable_options: &AcceptableOptions,
 ) -> Result<(), VerifierError> {
-    // build a seed for the public coin; the initial seed is the hash of public inputs and proof
-    // context, but as the protocol progresses, the coin will be reseeded with the info received
-    // from the prover
-    let mut public_coin_seed = Vec::new();
-    pub_inputs.write_into(&mut public_coin_seed);
-    proof.context.write_into(&mut public_coin_seed);
+    verify_proof::<AIR, SliceReader>(
+        ProofSource::Proof(Box::new(proof)),
+        pub_inputs.to_bytes(),
+        |trace_info, options| AIR::new(trace_info, pub_inputs, options),
+        acceptable_options,
+        None,
+        None,
+        None,
+    )
+}
 
-    // create AIR instance for the computation specified in the proof
-    let air = AIR::new(proof.get_trace_info(), pub_inputs, proof.options().clone());
+/// Verifies the specified proof and returns the values read and drawn by the verifier in the
+/// process.
+///
+/// The returned [ProofWitness] contains all challenges drawn by the verifier, all values opened
+/// at the queried positions together with their individual Merkle authentication paths, and all
+/// FRI folding data. It is intended to be used as a witness for an external circuit which
+/// verifies the proof (e.g., to wrap a STARK proof into a Groth16 or Plonk proof). See
+/// [ProofWitness] for the description of the witness format.
+///
+/// # Errors
+/// Returns an error for any of the reasons listed for [verify()] function; a witness is
+/// returned only for valid proofs.
+pub fn export_witness<AIR: Air>(
+    proof: StarkProof,
+    pub_inputs: AIR::PublicInputs,
+    acceptable_options: &AcceptableOptions,
+) -> Result<ProofWitness<AIR::BaseElement>, VerifierError> {
+    let mut witness = WitnessBuilder::new(proof.clone());
+    verify_proof::<AIR, SliceReader>(
+        ProofSource::Proof(Box::new(proof)),
+        pub_inputs.to_bytes(),
+        |trace_info, options| AIR::new(trace_info, pub_inputs, options),
+        acceptable_options,
+        Some(&mut witness),
+        None,
+        None,
+    )?;
+    Ok(witness.into_witness())
+}
+
+/// Verifies the specified proof and returns its commitments, out-of-domain evaluations, and the
+/// challenges drawn by the verifier.
+///
+/// This function accepts and rejects exactly the same proofs as [verify()] function, but for a
+/// valid proof it also returns a [VerificationOutput] containing the trace, constraint, and FRI
+/// layer commitments, the out-of-domain frame and constraint evaluations, and the challenges
+/// derived from the public coin. Unlike [export_witness()], this function does not copy the
+/// proof and does not extract the values opened at the queried positions.
+///
+/// # Errors
+/// Returns an error for any of the reasons listed for [verify()] function; the output is
+/// returned only for valid proofs.
+pub fn verify_with_output<AIR: Air>(
+    proof: StarkProof,
+    pub_inputs: AIR::PublicInputs,
+    acceptable_options: &AcceptableOptions,
+) -> Result<VerificationOutput<AIR::BaseElement>, VerifierError> {
+    let mut witness = WitnessBuilder::without_queries();
+    verify_proof::<AIR, SliceReader>(
+        ProofSource::Proof(Box::new(proof)),
+        pub_inputs.to_bytes(),
+        |trace_info, options| AIR::new(trace_info, pub_inputs, options),
+        acceptable_options,
+        Some(&mut witness),
+        None,
+        None,
+    )?;
+    Ok(VerificationOutput::from_witness(witness.into_witness()))
+}
+
+/// Verifies the specified proof and returns the result of verification together with the
+/// commitments, out-of-domain evaluations, and challenges read and drawn by the verifier.
+///
+/// This function accepts and rejects exactly the same proofs as [verify()] function. Unlike
+/// [verify_with_output()], it returns the [VerificationOutput] for invalid proofs as well: the
+/// output contains the values recorded up to the point at which verification finished or failed,
+/// and values recorded after that point are left empty. The output can be printed (or, with the
+/// `serde` feature enabled, serialized into JSON) with commitments rendered as hex strings and
+/// all values labeled, which makes it possible to compare the views of the prover and the
+/// verifier when debugging incompatibilities between them.
+///
+/// If the proof is rejected before the commitments are read (e.g., because its options are not
+/// acceptable), the returned output is empty.
+pub fn inspect_proof<AIR: Air>(
+    proof: StarkProof,
+    pub_inputs: AIR::PublicInputs,
+    acceptable_options: &AcceptableOptions,
+) -> (
+    Result<(), VerifierError>,
+    VerificationOutput<AIR::BaseElement>,
+) {
+    let mut witness = WitnessBuilder::without_queries();
+    let result = verify_proof::<AIR, SliceReader>(
+        ProofSource::Proof(Box::new(proof)),
+        pub_inputs.to_bytes(),
+        |trace_info, options| AIR::new(trace_info, pub_inputs, options),
+        acceptable_options,
+        Some(&mut witness),
+        None,
+        None,
+    );
+    (
+        result,
+        VerificationOutput::from_witness(witness.into_witness()),
+    )
+}
+
+/// Checks only that the out-of-domain evaluations in the specified proof are consistent with the
+/// constraints of the computation; this is NOT a full verification of the proof.
+///
+/// The proof context is checked against `acceptable_options`, the verifier's challenges are
+/// derived from the trace and constraint commitments exactly as in [verify()] function, and the
+/// constraints are evaluated over the out-of-domain frame sent by the prover. Queries, the FRI
+/// proof, and proof-of-work are not checked, and queries are not even parsed. Thus, this check is
+/// much cheaper than [verify()], and is intended for fast pre-filtering of obviously invalid
+/// proofs (e.g., proofs for different public inputs, or random bytes) in services exposed to
+/// denial-of-service attacks.
+///
+/// A prover can trivially produce a proof which passes this check without executing the
+/// computation. Thus, a proof which passes this check must still be verified via [verify()]
+/// function before it is accepted; only the failure of this check is conclusive.
+///
+/// # Errors
+/// Returns an error if options of the proof are not acceptable, if the commitments or the
+/// out-of-domain frame of the proof are malformed, or if the out-of-domain evaluations are not
+/// consistent with the constraints of the computation.
+#[rustfmt::skip]
+pub fn check_ood_consistency<AIR: Air>(
+    proof: StarkProof,
+    pub_inputs: AIR::PublicInputs,
+    acceptable_options: &AcceptableOptions,
+) -> Result<(), VerifierError> {
+    let source = ProofSource::<SliceReader>::Proof(Box::new(proof));
+    let (air, seed) = build_air_and_seed(
+        &source,
+        pub_inputs.to_bytes(),
+        |trace_info, options| AIR::new(trace_info, pub_inputs, options),
+        acceptable_options,
+    )?;
+
+    match air.options().field_extension() {
+        FieldExtension::None => match air.options().hash_fn() {
+            HashFunction::Blake3_256 => perform_ood_check::
+                <AIR, AIR::BaseElement, Blake3_256<AIR::BaseElement>, _>(air, source, &seed),
+            HashFunction::Blake3_192 => perform_ood_check::
+                <AIR, AIR::BaseElement, Blake3_192<AIR::BaseElement>, _>(air, source, &seed),
+            HashFunction::Sha3_256 => perform_ood_check::
+                <AIR, AIR::BaseElement, Sha3_256<AIR::BaseElement>, _>(air, source, &seed),
+        },
+        FieldExtension::Quadratic => match air.options().hash_fn() {
+            HashFunction::Blake3_256 => perform_ood_check::
+                <AIR, <AIR::BaseElement as StarkField>::QuadExtension, Blake3_256<AIR::BaseElement>, _>
+                (air, source, &seed),
+            HashFunction::Blake3_192 => perform_ood_check::
+                <AIR, <AIR::BaseElement as StarkField>::QuadExtension, Blake3_192<AIR::BaseElement>, _>
+                (air, source, &seed),
+            HashFunction::Sha3_256 => perform_ood_check::
+                <AIR, <AIR::BaseElement as StarkField>::QuadExtension, Sha3_256<AIR::BaseElement>, _>
+                (air, source, &seed),
+        },
+    }
+}
+
+/// Verifies the specified proof and, if verification fails, returns diagnostics describing
+/// which check the proof did not pass.
+///
+/// This function accepts and rejects exactly the same proofs as [verify()] function, but in
+/// case of a failure, the returned [VerificationDiagnostics] contain, in addition to the error,
+/// the [VerificationStage] at which verification failed and, where available, the details of the
+/// [FailedCheck]: the values which failed the out-of-domain consistency check, the query
+/// positions for which Merkle authentication paths were invalid, or the FRI layer at which
+/// queries did not match the layer commitment. This is intended to help with debugging
+/// incompatibilities between the prover and the verifier.
+///
+/// # Errors
+/// Returns diagnostics for any of the reasons listed for [verify()] function.
+pub fn verify_with_diagnostics<AIR: Air>(
+    proof: StarkProof,
+    pub_inputs: AIR::PublicInputs,
+    acceptable_options: &AcceptableOptions,
+) -> Result<(), VerificationDiagnostics<AIR::BaseElement>> {
+    let mut diagnostics = DiagnosticsBuilder::new();
+    verify_proof::<AIR, SliceReader>(
+        ProofSource::Proof(Box::new(proof)),
+        pub_inputs.to_bytes(),
+        |trace_info, options| AIR::new(trace_info, pub_inputs, options),
+        acceptable_options,
+        None,
+        Some(&mut diagnostics),
+        None,
+    )
+    .map_err(|err| diagnostics.into_diagnostics(err))
+}
+
+/// Verifies the specified proof and returns the result of verification together with the
+/// transcript of the public coin of the verifier.
+///
+/// This function accepts and rejects exactly the same proofs as [verify()] function. The
+/// returned [Transcript] contains, in order, the seed of the public coin, every value absorbed
+/// into the coin, and every challenge drawn from it up to the point at which verification
+/// finished or failed. The prover records the same transcript into the artifacts returned by
+/// `prove_with_artifacts()` function of the prover crate; for a valid proof the two transcripts
+/// are identical, and otherwise [Transcript::first_divergence()] identifies the first step at
+/// which the views of the prover and the verifier differ. A transcript can also be checked on its
+/// own via [Transcript::replay()].
+///
+/// If the proof is rejected before the public coin is instantiated (e.g., because its options
+/// are not acceptable), the returned transcript is empty.
+pub fn verify_with_transcript<AIR: Air>(
+    proof: StarkProof,
+    pub_inputs: AIR::PublicInputs,
+    acceptable_options: &AcceptableOptions,
+) -> (Result<(), VerifierError>, Transcript) {
+    let mut transcript = Transcript::default();
+    let result = verify_proof::<AIR, SliceReader>(
+        ProofSource::Proof(Box::new(proof)),
+        pub_inputs.to_bytes(),
+        |trace_info, options| AIR::new(trace_info, pub_inputs, options),
+        acceptable_options,
+        None,
+        None,
+        Some(&mut transcript),
+    );
+    (result, transcript)
+}
+
+/// Verifies that the specified computation was executed correctly against public inputs with the
+/// specified commitment.
+///
+/// The `proof` must have been generated via `prove_with_input_commitment()` function of the
+/// prover crate. The AIR is instantiated via [CommittedInputsAir::from_input_commitment()]
+/// function, and the `commitment` is used in place of the public inputs to seed the public coin;
+/// thus, the verifier never needs the public inputs themselves. See [CommittedInputsAir] for more
+/// info on how the commitment must be bound to the computation.
+///
+/// # Errors
+/// Returns an error for any of the reasons listed for [verify()] function; this includes proofs
+/// generated for public inputs with a different commitment.
+pub fn verify_with_input_commitment<AIR: CommittedInputsAir>(
+    proof: StarkProof,
+    commitment: AIR::InputCommitment,
+    acceptable_options: &AcceptableOptions,
+) -> Result<(), VerifierError> {
+    verify_proof::<AIR, SliceReader>(
+        ProofSource::Proof(Box::new(proof)),
+        commitment.to_bytes(),
+        |trace_info, options| AIR::from_input_commitment(trace_info, commitment, options),
+        acceptable_options,
+        None,
+        None,
+        None,
+    )
+}
+
+/// Verifies the specified proof using a public coin of type `P` to derive the verifier's
+/// challenges.
+///
+/// [verify()] function derives challenges via [RandomCoin] instantiated with the hash function
+/// specified in the proof options. This function instead uses the specified implementation of
+/// [PublicCoin], which makes it possible to verify proofs generated by systems which derive
+/// challenges via a different transcript construction (e.g., a sponge over field elements)
+/// without changing the rest of the protocol. The hash function of the coin is also used to
+/// verify all commitments in the proof; thus, the hash function specified in the proof options
+/// is not used by this function, but it can still be restricted via `acceptable_options`.
+///
+/// For a proof to be accepted, the prover must have absorbed the same values into a coin of type
+/// `P` instantiated with the same seed (i.e., the public inputs followed by the proof context).
+///
+/// # Errors
+/// Returns an error for any of the reasons listed for [verify()] function.
+pub fn verify_with_coin<AIR, P>(
+    proof: StarkProof,
+    pub_inputs: AIR::PublicInputs,
+    acceptable_options: &AcceptableOptions,
+) -> Result<(), VerifierError>
+where
+    AIR: Air,
+    P: PublicCoin<BaseField = AIR::BaseElement>,
+    P::Hasher: ElementHasher<BaseField = AIR::BaseElement>,
+{
+    let source = ProofSource::<SliceReader>::Proof(Box::new(proof));
+    let (air, public_coin_seed) = build_air_and_seed(
+        &source,
+        pub_inputs.to_bytes(),
+        |trace_info, options| AIR::new(trace_info, pub_inputs, options),
+        acceptable_options,
+    )?;
+
+    // the hash function is defined by the coin; thus, the dispatch is performed only over the
+    // extension field
+    let public_coin = P::new(&public_coin_seed);
+    match air.options().field_extension() {
+        FieldExtension::None => {
+            let (channel, stream) = source.open_channel(&air)?;
+            perform_verification::<AIR, AIR::BaseElement, P::Hasher, SliceReader, P>(
+                air,
+                channel,
+                public_coin,
+                stream,
+                None,
+                None,
+                None,
+            )
+        }
+        FieldExtension::Quadratic => {
+            let (channel, stream) = source.open_channel(&air)?;
+            perform_verification::<
+                AIR,
+                <AIR::BaseElement as StarkField>::QuadExtension,
+                P::Hasher,
+                SliceReader,
+                P,
+            >(air, channel, public_coin, stream, None, None, None)
+        }
+    }
+}
+
+/// Verifies that the specified batch proof attests to correct executions of many instances of
+/// the same computation.
+///
+/// The `proof` must have been generated via `prove_batch()` function of the prover crate, and
+/// `pub_inputs` must contain public inputs of every instance in the same order as the traces
+/// were passed to the prover. See [BatchAir] for more info.
+///
+/// All instances share trace and constraint commitments, the out-of-domain frame, and a single
+/// FRI proof; these are checked once against the public inputs of all instances. Thus, this is
+/// also the verifier for aggregated proofs of many instances of a computation.
+///
+/// # Errors
+/// Returns an error if `pub_inputs` is empty, if the trace width of the proof is not a multiple
+/// of the number of instances, or for any of the reasons listed for [verify()] function.
+#[doc(alias = "verify_aggregate")]
+pub fn verify_batch<AIR: Air>(
+    proof: StarkProof,
+    pub_inputs: Vec<AIR::PublicInputs>,
+    acceptable_options: &AcceptableOptions,
+) -> Result<(), VerifierError> {
+    let num_instances = pub_inputs.len();
+    if num_instances == 0 || !proof.context.trace_width().is_multiple_of(num_instances) {
+        return Err(VerifierError::InconsistentBatchSize);
+    }
+    verify::<BatchAir<AIR>>(proof, pub_inputs, acceptable_options)
+}
+
+/// Verifies that each of the specified proofs attests to a correct execution of the same
+/// computation against its public inputs.
+///
+/// Each of the `proofs` is verified against its own transcript and the same `acceptable_options`
+/// exactly as it would be verified via [verify()] function, and thus, soundness of each proof is
+/// not affected by the other proofs. When the crate is compiled with `concurrent` feature
+/// enabled, the proofs are verified in multiple threads; this makes the function suitable for
+/// verifying many independent proofs at once (e.g., all proofs included in a block).
+///
+/// # Errors
+/// Returns [VerifierError::ProofVerificationFailed] with the index of the first invalid proof
+/// and the reason it is invalid if any of the proofs does not attest to a correct execution of
+/// the computation. The reported proof is the same regardless of the number of threads.
+pub fn verify_many<AIR: Air>(
+    proofs: Vec<(StarkProof, AIR::PublicInputs)>,
+    acceptable_options: &AcceptableOptions,
+) -> Result<(), VerifierError>
+where
+    AIR::PublicInputs: Send,
+{
+    #[cfg(not(feature = "concurrent"))]
+    let results = proofs
+        .into_iter()
+        .map(|(proof, pub_inputs)| verify::<AIR>(proof, pub_inputs, acceptable_options));
+
+    #[cfg(feature = "concurrent")]
+    let results = proofs
+        .into_par_iter()
+        .map(|(proof, pub_inputs)| verify::<AIR>(proof, pub_inputs, acceptable_options))
+        .collect::<Vec<_>>();
+
+    for (i, result) in results.into_iter().enumerate() {
+        result.map_err(|err| VerifierError::ProofVerificationFailed(i, err.into()))?;
+    }
+    Ok(())
+}
+
+/// Verifies a proof read from the specified `source` as it is being read.
+///
+/// The proof must have been serialized via `StarkProof::to_streaming_bytes()` method. In this
+/// layout, values needed to draw the verifier's challenges precede the queries; thus, instead of
+/// reading the entire proof into memory first, the verifier reads the proof context, the
+/// commitments, and the out-of-domain frame, draws the challenges, and only then reads the trace
+/// and constraint queries followed by the FRI layers. Each part of the proof is parsed and checked
+/// when it is read, and its serialized form is discarded before the next part is read. This makes
+/// the function suitable for verifying proofs received over a network connection on
+/// memory-constrained devices; an `std::io::Read` source can be used via `ReadAdapter`.
+///
+/// Verification stops at the first invalid value; in this case, not all bytes of the proof may
+/// have been read from the `source`. On success, the source is positioned right after the proof.
+///
+/// # Errors
+/// Returns an error if a proof could not be read from the `source`, or for any of the reasons
+/// listed for [verify()] function.
+pub fn verify_from_reader<AIR: Air, R: ByteReader>(
+    source: &mut R,
+    pub_inputs: AIR::PublicInputs,
+    acceptable_options: &AcceptableOptions,
+) -> Result<(), VerifierError> {
+    let (_version, context) = StarkProof::read_header_from(source).map_err(|err| match err {
+        DeserializationError::UnsupportedVersion(version, latest) => {
+            VerifierError::UnsupportedProofVersion(version, latest)
+        }
+        err => VerifierError::ProofDeserializationError(err.to_string()),
+    })?;
+    verify_proof::<AIR, R>(
+        ProofSource::Stream(context, source),
+        pub_inputs.to_bytes(),
+        |trace_info, options| AIR::new(trace_info, pub_inputs, options),
+        acceptable_options,
+        None,
+        None,
+        None,
+    )
+}
+
+/// Verifies the proof read from the specified `source` against the specified acceptable options,
+/// recording the values read and drawn by the verifier into the `witness` builder, the progress
+/// of verification into the `diagnostics` builder, and the transcript of the public coin into
+/// `transcript`, if these are provided.
+///
+/// The public coin is seeded with `pub_inputs_bytes`, and the AIR is instantiated via `build_air`
+/// only after the proof context has been checked.
+#[rustfmt::skip]
+fn verify_proof<AIR: Air, R: ByteReader>(
+    source: ProofSource<R>,
+    pub_inputs_bytes: Vec<u8>,
+    build_air: impl FnOnce(TraceInfo, ProofOptions) -> AIR,
+    acceptable_options: &AcceptableOptions,
+    witness: Option<&mut WitnessBuilder<AIR::BaseElement>>,
+    diagnostics: Option<&mut DiagnosticsBuilder<AIR::BaseElement>>,
+    transcript: Option<&mut Transcript>,
+) -> Result<(), VerifierError> {
+    let (air, public_coin_seed) =
+        build_air_and_seed(&source, pub_inputs_bytes, build_air, acceptable_options)?;
 
     // figure out which version of the generic proof verification procedure to run. this is a sort
     // of static dispatch for selecting two generic parameter: extension field and hash function.
     match air.options().field_extension() {
         FieldExtension::None => match air.options().hash_fn() {
             HashFunction::Blake3_256 => {
-                let public_coin = RandomCoin::new(&public_coin_seed);
-                let channel = VerifierChannel::new(&air, proof)?;
+                let public_coin = build_public_coin(&public_coin_seed, transcript.is_some());
+                let (channel, stream) = source.open_channel(&air)?;
                 perform_verification::
-                    <AIR, AIR::BaseElement, Blake3_256<AIR::BaseElement>>
-                    (air, channel, public_coin)
+                    <AIR, AIR::BaseElement, Blake3_256<AIR::BaseElement>, R, _>
+                    (air, channel, public_coin, stream, witness, diagnostics, transcript)
             }
             HashFunction::Blake3_192 => {
-                let public_coin = RandomCoin::new(&public_coin_seed);
-                let channel = VerifierChannel::new(&air, proof)?;
+                let public_coin = build_public_coin(&public_coin_seed, transcript.is_some());
+                let (channel, stream) = source.open_channel(&air)?;
                 perform_verification::
-                    <AIR, AIR::BaseElement, Blake3_192<AIR::BaseElement>>
-                    (air, channel, public_coin)
+                    <AIR, AIR::BaseElement, Blake3_192<AIR::BaseElement>, R, _>
+                    (air, channel, public_coin, stream, witness, diagnostics, transcript)
             }
             HashFunction::Sha3_256 => {
-                let public_coin = RandomCoin::new(&public_coin_seed);
-                let channel = VerifierChannel::new(&air, proof)?;
+                let public_coin = build_public_coin(&public_coin_seed, transcript.is_some());
+                let (channel, stream) = source.open_channel(&air)?;
                 perform_verification::
-                    <AIR, AIR::BaseElement, Sha3_256<AIR::BaseElement>>
-                    (air, channel, public_coin)
+                    <AIR, AIR::BaseElement, Sha3_256<AIR::BaseElement>, R, _>
+                    (air, channel, public_coin, stream, witness, diagnostics, transcript)
             }
         },
         FieldExtension::Quadratic => match air.options().hash_fn() {
             HashFunction::Blake3_256 => {
-                let public_coin = RandomCoin::new(&public_coin_seed);
-                let channel = VerifierChannel::new(&air, proof)?;
+                let public_coin = build_public_coin(&public_coin_seed, transcript.is_some());
+                let (channel, stream) = source.open_channel(&air)?;
                 perform_verification::
-                    <AIR, <AIR::BaseElement as StarkField>::QuadExtension, Blake3_256<AIR::BaseElement>>
-                    (air, channel, public_coin)
+                    <AIR, <AIR::BaseElement as StarkField>::QuadExtension, Blake3_256<AIR::BaseElement>, R, _>
+                    (air, channel, public_coin, stream, witness, diagnostics, transcript)
             }
             HashFunction::Blake3_192 => {
-                let public_coin = RandomCoin::new(&public_coin_seed);
-                let channel = VerifierChannel::new(&air, proof)?;
+                let public_coin = build_public_coin(&public_coin_seed, transcript.is_some());
+                let (channel, stream) = source.open_channel(&air)?;
                 perform_verification::
-                    <AIR, <AIR::BaseElement as StarkField>::QuadExtension, Blake3_192<AIR::BaseElement>>
-                    (air, channel, public_coin)
+                    <AIR, <AIR::BaseElement as StarkField>::QuadExtension, Blake3_192<AIR::BaseElement>, R, _>
+                    (air, channel, public_coin, stream, witness, diagnostics, transcript)
             }
             HashFunction::Sha3_256 => {
-                let public_coin = RandomCoin::new(&public_coin_seed);
-                let channel = VerifierChannel::new(&air, proof)?;
+                let public_coin = build_public_coin(&public_coin_seed, transcript.is_some());
+                let (channel, stream) = source.open_channel(&air)?;
                 perform_verification::
-                    <AIR, <AIR::BaseElement as StarkField>::QuadExtension, Sha3_256<AIR::BaseElement>>
-                    (air, channel, public_coin)
+                    <AIR, <AIR::BaseElement as StarkField>::QuadExtension, Sha3_256<AIR::BaseElement>, R, _>
+                    (air, channel, public_coin, stream, witness, diagnostics, transcript)
             }
         },
     }
 }
 
+/// Checks the context of the proof read from the specified `source` and returns an AIR instance
+/// built via `build_air` together with the seed for the public coin.
+///
+/// The seed for the public coin is built from `pub_inputs_bytes` and the proof context; as the
+/// protocol progresses, the coin is reseeded with the info received from the prover.
+///
+/// # Errors
+/// Returns an error if the proof options are not acceptable, or if the proof context is not
+/// consistent with the base field of the AIR.
+fn build_air_and_seed<AIR: Air, R: ByteReader>(
+    source: &ProofSource<R>,
+    pub_inputs_bytes: Vec<u8>,
+    build_air: impl FnOnce(TraceInfo, ProofOptions) -> AIR,
+    acceptable_options: &AcceptableOptions,
+) -> Result<(AIR, Vec<u8>), VerifierError> {
+    let context = source.context();
+    let mut public_coin_seed = pub_inputs_bytes;
+    context.write_into(&mut public_coin_seed);
+
+    // make sure the proof was generated with options acceptable to the verifier
+    if !acceptable_options.accepts(context) {
+        return Err(VerifierError::UnacceptableProofOptions);
+    }
+
+    // make sure the proof context is consistent with the base field of the AIR before creating
+    // the AIR instance; this way, malformed proofs are rejected rather than cause a panic
+    if AIR::BaseElement::get_modulus_le_bytes() != context.field_modulus_bytes() {
+        return Err(VerifierError::InconsistentBaseField);
+    }
+    let two_adicity = AIR::BaseElement::TWO_ADICITY;
+    let lde_domain_size_log2 = context.trace_length().trailing_zeros()
+        + context.options().blowup_factor().trailing_zeros();
+    if lde_domain_size_log2 > two_adicity {
+        return Err(VerifierError::LdeDomainTooLarge(
+            lde_domain_size_log2,
+            two_adicity,
+        ));
+    }
+
+    // create AIR instance for the computation specified in the proof
+    let air = build_air(context.get_trace_info(), context.options().clone());
+    Ok((air, public_coin_seed))
+}
+
+// PROOF SOURCE
+// ================================================================================================
+/// Source from which the verifier reads a proof.
+enum ProofSource<'a, R: ByteReader> {
+    /// A fully deserialized proof.
+    Proof(Box<StarkProof>),
+    /// Context of a proof together with a reader from which the rest of the proof is read as
+    /// verification progresses.
+    Stream(Context, &'a mut R),
+}
+
+impl<'a, R: ByteReader> ProofSource<'a, R> {
+    /// Returns context of the proof.
+    fn context(&self) -> &Context {
+        match self {
+            Self::Proof(proof) => &proof.context,
+            Self::Stream(context, _) => context,
+        }
+    }
+
+    /// Returns a verifier channel for the proof. For streamed proofs, the channel contains only
+    /// the values sent by the prover during the commit phase of the protocol, and the stream from
+    /// which the remaining values should be read is returned as well.
+    #[allow(clippy::type_complexity)]
+    fn open_channel<A, E, H>(
+        self,
+        air: &A,
+    ) -> Result<(VerifierChannel<A::BaseElement, E, H>, Option<&'a mut R>), VerifierError>
+    where
+        A: Air,
+        E: FieldElement<BaseField = A::BaseElement>,
+        H: ElementHasher<BaseField = A::BaseElement>,
+    {
+        match self {
+            Self::Proof(proof) => Ok((VerifierChannel::new(air, *proof)?, None)),
+            Self::Stream(context, stream) => {
+                let channel = VerifierChannel::read_commit_phase_from(air, &context, stream)?;
+                Ok((channel, Some(stream)))
+            }
+        }
+    }
+
+    /// Returns a verifier channel which contains only the values sent by the prover during the
+    /// commit phase of the protocol; queries of a fully deserialized proof are discarded without
+    /// being parsed.
+    fn open_commit_phase<A, E, H>(
+        self,
+        air: &A,
+    ) -> Result<VerifierChannel<A::BaseElement, E, H>, VerifierError>
+    where
+        A: Air,
+        E: FieldElement<BaseField = A::BaseElement>,
+        H: ElementHasher<BaseField = A::BaseElement>,
+    {
+        match self {
+            Self::Proof(proof) => VerifierChannel::with_commit_phase(
+                air,
+                &proof.context,
+                proof.commitments,
+                proof.ood_frame,
+                proof.fri_proof,
+                proof.pow_nonce,
+            ),
+            Self::Stream(context, stream) => {
+                VerifierChannel::read_commit_phase_from(air, &context, stream)
+            }
+        }
+    }
+}
+
 // VERIFICATION PROCEDURE
 // ================================================================================================
 /// Performs the actual verification by reading the data from the `channel` and making sure it
 /// attests to a correct execution of the computation specified by the provided `air`.
-fn perform_verification<A, E, H>(
+///
+/// If a `stream` is provided, queries are read from it into the `channel` once the verifier is
+/// ready to check them. If a `witness` builder is provided, values read and drawn by the verifier
+/// are recorded into it as the verification progresses. Similarly, if a `diagnostics` builder is
+/// provided, the current step of the protocol and details of a failed check are recorded into it.
+/// If a `transcript` is provided, the transcript recorded by the `public_coin` is copied into it
+/// once verification finishes, regardless of whether verification was successful.
+fn perform_verification<A, E, H, R, P>(
+    air: A,
+    channel: VerifierChannel<A::BaseElement, E, H>,
+    mut public_coin: P,
+    stream: Option<&mut R>,
+    witness: Option<&mut WitnessBuilder<A::BaseElement>>,
+    diagnostics: Option<&mut DiagnosticsBuilder<A::BaseElement>>,
+    transcript: Option<&mut Transcript>,
+) -> Result<(), VerifierError>
+where
+    A: Air,
+    E: FieldElement<BaseField = A::BaseElement>,
+    H: ElementHasher<BaseField = A::BaseElement>,
+    R: ByteReader,
+    P: PublicCoin<BaseField = A::BaseElement, Hasher = H>,
+{
+    let result = run_protocol(air, channel, &mut public_coin, stream, witness, diagnostics);
+    if let (Some(transcript), Some(recorded)) = (transcript, public_coin.transcript()) {
+        *transcript = recorded.clone();
+    }
+    result
+}
+
+/// Checks that the out-of-domain evaluations of the proof read from the `source` are consistent
+/// with the constraints of the provided `air`; the rest of the proof is not checked.
+fn perform_ood_check<A, E, H, R>(
+    air: A,
+    source: ProofSource<R>,
+    public_coin_seed: &[u8],
+) -> Result<(), VerifierError>
+where
+    A: Air,
+    E: FieldElement<BaseField = A::BaseElement>,
+    H: ElementHasher<BaseField = A::BaseElement>,
+    R: ByteReader,
+{
+    let mut channel = source.open_commit_phase::<A, E, H>(&air)?;
+    let mut public_coin = RandomCoin::<A::BaseElement, H>::new(public_coin_seed);
+    check_commit_phase(&air, &mut channel, &mut public_coin, &mut None, &mut None)?;
+    Ok(())
+}
+
+/// Executes the verifier side of the protocol against the specified `public_coin`; see
+/// [perform_verification()] for the description of the parameters.
+fn run_protocol<A, E, H, R, P>(
     air: A,
     mut channel: VerifierChannel<A::BaseElement, E, H>,
-    mut public_coin: RandomCoin<A::BaseElement, H>,
+    public_coin: &mut P,
+    mut stream: Option<&mut R>,
+    mut witness: Option<&mut WitnessBuilder<A::BaseElement>>,
+    mut diagnostics: Option<&mut DiagnosticsBuilder<A::BaseElement>>,
 ) -> Result<(), VerifierError>
 where
     A: Air,
     E: FieldElement<BaseField = A::BaseElement>,
     H: ElementHasher<BaseField = A::BaseElement>,
+    R: ByteReader,
+    P: PublicCoin<BaseField = A::BaseElement, Hasher = H>,
+{
+    // 1 - 3 ----- commitments and OOD consistency check -----------------------------------------
+    let CommitPhase {
+        trace_commitments,
+        constraint_commitment,
+        z,
+        ood_frame,
+        ood_evaluations,
+    } = check_commit_phase(
+        &air,
+        &mut channel,
+        public_coin,
+        &mut witness,
+        &mut diagnostics,
+    )?;
+
+    // 4 ----- FRI commitments --------------------------------------------------------------------
+    enter_stage(&mut diagnostics, VerificationStage::FriCommitments);
+    // draw coefficients for computing DEEP composition polynomial from the public coin; in the
+    // interactive version of the protocol, the verifier sends these coefficients to the prover
+    // and the prover uses them to compute the DEEP composition polynomial. the prover, then
+    // applies FRI protocol to the evaluations of the DEEP composition polynomial.
+    let deep_coefficients = air
+        .get_deep_composition_coefficients::<E, P>(public_coin)
+        .map_err(|_| VerifierError::RandomCoinError)?;
+
+    // instantiates a FRI verifier with the FRI layer commitments read from the channel. From the
+    // verifier's perspective, this is equivalent to executing the commit phase of the FRI protocol.
+    // The verifier uses these commitments to update the public coin and draw random points alpha
+    // from them; in the interactive version of the protocol, the verifier sends these alphas to
+    // the prover, and the prover uses them to compute and commit to the subsequent FRI layers.
+    // If the proof options specify STIR as the low-degree test, a STIR verifier is used instead.
+    let fri_options = air.options().to_fri_options();
+    let max_poly_degree = air.trace_poly_degree();
+    let fri_verifier: Box<dyn LowDegreeVerifier<E, _>> = match fri_options.low_degree_test() {
+        LowDegreeTest::Fri | LowDegreeTest::Direct => Box::new(
+            FriVerifier::new(&mut channel, public_coin, fri_options, max_poly_degree)
+                .map_err(VerifierError::FriVerificationFailed)?,
+        ),
+        LowDegreeTest::Stir => Box::new(
+            StirVerifier::new(&mut channel, public_coin, fri_options, max_poly_degree)
+                .map_err(VerifierError::FriVerificationFailed)?,
+        ),
+    };
+    // the low-degree test must be executed over the LDE domain; otherwise, the DEEP composition
+    // polynomial evaluations derived from the queries would be checked against a wrong domain
+    if fri_verifier.domain_size() != air.lde_domain_size() {
+        return Err(VerifierError::InconsistentLowDegreeDomain(
+            air.lde_domain_size(),
+            fri_verifier.domain_size(),
+        ));
+    }
+    if let Some(witness) = witness.as_deref_mut() {
+        witness.record_fri_commitments::<E, H>(
+            &deep_coefficients,
+            fri_verifier.layer_commitments(),
+            fri_verifier.layer_alphas(),
+        );
+    }
+
+    // 5 ----- trace and constraint queries -------------------------------------------------------
+    enter_stage(&mut diagnostics, VerificationStage::QueryProofOfWork);
+    // read proof-of-work nonce sent by the prover and update the public coin with it
+    let pow_nonce = channel.read_pow_nonce();
+    public_coin.reseed_with_int(pow_nonce);
+
+    // make sure the proof-of-work specified by the grinding factor is satisfied; proof-of-work
+    // for FRI layers (if any) has already been checked by the FRI verifier above
+    if public_coin.leading_zeros() < air.options().grinding_factor() {
+        return Err(VerifierError::QuerySeedProofOfWorkVerificationFailed);
+    }
+
+    // draw pseudo-random query positions for the LDE domain from the public coin; in the
+    // interactive version of the protocol, the verifier sends these query positions to the prover,
+    // and the prover responds with decommitments against these positions for trace and constraint
+    // composition polynomial evaluations. positions are drawn in the same way as by the prover,
+    // as defined by the AIR.
+    let query_positions = air
+        .get_query_positions(public_coin)
+        .map_err(|_| VerifierError::RandomCoinError)?;
+    if let Some(witness) = witness.as_deref_mut() {
+        witness.record_query_positions(&query_positions);
+    }
+
+    // read evaluations of trace and constraint composition polynomials at the queried positions;
+    // this also checks that the read values are valid against trace and constraint commitments;
+    // if the check fails, the channel keeps track of positions with invalid authentication paths
+    enter_stage(&mut diagnostics, VerificationStage::Queries);
+    if let Some(stream) = stream.as_deref_mut() {
+        channel.read_queries_from(&air, stream)?;
+    }
+    let queried_trace_states = channel
+        .read_trace_states(&query_positions, &trace_commitments)
+        .map_err(|err| record_query_failure(&mut diagnostics, &mut channel, err))?;
+    let queried_evaluations = channel
+        .read_constraint_evaluations(&query_positions, &constraint_commitment)
+        .map_err(|err| record_query_failure(&mut diagnostics, &mut channel, err))?;
+
+    // read values of committed public columns at the queried positions; this also checks that
+    // the read values are valid against the column commitments. the values are tied to the
+    // registers during DEEP composition below
+    let committed_columns = air.get_committed_columns();
+    if let Some(column) = committed_columns
+        .iter()
+        .find(|column| column.register() >= air.trace_width())
+    {
+        return Err(VerifierError::InvalidCommittedColumnRegister(
+            column.register(),
+        ));
+    }
+    let committed_values = channel
+        .read_committed_column_values(&query_positions, &committed_columns)
+        .map_err(|err| record_query_failure(&mut diagnostics, &mut channel, err))?;
+
+    // 6 ----- DEEP composition -------------------------------------------------------------------
+    // compute evaluations of the DEEP composition polynomial at the queried positions
+    let composer = DeepComposer::new(&air, &query_positions, z, deep_coefficients);
+    let k_composition = composer.compose_committed_columns(
+        &queried_trace_states,
+        &committed_columns,
+        &committed_values,
+    );
+    let mut t_composition = composer.compose_registers(queried_trace_states, ood_frame);
+    add_in_place(&mut t_composition, &k_composition);
+    let c_composition = composer.compose_constraints(queried_evaluations, ood_evaluations);
+    let deep_evaluations = composer.combine_compositions(t_composition, c_composition);
+
+    // 7 ----- Verify low-degree proof -------------------------------------------------------------
+    // make sure that evaluations of the DEEP composition polynomial we computed in the previous
+    // step are in fact evaluations of a polynomial of degree equal to trace polynomial degree
+    enter_stage(&mut diagnostics, VerificationStage::LowDegreeProof);
+    if let Some(stream) = stream {
+        channel.read_fri_query_phase_from(stream)?;
+    }
+    fri_verifier
+        .verify(&mut channel, &deep_evaluations, &query_positions)
+        .map_err(|err| {
+            // layer proofs are read from the channel one at a time; thus, if queries did not
+            // match a layer commitment, the failed layer is the last one read from the channel
+            if err == fri::VerifierError::LayerCommitmentMismatch {
+                if let Some(diagnostics) = diagnostics {
+                    diagnostics.record_failed_check(Some(FailedCheck::FriLayerQueries {
+                        layer: channel.num_read_fri_layers() - 1,
+                    }));
+                }
+            }
+            VerifierError::FriVerificationFailed(err)
+        })?;
+
+    // 8 ----- Record queries ---------------------------------------------------------------------
+    // if a witness was requested, record values opened at the queried positions together with
+    // their authentication paths; this is done only once all of the values have been verified
+    if let Some(witness) = witness {
+        let fri_layer_indexes = fri_verifier.get_layer_query_indexes(&query_positions);
+        witness.record_queries::<A, E, H>(&air, &query_positions, &fri_layer_indexes)?;
+    }
+
+    Ok(())
+}
+
+/// Values sent by the prover and drawn by the verifier up to and including the out-of-domain
+/// consistency check.
+struct CommitPhase<E: FieldElement, D> {
+    trace_commitments: BoundedVec<D, MAX_TRACE_SEGMENTS>,
+    constraint_commitment: D,
+    z: E,
+    ood_frame: EvaluationFrame<E>,
+    ood_evaluations: Vec<E>,
+}
+
+/// Reads trace and constraint commitments and the out-of-domain evaluations from the `channel`,
+/// draws the corresponding challenges from the `public_coin`, and checks that the out-of-domain
+/// evaluations sent by the prover are consistent with the constraints of the `air`; see
+/// [perform_verification()] for the description of the `witness` and `diagnostics` builders.
+fn check_commit_phase<A, E, H, P>(
+    air: &A,
+    channel: &mut VerifierChannel<A::BaseElement, E, H>,
+    public_coin: &mut P,
+    witness: &mut Option<&mut WitnessBuilder<A::BaseElement>>,
+    diagnostics: &mut Option<&mut DiagnosticsBuilder<A::BaseElement>>,
+) -> Result<CommitPhase<E, H::Digest>, VerifierError>
+where
+    A: Air,
+    E: FieldElement<BaseField = A::BaseElement>,
+    H: ElementHasher<BaseField = A::BaseElement>,
+    P: PublicCoin<BaseField = A::BaseElement, Hasher = H>,
 {
     // 1 ----- trace commitment -------------------------------------------------------------------
-    // read the commitment to evaluations of the trace polynomials over the LDE domain sent by the
-    // prover, use it to update the public coin, and draw a set of random coefficients from the
-    // coin; in the interactive version of the protocol, the verifier sends these coefficients to
-    // the prover, and prover uses them to compute constraint composition polynomial.
-    let trace_commitment = channel.read_trace_commitment();
-    public_coin.reseed(trace_commitment);
+    enter_stage(diagnostics, VerificationStage::Commitments);
+    // read the commitments to evaluations of the trace polynomials over the LDE domain sent by the
+    // prover (one for each trace segment), use them to update the public coin, and draw a set of
+    // random coefficients from the coin; in the interactive version of the protocol, the verifier
+    // sends these coefficients to the prover, and prover uses them to compute constraint
+    // composition polynomial.
+    let trace_commitments = channel.read_trace_commitments();
+    for &trace_commitment in trace_commitments.iter() {
+        public_coin.reseed(trace_commitment);
+    }
     let constraint_coeffs = air
-        .get_constraint_composition_coefficients(&mut public_coin)
+        .get_constraint_composition_coefficients(public_coin)
         .map_err(|_| VerifierError::RandomCoinError)?;
 
     // 2 ----- constraint commitment --------------------------------------------------------------
@@ -181,15 +1021,23 @@ where
     let z = public_coin
         .draw::<E>()
         .map_err(|_| VerifierError::RandomCoinError)?;
+    if let Some(witness) = witness.as_deref_mut() {
+        witness.record_commitments::<E, H>(
+            &trace_commitments,
+            constraint_commitment,
+            &constraint_coeffs,
+        );
+    }
 
     // 3 ----- OOD consistency check --------------------------------------------------------------
+    enter_stage(diagnostics, VerificationStage::OodConsistency);
     // make sure that evaluations obtained by evaluating constraints over the out-of-domain frame
     // are consistent with the evaluations of composition polynomial columns sent by the prover
 
     // read the out-of-domain evaluation frame sent by the prover and evaluate constraints over it;
     // also, reseed the public coin with the OOD frame received from the prover
     let ood_frame = channel.read_ood_evaluation_frame();
-    let ood_constraint_evaluation_1 = evaluate_constraints(&air, constraint_coeffs, &ood_frame, z);
+    let ood_constraint_evaluation_1 = evaluate_constraints(air, constraint_coeffs, &ood_frame, z);
     public_coin.reseed(H::hash_elements(ood_frame.current()));
     public_coin.reseed(H::hash_elements(ood_frame.next()));
 
@@ -205,70 +1053,69 @@ where
             result + z.exp((i as u32).into()) * value
         });
     public_coin.reseed(H::hash_elements(&ood_evaluations));
+    if let Some(witness) = witness.as_deref_mut() {
+        witness.record_ood_evaluations(z, &ood_frame, &ood_evaluations);
+    }
 
     // finally, make sure the values are the same
     if ood_constraint_evaluation_1 != ood_constraint_evaluation_2 {
+        if let Some(diagnostics) = diagnostics.as_deref_mut() {
+            diagnostics
+                .record_ood_mismatch(ood_constraint_evaluation_1, ood_constraint_evaluation_2);
+        }
         return Err(VerifierError::InconsistentOodConstraintEvaluations);
     }
 
-    // 4 ----- FRI commitments --------------------------------------------------------------------
-    // draw coefficients for computing DEEP composition polynomial from the public coin; in the
-    // interactive version of the protocol, the verifier sends these coefficients to the prover
-    // and the prover uses them to compute the DEEP composition polynomial. the prover, then
-    // applies FRI protocol to the evaluations of the DEEP composition polynomial.
-    let deep_coefficients = air
-        .get_deep_composition_coefficients::<E, H>(&mut public_coin)
-        .map_err(|_| VerifierError::RandomCoinError)?;
-
-    // instantiates a FRI verifier with the FRI layer commitments read from the channel. From the
-    // verifier's perspective, this is equivalent to executing the commit phase of the FRI protocol.
-    // The verifier uses these commitments to update the public coin and draw random points alpha
-    // from them; in the interactive version of the protocol, the verifier sends these alphas to
-    // the prover, and the prover uses them to compute and commit to the subsequent FRI layers.
-    let fri_verifier = FriVerifier::new(
-        &mut channel,
-        &mut public_coin,
-        air.options().to_fri_options(),
-        air.trace_poly_degree(),
-    )
-    .map_err(VerifierError::FriVerificationFailed)?;
-    // TODO: make sure air.lde_domain_size() == fri_verifier.domain_size()
+    Ok(CommitPhase {
+        trace_commitments,
+        constraint_commitment,
+        z,
+        ood_frame,
+        ood_evaluations,
+    })
+}
 
-    // 5 ----- trace and constraint queries -------------------------------------------------------
-    // read proof-of-work nonce sent by the prover and update the public coin with it
-    let pow_nonce = channel.read_pow_nonce();
-    public_coin.reseed_with_int(pow_nonce);
+// HELPER FUNCTIONS
+// ================================================================================================
 
-    // make sure the proof-of-work specified by the grinding factor is satisfied
-    if public_coin.leading_zeros() < air.options().grinding_factor() {
-        return Err(VerifierError::QuerySeedProofOfWorkVerificationFailed);
+/// Returns a public coin instantiated with the specified `seed`, which records its transcript if
+/// `record_transcript` is set to true.
+fn build_public_coin<B: StarkField, H: ElementHasher<BaseField = B>>(
+    seed: &[u8],
+    record_transcript: bool,
+) -> RandomCoin<B, H> {
+    if record_transcript {
+        RandomCoin::with_transcript(seed)
+    } else {
+        RandomCoin::new(seed)
     }
+}
 
-    // draw pseudo-random query positions for the LDE domain from the public coin; in the
-    // interactive version of the protocol, the verifier sends these query positions to the prover,
-    // and the prover responds with decommitments against these positions for trace and constraint
-    // composition polynomial evaluations.
-    let query_positions = public_coin
-        .draw_integers(air.options().num_queries(), air.lde_domain_size())
-        .map_err(|_| VerifierError::RandomCoinError)?;
-
-    // read evaluations of trace and constraint composition polynomials at the queried positions;
-    // this also checks that the read values are valid against trace and constraint commitments
-    let queried_trace_states = channel.read_trace_states(&query_positions, &trace_commitment)?;
-    let queried_evaluations =
-        channel.read_constraint_evaluations(&query_positions, &constraint_commitment)?;
-
-    // 6 ----- DEEP composition -------------------------------------------------------------------
-    // compute evaluations of the DEEP composition polynomial at the queried positions
-    let composer = DeepComposer::new(&air, &query_positions, z, deep_coefficients);
-    let t_composition = composer.compose_registers(queried_trace_states, ood_frame);
-    let c_composition = composer.compose_constraints(queried_evaluations, ood_evaluations);
-    let deep_evaluations = composer.combine_compositions(t_composition, c_composition);
+/// Records that verification has advanced to the specified `stage` if a `diagnostics` builder
+/// is provided.
+fn enter_stage<B: StarkField>(
+    diagnostics: &mut Option<&mut DiagnosticsBuilder<B>>,
+    stage: VerificationStage,
+) {
+    if let Some(diagnostics) = diagnostics.as_deref_mut() {
+        diagnostics.enter(stage);
+    }
+}
 
-    // 7 ----- Verify low-degree proof -------------------------------------------------------------
-    // make sure that evaluations of the DEEP composition polynomial we computed in the previous
-    // step are in fact evaluations of a polynomial of degree equal to trace polynomial degree
-    fri_verifier
-        .verify(&mut channel, &deep_evaluations, &query_positions)
-        .map_err(VerifierError::FriVerificationFailed)
+/// Moves details of a failed query check from the `channel` into the `diagnostics` builder (if
+/// one is provided), and returns the specified error.
+fn record_query_failure<B, E, H>(
+    diagnostics: &mut Option<&mut DiagnosticsBuilder<B>>,
+    channel: &mut VerifierChannel<B, E, H>,
+    err: VerifierError,
+) -> VerifierError
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+    H: ElementHasher<BaseField = B>,
+{
+    if let Some(diagnostics) = diagnostics.as_deref_mut() {
+        diagnostics.record_failed_check(channel.take_failed_check());
+    }
+    err
 }
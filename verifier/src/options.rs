@@ -0,0 +1,158 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use air::{proof::Context, HashFunction, ProofOptions};
+use utils::collections::Vec;
+
+// ACCEPTABLE OPTIONS
+// ================================================================================================
+/// Defines which proof options a verifier is willing to accept.
+///
+/// Proof options are chosen by the prover and are included in the proof. Thus, unless the
+/// verifier restricts the set of acceptable options, a proof generated with, for example, a very
+/// small number of queries would pass verification even though it provides little soundness.
+/// [verify()](crate::verify) function checks options of a proof against the specified
+/// acceptable options before the proof is verified, and rejects the proof with
+/// [VerifierError::UnacceptableProofOptions](crate::VerifierError::UnacceptableProofOptions)
+/// error if the options are not acceptable.
+///
+/// Acceptable options are built starting from either [any()](AcceptableOptions::any), which
+/// accepts all options, or [from_options()](AcceptableOptions::from_options), which accepts only
+/// the specified set of options. Further restrictions can then be added via `with_*()` methods;
+/// options of a proof are accepted only if they satisfy all of the restrictions. For example:
+///
+/// ```
+/// # use winter_verifier::{AcceptableOptions, HashFunction};
+/// let acceptable_options = AcceptableOptions::any()
+///     .with_min_num_queries(27)
+///     .with_min_blowup_factor(8)
+///     .with_hash_fns(vec![HashFunction::Blake3_256, HashFunction::Sha3_256])
+///     .with_min_security(96, true);
+/// ```
+#[derive(Debug, Clone)]
+pub struct AcceptableOptions {
+    options: Option<Vec<ProofOptions>>,
+    min_num_queries: usize,
+    min_blowup_factor: usize,
+    min_grinding_factor: u32,
+    hash_fns: Option<Vec<HashFunction>>,
+    min_conjectured_security: u32,
+    min_proven_security: u32,
+    predicate: Option<fn(&ProofOptions) -> bool>,
+}
+
+impl AcceptableOptions {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+    /// Returns acceptable options which accept proofs generated with any options.
+    pub fn any() -> Self {
+        AcceptableOptions {
+            options: None,
+            min_num_queries: 0,
+            min_blowup_factor: 0,
+            min_grinding_factor: 0,
+            hash_fns: None,
+            min_conjectured_security: 0,
+            min_proven_security: 0,
+            predicate: None,
+        }
+    }
+
+    /// Returns acceptable options which accept only proofs generated with one of the specified
+    /// `options`.
+    pub fn from_options(options: Vec<ProofOptions>) -> Self {
+        AcceptableOptions {
+            options: Some(options),
+            ..Self::any()
+        }
+    }
+
+    // RESTRICTIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns these acceptable options updated to reject proofs with fewer than the specified
+    /// number of queries.
+    ///
+    /// For query sampling strategies which may draw the same position more than once, the
+    /// expected number of distinct query positions is compared against this number (see
+    /// [ProofOptions::expected_num_query_positions()]).
+    pub fn with_min_num_queries(mut self, num_queries: usize) -> Self {
+        self.min_num_queries = num_queries;
+        self
+    }
+
+    /// Returns these acceptable options updated to reject proofs with blowup factor smaller
+    /// than the specified `blowup_factor`.
+    pub fn with_min_blowup_factor(mut self, blowup_factor: usize) -> Self {
+        self.min_blowup_factor = blowup_factor;
+        self
+    }
+
+    /// Returns these acceptable options updated to reject proofs with query seed grinding
+    /// factor smaller than the specified `grinding_factor`.
+    pub fn with_min_grinding_factor(mut self, grinding_factor: u32) -> Self {
+        self.min_grinding_factor = grinding_factor;
+        self
+    }
+
+    /// Returns these acceptable options updated to reject proofs generated with hash functions
+    /// other than the specified `hash_fns`.
+    pub fn with_hash_fns(mut self, hash_fns: Vec<HashFunction>) -> Self {
+        self.hash_fns = Some(hash_fns);
+        self
+    }
+
+    /// Returns these acceptable options updated to reject proofs with security level (in bits)
+    /// below the specified `security_level`.
+    ///
+    /// When `conjectured` is true, conjectured security level of a proof is checked; otherwise,
+    /// provable security level is checked. See
+    /// [StarkProof::security_level()](air::proof::StarkProof::security_level) for more info.
+    pub fn with_min_security(mut self, security_level: u32, conjectured: bool) -> Self {
+        if conjectured {
+            self.min_conjectured_security = security_level;
+        } else {
+            self.min_proven_security = security_level;
+        }
+        self
+    }
+
+    /// Returns these acceptable options updated to reject proofs with options for which the
+    /// specified `predicate` returns false.
+    pub fn with_predicate(mut self, predicate: fn(&ProofOptions) -> bool) -> Self {
+        self.predicate = Some(predicate);
+        self
+    }
+
+    // VALIDATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns true if options of a proof generated in the specified `context` satisfy all
+    /// restrictions defined by these acceptable options.
+    pub fn accepts(&self, context: &Context) -> bool {
+        let options = context.options();
+        if let Some(acceptable) = &self.options {
+            if !acceptable.contains(options) {
+                return false;
+            }
+        }
+        if let Some(hash_fns) = &self.hash_fns {
+            if !hash_fns.contains(&options.hash_fn()) {
+                return false;
+            }
+        }
+        if let Some(predicate) = self.predicate {
+            if !predicate(options) {
+                return false;
+            }
+        }
+
+        options.expected_num_query_positions(context.lde_domain_size()) >= self.min_num_queries
+            && options.blowup_factor() >= self.min_blowup_factor
+            && options.grinding_factor() >= self.min_grinding_factor
+            && context.security_level(true) >= self.min_conjectured_security
+            && context.security_level(false) >= self.min_proven_security
+    }
+}
@@ -0,0 +1,210 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::ProofWitness;
+use core::fmt;
+use math::StarkField;
+use utils::collections::Vec;
+
+#[cfg(feature = "serde")]
+use utils::{
+    serde::{ser::SerializeStruct, Serialize, Serializer},
+    serde_adapter,
+    string::{String, ToString},
+};
+
+// VERIFICATION OUTPUT
+// ================================================================================================
+/// Commitments, out-of-domain evaluations, and challenges of a verified STARK proof.
+///
+/// The output is returned by [verify_with_output()](crate::verify_with_output) function, and
+/// only for proofs which pass verification; thus, all values contained in the output are bound
+/// to a valid proof. This is intended for applications which consume parts of a proof in
+/// another protocol (e.g., to commit to the trace root elsewhere) without parsing the proof
+/// separately. For debugging, [inspect_proof()](crate::inspect_proof) function returns the
+/// output for invalid proofs as well.
+///
+/// Values are represented in the same way as in [ProofWitness]: all field elements are elements
+/// of the base field of the computation, with an element of an extension field represented by
+/// `extension_degree` consecutive base field elements, and Merkle tree roots are digests of the
+/// hash function specified by the proof options serialized into bytes.
+///
+/// The output can be printed via its [Display](fmt::Display) implementation, which lists all
+/// values with their labels and renders Merkle tree roots as hex strings. When the `serde`
+/// feature is enabled, the output can also be serialized via serde (e.g., into JSON); Merkle
+/// tree roots are serialized as hex strings for human-readable formats, and field elements are
+/// serialized as decimal strings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerificationOutput<B: StarkField> {
+    /// Number of base field elements representing a single element of the field in which the
+    /// protocol was executed; this is 1 when no field extension is used.
+    pub extension_degree: usize,
+    /// Roots of the Merkle trees built from the rows of the extended execution trace; one root
+    /// for each trace segment, in the order of the segments.
+    pub trace_roots: Vec<Vec<u8>>,
+    /// Root of the Merkle tree built from the evaluations of the constraint composition
+    /// polynomial.
+    pub constraint_root: Vec<u8>,
+    /// Roots of the Merkle trees built for FRI layers (including the remainder), in the order in
+    /// which the layers were committed to.
+    pub fri_layer_roots: Vec<Vec<u8>>,
+    /// Coefficients for building the constraint composition polynomial: pairs of coefficients
+    /// for transition constraints followed by pairs of coefficients for boundary constraints.
+    pub constraint_composition_coeffs: Vec<B>,
+    /// Out-of-domain point z.
+    pub ood_point: Vec<B>,
+    /// Evaluations of trace polynomials at z.
+    pub ood_trace_current: Vec<B>,
+    /// Evaluations of trace polynomials at z * g, where g is the generator of the trace domain.
+    pub ood_trace_next: Vec<B>,
+    /// Evaluations of constraint composition polynomial columns at z.
+    pub ood_constraint_evaluations: Vec<B>,
+    /// Coefficients for building the DEEP composition polynomial: triplets of coefficients for
+    /// each trace polynomial, followed by coefficients for constraint composition polynomial
+    /// columns, and by the two degree adjustment coefficients.
+    pub deep_composition_coeffs: Vec<B>,
+    /// Values of α drawn after each of the FRI layers was committed to.
+    pub fri_alphas: Vec<B>,
+    /// Positions in the LDE domain at which the proof was queried.
+    pub query_positions: Vec<usize>,
+}
+
+impl<B: StarkField> VerificationOutput<B> {
+    /// Returns an output consisting of the commitments, out-of-domain evaluations, and
+    /// challenges recorded into the specified `witness`.
+    pub(crate) fn from_witness(witness: ProofWitness<B>) -> Self {
+        VerificationOutput {
+            extension_degree: witness.extension_degree,
+            trace_roots: witness.trace_roots,
+            constraint_root: witness.constraint_root,
+            fri_layer_roots: witness.fri_layer_roots,
+            constraint_composition_coeffs: witness.constraint_composition_coeffs,
+            ood_point: witness.ood_point,
+            ood_trace_current: witness.ood_trace_current,
+            ood_trace_next: witness.ood_trace_next,
+            ood_constraint_evaluations: witness.ood_constraint_evaluations,
+            deep_composition_coeffs: witness.deep_composition_coeffs,
+            fri_alphas: witness.fri_alphas,
+            query_positions: witness.query_positions,
+        }
+    }
+}
+
+impl<B: StarkField> fmt::Display for VerificationOutput<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "extension degree: {}", self.extension_degree)?;
+        for (i, root) in self.trace_roots.iter().enumerate() {
+            writeln!(f, "trace root {}: {}", i, Hex(root))?;
+        }
+        writeln!(f, "constraint root: {}", Hex(&self.constraint_root))?;
+        for (i, root) in self.fri_layer_roots.iter().enumerate() {
+            writeln!(f, "FRI layer root {}: {}", i, Hex(root))?;
+        }
+        write_elements(
+            f,
+            "constraint composition coefficients",
+            &self.constraint_composition_coeffs,
+        )?;
+        write_elements(f, "OOD point", &self.ood_point)?;
+        write_elements(f, "OOD trace current", &self.ood_trace_current)?;
+        write_elements(f, "OOD trace next", &self.ood_trace_next)?;
+        write_elements(
+            f,
+            "OOD constraint evaluations",
+            &self.ood_constraint_evaluations,
+        )?;
+        write_elements(
+            f,
+            "DEEP composition coefficients",
+            &self.deep_composition_coeffs,
+        )?;
+        write_elements(f, "FRI alphas", &self.fri_alphas)?;
+        write!(f, "query positions: {:?}", self.query_positions)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<B: StarkField> Serialize for VerificationOutput<B> {
+    /// Serializes this output into the `serializer` as a struct with the same fields.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("VerificationOutput", 12)?;
+        state.serialize_field("extension_degree", &self.extension_degree)?;
+        state.serialize_field("trace_roots", &hex_roots(&self.trace_roots))?;
+        state.serialize_field("constraint_root", &HexBytes(&self.constraint_root))?;
+        state.serialize_field("fri_layer_roots", &hex_roots(&self.fri_layer_roots))?;
+        state.serialize_field(
+            "constraint_composition_coeffs",
+            &element_strings(&self.constraint_composition_coeffs),
+        )?;
+        state.serialize_field("ood_point", &element_strings(&self.ood_point))?;
+        state.serialize_field(
+            "ood_trace_current",
+            &element_strings(&self.ood_trace_current),
+        )?;
+        state.serialize_field("ood_trace_next", &element_strings(&self.ood_trace_next))?;
+        state.serialize_field(
+            "ood_constraint_evaluations",
+            &element_strings(&self.ood_constraint_evaluations),
+        )?;
+        state.serialize_field(
+            "deep_composition_coeffs",
+            &element_strings(&self.deep_composition_coeffs),
+        )?;
+        state.serialize_field("fri_alphas", &element_strings(&self.fri_alphas))?;
+        state.serialize_field("query_positions", &self.query_positions)?;
+        state.end()
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Formats bytes as a lowercase hex string.
+struct Hex<'a>(&'a [u8]);
+
+impl<'a> fmt::Display for Hex<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in self.0 {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+/// Serializes bytes via [serde_adapter::serialize_bytes()].
+#[cfg(feature = "serde")]
+struct HexBytes<'a>(&'a [u8]);
+
+#[cfg(feature = "serde")]
+impl<'a> Serialize for HexBytes<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serde_adapter::serialize_bytes(self.0, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+fn hex_roots(roots: &[Vec<u8>]) -> Vec<HexBytes<'_>> {
+    roots.iter().map(|root| HexBytes(root)).collect()
+}
+
+#[cfg(feature = "serde")]
+fn element_strings<B: StarkField>(elements: &[B]) -> Vec<String> {
+    elements.iter().map(|element| element.to_string()).collect()
+}
+
+fn write_elements<B: StarkField>(
+    f: &mut fmt::Formatter<'_>,
+    label: &str,
+    elements: &[B],
+) -> fmt::Result {
+    write!(f, "{}: [", label)?;
+    for (i, element) in elements.iter().enumerate() {
+        if i > 0 {
+            write!(f, ", ")?;
+        }
+        write!(f, "{}", element)?;
+    }
+    writeln!(f, "]")
+}
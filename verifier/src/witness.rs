@@ -0,0 +1,439 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::VerifierError;
+use air::{
+    proof::StarkProof, Air, ConstraintCompositionCoefficients, DeepCompositionCoefficients,
+    EvaluationFrame,
+};
+use crypto::{BatchMerkleProof, ElementHasher};
+use math::{FieldElement, StarkField};
+use utils::{
+    collections::Vec,
+    string::{String, ToString},
+    ByteWriter, Serializable,
+};
+
+// PROOF WITNESS
+// ================================================================================================
+/// Values read and drawn by the verifier while verifying a STARK proof, arranged for consumption
+/// by an external verification circuit (e.g., a circuit which wraps a STARK proof into a Groth16
+/// or Plonk proof).
+///
+/// A witness is built via [export_witness()](crate::export_witness) function, and only for proofs
+/// which pass verification.
+///
+/// All field elements are represented by elements of the base field of the computation. An
+/// element of an extension field is represented by `extension_degree` consecutive base field
+/// elements, in the order defined by `FieldElement::as_base_elements()`. Merkle tree roots and
+/// nodes are digests of the hash function specified by the proof options serialized into bytes.
+///
+/// Merkle authentication paths are in the format used by `MerkleTree::prove()`: the first element
+/// is the leaf itself, the second element is its sibling, and the remaining elements are the
+/// siblings of the leaf's ancestors, ending with a child of the root. A leaf is the hash of all
+/// values included in the corresponding query.
+///
+/// # Serialization format
+/// A witness is serialized via the [Serializable] trait as follows (all integers are
+/// little-endian):
+/// * A byte string is written as a `u32` length followed by the bytes; a list of byte strings
+///   or of queries is written as a `u32` length followed by the items.
+/// * A list of field elements is written as a `u32` length followed by the elements serialized
+///   via their [Serializable] implementation.
+/// * A query is written as its `position` (`u64`), followed by the list of its `values`, and by
+///   the list of nodes of its `path`.
+/// * Fields are written in the order in which they are declared; `extension_degree` is written
+///   as a `u8`, proof-of-work nonces are written as `u64` values, query positions are written as
+///   a `u32` length followed by `u64` values, and lists of lists are written as a `u32` length
+///   followed by the inner lists.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofWitness<B: StarkField> {
+    /// Number of base field elements representing a single element of the field in which the
+    /// protocol was executed; this is 1 when no field extension is used.
+    pub extension_degree: usize,
+    /// Roots of the Merkle trees built from the rows of the extended execution trace; one root
+    /// for each trace segment, in the order of the segments.
+    pub trace_roots: Vec<Vec<u8>>,
+    /// Root of the Merkle tree built from the evaluations of the constraint composition
+    /// polynomial.
+    pub constraint_root: Vec<u8>,
+    /// Roots of the Merkle trees built for FRI layers (including the remainder), in the order in
+    /// which the layers were committed to.
+    pub fri_layer_roots: Vec<Vec<u8>>,
+    /// Coefficients for building the constraint composition polynomial: pairs of coefficients
+    /// for transition constraints followed by pairs of coefficients for boundary constraints.
+    pub constraint_composition_coeffs: Vec<B>,
+    /// Out-of-domain point z.
+    pub ood_point: Vec<B>,
+    /// Evaluations of trace polynomials at z.
+    pub ood_trace_current: Vec<B>,
+    /// Evaluations of trace polynomials at z * g, where g is the generator of the trace domain.
+    pub ood_trace_next: Vec<B>,
+    /// Evaluations of constraint composition polynomial columns at z.
+    pub ood_constraint_evaluations: Vec<B>,
+    /// Coefficients for building the DEEP composition polynomial: triplets of coefficients for
+    /// each trace polynomial, followed by coefficients for constraint composition polynomial
+    /// columns, by the two degree adjustment coefficients, and by coefficients for committed
+    /// columns.
+    pub deep_composition_coeffs: Vec<B>,
+    /// Values of α drawn after each of the FRI layers was committed to.
+    pub fri_alphas: Vec<B>,
+    /// Proof-of-work nonce for the query seed.
+    pub pow_nonce: u64,
+    /// Proof-of-work nonces for each FRI layer; empty if FRI layer grinding is not required.
+    pub fri_layer_pow_nonces: Vec<u64>,
+    /// Positions in the LDE domain at which the proof was queried.
+    pub query_positions: Vec<usize>,
+    /// Rows of the extended execution trace at the queried positions, one list per trace
+    /// segment; values of each query are the values of the columns in the segment.
+    pub trace_queries: Vec<Vec<QueryWitness<B>>>,
+    /// Rows of constraint composition polynomial evaluations at the queried positions.
+    pub constraint_queries: Vec<QueryWitness<B>>,
+    /// Values of committed public columns at the queried positions, one list per committed
+    /// column in the order defined by the AIR.
+    pub committed_column_queries: Vec<Vec<QueryWitness<B>>>,
+    /// Queries against each FRI layer, excluding the remainder. Positions of these queries are
+    /// indexes of leaves in the layer commitment Merkle tree, and values of each query are the
+    /// evaluations (as many as the folding factor of the layer) which are folded into a single
+    /// evaluation of the next layer.
+    pub fri_layer_queries: Vec<Vec<QueryWitness<B>>>,
+    /// All evaluations of the last FRI layer, or all coefficients of the remainder polynomial
+    /// if the remainder is sent as coefficients.
+    pub fri_remainder: Vec<B>,
+}
+
+/// Values opened at a single position of a Merkle tree commitment together with their Merkle
+/// authentication path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryWitness<B: StarkField> {
+    /// Index of the leaf in the Merkle tree.
+    pub position: usize,
+    /// Values hashed into the leaf.
+    pub values: Vec<B>,
+    /// Authentication path of the leaf, starting with the leaf itself.
+    pub path: Vec<Vec<u8>>,
+}
+
+impl<B: StarkField> ProofWitness<B> {
+    /// Returns an empty witness; the values are filled in by a [WitnessBuilder].
+    fn new() -> Self {
+        ProofWitness {
+            extension_degree: 0,
+            trace_roots: Vec::new(),
+            constraint_root: Vec::new(),
+            fri_layer_roots: Vec::new(),
+            constraint_composition_coeffs: Vec::new(),
+            ood_point: Vec::new(),
+            ood_trace_current: Vec::new(),
+            ood_trace_next: Vec::new(),
+            ood_constraint_evaluations: Vec::new(),
+            deep_composition_coeffs: Vec::new(),
+            fri_alphas: Vec::new(),
+            pow_nonce: 0,
+            fri_layer_pow_nonces: Vec::new(),
+            query_positions: Vec::new(),
+            trace_queries: Vec::new(),
+            constraint_queries: Vec::new(),
+            committed_column_queries: Vec::new(),
+            fri_layer_queries: Vec::new(),
+            fri_remainder: Vec::new(),
+        }
+    }
+}
+
+impl<B: StarkField> Serializable for ProofWitness<B> {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8(self.extension_degree as u8);
+        target.write_u32(self.trace_roots.len() as u32);
+        for root in self.trace_roots.iter() {
+            write_bytes(root, target);
+        }
+        write_bytes(&self.constraint_root, target);
+        target.write_u32(self.fri_layer_roots.len() as u32);
+        for root in self.fri_layer_roots.iter() {
+            write_bytes(root, target);
+        }
+        write_elements(&self.constraint_composition_coeffs, target);
+        write_elements(&self.ood_point, target);
+        write_elements(&self.ood_trace_current, target);
+        write_elements(&self.ood_trace_next, target);
+        write_elements(&self.ood_constraint_evaluations, target);
+        write_elements(&self.deep_composition_coeffs, target);
+        write_elements(&self.fri_alphas, target);
+        target.write_u64(self.pow_nonce);
+        target.write_u32(self.fri_layer_pow_nonces.len() as u32);
+        for &nonce in self.fri_layer_pow_nonces.iter() {
+            target.write_u64(nonce);
+        }
+        target.write_u32(self.query_positions.len() as u32);
+        for &position in self.query_positions.iter() {
+            target.write_u64(position as u64);
+        }
+        target.write_u32(self.trace_queries.len() as u32);
+        for queries in self.trace_queries.iter() {
+            write_queries(queries, target);
+        }
+        write_queries(&self.constraint_queries, target);
+        target.write_u32(self.committed_column_queries.len() as u32);
+        for queries in self.committed_column_queries.iter() {
+            write_queries(queries, target);
+        }
+        target.write_u32(self.fri_layer_queries.len() as u32);
+        for queries in self.fri_layer_queries.iter() {
+            write_queries(queries, target);
+        }
+        write_elements(&self.fri_remainder, target);
+    }
+}
+
+// WITNESS BUILDER
+// ================================================================================================
+/// Records values into a [ProofWitness] as they are read and drawn by the verifier.
+///
+/// Query values and Merkle authentication paths are parsed from a copy of the proof once the
+/// query phase of the protocol has been verified; if the builder was created without a copy of
+/// the proof, only query positions are recorded.
+pub(crate) struct WitnessBuilder<B: StarkField> {
+    proof: Option<StarkProof>,
+    witness: ProofWitness<B>,
+}
+
+impl<B: StarkField> WitnessBuilder<B> {
+    /// Returns a new builder for a witness of the specified `proof`.
+    pub fn new(proof: StarkProof) -> Self {
+        WitnessBuilder {
+            proof: Some(proof),
+            witness: ProofWitness::new(),
+        }
+    }
+
+    /// Returns a new builder which records commitments, out-of-domain evaluations, and
+    /// challenges, but not the values opened at the queried positions.
+    pub fn without_queries() -> Self {
+        WitnessBuilder {
+            proof: None,
+            witness: ProofWitness::new(),
+        }
+    }
+
+    /// Returns the witness built by this builder.
+    pub fn into_witness(self) -> ProofWitness<B> {
+        self.witness
+    }
+
+    /// Records trace and constraint commitments together with the constraint composition
+    /// coefficients drawn after the trace commitment.
+    pub fn record_commitments<E, H>(
+        &mut self,
+        trace_roots: &[H::Digest],
+        constraint_root: H::Digest,
+        coefficients: &ConstraintCompositionCoefficients<E>,
+    ) where
+        E: FieldElement<BaseField = B>,
+        H: ElementHasher<BaseField = B>,
+    {
+        self.witness.extension_degree = E::ELEMENT_BYTES / B::ELEMENT_BYTES;
+        self.witness.trace_roots = trace_roots.iter().map(|root| root.to_bytes()).collect();
+        self.witness.constraint_root = constraint_root.to_bytes();
+        self.witness.constraint_composition_coeffs = coefficients
+            .transition
+            .iter()
+            .chain(coefficients.boundary.iter())
+            .flat_map(|&(a, b)| flatten(&[a, b]))
+            .collect();
+    }
+
+    /// Records the out-of-domain point together with the out-of-domain evaluations sent by the
+    /// prover.
+    pub fn record_ood_evaluations<E>(&mut self, z: E, frame: &EvaluationFrame<E>, evaluations: &[E])
+    where
+        E: FieldElement<BaseField = B>,
+    {
+        self.witness.ood_point = flatten(&[z]);
+        self.witness.ood_trace_current = flatten(frame.current());
+        self.witness.ood_trace_next = flatten(frame.next());
+        self.witness.ood_constraint_evaluations = flatten(evaluations);
+    }
+
+    /// Records the DEEP composition coefficients, FRI layer commitments and α values drawn
+    /// after each FRI layer commitment.
+    pub fn record_fri_commitments<E, H>(
+        &mut self,
+        coefficients: &DeepCompositionCoefficients<E>,
+        layer_roots: &[H::Digest],
+        layer_alphas: &[E],
+    ) where
+        E: FieldElement<BaseField = B>,
+        H: ElementHasher<BaseField = B>,
+    {
+        let mut deep_coefficients = Vec::new();
+        for &(a, b, c) in coefficients.trace.iter() {
+            deep_coefficients.extend(flatten(&[a, b, c]));
+        }
+        deep_coefficients.extend(flatten(&coefficients.constraints));
+        deep_coefficients.extend(flatten(&[coefficients.degree.0, coefficients.degree.1]));
+        deep_coefficients.extend(flatten(&coefficients.columns));
+        self.witness.deep_composition_coeffs = deep_coefficients;
+
+        self.witness.fri_layer_roots = layer_roots.iter().map(|root| root.to_bytes()).collect();
+        self.witness.fri_alphas = flatten(layer_alphas);
+    }
+
+    /// Records the query positions drawn by the verifier.
+    pub fn record_query_positions(&mut self, positions: &[usize]) {
+        self.witness.query_positions = positions.to_vec();
+    }
+
+    /// Records all values opened at the query positions together with their Merkle
+    /// authentication paths; the values are not recorded if the builder does not hold a copy of
+    /// the proof.
+    ///
+    /// `fri_layer_indexes` must contain indexes of the leaves opened in each FRI layer, in the
+    /// order in which the FRI verifier reads them.
+    pub fn record_queries<A, E, H>(
+        &mut self,
+        air: &A,
+        positions: &[usize],
+        fri_layer_indexes: &[Vec<usize>],
+    ) -> Result<(), VerifierError>
+    where
+        A: Air<BaseElement = B>,
+        E: FieldElement<BaseField = B>,
+        H: ElementHasher<BaseField = B>,
+    {
+        let proof = match self.proof.as_ref() {
+            Some(proof) => proof.clone(),
+            None => return Ok(()),
+        };
+        let lde_domain_size = air.lde_domain_size();
+        let fri_options = air.options().to_fri_options();
+        self.witness.pow_nonce = proof.pow_nonce;
+        self.witness.fri_layer_pow_nonces = proof.fri_proof.layer_pow_nonces().to_vec();
+
+        // --- trace and constraint queries -------------------------------------------------------
+        let trace_segments = air.options().trace_segments(air.trace_width());
+        let mut trace_queries = Vec::with_capacity(trace_segments.len());
+        for (queries, segment) in proof.trace_queries.into_iter().zip(trace_segments) {
+            let (trace_proof, trace_states) = queries
+                .parse::<H, B>(lde_domain_size, positions.len(), segment.len())
+                .map_err(deserialization_error)?;
+            trace_queries.push(build_queries(positions, trace_states, &trace_proof)?);
+        }
+        self.witness.trace_queries = trace_queries;
+
+        let (constraint_proof, constraint_evaluations) = proof
+            .constraint_queries
+            .parse::<H, E>(lde_domain_size, positions.len(), air.ce_blowup_factor())
+            .map_err(deserialization_error)?;
+        self.witness.constraint_queries =
+            build_queries(positions, constraint_evaluations, &constraint_proof)?;
+
+        let mut committed_column_queries = Vec::new();
+        for queries in proof.committed_column_queries {
+            let (column_proof, column_values) = queries
+                .parse::<H, B>(lde_domain_size, positions.len(), 1)
+                .map_err(deserialization_error)?;
+            committed_column_queries.push(build_queries(positions, column_values, &column_proof)?);
+        }
+        self.witness.committed_column_queries = committed_column_queries;
+
+        // --- FRI queries ------------------------------------------------------------------------
+        self.witness.fri_remainder = flatten(
+            &proof
+                .fri_proof
+                .parse_remainder::<E>()
+                .map_err(deserialization_error)?,
+        );
+        let (layer_queries, layer_proofs) = proof
+            .fri_proof
+            .parse_layers::<H, E>(lde_domain_size, &fri_options)
+            .map_err(deserialization_error)?;
+
+        let mut fri_layer_queries = Vec::with_capacity(fri_layer_indexes.len());
+        for (depth, ((indexes, values), layer_proof)) in fri_layer_indexes
+            .iter()
+            .zip(layer_queries)
+            .zip(layer_proofs.iter())
+            .enumerate()
+        {
+            let values = values
+                .chunks(fri_options.layer_folding_factor(depth))
+                .map(|chunk| chunk.to_vec())
+                .collect();
+            fri_layer_queries.push(build_queries(indexes, values, layer_proof)?);
+        }
+        self.witness.fri_layer_queries = fri_layer_queries;
+
+        Ok(())
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Converts a list of elements of the field in which the protocol was executed into a list of
+/// base field elements.
+fn flatten<B: StarkField, E: FieldElement<BaseField = B>>(elements: &[E]) -> Vec<B> {
+    E::as_base_elements(elements).to_vec()
+}
+
+/// Combines values opened at the specified positions with their authentication paths extracted
+/// from the batch Merkle proof.
+fn build_queries<B, E, H>(
+    positions: &[usize],
+    values: Vec<Vec<E>>,
+    proof: &BatchMerkleProof<H>,
+) -> Result<Vec<QueryWitness<B>>, VerifierError>
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+    H: ElementHasher<BaseField = B>,
+{
+    let paths = proof
+        .get_paths(positions)
+        .map_err(|err| deserialization_error(err.to_string()))?;
+
+    Ok(positions
+        .iter()
+        .zip(values)
+        .zip(paths)
+        .map(|((&position, values), path)| QueryWitness {
+            position,
+            values: flatten(&values),
+            path: path.iter().map(|node| node.to_bytes()).collect(),
+        })
+        .collect())
+}
+
+fn deserialization_error<T: ToString>(err: T) -> VerifierError {
+    VerifierError::ProofDeserializationError(
+        String::from("witness export failed: ") + &err.to_string(),
+    )
+}
+
+fn write_bytes<W: ByteWriter>(bytes: &[u8], target: &mut W) {
+    target.write_u32(bytes.len() as u32);
+    target.write_u8_slice(bytes);
+}
+
+fn write_elements<B: StarkField, W: ByteWriter>(elements: &[B], target: &mut W) {
+    target.write_u32(elements.len() as u32);
+    for element in elements {
+        element.write_into(target);
+    }
+}
+
+fn write_queries<B: StarkField, W: ByteWriter>(queries: &[QueryWitness<B>], target: &mut W) {
+    target.write_u32(queries.len() as u32);
+    for query in queries {
+        target.write_u64(query.position as u64);
+        write_elements(&query.values, target);
+        target.write_u32(query.path.len() as u32);
+        for node in query.path.iter() {
+            write_bytes(node, target);
+        }
+    }
+}
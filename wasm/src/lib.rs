@@ -0,0 +1,189 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! This crate contains WebAssembly bindings for Winterfell STARK verifier.
+//!
+//! The bindings expose a single `verify(air, proof, pub_inputs)` function to JavaScript via
+//! [wasm-bindgen](https://rustwasm.github.io/wasm-bindgen/). The function verifies a serialized
+//! proof against serialized public inputs for one of the AIRs registered at compile time.
+//!
+//! # Usage
+//! To build a verifier for a set of computations, create a `cdylib` crate which depends on this
+//! crate, and register AIRs of the computations together with proof options acceptable for
+//! each of them via [register_airs!] macro:
+//!
+//! ```ignore
+//! winterfell_wasm::register_airs! {
+//!     "fib" => (FibAir, AcceptableOptions::any().with_min_security(96, true)),
+//!     "rescue" => (RescueAir, AcceptableOptions::from_options(vec![rescue_options()])),
+//! }
+//! ```
+//!
+//! Each registered AIR must have public inputs which implement [Deserializable] trait. The crate
+//! can then be compiled via `wasm-pack build --target web`, and the proofs can be verified in
+//! JavaScript by calling `verify("fib", proofBytes, pubInputBytes)`; the function throws an
+//! `Error` describing the reason if a proof is invalid.
+//!
+//! # Untrusted input
+//! Proofs and public inputs are expected to come from untrusted sources. Thus, before a proof is
+//! verified, its size is checked against [MAX_PROOF_SIZE], and both the proof and the public
+//! inputs are parsed in full; inputs which contain trailing bytes are rejected. Proof parameters
+//! which are invalid, are not supported by the base field of the AIR, or are not acceptable for
+//! the AIR are rejected by the verifier before the AIR is instantiated.
+
+use core::fmt;
+use verifier::{
+    AcceptableOptions, Air, ByteReader, Deserializable, DeserializationError, SliceReader,
+    StarkProof, VerifierError,
+};
+
+pub use verifier;
+pub use wasm_bindgen;
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Maximum size of a proof (in bytes) accepted by [verify_bytes()] function; currently set to
+/// 4 MB, which is an order of magnitude more than the size of proofs for the largest practical
+/// computations.
+pub const MAX_PROOF_SIZE: usize = 1 << 22;
+
+// VERIFIER
+// ================================================================================================
+
+/// Verifies the proof serialized into `proof_bytes` against public inputs serialized into
+/// `pub_input_bytes` for the computation specified by `AIR` type parameter. The proof is
+/// accepted only if it was generated with options specified by `acceptable_options`.
+///
+/// # Errors
+/// Returns an error if:
+/// * The proof is larger than [MAX_PROOF_SIZE].
+/// * The proof or the public inputs could not be deserialized, or not all of their bytes were
+///   consumed during deserialization.
+/// * The proof was generated with options which are not acceptable, or it does not attest to a
+///   correct execution of the computation against the public inputs.
+pub fn verify_bytes<AIR: Air>(
+    proof_bytes: &[u8],
+    pub_input_bytes: &[u8],
+    acceptable_options: &AcceptableOptions,
+) -> Result<(), WasmVerifierError>
+where
+    AIR::PublicInputs: Deserializable,
+{
+    if proof_bytes.len() > MAX_PROOF_SIZE {
+        return Err(WasmVerifierError::ProofTooLarge(
+            MAX_PROOF_SIZE,
+            proof_bytes.len(),
+        ));
+    }
+    let proof = StarkProof::from_bytes(proof_bytes).map_err(WasmVerifierError::MalformedProof)?;
+
+    let mut source = SliceReader::new(pub_input_bytes);
+    let pub_inputs = AIR::PublicInputs::read_from(&mut source)
+        .map_err(WasmVerifierError::MalformedPublicInputs)?;
+    if source.has_more_bytes() {
+        return Err(WasmVerifierError::MalformedPublicInputs(
+            DeserializationError::UnconsumedBytes,
+        ));
+    }
+
+    verifier::verify::<AIR>(proof, pub_inputs, acceptable_options)
+        .map_err(WasmVerifierError::VerificationFailed)
+}
+
+/// Generates functions which verify proofs for the specified set of AIRs.
+///
+/// The macro takes a list of `name => (AirType, acceptable_options)` entries, where
+/// `acceptable_options` is an expression evaluating to [AcceptableOptions] for the AIR, and
+/// generates two functions in the module in which it is invoked:
+/// * `verify_registered(air: &str, proof: &[u8], pub_inputs: &[u8])`, which verifies the proof
+///   via [verify_bytes()] function for the AIR registered under the specified name against the
+///   options acceptable for this AIR, and returns a [WasmVerifierError] if verification fails.
+/// * `verify(air: &str, proof: &[u8], pub_inputs: &[u8])`, which is exported to JavaScript via
+///   wasm-bindgen, and throws an `Error` with the description of the [WasmVerifierError] if
+///   verification fails.
+#[macro_export]
+macro_rules! register_airs {
+    ($($name:literal => ($air:ty, $options:expr)),+ $(,)?) => {
+        /// Verifies the specified proof against the specified public inputs for the AIR
+        /// registered under the name `air`.
+        pub fn verify_registered(
+            air: &str,
+            proof: &[u8],
+            pub_inputs: &[u8],
+        ) -> Result<(), $crate::WasmVerifierError> {
+            match air {
+                $($name => $crate::verify_bytes::<$air>(proof, pub_inputs, &$options),)+
+                _ => Err($crate::WasmVerifierError::UnknownAir(air.into())),
+            }
+        }
+
+        /// Verifies the specified proof against the specified public inputs for the AIR
+        /// registered under the name `air`, and throws an error if the proof is invalid.
+        #[$crate::wasm_bindgen::prelude::wasm_bindgen(wasm_bindgen = $crate::wasm_bindgen)]
+        pub fn verify(
+            air: &str,
+            proof: &[u8],
+            pub_inputs: &[u8],
+        ) -> Result<(), $crate::wasm_bindgen::JsError> {
+            verify_registered(air, proof, pub_inputs)
+                .map_err(|err| $crate::wasm_bindgen::JsError::new(&err.to_string()))
+        }
+    };
+}
+
+// ERRORS
+// ================================================================================================
+
+/// Represents an error returned when a serialized proof could not be verified.
+#[derive(Debug, PartialEq)]
+pub enum WasmVerifierError {
+    /// This error occurs when no AIR is registered under the specified name.
+    UnknownAir(String),
+    /// This error occurs when the size of a proof exceeds the maximum size of accepted proofs.
+    ProofTooLarge(usize, usize),
+    /// This error occurs when a proof could not be deserialized.
+    MalformedProof(DeserializationError),
+    /// This error occurs when public inputs could not be deserialized.
+    MalformedPublicInputs(DeserializationError),
+    /// This error occurs when a proof does not attest to a correct execution of the computation.
+    VerificationFailed(VerifierError),
+}
+
+impl fmt::Display for WasmVerifierError {
+    #[rustfmt::skip]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownAir(name) => {
+                write!(f, "no AIR is registered under name '{}'", name)
+            }
+            Self::ProofTooLarge(max_size, size) => {
+                write!(f, "proof size cannot exceed {} bytes, but was {} bytes", max_size, size)
+            }
+            Self::MalformedProof(err) => {
+                write!(f, "proof deserialization failed: {}", err)
+            }
+            Self::MalformedPublicInputs(err) => {
+                write!(f, "public input deserialization failed: {}", err)
+            }
+            Self::VerificationFailed(err) => {
+                write!(f, "proof verification failed: {}", err)
+            }
+        }
+    }
+}
+
+impl std::error::Error for WasmVerifierError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::MalformedProof(err) | Self::MalformedPublicInputs(err) => Some(err),
+            Self::VerificationFailed(err) => Some(err),
+            _ => None,
+        }
+    }
+}
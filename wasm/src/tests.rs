@@ -0,0 +1,240 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{WasmVerifierError, MAX_PROOF_SIZE};
+use prover::{
+    math::{fields::f128::BaseElement, FieldElement},
+    Air, AirContext, Assertion, DeserializationError, EvaluationFrame, ExecutionTrace,
+    FieldExtension, HashFunction, ProofOptions, Serializable, StarkProof, TraceInfo,
+    TransitionConstraintDegree,
+};
+use verifier::{AcceptableOptions, VerifierError};
+
+crate::register_airs! {
+    "fib" => (FibAir, AcceptableOptions::any().with_min_num_queries(28)),
+}
+
+// REGISTERED VERIFIER TESTS
+// ================================================================================================
+
+#[test]
+fn verify_registered_air() {
+    let (proof, result) = build_fib_proof(64);
+    let proof_bytes = proof.to_bytes();
+    assert_eq!(
+        Ok(()),
+        verify_registered("fib", &proof_bytes, &result.to_bytes())
+    );
+
+    // verification against a wrong result should fail
+    let wrong_result = (result + BaseElement::ONE).to_bytes();
+    assert!(matches!(
+        verify_registered("fib", &proof_bytes, &wrong_result),
+        Err(WasmVerifierError::VerificationFailed(_))
+    ));
+
+    // proofs generated with options which are not acceptable should be rejected
+    let (weak_proof, result) = build_fib_proof_with_queries(64, 27);
+    assert_eq!(
+        Err(WasmVerifierError::VerificationFailed(
+            VerifierError::UnacceptableProofOptions
+        )),
+        verify_registered("fib", &weak_proof.to_bytes(), &result.to_bytes())
+    );
+
+    // proofs can be verified only for registered AIRs
+    assert_eq!(
+        Err(WasmVerifierError::UnknownAir("rescue".into())),
+        verify_registered("rescue", &proof_bytes, &result.to_bytes())
+    );
+}
+
+#[test]
+fn verify_malformed_inputs() {
+    let (proof, result) = build_fib_proof(64);
+    let proof_bytes = proof.to_bytes();
+    let result_bytes = result.to_bytes();
+
+    // truncated proofs and proofs with trailing bytes should be rejected
+    for &len in [0, 1, 10, proof_bytes.len() / 2, proof_bytes.len() - 1].iter() {
+        assert!(matches!(
+            verify_registered("fib", &proof_bytes[..len], &result_bytes),
+            Err(WasmVerifierError::MalformedProof(_))
+        ));
+    }
+    let mut extended_proof = proof_bytes.clone();
+    extended_proof.push(0);
+    assert_eq!(
+        Err(WasmVerifierError::MalformedProof(
+            DeserializationError::UnconsumedBytes
+        )),
+        verify_registered("fib", &extended_proof, &result_bytes)
+    );
+
+    // public inputs with missing or trailing bytes should be rejected
+    assert!(matches!(
+        verify_registered("fib", &proof_bytes, &result_bytes[1..]),
+        Err(WasmVerifierError::MalformedPublicInputs(_))
+    ));
+    let mut extended_result = result_bytes.clone();
+    extended_result.push(0);
+    assert_eq!(
+        Err(WasmVerifierError::MalformedPublicInputs(
+            DeserializationError::UnconsumedBytes
+        )),
+        verify_registered("fib", &proof_bytes, &extended_result)
+    );
+
+    // oversized proofs should be rejected before they are parsed
+    let oversized_proof = vec![0u8; MAX_PROOF_SIZE + 1];
+    assert_eq!(
+        Err(WasmVerifierError::ProofTooLarge(
+            MAX_PROOF_SIZE,
+            MAX_PROOF_SIZE + 1
+        )),
+        verify_registered("fib", &oversized_proof, &result_bytes)
+    );
+
+    // corrupting any single byte of the proof should result in an error rather than a panic
+    for i in 0..proof_bytes.len() {
+        let mut corrupted_proof = proof_bytes.clone();
+        corrupted_proof[i] ^= 0xff;
+        assert!(verify_registered("fib", &corrupted_proof, &result_bytes).is_err());
+    }
+}
+
+#[test]
+fn verify_unsupported_trace_length() {
+    let (proof, result) = build_fib_proof(64);
+    let mut proof_bytes = proof.to_bytes();
+
+    // the second byte of the proof context (which follows the version header) is log2 of the
+    // trace length; trace of length 2^40 together with blowup factor 8 requires an LDE domain
+    // larger than the two-adicity of the field
+    proof_bytes[header_len(&proof) + 1] = 40;
+    assert_eq!(
+        Err(WasmVerifierError::VerificationFailed(
+            VerifierError::LdeDomainTooLarge(43, 40)
+        )),
+        verify_registered("fib", &proof_bytes, &result.to_bytes())
+    );
+}
+
+#[test]
+fn verify_proof_versions() {
+    let (proof, result) = build_fib_proof(64);
+    let proof_bytes = proof.to_bytes();
+
+    // proofs serialized before the version header was introduced should still be accepted
+    assert_eq!(
+        Ok(()),
+        verify_registered(
+            "fib",
+            &proof_bytes[header_len(&proof)..],
+            &result.to_bytes()
+        )
+    );
+
+    // proofs of a version newer than the supported one should be rejected
+    let mut future_proof = proof_bytes;
+    future_proof[1] = StarkProof::VERSION + 1;
+    assert_eq!(
+        Err(WasmVerifierError::MalformedProof(
+            DeserializationError::UnsupportedVersion(StarkProof::VERSION + 1, StarkProof::VERSION)
+        )),
+        verify_registered("fib", &future_proof, &result.to_bytes())
+    );
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the size of the version header of the specified `proof`; the header consists of the
+/// marker, the version, the hash function, and the length-prefixed base field modulus.
+fn header_len(proof: &StarkProof) -> usize {
+    4 + proof.context.field_modulus_bytes().len()
+}
+
+/// Generates a proof of computing `n`th term of a Fibonacci sequence and returns it together
+/// with the result of the computation.
+fn build_fib_proof(n: usize) -> (prover::StarkProof, BaseElement) {
+    build_fib_proof_with_queries(n, 28)
+}
+
+/// Generates a proof of computing `n`th term of a Fibonacci sequence using the specified number
+/// of queries, and returns it together with the result of the computation.
+fn build_fib_proof_with_queries(n: usize, num_queries: usize) -> (prover::StarkProof, BaseElement) {
+    let mut trace = ExecutionTrace::new(2, n / 2);
+    trace.fill(
+        |state| {
+            state[0] = BaseElement::ONE;
+            state[1] = BaseElement::ONE;
+        },
+        |_, state| {
+            state[0] += state[1];
+            state[1] += state[0];
+        },
+    );
+    let result = trace.get(1, n / 2 - 1);
+
+    let options = ProofOptions::new(
+        num_queries,
+        8,
+        0,
+        HashFunction::Blake3_256,
+        FieldExtension::None,
+        4,
+        256,
+    );
+    let proof = prover::prove::<FibAir>(trace, result, options).unwrap();
+    (proof, result)
+}
+
+/// AIR for a Fibonacci sequence computed two terms per step.
+struct FibAir {
+    context: AirContext<BaseElement>,
+    result: BaseElement,
+}
+
+impl Air for FibAir {
+    type BaseElement = BaseElement;
+    type PublicInputs = BaseElement;
+
+    fn new(trace_info: TraceInfo, pub_inputs: BaseElement, options: ProofOptions) -> Self {
+        let degrees = vec![
+            TransitionConstraintDegree::new(1),
+            TransitionConstraintDegree::new(1),
+        ];
+        FibAir {
+            context: AirContext::new(trace_info, degrees, options),
+            result: pub_inputs,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseElement> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseElement>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        result[0] = next[0] - (current[0] + current[1]);
+        result[1] = next[1] - (current[1] + next[0]);
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseElement>> {
+        let last_step = self.last_step();
+        vec![
+            Assertion::single(0, 0, BaseElement::ONE),
+            Assertion::single(1, 0, BaseElement::ONE),
+            Assertion::single(1, last_step, self.result),
+        ]
+    }
+}
@@ -34,7 +34,8 @@
 //! When the crate is compiled with `concurrent` feature enabled, proof generation will be
 //! performed in multiple threads (usually, as many threads as there are logical cores on the
 //! machine). The number of threads can be configured via `RAYON_NUM_THREADS` environment
-//! variable.
+//! variable. To generate several proofs concurrently without oversubscribing CPU cores, each
+//! proof can also be generated in its own thread pool via `prove_in_pool()` function.
 //!
 //! ## Prof verification
 //! To verify a [StarkProof] generated as described in the previous sections, you'll need to
@@ -43,7 +44,7 @@
 //! 1. Define an *algebraic intermediate representation* (AIR) for you computation. This AIR
 //!    must be the same as the one used during proof generation process.
 //! 2. Execute [verify()] function and supply the AIR of your computation together with the
-//!    [StarkProof] and related public inputs as parameters.
+//!    [StarkProof], related public inputs, and the set of [AcceptableOptions] as parameters.
 //!
 //! Proof verification is extremely fast and is nearly independent of the complexity of the
 //! computation being verified. In vast majority of cases proofs can be verified in 3 - 5 ms
@@ -157,7 +158,9 @@
 //!     result: BaseElement,
 //! }
 //!
-//! // We need to describe how public inputs can be converted to bytes.
+//! // We need to describe how public inputs can be converted to bytes. With `derive` feature
+//! // enabled, this can also be done via `#[derive(Serializable)]` attribute, together with
+//! // `#[serialization(crate = "winterfell")]` attribute.
 //! impl Serializable for PublicInputs {
 //!     fn write_into<W: ByteWriter>(&self, target: &mut W) {
 //!         target.write(self.start);
@@ -254,7 +257,7 @@
 //! #    math::{fields::f128::BaseElement, FieldElement},
 //! #    Air, AirContext, Assertion, ByteWriter, EvaluationFrame, Serializable,
 //! #    TraceInfo, TransitionConstraintDegree,
-//! #    ExecutionTrace, FieldExtension, HashFunction, ProofOptions, StarkProof,
+//! #    AcceptableOptions, ExecutionTrace, FieldExtension, HashFunction, ProofOptions, StarkProof,
 //! # };
 //! #
 //! # pub fn build_do_work_trace(start: BaseElement, n: usize) -> ExecutionTrace<BaseElement> {
@@ -353,9 +356,13 @@
 //! let proof = winterfell::prove::<WorkAir>(trace, pub_inputs, options).unwrap();
 //!
 //! // Verify the proof. The number of steps and options are encoded in the proof itself,
-//! // so we don't need to pass them explicitly to the verifier.
+//! // so we don't need to pass them explicitly to the verifier. However, we do need to
+//! // specify which options we are willing to accept.
 //! let pub_inputs = PublicInputs { start, result };
-//! assert!(winterfell::verify::<WorkAir>(proof, pub_inputs).is_ok());
+//! let acceptable_options = AcceptableOptions::any()
+//!     .with_min_num_queries(32)
+//!     .with_min_blowup_factor(8);
+//! assert!(winterfell::verify::<WorkAir>(proof, pub_inputs, &acceptable_options).is_ok());
 //! ```
 //!
 //! That's all there is to it!
@@ -384,11 +391,39 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 pub use prover::{
-    crypto, iterators, math, prove, Air, AirContext, Assertion, BoundaryConstraint,
-    BoundaryConstraintGroup, ByteReader, ByteWriter, ConstraintCompositionCoefficients,
-    ConstraintDivisor, DeepCompositionCoefficients, Deserializable, DeserializationError,
-    EvaluationFrame, ExecutionTrace, ExecutionTraceFragment, FieldExtension, HashFunction,
-    ProofOptions, ProverError, Serializable, StarkProof, TraceInfo, TransitionConstraintDegree,
-    TransitionConstraintGroup,
+    build_trace_polys, cbor, crypto, fri, iterators, math, merge_trace_commitments, proof, prove,
+    prove_batch, prove_from_polys, prove_with_artifacts, prove_with_channel,
+    prove_with_checkpoints, prove_with_commitment, prove_with_context, prove_with_input_commitment,
+    prove_with_mode, prove_with_observer, table, validate, Air, AirContext, Assertion, BatchAir,
+    BoundaryConstraint, BoundaryConstraintGroup, BufferPool, ByteReader, ByteWriter,
+    CheckpointStage, CommittedColumn, CommittedInputsAir, ConstraintCompositionCoefficients,
+    ConstraintDivisor, ConstraintReport, DeepCompositionCoefficients, DefaultProverChannel,
+    Deserializable, DeserializationError, DistinctSampler, EvaluationFrame, ExecutionTrace,
+    ExecutionTraceFragment, FieldExtension, FragmentScheduling, HashFunction, PeriodicMask,
+    ProofOptions, ProverArtifacts, ProverChannel, ProverCheckpoint, ProverContext, ProverError,
+    ProverObserver, ProverStage, ProvingMode, QuerySampler, QuerySampling, RecyclingPool,
+    Serializable, SliceReader, StarkProof, StratifiedSampler, Trace, TraceCommitment,
+    TraceCommitmentJob, TraceCommitmentJobResult, TraceInfo, TracePadding, TraceValidationError,
+    Transcript, TransitionConstraintDegree, TransitionConstraintGroup, UniformSampler,
 };
-pub use verifier::{verify, VerifierError};
+pub use verifier::{
+    check_ood_consistency, export_witness, inspect_proof, verify, verify_batch, verify_from_reader,
+    verify_many, verify_with_coin, verify_with_diagnostics, verify_with_input_commitment,
+    verify_with_output, verify_with_transcript, AcceptableOptions, FailedCheck, ProofWitness,
+    PublicCoin, QueryWitness, VerificationDiagnostics, VerificationOutput, VerificationStage,
+    VerifierError,
+};
+
+#[cfg(feature = "std")]
+pub use prover::WriteAdapter;
+#[cfg(feature = "std")]
+pub use verifier::{BufReadAdapter, ReadAdapter};
+
+#[cfg(feature = "concurrent")]
+pub use prover::{prove_in_pool, rayon};
+
+#[cfg(feature = "profiling")]
+pub use prover::{prove_with_profile, ProverProfile, StageProfile};
+
+#[cfg(test)]
+mod tests;
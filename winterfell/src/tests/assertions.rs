@@ -0,0 +1,140 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{build_sum_options, SumInstance};
+use crate::{
+    math::{fields::f128::BaseElement, FieldElement},
+    AcceptableOptions, Air, AirContext, Assertion, ByteWriter, EvaluationFrame, ProofOptions,
+    Serializable, StarkProof, TraceInfo, TransitionConstraintDegree, VerifierError,
+};
+
+// RANGE ASSERTION TESTS
+// ================================================================================================
+
+#[test]
+fn prove_range_assertion() {
+    for (first_step, num_values) in [(0, 1), (3, 50), (1, 63), (0, 62)] {
+        let sum = SumInstance::new(63, build_sum_options(false));
+        let inputs = RangeInputs::new(&sum, first_step, num_values);
+        let proof = prove_range(&sum, &inputs);
+        assert_eq!(Ok(()), verify_range(proof.clone(), inputs.clone()));
+
+        // a proof is rejected when any of the asserted values is different
+        for i in [0, num_values / 2, num_values - 1] {
+            let mut wrong_inputs = inputs.clone();
+            wrong_inputs.values[i] += BaseElement::ONE;
+            assert!(verify_range(proof.clone(), wrong_inputs).is_err());
+        }
+
+        // a proof is also rejected when the range is shifted
+        if first_step + num_values < 64 {
+            let mut wrong_inputs = inputs.clone();
+            wrong_inputs.first_step += 1;
+            assert!(verify_range(proof, wrong_inputs).is_err());
+        }
+    }
+}
+
+#[test]
+fn prove_range_assertion_in_extension_field() {
+    let sum = SumInstance::new(63, build_sum_options(true));
+    let inputs = RangeInputs::new(&sum, 5, 40);
+    let proof = prove_range(&sum, &inputs);
+    assert_eq!(Ok(()), verify_range(proof.clone(), inputs.clone()));
+
+    let mut wrong_inputs = inputs;
+    wrong_inputs.values[17] += BaseElement::ONE;
+    assert!(verify_range(proof, wrong_inputs).is_err());
+}
+
+// RANGE AIR
+// ================================================================================================
+
+/// Public inputs of [RangeAir]: the sum of the values in the first register, and the values
+/// of the first register at a contiguous range of steps.
+#[derive(Clone)]
+struct RangeInputs {
+    result: BaseElement,
+    first_step: usize,
+    values: Vec<BaseElement>,
+}
+
+impl RangeInputs {
+    fn new(sum: &SumInstance, first_step: usize, num_values: usize) -> Self {
+        let trace = sum.build_trace();
+        let values = (first_step..first_step + num_values)
+            .map(|step| trace.get(0, step))
+            .collect();
+        RangeInputs {
+            result: sum.result,
+            first_step,
+            values,
+        }
+    }
+}
+
+impl Serializable for RangeInputs {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write(self.result);
+        target.write_u32(self.first_step as u32);
+        target.write(&self.values);
+    }
+}
+
+/// Same computation as [SumAir](super::SumAir), but with the values of the first register
+/// asserted over a range of steps instead of being bound to a committed column.
+struct RangeAir {
+    context: AirContext<BaseElement>,
+    inputs: RangeInputs,
+}
+
+impl Air for RangeAir {
+    type BaseElement = BaseElement;
+    type PublicInputs = RangeInputs;
+
+    fn new(trace_info: TraceInfo, pub_inputs: RangeInputs, options: ProofOptions) -> Self {
+        let degrees = vec![TransitionConstraintDegree::new(1)];
+        RangeAir {
+            context: AirContext::new(trace_info, degrees, options),
+            inputs: pub_inputs,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseElement> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseElement>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        result[0] = next[1] - (current[1] + current[0]);
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseElement>> {
+        let first_step = self.inputs.first_step;
+        let last_step = first_step + self.inputs.values.len() - 1;
+        vec![
+            Assertion::single(1, 0, BaseElement::ZERO),
+            Assertion::single(1, self.last_step(), self.inputs.result),
+            Assertion::range(0, first_step, last_step, self.inputs.values.clone()),
+        ]
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn prove_range(sum: &SumInstance, inputs: &RangeInputs) -> StarkProof {
+    crate::prove::<RangeAir>(sum.build_trace(), inputs.clone(), sum.options.clone()).unwrap()
+}
+
+fn verify_range(proof: StarkProof, inputs: RangeInputs) -> Result<(), VerifierError> {
+    crate::verify::<RangeAir>(proof, inputs, &AcceptableOptions::any())
+}
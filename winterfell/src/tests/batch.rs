@@ -0,0 +1,104 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{build_sum_options, SumAir, SumInputs, SumInstance};
+use crate::{
+    math::{fields::f128::BaseElement, FieldElement},
+    AcceptableOptions, StarkProof, VerifierError,
+};
+
+// BATCH PROVING TESTS
+// ================================================================================================
+
+#[test]
+fn prove_batch() {
+    // build 4 instances summing up different values
+    let instances = (0..4u128)
+        .map(|i| {
+            let values = (0..63u128).map(|j| BaseElement::new(j * i + 1)).collect();
+            SumInstance::with_values(values, build_sum_options(false))
+        })
+        .collect::<Vec<_>>();
+    let build_pub_inputs = || {
+        instances
+            .iter()
+            .map(SumInstance::pub_inputs)
+            .collect::<Vec<SumInputs>>()
+    };
+    let traces = instances.iter().map(SumInstance::build_trace).collect();
+
+    let proof =
+        crate::prove_batch::<SumAir, _>(traces, build_pub_inputs(), build_sum_options(false))
+            .unwrap();
+    assert_eq!(8, proof.context.trace_width());
+    assert!(crate::verify_batch::<SumAir>(
+        proof.clone(),
+        build_pub_inputs(),
+        &AcceptableOptions::any()
+    )
+    .is_ok());
+
+    // the proof should not verify if public inputs of instances are swapped
+    let mut swapped_inputs = build_pub_inputs();
+    swapped_inputs.swap(1, 2);
+    assert!(crate::verify_batch::<SumAir>(
+        proof.clone(),
+        swapped_inputs,
+        &AcceptableOptions::any()
+    )
+    .is_err());
+
+    // the number of instances must be consistent with the trace width of the proof
+    let mut fewer_inputs = build_pub_inputs();
+    fewer_inputs.pop();
+    assert_eq!(
+        Err(VerifierError::InconsistentBatchSize),
+        crate::verify_batch::<SumAir>(proof, fewer_inputs, &AcceptableOptions::any())
+    );
+}
+
+// MULTIPLE PROOF VERIFICATION TESTS
+// ================================================================================================
+
+#[test]
+fn verify_many() {
+    // generate independent proofs for instances summing up different values
+    let instances = (0..4u128)
+        .map(|i| {
+            let values = (0..63u128).map(|j| BaseElement::new(j * i + 1)).collect();
+            SumInstance::with_values(values, build_sum_options(false))
+        })
+        .collect::<Vec<_>>();
+    let proofs = instances.iter().map(|sum| sum.prove()).collect::<Vec<_>>();
+
+    // builds proofs paired with public inputs; inputs for the specified proofs are wrong
+    let build_proofs = |wrong: &[usize]| {
+        instances
+            .iter()
+            .zip(proofs.iter())
+            .enumerate()
+            .map(|(i, (sum, proof))| {
+                let mut inputs = sum.pub_inputs();
+                if wrong.contains(&i) {
+                    inputs.result += BaseElement::ONE;
+                }
+                (proof.clone(), inputs)
+            })
+            .collect::<Vec<(StarkProof, SumInputs)>>()
+    };
+
+    assert!(crate::verify_many::<SumAir>(build_proofs(&[]), &AcceptableOptions::any()).is_ok());
+    assert!(crate::verify_many::<SumAir>(Vec::new(), &AcceptableOptions::any()).is_ok());
+
+    // the first invalid proof should be reported
+    let expected = VerifierError::ProofVerificationFailed(
+        1,
+        Box::new(VerifierError::InconsistentOodConstraintEvaluations),
+    );
+    assert_eq!(
+        Err(expected),
+        crate::verify_many::<SumAir>(build_proofs(&[1, 3]), &AcceptableOptions::any())
+    );
+}
@@ -0,0 +1,301 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{build_sum_options, SumAir, SumInstance};
+use crate::{
+    crypto::{hashers::Blake3_256, Hasher, PublicCoin, RandomCoin, RandomCoinError},
+    fri::FriProof,
+    math::{fields::f128::BaseElement, FieldElement, StarkField},
+    proof::{Context, Queries},
+    AcceptableOptions, Air, ConstraintCompositionCoefficients, DeepCompositionCoefficients,
+    DefaultProverChannel, EvaluationFrame, ProverChannel, ProvingMode, Serializable, StarkProof,
+};
+
+type QuadElement = <BaseElement as StarkField>::QuadExtension;
+
+// PROVER CHANNEL TESTS
+// ================================================================================================
+
+#[test]
+fn prove_with_channel() {
+    let sum = SumInstance::new(63, build_sum_options(true));
+    let proof = sum.prove();
+
+    // a proof generated with a channel which only observes the transcript should be identical
+    // to the proof generated via prove()
+    let trace_info = sum.build_trace().get_info();
+    let air = SumAir::new(trace_info.clone(), sum.pub_inputs(), sum.options.clone());
+    let channel = TranscriptChannel::new(&air, sum.pub_inputs().to_bytes(), None);
+    let channel_proof =
+        crate::prove_with_channel(air, sum.build_trace(), channel, ProvingMode::InMemory).unwrap();
+    assert_eq!(proof.to_bytes(), channel_proof.to_bytes());
+
+    // a channel which absorbs an extra commitment changes the transcript, and thus the proof
+    // cannot be verified by the standard verifier
+    let air = SumAir::new(trace_info, sum.pub_inputs(), sum.options.clone());
+    let channel = TranscriptChannel::new(
+        &air,
+        sum.pub_inputs().to_bytes(),
+        Some(CompanionCoin::extra()),
+    );
+    let channel_proof =
+        crate::prove_with_channel(air, sum.build_trace(), channel, ProvingMode::InMemory).unwrap();
+    assert_ne!(proof.to_bytes(), channel_proof.to_bytes());
+    assert!(
+        crate::verify::<SumAir>(channel_proof, sum.pub_inputs(), &AcceptableOptions::any())
+            .is_err()
+    );
+}
+
+#[test]
+fn prove_with_channel_for_custom_coin() {
+    type DefaultCoin = RandomCoin<BaseElement, Blake3_256<BaseElement>>;
+
+    let sum = SumInstance::new(63, build_sum_options(true));
+    let acceptable_options = AcceptableOptions::any();
+
+    // proofs generated by the default prover should be accepted when verified with the default
+    // coin, but not with a coin which derives challenges differently
+    let proof = sum.prove();
+    assert_eq!(
+        Ok(()),
+        crate::verify_with_coin::<SumAir, DefaultCoin>(
+            proof.clone(),
+            sum.pub_inputs(),
+            &acceptable_options
+        )
+    );
+    assert!(crate::verify_with_coin::<SumAir, CompanionCoin>(
+        proof,
+        sum.pub_inputs(),
+        &acceptable_options
+    )
+    .is_err());
+
+    // a proof generated with a channel which absorbs an extra digest after the trace commitment
+    // should be accepted only when verified with a coin which absorbs the same digest
+    let air = SumAir::new(
+        sum.build_trace().get_info(),
+        sum.pub_inputs(),
+        sum.options.clone(),
+    );
+    let channel = TranscriptChannel::new(
+        &air,
+        sum.pub_inputs().to_bytes(),
+        Some(CompanionCoin::extra()),
+    );
+    let channel_proof =
+        crate::prove_with_channel(air, sum.build_trace(), channel, ProvingMode::InMemory).unwrap();
+    assert_eq!(
+        Ok(()),
+        crate::verify_with_coin::<SumAir, CompanionCoin>(
+            channel_proof.clone(),
+            sum.pub_inputs(),
+            &acceptable_options
+        )
+    );
+    assert!(crate::verify_with_coin::<SumAir, DefaultCoin>(
+        channel_proof,
+        sum.pub_inputs(),
+        &acceptable_options
+    )
+    .is_err());
+}
+
+// HELPER TYPES
+// ================================================================================================
+
+/// Wraps the default prover channel, counts commitments sent through it, and optionally absorbs
+/// an extra digest into the transcript right after the trace commitment.
+struct TranscriptChannel {
+    inner: DefaultProverChannel<SumAir, QuadElement, Blake3_256<BaseElement>>,
+    num_commitments: usize,
+    extra: Option<<Blake3_256<BaseElement> as Hasher>::Digest>,
+}
+
+impl TranscriptChannel {
+    fn new(
+        air: &SumAir,
+        pub_inputs_bytes: Vec<u8>,
+        extra: Option<<Blake3_256<BaseElement> as Hasher>::Digest>,
+    ) -> Self {
+        TranscriptChannel {
+            inner: DefaultProverChannel::new(air, pub_inputs_bytes),
+            num_commitments: 0,
+            extra,
+        }
+    }
+}
+
+impl ProverChannel<SumAir, QuadElement> for TranscriptChannel {
+    fn context(&self) -> &Context {
+        self.inner.context()
+    }
+
+    fn commit_trace(&mut self, trace_root: <Self::Hasher as Hasher>::Digest) {
+        self.num_commitments += 1;
+        self.inner.commit_trace(trace_root);
+        if let Some(extra) = self.extra {
+            self.inner.reseed(extra);
+        }
+    }
+
+    fn get_constraint_composition_coeffs(
+        &mut self,
+        air: &SumAir,
+    ) -> ConstraintCompositionCoefficients<QuadElement> {
+        self.inner.get_constraint_composition_coeffs(air)
+    }
+
+    fn commit_constraints(&mut self, constraint_root: <Self::Hasher as Hasher>::Digest) {
+        self.num_commitments += 1;
+        self.inner.commit_constraints(constraint_root);
+    }
+
+    fn get_ood_point(&mut self) -> QuadElement {
+        ProverChannel::<SumAir, QuadElement>::get_ood_point(&mut self.inner)
+    }
+
+    fn send_ood_evaluation_frame(&mut self, frame: &EvaluationFrame<QuadElement>) {
+        self.inner.send_ood_evaluation_frame(frame);
+    }
+
+    fn send_ood_constraint_evaluations(&mut self, evaluations: &[QuadElement]) {
+        self.inner.send_ood_constraint_evaluations(evaluations);
+    }
+
+    fn get_deep_composition_coeffs(
+        &mut self,
+        air: &SumAir,
+    ) -> DeepCompositionCoefficients<QuadElement> {
+        self.inner.get_deep_composition_coeffs(air)
+    }
+
+    fn grind_query_seed(&mut self) {
+        ProverChannel::<SumAir, QuadElement>::grind_query_seed(&mut self.inner);
+    }
+
+    fn get_query_positions(&mut self, air: &SumAir) -> Vec<usize> {
+        ProverChannel::<SumAir, QuadElement>::get_query_positions(&mut self.inner, air)
+    }
+
+    fn build_proof(
+        self,
+        trace_queries: Vec<Queries>,
+        constraint_queries: Queries,
+        committed_column_queries: Vec<Queries>,
+        fri_proof: FriProof,
+    ) -> StarkProof {
+        // one trace commitment and one constraint commitment; FRI layers are committed via
+        // the FRI channel interface
+        assert_eq!(2, self.num_commitments);
+        self.inner.build_proof(
+            trace_queries,
+            constraint_queries,
+            committed_column_queries,
+            fri_proof,
+        )
+    }
+}
+
+impl crate::fri::ProverChannel<QuadElement> for TranscriptChannel {
+    type Hasher = Blake3_256<BaseElement>;
+
+    fn commit_fri_layer(&mut self, layer_root: <Self::Hasher as Hasher>::Digest) {
+        self.inner.commit_fri_layer(layer_root);
+    }
+
+    fn draw_fri_alpha(&mut self) -> QuadElement {
+        self.inner.draw_fri_alpha()
+    }
+
+    fn grind_fri_layer(&mut self, grinding_factor: u32) -> u64 {
+        self.inner.grind_fri_layer(grinding_factor)
+    }
+
+    fn commit_fri_ood_evaluations(&mut self, evaluations_hash: <Self::Hasher as Hasher>::Digest) {
+        self.inner.commit_fri_ood_evaluations(evaluations_hash);
+    }
+
+    fn draw_fri_query_indexes(&mut self, num_indexes: usize, domain_size: usize) -> Vec<usize> {
+        self.inner.draw_fri_query_indexes(num_indexes, domain_size)
+    }
+}
+
+/// Public coin which absorbs an extra digest after the first commitment; this matches the
+/// transcript of a [TranscriptChannel] instantiated with [CompanionCoin::extra()] digest.
+struct CompanionCoin {
+    inner: RandomCoin<BaseElement, Blake3_256<BaseElement>>,
+    absorbed_extra: bool,
+}
+
+impl CompanionCoin {
+    fn extra() -> <Blake3_256<BaseElement> as Hasher>::Digest {
+        Blake3_256::<BaseElement>::hash(b"companion protocol")
+    }
+}
+
+impl PublicCoin for CompanionCoin {
+    type BaseField = BaseElement;
+    type Hasher = Blake3_256<BaseElement>;
+
+    fn new(seed: &[u8]) -> Self {
+        CompanionCoin {
+            inner: RandomCoin::new(seed),
+            absorbed_extra: false,
+        }
+    }
+
+    fn reseed(&mut self, data: <Self::Hasher as Hasher>::Digest) {
+        self.inner.reseed(data);
+        if !self.absorbed_extra {
+            self.inner.reseed(Self::extra());
+            self.absorbed_extra = true;
+        }
+    }
+
+    fn reseed_with_int(&mut self, value: u64) {
+        self.inner.reseed_with_int(value);
+    }
+
+    fn leading_zeros(&self) -> u32 {
+        self.inner.leading_zeros()
+    }
+
+    fn check_leading_zeros(&self, value: u64) -> u32 {
+        self.inner.check_leading_zeros(value)
+    }
+
+    fn draw<E: FieldElement<BaseField = BaseElement>>(&mut self) -> Result<E, RandomCoinError> {
+        self.inner.draw()
+    }
+
+    fn draw_integers(
+        &mut self,
+        num_values: usize,
+        domain_size: usize,
+    ) -> Result<Vec<usize>, RandomCoinError> {
+        self.inner.draw_integers(num_values, domain_size)
+    }
+
+    fn draw_integers_with_replacement(
+        &mut self,
+        num_values: usize,
+        domain_size: usize,
+    ) -> Vec<usize> {
+        self.inner
+            .draw_integers_with_replacement(num_values, domain_size)
+    }
+
+    fn draw_integers_stratified(
+        &mut self,
+        num_values: usize,
+        domain_size: usize,
+        num_strata: usize,
+    ) -> Result<Vec<usize>, RandomCoinError> {
+        self.inner
+            .draw_integers_stratified(num_values, domain_size, num_strata)
+    }
+}
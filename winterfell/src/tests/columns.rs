@@ -0,0 +1,133 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{build_sum_column, build_sum_options, tweak_values, SumAir, SumInputs, SumInstance};
+use crate::{
+    crypto::hashers::Blake3_256,
+    math::{fft, fields::f128::BaseElement, FieldElement},
+    proof::Queries,
+    AcceptableOptions, Air, AirContext, Assertion, ByteWriter, CommittedColumn, EvaluationFrame,
+    ProofOptions, ProvingMode, Serializable, TraceInfo, VerifierError,
+};
+
+// COMMITTED COLUMN TESTS
+// ================================================================================================
+
+#[test]
+fn verify_wrong_column_commitment() {
+    let sum = SumInstance::new(63, build_sum_options(false));
+    let proof = sum.prove();
+    assert_eq!(Ok(()), sum.verify(proof.clone()));
+
+    // commit to a different set of values which have the same sum
+    let values = tweak_values(&sum.values);
+    let pub_inputs = SumInputs {
+        commitment: CommittedColumn::build_commitment(&build_sum_column(&values), &sum.options),
+        result: sum.result,
+    };
+    assert!(crate::verify::<SumAir>(proof, pub_inputs, &AcceptableOptions::any()).is_err());
+}
+
+#[test]
+fn verify_register_which_does_not_match_column() {
+    let sum = SumInstance::new(63, build_sum_options(false));
+    let (mut proof, artifacts) = crate::prove_with_artifacts::<UnboundSumAir, BaseElement>(
+        sum.build_trace(),
+        UnboundInputs(sum.pub_inputs()),
+        sum.options.clone(),
+        ProvingMode::InMemory,
+    )
+    .unwrap();
+    assert_eq!(
+        Ok(()),
+        crate::verify::<UnboundSumAir>(
+            proof.clone(),
+            UnboundInputs(sum.pub_inputs()),
+            &AcceptableOptions::any()
+        )
+    );
+
+    // open a column with different values at the queried positions; the opened values are
+    // valid against the commitment to this column, but the register does not match the column,
+    // and thus, the DEEP composition polynomial is not of low degree
+    let column = build_sum_column(&tweak_values(&sum.values));
+    let evaluations = extend_column(&column, &sum.options);
+    let tree = CommittedColumn::build_tree::<Blake3_256<BaseElement>>(&evaluations);
+    let positions = artifacts.query_positions;
+    let values = positions.iter().map(|&i| vec![evaluations[i]]).collect();
+    let paths = tree.prove_batch(&positions).unwrap();
+    proof.committed_column_queries = vec![Queries::new(paths, values)];
+
+    let pub_inputs = UnboundInputs(SumInputs {
+        commitment: CommittedColumn::build_commitment(&column, &sum.options),
+        result: sum.result,
+    });
+    assert!(matches!(
+        crate::verify::<UnboundSumAir>(proof, pub_inputs, &AcceptableOptions::any()),
+        Err(VerifierError::FriVerificationFailed(_))
+    ));
+}
+
+// UNBOUND SUM AIR
+// ================================================================================================
+
+/// Public inputs of [UnboundSumAir]; only the result is written into the transcript, and thus,
+/// the column commitment is not bound to the proof.
+struct UnboundInputs(SumInputs);
+
+impl Serializable for UnboundInputs {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write(self.0.result);
+    }
+}
+
+/// Sum AIR which does not bind the column commitment to the proof; this makes it possible to
+/// check a proof against a different column without changing the queried positions.
+struct UnboundSumAir(SumAir);
+
+impl Air for UnboundSumAir {
+    type BaseElement = BaseElement;
+    type PublicInputs = UnboundInputs;
+
+    fn new(trace_info: TraceInfo, pub_inputs: UnboundInputs, options: ProofOptions) -> Self {
+        UnboundSumAir(SumAir::new(trace_info, pub_inputs.0, options))
+    }
+
+    fn context(&self) -> &AirContext<BaseElement> {
+        self.0.context()
+    }
+
+    fn evaluate_transition<E: FieldElement<BaseField = BaseElement>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        self.0.evaluate_transition(frame, periodic_values, result)
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<BaseElement>> {
+        self.0.get_assertions()
+    }
+
+    fn get_committed_columns(&self) -> Vec<CommittedColumn> {
+        self.0.get_committed_columns()
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns evaluations of the column polynomial over the LDE domain defined by `options`.
+fn extend_column(column: &[BaseElement], options: &ProofOptions) -> Vec<BaseElement> {
+    let mut poly = column.to_vec();
+    fft::interpolate_poly(&mut poly, &fft::get_inv_twiddles(column.len()));
+    fft::evaluate_poly_with_offset(
+        &poly,
+        &fft::get_twiddles(column.len()),
+        options.domain_offset(),
+        options.blowup_factor(),
+    )
+}
@@ -0,0 +1,64 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{build_sum_options, SumAir, SumInstance};
+use crate::{
+    math::{fields::f128::BaseElement, FieldElement},
+    AcceptableOptions, VerifierError,
+};
+
+// OOD CONSISTENCY TESTS
+// ================================================================================================
+
+#[test]
+fn check_ood_consistency() {
+    let sum = SumInstance::new(63, build_sum_options(false));
+    let proof = sum.prove();
+    assert_eq!(
+        Ok(()),
+        crate::check_ood_consistency::<SumAir>(
+            proof.clone(),
+            sum.pub_inputs(),
+            &AcceptableOptions::any()
+        )
+    );
+
+    // a proof for different public inputs should be rejected
+    let mut wrong_inputs = sum.pub_inputs();
+    wrong_inputs.result += BaseElement::ONE;
+    assert_eq!(
+        Err(VerifierError::InconsistentOodConstraintEvaluations),
+        crate::check_ood_consistency::<SumAir>(
+            proof.clone(),
+            wrong_inputs,
+            &AcceptableOptions::any()
+        )
+    );
+
+    // proof options are checked as well
+    let acceptable_options = AcceptableOptions::any().with_min_num_queries(64);
+    assert_eq!(
+        Err(VerifierError::UnacceptableProofOptions),
+        crate::check_ood_consistency::<SumAir>(
+            proof.clone(),
+            sum.pub_inputs(),
+            &acceptable_options
+        )
+    );
+
+    // the check does not cover queries; thus, a proof with invalid queries passes the check
+    // but is rejected by the verifier
+    let mut tampered_proof = proof;
+    tampered_proof.pow_nonce += 1;
+    assert_eq!(
+        Ok(()),
+        crate::check_ood_consistency::<SumAir>(
+            tampered_proof.clone(),
+            sum.pub_inputs(),
+            &AcceptableOptions::any()
+        )
+    );
+    assert!(sum.verify(tampered_proof).is_err());
+}
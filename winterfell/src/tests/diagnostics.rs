@@ -0,0 +1,74 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{build_sum_options, SumAir, SumInstance};
+use crate::{
+    math::{fields::f128::BaseElement, FieldElement},
+    AcceptableOptions, FailedCheck, VerificationStage, VerifierError,
+};
+
+// VERIFICATION DIAGNOSTICS TESTS
+// ================================================================================================
+
+#[test]
+fn verify_with_diagnostics() {
+    let sum = SumInstance::new(63, build_sum_options(false).with_trace_segment_width(1));
+    let proof = sum.prove();
+    assert_eq!(
+        Ok(()),
+        crate::verify_with_diagnostics::<SumAir>(
+            proof.clone(),
+            sum.pub_inputs(),
+            &AcceptableOptions::any()
+        )
+    );
+
+    // a wrong result should be caught by the out-of-domain consistency check
+    let mut wrong_inputs = sum.pub_inputs();
+    wrong_inputs.result += BaseElement::ONE;
+    let diagnostics = crate::verify_with_diagnostics::<SumAir>(
+        proof.clone(),
+        wrong_inputs,
+        &AcceptableOptions::any(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        VerifierError::InconsistentOodConstraintEvaluations,
+        diagnostics.error
+    );
+    assert_eq!(VerificationStage::OodConsistency, diagnostics.stage);
+    match diagnostics.failed_check {
+        Some(FailedCheck::OodConstraintEvaluations {
+            constraint_evaluation,
+            composition_evaluation,
+        }) => assert_ne!(constraint_evaluation, composition_evaluation),
+        check => panic!("unexpected failed check: {:?}", check),
+    }
+
+    // queries against one segment should be reported as invalid at every queried position
+    let witness =
+        crate::export_witness::<SumAir>(proof.clone(), sum.pub_inputs(), &AcceptableOptions::any())
+            .unwrap();
+    let mut swapped_proof = proof;
+    swapped_proof.trace_queries.swap(0, 1);
+    let diagnostics = crate::verify_with_diagnostics::<SumAir>(
+        swapped_proof,
+        sum.pub_inputs(),
+        &AcceptableOptions::any(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        VerifierError::TraceQueryDoesNotMatchCommitment,
+        diagnostics.error
+    );
+    assert_eq!(VerificationStage::Queries, diagnostics.stage);
+    assert_eq!(
+        Some(FailedCheck::TraceQueries {
+            segment: 0,
+            positions: witness.query_positions,
+        }),
+        diagnostics.failed_check
+    );
+}
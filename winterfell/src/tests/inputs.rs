@@ -0,0 +1,139 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{build_sum_column, build_sum_options, tweak_values, SumAir, SumInputs, SumInstance};
+use crate::{
+    math::{fields::f128::BaseElement, FieldElement},
+    AcceptableOptions, Air, AirContext, Assertion, ByteWriter, CommittedColumn, CommittedInputsAir,
+    EvaluationFrame, ProofOptions, Serializable, TraceInfo,
+};
+
+// INPUT COMMITMENT TESTS
+// ================================================================================================
+
+#[test]
+fn verify_with_input_commitment() {
+    let sum = SumInstance::new(63, build_sum_options(false));
+    let calldata = CalldataInputs {
+        values: sum.values.clone(),
+        result: sum.result,
+    };
+    let proof = crate::prove_with_input_commitment::<CalldataSumAir>(
+        sum.build_trace(),
+        calldata,
+        sum.options.clone(),
+    )
+    .unwrap();
+    let acceptable_options = AcceptableOptions::any();
+
+    // the proof should be verified against the commitment without the summed values
+    assert_eq!(
+        Ok(()),
+        crate::verify_with_input_commitment::<CalldataSumAir>(
+            proof.clone(),
+            sum.pub_inputs(),
+            &acceptable_options
+        )
+    );
+
+    // commitments to a different result or to different values should be rejected
+    let mut wrong_result = sum.pub_inputs();
+    wrong_result.result += BaseElement::ONE;
+    let wrong_column = SumInputs {
+        commitment: CommittedColumn::build_commitment(
+            &build_sum_column(&tweak_values(&sum.values)),
+            &sum.options,
+        ),
+        result: sum.result,
+    };
+    for commitment in [wrong_result, wrong_column] {
+        assert!(crate::verify_with_input_commitment::<CalldataSumAir>(
+            proof.clone(),
+            commitment,
+            &acceptable_options
+        )
+        .is_err());
+    }
+
+    // the proof is bound to the commitment, and thus, cannot be verified against the full inputs
+    let calldata = CalldataInputs {
+        values: sum.values.clone(),
+        result: sum.result,
+    };
+    assert!(crate::verify::<CalldataSumAir>(proof, calldata, &acceptable_options).is_err());
+}
+
+// CALLDATA SUM AIR
+// ================================================================================================
+
+/// Public inputs of the sum computation which include all of the summed values.
+struct CalldataInputs {
+    values: Vec<BaseElement>,
+    result: BaseElement,
+}
+
+impl Serializable for CalldataInputs {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write(&self.values[..]);
+        target.write(self.result);
+    }
+}
+
+/// Sum AIR which takes the summed values as public inputs, and can be instantiated from a
+/// commitment to the values as well.
+struct CalldataSumAir(SumAir);
+
+impl Air for CalldataSumAir {
+    type BaseElement = BaseElement;
+    type PublicInputs = CalldataInputs;
+
+    fn new(trace_info: TraceInfo, pub_inputs: CalldataInputs, options: ProofOptions) -> Self {
+        let commitment = Self::commit_to_inputs(&pub_inputs, &options);
+        Self::from_input_commitment(trace_info, commitment, options)
+    }
+
+    fn context(&self) -> &AirContext<BaseElement> {
+        self.0.context()
+    }
+
+    fn evaluate_transition<E: FieldElement<BaseField = BaseElement>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        self.0.evaluate_transition(frame, periodic_values, result)
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<BaseElement>> {
+        self.0.get_assertions()
+    }
+
+    fn get_committed_columns(&self) -> Vec<CommittedColumn> {
+        self.0.get_committed_columns()
+    }
+}
+
+impl CommittedInputsAir for CalldataSumAir {
+    type InputCommitment = SumInputs;
+
+    fn commit_to_inputs(pub_inputs: &CalldataInputs, options: &ProofOptions) -> SumInputs {
+        SumInputs {
+            commitment: CommittedColumn::build_commitment(
+                &build_sum_column(&pub_inputs.values),
+                options,
+            ),
+            result: pub_inputs.result,
+        }
+    }
+
+    fn from_input_commitment(
+        trace_info: TraceInfo,
+        commitment: SumInputs,
+        options: ProofOptions,
+    ) -> Self {
+        CalldataSumAir(SumAir::new(trace_info, commitment, options))
+    }
+}
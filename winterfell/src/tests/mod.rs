@@ -0,0 +1,185 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{
+    math::{fields::f128::BaseElement, FieldElement},
+    AcceptableOptions, Air, AirContext, Assertion, ByteWriter, CommittedColumn, EvaluationFrame,
+    ExecutionTrace, FieldExtension, HashFunction, ProofOptions, Serializable, StarkProof,
+    TraceInfo, TransitionConstraintDegree, VerifierError,
+};
+
+mod assertions;
+mod batch;
+mod channel;
+mod columns;
+mod consistency;
+mod diagnostics;
+mod inputs;
+mod modes;
+mod options;
+mod output;
+mod segments;
+mod streaming;
+mod trace;
+mod transcript;
+mod witness;
+
+// SUM AIR
+// ================================================================================================
+
+/// Public inputs of [SumAir]: a commitment to the summed values and the sum of the values.
+pub struct SumInputs {
+    pub commitment: Vec<u8>,
+    pub result: BaseElement,
+}
+
+impl Serializable for SumInputs {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8_slice(&self.commitment);
+        target.write(self.result);
+    }
+}
+
+/// AIR for summing up values of a committed column; the first register contains the values,
+/// and the second register accumulates them.
+pub struct SumAir {
+    context: AirContext<BaseElement>,
+    commitment: Vec<u8>,
+    result: BaseElement,
+}
+
+impl Air for SumAir {
+    type BaseElement = BaseElement;
+    type PublicInputs = SumInputs;
+
+    fn new(trace_info: TraceInfo, pub_inputs: SumInputs, options: ProofOptions) -> Self {
+        let degrees = vec![TransitionConstraintDegree::new(1)];
+        SumAir {
+            context: AirContext::new(trace_info, degrees, options),
+            commitment: pub_inputs.commitment,
+            result: pub_inputs.result,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseElement> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseElement>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        result[0] = next[1] - (current[1] + current[0]);
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseElement>> {
+        let last_step = self.last_step();
+        vec![
+            Assertion::single(1, 0, BaseElement::ZERO),
+            Assertion::single(1, last_step, self.result),
+        ]
+    }
+
+    fn get_committed_columns(&self) -> Vec<CommittedColumn> {
+        vec![CommittedColumn::new(0, self.commitment.clone())]
+    }
+}
+
+/// An instance of the sum computation defined by [SumAir].
+pub struct SumInstance {
+    pub options: ProofOptions,
+    pub values: Vec<BaseElement>,
+    pub commitment: Vec<u8>,
+    pub result: BaseElement,
+}
+
+impl SumInstance {
+    /// Returns an instance which sums up `num_values` values; `num_values + 1` must be a power
+    /// of two.
+    pub fn new(num_values: usize, options: ProofOptions) -> Self {
+        let values = (0..num_values as u128)
+            .map(|i| BaseElement::new(i * i + 1))
+            .collect();
+        Self::with_values(values, options)
+    }
+
+    /// Returns an instance which sums up the specified values.
+    pub fn with_values(values: Vec<BaseElement>, options: ProofOptions) -> Self {
+        let commitment = CommittedColumn::build_commitment(&build_sum_column(&values), &options);
+        let result = values.iter().fold(BaseElement::ZERO, |acc, &v| acc + v);
+        SumInstance {
+            options,
+            values,
+            commitment,
+            result,
+        }
+    }
+
+    pub fn pub_inputs(&self) -> SumInputs {
+        SumInputs {
+            commitment: self.commitment.clone(),
+            result: self.result,
+        }
+    }
+
+    pub fn build_trace(&self) -> ExecutionTrace<BaseElement> {
+        build_sum_trace(&self.values)
+    }
+
+    pub fn prove(&self) -> StarkProof {
+        crate::prove::<SumAir>(self.build_trace(), self.pub_inputs(), self.options.clone()).unwrap()
+    }
+
+    pub fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        crate::verify::<SumAir>(proof, self.pub_inputs(), &AcceptableOptions::any())
+    }
+}
+
+/// Builds an execution trace for summing up the specified values; the values are padded with a
+/// single zero so that the number of steps is a power of two.
+pub fn build_sum_trace(values: &[BaseElement]) -> ExecutionTrace<BaseElement> {
+    let column = build_sum_column(values);
+    let mut trace = ExecutionTrace::new(2, column.len());
+    trace.fill(
+        |state| {
+            state[0] = column[0];
+            state[1] = BaseElement::ZERO;
+        },
+        |step, state| {
+            state[1] += state[0];
+            state[0] = column[step + 1];
+        },
+    );
+    trace
+}
+
+/// Returns values of the committed column for the specified values.
+pub fn build_sum_column(values: &[BaseElement]) -> Vec<BaseElement> {
+    let mut column = values.to_vec();
+    column.push(BaseElement::ZERO);
+    column
+}
+
+/// Returns a copy of the specified values, with two of the values changed such that the sum of
+/// the values remains the same.
+pub fn tweak_values(values: &[BaseElement]) -> Vec<BaseElement> {
+    let mut values = values.to_vec();
+    values[0] += BaseElement::ONE;
+    values[1] -= BaseElement::ONE;
+    values
+}
+
+pub fn build_sum_options(use_extension_field: bool) -> ProofOptions {
+    let extension = if use_extension_field {
+        FieldExtension::Quadratic
+    } else {
+        FieldExtension::None
+    };
+    ProofOptions::new(28, 8, 0, HashFunction::Blake3_256, extension, 4, 256)
+}
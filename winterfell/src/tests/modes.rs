@@ -0,0 +1,108 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{build_sum_options, SumAir, SumInstance};
+use crate::ProvingMode;
+
+// PROVING MODE TESTS
+// ================================================================================================
+
+#[test]
+fn prove_chunked_mode() {
+    let sum = SumInstance::new(63, build_sum_options(true));
+    let proof = sum.prove();
+
+    // a proof generated in chunked mode should be identical to a proof generated in memory
+    let chunked_proof = crate::prove_with_mode::<SumAir>(
+        sum.build_trace(),
+        sum.pub_inputs(),
+        sum.options.clone(),
+        ProvingMode::Chunked,
+    )
+    .unwrap();
+    assert_eq!(proof.to_bytes(), chunked_proof.to_bytes());
+    assert!(sum.verify(chunked_proof).is_ok());
+}
+
+#[test]
+fn prove_row_major_mode() {
+    let sum = SumInstance::new(63, build_sum_options(false));
+    let proof = sum.prove();
+
+    // a proof generated with a row-major trace layout should be identical to a proof generated
+    // with a column-major layout
+    let row_major_proof = crate::prove_with_mode::<SumAir>(
+        sum.build_trace(),
+        sum.pub_inputs(),
+        sum.options.clone(),
+        ProvingMode::RowMajor,
+    )
+    .unwrap();
+    assert_eq!(proof.to_bytes(), row_major_proof.to_bytes());
+}
+
+#[test]
+fn prove_transient_mode() {
+    let sum = SumInstance::new(63, build_sum_options(false));
+    let proof = sum.prove();
+
+    // a proof generated with the extended trace discarded after constraint evaluation should be
+    // identical to a proof generated with the extended trace kept in memory
+    let transient_proof = crate::prove_with_mode::<SumAir>(
+        sum.build_trace(),
+        sum.pub_inputs(),
+        sum.options.clone(),
+        ProvingMode::Transient,
+    )
+    .unwrap();
+    assert_eq!(proof.to_bytes(), transient_proof.to_bytes());
+    assert!(sum.verify(transient_proof).is_ok());
+}
+
+#[test]
+fn prove_from_polys() {
+    let sum = SumInstance::new(63, build_sum_options(false));
+    let proof = sum.prove();
+
+    // proofs generated from trace polynomials should be the same as proofs generated from the
+    // execution trace for all proving modes
+    let trace = sum.build_trace();
+    let trace_info = trace.get_info();
+    let trace_polys = crate::build_trace_polys(trace);
+    for mode in [
+        ProvingMode::InMemory,
+        ProvingMode::Chunked,
+        ProvingMode::Transient,
+    ] {
+        let polys_proof = crate::prove_from_polys::<SumAir>(
+            trace_info.clone(),
+            trace_polys.clone(),
+            sum.pub_inputs(),
+            sum.options.clone(),
+            mode,
+        )
+        .unwrap();
+        assert_eq!(proof.to_bytes(), polys_proof.to_bytes());
+    }
+    assert!(sum.verify(proof).is_ok());
+}
+
+#[test]
+#[cfg(feature = "mmap")]
+fn prove_memory_mapped_mode() {
+    let sum = SumInstance::new(63, build_sum_options(false));
+    let proof = sum.prove();
+
+    // a proof generated from memory-mapped trace should be identical to a proof generated in
+    // memory
+    let mapped_proof = crate::prove_with_mode::<SumAir>(
+        sum.build_trace(),
+        sum.pub_inputs(),
+        sum.options.clone(),
+        ProvingMode::MemoryMapped,
+    )
+    .unwrap();
+    assert_eq!(proof.to_bytes(), mapped_proof.to_bytes());
+}
@@ -0,0 +1,262 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{build_sum_options, SumAir, SumInstance};
+use crate::{
+    crypto::hashers::Blake3_256,
+    fri::RemainderFormat,
+    math::{fields::f128::BaseElement, FieldElement},
+    proof::Context,
+    AcceptableOptions, Deserializable, FieldExtension, HashFunction, ProofOptions, QuerySampling,
+    Serializable, SliceReader, StarkProof, VerificationStage, VerifierError,
+};
+
+// PROOF OPTIONS TESTS
+// ================================================================================================
+
+#[test]
+fn prove_with_query_sampling() {
+    // with 100 queries into an LDE domain of 512 elements, uniform sampling is virtually
+    // guaranteed to draw some positions more than once
+    let options = ProofOptions::new(
+        100,
+        8,
+        0,
+        HashFunction::Blake3_256,
+        FieldExtension::None,
+        4,
+        256,
+    );
+    let distinct_proof = SumInstance::new(63, options.clone()).prove();
+    assert_eq!(100, count_trace_queries(&distinct_proof));
+
+    for query_sampling in [QuerySampling::Uniform, QuerySampling::StratifiedByCoset] {
+        let sum = SumInstance::new(63, options.clone().with_query_sampling(query_sampling));
+        let proof = sum.prove();
+        assert_eq!(query_sampling, proof.options().query_sampling());
+        if query_sampling == QuerySampling::Uniform {
+            assert!(count_trace_queries(&proof) < 100);
+        } else {
+            assert_eq!(100, count_trace_queries(&proof));
+        }
+        assert!(sum.verify(proof).is_ok());
+    }
+}
+
+#[test]
+fn prove_with_fri_folding_factors() {
+    // with an LDE domain of 512 elements and max remainder size of 64, proofs contain between
+    // one and three FRI layers depending on the folding factor
+    for folding_factor in [2, 4, 8, 16] {
+        let options = ProofOptions::new(
+            28,
+            8,
+            0,
+            HashFunction::Blake3_256,
+            FieldExtension::None,
+            folding_factor,
+            64,
+        );
+        let sum = SumInstance::new(63, options);
+        let proof = sum.prove();
+        assert_eq!(
+            folding_factor,
+            proof.options().to_fri_options().folding_factor()
+        );
+        assert!(sum.verify(proof).is_ok());
+    }
+}
+
+#[test]
+fn prove_with_fri_folding_schedule() {
+    // the LDE domain of 512 elements is folded into 256 and 32 elements; the remainder layer is
+    // committed to using the default folding factor
+    let options = ProofOptions::new(
+        28,
+        8,
+        0,
+        HashFunction::Blake3_256,
+        FieldExtension::None,
+        4,
+        32,
+    )
+    .with_fri_folding_schedule(&[2, 8]);
+    let sum = SumInstance::new(63, options);
+    let proof = sum.prove();
+    assert_eq!(2, proof.fri_proof.num_layers());
+
+    // the schedule should be a part of the serialized proof
+    let proof = StarkProof::from_bytes(&proof.to_bytes()).unwrap();
+    assert_eq!(&[2, 8], proof.options().to_fri_options().folding_schedule());
+
+    let output =
+        crate::verify_with_output::<SumAir>(proof, sum.pub_inputs(), &AcceptableOptions::any())
+            .unwrap();
+    assert_eq!(3, output.fri_alphas.len());
+}
+
+#[test]
+fn prove_with_fri_remainder_coefficients() {
+    let options = ProofOptions::new(
+        28,
+        8,
+        0,
+        HashFunction::Blake3_256,
+        FieldExtension::None,
+        4,
+        64,
+    );
+    let coefficient_options = options
+        .clone()
+        .with_fri_remainder_format(RemainderFormat::Coefficients);
+
+    // the remainder of 32 evaluations should be sent as 4 coefficients
+    let proof = SumInstance::new(63, options).prove();
+    assert_eq!(32, proof.fri_proof.num_remainder_elements::<BaseElement>());
+    let sum = SumInstance::new(63, coefficient_options);
+    let coefficient_proof = sum.prove();
+    assert_eq!(
+        4,
+        coefficient_proof
+            .fri_proof
+            .num_remainder_elements::<BaseElement>()
+    );
+
+    // the format should be a part of the serialized proof
+    let coefficient_proof = StarkProof::from_bytes(&coefficient_proof.to_bytes()).unwrap();
+    assert_eq!(
+        RemainderFormat::Coefficients,
+        coefficient_proof
+            .options()
+            .to_fri_options()
+            .remainder_format()
+    );
+    assert!(sum.verify(coefficient_proof).is_ok());
+}
+
+// ACCEPTABLE OPTIONS TESTS
+// ================================================================================================
+
+#[test]
+fn verify_with_acceptable_options() {
+    let sum = SumInstance::new(63, build_sum_options(false));
+    let proof = sum.prove();
+    let verify = |acceptable_options: AcceptableOptions| {
+        crate::verify::<SumAir>(proof.clone(), sum.pub_inputs(), &acceptable_options)
+    };
+
+    // proofs should be accepted when all restrictions are satisfied
+    assert_eq!(Ok(()), verify(AcceptableOptions::any()));
+    assert_eq!(
+        Ok(()),
+        verify(AcceptableOptions::from_options(vec![
+            build_sum_options(true),
+            sum.options.clone()
+        ]))
+    );
+    assert_eq!(
+        Ok(()),
+        verify(
+            AcceptableOptions::any()
+                .with_min_num_queries(28)
+                .with_min_blowup_factor(8)
+                .with_hash_fns(vec![HashFunction::Blake3_256])
+                .with_min_security(proof.security_level(true), true)
+                .with_min_security(proof.security_level(false), false)
+                .with_predicate(|options| options.field_extension() == FieldExtension::None)
+        )
+    );
+
+    // and rejected if any of the restrictions is violated
+    let unacceptable = [
+        AcceptableOptions::from_options(vec![build_sum_options(true)]),
+        AcceptableOptions::from_options(vec![sum.options.clone()]).with_min_num_queries(29),
+        AcceptableOptions::any().with_min_blowup_factor(16),
+        AcceptableOptions::any().with_min_grinding_factor(1),
+        AcceptableOptions::any().with_hash_fns(vec![HashFunction::Sha3_256]),
+        AcceptableOptions::any().with_min_security(proof.security_level(true) + 1, true),
+        AcceptableOptions::any().with_min_security(proof.security_level(false) + 1, false),
+        AcceptableOptions::any()
+            .with_predicate(|options| options.field_extension() == FieldExtension::Quadratic),
+    ];
+    for acceptable_options in unacceptable {
+        assert_eq!(
+            Err(VerifierError::UnacceptableProofOptions),
+            verify(acceptable_options)
+        );
+    }
+
+    // options are checked before the proof is verified
+    let mut wrong_inputs = sum.pub_inputs();
+    wrong_inputs.result += BaseElement::ONE;
+    let diagnostics = crate::verify_with_diagnostics::<SumAir>(
+        proof.clone(),
+        wrong_inputs,
+        &AcceptableOptions::any().with_min_grinding_factor(1),
+    )
+    .unwrap_err();
+    assert_eq!(VerifierError::UnacceptableProofOptions, diagnostics.error);
+    assert_eq!(VerificationStage::Setup, diagnostics.stage);
+}
+
+#[test]
+fn verify_with_oversized_lde_domain() {
+    let sum = SumInstance::new(63, build_sum_options(false));
+    let proof = sum.prove();
+    let blowup_factor_log2 = proof.options().blowup_factor().trailing_zeros();
+
+    // the trace length is serialized as log2 of its value right after the trace width; the
+    // header preceding the context consists of the marker, the version, the hash function, and
+    // the length-prefixed modulus
+    let trace_length_pos = 5 + proof.context.field_modulus_bytes().len();
+
+    // acceptable options should be checked against the largest LDE domain which fits into usize
+    // without overflowing
+    let mut context_bytes = proof.context.to_bytes();
+    context_bytes[1] = (usize::BITS - blowup_factor_log2 - 1) as u8;
+    let context = Context::read_from(&mut SliceReader::new(&context_bytes)).unwrap();
+    assert!(AcceptableOptions::any()
+        .with_min_num_queries(28)
+        .accepts(&context));
+    for conjectured in [true, false] {
+        assert!(!AcceptableOptions::any()
+            .with_min_security(128, conjectured)
+            .accepts(&context));
+    }
+
+    // a proof for which the size of the LDE domain overflows should be rejected for all
+    // acceptable options
+    let mut proof_bytes = proof.to_bytes();
+    proof_bytes[trace_length_pos] = (usize::BITS - blowup_factor_log2) as u8;
+    assert!(StarkProof::from_bytes(&proof_bytes).is_err());
+    for acceptable_options in [
+        AcceptableOptions::any(),
+        AcceptableOptions::any().with_min_num_queries(28),
+        AcceptableOptions::any().with_min_security(100, false),
+    ] {
+        assert!(crate::verify_from_reader::<SumAir, _>(
+            &mut SliceReader::new(&proof_bytes),
+            sum.pub_inputs(),
+            &acceptable_options
+        )
+        .is_err());
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the number of distinct positions at which the trace of the specified proof is opened.
+fn count_trace_queries(proof: &StarkProof) -> usize {
+    let lde_domain_size = proof.lde_domain_size();
+    (1..=proof.options().num_queries())
+        .find(|&num_queries| {
+            proof.trace_queries[0]
+                .clone()
+                .parse::<Blake3_256<BaseElement>, BaseElement>(lde_domain_size, num_queries, 2)
+                .is_ok()
+        })
+        .expect("trace queries could not be parsed")
+}
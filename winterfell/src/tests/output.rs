@@ -0,0 +1,136 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{build_sum_options, SumAir, SumInstance};
+use crate::{
+    math::{fields::f128::BaseElement, FieldElement},
+    AcceptableOptions, ProvingMode, VerifierError,
+};
+
+// VERIFICATION OUTPUT TESTS
+// ================================================================================================
+
+#[test]
+fn verify_with_output() {
+    let sum = SumInstance::new(63, build_sum_options(false));
+    let (proof, artifacts) = crate::prove_with_artifacts::<SumAir, BaseElement>(
+        sum.build_trace(),
+        sum.pub_inputs(),
+        sum.options.clone(),
+        ProvingMode::InMemory,
+    )
+    .unwrap();
+    let output = crate::verify_with_output::<SumAir>(
+        proof.clone(),
+        sum.pub_inputs(),
+        &AcceptableOptions::any(),
+    )
+    .unwrap();
+
+    // commitments, OOD frame and challenges should match the ones produced by the prover
+    assert_eq!(1, output.extension_degree);
+    assert_eq!(artifacts.trace_roots, output.trace_roots);
+    assert_eq!(artifacts.constraint_root, output.constraint_root);
+    assert_eq!(artifacts.fri_layer_roots, output.fri_layer_roots);
+    assert_eq!(vec![artifacts.ood_point], output.ood_point);
+    assert_eq!(artifacts.ood_frame.current(), &output.ood_trace_current[..]);
+    assert_eq!(artifacts.ood_frame.next(), &output.ood_trace_next[..]);
+    assert_eq!(artifacts.fri_alphas, output.fri_alphas);
+    assert_eq!(artifacts.query_positions, output.query_positions);
+
+    // the output should match the corresponding part of the exported witness
+    let witness =
+        crate::export_witness::<SumAir>(proof.clone(), sum.pub_inputs(), &AcceptableOptions::any())
+            .unwrap();
+    assert_eq!(
+        witness.constraint_composition_coeffs,
+        output.constraint_composition_coeffs
+    );
+    assert_eq!(
+        witness.ood_constraint_evaluations,
+        output.ood_constraint_evaluations
+    );
+    assert_eq!(
+        witness.deep_composition_coeffs,
+        output.deep_composition_coeffs
+    );
+
+    // no output should be returned for an invalid proof
+    let mut wrong_inputs = sum.pub_inputs();
+    wrong_inputs.result += BaseElement::ONE;
+    assert_eq!(
+        Err(VerifierError::InconsistentOodConstraintEvaluations),
+        crate::verify_with_output::<SumAir>(proof, wrong_inputs, &AcceptableOptions::any())
+    );
+}
+
+#[test]
+fn inspect_proof() {
+    let sum = SumInstance::new(63, build_sum_options(false));
+    let proof = sum.prove();
+
+    // for a valid proof, the output should be the same as for verify_with_output()
+    let (result, output) =
+        crate::inspect_proof::<SumAir>(proof.clone(), sum.pub_inputs(), &AcceptableOptions::any());
+    assert_eq!(Ok(()), result);
+    let expected = crate::verify_with_output::<SumAir>(
+        proof.clone(),
+        sum.pub_inputs(),
+        &AcceptableOptions::any(),
+    )
+    .unwrap();
+    assert_eq!(expected, output);
+
+    // the output should be rendered with labels and commitments as hex strings
+    let rendered = output.to_string();
+    let constraint_root = to_hex(&output.constraint_root);
+    assert!(rendered.contains(&format!("constraint root: {}", constraint_root)));
+    assert!(rendered.contains(&format!("query positions: {:?}", output.query_positions)));
+    assert!(rendered.contains(&format!("OOD point: [{}]", output.ood_point[0])));
+
+    // for an invalid proof, the output should contain values recorded up to the failed check
+    let mut wrong_inputs = sum.pub_inputs();
+    wrong_inputs.result += BaseElement::ONE;
+    let (result, output) =
+        crate::inspect_proof::<SumAir>(proof, wrong_inputs, &AcceptableOptions::any());
+    assert_eq!(
+        Err(VerifierError::InconsistentOodConstraintEvaluations),
+        result
+    );
+    assert_eq!(expected.trace_roots, output.trace_roots);
+    assert_eq!(expected.ood_trace_current, output.ood_trace_current);
+    assert!(output.fri_layer_roots.is_empty());
+    assert!(output.query_positions.is_empty());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serialize_verification_output() {
+    let sum = SumInstance::new(63, build_sum_options(false));
+    let output = crate::verify_with_output::<SumAir>(
+        sum.prove(),
+        sum.pub_inputs(),
+        &AcceptableOptions::any(),
+    )
+    .unwrap();
+
+    // commitments should be serialized into human-readable formats as hex strings, and field
+    // elements as decimal strings
+    let json: serde_json::Value = serde_json::to_value(&output).unwrap();
+    assert_eq!(to_hex(&output.constraint_root), json["constraint_root"]);
+    assert_eq!(output.ood_point[0].to_string(), json["ood_point"][0]);
+    assert_eq!(
+        output.query_positions.len(),
+        json["query_positions"].as_array().unwrap().len()
+    );
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns a lowercase hex encoding of the specified bytes.
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
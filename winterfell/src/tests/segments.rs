@@ -0,0 +1,112 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{build_sum_options, SumAir, SumInstance};
+use crate::{
+    crypto::hashers::Blake3_256, math::fields::f128::BaseElement, AcceptableOptions, Air,
+    ProvingMode, Serializable, TraceCommitment, TraceCommitmentJob, VerifierError,
+};
+
+// TRACE SEGMENT TESTS
+// ================================================================================================
+
+#[test]
+fn prove_with_trace_segments() {
+    let sum = SumInstance::new(63, build_sum_options(true).with_trace_segment_width(1));
+    let proof = sum.prove();
+
+    // each of the 2 registers should be committed to and queried separately
+    assert_eq!(2, proof.trace_queries.len());
+    let (trace_roots, _, _) = proof
+        .commitments
+        .clone()
+        .parse::<Blake3_256<BaseElement>>(2, proof.fri_proof.num_layers())
+        .unwrap();
+    assert!(sum.verify(proof.clone()).is_ok());
+
+    // segments should be committed to the same way in all proving modes
+    for mode in [
+        ProvingMode::Chunked,
+        ProvingMode::RowMajor,
+        ProvingMode::Transient,
+    ] {
+        let mode_proof = crate::prove_with_mode::<SumAir>(
+            sum.build_trace(),
+            sum.pub_inputs(),
+            sum.options.clone(),
+            mode,
+        )
+        .unwrap();
+        assert_eq!(proof.to_bytes(), mode_proof.to_bytes());
+    }
+
+    // and when the trace is committed to ahead of time
+    let commitment = TraceCommitment::new(sum.build_trace(), sum.options.clone());
+    assert_eq!(
+        trace_roots.iter().map(|r| r.to_bytes()).collect::<Vec<_>>(),
+        commitment.roots()
+    );
+    let committed_proof = crate::prove_with_commitment::<SumAir>(
+        &commitment,
+        sum.pub_inputs(),
+        ProvingMode::InMemory,
+    )
+    .unwrap();
+    assert_eq!(proof.to_bytes(), committed_proof.to_bytes());
+
+    // or by remote workers
+    let trace = sum.build_trace();
+    let air = SumAir::new(trace.get_info(), sum.pub_inputs(), sum.options.clone());
+    let trace_polys = crate::build_trace_polys(trace);
+    let results = TraceCommitmentJob::build_all(&air)
+        .iter()
+        .map(|job| job.execute(&trace_polys))
+        .collect();
+    let checkpoint = crate::merge_trace_commitments(&air, &sum.pub_inputs(), &trace_polys, results);
+    let distributed_proof = crate::prove_with_checkpoints::<SumAir>(
+        sum.build_trace(),
+        sum.pub_inputs(),
+        sum.options.clone(),
+        ProvingMode::InMemory,
+        Some(checkpoint),
+        |_| {},
+    )
+    .unwrap();
+    assert_eq!(proof.to_bytes(), distributed_proof.to_bytes());
+}
+
+// TRACE SEGMENT TESTS
+// ================================================================================================
+
+#[test]
+fn verify_trace_segments() {
+    let sum = SumInstance::new(63, build_sum_options(true).with_trace_segment_width(1));
+    let proof = sum.prove();
+    assert_eq!(Ok(()), sum.verify(proof.clone()));
+
+    // a witness should contain queries against each segment
+    let witness =
+        crate::export_witness::<SumAir>(proof.clone(), sum.pub_inputs(), &AcceptableOptions::any())
+            .unwrap();
+    assert_eq!(2, witness.trace_roots.len());
+    assert_eq!(2, witness.trace_queries.len());
+    assert!(witness.trace_queries[1].iter().all(|q| q.values.len() == 1));
+
+    // queries against one segment should not be accepted for another segment
+    let mut swapped_proof = proof.clone();
+    swapped_proof.trace_queries.swap(0, 1);
+    assert_eq!(
+        Err(VerifierError::TraceQueryDoesNotMatchCommitment),
+        sum.verify(swapped_proof)
+    );
+
+    // and a proof must contain queries for every segment
+    let mut truncated_proof = proof;
+    truncated_proof.trace_queries.pop();
+    assert!(matches!(
+        sum.verify(truncated_proof),
+        Err(VerifierError::ProofDeserializationError(_))
+    ));
+}
@@ -0,0 +1,142 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{build_sum_options, SumAir, SumInstance};
+use crate::{
+    math::{fields::f128::BaseElement, FieldElement},
+    proof::{ProofLimits, StarkProofRef},
+    AcceptableOptions, BufReadAdapter, ByteReader, ReadAdapter, SliceReader, StarkProof,
+    VerifierError,
+};
+
+// STREAMING VERIFICATION TESTS
+// ================================================================================================
+
+#[test]
+fn verify_from_reader() {
+    let sum = SumInstance::new(63, build_sum_options(true).with_trace_segment_width(1));
+    let proof = sum.prove();
+    let acceptable_options = AcceptableOptions::any();
+
+    // the streaming layout contains the same values as the regular one
+    let proof_bytes = proof.to_streaming_bytes();
+    assert_eq!(proof.to_bytes().len(), proof_bytes.len());
+
+    // proofs should be verified from any reader; consecutive proofs can be read from the same
+    // reader since a verified proof is read in full
+    let mut two_proofs = proof_bytes.clone();
+    two_proofs.extend_from_slice(&proof_bytes);
+    let mut reader = ReadAdapter::new(&two_proofs[..]);
+    for _ in 0..2 {
+        assert_eq!(
+            Ok(()),
+            crate::verify_from_reader::<SumAir, _>(
+                &mut reader,
+                sum.pub_inputs(),
+                &acceptable_options
+            )
+        );
+    }
+    assert!(!reader.has_more_bytes());
+
+    // verification should stop before the queries are read if the proof is inconsistent with
+    // the public inputs
+    let mut wrong_inputs = sum.pub_inputs();
+    wrong_inputs.result += BaseElement::ONE;
+    let mut reader = SliceReader::new(&proof_bytes);
+    assert_eq!(
+        Err(VerifierError::InconsistentOodConstraintEvaluations),
+        crate::verify_from_reader::<SumAir, _>(&mut reader, wrong_inputs, &acceptable_options)
+    );
+    assert!(reader.has_more_bytes());
+
+    // truncated proofs and proofs serialized using the regular layout should be rejected
+    for bytes in [&proof_bytes[..proof_bytes.len() - 1], &proof.to_bytes()] {
+        assert!(crate::verify_from_reader::<SumAir, _>(
+            &mut SliceReader::new(bytes),
+            sum.pub_inputs(),
+            &acceptable_options
+        )
+        .is_err());
+    }
+}
+
+#[test]
+fn verify_from_buf_reader() {
+    let sum = SumInstance::new(63, build_sum_options(false));
+    let proof = sum.prove();
+
+    // proofs should be verified from a buffered reader; whatever follows a verified proof
+    // should remain in the reader
+    let mut source = proof.to_streaming_bytes();
+    source.extend_from_slice(&[1, 2, 3]);
+    let mut reader = BufReadAdapter::new(std::io::BufReader::with_capacity(100, &source[..]));
+    assert_eq!(
+        Ok(()),
+        crate::verify_from_reader::<SumAir, _>(
+            &mut reader,
+            sum.pub_inputs(),
+            &AcceptableOptions::any()
+        )
+    );
+    assert!(reader.has_more_bytes());
+    let mut rest = Vec::new();
+    std::io::Read::read_to_end(&mut reader.into_inner(), &mut rest).unwrap();
+    assert_eq!(vec![1, 2, 3], rest);
+}
+
+#[test]
+fn verify_decoded_proof_ref() {
+    let sum = SumInstance::new(63, build_sum_options(false));
+    let proof_bytes = sum.prove().to_bytes();
+
+    // a proof decoded from a proof view should verify
+    let proof_ref = StarkProofRef::new(&proof_bytes, &ProofLimits::new()).unwrap();
+    assert_eq!(Ok(()), sum.verify(proof_ref.to_proof().unwrap()));
+}
+
+#[test]
+fn verify_from_reader_with_future_version() {
+    let sum = SumInstance::new(63, build_sum_options(false));
+    let proof = sum.prove();
+
+    // proofs of a newer version should be rejected before anything else is read
+    let future_version = StarkProof::VERSION + 1;
+    let mut proof_bytes = proof.to_streaming_bytes();
+    proof_bytes[1] = future_version;
+    assert_eq!(
+        Err(VerifierError::UnsupportedProofVersion(
+            future_version,
+            StarkProof::VERSION
+        )),
+        crate::verify_from_reader::<SumAir, _>(
+            &mut SliceReader::new(&proof_bytes),
+            sum.pub_inputs(),
+            &AcceptableOptions::any()
+        )
+    );
+}
+
+#[test]
+#[cfg(feature = "legacy-proofs")]
+fn verify_legacy_proofs() {
+    let sum = SumInstance::new(63, build_sum_options(false));
+    let proof = sum.prove();
+
+    // proofs without the version header should be accepted by the verifier in both layouts;
+    // the header consists of the marker, the version, the hash function, and the
+    // length-prefixed modulus
+    let header_len = 4 + proof.context.field_modulus_bytes().len();
+    let legacy_proof = StarkProof::from_bytes(&proof.to_bytes()[header_len..]).unwrap();
+    assert_eq!(Ok(()), sum.verify(legacy_proof));
+    assert_eq!(
+        Ok(()),
+        crate::verify_from_reader::<SumAir, _>(
+            &mut SliceReader::new(&proof.to_streaming_bytes()[header_len..]),
+            sum.pub_inputs(),
+            &AcceptableOptions::any()
+        )
+    );
+}
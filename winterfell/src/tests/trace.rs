@@ -0,0 +1,63 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{build_sum_options, SumAir, SumInputs};
+use crate::{
+    math::{fields::f128::BaseElement, FieldElement},
+    AcceptableOptions, CommittedColumn, ExecutionTrace,
+};
+
+// TRACE PADDING TESTS
+// ================================================================================================
+
+#[test]
+fn prove_fixed_length_trace() {
+    // statements of different sizes are proven using traces of the same length; the effective
+    // length is a part of the proof context
+    let options = build_sum_options(false);
+    for num_values in [5, 20, 50] {
+        let values = (0..num_values as u128)
+            .map(|i| BaseElement::new(i * 3 + 1))
+            .collect::<Vec<_>>();
+
+        // the accumulator register is built for the values only; the rest of the trace is filled
+        // by padding rows which keep the accumulator unchanged
+        let mut column = values.clone();
+        column.push(BaseElement::ZERO);
+        let mut accumulator = vec![BaseElement::ZERO; column.len()];
+        for i in 1..column.len() {
+            accumulator[i] = accumulator[i - 1] + column[i - 1];
+        }
+        let trace =
+            ExecutionTrace::init_padded_to_with(vec![column, accumulator], 64, |_, state| {
+                state[1] += state[0];
+                state[0] = BaseElement::ZERO;
+            });
+
+        let mut padded_column = values.clone();
+        padded_column.resize(64, BaseElement::ZERO);
+        let commitment = CommittedColumn::build_commitment(&padded_column, &options);
+        let result = values.iter().fold(BaseElement::ZERO, |acc, &v| acc + v);
+        let build_pub_inputs = |result| SumInputs {
+            commitment: commitment.clone(),
+            result,
+        };
+
+        let proof =
+            crate::prove::<SumAir>(trace, build_pub_inputs(result), options.clone()).unwrap();
+        assert_eq!(64, proof.context.trace_length());
+        assert_eq!(num_values + 1, proof.context.unpadded_trace_length());
+        crate::verify::<SumAir>(
+            proof.clone(),
+            build_pub_inputs(result),
+            &AcceptableOptions::any(),
+        )
+        .unwrap();
+
+        // the result is checked at the last step before the padding rows
+        let wrong_inputs = build_pub_inputs(result + BaseElement::ONE);
+        assert!(crate::verify::<SumAir>(proof, wrong_inputs, &AcceptableOptions::any()).is_err());
+    }
+}
@@ -0,0 +1,82 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{build_sum_options, SumAir, SumInstance};
+use crate::{
+    crypto::{hashers::Blake3_256, RandomCoinError, TranscriptEntry},
+    math::{fields::f128::BaseElement, FieldElement, StarkField},
+    AcceptableOptions, Deserializable, ProvingMode, Serializable, SliceReader, Transcript,
+};
+
+type QuadElement = <BaseElement as StarkField>::QuadExtension;
+
+// TRANSCRIPT TESTS
+// ================================================================================================
+
+#[test]
+fn verify_with_transcript() {
+    let sum = SumInstance::new(63, build_sum_options(true));
+    let (proof, artifacts) = crate::prove_with_artifacts::<SumAir, QuadElement>(
+        sum.build_trace(),
+        sum.pub_inputs(),
+        sum.options.clone(),
+        ProvingMode::InMemory,
+    )
+    .unwrap();
+    let acceptable_options = AcceptableOptions::any();
+
+    // for a valid proof, transcripts of the prover and the verifier should be identical
+    let (result, transcript) = crate::verify_with_transcript::<SumAir>(
+        proof.clone(),
+        sum.pub_inputs(),
+        &acceptable_options,
+    );
+    assert_eq!(Ok(()), result);
+    assert_eq!(None, artifacts.transcript.first_divergence(&transcript));
+    assert_eq!(artifacts.transcript, transcript);
+    assert!(matches!(transcript.entries()[0], TranscriptEntry::Seed(_)));
+    assert_eq!(
+        transcript.entries().len(),
+        transcript.to_string().lines().count()
+    );
+
+    // the transcript should be consistent with a fresh coin, but only for the field and hash
+    // function with which it was recorded
+    assert_eq!(
+        Ok(()),
+        transcript.replay::<BaseElement, QuadElement, Blake3_256<BaseElement>>()
+    );
+    let first_draw = transcript
+        .entries()
+        .iter()
+        .position(|entry| matches!(entry, TranscriptEntry::DrawElement(_)))
+        .unwrap();
+    assert_eq!(
+        Err(RandomCoinError::InconsistentTranscript(first_draw)),
+        transcript.replay::<BaseElement, BaseElement, Blake3_256<BaseElement>>()
+    );
+
+    // transcripts should survive serialization round trip
+    let transcript_bytes = transcript.to_bytes();
+    assert_eq!(
+        transcript,
+        Transcript::read_from(&mut SliceReader::new(&transcript_bytes)).unwrap()
+    );
+
+    // verification against a wrong result should diverge from the prover at the seed
+    let mut wrong_inputs = sum.pub_inputs();
+    wrong_inputs.result += BaseElement::ONE;
+    let (result, wrong_transcript) =
+        crate::verify_with_transcript::<SumAir>(proof, wrong_inputs, &acceptable_options);
+    assert!(result.is_err());
+    assert_eq!(
+        Some(0),
+        artifacts.transcript.first_divergence(&wrong_transcript)
+    );
+    assert_eq!(
+        Ok(()),
+        wrong_transcript.replay::<BaseElement, QuadElement, Blake3_256<BaseElement>>()
+    );
+}
@@ -0,0 +1,96 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{build_sum_options, SumAir, SumInstance};
+use crate::{
+    crypto::{hashers::Blake3_256, ElementHasher, Hasher, MerkleTree},
+    math::{fields::f128::BaseElement, FieldElement, StarkField},
+    AcceptableOptions, Deserializable, ProvingMode, QueryWitness, Serializable, SliceReader,
+};
+
+type QuadElement = <BaseElement as StarkField>::QuadExtension;
+type Digest = <Blake3_256<BaseElement> as Hasher>::Digest;
+
+// PROOF WITNESS TESTS
+// ================================================================================================
+
+#[test]
+fn export_witness() {
+    let sum = SumInstance::new(63, build_sum_options(true));
+    let (proof, artifacts) = crate::prove_with_artifacts::<SumAir, QuadElement>(
+        sum.build_trace(),
+        sum.pub_inputs(),
+        sum.options.clone(),
+        ProvingMode::InMemory,
+    )
+    .unwrap();
+    let witness =
+        crate::export_witness::<SumAir>(proof.clone(), sum.pub_inputs(), &AcceptableOptions::any())
+            .unwrap();
+
+    // commitments and challenges in the witness should match the ones drawn by the prover
+    let flatten = |elements: &[QuadElement]| QuadElement::as_base_elements(elements).to_vec();
+    assert_eq!(2, witness.extension_degree);
+    assert_eq!(artifacts.trace_roots, witness.trace_roots);
+    assert_eq!(artifacts.constraint_root, witness.constraint_root);
+    assert_eq!(artifacts.fri_layer_roots, witness.fri_layer_roots);
+    assert_eq!(flatten(&[artifacts.ood_point]), witness.ood_point);
+    assert_eq!(
+        flatten(artifacts.ood_frame.current()),
+        witness.ood_trace_current
+    );
+    assert_eq!(flatten(&artifacts.fri_alphas), witness.fri_alphas);
+    assert_eq!(artifacts.query_positions, witness.query_positions);
+    assert_eq!(proof.pow_nonce, witness.pow_nonce);
+
+    // every query should open a leaf which authenticates against the committed root
+    assert_eq!(1, witness.trace_queries.len());
+    assert_eq!(
+        witness.query_positions.len(),
+        witness.trace_queries[0].len()
+    );
+    verify_queries(&witness.trace_roots[0], &witness.trace_queries[0]);
+    verify_queries(&witness.constraint_root, &witness.constraint_queries);
+    assert_eq!(1, witness.committed_column_queries.len());
+    verify_queries(&sum.commitment, &witness.committed_column_queries[0]);
+    assert_eq!(
+        proof.fri_proof.num_layers(),
+        witness.fri_layer_queries.len()
+    );
+    for (root, queries) in witness
+        .fri_layer_roots
+        .iter()
+        .zip(witness.fri_layer_queries.iter())
+    {
+        verify_queries(root, queries);
+    }
+    assert!(!witness.fri_remainder.is_empty());
+    assert!(!witness.to_bytes().is_empty());
+
+    // no witness should be exported for an invalid proof
+    let mut pub_inputs = sum.pub_inputs();
+    pub_inputs.result += BaseElement::ONE;
+    assert!(crate::export_witness::<SumAir>(proof, pub_inputs, &AcceptableOptions::any()).is_err());
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Checks that every query opens a leaf which authenticates against the specified root.
+fn verify_queries(root: &[u8], queries: &[QueryWitness<BaseElement>]) {
+    let root = Digest::read_from(&mut SliceReader::new(root)).unwrap();
+    for query in queries {
+        let path = query
+            .path
+            .iter()
+            .map(|node| Digest::read_from(&mut SliceReader::new(node)).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            Blake3_256::<BaseElement>::hash_elements(&query.values),
+            path[0]
+        );
+        MerkleTree::<Blake3_256<BaseElement>>::verify(root, query.position, &path).unwrap();
+    }
+}